@@ -15,6 +15,7 @@ use nym_task::TaskHandle;
 use nym_validator_client::QueryHttpRpcNyxdClient;
 use std::error::Error;
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 pub use nym_sphinx::addressing::clients::Recipient;
 
@@ -29,6 +30,9 @@ pub struct SocketClient {
 
     /// Optional path to a .json file containing standalone network details.
     custom_mixnet: Option<PathBuf>,
+
+    /// Optional passphrase used for decrypting the client keys stored at rest.
+    keys_passphrase: Option<Zeroizing<String>>,
 }
 
 impl SocketClient {
@@ -36,9 +40,15 @@ impl SocketClient {
         SocketClient {
             config,
             custom_mixnet,
+            keys_passphrase: None,
         }
     }
 
+    pub fn with_keys_passphrase(mut self, keys_passphrase: Zeroizing<String>) -> Self {
+        self.keys_passphrase = Some(keys_passphrase);
+        self
+    }
+
     fn start_websocket_listener(
         config: &Config,
         client_input: ClientInput,
@@ -89,9 +99,10 @@ impl SocketClient {
     }
 
     async fn initialise_storage(&self) -> Result<OnDiskPersistent, ClientError> {
-        Ok(OnDiskPersistent::from_paths(
+        Ok(OnDiskPersistent::from_paths_with_keys_passphrase(
             self.config.storage_paths.common_paths.clone(),
             &self.config.base.debug,
+            self.keys_passphrase.as_ref().map(|p| p.as_bytes()),
         )
         .await?)
     }
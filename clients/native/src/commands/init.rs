@@ -12,6 +12,7 @@ use crate::{
 };
 use clap::Args;
 use nym_bin_common::output_format::OutputFormat;
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientKeysPassphraseArgs;
 use nym_client_core::cli_helpers::client_init::{
     initialise_client, CommonClientInitArgs, InitResultsWithConfig, InitialisableClient,
 };
@@ -20,6 +21,7 @@ use std::fmt::Display;
 use std::fs;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 impl InitialisableClient for CliNativeClient {
     type InitArgs = Init;
@@ -40,6 +42,15 @@ impl InitialisableClient for CliNativeClient {
             OverrideConfig::from(init_args.clone()),
         )
     }
+
+    fn keys_passphrase(
+        init_args: &Self::InitArgs,
+    ) -> Result<Option<Zeroizing<String>>, Self::Error> {
+        if !init_args.encrypt_keys {
+            return Ok(None);
+        }
+        Ok(Some(init_args.keys_passphrase.required_passphrase()?))
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -47,6 +58,14 @@ pub(crate) struct Init {
     #[command(flatten)]
     common_args: CommonClientInitArgs,
 
+    /// Encrypt the client keys at rest using the passphrase provided via the environment variable
+    /// specified with `--keys-passphrase-env`
+    #[clap(long)]
+    encrypt_keys: bool,
+
+    #[command(flatten)]
+    keys_passphrase: CommonClientKeysPassphraseArgs,
+
     /// Whether to not start the websocket
     #[clap(long)]
     disable_socket: Option<bool>,
@@ -81,6 +100,7 @@ impl From<Init> for OverrideConfig {
 
             nyxd_urls: init_config.common_args.nyxd_urls,
             enabled_credentials_mode: init_config.common_args.enabled_credentials_mode,
+            metrics_address: None,
         }
     }
 }
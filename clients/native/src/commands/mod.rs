@@ -12,16 +12,18 @@ use clap::{Parser, Subcommand};
 use log::{error, info};
 use nym_bin_common::bin_info;
 use nym_bin_common::completions::{fig_generate, ArgShell};
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientEncryptKeysArgs;
 use nym_client_core::cli_helpers::client_import_credential::CommonClientImportCredentialArgs;
 use nym_client_core::cli_helpers::CliClient;
 use nym_client_core::client::base_client::storage::migration_helpers::v1_1_33;
 use nym_config::OptionalSet;
 use std::error::Error;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::OnceLock;
 
 mod add_gateway;
 pub(crate) mod build_info;
+mod encrypt_keys;
 pub(crate) mod import_credential;
 pub(crate) mod init;
 mod list_gateways;
@@ -84,6 +86,10 @@ pub(crate) enum Commands {
     /// Change the currently active gateway. Note that you must have already registered with the new gateway!
     SwitchGateway(switch_gateway::Args),
 
+    /// Encrypt the existing plaintext keys of this client at rest.
+    /// The passphrase is read from the environment variable specified with `--keys-passphrase-env`
+    EncryptKeys(CommonClientEncryptKeysArgs),
+
     /// Show build information of this binary
     BuildInfo(build_info::BuildInfo),
 
@@ -104,6 +110,7 @@ pub(crate) struct OverrideConfig {
     no_cover: bool,
     nyxd_urls: Option<Vec<url::Url>>,
     enabled_credentials_mode: Option<bool>,
+    metrics_address: Option<SocketAddr>,
 }
 
 pub(crate) async fn execute(args: Cli) -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -116,6 +123,7 @@ pub(crate) async fn execute(args: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Commands::ListGateways(args) => list_gateways::execute(args).await?,
         Commands::AddGateway(args) => add_gateway::execute(args).await?,
         Commands::SwitchGateway(args) => switch_gateway::execute(args).await?,
+        Commands::EncryptKeys(args) => encrypt_keys::execute(args).await?,
         Commands::BuildInfo(m) => build_info::execute(m),
         Commands::Completions(s) => s.generate(&mut Cli::command(), bin_name),
         Commands::GenerateFigSpec => fig_generate(&mut Cli::command(), bin_name),
@@ -149,6 +157,7 @@ pub(crate) fn override_config(config: Config, args: OverrideConfig) -> Config {
             BaseClientConfig::with_disabled_credentials,
             args.enabled_credentials_mode.map(|b| !b),
         )
+        .with_optional_ext(BaseClientConfig::with_metrics_server, args.metrics_address)
 }
 
 async fn try_upgrade_v1_1_13_config(id: &str) -> Result<bool, ClientError> {
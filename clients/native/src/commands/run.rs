@@ -10,6 +10,7 @@ use crate::{
 use clap::Args;
 use log::*;
 use nym_bin_common::version_checker::is_minor_version_compatible;
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientKeysPassphraseArgs;
 use nym_client_core::cli_helpers::client_run::CommonClientRunArgs;
 use std::error::Error;
 use std::net::IpAddr;
@@ -19,6 +20,9 @@ pub(crate) struct Run {
     #[command(flatten)]
     common_args: CommonClientRunArgs,
 
+    #[command(flatten)]
+    keys_passphrase: CommonClientKeysPassphraseArgs,
+
     /// Whether to not start the websocket
     #[clap(long)]
     disable_socket: Option<bool>,
@@ -43,6 +47,7 @@ impl From<Run> for OverrideConfig {
             no_cover: run_config.common_args.no_cover,
             nyxd_urls: run_config.common_args.nyxd_urls,
             enabled_credentials_mode: run_config.common_args.enabled_credentials_mode,
+            metrics_address: run_config.common_args.metrics_address,
         }
     }
 }
@@ -77,7 +82,9 @@ pub(crate) async fn execute(args: Run) -> Result<(), Box<dyn Error + Send + Sync
         return Err(Box::new(ClientError::FailedLocalVersionCheck));
     }
 
-    SocketClient::new(config, args.common_args.custom_mixnet)
-        .run_socket_forever()
-        .await
+    let mut client = SocketClient::new(config, args.common_args.custom_mixnet);
+    if let Some(passphrase) = args.keys_passphrase.passphrase() {
+        client = client.with_keys_passphrase(passphrase);
+    }
+    client.run_socket_forever().await
 }
@@ -0,0 +1,14 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commands::CliSocks5Client;
+use crate::error::Socks5ClientError;
+use nym_client_core::cli_helpers::client_encrypt_keys::{
+    encrypt_keys, CommonClientEncryptKeysArgs,
+};
+
+pub(crate) async fn execute(args: CommonClientEncryptKeysArgs) -> Result<(), Socks5ClientError> {
+    encrypt_keys::<CliSocks5Client, _>(args).await?;
+    println!("successfully encrypted the client keys!");
+    Ok(())
+}
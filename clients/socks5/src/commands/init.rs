@@ -11,6 +11,7 @@ use crate::{
 };
 use clap::Args;
 use nym_bin_common::output_format::OutputFormat;
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientKeysPassphraseArgs;
 use nym_client_core::cli_helpers::client_init::{
     initialise_client, CommonClientInitArgs, InitResultsWithConfig, InitialisableClient,
 };
@@ -20,6 +21,7 @@ use std::fmt::Display;
 use std::fs;
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 impl InitialisableClient for CliSocks5Client {
     type InitArgs = Init;
@@ -40,6 +42,15 @@ impl InitialisableClient for CliSocks5Client {
             OverrideConfig::from(init_args.clone()),
         )
     }
+
+    fn keys_passphrase(
+        init_args: &Self::InitArgs,
+    ) -> Result<Option<Zeroizing<String>>, Self::Error> {
+        if !init_args.encrypt_keys {
+            return Ok(None);
+        }
+        Ok(Some(init_args.keys_passphrase.required_passphrase()?))
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -47,6 +58,14 @@ pub(crate) struct Init {
     #[command(flatten)]
     common_args: CommonClientInitArgs,
 
+    /// Encrypt the client keys at rest using the passphrase provided via the environment variable
+    /// specified with `--keys-passphrase-env`
+    #[clap(long)]
+    encrypt_keys: bool,
+
+    #[command(flatten)]
+    keys_passphrase: CommonClientKeysPassphraseArgs,
+
     /// Address of the socks5 provider to send messages to.
     #[clap(long)]
     provider: Recipient,
@@ -91,6 +110,7 @@ impl From<Init> for OverrideConfig {
             medium_toggle: false,
             nyxd_urls: init_config.common_args.nyxd_urls,
             enabled_credentials_mode: init_config.common_args.enabled_credentials_mode,
+            metrics_address: None,
             outfox: false,
         }
     }
@@ -13,6 +13,7 @@ use clap::{Parser, Subcommand};
 use log::{error, info};
 use nym_bin_common::bin_info;
 use nym_bin_common::completions::{fig_generate, ArgShell};
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientEncryptKeysArgs;
 use nym_client_core::cli_helpers::client_import_credential::CommonClientImportCredentialArgs;
 use nym_client_core::cli_helpers::CliClient;
 use nym_client_core::client::base_client::storage::migration_helpers::v1_1_33;
@@ -21,11 +22,12 @@ use nym_client_core::config::{GroupBy, TopologyStructure};
 use nym_config::OptionalSet;
 use nym_sphinx::params::{PacketSize, PacketType};
 use std::error::Error;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::OnceLock;
 
 mod add_gateway;
 pub(crate) mod build_info;
+mod encrypt_keys;
 mod import_credential;
 pub mod init;
 mod list_gateways;
@@ -88,6 +90,10 @@ pub(crate) enum Commands {
     /// Change the currently active gateway. Note that you must have already registered with the new gateway!
     SwitchGateway(switch_gateway::Args),
 
+    /// Encrypt the existing plaintext keys of this client at rest.
+    /// The passphrase is read from the environment variable specified with `--keys-passphrase-env`
+    EncryptKeys(CommonClientEncryptKeysArgs),
+
     /// Show build information of this binary
     BuildInfo(build_info::BuildInfo),
 
@@ -110,6 +116,7 @@ pub(crate) struct OverrideConfig {
     medium_toggle: bool,
     nyxd_urls: Option<Vec<url::Url>>,
     enabled_credentials_mode: Option<bool>,
+    metrics_address: Option<SocketAddr>,
     outfox: bool,
 }
 
@@ -123,6 +130,7 @@ pub(crate) async fn execute(args: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Commands::ListGateways(args) => list_gateways::execute(args).await?,
         Commands::AddGateway(args) => add_gateway::execute(args).await?,
         Commands::SwitchGateway(args) => switch_gateway::execute(args).await?,
+        Commands::EncryptKeys(args) => encrypt_keys::execute(args).await?,
         Commands::BuildInfo(m) => build_info::execute(m),
         Commands::Completions(s) => s.generate(&mut Cli::command(), bin_name),
         Commands::GenerateFigSpec => fig_generate(&mut Cli::command(), bin_name),
@@ -196,6 +204,7 @@ pub(crate) fn override_config(config: Config, args: OverrideConfig) -> Config {
             BaseClientConfig::with_disabled_credentials,
             args.enabled_credentials_mode.map(|b| !b),
         )
+        .with_optional_base(BaseClientConfig::with_metrics_server, args.metrics_address)
 }
 
 async fn try_upgrade_v1_1_13_config(id: &str) -> Result<bool, Socks5ClientError> {
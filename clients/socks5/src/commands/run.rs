@@ -10,6 +10,7 @@ use crate::{
 use clap::Args;
 use log::*;
 use nym_bin_common::version_checker::is_minor_version_compatible;
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientKeysPassphraseArgs;
 use nym_client_core::cli_helpers::client_run::CommonClientRunArgs;
 use nym_client_core::client::base_client::storage::OnDiskPersistent;
 use nym_client_core::client::topology_control::geo_aware_provider::CountryGroup;
@@ -22,6 +23,9 @@ pub(crate) struct Run {
     #[command(flatten)]
     common_args: CommonClientRunArgs,
 
+    #[command(flatten)]
+    keys_passphrase: CommonClientKeysPassphraseArgs,
+
     /// Specifies whether this client is going to use an anonymous sender tag for communication with the service provider.
     /// While this is going to hide its actual address information, it will make the actual communication
     /// slower and consume nearly double the bandwidth as it will require sending reply SURBs.
@@ -69,6 +73,7 @@ impl From<Run> for OverrideConfig {
             medium_toggle: run_config.medium_toggle,
             nyxd_urls: run_config.common_args.nyxd_urls,
             enabled_credentials_mode: run_config.common_args.enabled_credentials_mode,
+            metrics_address: run_config.common_args.metrics_address,
             outfox: run_config.outfox,
         }
     }
@@ -113,9 +118,13 @@ pub(crate) async fn execute(args: Run) -> Result<(), Box<dyn std::error::Error +
         return Err(Box::new(Socks5ClientError::FailedLocalVersionCheck));
     }
 
-    let storage =
-        OnDiskPersistent::from_paths(config.storage_paths.common_paths, &config.core.base.debug)
-            .await?;
+    let keys_passphrase = args.keys_passphrase.passphrase();
+    let storage = OnDiskPersistent::from_paths_with_keys_passphrase(
+        config.storage_paths.common_paths,
+        &config.core.base.debug,
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    )
+    .await?;
     NymClient::new(config.core, storage, args.common_args.custom_mixnet)
         .run_forever()
         .await
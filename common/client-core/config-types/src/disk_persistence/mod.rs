@@ -18,6 +18,7 @@ pub const DEFAULT_PUBLIC_IDENTITY_KEY_FILENAME: &str = "public_identity.pem";
 pub const DEFAULT_PRIVATE_ENCRYPTION_KEY_FILENAME: &str = "private_encryption.pem";
 pub const DEFAULT_PUBLIC_ENCRYPTION_KEY_FILENAME: &str = "public_encryption.pem";
 pub const DEFAULT_ACK_KEY_FILENAME: &str = "ack_key.pem";
+pub const DEFAULT_KEYS_CIPHER_FILENAME: &str = "keys_cipher.json";
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -100,6 +101,7 @@ impl ClientKeysPaths {
             || matches!(self.public_encryption_key_file.try_exists(), Ok(true))
             || matches!(self.private_encryption_key_file.try_exists(), Ok(true))
             || matches!(self.ack_key_file.try_exists(), Ok(true))
+            || matches!(self.keys_cipher().try_exists(), Ok(true))
     }
 
     pub fn any_file_exists_and_return(&self) -> Option<PathBuf> {
@@ -108,6 +110,7 @@ impl ClientKeysPaths {
             .or_else(|| file_exists(&self.public_encryption_key_file))
             .or_else(|| file_exists(&self.private_encryption_key_file))
             .or_else(|| file_exists(&self.ack_key_file))
+            .or_else(|| file_exists(&self.keys_cipher()))
     }
 
     pub fn private_identity_key(&self) -> &Path {
@@ -129,6 +132,15 @@ impl ClientKeysPaths {
     pub fn ack_key(&self) -> &Path {
         &self.ack_key_file
     }
+
+    /// Path to the file containing information about the cipher used for encrypting the keys at rest.
+    /// It's always placed alongside the ack key.
+    pub fn keys_cipher(&self) -> PathBuf {
+        match self.ack_key_file.parent() {
+            Some(parent) => parent.join(DEFAULT_KEYS_CIPHER_FILENAME),
+            None => PathBuf::from(DEFAULT_KEYS_CIPHER_FILENAME),
+        }
+    }
 }
 
 fn file_exists(path: &Path) -> Option<PathBuf> {
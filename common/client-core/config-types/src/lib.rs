@@ -3,8 +3,15 @@
 
 use nym_config::defaults::NymNetworkDetails;
 use nym_sphinx_addressing::Recipient;
-use nym_sphinx_params::{PacketSize, PacketType};
+use nym_sphinx_params::message_compression::DEFAULT_MESSAGE_COMPRESSION_THRESHOLD;
+use nym_sphinx_params::reconstruction::{
+    DEFAULT_MAXIMUM_BUFFERED_FRAGMENTS_SIZE, DEFAULT_MAXIMUM_BUFFERED_FRAGMENT_SETS,
+    DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE,
+};
+use nym_sphinx_params::{MessageCompression, MessageCompressionSettings, PacketSize, PacketType};
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::time::Duration;
 use url::Url;
 
@@ -35,6 +42,8 @@ const DEFAULT_MAX_STARTUP_GATEWAY_WAITING_PERIOD: Duration = Duration::from_secs
 // bought bandwidth tokens to not have time to be spent; Once we remove the gateway from the
 // bandwidth bridging protocol, we can come back to a smaller timeout value
 const DEFAULT_GATEWAY_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_MAXIMUM_CONSECUTIVE_GATEWAY_FAILURES: usize = 100;
+const DEFAULT_MAXIMUM_FAILOVER_ATTEMPTS: usize = 3;
 
 const DEFAULT_COVER_TRAFFIC_PRIMARY_SIZE_RATIO: f64 = 0.70;
 
@@ -61,6 +70,11 @@ const DEFAULT_MAXIMUM_REPLY_SURB_AGE: Duration = Duration::from_secs(12 * 60 * 6
 // 24 hours
 const DEFAULT_MAXIMUM_REPLY_KEY_AGE: Duration = Duration::from_secs(24 * 60 * 60);
 
+const DEFAULT_RECONSTRUCTED_SET_RETENTION: Duration = Duration::from_secs(30 * 60);
+const DEFAULT_MAXIMUM_REMEMBERED_RECONSTRUCTED_SETS: usize = 100_000;
+
+const DEFAULT_METRICS_SERVER_PORT: u16 = 18000;
+
 use crate::error::InvalidTrafficModeFailure;
 pub use nym_country_group::CountryGroup;
 
@@ -93,6 +107,17 @@ impl Config {
         self.debug.validate()
     }
 
+    pub fn with_metrics_server(mut self, server_address: SocketAddr) -> Self {
+        self.debug.metrics.enable_server = true;
+        self.debug.metrics.server_address = server_address;
+        self
+    }
+
+    pub fn with_gateway_failover(mut self, enabled: bool) -> Self {
+        self.debug.gateway_failover.enabled = enabled;
+        self
+    }
+
     pub fn with_debug_config(mut self, debug: DebugConfig) -> Self {
         self.debug = debug;
         self
@@ -121,6 +146,15 @@ impl Config {
         self.client.nym_api_urls = nym_api_urls;
     }
 
+    pub fn with_topology_snapshot(mut self, topology_snapshot: TopologySnapshotSource) -> Self {
+        self.set_topology_snapshot(topology_snapshot);
+        self
+    }
+
+    pub fn set_topology_snapshot(&mut self, topology_snapshot: TopologySnapshotSource) {
+        self.client.topology_snapshot = Some(topology_snapshot);
+    }
+
     pub fn with_high_default_traffic_volume(mut self, enabled: bool) -> Self {
         if enabled {
             self.set_high_default_traffic_volume();
@@ -249,6 +283,15 @@ impl Config {
         self.debug.topology.topology_structure = topology_structure;
     }
 
+    pub fn with_route_selection(mut self, route_selection: RouteSelection) -> Self {
+        self.set_route_selection(route_selection);
+        self
+    }
+
+    pub fn set_route_selection(&mut self, route_selection: RouteSelection) {
+        self.debug.topology.route_selection = route_selection;
+    }
+
     pub fn with_no_per_hop_delays(mut self, no_per_hop_delays: bool) -> Self {
         if no_per_hop_delays {
             self.set_no_per_hop_delays()
@@ -314,6 +357,25 @@ pub struct Client {
     /// Addresses to APIs running on validator from which the client gets the view of the network.
     #[serde(alias = "validator_api_urls")]
     pub nym_api_urls: Vec<Url>,
+
+    /// If set, the network topology is going to be loaded from the specified signed snapshot
+    /// rather than being retrieved from the nym-api.
+    #[serde(default)]
+    pub topology_snapshot: Option<TopologySnapshotSource>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopologySnapshotSource {
+    /// Path to the signed topology snapshot, as exported with `nym-cli topology export`.
+    pub path: PathBuf,
+
+    /// Base58-encoded ed25519 public key the snapshot has to be signed with.
+    pub trusted_signer: String,
+
+    /// Maximum age of the snapshot after which it's no longer going to be used.
+    #[serde(with = "humantime_serde")]
+    pub max_age: Duration,
 }
 
 impl Client {
@@ -340,6 +402,7 @@ impl Client {
             disabled_credentials_mode: true,
             nyxd_urls,
             nym_api_urls,
+            topology_snapshot: None,
         }
     }
 
@@ -356,6 +419,7 @@ impl Client {
             disabled_credentials_mode,
             nyxd_urls,
             nym_api_urls,
+            topology_snapshot: None,
         }
     }
 }
@@ -391,9 +455,28 @@ pub struct Traffic {
     pub secondary_packet_size: Option<PacketSize>,
 
     pub packet_type: PacketType,
+
+    /// Specifies the algorithm used for compressing sent messages before they get split into packets.
+    /// Replies are only compressed if the party we're replying to has used compression itself.
+    pub message_compression: Option<MessageCompression>,
+
+    /// Specifies the minimum size (in bytes) of a message for the compression to be attempted.
+    pub message_compression_threshold: usize,
+
+    /// Specifies whether non-reply messages should also get compressed.
+    /// Recipients running older software versions will not be able to read compressed messages,
+    /// so only enable it if all of them are known to understand them.
+    pub compress_non_reply_messages: bool,
 }
 
 impl Traffic {
+    pub fn message_compression_settings(&self) -> Option<MessageCompressionSettings> {
+        self.message_compression.map(|algorithm| {
+            MessageCompressionSettings::new(algorithm)
+                .with_threshold(self.message_compression_threshold)
+        })
+    }
+
     pub fn validate(&self) -> bool {
         if let Some(secondary_packet_size) = self.secondary_packet_size {
             if secondary_packet_size == PacketSize::AckPacket
@@ -415,6 +498,9 @@ impl Default for Traffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: None,
             packet_type: PacketType::Mix,
+            message_compression: None,
+            message_compression_threshold: DEFAULT_MESSAGE_COMPRESSION_THRESHOLD,
+            compress_non_reply_messages: false,
         }
     }
 }
@@ -463,6 +549,31 @@ impl Default for GatewayConnection {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GatewayFailover {
+    /// Specifies whether the client should automatically switch to a different gateway
+    /// once the current one is assumed to be dead. Otherwise the client is going to halt.
+    pub enabled: bool,
+
+    /// Number of consecutive failures to send packets to the gateway before it is assumed to be dead.
+    pub maximum_consecutive_failures: usize,
+
+    /// Maximum number of different gateways the client is going to try to switch to,
+    /// before giving up, upon a single gateway failure.
+    pub maximum_failover_attempts: usize,
+}
+
+impl Default for GatewayFailover {
+    fn default() -> Self {
+        GatewayFailover {
+            enabled: false,
+            maximum_consecutive_failures: DEFAULT_MAXIMUM_CONSECUTIVE_GATEWAY_FAILURES,
+            maximum_failover_attempts: DEFAULT_MAXIMUM_FAILOVER_ATTEMPTS,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct Acknowledgements {
@@ -523,12 +634,27 @@ pub struct Topology {
     pub topology_structure: TopologyStructure,
 
     /// Specifies a minimum performance of a mixnode that is used on route construction.
-    /// This setting is only applicable when `NymApi` topology is used.
+    /// This setting is only applicable when `NymApi` or `NymApiQuorum` topology is used.
     pub minimum_mixnode_performance: u8,
 
     /// Specifies a minimum performance of a gateway that is used on route construction.
-    /// This setting is only applicable when `NymApi` topology is used.
+    /// This setting is only applicable when `NymApi` or `NymApiQuorum` topology is used.
     pub minimum_gateway_performance: u8,
+
+    /// Specifies how mixnodes are chosen from each layer when constructing packet routes.
+    pub route_selection: RouteSelection,
+
+    /// Specifies whether a single route can contain at most one mixnode from the same
+    /// /16 (IPv4) or /32 (IPv6) subnet.
+    /// Note that mixnodes belonging to the same family are never put in the same route
+    /// regardless of this setting.
+    pub distinct_route_subnets: bool,
+
+    /// Specifies whether the client should subscribe to the network events pushed by the nym-api
+    /// and refresh its topology as soon as, for example, a new epoch begins,
+    /// rather than only every `topology_refresh_rate`.
+    /// This setting is only applicable when `NymApi` topology is used.
+    pub subscribe_to_network_events: bool,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -537,6 +663,30 @@ pub enum TopologyStructure {
     #[default]
     NymApi,
     GeoAware(GroupBy),
+
+    /// Query all the configured nym-api endpoints and only accept the topology
+    /// once at least `quorum` of them returned the same set of nodes and keys.
+    NymApiQuorum {
+        quorum: usize,
+    },
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RouteSelection {
+    /// Every mixnode within a layer is equally likely to get chosen.
+    #[default]
+    Uniform,
+
+    /// Mixnodes are chosen proportionally to their performance.
+    PerformanceWeighted,
+
+    /// Mixnodes are chosen proportionally to their stake saturation.
+    StakeWeighted,
+
+    /// Mixnodes with lower round-trip-time, as measured by other nodes in the network, are preferred.
+    /// `anonymity_budget` is the percentage (0-100) of the selection probability that gets distributed
+    /// based on the latency, the remainder is spread uniformly across all nodes in the layer.
+    LatencyWeighted { anonymity_budget: u8 },
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -565,6 +715,9 @@ impl Default for Topology {
             topology_structure: TopologyStructure::default(),
             minimum_mixnode_performance: DEFAULT_MIN_MIXNODE_PERFORMANCE,
             minimum_gateway_performance: DEFAULT_MIN_GATEWAY_PERFORMANCE,
+            route_selection: RouteSelection::default(),
+            distinct_route_subnets: false,
+            subscribe_to_network_events: false,
         }
     }
 }
@@ -631,6 +784,96 @@ impl Default for ReplySurbs {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageReconstruction {
+    /// Defines maximum amount of time an incomplete message can go without receiving any new fragments
+    /// before it's assumed the remaining ones were lost and the message gets abandoned.
+    #[serde(with = "humantime_serde")]
+    pub maximum_incomplete_message_age: Duration,
+
+    /// Defines maximum number of fragment sets of incomplete messages that can be buffered at once.
+    /// Once exceeded, the least recently updated ones get abandoned.
+    pub maximum_buffered_fragment_sets: usize,
+
+    /// Defines maximum total size (in bytes) of fragments of incomplete messages that can be buffered at once.
+    /// Once exceeded, the least recently updated ones get abandoned.
+    pub maximum_buffered_fragments_size: usize,
+
+    /// Defines for how long ids of the already reconstructed fragment sets are remembered
+    /// so that any duplicate fragments (for example due to lost acks) could be discarded.
+    #[serde(with = "humantime_serde")]
+    pub reconstructed_set_retention: Duration,
+
+    /// Defines maximum number of ids of the already reconstructed fragment sets that are remembered.
+    pub maximum_remembered_reconstructed_sets: usize,
+}
+
+impl Default for MessageReconstruction {
+    fn default() -> Self {
+        MessageReconstruction {
+            maximum_incomplete_message_age: DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE,
+            maximum_buffered_fragment_sets: DEFAULT_MAXIMUM_BUFFERED_FRAGMENT_SETS,
+            maximum_buffered_fragments_size: DEFAULT_MAXIMUM_BUFFERED_FRAGMENTS_SIZE,
+            reconstructed_set_retention: DEFAULT_RECONSTRUCTED_SET_RETENTION,
+            maximum_remembered_reconstructed_sets: DEFAULT_MAXIMUM_REMEMBERED_RECONSTRUCTED_SETS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErasureCoding {
+    /// Specifies the ratio of parity to data fragments (e.g. 0.25 means a single parity fragment
+    /// for every 4 data fragments) of multi-fragment messages sent on the general lane.
+    /// If not set, the messages are not erasure coded and every lost fragment has to get retransmitted.
+    pub general_lane_redundancy: Option<f64>,
+
+    /// Specifies the ratio of parity to data fragments of multi-fragment messages sent on
+    /// the per-connection lanes.
+    pub connection_lanes_redundancy: Option<f64>,
+
+    /// Specifies the ratio of parity to data fragments of multi-fragment messages containing
+    /// additional reply SURBs.
+    pub additional_reply_surbs_lane_redundancy: Option<f64>,
+}
+
+impl ErasureCoding {
+    pub fn validate(&self) -> bool {
+        [
+            self.general_lane_redundancy,
+            self.connection_lanes_redundancy,
+            self.additional_reply_surbs_lane_redundancy,
+        ]
+        .into_iter()
+        .flatten()
+        .all(|redundancy| redundancy.is_finite() && redundancy > 0.)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Metrics {
+    /// Specifies whether the packet statistics should be served in the Prometheus text format
+    /// on the `/metrics` endpoint of a local HTTP server.
+    pub enable_server: bool,
+
+    /// Address the metrics server is going to listen on, if enabled.
+    pub server_address: SocketAddr,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            enable_server: false,
+            server_address: SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                DEFAULT_METRICS_SERVER_PORT,
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DebugConfig {
@@ -643,6 +886,9 @@ pub struct DebugConfig {
     /// Defines all configuration options related to the gateway connection.
     pub gateway_connection: GatewayConnection,
 
+    /// Defines all configuration options related to switching to a different gateway upon failure.
+    pub gateway_failover: GatewayFailover,
+
     /// Defines all configuration options related to acknowledgements, such as delays or wait timeouts.
     pub acknowledgements: Acknowledgements,
 
@@ -651,12 +897,21 @@ pub struct DebugConfig {
 
     /// Defines all configuration options related to reply SURBs.
     pub reply_surbs: ReplySurbs,
+
+    /// Defines all configuration options related to reconstruction of received messages.
+    pub message_reconstruction: MessageReconstruction,
+
+    /// Defines all configuration options related to erasure coding of multi-fragment messages.
+    pub erasure_coding: ErasureCoding,
+
+    /// Defines all configuration options related to exporting the packet statistics.
+    pub metrics: Metrics,
 }
 
 impl DebugConfig {
     pub fn validate(&self) -> bool {
         // no other sections have explicit requirements (yet)
-        self.traffic.validate()
+        self.traffic.validate() && self.erasure_coding.validate()
     }
 }
 
@@ -669,9 +924,13 @@ impl Default for DebugConfig {
             traffic: Default::default(),
             cover_traffic: Default::default(),
             gateway_connection: Default::default(),
+            gateway_failover: Default::default(),
             acknowledgements: Default::default(),
             topology: Default::default(),
             reply_surbs: Default::default(),
+            message_reconstruction: Default::default(),
+            erasure_coding: Default::default(),
+            metrics: Default::default(),
         }
     }
 }
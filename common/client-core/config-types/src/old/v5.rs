@@ -96,6 +96,7 @@ impl From<ConfigV5> for Config {
                 disabled_credentials_mode: value.client.disabled_credentials_mode,
                 nyxd_urls: value.client.nyxd_urls,
                 nym_api_urls: value.client.nym_api_urls,
+                topology_snapshot: None,
             },
             debug: DebugConfig {
                 traffic: Traffic {
@@ -111,6 +112,7 @@ impl From<ConfigV5> for Config {
                     primary_packet_size: value.debug.traffic.primary_packet_size,
                     secondary_packet_size: value.debug.traffic.secondary_packet_size,
                     packet_type: value.debug.traffic.packet_type,
+                    ..Default::default()
                 },
                 cover_traffic: CoverTraffic {
                     loop_cover_traffic_average_delay: value
@@ -132,6 +134,7 @@ impl From<ConfigV5> for Config {
                         .gateway_connection
                         .gateway_response_timeout,
                 },
+                gateway_failover: Default::default(),
                 acknowledgements: Acknowledgements {
                     average_ack_delay: value.debug.acknowledgements.average_ack_delay,
                     ack_wait_multiplier: value.debug.acknowledgements.ack_wait_multiplier,
@@ -181,6 +184,9 @@ impl From<ConfigV5> for Config {
                     maximum_reply_key_age: value.debug.reply_surbs.maximum_reply_key_age,
                     surb_mix_hops: value.debug.reply_surbs.surb_mix_hops,
                 },
+                message_reconstruction: Default::default(),
+                erasure_coding: Default::default(),
+                metrics: Default::default(),
             },
         }
     }
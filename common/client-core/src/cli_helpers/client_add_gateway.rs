@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::cli_helpers::client_encrypt_keys::{keys_passphrase_from_env, KEYS_PASSPHRASE_ENV_VAR};
 use crate::cli_helpers::types::GatewayInfo;
 use crate::cli_helpers::{CliClient, CliClientConfig};
 use crate::client::base_client::non_wasm_helpers::setup_fs_gateways_storage;
@@ -39,6 +40,21 @@ pub struct CommonClientAddGatewayArgs {
     #[cfg_attr(feature = "cli", clap(long, conflicts_with = "gateway_id"))]
     pub latency_based_selection: bool,
 
+    /// Specifies whether the new gateway should be chosen based on the round-trip-times reported by the network,
+    /// rather than measured by the client itself. The provided value is the anonymity budget, i.e. the percentage
+    /// (0-100) of the selection probability that gets distributed based on the latency.
+    /// The remainder is spread uniformly across all gateways.
+    #[cfg_attr(
+        feature = "cli",
+        clap(
+            long,
+            value_name = "ANONYMITY_BUDGET",
+            value_parser = clap::value_parser!(u8).range(0..=100),
+            conflicts_with_all = ["gateway_id", "latency_based_selection"]
+        )
+    )]
+    pub reported_latency_based_selection: Option<u8>,
+
     /// Specify whether this new gateway should be set as the active one
     #[cfg_attr(feature = "cli", clap(long, default_value_t = true))]
     pub set_active: bool,
@@ -72,7 +88,12 @@ where
     let core = config.core_config();
     let paths = config.common_paths();
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    // if the keys are encrypted at rest, their passphrase must be provided via the default env variable
+    let keys_passphrase = keys_passphrase_from_env(KEYS_PASSPHRASE_ENV_VAR);
+    let key_store = OnDiskKeys::new_with_optional_passphrase(
+        paths.keys.clone(),
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    );
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     // Attempt to use a user-provided gateway, if possible
@@ -82,6 +103,7 @@ where
     let selection_spec = GatewaySelectionSpecification::new(
         user_chosen_gateway_id.map(|id| id.to_base58_string()),
         Some(common_args.latency_based_selection),
+        common_args.reported_latency_based_selection,
         common_args.force_tls_gateway,
     );
     log::debug!("Gateway selection specification: {selection_spec:?}");
@@ -111,7 +133,12 @@ where
         hardcoded_topology.get_gateways()
     } else {
         let mut rng = rand::thread_rng();
-        crate::init::helpers::current_gateways(&mut rng, &core.client.nym_api_urls).await?
+        crate::init::helpers::current_gateways_for_selection(
+            &mut rng,
+            &core.client.nym_api_urls,
+            &selection_spec,
+        )
+        .await?
     };
 
     // since we're registering with a brand new gateway,
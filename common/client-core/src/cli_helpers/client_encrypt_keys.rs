@@ -0,0 +1,87 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_helpers::{CliClient, CliClientConfig};
+use crate::client::key_manager::persistence::OnDiskKeys;
+use crate::error::ClientCoreError;
+use log::info;
+use zeroize::Zeroizing;
+
+/// Default name of the environment variable holding the passphrase used for encrypting client keys at rest.
+pub const KEYS_PASSPHRASE_ENV_VAR: &str = "NYM_CLIENT_KEYS_PASSPHRASE";
+
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone)]
+pub struct CommonClientKeysPassphraseArgs {
+    /// Name of the environment variable holding the passphrase used for encrypting the client keys at rest.
+    /// Note that the passphrase itself is never accepted as a command line argument.
+    #[cfg_attr(feature = "cli", clap(long, default_value = KEYS_PASSPHRASE_ENV_VAR))]
+    pub keys_passphrase_env: String,
+}
+
+impl Default for CommonClientKeysPassphraseArgs {
+    fn default() -> Self {
+        CommonClientKeysPassphraseArgs {
+            keys_passphrase_env: KEYS_PASSPHRASE_ENV_VAR.to_string(),
+        }
+    }
+}
+
+impl CommonClientKeysPassphraseArgs {
+    /// Attempts to read the passphrase from the specified environment variable.
+    pub fn passphrase(&self) -> Option<Zeroizing<String>> {
+        keys_passphrase_from_env(&self.keys_passphrase_env)
+    }
+
+    pub fn required_passphrase(&self) -> Result<Zeroizing<String>, ClientCoreError> {
+        self.passphrase()
+            .ok_or_else(|| ClientCoreError::MissingKeysPassphrase {
+                env_var: self.keys_passphrase_env.clone(),
+            })
+    }
+}
+
+/// Attempts to read non-empty keys passphrase from the provided environment variable.
+pub fn keys_passphrase_from_env(env_var: &str) -> Option<Zeroizing<String>> {
+    std::env::var(env_var)
+        .ok()
+        .map(Zeroizing::new)
+        .filter(|passphrase| !passphrase.is_empty())
+}
+
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone)]
+pub struct CommonClientEncryptKeysArgs {
+    /// Id of client whose keys are going to be encrypted.
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub id: String,
+
+    #[cfg_attr(feature = "cli", command(flatten))]
+    pub keys_passphrase: CommonClientKeysPassphraseArgs,
+}
+
+/// Encrypts the existing plaintext keys of the client at rest.
+/// If the previous migration has been interrupted, it's going to be resumed instead.
+pub async fn encrypt_keys<C, A>(args: A) -> Result<(), C::Error>
+where
+    A: Into<CommonClientEncryptKeysArgs>,
+    C: CliClient,
+{
+    let common_args = args.into();
+    let id = &common_args.id;
+
+    let config = C::try_load_current_config(id).await?;
+    let paths = config.common_paths();
+
+    let passphrase = common_args.keys_passphrase.required_passphrase()?;
+    let key_store = OnDiskKeys::new_encrypted(paths.keys.clone(), passphrase.as_bytes());
+
+    info!("encrypting the keys of {} client \"{id}\"", C::NAME);
+    key_store
+        .encrypt_existing_keys()
+        .map_err(|source| ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        })?;
+
+    Ok(())
+}
@@ -18,6 +18,7 @@ use nym_crypto::asymmetric::identity;
 use nym_topology::NymTopology;
 use rand::rngs::OsRng;
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 // we can suppress this warning (as suggested by linter itself) since we're only using it in our own code
 #[allow(async_fn_in_trait)]
@@ -29,6 +30,14 @@ pub trait InitialisableClient: CliClient {
     fn default_config_path(id: &str) -> PathBuf;
 
     fn construct_config(init_args: &Self::InitArgs) -> Self::Config;
+
+    /// Passphrase used for encrypting the newly generated keys at rest.
+    /// If none is provided, the keys are going to be stored in plaintext.
+    fn keys_passphrase(
+        _init_args: &Self::InitArgs,
+    ) -> Result<Option<Zeroizing<String>>, Self::Error> {
+        Ok(None)
+    }
 }
 
 #[cfg_attr(feature = "cli", derive(clap::Args))]
@@ -51,6 +60,21 @@ pub struct CommonClientInitArgs {
     #[cfg_attr(feature = "cli", clap(long, conflicts_with = "gateway"))]
     pub latency_based_selection: bool,
 
+    /// Specifies whether the new gateway should be chosen based on the round-trip-times reported by the network,
+    /// rather than measured by the client itself. The provided value is the anonymity budget, i.e. the percentage
+    /// (0-100) of the selection probability that gets distributed based on the latency.
+    /// The remainder is spread uniformly across all gateways.
+    #[cfg_attr(
+        feature = "cli",
+        clap(
+            long,
+            value_name = "ANONYMITY_BUDGET",
+            value_parser = clap::value_parser!(u8).range(0..=100),
+            conflicts_with_all = ["gateway", "latency_based_selection"]
+        )
+    )]
+    pub reported_latency_based_selection: Option<u8>,
+
     /// Comma separated list of rest endpoints of the nyxd validators
     #[cfg_attr(
         feature = "cli",
@@ -115,6 +139,8 @@ where
         .into());
     }
 
+    let keys_passphrase = C::keys_passphrase(&init_args)?;
+
     C::initialise_storage_paths(id)?;
 
     // Attempt to use a user-provided gateway, if possible
@@ -124,6 +150,7 @@ where
     let selection_spec = GatewaySelectionSpecification::new(
         user_chosen_gateway_id.map(|id| id.to_base58_string()),
         Some(common_args.latency_based_selection),
+        common_args.reported_latency_based_selection,
         common_args.force_tls_gateway,
     );
     log::debug!("Gateway selection specification: {selection_spec:?}");
@@ -145,7 +172,10 @@ where
             .join(",")
     );
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    let key_store = OnDiskKeys::new_with_optional_passphrase(
+        paths.keys.clone(),
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    );
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     let mut rng = OsRng;
@@ -163,7 +193,12 @@ where
         hardcoded_topology.get_gateways()
     } else {
         let mut rng = rand::thread_rng();
-        crate::init::helpers::current_gateways(&mut rng, &core.client.nym_api_urls).await?
+        crate::init::helpers::current_gateways_for_selection(
+            &mut rng,
+            &core.client.nym_api_urls,
+            &selection_spec,
+        )
+        .await?
     };
 
     let gateway_setup = GatewaySetup::New {
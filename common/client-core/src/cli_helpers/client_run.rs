@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nym_crypto::asymmetric::identity;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 #[cfg_attr(feature = "cli", derive(clap::Args))]
@@ -56,4 +57,9 @@ pub struct CommonClientRunArgs {
     // has defined the conflict on that field itself
     #[cfg_attr(feature = "cli", clap(long, hide = true))]
     pub no_cover: bool,
+
+    /// Serve the packet statistics in the Prometheus format on the `/metrics` endpoint
+    /// of a local HTTP server listening on the specified address
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub metrics_address: Option<SocketAddr>,
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod client_add_gateway;
+pub mod client_encrypt_keys;
 pub mod client_import_credential;
 pub mod client_init;
 pub mod client_list_gateways;
@@ -0,0 +1,411 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::base_client::storage::helpers::{set_active_gateway, store_gateway_details};
+use crate::client::helpers::{get_time_now, Instant};
+use crate::client::mix_traffic::failover::GatewayFailover;
+use crate::client::mix_traffic::transceiver::{GatewayTransceiver, RemoteGateway};
+use crate::client::self_address::SelfAddressUpdater;
+use crate::client::topology_control::TopologyAccessor;
+use crate::config;
+use crate::error::ClientCoreError;
+use crate::init::helpers::{register_with_gateway, uniformly_random_gateway};
+use crate::init::types::SelectedGateway;
+use crate::MaybeSendSync;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use nym_client_core_gateways_storage::{
+    GatewayDetails, GatewayRegistration, GatewaysDetailsStore, RemoteGatewayDetails,
+};
+use nym_credential_storage::storage::Storage as CredentialStorage;
+use nym_crypto::asymmetric::identity;
+use nym_gateway_client::{GatewayClient, GatewayConfig, PacketRouter};
+use nym_sphinx::addressing::clients::Recipient;
+use nym_task::TaskClient;
+use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
+use rand::rngs::OsRng;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a gateway that has failed us is excluded from being failed over to.
+const FAILED_GATEWAY_EXCLUSION: Duration = Duration::from_secs(60 * 60);
+
+/// Gateways that have recently failed us alongside the time of their (latest) failure.
+#[derive(Debug)]
+struct FailedGateways {
+    exclusion: Duration,
+    failures: Vec<(identity::PublicKey, Instant)>,
+}
+
+impl FailedGateways {
+    fn new(exclusion: Duration) -> Self {
+        FailedGateways {
+            exclusion,
+            failures: Vec::new(),
+        }
+    }
+
+    fn contains(&self, gateway: &identity::PublicKey) -> bool {
+        self.failures.iter().any(|(failed, _)| failed == gateway)
+    }
+
+    fn insert(&mut self, gateway: identity::PublicKey, now: Instant) {
+        match self
+            .failures
+            .iter_mut()
+            .find(|(failed, _)| failed == &gateway)
+        {
+            Some((_, failed_at)) => *failed_at = now,
+            None => self.failures.push((gateway, now)),
+        }
+    }
+
+    // gives gateways another chance once enough time has passed,
+    // so that we wouldn't eventually run out of gateways to fail over to
+    fn forget_expired(&mut self, now: Instant) {
+        let exclusion = self.exclusion;
+        self.failures
+            .retain(|(_, failed_at)| now.saturating_duration_since(*failed_at) < exclusion)
+    }
+}
+
+/// Replaces a dead gateway with another one this client has already registered with
+/// or, failing that, with a freshly registered gateway chosen from the current topology.
+///
+/// Note: it's only usable in the disabled credentials mode as otherwise
+/// we'd have to be spending credentials with the new gateway.
+pub(crate) struct RemoteGatewayFailover<C, St, D> {
+    config: config::GatewayFailover,
+    gateway_connection: config::GatewayConnection,
+
+    self_address: Recipient,
+    address_updater: SelfAddressUpdater,
+    identity_keys: Arc<identity::KeyPair>,
+
+    details_store: D,
+    topology_accessor: TopologyAccessor,
+
+    packet_router: PacketRouter,
+    shutdown: TaskClient,
+
+    /// Gateways that have recently failed us, so that we wouldn't attempt to go back to them.
+    failed_gateways: FailedGateways,
+
+    _credentials: PhantomData<fn() -> (C, St)>,
+}
+
+impl<C, St, D> RemoteGatewayFailover<C, St, D>
+where
+    C: DkgQueryClient + Send + Sync + 'static,
+    St: CredentialStorage + 'static,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+    D: GatewaysDetailsStore + MaybeSendSync,
+    D::StorageError: Send + Sync + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        config: config::GatewayFailover,
+        gateway_connection: config::GatewayConnection,
+        self_address: Recipient,
+        address_updater: SelfAddressUpdater,
+        identity_keys: Arc<identity::KeyPair>,
+        details_store: D,
+        topology_accessor: TopologyAccessor,
+        packet_router: PacketRouter,
+        shutdown: TaskClient,
+    ) -> Self {
+        RemoteGatewayFailover {
+            config,
+            gateway_connection,
+            self_address,
+            address_updater,
+            identity_keys,
+            details_store,
+            topology_accessor,
+            packet_router,
+            shutdown,
+            failed_gateways: FailedGateways::new(FAILED_GATEWAY_EXCLUSION),
+            _credentials: PhantomData,
+        }
+    }
+
+    fn has_failed(&self, gateway: &identity::PublicKey) -> bool {
+        self.failed_gateways.contains(gateway)
+    }
+
+    fn mark_as_failed(&mut self, gateway: identity::PublicKey) {
+        self.failed_gateways.insert(gateway, get_time_now())
+    }
+
+    // gateways we have already registered with that are still present in the topology,
+    // ordered from the most recently registered
+    async fn registered_candidates(
+        &self,
+        online: &[identity::PublicKey],
+    ) -> Result<Vec<RemoteGatewayDetails>, ClientCoreError> {
+        let mut registrations = self.details_store.all_gateways().await.map_err(|source| {
+            ClientCoreError::GatewaysDetailsStoreError {
+                source: Box::new(source),
+            }
+        })?;
+        registrations
+            .sort_by_key(|registration| std::cmp::Reverse(registration.registration_timestamp));
+
+        Ok(registrations
+            .into_iter()
+            .filter_map(|registration| match registration.details {
+                GatewayDetails::Remote(details) => Some(details),
+                GatewayDetails::Custom(_) => None,
+            })
+            .filter(|details| {
+                !self.has_failed(&details.gateway_id) && online.contains(&details.gateway_id)
+            })
+            .collect())
+    }
+
+    async fn connect(
+        &self,
+        details: &RemoteGatewayDetails,
+    ) -> Result<GatewayClient<C, St>, ClientCoreError> {
+        let cfg = GatewayConfig::new(
+            details.gateway_id,
+            details
+                .gateway_owner_address
+                .as_ref()
+                .map(|o| o.to_string()),
+            details.gateway_listener.to_string(),
+        );
+
+        let mut gateway_client = GatewayClient::new(
+            cfg,
+            Arc::clone(&self.identity_keys),
+            Some(Arc::clone(&details.derived_aes128_ctr_blake3_hmac_keys)),
+            self.packet_router.clone(),
+            None,
+            self.shutdown.fork(details.gateway_id.to_base58_string()),
+        )
+        .with_disabled_credentials_mode(true)
+        .with_response_timeout(self.gateway_connection.gateway_response_timeout);
+
+        gateway_client
+            .authenticate_and_start()
+            .await
+            .map_err(|source| ClientCoreError::GatewayClientError {
+                gateway_id: details.gateway_id.to_base58_string(),
+                source,
+            })?;
+
+        Ok(gateway_client)
+    }
+
+    async fn register(
+        &self,
+        selected: SelectedGateway,
+    ) -> Result<(GatewayClient<C, St>, GatewayRegistration), ClientCoreError> {
+        let SelectedGateway::Remote {
+            gateway_id,
+            gateway_owner_address,
+            gateway_listener,
+            ..
+        } = selected
+        else {
+            return Err(ClientCoreError::UnexpectedPersistedCustomGatewayDetails);
+        };
+
+        let registration = register_with_gateway(
+            gateway_id,
+            gateway_listener.clone(),
+            Arc::clone(&self.identity_keys),
+        )
+        .await?;
+
+        let gateway_client = registration.authenticated_ephemeral_client.upgrade(
+            self.packet_router.clone(),
+            None,
+            self.shutdown.fork(gateway_id.to_base58_string()),
+        );
+
+        let details = GatewayDetails::new_remote(
+            gateway_id,
+            registration.shared_keys,
+            gateway_owner_address,
+            gateway_listener,
+            None,
+        );
+
+        Ok((gateway_client, details.into()))
+    }
+
+    async fn switch_to(&mut self, gateway_id: identity::PublicKey) -> Result<(), ClientCoreError> {
+        set_active_gateway(&self.details_store, &gateway_id.to_base58_string()).await?;
+
+        self.self_address = Recipient::new(
+            *self.self_address.identity(),
+            *self.self_address.encryption_key(),
+            gateway_id,
+        );
+        info!("our new address is {}", self.self_address);
+        self.address_updater.update(self.self_address);
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C, St, D> GatewayFailover for RemoteGatewayFailover<C, St, D>
+where
+    C: DkgQueryClient + Send + Sync + 'static,
+    St: CredentialStorage + 'static,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+    D: GatewaysDetailsStore + MaybeSendSync,
+    D::StorageError: Send + Sync + 'static,
+{
+    async fn replace_gateway(
+        &mut self,
+        failed_gateway: identity::PublicKey,
+    ) -> Result<Box<dyn GatewayTransceiver + Send>, ClientCoreError> {
+        self.failed_gateways.forget_expired(get_time_now());
+        self.mark_as_failed(failed_gateway);
+
+        let failure = |attempts| ClientCoreError::GatewayFailoverFailure {
+            failed_gateway: failed_gateway.to_base58_string(),
+            attempts,
+        };
+
+        let Some(topology) = self.topology_accessor.current_topology().await else {
+            warn!("there's no valid network topology available to choose a new gateway from");
+            return Err(failure(0));
+        };
+        let online = topology
+            .gateways()
+            .iter()
+            .map(|node| node.identity_key)
+            .collect::<Vec<_>>();
+
+        let mut attempts = 0;
+
+        // firstly try the gateways we have already registered with as it doesn't require
+        // establishing new shared keys
+        for details in self.registered_candidates(&online).await? {
+            if attempts >= self.config.maximum_failover_attempts {
+                return Err(failure(attempts));
+            }
+            attempts += 1;
+
+            debug!("attempting to fail over to gateway {}", details.gateway_id);
+            match self.connect(&details).await {
+                Ok(gateway_client) => {
+                    self.switch_to(details.gateway_id).await?;
+                    return Ok(Box::new(RemoteGateway::new(gateway_client)));
+                }
+                Err(err) => {
+                    warn!(
+                        "failed to fail over to gateway {}: {err}",
+                        details.gateway_id
+                    );
+                    self.mark_as_failed(details.gateway_id)
+                }
+            }
+        }
+
+        // if we're communicating via wss with the current gateway, the new one must support it too
+        let must_use_tls = self
+            .details_store
+            .load_gateway_details(&failed_gateway.to_base58_string())
+            .await
+            .ok()
+            .and_then(|registration| match registration.details {
+                GatewayDetails::Remote(details) => Some(details.gateway_listener.scheme() == "wss"),
+                GatewayDetails::Custom(_) => None,
+            })
+            .unwrap_or_default();
+
+        let mut rng = OsRng;
+        while attempts < self.config.maximum_failover_attempts {
+            attempts += 1;
+
+            let candidates = topology
+                .gateways()
+                .iter()
+                .filter(|node| !self.has_failed(&node.identity_key))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let node = match uniformly_random_gateway(&mut rng, &candidates, must_use_tls) {
+                Ok(node) => node,
+                Err(err) => {
+                    warn!("could not choose a new gateway: {err}");
+                    break;
+                }
+            };
+            let gateway_id = node.identity_key;
+
+            debug!("attempting to register with and fail over to gateway {gateway_id}");
+            let selected = SelectedGateway::from_topology_node(node, None, must_use_tls)?;
+            match self.register(selected).await {
+                Ok((mut gateway_client, registration)) => {
+                    if let Err(err) = gateway_client.authenticate_and_start().await {
+                        warn!("failed to start the connection with gateway {gateway_id}: {err}");
+                        self.mark_as_failed(gateway_id);
+                        continue;
+                    }
+                    store_gateway_details(&self.details_store, &registration).await?;
+                    self.switch_to(gateway_id).await?;
+                    return Ok(Box::new(RemoteGateway::new(gateway_client)));
+                }
+                Err(err) => {
+                    warn!("failed to register with gateway {gateway_id}: {err}");
+                    self.mark_as_failed(gateway_id)
+                }
+            }
+        }
+
+        Err(failure(attempts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gateway() -> identity::PublicKey {
+        *identity::KeyPair::new(&mut OsRng).public_key()
+    }
+
+    #[test]
+    fn failed_gateways_expire() {
+        let first = gateway();
+        let second = gateway();
+
+        let exclusion = Duration::from_secs(60);
+        let mut failed = FailedGateways::new(exclusion);
+        let start = get_time_now();
+        failed.insert(first, start);
+        failed.insert(second, start + exclusion / 2);
+        assert!(failed.contains(&first));
+        assert!(failed.contains(&second));
+
+        failed.forget_expired(start + exclusion);
+        assert!(!failed.contains(&first));
+        assert!(failed.contains(&second));
+
+        failed.forget_expired(start + exclusion * 2);
+        assert!(!failed.contains(&second));
+    }
+
+    #[test]
+    fn repeated_failures_extend_the_exclusion() {
+        let first = gateway();
+
+        let exclusion = Duration::from_secs(60);
+        let mut failed = FailedGateways::new(exclusion);
+        let start = get_time_now();
+        failed.insert(first, start);
+        failed.insert(first, start + exclusion / 2);
+        assert_eq!(failed.failures.len(), 1);
+
+        failed.forget_expired(start + exclusion);
+        assert!(failed.contains(&first));
+    }
+}
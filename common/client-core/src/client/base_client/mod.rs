@@ -4,12 +4,14 @@
 use super::packet_statistics_control::PacketStatisticsReporter;
 use super::received_buffer::ReceivedBufferMessage;
 use super::topology_control::geo_aware_provider::GeoAwareTopologyProvider;
+use crate::client::base_client::gateway_failover::RemoteGatewayFailover;
 use crate::client::base_client::storage::helpers::store_client_keys;
 use crate::client::base_client::storage::MixnetClientStorage;
 use crate::client::cover_traffic_stream::LoopCoverTrafficStream;
 use crate::client::inbound_messages::{InputMessage, InputMessageReceiver, InputMessageSender};
 use crate::client::key_manager::persistence::KeyStore;
 use crate::client::key_manager::ClientKeys;
+use crate::client::mix_traffic::failover::BoxedGatewayFailover;
 use crate::client::mix_traffic::transceiver::{GatewayReceiver, GatewayTransceiver, RemoteGateway};
 use crate::client::mix_traffic::{BatchMixMessageSender, MixTrafficController};
 use crate::client::packet_statistics_control::PacketStatisticsControl;
@@ -23,9 +25,14 @@ use crate::client::replies::reply_controller::{ReplyControllerReceiver, ReplyCon
 use crate::client::replies::reply_storage::{
     CombinedReplyStorage, PersistentReplyStorage, ReplyStorageBackend, SentReplyKeys,
 };
+use crate::client::self_address::{address_channel, SelfAddress};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::topology_control::event_driven_provider::EventDrivenTopologyProvider;
 use crate::client::topology_control::nym_api_provider::NymApiTopologyProvider;
+use crate::client::topology_control::quorum_provider::QuorumTopologyProvider;
 use crate::client::topology_control::{
-    nym_api_provider, TopologyAccessor, TopologyRefresher, TopologyRefresherConfig,
+    nym_api_provider, quorum_provider, route_selection_strategy, TopologyAccessor,
+    TopologyRefresher, TopologyRefresherConfig,
 };
 use crate::config::{Config, DebugConfig};
 use crate::error::ClientCoreError;
@@ -33,7 +40,7 @@ use crate::init::{
     setup_gateway,
     types::{GatewaySetup, InitialisationResult},
 };
-use crate::{config, spawn_future};
+use crate::{config, spawn_future, MaybeSendSync};
 use futures::channel::mpsc;
 use log::{debug, error, info, warn};
 use nym_bandwidth_controller::BandwidthController;
@@ -52,7 +59,10 @@ use nym_sphinx::receiver::{ReconstructedMessage, SphinxMessageReceiver};
 use nym_task::connections::{ConnectionCommandReceiver, ConnectionCommandSender, LaneQueueLengths};
 use nym_task::{TaskClient, TaskHandle};
 use nym_topology::provider_trait::TopologyProvider;
-use nym_topology::HardcodedTopologyProvider;
+use nym_topology::{
+    HardcodedTopologyProvider, RouteConstraints, RouteSelectionStrategy,
+    SignedSnapshotTopologyProvider,
+};
 use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
 use rand::rngs::OsRng;
 use std::fmt::Debug;
@@ -68,6 +78,7 @@ use url::Url;
 ))]
 pub mod non_wasm_helpers;
 
+mod gateway_failover;
 pub mod helpers;
 pub mod storage;
 
@@ -113,6 +124,7 @@ pub struct ClientState {
     pub reply_controller_sender: ReplyControllerSender,
     pub topology_accessor: TopologyAccessor,
     pub gateway_connection: GatewayConnection,
+    pub self_address: SelfAddress,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -182,6 +194,7 @@ pub struct BaseClientBuilder<'a, C, S: MixnetClientStorage> {
     wait_for_gateway: bool,
     wireguard_connection: bool,
     custom_topology_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
+    custom_route_selection: Option<RouteSelectionStrategy>,
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send>>,
     shutdown: Option<TaskClient>,
 
@@ -205,6 +218,7 @@ where
             wait_for_gateway: false,
             wireguard_connection: false,
             custom_topology_provider: None,
+            custom_route_selection: None,
             custom_gateway_transceiver: None,
             shutdown: None,
             setup_method: GatewaySetup::MustLoad { gateway_id: None },
@@ -238,6 +252,14 @@ where
         self
     }
 
+    /// Overrides the route selection strategy specified in the config,
+    /// for example in order to use custom weights.
+    #[must_use]
+    pub fn with_route_selection(mut self, route_selection: RouteSelectionStrategy) -> Self {
+        self.custom_route_selection = Some(route_selection);
+        self
+    }
+
     #[must_use]
     pub fn with_gateway_transceiver(mut self, sender: Box<dyn GatewayTransceiver + Send>) -> Self {
         self.custom_gateway_transceiver = Some(sender);
@@ -270,7 +292,7 @@ where
     fn start_cover_traffic_stream(
         debug_config: &DebugConfig,
         ack_key: Arc<AckKey>,
-        self_address: Recipient,
+        self_address: SelfAddress,
         topology_accessor: TopologyAccessor,
         mix_tx: BatchMixMessageSender,
         stats_tx: PacketStatisticsReporter,
@@ -336,6 +358,7 @@ where
         reply_controller_sender: ReplyControllerSender,
         shutdown: TaskClient,
         packet_statistics_control: PacketStatisticsReporter,
+        reconstruction_config: config::MessageReconstruction,
     ) {
         info!("Starting received messages buffer controller...");
         let controller: ReceivedMessagesBufferController<SphinxMessageReceiver> =
@@ -346,6 +369,7 @@ where
                 reply_key_storage,
                 reply_controller_sender,
                 packet_statistics_control,
+                reconstruction_config,
             );
         controller.start_with_shutdown(shutdown)
     }
@@ -465,19 +489,47 @@ where
 
     fn setup_topology_provider(
         custom_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
+        topology_snapshot: Option<config::TopologySnapshotSource>,
         config_topology: config::Topology,
         nym_api_urls: Vec<Url>,
-    ) -> Box<dyn TopologyProvider + Send + Sync> {
+    ) -> Result<Box<dyn TopologyProvider + Send + Sync>, ClientCoreError> {
         // if no custom provider was ... provided ..., create one using nym-api
-        custom_provider.unwrap_or_else(|| match config_topology.topology_structure {
-            config::TopologyStructure::NymApi => Box::new(NymApiTopologyProvider::new(
-                nym_api_provider::Config {
-                    min_mixnode_performance: config_topology.minimum_mixnode_performance,
-                    min_gateway_performance: config_topology.minimum_gateway_performance,
-                },
-                nym_api_urls,
-                env!("CARGO_PKG_VERSION").to_string(),
-            )),
+        if let Some(custom_provider) = custom_provider {
+            return Ok(custom_provider);
+        }
+
+        // or use the signed snapshot, if one was configured
+        if let Some(snapshot) = topology_snapshot {
+            let trusted_signer = identity::PublicKey::from_base58_string(&snapshot.trusted_signer)
+                .map_err(|source| ClientCoreError::MalformedTopologySnapshotSigner { source })?;
+            return Ok(Box::new(SignedSnapshotTopologyProvider::new_from_file(
+                snapshot.path,
+                &trusted_signer,
+                snapshot.max_age,
+            )?));
+        }
+
+        Ok(match config_topology.topology_structure {
+            config::TopologyStructure::NymApi => {
+                let provider = NymApiTopologyProvider::new(
+                    nym_api_provider::Config {
+                        min_mixnode_performance: config_topology.minimum_mixnode_performance,
+                        min_gateway_performance: config_topology.minimum_gateway_performance,
+                    },
+                    nym_api_urls.clone(),
+                    env!("CARGO_PKG_VERSION").to_string(),
+                );
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if config_topology.subscribe_to_network_events {
+                    return Ok(Box::new(EventDrivenTopologyProvider::new(
+                        provider,
+                        nym_api_urls,
+                    )));
+                }
+
+                Box::new(provider)
+            }
             config::TopologyStructure::GeoAware(group_by) => {
                 Box::new(GeoAwareTopologyProvider::new(
                     nym_api_urls,
@@ -485,6 +537,17 @@ where
                     group_by,
                 ))
             }
+            config::TopologyStructure::NymApiQuorum { quorum } => {
+                Box::new(QuorumTopologyProvider::new(
+                    quorum_provider::Config {
+                        quorum,
+                        min_mixnode_performance: config_topology.minimum_mixnode_performance,
+                        min_gateway_performance: config_topology.minimum_gateway_performance,
+                    },
+                    nym_api_urls,
+                    env!("CARGO_PKG_VERSION").to_string(),
+                )?)
+            }
         })
     }
 
@@ -492,6 +555,7 @@ where
     // the current global view of topology
     async fn start_topology_refresher(
         topology_provider: Box<dyn TopologyProvider + Send + Sync>,
+        route_selection: RouteSelectionStrategy,
         topology_config: config::Topology,
         topology_accessor: TopologyAccessor,
         local_gateway: &NodeIdentity,
@@ -499,7 +563,11 @@ where
         mut shutdown: TaskClient,
     ) -> Result<(), ClientCoreError> {
         let topology_refresher_config =
-            TopologyRefresherConfig::new(topology_config.topology_refresh_rate);
+            TopologyRefresherConfig::new(topology_config.topology_refresh_rate)
+                .with_route_selection(route_selection)
+                .with_route_constraints(RouteConstraints::new(
+                    topology_config.distinct_route_subnets,
+                ));
 
         let mut topology_refresher = TopologyRefresher::new(
             topology_refresher_config,
@@ -559,23 +627,56 @@ where
         Ok(())
     }
 
-    fn start_packet_statistics_control(shutdown: TaskClient) -> PacketStatisticsReporter {
+    fn start_packet_statistics_control(
+        metrics_config: config::Metrics,
+        shutdown: TaskClient,
+    ) -> PacketStatisticsReporter {
         info!("Starting packet statistics control...");
-        let (packet_statistics_control, packet_stats_reporter) = PacketStatisticsControl::new();
+        let (packet_statistics_control, packet_stats_reporter) =
+            PacketStatisticsControl::new(metrics_config);
         packet_statistics_control.start_with_shutdown(shutdown);
         packet_stats_reporter
     }
 
     fn start_mix_traffic_controller(
         gateway_transceiver: Box<dyn GatewayTransceiver + Send>,
+        failover_config: config::GatewayFailover,
+        failover: Option<BoxedGatewayFailover>,
         shutdown: TaskClient,
     ) -> BatchMixMessageSender {
         info!("Starting mix traffic controller...");
-        let (mix_traffic_controller, mix_tx) = MixTrafficController::new(gateway_transceiver);
+        let (mut mix_traffic_controller, mix_tx) =
+            MixTrafficController::new_dynamic(gateway_transceiver);
+        mix_traffic_controller = mix_traffic_controller
+            .with_max_failure_count(failover_config.maximum_consecutive_failures);
+        if let Some(failover) = failover {
+            mix_traffic_controller = mix_traffic_controller.with_failover(failover);
+        }
         mix_traffic_controller.start_with_shutdown(shutdown);
         mix_tx
     }
 
+    // failover is only supported for standard, websocket, connections to remote gateways
+    // that do not require spending any credentials
+    fn use_gateway_failover(
+        config: &Config,
+        wireguard_connection: bool,
+        has_custom_gateway_transceiver: bool,
+    ) -> bool {
+        if !config.debug.gateway_failover.enabled {
+            return false;
+        }
+        if has_custom_gateway_transceiver || wireguard_connection {
+            warn!("gateway failover is not supported for custom gateway transceivers or wireguard connections");
+            return false;
+        }
+        if !config.client.disabled_credentials_mode {
+            warn!("gateway failover is only supported in the disabled credentials mode");
+            return false;
+        }
+        true
+    }
+
     // TODO: rename it as it implies the data is persistent whilst one can use InMemBackend
     async fn setup_persistent_reply_storage(
         backend: S::ReplyStore,
@@ -630,6 +731,7 @@ where
         <S::KeyStore as KeyStore>::StorageError: Send + Sync,
         <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
         <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync + 'static,
+        S::GatewaysDetailsStore: MaybeSendSync,
         <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Sync + Send,
     {
         info!("Starting nym client");
@@ -642,7 +744,8 @@ where
         )
         .await?;
 
-        let (reply_storage_backend, credential_store) = self.client_store.into_runtime_stores();
+        let (reply_storage_backend, credential_store, details_store) =
+            self.client_store.into_runtime_stores();
 
         // channels for inter-component communication
         // TODO: make the channels be internally created by the relevant components
@@ -675,6 +778,7 @@ where
             reply_controller::requests::new_control_channels();
 
         let self_address = Self::mix_address(&init_res);
+        let (address_updater, self_address_handle) = address_channel(self_address);
         let ack_key = init_res.client_keys.ack_key();
         let encryption_keys = init_res.client_keys.encryption_keypair();
         let identity_keys = init_res.client_keys.identity_keypair();
@@ -687,13 +791,19 @@ where
 
         let topology_provider = Self::setup_topology_provider(
             self.custom_topology_provider.take(),
+            self.config.client.topology_snapshot.clone(),
             self.config.debug.topology,
             self.config.get_nym_api_endpoints(),
-        );
+        )?;
+
+        let route_selection = self.custom_route_selection.take().unwrap_or_else(|| {
+            route_selection_strategy(self.config.debug.topology.route_selection)
+        });
 
         // needs to be started as the first thing to block if required waiting for the gateway
         Self::start_topology_refresher(
             topology_provider,
+            route_selection,
             self.config.debug.topology,
             shared_topology_accessor.clone(),
             self_address.gateway(),
@@ -702,14 +812,45 @@ where
         )
         .await?;
 
-        let packet_stats_reporter =
-            Self::start_packet_statistics_control(shutdown.fork("packet_statistics_control"));
+        let packet_stats_reporter = Self::start_packet_statistics_control(
+            self.config.debug.metrics,
+            shutdown.fork("packet_statistics_control"),
+        );
 
-        let gateway_packet_router = PacketRouter::new(
+        let mut gateway_packet_router = PacketRouter::new(
             ack_sender,
             mixnet_messages_sender,
             shutdown.get_handle().named("gateway-packet-router"),
         );
+        let mut gateway_transceiver_shutdown = shutdown.fork("gateway_transceiver");
+
+        let use_gateway_failover = Self::use_gateway_failover(
+            self.config,
+            self.wireguard_connection,
+            self.custom_gateway_transceiver.is_some(),
+        );
+        let gateway_failover: Option<BoxedGatewayFailover> = if use_gateway_failover {
+            // with the failover in place, the gateway connection dying must not bring the
+            // whole client down
+            gateway_packet_router.mark_as_success();
+            gateway_transceiver_shutdown.disarm();
+
+            Some(Box::new(
+                RemoteGatewayFailover::<C, S::CredentialStore, _>::new(
+                    self.config.debug.gateway_failover,
+                    self.config.debug.gateway_connection,
+                    self_address,
+                    address_updater,
+                    Arc::clone(&identity_keys),
+                    details_store,
+                    shared_topology_accessor.clone(),
+                    gateway_packet_router.clone(),
+                    gateway_transceiver_shutdown.fork("failover"),
+                ),
+            ))
+        } else {
+            None
+        };
 
         let gateway_transceiver = Self::setup_gateway_transceiver(
             self.custom_gateway_transceiver,
@@ -718,7 +859,7 @@ where
             init_res,
             bandwidth_controller,
             gateway_packet_router,
-            shutdown.fork("gateway_transceiver"),
+            gateway_transceiver_shutdown,
         )
         .await?;
         let gateway_ws_fd = gateway_transceiver.ws_fd();
@@ -737,6 +878,7 @@ where
             reply_controller_sender.clone(),
             shutdown.fork("received_messages_buffer"),
             packet_stats_reporter.clone(),
+            self.config.debug.message_reconstruction,
         );
 
         // The message_sender is the transmitter for any component generating sphinx packets
@@ -745,6 +887,8 @@ where
         // The MixTrafficController then sends the actual traffic
         let message_sender = Self::start_mix_traffic_controller(
             gateway_transceiver,
+            self.config.debug.gateway_failover,
+            gateway_failover,
             shutdown.fork("mix_traffic_controller"),
         );
 
@@ -759,7 +903,7 @@ where
         let controller_config = real_messages_control::Config::new(
             &self.config.debug,
             Arc::clone(&ack_key),
-            self_address,
+            self_address_handle.clone(),
         );
 
         Self::start_real_traffic_controller(
@@ -787,7 +931,7 @@ where
             Self::start_cover_traffic_stream(
                 &self.config.debug,
                 ack_key,
-                self_address,
+                self_address_handle.clone(),
                 shared_topology_accessor.clone(),
                 message_sender,
                 packet_stats_reporter,
@@ -817,6 +961,7 @@ where
                 reply_controller_sender,
                 topology_accessor: shared_topology_accessor,
                 gateway_connection: GatewayConnection { gateway_ws_fd },
+                self_address: self_address_handle,
             },
             task_handle: shutdown,
         })
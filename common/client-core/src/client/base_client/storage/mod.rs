@@ -49,7 +49,13 @@ pub trait MixnetClientStorage {
     type CredentialStore: CredentialStorage;
     type GatewaysDetailsStore: GatewaysDetailsStore;
 
-    fn into_runtime_stores(self) -> (Self::ReplyStore, Self::CredentialStore);
+    fn into_runtime_stores(
+        self,
+    ) -> (
+        Self::ReplyStore,
+        Self::CredentialStore,
+        Self::GatewaysDetailsStore,
+    );
 
     fn key_store(&self) -> &Self::KeyStore;
     fn reply_store(&self) -> &Self::ReplyStore;
@@ -77,8 +83,18 @@ impl MixnetClientStorage for Ephemeral {
     type CredentialStore = EphemeralCredentialStorage;
     type GatewaysDetailsStore = InMemGatewaysDetails;
 
-    fn into_runtime_stores(self) -> (Self::ReplyStore, Self::CredentialStore) {
-        (self.reply_store, self.credential_store)
+    fn into_runtime_stores(
+        self,
+    ) -> (
+        Self::ReplyStore,
+        Self::CredentialStore,
+        Self::GatewaysDetailsStore,
+    ) {
+        (
+            self.reply_store,
+            self.credential_store,
+            self.gateway_details_store,
+        )
     }
 
     fn key_store(&self) -> &Self::KeyStore {
@@ -134,7 +150,17 @@ impl OnDiskPersistent {
         paths: CommonClientPaths,
         debug_config: &config::DebugConfig,
     ) -> Result<Self, ClientCoreError> {
-        let key_store = OnDiskKeys::new(paths.keys);
+        Self::from_paths_with_keys_passphrase(paths, debug_config, None).await
+    }
+
+    /// Same as [`Self::from_paths`], but if the passphrase is provided,
+    /// the client keys are expected to be encrypted at rest.
+    pub async fn from_paths_with_keys_passphrase(
+        paths: CommonClientPaths,
+        debug_config: &config::DebugConfig,
+        keys_passphrase: Option<&[u8]>,
+    ) -> Result<Self, ClientCoreError> {
+        let key_store = OnDiskKeys::new_with_optional_passphrase(paths.keys, keys_passphrase);
 
         let reply_store = non_wasm_helpers::setup_fs_reply_surb_backend(
             paths.reply_surb_database,
@@ -168,8 +194,18 @@ impl MixnetClientStorage for OnDiskPersistent {
     type CredentialStore = PersistentCredentialStorage;
     type GatewaysDetailsStore = OnDiskGatewaysDetails;
 
-    fn into_runtime_stores(self) -> (Self::ReplyStore, Self::CredentialStore) {
-        (self.reply_store, self.credential_store)
+    fn into_runtime_stores(
+        self,
+    ) -> (
+        Self::ReplyStore,
+        Self::CredentialStore,
+        Self::GatewaysDetailsStore,
+    ) {
+        (
+            self.reply_store,
+            self.credential_store,
+            self.gateway_details_store,
+        )
     }
 
     fn key_store(&self) -> &Self::KeyStore {
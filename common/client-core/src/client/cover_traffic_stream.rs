@@ -3,13 +3,13 @@
 
 use crate::client::mix_traffic::BatchMixMessageSender;
 use crate::client::packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter};
+use crate::client::self_address::SelfAddress;
 use crate::client::topology_control::TopologyAccessor;
 use crate::{config, spawn_future};
 use futures::task::{Context, Poll};
 use futures::{Future, Stream, StreamExt};
 use log::*;
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::cover::generate_loop_cover_packet;
 use nym_sphinx::params::{PacketSize, PacketType};
 use nym_sphinx::utils::sample_poisson_duration;
@@ -47,7 +47,7 @@ where
     mix_tx: BatchMixMessageSender,
 
     /// Represents full address of this client.
-    our_full_destination: Recipient,
+    our_full_destination: SelfAddress,
 
     /// Instance of a cryptographically secure random number generator.
     rng: R,
@@ -105,7 +105,7 @@ impl LoopCoverTrafficStream<OsRng> {
         ack_key: Arc<AckKey>,
         average_ack_delay: Duration,
         mix_tx: BatchMixMessageSender,
-        our_full_destination: Recipient,
+        our_full_destination: SelfAddress,
         topology_access: TopologyAccessor,
         traffic_config: config::Traffic,
         cover_config: config::CoverTraffic,
@@ -162,11 +162,11 @@ impl LoopCoverTrafficStream<OsRng> {
         // to wait a really tiny bit before actually obtaining the permit hence messing with our
         // poisson delay, but is it really a problem?
         let topology_permit = self.topology_access.get_read_permit().await;
+        let our_full_destination = self.our_full_destination.current();
         // the ack is sent back to ourselves (and then ignored)
-        let topology_ref = match topology_permit.try_get_valid_topology_ref(
-            &self.our_full_destination,
-            Some(&self.our_full_destination),
-        ) {
+        let topology_ref = match topology_permit
+            .try_get_valid_topology_ref(&our_full_destination, Some(&our_full_destination))
+        {
             Ok(topology) => topology,
             Err(err) => {
                 warn!("We're not going to send any loop cover message this time, as the current topology seem to be invalid - {err}");
@@ -178,7 +178,7 @@ impl LoopCoverTrafficStream<OsRng> {
             &mut self.rng,
             topology_ref,
             &self.ack_key,
-            &self.our_full_destination,
+            &our_full_destination,
             self.average_ack_delay,
             self.cover_traffic.loop_cover_traffic_average_delay,
             cover_traffic_packet_size,
@@ -16,6 +16,15 @@ use nym_pemstore::traits::{PemStorableKey, PemStorableKeyPair};
 use nym_pemstore::KeyPairPath;
 #[cfg(not(target_arch = "wasm32"))]
 use nym_sphinx::acknowledgements::AckKey;
+#[cfg(not(target_arch = "wasm32"))]
+use nym_store_cipher::{
+    Aes256Gcm, Algorithm, EncryptedData, ExportedStoreCipher, KdfInfo, KeySizeUser, Params,
+    StoreCipher, Unsigned, Version, AES256GCM_NONCE_SIZE,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use zeroize::Zeroizing;
 
 // we have to define it as an async trait since wasm storage is async
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -62,47 +71,290 @@ pub enum OnDiskKeysError {
         #[source]
         err: std::io::Error,
     },
+
+    #[error("the keys are encrypted at rest (cipher information found at {path:?}), but no passphrase has been provided")]
+    PassphraseRequired { path: PathBuf },
+
+    #[error("a passphrase has been provided, but the keys are not encrypted at rest (no cipher information found at {path:?}). you might want to encrypt them first")]
+    KeysNotEncrypted { path: PathBuf },
+
+    #[error("no passphrase has been provided for encrypting the keys")]
+    NoPassphraseProvided,
+
+    #[error("failed to load keys cipher information from {path:?}: {err}")]
+    CipherLoadFailure {
+        path: PathBuf,
+        #[source]
+        err: std::io::Error,
+    },
+
+    #[error("failed to store keys cipher information to {path:?}: {err}")]
+    CipherStoreFailure {
+        path: PathBuf,
+        #[source]
+        err: std::io::Error,
+    },
+
+    #[error("the keys cipher information at {path:?} is malformed: {err}")]
+    MalformedCipherInfo {
+        path: PathBuf,
+        #[source]
+        err: serde_json::Error,
+    },
+
+    #[error("the encrypted {key} key at {path} is malformed")]
+    MalformedEncryptedKey { key: String, path: String },
+
+    #[error("failed to encrypt or decrypt the keys: {source}")]
+    StoreCipherFailure {
+        #[from]
+        source: nym_store_cipher::Error,
+    },
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+const MEMORY_COST: u32 = 19 * 1024;
+#[cfg(not(target_arch = "wasm32"))]
+const ITERATIONS: u32 = 2;
+#[cfg(not(target_arch = "wasm32"))]
+const PARALLELISM: u32 = 1;
+#[cfg(not(target_arch = "wasm32"))]
+const OUTPUT_LENGTH: usize = <Aes256Gcm as KeySizeUser>::KeySize::USIZE;
+
+// use hardcoded values in case any `Default` implementation changes in the future
+#[cfg(not(target_arch = "wasm32"))]
+fn new_keys_kdf() -> Result<KdfInfo, nym_store_cipher::Error> {
+    let kdf_salt = KdfInfo::random_salt()?;
+    Ok(KdfInfo::Argon2 {
+        params: Params::new(MEMORY_COST, ITERATIONS, PARALLELISM, Some(OUTPUT_LENGTH))?,
+        algorithm: Algorithm::Argon2id,
+        version: Version::V0x13,
+        kdf_salt,
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn encrypted_pem_type<T: PemStorableKey>() -> String {
+    format!("ENCRYPTED {}", T::pem_type())
+}
+
+// the encrypted keys are stored as `version || nonce || ciphertext`
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_encrypted_key(data: EncryptedData) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + data.nonce.len() + data.ciphertext.len());
+    out.push(data.version);
+    out.extend_from_slice(&data.nonce);
+    out.extend_from_slice(&data.ciphertext);
+    out
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_encrypted_key(raw: &[u8]) -> Option<EncryptedData> {
+    if raw.len() <= 1 + AES256GCM_NONCE_SIZE {
+        return None;
+    }
+
+    Some(EncryptedData {
+        version: raw[0],
+        nonce: raw[1..1 + AES256GCM_NONCE_SIZE].to_vec(),
+        ciphertext: raw[1 + AES256GCM_NONCE_SIZE..].to_vec(),
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path_string(path: &Path) -> String {
+    path.to_str().map(|s| s.to_owned()).unwrap_or_default()
+}
+
+/// Filesystem-backed key store. If it's been constructed with a passphrase, all the private keys
+/// (alongside the ack key) are kept encrypted at rest, while the public keys remain in plaintext.
 #[cfg(not(target_arch = "wasm32"))]
 pub struct OnDiskKeys {
     paths: ClientKeysPaths,
+    passphrase: Option<Zeroizing<Vec<u8>>>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl From<ClientKeysPaths> for OnDiskKeys {
     fn from(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys::new(paths)
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl OnDiskKeys {
     pub fn new(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys {
+            paths,
+            passphrase: None,
+        }
+    }
+
+    /// Creates a key store that keeps the private keys encrypted with a cipher derived from the provided passphrase.
+    pub fn new_encrypted(paths: ClientKeysPaths, passphrase: &[u8]) -> Self {
+        OnDiskKeys {
+            paths,
+            passphrase: Some(Zeroizing::new(passphrase.to_vec())),
+        }
+    }
+
+    pub fn new_with_optional_passphrase(paths: ClientKeysPaths, passphrase: Option<&[u8]>) -> Self {
+        match passphrase {
+            Some(passphrase) => Self::new_encrypted(paths, passphrase),
+            None => Self::new(paths),
+        }
+    }
+
+    /// Checks whether the keys stored at the underlying paths have been encrypted at rest.
+    pub fn keys_encrypted_at_rest(&self) -> bool {
+        matches!(self.paths.keys_cipher().try_exists(), Ok(true))
     }
 
     #[doc(hidden)]
     pub fn load_encryption_keypair(&self) -> Result<encryption::KeyPair, OnDiskKeysError> {
+        let cipher = self.load_cipher()?;
         let encryption_paths = self.paths.encryption_key_pair_path();
-        self.load_keypair(encryption_paths, "encryption")
+        self.load_keypair(encryption_paths, "encryption", cipher.as_ref())
     }
 
     #[doc(hidden)]
     pub fn load_identity_keypair(&self) -> Result<identity::KeyPair, OnDiskKeysError> {
+        let cipher = self.load_cipher()?;
         let identity_paths = self.paths.identity_key_pair_path();
-        self.load_keypair(identity_paths, "identity")
+        self.load_keypair(identity_paths, "identity", cipher.as_ref())
+    }
+
+    /// Encrypts the existing plaintext keys using the passphrase this store has been constructed with.
+    /// If the previous attempt has been interrupted, the migration is resumed using the already
+    /// persisted cipher information.
+    pub fn encrypt_existing_keys(&self) -> Result<(), OnDiskKeysError> {
+        let Some(passphrase) = &self.passphrase else {
+            return Err(OnDiskKeysError::NoPassphraseProvided);
+        };
+
+        // make sure to load all the keys before writing anything to the disk
+        let keys = if self.keys_encrypted_at_rest() {
+            let cipher = self.import_cipher(passphrase)?;
+            self.load_keys_with_cipher(Some(&cipher))?
+        } else {
+            self.load_keys_with_cipher(None)?
+        };
+
+        self.store_keys(&keys)
+    }
+
+    fn import_cipher(&self, passphrase: &[u8]) -> Result<StoreCipher, OnDiskKeysError> {
+        let path = self.paths.keys_cipher();
+        let raw = std::fs::read(&path).map_err(|err| OnDiskKeysError::CipherLoadFailure {
+            path: path.clone(),
+            err,
+        })?;
+        let exported: ExportedStoreCipher = serde_json::from_slice(&raw)
+            .map_err(|err| OnDiskKeysError::MalformedCipherInfo { path, err })?;
+
+        Ok(StoreCipher::import_aes256gcm(passphrase, exported)?)
+    }
+
+    fn create_cipher(&self, passphrase: &[u8]) -> Result<StoreCipher, OnDiskKeysError> {
+        let path = self.paths.keys_cipher();
+        let cipher = StoreCipher::new_aes256gcm(passphrase, new_keys_kdf()?)?;
+
+        // the unwrap is fine as serialization of the exported cipher can't fail
+        let exported = serde_json::to_vec_pretty(&cipher.export_aes256gcm()?).unwrap();
+
+        if let Some(parent_dir) = path.parent() {
+            std::fs::create_dir_all(parent_dir).map_err(|err| {
+                OnDiskKeysError::CipherStoreFailure {
+                    path: path.clone(),
+                    err,
+                }
+            })?;
+        }
+        std::fs::write(&path, exported)
+            .map_err(|err| OnDiskKeysError::CipherStoreFailure { path, err })?;
+        Ok(cipher)
+    }
+
+    // retrieves the cipher used for decrypting the existing keys
+    fn load_cipher(&self) -> Result<Option<StoreCipher>, OnDiskKeysError> {
+        match (&self.passphrase, self.keys_encrypted_at_rest()) {
+            (None, false) => Ok(None),
+            (None, true) => Err(OnDiskKeysError::PassphraseRequired {
+                path: self.paths.keys_cipher(),
+            }),
+            (Some(_), false) => Err(OnDiskKeysError::KeysNotEncrypted {
+                path: self.paths.keys_cipher(),
+            }),
+            (Some(passphrase), true) => self.import_cipher(passphrase).map(Some),
+        }
+    }
+
+    // retrieves (or creates) the cipher used for encrypting the keys that are about to be stored
+    fn storage_cipher(&self) -> Result<Option<StoreCipher>, OnDiskKeysError> {
+        match (&self.passphrase, self.keys_encrypted_at_rest()) {
+            (None, false) => Ok(None),
+            // don't mix plaintext and encrypted keys in the same directory
+            (None, true) => Err(OnDiskKeysError::PassphraseRequired {
+                path: self.paths.keys_cipher(),
+            }),
+            (Some(passphrase), false) => self.create_cipher(passphrase).map(Some),
+            (Some(passphrase), true) => self.import_cipher(passphrase).map(Some),
+        }
     }
 
     fn load_key<T: PemStorableKey>(
         &self,
-        path: &std::path::Path,
+        path: &Path,
         name: impl Into<String>,
+        cipher: Option<&StoreCipher>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_key(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
-            err,
+        let name = name.into();
+        let Some(cipher) = cipher else {
+            return nym_pemstore::load_key(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
+                key: name,
+                path: path_string(path),
+                err,
+            });
+        };
+
+        let pem =
+            nym_pemstore::read_pem_file(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
+                key: name.clone(),
+                path: path_string(path),
+                err,
+            })?;
+
+        let key_bytes = if pem.tag == T::pem_type() {
+            // the migration has been interrupted before this key got encrypted
+            log::warn!("the {name} key at {path:?} is not encrypted at rest");
+            Zeroizing::new(pem.contents)
+        } else if pem.tag == encrypted_pem_type::<T>() {
+            let encrypted = decode_encrypted_key(&pem.contents).ok_or_else(|| {
+                OnDiskKeysError::MalformedEncryptedKey {
+                    key: name.clone(),
+                    path: path_string(path),
+                }
+            })?;
+            Zeroizing::new(cipher.decrypt_data(encrypted)?)
+        } else {
+            return Err(OnDiskKeysError::KeyLoadFailure {
+                key: name,
+                path: path_string(path),
+                err: std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!(
+                        "unexpected key pem tag. Got '{}', expected: '{}'",
+                        pem.tag,
+                        encrypted_pem_type::<T>()
+                    ),
+                ),
+            });
+        };
+
+        T::from_bytes(&key_bytes).map_err(|err| OnDiskKeysError::KeyLoadFailure {
+            key: name,
+            path: path_string(path),
+            err: std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()),
         })
     }
 
@@ -110,23 +362,49 @@ impl OnDiskKeys {
         &self,
         paths: KeyPairPath,
         name: impl Into<String>,
+        cipher: Option<&StoreCipher>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_keypair(&paths).map_err(|err| OnDiskKeysError::KeyPairLoadFailure {
-            keys: name.into(),
-            paths,
-            err,
-        })
+        let name = name.into();
+        if cipher.is_none() {
+            return nym_pemstore::load_keypair(&paths).map_err(|err| {
+                OnDiskKeysError::KeyPairLoadFailure {
+                    keys: name,
+                    paths,
+                    err,
+                }
+            });
+        }
+
+        // public keys are never encrypted
+        let private_key =
+            self.load_key(&paths.private_key_path, format!("private {name}"), cipher)?;
+        let public_key = self.load_key(&paths.public_key_path, format!("public {name}"), None)?;
+        Ok(T::from_keys(private_key, public_key))
     }
 
     fn store_key<T: PemStorableKey>(
         &self,
         key: &T,
-        path: &std::path::Path,
+        path: &Path,
         name: impl Into<String>,
+        cipher: Option<&StoreCipher>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_key(key, path).map_err(|err| OnDiskKeysError::KeyStoreFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+        let name = name.into();
+        let res = match cipher {
+            None => nym_pemstore::store_key(key, path),
+            Some(cipher) => {
+                let encrypted = cipher.encrypt_data(key.to_bytes())?;
+                nym_pemstore::write_pem_file(
+                    path,
+                    encode_encrypted_key(encrypted),
+                    &encrypted_pem_type::<T>(),
+                )
+            }
+        };
+
+        res.map_err(|err| OnDiskKeysError::KeyStoreFailure {
+            key: name,
+            path: path_string(path),
             err,
         })
     }
@@ -136,20 +414,42 @@ impl OnDiskKeys {
         keys: &T,
         paths: KeyPairPath,
         name: impl Into<String>,
+        cipher: Option<&StoreCipher>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_keypair(keys, &paths).map_err(|err| {
-            OnDiskKeysError::KeyPairStoreFailure {
-                keys: name.into(),
-                paths,
-                err,
-            }
-        })
+        let name = name.into();
+        if cipher.is_none() {
+            return nym_pemstore::store_keypair(keys, &paths).map_err(|err| {
+                OnDiskKeysError::KeyPairStoreFailure {
+                    keys: name,
+                    paths,
+                    err,
+                }
+            });
+        }
+
+        self.store_key(
+            keys.public_key(),
+            &paths.public_key_path,
+            format!("public {name}"),
+            None,
+        )?;
+        self.store_key(
+            keys.private_key(),
+            &paths.private_key_path,
+            format!("private {name}"),
+            cipher,
+        )
     }
 
-    fn load_keys(&self) -> Result<ClientKeys, OnDiskKeysError> {
-        let identity_keypair = self.load_identity_keypair()?;
-        let encryption_keypair = self.load_encryption_keypair()?;
-        let ack_key: AckKey = self.load_key(self.paths.ack_key(), "ack key")?;
+    fn load_keys_with_cipher(
+        &self,
+        cipher: Option<&StoreCipher>,
+    ) -> Result<ClientKeys, OnDiskKeysError> {
+        let identity_keypair =
+            self.load_keypair(self.paths.identity_key_pair_path(), "identity", cipher)?;
+        let encryption_keypair =
+            self.load_keypair(self.paths.encryption_key_pair_path(), "encryption", cipher)?;
+        let ack_key: AckKey = self.load_key(self.paths.ack_key(), "ack key", cipher)?;
 
         Ok(ClientKeys::from_keys(
             identity_keypair,
@@ -158,7 +458,17 @@ impl OnDiskKeys {
         ))
     }
 
+    fn load_keys(&self) -> Result<ClientKeys, OnDiskKeysError> {
+        let cipher = self.load_cipher()?;
+        self.load_keys_with_cipher(cipher.as_ref())
+    }
+
     fn store_keys(&self, keys: &ClientKeys) -> Result<(), OnDiskKeysError> {
+        // note: if the keys are meant to be encrypted, the cipher information is persisted
+        // before any of the keys
+        let cipher = self.storage_cipher()?;
+        let cipher = cipher.as_ref();
+
         let identity_paths = self.paths.identity_key_pair_path();
         let encryption_paths = self.paths.encryption_key_pair_path();
 
@@ -166,14 +476,21 @@ impl OnDiskKeys {
             keys.identity_keypair.as_ref(),
             identity_paths,
             "identity keys",
+            cipher,
         )?;
         self.store_keypair(
             keys.encryption_keypair.as_ref(),
             encryption_paths,
             "encryption keys",
+            cipher,
         )?;
 
-        self.store_key(keys.ack_key.as_ref(), self.paths.ack_key(), "ack key")?;
+        self.store_key(
+            keys.ack_key.as_ref(),
+            self.paths.ack_key(),
+            "ack key",
+            cipher,
+        )?;
 
         Ok(())
     }
@@ -216,3 +533,74 @@ impl KeyStore for InMemEphemeralKeys {
         Ok(())
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn assert_same_keys(a: &ClientKeys, b: &ClientKeys) {
+        assert_eq!(
+            a.identity_keypair().private_key().to_bytes(),
+            b.identity_keypair().private_key().to_bytes()
+        );
+        assert_eq!(
+            a.encryption_keypair().private_key().to_bytes(),
+            b.encryption_keypair().private_key().to_bytes()
+        );
+        assert_eq!(a.ack_key().to_bytes(), b.ack_key().to_bytes());
+    }
+
+    #[test]
+    fn encrypted_keys_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ClientKeysPaths::new_base(dir.path());
+        let keys = ClientKeys::generate_new(&mut OsRng);
+
+        let store = OnDiskKeys::new_encrypted(paths.clone(), b"foomp");
+        store.store_keys(&keys).unwrap();
+        assert!(store.keys_encrypted_at_rest());
+
+        let loaded = store.load_keys().unwrap();
+        assert_same_keys(&keys, &loaded);
+
+        // private keys are no longer readable as plaintext
+        assert!(
+            nym_pemstore::load_key::<identity::PrivateKey, _>(paths.private_identity_key())
+                .is_err()
+        );
+        assert!(
+            nym_pemstore::load_key::<identity::PublicKey, _>(paths.public_identity_key()).is_ok()
+        );
+
+        assert!(matches!(
+            OnDiskKeys::new(paths.clone()).load_keys(),
+            Err(OnDiskKeysError::PassphraseRequired { .. })
+        ));
+        assert!(OnDiskKeys::new_encrypted(paths, b"bar")
+            .load_keys()
+            .is_err());
+    }
+
+    #[test]
+    fn encrypting_existing_plaintext_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ClientKeysPaths::new_base(dir.path());
+        let keys = ClientKeys::generate_new(&mut OsRng);
+
+        OnDiskKeys::new(paths.clone()).store_keys(&keys).unwrap();
+
+        let encrypted = OnDiskKeys::new_encrypted(paths.clone(), b"foomp");
+        assert!(matches!(
+            encrypted.load_keys(),
+            Err(OnDiskKeysError::KeysNotEncrypted { .. })
+        ));
+
+        encrypted.encrypt_existing_keys().unwrap();
+        assert_same_keys(&keys, &encrypted.load_keys().unwrap());
+
+        // running the migration again is a no-op
+        encrypted.encrypt_existing_keys().unwrap();
+        assert_same_keys(&keys, &encrypted.load_keys().unwrap());
+    }
+}
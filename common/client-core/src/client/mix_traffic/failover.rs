@@ -0,0 +1,24 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::mix_traffic::transceiver::GatewayTransceiver;
+use crate::error::ClientCoreError;
+use async_trait::async_trait;
+use nym_crypto::asymmetric::identity;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxedGatewayFailover = Box<dyn GatewayFailover + Send>;
+
+#[cfg(target_arch = "wasm32")]
+pub type BoxedGatewayFailover = Box<dyn GatewayFailover>;
+
+/// This trait defines the functionality of replacing a gateway that is assumed to be dead
+/// with a new, working, one.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait GatewayFailover {
+    async fn replace_gateway(
+        &mut self,
+        failed_gateway: identity::PublicKey,
+    ) -> Result<Box<dyn GatewayTransceiver + Send>, ClientCoreError>;
+}
@@ -1,6 +1,7 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::mix_traffic::failover::BoxedGatewayFailover;
 use crate::client::mix_traffic::transceiver::GatewayTransceiver;
 use crate::spawn_future;
 use log::*;
@@ -9,11 +10,12 @@ use nym_sphinx::forwarding::packet::MixPacket;
 pub type BatchMixMessageSender = tokio::sync::mpsc::Sender<Vec<MixPacket>>;
 pub type BatchMixMessageReceiver = tokio::sync::mpsc::Receiver<Vec<MixPacket>>;
 
+pub mod failover;
 pub mod transceiver;
 
 // We remind ourselves that 32 x 32kb = 1024kb, a reasonable size for a network buffer.
 pub const MIX_MESSAGE_RECEIVER_BUFFER_SIZE: usize = 32;
-const MAX_FAILURE_COUNT: usize = 100;
+const DEFAULT_MAX_FAILURE_COUNT: usize = 100;
 
 // that's also disgusting.
 pub struct Empty;
@@ -26,6 +28,12 @@ pub struct MixTrafficController {
     // TODO: this is temporary work-around.
     // in long run `gateway_client` will be moved away from `MixTrafficController` anyway.
     consecutive_gateway_failure_count: usize,
+
+    /// Number of consecutive failures after which the gateway is assumed to be dead.
+    max_failure_count: usize,
+
+    /// If specified, used for replacing the gateway once it's assumed to be dead.
+    failover: Option<BoxedGatewayFailover>,
 }
 
 impl MixTrafficController {
@@ -40,6 +48,8 @@ impl MixTrafficController {
                 gateway_transceiver: Box::new(gateway_transceiver),
                 mix_rx: message_receiver,
                 consecutive_gateway_failure_count: 0,
+                max_failure_count: DEFAULT_MAX_FAILURE_COUNT,
+                failover: None,
             },
             message_sender,
         )
@@ -55,11 +65,50 @@ impl MixTrafficController {
                 gateway_transceiver,
                 mix_rx: message_receiver,
                 consecutive_gateway_failure_count: 0,
+                max_failure_count: DEFAULT_MAX_FAILURE_COUNT,
+                failover: None,
             },
             message_sender,
         )
     }
 
+    #[must_use]
+    pub fn with_max_failure_count(mut self, max_failure_count: usize) -> Self {
+        self.max_failure_count = max_failure_count;
+        self
+    }
+
+    #[must_use]
+    pub fn with_failover(mut self, failover: BoxedGatewayFailover) -> Self {
+        self.failover = Some(failover);
+        self
+    }
+
+    async fn on_gateway_failure(&mut self) {
+        let failed_gateway = self.gateway_transceiver.gateway_identity();
+        let max_failures = self.max_failure_count;
+
+        let Some(failover) = self.failover.as_mut() else {
+            // todo: in the future this should initiate a 'graceful' shutdown
+            panic!("failed to send sphinx packet to the gateway {max_failures} times in a row - assuming the gateway is dead. Can't do anything about it without failover enabled :(")
+        };
+
+        warn!("failed to send sphinx packet to gateway {failed_gateway} {max_failures} times in a row - attempting to switch to a different gateway");
+        match failover.replace_gateway(failed_gateway).await {
+            Ok(new_transceiver) => {
+                info!(
+                    "switched from gateway {failed_gateway} to {}",
+                    new_transceiver.gateway_identity()
+                );
+                self.gateway_transceiver = new_transceiver;
+                self.consecutive_gateway_failure_count = 0;
+            }
+            Err(err) => {
+                panic!("gateway {failed_gateway} is assumed to be dead and we failed to switch to a different one: {err}")
+            }
+        }
+    }
+
     async fn on_messages(&mut self, mut mix_packets: Vec<MixPacket>) {
         debug_assert!(!mix_packets.is_empty());
 
@@ -76,10 +125,8 @@ impl MixTrafficController {
             Err(err) => {
                 error!("Failed to send sphinx packet(s) to the gateway: {err}");
                 self.consecutive_gateway_failure_count += 1;
-                if self.consecutive_gateway_failure_count == MAX_FAILURE_COUNT {
-                    // todo: in the future this should initiate a 'graceful' shutdown or try
-                    // to reconnect?
-                    panic!("failed to send sphinx packet to the gateway {MAX_FAILURE_COUNT} times in a row - assuming the gateway is dead. Can't do anything about it yet :(")
+                if self.consecutive_gateway_failure_count >= self.max_failure_count {
+                    self.on_gateway_failure().await
                 }
             }
             Ok(_) => {
@@ -115,3 +162,218 @@ impl MixTrafficController {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mix_traffic::failover::GatewayFailover;
+    use crate::client::mix_traffic::transceiver::{
+        erase_err, ErasedGatewayError, GatewayReceiver, GatewaySender, MockGatewayError,
+    };
+    use crate::error::ClientCoreError;
+    use async_trait::async_trait;
+    use nym_crypto::asymmetric::identity;
+    use nym_sphinx::addressing::nodes::NymNodeRoutingAddress;
+    use nym_sphinx::params::{PacketSize, PacketType};
+    use nym_sphinx::NymPacket;
+    use rand::rngs::OsRng;
+    use std::collections::VecDeque;
+    use std::net::SocketAddr;
+    use std::os::raw::c_int as RawFd;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    // identities of gateways used for sending each of the packets
+    type SentPackets = Arc<Mutex<Vec<identity::PublicKey>>>;
+
+    struct TestGateway {
+        identity: identity::PublicKey,
+        broken: Arc<AtomicBool>,
+        sent: SentPackets,
+    }
+
+    impl TestGateway {
+        fn new(sent: &SentPackets) -> Self {
+            TestGateway {
+                identity: *identity::KeyPair::new(&mut OsRng).public_key(),
+                broken: Arc::new(AtomicBool::new(false)),
+                sent: Arc::clone(sent),
+            }
+        }
+
+        fn broken(sent: &SentPackets) -> Self {
+            let gateway = TestGateway::new(sent);
+            gateway.broken.store(true, Ordering::SeqCst);
+            gateway
+        }
+    }
+
+    impl GatewayReceiver for TestGateway {}
+
+    #[async_trait]
+    impl GatewaySender for TestGateway {
+        async fn send_mix_packet(&mut self, _: MixPacket) -> Result<(), ErasedGatewayError> {
+            if self.broken.load(Ordering::SeqCst) {
+                return Err(erase_err(MockGatewayError));
+            }
+            self.sent.lock().unwrap().push(self.identity);
+            Ok(())
+        }
+    }
+
+    impl GatewayTransceiver for TestGateway {
+        fn gateway_identity(&self) -> identity::PublicKey {
+            self.identity
+        }
+        fn ws_fd(&self) -> Option<RawFd> {
+            None
+        }
+    }
+
+    struct TestFailover {
+        replacements: VecDeque<TestGateway>,
+        failed: Arc<Mutex<Vec<identity::PublicKey>>>,
+    }
+
+    #[async_trait]
+    impl GatewayFailover for TestFailover {
+        async fn replace_gateway(
+            &mut self,
+            failed_gateway: identity::PublicKey,
+        ) -> Result<Box<dyn GatewayTransceiver + Send>, ClientCoreError> {
+            self.failed.lock().unwrap().push(failed_gateway);
+            match self.replacements.pop_front() {
+                Some(replacement) => Ok(Box::new(replacement)),
+                None => Err(ClientCoreError::GatewayFailoverFailure {
+                    failed_gateway: failed_gateway.to_base58_string(),
+                    attempts: 0,
+                }),
+            }
+        }
+    }
+
+    fn mix_packet() -> MixPacket {
+        let next_hop = NymNodeRoutingAddress::from("1.2.3.4:1789".parse::<SocketAddr>().unwrap());
+        let packet =
+            NymPacket::sphinx_from_bytes(&vec![0; PacketSize::RegularPacket.size()]).unwrap();
+        MixPacket::new(next_hop, packet, PacketType::Mix)
+    }
+
+    fn controller_with_failover(
+        gateway: TestGateway,
+        replacements: Vec<TestGateway>,
+        max_failure_count: usize,
+    ) -> (MixTrafficController, Arc<Mutex<Vec<identity::PublicKey>>>) {
+        let failed = Arc::new(Mutex::new(Vec::new()));
+        let failover = TestFailover {
+            replacements: replacements.into(),
+            failed: Arc::clone(&failed),
+        };
+        let (controller, _) = MixTrafficController::new(gateway);
+        let controller = controller
+            .with_max_failure_count(max_failure_count)
+            .with_failover(Box::new(failover));
+        (controller, failed)
+    }
+
+    #[tokio::test]
+    async fn gateway_is_kept_until_reaching_failure_threshold() {
+        let sent = SentPackets::default();
+        let gateway = TestGateway::broken(&sent);
+        let broken = Arc::clone(&gateway.broken);
+        let identity = gateway.identity;
+        let replacement = TestGateway::new(&sent);
+
+        let (mut controller, failed) = controller_with_failover(gateway, vec![replacement], 3);
+
+        controller.on_messages(vec![mix_packet()]).await;
+        controller
+            .on_messages(vec![mix_packet(), mix_packet()])
+            .await;
+        assert!(failed.lock().unwrap().is_empty());
+        assert_eq!(controller.gateway_transceiver.gateway_identity(), identity);
+
+        // a successful send resets the failure count
+        broken.store(false, Ordering::SeqCst);
+        controller.on_messages(vec![mix_packet()]).await;
+        broken.store(true, Ordering::SeqCst);
+        controller.on_messages(vec![mix_packet()]).await;
+        controller.on_messages(vec![mix_packet()]).await;
+
+        assert!(failed.lock().unwrap().is_empty());
+        assert_eq!(controller.gateway_transceiver.gateway_identity(), identity);
+        assert_eq!(*sent.lock().unwrap(), vec![identity]);
+    }
+
+    #[tokio::test]
+    async fn dead_gateway_is_replaced() {
+        let sent = SentPackets::default();
+        let gateway = TestGateway::broken(&sent);
+        let dead_identity = gateway.identity;
+        let replacement = TestGateway::new(&sent);
+        let replacement_identity = replacement.identity;
+
+        let (mut controller, failed) = controller_with_failover(gateway, vec![replacement], 2);
+
+        controller.on_messages(vec![mix_packet()]).await;
+        controller.on_messages(vec![mix_packet()]).await;
+        assert_eq!(*failed.lock().unwrap(), vec![dead_identity]);
+        assert_eq!(
+            controller.gateway_transceiver.gateway_identity(),
+            replacement_identity
+        );
+        assert_eq!(controller.consecutive_gateway_failure_count, 0);
+
+        controller
+            .on_messages(vec![mix_packet(), mix_packet()])
+            .await;
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![replacement_identity, replacement_identity]
+        );
+    }
+
+    #[tokio::test]
+    async fn replacement_gateway_can_also_be_replaced() {
+        let sent = SentPackets::default();
+        let gateway = TestGateway::broken(&sent);
+        let first_identity = gateway.identity;
+        let replacement = TestGateway::broken(&sent);
+        let second_identity = replacement.identity;
+        let another_replacement = TestGateway::new(&sent);
+        let third_identity = another_replacement.identity;
+
+        let (mut controller, failed) =
+            controller_with_failover(gateway, vec![replacement, another_replacement], 1);
+
+        controller.on_messages(vec![mix_packet()]).await;
+        controller.on_messages(vec![mix_packet()]).await;
+        controller.on_messages(vec![mix_packet()]).await;
+
+        assert_eq!(
+            *failed.lock().unwrap(),
+            vec![first_identity, second_identity]
+        );
+        assert_eq!(*sent.lock().unwrap(), vec![third_identity]);
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn dead_gateway_without_failover_is_fatal() {
+        let sent = SentPackets::default();
+        let (controller, _) = MixTrafficController::new(TestGateway::broken(&sent));
+        let mut controller = controller.with_max_failure_count(1);
+
+        controller.on_messages(vec![mix_packet()]).await;
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn failed_failover_is_fatal() {
+        let sent = SentPackets::default();
+        let (mut controller, _) =
+            controller_with_failover(TestGateway::broken(&sent), Vec::new(), 1);
+
+        controller.on_messages(vec![mix_packet()]).await;
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use nym_metrics::{inc, inc_by};
+use nym_metrics::{inc, inc_by, set_float};
 use si_scale::helpers::bibytes2;
 
 // Metrics server
@@ -24,11 +24,9 @@ use hyper_util::rt::TokioIo;
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::convert::Infallible;
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
-#[cfg(feature = "metrics-server")]
-use std::net::SocketAddr;
-#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use tokio::net::TcpListener;
 
+use crate::config;
 use crate::spawn_future;
 
 // Time interval between reporting packet statistics
@@ -69,6 +67,10 @@ struct PacketStatistics {
     retransmissions_queued: u64,
     reply_surbs_queued: u64,
     additional_reply_surbs_queued: u64,
+
+    // Incomplete messages whose reconstruction has been abandoned
+    incomplete_messages_abandoned: u64,
+    abandoned_fragments: u64,
 }
 
 impl PacketStatistics {
@@ -132,6 +134,12 @@ impl PacketStatistics {
                 self.additional_reply_surbs_queued += 1;
                 inc!("additional_reply_surbs_queued");
             }
+            PacketStatisticsEvent::IncompleteMessageAbandoned(received_fragments) => {
+                self.incomplete_messages_abandoned += 1;
+                self.abandoned_fragments += received_fragments as u64;
+                inc!("incomplete_messages_abandoned");
+                inc_by!("abandoned_fragments", received_fragments);
+            }
         }
     }
 
@@ -145,12 +153,13 @@ impl PacketStatistics {
                 self.retransmissions_queued,
             ),
             format!(
-                "packets received: {}, (real: {}, cover: {}, acks: {}, acks for cover: {})",
+                "packets received: {}, (real: {}, cover: {}, acks: {}, acks for cover: {}, abandoned incomplete messages: {})",
                 self.real_packets_received + self.cover_packets_received,
                 self.real_packets_received,
                 self.cover_packets_received,
                 self.real_acks_received,
                 self.cover_acks_received,
+                self.incomplete_messages_abandoned,
             ),
         )
     }
@@ -185,6 +194,10 @@ impl std::ops::Sub for PacketStatistics {
             reply_surbs_queued: self.reply_surbs_queued - rhs.reply_surbs_queued,
             additional_reply_surbs_queued: self.additional_reply_surbs_queued
                 - rhs.additional_reply_surbs_queued,
+
+            incomplete_messages_abandoned: self.incomplete_messages_abandoned
+                - rhs.incomplete_messages_abandoned,
+            abandoned_fragments: self.abandoned_fragments - rhs.abandoned_fragments,
         }
     }
 }
@@ -306,6 +319,37 @@ impl std::ops::Div<f64> for PacketRates {
 }
 
 impl PacketRates {
+    // expose the latest rates as gauges alongside the raw counters
+    fn publish(&self) {
+        set_float!("real_packets_sent_rate", self.real_packets_sent);
+        set_float!("real_packets_sent_size_rate", self.real_packets_sent_size);
+        set_float!("cover_packets_sent_rate", self.cover_packets_sent);
+        set_float!("cover_packets_sent_size_rate", self.cover_packets_sent_size);
+
+        set_float!("real_packets_received_rate", self.real_packets_received);
+        set_float!(
+            "real_packets_received_size_rate",
+            self.real_packets_received_size
+        );
+        set_float!("cover_packets_received_rate", self.cover_packets_received);
+        set_float!(
+            "cover_packets_received_size_rate",
+            self.cover_packets_received_size
+        );
+
+        set_float!("total_acks_received_rate", self.total_acks_received);
+        set_float!("real_acks_received_rate", self.real_acks_received);
+        set_float!("cover_acks_received_rate", self.cover_acks_received);
+
+        set_float!("real_packets_queued_rate", self.real_packets_queued);
+        set_float!("retransmissions_queued_rate", self.retransmissions_queued);
+        set_float!("reply_surbs_queued_rate", self.reply_surbs_queued);
+        set_float!(
+            "additional_reply_surbs_queued_rate",
+            self.additional_reply_surbs_queued
+        );
+    }
+
     fn summary(&self) -> String {
         format!(
             "down: {}/s, up: {}/s (cover down: {}/s, cover up: {}/s)",
@@ -355,6 +399,10 @@ pub(crate) enum PacketStatisticsEvent {
     RetransmissionQueued,
     ReplySurbRequestQueued,
     AdditionalReplySurbRequestQueued,
+
+    // Reconstruction of an incomplete message has been abandoned after receiving the given number
+    // of its fragments, either due to inactivity or running out of the buffering space
+    IncompleteMessageAbandoned(usize),
 }
 
 type PacketStatisticsReceiver = tokio::sync::mpsc::UnboundedReceiver<PacketStatisticsEvent>;
@@ -389,10 +437,13 @@ pub(crate) struct PacketStatisticsControl {
 
     // Keep previous rates so that we can detect notable events
     rates: VecDeque<(Instant, PacketRates)>,
+
+    // Configuration of the local server exposing the metrics
+    metrics_config: config::Metrics,
 }
 
 impl PacketStatisticsControl {
-    pub(crate) fn new() -> (Self, PacketStatisticsReporter) {
+    pub(crate) fn new(metrics_config: config::Metrics) -> (Self, PacketStatisticsReporter) {
         let (stats_tx, stats_rx) = tokio::sync::mpsc::unbounded_channel();
 
         (
@@ -401,6 +452,7 @@ impl PacketStatisticsControl {
                 stats: PacketStatistics::default(),
                 history: VecDeque::new(),
                 rates: VecDeque::new(),
+                metrics_config,
             },
             PacketStatisticsReporter::new(stats_tx),
         )
@@ -442,6 +494,7 @@ impl PacketStatisticsControl {
     fn update_rates(&mut self) {
         // Update latest
         if let Some(rates) = self.compute_rates() {
+            rates.publish();
             self.rates.push_back((Instant::now(), rates));
         }
 
@@ -509,30 +562,27 @@ impl PacketStatisticsControl {
 
         cfg_if::cfg_if! {
             if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
-                log::warn!("Metrics server is not supported on wasm32-unknown-unknown");
+                if self.metrics_config.enable_server {
+                    log::warn!("Metrics server is not supported on wasm32-unknown-unknown");
+                }
                 let listener: Option<WasmEmpty> = None;
-            } else if #[cfg(feature = "metrics-server")] {
-                let mut metrics_port = 18000;
-                let listener: Option<TcpListener>;
-                loop {
-                    let addr = SocketAddr::from(([0, 0, 0, 0], metrics_port));
-                    match TcpListener::bind(addr).await {
-                        Ok(l) => {
-                            log::info!("###############################");
-                            log::info!("Metrics endpoint is at: {:?}", l.local_addr());
-                            log::info!("###############################");
-                            listener = Some(l);
-                            break;
-                        },
+            } else {
+                let listener = if self.metrics_config.enable_server {
+                    let address = self.metrics_config.server_address;
+                    match TcpListener::bind(address).await {
+                        Ok(listener) => {
+                            log::info!("Metrics endpoint is at: http://{address}/metrics");
+                            Some(listener)
+                        }
                         Err(err) => {
-                            log::warn!("Failed to bind metrics server: {:?}", err);
-                            metrics_port += 1;
+                            log::error!("Failed to bind metrics server to {address}: {err}");
+                            None
                         }
-                    };
-                }
-            } else {
-                log::info!("Metrics server is disabled!");
-                let listener: Option<TcpListener> = None;
+                    }
+                } else {
+                    log::debug!("Metrics server is disabled");
+                    None
+                };
             }
         }
 
@@ -605,11 +655,23 @@ impl PacketStatisticsControl {
 
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 async fn serve_metrics(
-    _: Request<hyper::body::Incoming>,
+    request: Request<hyper::body::Incoming>,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
+    use hyper::{header, StatusCode};
     use nym_metrics::metrics;
 
-    Ok(Response::new(Full::new(Bytes::from(metrics!()))))
+    let response = if request.uri().path() == "/metrics" {
+        Response::builder()
+            .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+            .body(Full::new(Bytes::from(metrics!())))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::new()))
+    };
+
+    // the builder can only fail on invalid header values
+    Ok(response.unwrap_or_default())
 }
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
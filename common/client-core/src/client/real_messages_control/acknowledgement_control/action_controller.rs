@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::PendingAcknowledgement;
+use crate::client::helpers::{get_time_now, new_interval_stream, Instant};
 use crate::client::real_messages_control::acknowledgement_control::RetransmissionRequestSender;
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
 use nym_nonexhaustive_delayqueue::{Expired, NonExhaustiveDelayQueue, QueueKey};
 use nym_sphinx::chunking::fragment::FragmentIdentifier;
+use nym_sphinx::params::reconstruction::DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE;
 use nym_sphinx::Delay as SphinxDelay;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -19,6 +21,27 @@ pub(crate) type AckActionReceiver = mpsc::UnboundedReceiver<Action>;
 // The actual data being sent off as well as potential key to the delay queue
 type PendingAckEntry = (Arc<PendingAcknowledgement>, Option<QueueKey>);
 
+// if we haven't received any acks for an erasure coded set for that long, the recipient has most likely
+// abandoned its reconstruction, so there's no point in keeping track of it anymore
+const MAXIMUM_ERASURE_CODED_SET_ACK_AGE: Duration = DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE;
+
+const ERASURE_CODED_SETS_INSPECTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Acknowledgement progress of an erasure coded set of fragments.
+struct ErasureCodedSetAcks {
+    /// Number of fragments the recipient requires in order to recover the set.
+    required: usize,
+
+    /// Number of fragments of the set that got acknowledged so far.
+    acknowledged: usize,
+
+    /// All fragments of the set that we have sent.
+    fragments: Vec<FragmentIdentifier>,
+
+    /// Time at which we last made any progress on the set, i.e. inserted or got an ack for its fragment.
+    last_updated: Instant,
+}
+
 // we can either:
 // - have a completely new set of packets we just sent and need to create entries for
 // - received an ack so we want to remove an entry
@@ -88,6 +111,11 @@ pub(super) struct ActionController {
     /// key to its `AckDelayQueue` entry if it was started.
     pending_acks_data: HashMap<FragmentIdentifier, PendingAckEntry>,
 
+    /// Acknowledgement progress of erasure coded sets that are still awaiting acks, keyed by their set id.
+    /// Once sufficient number of fragments of a set got acknowledged, the remaining ones are no longer
+    /// retransmitted as the recipient is able to recover the set without them.
+    erasure_coded_sets: HashMap<i32, ErasureCodedSetAcks>,
+
     // This structure ensures that we will EITHER handle expired timer or a received action and NEVER both
     // at the same time hence getting rid of one possible race condition that we suffered from in the
     // previous version.
@@ -111,6 +139,7 @@ impl ActionController {
         ActionController {
             config,
             pending_acks_data: HashMap::new(),
+            erasure_coded_sets: HashMap::new(),
             pending_acks_timers: NonExhaustiveDelayQueue::new(),
             incoming_actions,
             retransmission_sender,
@@ -122,6 +151,20 @@ impl ActionController {
             let frag_id = pending_ack.message_chunk.fragment_identifier();
             trace!("{} is inserted", frag_id);
 
+            if let Some(data_fragments) = pending_ack.message_chunk.data_fragments() {
+                let set_acks = self
+                    .erasure_coded_sets
+                    .entry(frag_id.set_id())
+                    .or_insert_with(|| ErasureCodedSetAcks {
+                        required: data_fragments as usize,
+                        acknowledged: 0,
+                        fragments: Vec::new(),
+                        last_updated: get_time_now(),
+                    });
+                set_acks.fragments.push(frag_id);
+                set_acks.last_updated = get_time_now();
+            }
+
             if self
                 .pending_acks_data
                 .insert(frag_id, (Arc::new(pending_ack), None))
@@ -162,6 +205,59 @@ impl ActionController {
         }
     }
 
+    // once the recipient has received enough fragments of an erasure coded set to recover it,
+    // there's no point in waiting for (and retransmitting) the remaining ones
+    fn handle_erasure_coded_ack(&mut self, frag_id: FragmentIdentifier) {
+        let Some(set_acks) = self.erasure_coded_sets.get_mut(&frag_id.set_id()) else {
+            return;
+        };
+        set_acks.acknowledged += 1;
+        set_acks.last_updated = get_time_now();
+        if set_acks.acknowledged < set_acks.required
+            && set_acks.acknowledged < set_acks.fragments.len()
+        {
+            return;
+        }
+
+        let Some(set_acks) = self.erasure_coded_sets.remove(&frag_id.set_id()) else {
+            return;
+        };
+        for remaining in set_acks.fragments {
+            if let Some((_, queue_key)) = self.pending_acks_data.remove(&remaining) {
+                trace!("{remaining} is no longer required for recovering its set");
+                if let Some(queue_key) = queue_key {
+                    self.pending_acks_timers.remove(&queue_key);
+                }
+            }
+        }
+    }
+
+    // gets rid of erasure coded sets that are never going to collect sufficient number of acks,
+    // for example because their recipient went offline. Their fragments (if any are still pending)
+    // are going to keep getting retransmitted like any other fragment.
+    fn remove_stale_erasure_coded_sets(&mut self) {
+        let now = get_time_now();
+        let pending_acks_data = &self.pending_acks_data;
+
+        self.erasure_coded_sets.retain(|set_id, set_acks| {
+            let still_pending = set_acks
+                .fragments
+                .iter()
+                .any(|frag_id| pending_acks_data.contains_key(frag_id));
+            let stale =
+                now.duration_since(set_acks.last_updated) > MAXIMUM_ERASURE_CODED_SET_ACK_AGE;
+
+            if !still_pending || stale {
+                debug!(
+                    "erasure coded set {set_id} has only received {}/{} acks and is no longer being tracked",
+                    set_acks.acknowledged, set_acks.required
+                );
+                return false;
+            }
+            true
+        });
+    }
+
     fn handle_remove(&mut self, frag_id: FragmentIdentifier) {
         trace!("{} is getting removed", frag_id);
 
@@ -172,7 +268,11 @@ impl ActionController {
                     frag_id
                 );
             }
-            Some((_, queue_key)) => {
+            Some((pending_ack, queue_key)) => {
+                if pending_ack.message_chunk.is_erasure_coded() {
+                    self.handle_erasure_coded_ack(frag_id);
+                }
+
                 if let Some(queue_key) = queue_key {
                     // there are no possible checks here, we must GUARANTEE that we NEVER try
                     // to remove an entry that doesn't exist (and we MUST GUARANTEE that
@@ -265,6 +365,9 @@ impl ActionController {
     pub(super) async fn run_with_shutdown(&mut self, mut shutdown: nym_task::TaskClient) {
         debug!("Started ActionController with graceful shutdown support");
 
+        let mut erasure_coded_sets_inspection =
+            new_interval_stream(ERASURE_CODED_SETS_INSPECTION_INTERVAL);
+
         loop {
             tokio::select! {
                 action = self.incoming_actions.next() => match action {
@@ -283,6 +386,9 @@ impl ActionController {
                         break;
                     }
                 },
+                _ = erasure_coded_sets_inspection.next() => {
+                    self.remove_stale_erasure_coded_sets()
+                },
                 _ = shutdown.recv_with_delay() => {
                     log::trace!("ActionController: Received shutdown");
                     break;
@@ -7,15 +7,19 @@ use crate::client::real_messages_control::real_traffic_stream::{
 };
 use crate::client::real_messages_control::{AckActionSender, Action};
 use crate::client::replies::reply_storage::{ReceivedReplySurbsMap, SentReplyKeys, UsedSenderTags};
+use crate::client::self_address::SelfAddress;
 use crate::client::topology_control::{TopologyAccessor, TopologyReadPermit};
+use crate::config;
 use log::{debug, error, info, trace, warn};
 use nym_sphinx::acknowledgements::AckKey;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::{AnonymousSenderTag, RepliableMessage, ReplyMessage};
 use nym_sphinx::anonymous_replies::{ReplySurb, SurbEncryptionKey};
 use nym_sphinx::chunking::fragment::{Fragment, FragmentIdentifier};
-use nym_sphinx::message::NymMessage;
-use nym_sphinx::params::{PacketSize, PacketType, DEFAULT_NUM_MIX_HOPS};
+use nym_sphinx::message::{NymMessage, SerializedMessage};
+use nym_sphinx::params::{
+    MessageCompressionSettings, PacketSize, PacketType, DEFAULT_NUM_MIX_HOPS,
+};
 use nym_sphinx::preparer::{MessagePreparer, PreparedFragment};
 use nym_sphinx::Delay;
 use nym_task::connections::TransmissionLane;
@@ -89,7 +93,7 @@ pub(crate) struct Config {
 
     /// Address of this client which also represent an address to which all acknowledgements
     /// and surb-based are going to be sent.
-    sender_address: Recipient,
+    sender_address: SelfAddress,
 
     /// Average delay a data packet is going to get delay at a single mixnode.
     average_packet_delay: Duration,
@@ -106,12 +110,23 @@ pub(crate) struct Config {
 
     /// Optional secondary predefined packet size used for the encapsulated messages.
     secondary_packet_size: Option<PacketSize>,
+
+    /// Optional compression applied to the messages before they get split into packets.
+    /// Replies are only compressed if their recipient has used compression itself.
+    message_compression: Option<MessageCompressionSettings>,
+
+    /// Specifies whether non-reply messages should get compressed, i.e. whether
+    /// all of our recipients are known to understand compressed messages.
+    compress_non_reply_messages: bool,
+
+    /// Redundancy of erasure coded multi-fragment messages sent on particular lanes.
+    erasure_coding: config::ErasureCoding,
 }
 
 impl Config {
     pub fn new(
         ack_key: Arc<AckKey>,
-        sender_address: Recipient,
+        sender_address: SelfAddress,
         average_packet_delay: Duration,
         average_ack_delay: Duration,
     ) -> Self {
@@ -123,6 +138,9 @@ impl Config {
             num_mix_hops: DEFAULT_NUM_MIX_HOPS,
             primary_packet_size: PacketSize::default(),
             secondary_packet_size: None,
+            message_compression: None,
+            compress_non_reply_messages: false,
+            erasure_coding: Default::default(),
         }
     }
 
@@ -144,6 +162,50 @@ impl Config {
         self.secondary_packet_size = packet_size;
         self
     }
+
+    /// Allows compressing the messages before they get split into packets.
+    pub fn with_message_compression(
+        mut self,
+        message_compression: Option<MessageCompressionSettings>,
+    ) -> Self {
+        self.message_compression = message_compression;
+        self
+    }
+
+    /// Allows compressing the non-reply messages without knowing whether their recipients
+    /// are going to understand them.
+    pub fn with_compressed_non_reply_messages(mut self, compress_non_reply_messages: bool) -> Self {
+        self.compress_non_reply_messages = compress_non_reply_messages;
+        self
+    }
+
+    fn non_reply_message_compression(&self) -> Option<MessageCompressionSettings> {
+        self.message_compression
+            .filter(|_| self.compress_non_reply_messages)
+    }
+
+    fn reply_message_compression(&self, compress: bool) -> Option<MessageCompressionSettings> {
+        self.message_compression.filter(|_| compress)
+    }
+
+    /// Allows erasure coding the multi-fragment messages sent on the specified lanes.
+    pub fn with_erasure_coding(mut self, erasure_coding: config::ErasureCoding) -> Self {
+        self.erasure_coding = erasure_coding;
+        self
+    }
+
+    /// Returns the redundancy of erasure coded messages sent on the provided lane, if any.
+    /// Retransmissions and reply SURB requests are never erasure coded.
+    fn erasure_coding_redundancy(&self, lane: &TransmissionLane) -> Option<f64> {
+        match lane {
+            TransmissionLane::General => self.erasure_coding.general_lane_redundancy,
+            TransmissionLane::ConnectionId(_) => self.erasure_coding.connection_lanes_redundancy,
+            TransmissionLane::AdditionalReplySurbs => {
+                self.erasure_coding.additional_reply_surbs_lane_redundancy
+            }
+            TransmissionLane::ReplySurbRequest | TransmissionLane::Retransmission => None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -176,7 +238,7 @@ where
     {
         let message_preparer = MessagePreparer::new(
             rng,
-            config.sender_address,
+            config.sender_address.current(),
             config.average_packet_delay,
             config.average_ack_delay,
         )
@@ -207,11 +269,20 @@ where
         }
     }
 
+    /// Makes sure the packets are going to be prepared with our current address
+    /// in case it has changed since, for example due to switching to a different gateway.
+    fn sync_sender_address(&mut self) {
+        if let Some(new_address) = self.config.sender_address.updated() {
+            debug!("our address has changed to {new_address}");
+            self.message_preparer.set_sender_address(new_address)
+        }
+    }
+
     fn get_topology<'a>(
         &self,
         permit: &'a TopologyReadPermit<'a>,
     ) -> Result<&'a NymTopology, PreparationError> {
-        match permit.try_get_valid_topology_ref(&self.config.sender_address, None) {
+        match permit.try_get_valid_topology_ref(&self.config.sender_address.current(), None) {
             Ok(topology_ref) => Ok(topology_ref),
             Err(err) => {
                 warn!("Could not process the packet - the network topology is invalid - {err}");
@@ -220,16 +291,17 @@ where
         }
     }
 
-    fn optimal_packet_size(&self, msg: &NymMessage) -> PacketSize {
+    fn optimal_packet_size(&self, msg: &SerializedMessage) -> PacketSize {
         // if secondary packet was never set, then it's obvious we have to use the primary packet
         let Some(secondary_packet) = self.config.secondary_packet_size else {
             trace!("only primary packet size is available");
             return self.config.primary_packet_size;
         };
 
-        let primary_count =
-            msg.required_packets(self.config.primary_packet_size, self.config.num_mix_hops);
-        let secondary_count = msg.required_packets(secondary_packet, self.config.num_mix_hops);
+        // note: the message has already been compressed (if applicable),
+        // so this is based on the actual number of bytes that are going to be sent
+        let primary_count = msg.required_packets(self.config.primary_packet_size);
+        let secondary_count = msg.required_packets(secondary_packet);
 
         trace!("This message would require: {primary_count} primary packets or {secondary_count} secondary packets...");
         // if there would be no benefit in using the secondary packet - use the primary (duh)
@@ -246,6 +318,7 @@ where
         &mut self,
         amount: usize,
     ) -> Result<(Vec<ReplySurb>, Vec<SurbEncryptionKey>), PreparationError> {
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
@@ -268,13 +341,13 @@ where
         reply_surb: ReplySurb,
         is_extra_surb_request: bool,
     ) -> Result<(), SurbWrappedPreparationError> {
-        let msg = NymMessage::new_reply(message);
+        let msg = NymMessage::new_reply(message).serialize(None);
         let packet_size = self.optimal_packet_size(&msg);
         debug!("Using {packet_size} packets for {msg}");
 
         let mut fragment = self
             .message_preparer
-            .pad_and_split_message(msg, packet_size);
+            .pad_and_split_serialized_message(msg, packet_size);
         if fragment.len() > 1 {
             // well, it's not a single surb message
             return Err(SurbWrappedPreparationError {
@@ -319,19 +392,26 @@ where
         debug!("requesting {amount} reply SURBs from {from}");
 
         let surbs_request =
-            ReplyMessage::new_surb_request_message(self.config.sender_address, amount);
+            ReplyMessage::new_surb_request_message(self.config.sender_address.current(), amount);
         self.try_send_single_surb_message(from, surbs_request, reply_surb, true)
             .await
     }
 
     // // TODO: this will require additional argument to make it use different variant of `ReplyMessage`
-    pub(crate) fn split_reply_message(&mut self, message: Vec<u8>) -> Vec<Fragment> {
-        let msg = NymMessage::new_reply(ReplyMessage::new_data_message(message));
+    /// Note: `compress` must only be set if the recipient is known to understand compressed messages.
+    pub(crate) fn split_reply_message(
+        &mut self,
+        message: Vec<u8>,
+        compress: bool,
+    ) -> Vec<Fragment> {
+        let compression = self.config.reply_message_compression(compress);
+        let msg =
+            NymMessage::new_reply(ReplyMessage::new_data_message(message)).serialize(compression);
         let packet_size = self.optimal_packet_size(&msg);
         debug!("Using {packet_size} packets for {msg}");
 
         self.message_preparer
-            .pad_and_split_message(msg, packet_size)
+            .pad_and_split_serialized_message(msg, packet_size)
     }
 
     pub(crate) async fn send_retransmission_reply_chunks(
@@ -438,18 +518,34 @@ where
         debug_assert!(!matches!(message, NymMessage::Reply(_)));
 
         // TODO2: it's really annoying we have to get topology permit again here due to borrow-checker
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
+        let message = message.serialize(self.config.non_reply_message_compression());
         let packet_size = if packet_type == PacketType::Outfox {
             PacketSize::OutfoxRegularPacket
         } else {
             self.optimal_packet_size(&message)
         };
         debug!("Using {packet_size} packets for {message}");
-        let fragments = self
-            .message_preparer
-            .pad_and_split_message(message, packet_size);
+
+        // there's no point in erasure coding messages that fit in a single packet
+        let redundancy = self
+            .config
+            .erasure_coding_redundancy(&lane)
+            .filter(|_| message.required_packets(packet_size) > 1);
+
+        let fragments = if let Some(redundancy) = redundancy {
+            self.message_preparer.pad_and_split_erasure_coded_message(
+                message,
+                packet_size,
+                redundancy,
+            )
+        } else {
+            self.message_preparer
+                .pad_and_split_serialized_message(message, packet_size)
+        };
 
         let mut pending_acks = Vec::with_capacity(fragments.len());
         let mut real_messages = Vec::with_capacity(fragments.len());
@@ -551,6 +647,7 @@ where
         mix_hops: Option<u8>,
     ) -> Result<PreparedFragment, PreparationError> {
         debug!("Sending single chunk with packet type {packet_type}");
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
@@ -582,6 +679,7 @@ where
             reply_surbs.len()
         );
 
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = match self.get_topology(&topology_permit) {
             Ok(topology) => topology,
@@ -611,6 +709,7 @@ where
         reply_surb: ReplySurb,
         chunk: Fragment,
     ) -> Result<PreparedFragment, SurbWrappedPreparationError> {
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = match self.get_topology(&topology_permit) {
             Ok(topology) => topology,
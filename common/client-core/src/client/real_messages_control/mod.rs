@@ -13,6 +13,7 @@ use crate::client::replies::reply_controller::{
     ReplyController, ReplyControllerReceiver, ReplyControllerSender,
 };
 use crate::client::replies::reply_storage::CombinedReplyStorage;
+use crate::client::self_address::SelfAddress;
 use crate::{
     client::{
         inbound_messages::InputMessageReceiver, mix_traffic::BatchMixMessageSender,
@@ -25,7 +26,6 @@ use futures::channel::mpsc;
 use log::*;
 use nym_gateway_client::AcknowledgementReceiver;
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::params::PacketType;
 use nym_task::connections::{ConnectionCommandReceiver, LaneQueueLengths};
 use rand::{rngs::OsRng, CryptoRng, Rng};
@@ -47,7 +47,7 @@ pub struct Config {
     ack_key: Arc<AckKey>,
 
     /// Address of `this` client.
-    self_recipient: Recipient,
+    self_recipient: SelfAddress,
 
     /// Specifies all traffic related configuration options.
     traffic: config::Traffic,
@@ -60,6 +60,9 @@ pub struct Config {
 
     /// Specifies all reply SURBs related configuration options.
     reply_surbs: config::ReplySurbs,
+
+    /// Specifies all erasure coding related configuration options.
+    erasure_coding: config::ErasureCoding,
 }
 
 impl<'a> From<&'a Config> for acknowledgement_control::Config {
@@ -76,7 +79,7 @@ impl<'a> From<&'a Config> for real_traffic_stream::Config {
     fn from(cfg: &'a Config) -> Self {
         real_traffic_stream::Config::new(
             Arc::clone(&cfg.ack_key),
-            cfg.self_recipient,
+            cfg.self_recipient.clone(),
             cfg.acks.average_ack_delay,
             cfg.traffic,
             cfg.cover_traffic.cover_traffic_primary_size_ratio,
@@ -94,12 +97,15 @@ impl<'a> From<&'a Config> for message_handler::Config {
     fn from(cfg: &'a Config) -> Self {
         message_handler::Config::new(
             Arc::clone(&cfg.ack_key),
-            cfg.self_recipient,
+            cfg.self_recipient.clone(),
             cfg.traffic.average_packet_delay,
             cfg.acks.average_ack_delay,
         )
         .with_custom_primary_packet_size(cfg.traffic.primary_packet_size)
         .with_custom_secondary_packet_size(cfg.traffic.secondary_packet_size)
+        .with_message_compression(cfg.traffic.message_compression_settings())
+        .with_compressed_non_reply_messages(cfg.traffic.compress_non_reply_messages)
+        .with_erasure_coding(cfg.erasure_coding)
     }
 }
 
@@ -107,7 +113,7 @@ impl Config {
     pub fn new(
         base_client_debug_config: &config::DebugConfig,
         ack_key: Arc<AckKey>,
-        self_recipient: Recipient,
+        self_recipient: SelfAddress,
     ) -> Self {
         Config {
             ack_key,
@@ -116,6 +122,7 @@ impl Config {
             cover_traffic: base_client_debug_config.cover_traffic,
             acks: base_client_debug_config.acknowledgements,
             reply_surbs: base_client_debug_config.reply_surbs,
+            erasure_coding: base_client_debug_config.erasure_coding,
         }
     }
 }
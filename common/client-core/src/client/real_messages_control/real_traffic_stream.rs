@@ -5,6 +5,7 @@ use self::sending_delay_controller::SendingDelayController;
 use crate::client::mix_traffic::BatchMixMessageSender;
 use crate::client::packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter};
 use crate::client::real_messages_control::acknowledgement_control::SentPacketNotificationSender;
+use crate::client::self_address::SelfAddress;
 use crate::client::topology_control::TopologyAccessor;
 use crate::client::transmission_buffer::TransmissionBuffer;
 use crate::config;
@@ -12,7 +13,6 @@ use futures::task::{Context, Poll};
 use futures::{Future, Stream, StreamExt};
 use log::*;
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::chunking::fragment::FragmentIdentifier;
 use nym_sphinx::cover::generate_loop_cover_packet;
 use nym_sphinx::forwarding::packet::MixPacket;
@@ -41,7 +41,7 @@ pub(crate) struct Config {
     ack_key: Arc<AckKey>,
 
     /// Represents full address of this client.
-    our_full_destination: Recipient,
+    our_full_destination: SelfAddress,
 
     /// Average delay an acknowledgement packet is going to get delay at a single mixnode.
     average_ack_delay: Duration,
@@ -57,7 +57,7 @@ pub(crate) struct Config {
 impl Config {
     pub(crate) fn new(
         ack_key: Arc<AckKey>,
-        our_full_destination: Recipient,
+        our_full_destination: SelfAddress,
         average_ack_delay: Duration,
         traffic: config::Traffic,
         cover_traffic_primary_size_ratio: f64,
@@ -229,11 +229,11 @@ where
                 // to wait a really tiny bit before actually obtaining the permit hence messing with our
                 // poisson delay, but is it really a problem?
                 let topology_permit = self.topology_access.get_read_permit().await;
+                let our_full_destination = self.config.our_full_destination.current();
                 // the ack is sent back to ourselves (and then ignored)
-                let topology_ref = match topology_permit.try_get_valid_topology_ref(
-                    &self.config.our_full_destination,
-                    Some(&self.config.our_full_destination),
-                ) {
+                let topology_ref = match topology_permit
+                    .try_get_valid_topology_ref(&our_full_destination, Some(&our_full_destination))
+                {
                     Ok(topology) => topology,
                     Err(err) => {
                         warn!("We're not going to send any loop cover message this time, as the current topology seem to be invalid - {err}");
@@ -246,7 +246,7 @@ where
                         &mut self.rng,
                         topology_ref,
                         &self.config.ack_key,
-                        &self.config.our_full_destination,
+                        &our_full_destination,
                         self.config.average_ack_delay,
                         self.config.traffic.average_packet_delay,
                         cover_traffic_packet_size,
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client::{
+    helpers::{get_time_now, Instant},
     packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter},
     replies::{reply_controller::ReplyControllerSender, reply_storage::SentReplyKeys},
 };
+use crate::config;
 use crate::spawn_future;
 use futures::channel::mpsc;
 use futures::lock::Mutex;
@@ -17,11 +19,15 @@ use nym_sphinx::anonymous_replies::requests::{
     RepliableMessage, RepliableMessageContent, ReplyMessage, ReplyMessageContent,
 };
 use nym_sphinx::anonymous_replies::{encryption_key::EncryptionKeyDigest, SurbEncryptionKey};
+use nym_sphinx::chunking::reconstruction::ReconstructionLimits;
 use nym_sphinx::message::{NymMessage, PlainMessage};
 use nym_sphinx::params::ReplySurbKeyDigestAlgorithm;
-use nym_sphinx::receiver::{MessageReceiver, MessageRecoveryError, ReconstructedMessage};
-use std::collections::HashSet;
+use nym_sphinx::receiver::{
+    MessageReceiver, MessageRecoveryError, ReconstructedMessage, RecoveredMessage,
+};
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
 
 // Buffer Requests to say "hey, send any reconstructed messages to this channel"
 // or to say "hey, I'm going offline, don't send anything more to me. Just buffer them instead"
@@ -32,6 +38,56 @@ pub type ReceivedBufferRequestReceiver = mpsc::UnboundedReceiver<ReceivedBufferM
 pub type ReconstructedMessagesSender = mpsc::UnboundedSender<Vec<ReconstructedMessage>>;
 pub type ReconstructedMessagesReceiver = mpsc::UnboundedReceiver<Vec<ReconstructedMessage>>;
 
+/// Ids of the recently reconstructed fragment sets used for discarding their duplicate fragments.
+/// Both the number of remembered ids and the duration for which they're kept are bounded.
+struct RecentlyReconstructed {
+    ids: HashSet<i32>,
+
+    /// Remembered ids, in order of their reconstruction, alongside the time it happened.
+    reconstruction_order: VecDeque<(i32, Instant)>,
+
+    retention: Duration,
+    max_remembered: usize,
+}
+
+impl RecentlyReconstructed {
+    fn new(retention: Duration, max_remembered: usize) -> Self {
+        RecentlyReconstructed {
+            ids: HashSet::new(),
+            reconstruction_order: VecDeque::new(),
+            retention,
+            max_remembered,
+        }
+    }
+
+    fn contains(&self, id: &i32) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Remembers the provided id. Returns whether it was not already present.
+    fn insert(&mut self, id: i32) -> bool {
+        let now = get_time_now();
+        let inserted = self.ids.insert(id);
+        if inserted {
+            self.reconstruction_order.push_back((id, now));
+        }
+        self.forget_old(now);
+        inserted
+    }
+
+    fn forget_old(&mut self, now: Instant) {
+        while let Some(&(id, reconstructed_at)) = self.reconstruction_order.front() {
+            if self.reconstruction_order.len() <= self.max_remembered
+                && now.saturating_duration_since(reconstructed_at) <= self.retention
+            {
+                break;
+            }
+            self.ids.remove(&id);
+            self.reconstruction_order.pop_front();
+        }
+    }
+}
+
 struct ReceivedMessagesBufferInner<R: MessageReceiver> {
     messages: Vec<ReconstructedMessage>,
     local_encryption_keypair: Arc<encryption::KeyPair>,
@@ -41,20 +97,34 @@ struct ReceivedMessagesBufferInner<R: MessageReceiver> {
     message_receiver: R,
     message_sender: Option<ReconstructedMessagesSender>,
 
-    // TODO: this will get cleared upon re-running the client
-    // but perhaps it should be changed to include timestamps of when the message was reconstructed
-    // and every now and then remove ids older than X
-    recently_reconstructed: HashSet<i32>,
+    // note: this will get cleared upon re-running the client
+    recently_reconstructed: RecentlyReconstructed,
 
     stats_tx: PacketStatisticsReporter,
 }
 
 impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
+    fn report_abandoned_sets(&mut self) {
+        for abandoned in self.message_receiver.reconstructor().take_abandoned_sets() {
+            debug!(
+                "abandoned reconstruction of set {} after receiving {}/{} of its fragments ({:?})",
+                abandoned.id,
+                abandoned.received_fragments,
+                abandoned.total_fragments,
+                abandoned.reason
+            );
+            self.stats_tx
+                .report(PacketStatisticsEvent::IncompleteMessageAbandoned(
+                    abandoned.received_fragments,
+                ));
+        }
+    }
+
     fn recover_from_fragment(
         &mut self,
         fragment_data: &[u8],
         fragment_data_size: usize,
-    ) -> Option<NymMessage> {
+    ) -> Option<RecoveredMessage> {
         if nym_sphinx::cover::is_cover(fragment_data) {
             trace!("The message was a loop cover message! Skipping it");
             // NOTE: it's important to note that there is quite a bit of difference in size of
@@ -85,8 +155,11 @@ impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
             return None;
         }
 
+        let reconstruction_result = self.message_receiver.insert_new_fragment(fragment);
+        self.report_abandoned_sets();
+
         // if we returned an error the underlying message is malformed in some way
-        match self.message_receiver.insert_new_fragment(fragment) {
+        match reconstruction_result {
             Err(err) => match err {
                 MessageRecoveryError::MalformedReconstructedMessage { source, used_sets } => {
                     error!("message reconstruction failed - {source}. Attempting to re-use the message sets...");
@@ -104,14 +177,14 @@ impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
                 ),
             },
             Ok(reconstruction_result) => match reconstruction_result {
-                Some((reconstructed_message, used_sets)) => {
-                    for set_id in used_sets {
+                Some(recovered_message) => {
+                    for &set_id in &recovered_message.used_sets {
                         if !self.recently_reconstructed.insert(set_id) {
                             // or perhaps we should even panic at this point?
                             error!("Reconstructed another message containing already used set id!")
                         }
                     }
-                    Some(reconstructed_message)
+                    Some(recovered_message)
                 }
                 None => None,
             },
@@ -122,7 +195,7 @@ impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
         &mut self,
         reply_ciphertext: &mut [u8],
         reply_key: SurbEncryptionKey,
-    ) -> Result<Option<NymMessage>, MessageRecoveryError> {
+    ) -> Result<Option<RecoveredMessage>, MessageRecoveryError> {
         let reply_ciphertext_size = reply_ciphertext.len();
         // note: this performs decryption IN PLACE without extra allocation
         self.message_receiver
@@ -132,7 +205,10 @@ impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
         Ok(self.recover_from_fragment(fragment_data, reply_ciphertext_size))
     }
 
-    fn process_received_regular_packet(&mut self, mut raw_fragment: Vec<u8>) -> Option<NymMessage> {
+    fn process_received_regular_packet(
+        &mut self,
+        mut raw_fragment: Vec<u8>,
+    ) -> Option<RecoveredMessage> {
         let raw_fragment_size = raw_fragment.len();
         let fragment_data = match self.message_receiver.recover_plaintext_from_regular_packet(
             self.local_encryption_keypair.private_key(),
@@ -164,14 +240,27 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         stats_tx: PacketStatisticsReporter,
+        reconstruction_config: config::MessageReconstruction,
     ) -> Self {
+        let mut message_receiver = R::new();
+        message_receiver
+            .reconstructor()
+            .set_limits(ReconstructionLimits {
+                max_incomplete_set_age: reconstruction_config.maximum_incomplete_message_age,
+                max_buffered_sets: reconstruction_config.maximum_buffered_fragment_sets,
+                max_buffered_bytes: reconstruction_config.maximum_buffered_fragments_size,
+            });
+
         ReceivedMessagesBuffer {
             inner: Arc::new(Mutex::new(ReceivedMessagesBufferInner {
                 messages: Vec::new(),
                 local_encryption_keypair,
-                message_receiver: R::new(),
+                message_receiver,
                 message_sender: None,
-                recently_reconstructed: HashSet::new(),
+                recently_reconstructed: RecentlyReconstructed::new(
+                    reconstruction_config.reconstructed_set_retention,
+                    reconstruction_config.maximum_remembered_reconstructed_sets,
+                ),
                 stats_tx,
             })),
             reply_key_storage,
@@ -226,10 +315,10 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
 
     fn handle_reconstructed_repliable_messages(
         &mut self,
-        msgs: Vec<RepliableMessage>,
+        msgs: Vec<(RepliableMessage, bool)>,
     ) -> Vec<ReconstructedMessage> {
         let mut reconstructed = Vec::new();
-        for msg in msgs {
+        for (msg, compressed) in msgs {
             let (reply_surbs, from_surb_request) = match msg.content {
                 RepliableMessageContent::Data {
                     message,
@@ -261,10 +350,12 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
                 }
             };
 
+            // if the sender has compressed its message, it will also understand compressed replies
             self.reply_controller_sender.send_additional_surbs(
                 msg.sender_tag,
                 reply_surbs,
                 from_surb_request,
+                compressed,
             )
         }
         reconstructed
@@ -288,7 +379,7 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
         reconstructed
     }
 
-    async fn handle_reconstructed_messages(&mut self, msgs: Vec<NymMessage>) {
+    async fn handle_reconstructed_messages(&mut self, msgs: Vec<RecoveredMessage>) {
         if msgs.is_empty() {
             return;
         }
@@ -298,9 +389,11 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
         let mut reply_messages = Vec::new();
 
         for msg in msgs {
-            match msg {
+            match msg.message {
                 NymMessage::Plain(plain) => plain_messages.push(plain),
-                NymMessage::Repliable(repliable) => repliable_messages.push(repliable),
+                NymMessage::Repliable(repliable) => {
+                    repliable_messages.push((repliable, msg.compressed))
+                }
                 NymMessage::Reply(reply) => reply_messages.push(reply),
             }
         }
@@ -377,7 +470,7 @@ impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
                 };
 
             if let Some(completed) = completed_message {
-                debug!("received {completed}");
+                debug!("received {}", completed.message);
                 completed_messages.push(completed)
             }
         }
@@ -505,12 +598,14 @@ impl<R: MessageReceiver + Clone + Send + 'static> ReceivedMessagesBufferControll
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         packet_statistics_reporter: PacketStatisticsReporter,
+        reconstruction_config: config::MessageReconstruction,
     ) -> Self {
         let received_buffer = ReceivedMessagesBuffer::new(
             local_encryption_keypair,
             reply_key_storage,
             reply_controller_sender,
             packet_statistics_reporter,
+            reconstruction_config,
         );
 
         ReceivedMessagesBufferController {
@@ -15,7 +15,7 @@ use nym_task::connections::{ConnectionId, TransmissionLane};
 use rand::{CryptoRng, Rng};
 use std::cmp::{max, min};
 use std::collections::btree_map::Entry;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use time::OffsetDateTime;
@@ -66,6 +66,10 @@ pub struct ReplyController<R> {
     pending_retransmissions:
         HashMap<AnonymousSenderTag, BTreeMap<FragmentIdentifier, Weak<PendingAcknowledgement>>>,
 
+    /// Senders that have used compression themselves and thus are going to understand
+    /// compressed replies.
+    compression_capable_senders: HashSet<AnonymousSenderTag>,
+
     message_handler: MessageHandler<R>,
     full_reply_storage: CombinedReplyStorage,
 }
@@ -85,6 +89,7 @@ where
             request_receiver,
             pending_replies: HashMap::new(),
             pending_retransmissions: HashMap::new(),
+            compression_capable_senders: HashSet::new(),
             message_handler,
             full_reply_storage,
         }
@@ -196,7 +201,8 @@ where
         }
 
         trace!("handling reply to {:?}", recipient_tag);
-        let mut fragments = self.message_handler.split_reply_message(data);
+        let compress = self.compression_capable_senders.contains(&recipient_tag);
+        let mut fragments = self.message_handler.split_reply_message(data, compress);
         let total_size = fragments.len();
         trace!("This reply requires {:?} SURBs", total_size);
 
@@ -449,9 +455,14 @@ where
         from: AnonymousSenderTag,
         reply_surbs: Vec<ReplySurb>,
         from_surb_request: bool,
+        supports_compression: bool,
     ) {
         trace!("handling received surbs");
 
+        if supports_compression && self.compression_capable_senders.insert(from) {
+            debug!("{from} understands compressed messages");
+        }
+
         // clear the requesting flag since we should have been asking for surbs
         self.full_reply_storage
             .surbs_storage_ref()
@@ -663,9 +674,15 @@ where
                 sender_tag,
                 reply_surbs,
                 from_surb_request,
+                supports_compression,
             } => {
-                self.handle_received_surbs(sender_tag, reply_surbs, from_surb_request)
-                    .await
+                self.handle_received_surbs(
+                    sender_tag,
+                    reply_surbs,
+                    from_surb_request,
+                    supports_compression,
+                )
+                .await
             }
             ReplyControllerMessage::LaneQueueLength {
                 connection_id,
@@ -778,7 +795,7 @@ where
         }
     }
 
-    async fn invalidate_old_data(&self) {
+    async fn invalidate_old_data(&mut self) {
         let now = OffsetDateTime::now_utc();
 
         let mut to_remove_surbs = Vec::new();
@@ -835,6 +852,7 @@ where
             self.full_reply_storage
                 .surbs_storage_ref()
                 .remove(&to_remove);
+            self.compression_capable_senders.remove(&to_remove);
         }
 
         for to_remove in to_remove_keys {
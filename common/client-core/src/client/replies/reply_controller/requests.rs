@@ -60,12 +60,14 @@ impl ReplyControllerSender {
         sender_tag: AnonymousSenderTag,
         reply_surbs: Vec<ReplySurb>,
         from_surb_request: bool,
+        supports_compression: bool,
     ) {
         self.0
             .unbounded_send(ReplyControllerMessage::AdditionalSurbs {
                 sender_tag,
                 reply_surbs,
                 from_surb_request,
+                supports_compression,
             })
             .expect("ReplyControllerReceiver has died!")
     }
@@ -137,6 +139,7 @@ pub(crate) enum ReplyControllerMessage {
         sender_tag: AnonymousSenderTag,
         reply_surbs: Vec<ReplySurb>,
         from_surb_request: bool,
+        supports_compression: bool,
     },
 
     // this one doesn't belong here either...
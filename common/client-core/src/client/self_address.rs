@@ -0,0 +1,106 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_sphinx::addressing::clients::Recipient;
+use tokio::sync::watch;
+
+/// Creates a new channel for publishing changes of this client's own address,
+/// i.e. whenever the client switches to a different gateway.
+pub fn address_channel(initial: Recipient) -> (SelfAddressUpdater, SelfAddress) {
+    let (tx, rx) = watch::channel(initial);
+    (SelfAddressUpdater(tx), SelfAddress(rx))
+}
+
+/// Publisher of this client's address changes.
+#[derive(Debug)]
+pub struct SelfAddressUpdater(watch::Sender<Recipient>);
+
+impl SelfAddressUpdater {
+    pub fn update(&self, new_address: Recipient) {
+        // we don't care whether anyone is currently listening for the changes
+        self.0.send_replace(new_address);
+    }
+
+    pub fn subscribe(&self) -> SelfAddress {
+        SelfAddress(self.0.subscribe())
+    }
+}
+
+/// Handle to this client's current address that gets updated whenever the client switches gateways.
+#[derive(Clone, Debug)]
+pub struct SelfAddress(watch::Receiver<Recipient>);
+
+impl SelfAddress {
+    /// Returns the current address of this client.
+    pub fn current(&self) -> Recipient {
+        *self.0.borrow()
+    }
+
+    /// Returns the new address of this client if it has changed since the last time it was observed.
+    pub fn updated(&mut self) -> Option<Recipient> {
+        // if the sender got dropped, the address is never going to change again
+        if self.0.has_changed().unwrap_or_default() {
+            Some(*self.0.borrow_and_update())
+        } else {
+            None
+        }
+    }
+
+    /// Waits until the address of this client changes and returns the new value.
+    /// Returns `None` if the address is never going to change again.
+    pub async fn changed(&mut self) -> Option<Recipient> {
+        self.0.changed().await.ok()?;
+        Some(*self.0.borrow_and_update())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::key_manager::ClientKeys;
+    use rand::rngs::OsRng;
+
+    fn address_with_new_gateway(address: Option<Recipient>) -> Recipient {
+        let mut rng = OsRng;
+        let gateway = ClientKeys::generate_new(&mut rng).identity_keypair();
+        match address {
+            Some(address) => Recipient::new(
+                *address.identity(),
+                *address.encryption_key(),
+                *gateway.public_key(),
+            ),
+            None => {
+                let client = ClientKeys::generate_new(&mut rng);
+                Recipient::new(
+                    *client.identity_keypair().public_key(),
+                    *client.encryption_keypair().public_key(),
+                    *gateway.public_key(),
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn address_changes_are_observed_once() {
+        let initial = address_with_new_gateway(None);
+        let (updater, mut address) = address_channel(initial);
+        assert_eq!(address.current(), initial);
+        assert!(address.updated().is_none());
+
+        let new_address = address_with_new_gateway(Some(initial));
+        updater.update(new_address);
+
+        // newly subscribed handles start with the latest address
+        let mut other = updater.subscribe();
+        assert_eq!(address.updated(), Some(new_address));
+        assert!(address.updated().is_none());
+        assert_eq!(address.current(), new_address);
+        assert_eq!(other.current(), new_address);
+
+        // the address doesn't change once the updater is gone
+        drop(updater);
+        assert!(address.updated().is_none());
+        assert_eq!(address.current(), new_address);
+        assert!(other.updated().is_none());
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use log::{debug, warn};
+use nym_topology::provider_trait::TopologyProvider;
+use nym_topology::NymTopology;
+use nym_validator_client::client::NymApiClient;
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use url::Url;
+
+const INITIAL_RECONNECTION_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_RECONNECTION_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Topology provider that, on top of the regular refreshes of the wrapped provider, subscribes
+/// to the network events pushed by the nym-api and requests a refresh as soon as,
+/// for example, the epoch changes.
+pub(crate) struct EventDrivenTopologyProvider<P> {
+    inner: P,
+    update_notify: Arc<Notify>,
+    listener: JoinHandle<()>,
+}
+
+impl<P> EventDrivenTopologyProvider<P> {
+    pub(crate) fn new(inner: P, mut nym_api_urls: Vec<Url>) -> Self {
+        nym_api_urls.shuffle(&mut thread_rng());
+
+        let update_notify = Arc::new(Notify::new());
+        let listener = tokio::spawn(listen_for_network_events(
+            nym_api_urls,
+            Arc::clone(&update_notify),
+            INITIAL_RECONNECTION_BACKOFF,
+        ));
+
+        EventDrivenTopologyProvider {
+            inner,
+            update_notify,
+            listener,
+        }
+    }
+}
+
+impl<P> Drop for EventDrivenTopologyProvider<P> {
+    fn drop(&mut self) {
+        self.listener.abort()
+    }
+}
+
+async fn listen_for_network_events(
+    nym_api_urls: Vec<Url>,
+    update_notify: Arc<Notify>,
+    initial_backoff: Duration,
+) {
+    if nym_api_urls.is_empty() {
+        warn!("no nym-api urls available - won't be able to subscribe to the network events");
+        return;
+    }
+
+    let mut backoff = initial_backoff;
+    let mut missed_events = false;
+
+    for nym_api in nym_api_urls.iter().cycle() {
+        let client = NymApiClient::new(nym_api.clone());
+        match client.subscribe_network_events().await {
+            Ok(mut events) => {
+                debug!("subscribed to the network events of {nym_api}");
+                backoff = initial_backoff;
+
+                // we might have missed something while we were disconnected
+                if missed_events {
+                    update_notify.notify_one();
+                }
+
+                while let Some(event) = events.next().await {
+                    match event {
+                        Ok(event) => {
+                            debug!("received network event: {event:?}");
+                            update_notify.notify_one();
+                        }
+                        Err(err) => {
+                            warn!("failed to receive network event from {nym_api}: {err}");
+                            break;
+                        }
+                    }
+                }
+                debug!("the network events stream of {nym_api} has been closed");
+            }
+            Err(err) => {
+                warn!("failed to subscribe to the network events of {nym_api}: {err}");
+            }
+        }
+
+        missed_events = true;
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_RECONNECTION_BACKOFF);
+    }
+}
+
+#[async_trait]
+impl<P> TopologyProvider for EventDrivenTopologyProvider<P>
+where
+    P: TopologyProvider + Send,
+{
+    async fn get_new_topology(&mut self) -> Option<NymTopology> {
+        self.inner.get_new_topology().await
+    }
+
+    async fn wait_for_update(&mut self) {
+        self.update_notify.notified().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::time::{timeout, Instant};
+
+    const TEST_BACKOFF: Duration = Duration::from_millis(50);
+    const TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+    struct LocalNymApi {
+        listener: TcpListener,
+    }
+
+    impl LocalNymApi {
+        async fn new() -> Self {
+            LocalNymApi {
+                listener: TcpListener::bind("127.0.0.1:0").await.unwrap(),
+            }
+        }
+
+        fn url(&self) -> Url {
+            format!("http://{}", self.listener.local_addr().unwrap())
+                .parse()
+                .unwrap()
+        }
+
+        async fn receive_subscription(&self) -> TcpStream {
+            let (mut connection, _) = timeout(TEST_TIMEOUT, self.listener.accept())
+                .await
+                .expect("the client didn't (re)connect")
+                .unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = connection.read(&mut buf).await.unwrap();
+                assert_ne!(
+                    n, 0,
+                    "the connection got closed before receiving the request"
+                );
+                request.extend_from_slice(&buf[..n]);
+            }
+
+            let request = String::from_utf8_lossy(&request);
+            assert!(request.starts_with("GET /v1/unstable/events "));
+            connection
+        }
+
+        async fn accept_subscription(&self) -> TcpStream {
+            let mut connection = self.receive_subscription().await;
+            connection
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n")
+                .await
+                .unwrap();
+            connection
+        }
+
+        async fn reject_subscription(&self) -> Instant {
+            let mut connection = self.receive_subscription().await;
+            connection
+                .write_all(
+                    b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                )
+                .await
+                .unwrap();
+            Instant::now()
+        }
+    }
+
+    async fn send_event(connection: &mut TcpStream) {
+        connection
+            .write_all(b"event: lagged\ndata: {\"type\":\"lagged\",\"missed\":1}\n\n")
+            .await
+            .unwrap();
+    }
+
+    async fn assert_notified(notify: &Notify) {
+        timeout(TEST_TIMEOUT, notify.notified())
+            .await
+            .expect("the topology refresh has not been requested")
+    }
+
+    async fn assert_not_notified(notify: &Notify) {
+        assert!(
+            timeout(Duration::from_millis(100), notify.notified())
+                .await
+                .is_err(),
+            "unexpected topology refresh request"
+        )
+    }
+
+    #[tokio::test]
+    async fn events_request_topology_refresh() {
+        let nym_api = LocalNymApi::new().await;
+        let notify = Arc::new(Notify::new());
+        let listener = tokio::spawn(listen_for_network_events(
+            vec![nym_api.url()],
+            Arc::clone(&notify),
+            TEST_BACKOFF,
+        ));
+
+        let mut connection = nym_api.accept_subscription().await;
+        assert_not_notified(&notify).await;
+
+        send_event(&mut connection).await;
+        assert_notified(&notify).await;
+
+        send_event(&mut connection).await;
+        assert_notified(&notify).await;
+        assert_not_notified(&notify).await;
+
+        listener.abort();
+    }
+
+    #[tokio::test]
+    async fn reconnects_with_backoff_and_notifies_about_missed_window() {
+        let nym_api = LocalNymApi::new().await;
+        let notify = Arc::new(Notify::new());
+        let listener = tokio::spawn(listen_for_network_events(
+            vec![nym_api.url()],
+            Arc::clone(&notify),
+            TEST_BACKOFF,
+        ));
+
+        // the nym-api isn't ready to serve the events yet
+        let first_attempt = nym_api.reject_subscription().await;
+        let second_attempt = nym_api.reject_subscription().await;
+        let third_attempt = nym_api.reject_subscription().await;
+        assert!(second_attempt - first_attempt >= TEST_BACKOFF);
+        assert!(third_attempt - second_attempt >= TEST_BACKOFF * 2);
+
+        // we might have missed something whilst we were failing to connect
+        let connection = nym_api.accept_subscription().await;
+        assert_notified(&notify).await;
+        assert_not_notified(&notify).await;
+
+        // the connection drops and the client subscribes again after the (reset) backoff
+        drop(connection);
+        let _connection = nym_api.accept_subscription().await;
+        assert_notified(&notify).await;
+
+        listener.abort();
+    }
+
+    #[tokio::test]
+    async fn nothing_happens_without_any_nym_apis() {
+        let notify = Arc::new(Notify::new());
+        timeout(
+            TEST_TIMEOUT,
+            listen_for_network_events(Vec::new(), Arc::clone(&notify), TEST_BACKOFF),
+        )
+        .await
+        .expect("the listener should have returned immediately");
+        assert_not_notified(&notify).await;
+    }
+}
@@ -1,13 +1,14 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::config::RouteSelection;
 use crate::spawn_future;
 pub(crate) use accessor::{TopologyAccessor, TopologyReadPermit};
 use futures::StreamExt;
 use log::*;
 use nym_sphinx::addressing::nodes::NodeIdentity;
 use nym_topology::provider_trait::TopologyProvider;
-use nym_topology::NymTopologyError;
+use nym_topology::{NymTopologyError, RouteConstraints, RouteSelectionStrategy};
 use std::time::Duration;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -17,19 +18,53 @@ use tokio::time::sleep;
 use wasmtimer::tokio::sleep;
 
 mod accessor;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod event_driven_provider;
 pub mod geo_aware_provider;
 pub(crate) mod nym_api_provider;
+pub mod quorum_provider;
 
 // TODO: move it to config later
 const MAX_FAILURE_COUNT: usize = 10;
 
 pub struct TopologyRefresherConfig {
     refresh_rate: Duration,
+    route_selection: RouteSelectionStrategy,
+    route_constraints: RouteConstraints,
 }
 
 impl TopologyRefresherConfig {
     pub fn new(refresh_rate: Duration) -> Self {
-        TopologyRefresherConfig { refresh_rate }
+        TopologyRefresherConfig {
+            refresh_rate,
+            route_selection: Default::default(),
+            route_constraints: Default::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_route_selection(mut self, route_selection: RouteSelectionStrategy) -> Self {
+        self.route_selection = route_selection;
+        self
+    }
+
+    #[must_use]
+    pub fn with_route_constraints(mut self, route_constraints: RouteConstraints) -> Self {
+        self.route_constraints = route_constraints;
+        self
+    }
+}
+
+pub fn route_selection_strategy(route_selection: RouteSelection) -> RouteSelectionStrategy {
+    match route_selection {
+        RouteSelection::Uniform => RouteSelectionStrategy::Uniform,
+        RouteSelection::PerformanceWeighted => RouteSelectionStrategy::PerformanceWeighted,
+        RouteSelection::StakeWeighted => RouteSelectionStrategy::StakeWeighted,
+        RouteSelection::LatencyWeighted { anonymity_budget } => {
+            RouteSelectionStrategy::LatencyWeighted {
+                anonymity_budget: f64::from(anonymity_budget.min(100)) / 100.,
+            }
+        }
     }
 }
 
@@ -38,6 +73,8 @@ pub struct TopologyRefresher {
     topology_accessor: TopologyAccessor,
 
     refresh_rate: Duration,
+    route_selection: RouteSelectionStrategy,
+    route_constraints: RouteConstraints,
     consecutive_failure_count: usize,
 }
 
@@ -51,6 +88,8 @@ impl TopologyRefresher {
             topology_provider,
             topology_accessor,
             refresh_rate: cfg.refresh_rate,
+            route_selection: cfg.route_selection,
+            route_constraints: cfg.route_constraints,
             consecutive_failure_count: 0,
         }
     }
@@ -69,7 +108,15 @@ impl TopologyRefresher {
                 .await;
         }
 
-        let new_topology = self.topology_provider.get_new_topology().await;
+        let new_topology = self
+            .topology_provider
+            .get_new_topology()
+            .await
+            .map(|topology| {
+                topology
+                    .with_route_selection(self.route_selection.clone())
+                    .with_route_constraints(self.route_constraints)
+            });
         if new_topology.is_none() {
             warn!("failed to obtain new network topology");
         }
@@ -159,6 +206,10 @@ impl TopologyRefresher {
                     _ = interval.next() => {
                         self.try_refresh().await;
                     },
+                    _ = self.topology_provider.wait_for_update() => {
+                        debug!("the topology provider has been notified of a network change");
+                        self.try_refresh().await;
+                    },
                     _ = shutdown.recv() => {
                         log::trace!("TopologyRefresher: Received shutdown");
                     },
@@ -5,8 +5,13 @@ use async_trait::async_trait;
 use log::{debug, error, warn};
 use nym_topology::provider_trait::TopologyProvider;
 use nym_topology::{NymTopology, NymTopologyError};
+use nym_validator_client::nym_api::error::NymAPIError;
+use nym_validator_client::nym_api::Conditional;
+use nym_validator_client::nym_nodes::{NodesDelta, SkimmedNode};
+use nym_validator_client::ValidatorClientError;
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
+use std::collections::BTreeMap;
 use url::Url;
 
 // the same values as our current (10.06.24) blacklist
@@ -28,6 +33,62 @@ impl Default for Config {
     }
 }
 
+/// Locally held copy of a node set alongside the revision (or the entity tag) it corresponds to,
+/// so that only the changes would have to be retrieved on subsequent refreshes.
+/// The refresh time reported by the nym-api is deliberately not kept around,
+/// as it's not updated by a `304 Not Modified` response.
+#[derive(Default)]
+struct CachedNodes {
+    revision: Option<String>,
+    etag: Option<String>,
+    nodes: BTreeMap<String, SkimmedNode>,
+}
+
+impl CachedNodes {
+    fn apply_delta(&mut self, delta: NodesDelta<SkimmedNode>) {
+        if delta.full {
+            self.nodes.clear();
+        }
+        for node in delta.added.into_iter().chain(delta.changed) {
+            self.nodes
+                .insert(node.ed25519_identity_pubkey.clone(), node);
+        }
+        for removed in &delta.removed {
+            self.nodes.remove(removed);
+        }
+        self.revision = Some(delta.revision);
+        self.etag = None;
+    }
+
+    fn apply_full(&mut self, response: Conditional<Vec<SkimmedNode>>) {
+        let Conditional::Modified { value: nodes, etag } = response else {
+            // we already hold the current version of the nodes
+            return;
+        };
+
+        self.revision = None;
+        self.etag = etag;
+        self.nodes = nodes
+            .into_iter()
+            .map(|node| (node.ed25519_identity_pubkey.clone(), node))
+            .collect();
+    }
+
+    fn reset(&mut self) {
+        *self = CachedNodes::default()
+    }
+}
+
+// older nym-apis don't have the delta routes at all
+fn deltas_unsupported(err: &ValidatorClientError) -> bool {
+    matches!(
+        err,
+        ValidatorClientError::NymAPIError {
+            source: NymAPIError::NotFound
+        }
+    )
+}
+
 pub(crate) struct NymApiTopologyProvider {
     config: Config,
 
@@ -36,6 +97,12 @@ pub(crate) struct NymApiTopologyProvider {
 
     client_version: String,
     currently_used_api: usize,
+
+    mixnodes: CachedNodes,
+    gateways: CachedNodes,
+
+    /// Indicates whether the currently used nym-api has told us it doesn't support the delta queries.
+    deltas_unsupported: bool,
 }
 
 impl NymApiTopologyProvider {
@@ -50,6 +117,9 @@ impl NymApiTopologyProvider {
             nym_api_urls,
             client_version,
             currently_used_api: 0,
+            mixnodes: CachedNodes::default(),
+            gateways: CachedNodes::default(),
+            deltas_unsupported: false,
         }
     }
 
@@ -61,7 +131,72 @@ impl NymApiTopologyProvider {
 
         self.currently_used_api = (self.currently_used_api + 1) % self.nym_api_urls.len();
         self.validator_client
-            .change_nym_api(self.nym_api_urls[self.currently_used_api].clone())
+            .change_nym_api(self.nym_api_urls[self.currently_used_api].clone());
+
+        // revisions are only meaningful for the api that has issued them
+        self.mixnodes.reset();
+        self.gateways.reset();
+        self.deltas_unsupported = false;
+    }
+
+    async fn refresh_mixnodes(&mut self) -> Result<(), ValidatorClientError> {
+        let version = Some(self.client_version.clone());
+        if !self.deltas_unsupported {
+            match self
+                .validator_client
+                .get_basic_mixnodes_delta(self.mixnodes.revision.clone(), version.clone())
+                .await
+            {
+                Ok(delta) => {
+                    self.mixnodes.apply_delta(delta);
+                    return Ok(());
+                }
+                Err(err) if deltas_unsupported(&err) => {
+                    debug!("the nym api does not support the delta queries. falling back to the full queries");
+                    self.deltas_unsupported = true;
+                }
+                Err(err) => {
+                    debug!("failed to get mixnodes delta: {err}. falling back to the full query")
+                }
+            }
+        }
+
+        let mixnodes = self
+            .validator_client
+            .get_basic_mixnodes_if_none_match(version, self.mixnodes.etag.clone())
+            .await?;
+        self.mixnodes.apply_full(mixnodes);
+        Ok(())
+    }
+
+    async fn refresh_gateways(&mut self) -> Result<(), ValidatorClientError> {
+        let version = Some(self.client_version.clone());
+        if !self.deltas_unsupported {
+            match self
+                .validator_client
+                .get_basic_gateways_delta(self.gateways.revision.clone(), version.clone())
+                .await
+            {
+                Ok(delta) => {
+                    self.gateways.apply_delta(delta);
+                    return Ok(());
+                }
+                Err(err) if deltas_unsupported(&err) => {
+                    debug!("the nym api does not support the delta queries. falling back to the full queries");
+                    self.deltas_unsupported = true;
+                }
+                Err(err) => {
+                    debug!("failed to get gateways delta: {err}. falling back to the full query")
+                }
+            }
+        }
+
+        let gateways = self
+            .validator_client
+            .get_basic_gateways_if_none_match(version, self.gateways.etag.clone())
+            .await?;
+        self.gateways.apply_full(gateways);
+        Ok(())
     }
 
     /// Verifies whether nodes a reasonably distributed among all mix layers.
@@ -83,29 +218,18 @@ impl NymApiTopologyProvider {
     }
 
     async fn get_current_compatible_topology(&mut self) -> Option<NymTopology> {
-        let mixnodes = match self
-            .validator_client
-            .get_basic_mixnodes(Some(self.client_version.clone()))
-            .await
-        {
-            Err(err) => {
-                error!("failed to get network mixnodes - {err}");
-                return None;
-            }
-            Ok(mixes) => mixes,
-        };
+        if let Err(err) = self.refresh_mixnodes().await {
+            error!("failed to get network mixnodes - {err}");
+            return None;
+        }
 
-        let gateways = match self
-            .validator_client
-            .get_basic_gateways(Some(self.client_version.clone()))
-            .await
-        {
-            Err(err) => {
-                error!("failed to get network gateways - {err}");
-                return None;
-            }
-            Ok(gateways) => gateways,
-        };
+        if let Err(err) = self.refresh_gateways().await {
+            error!("failed to get network gateways - {err}");
+            return None;
+        }
+
+        let mixnodes = &self.mixnodes.nodes;
+        let gateways = &self.gateways.nodes;
 
         debug!(
             "there are {} mixnodes and {} gateways in total (before performance filtering)",
@@ -114,10 +238,10 @@ impl NymApiTopologyProvider {
         );
 
         let topology = NymTopology::from_unordered(
-            mixnodes.iter().filter(|m| {
+            mixnodes.values().filter(|m| {
                 m.performance.round_to_integer() >= self.config.min_mixnode_performance
             }),
-            gateways.iter().filter(|g| {
+            gateways.values().filter(|g| {
                 g.performance.round_to_integer() >= self.config.min_gateway_performance
             }),
         );
@@ -148,3 +272,59 @@ impl TopologyProvider for NymApiTopologyProvider {
         self.get_current_compatible_topology().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_validator_client::nym_nodes::NodeRole;
+
+    fn node(identity: &str) -> SkimmedNode {
+        SkimmedNode {
+            node_id: 1,
+            ed25519_identity_pubkey: identity.to_string(),
+            ip_addresses: vec!["1.2.3.4".parse().unwrap()],
+            mix_port: 1789,
+            x25519_sphinx_pubkey: "sphinx".to_string(),
+            role: NodeRole::Mixnode { layer: 1 },
+            entry: None,
+            performance: Default::default(),
+            stake_saturation: Default::default(),
+            family: None,
+            latency: None,
+        }
+    }
+
+    #[test]
+    fn unmodified_nodes_are_kept() {
+        let mut cached = CachedNodes::default();
+        cached.apply_full(Conditional::Modified {
+            value: vec![node("a"), node("b")],
+            etag: Some("\"1\"".to_string()),
+        });
+        assert_eq!(cached.etag.as_deref(), Some("\"1\""));
+
+        cached.apply_full(Conditional::NotModified);
+        assert_eq!(cached.etag.as_deref(), Some("\"1\""));
+        assert_eq!(cached.nodes.len(), 2);
+
+        cached.apply_full(Conditional::Modified {
+            value: vec![node("c")],
+            etag: None,
+        });
+        assert!(cached.etag.is_none());
+        assert_eq!(cached.nodes.keys().collect::<Vec<_>>(), vec!["c"]);
+    }
+
+    #[test]
+    fn only_missing_routes_disable_deltas() {
+        let not_found = ValidatorClientError::NymAPIError {
+            source: NymAPIError::NotFound,
+        };
+        let failure = ValidatorClientError::NymAPIError {
+            source: NymAPIError::GenericRequestFailure("timeout".to_string()),
+        };
+
+        assert!(deltas_unsupported(&not_found));
+        assert!(!deltas_unsupported(&failure));
+    }
+}
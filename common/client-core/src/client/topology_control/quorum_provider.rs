@@ -0,0 +1,403 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::topology_control::nym_api_provider::{
+    DEFAULT_MIN_GATEWAY_PERFORMANCE, DEFAULT_MIN_MIXNODE_PERFORMANCE,
+};
+use async_trait::async_trait;
+use futures::future::join_all;
+use log::{debug, error, warn};
+use nym_topology::provider_trait::TopologyProvider;
+use nym_topology::{MixLayer, NymTopology, NymTopologyError};
+use nym_validator_client::client::{MixId, NymApiClient};
+use nym_validator_client::nym_nodes::SkimmedNode;
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use tap::TapFallible;
+use thiserror::Error;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum QuorumTopologyError {
+    #[error("the quorum of {quorum} can't be reached with only {available} nym-api endpoints")]
+    UnreachableQuorum { quorum: usize, available: usize },
+
+    #[error(
+        "only {received} nym-api endpoints returned valid topology while {quorum} were required"
+    )]
+    InsufficientResponses { received: usize, quorum: usize },
+
+    #[error("nym-api endpoints returned {distinct_views} diverging topologies and at most {largest_agreement} of them agreed with each other while {quorum} were required")]
+    TopologyDivergence {
+        distinct_views: usize,
+        largest_agreement: usize,
+        quorum: usize,
+    },
+
+    #[error("{agreeing_views} diverging topologies were each returned by at least {quorum} nym-api endpoints")]
+    AmbiguousQuorum {
+        agreeing_views: usize,
+        quorum: usize,
+    },
+
+    #[error("the agreed upon topology can't be used: {source}")]
+    UnusableTopology {
+        #[from]
+        source: NymTopologyError,
+    },
+}
+
+pub struct Config {
+    /// Number of nym-api endpoints that have to return identical topology for it to be accepted.
+    /// If it's not greater than half of the endpoints, multiple diverging topologies might reach it,
+    /// in which case none of them is accepted.
+    pub quorum: usize,
+
+    pub min_mixnode_performance: u8,
+    pub min_gateway_performance: u8,
+}
+
+impl Config {
+    pub fn new(quorum: usize) -> Self {
+        Config {
+            quorum,
+            min_mixnode_performance: DEFAULT_MIN_MIXNODE_PERFORMANCE,
+            min_gateway_performance: DEFAULT_MIN_GATEWAY_PERFORMANCE,
+        }
+    }
+}
+
+// (layer, mix id, identity key, sphinx key, host, mix host)
+type MixnodeFingerprint = (MixLayer, MixId, [u8; 32], [u8; 32], String, SocketAddr);
+
+// (identity key, sphinx key, host, mix host, clients ws port, clients wss port)
+type GatewayFingerprint = ([u8; 32], [u8; 32], String, SocketAddr, u16, Option<u16>);
+
+// the parts of the topology all the endpoints have to agree on.
+// note that it doesn't include performance as it's not going to be identical between the apis
+#[derive(Debug, PartialEq, Eq)]
+struct TopologyFingerprint {
+    mixnodes: BTreeSet<MixnodeFingerprint>,
+    gateways: BTreeSet<GatewayFingerprint>,
+}
+
+impl TopologyFingerprint {
+    fn new(topology: &NymTopology) -> Self {
+        let mixnodes = topology
+            .mixes()
+            .iter()
+            .flat_map(|(layer, nodes)| {
+                nodes.iter().map(|node| {
+                    (
+                        *layer,
+                        node.mix_id,
+                        node.identity_key.to_bytes(),
+                        node.sphinx_key.to_bytes(),
+                        node.host.to_string(),
+                        node.mix_host,
+                    )
+                })
+            })
+            .collect();
+
+        let gateways = topology
+            .gateways()
+            .iter()
+            .map(|node| {
+                (
+                    node.identity_key.to_bytes(),
+                    node.sphinx_key.to_bytes(),
+                    node.host.to_string(),
+                    node.mix_host,
+                    node.clients_ws_port,
+                    node.clients_wss_port,
+                )
+            })
+            .collect();
+
+        TopologyFingerprint { mixnodes, gateways }
+    }
+}
+
+// nodes returned by a single nym-api, before applying any performance filtering
+struct ApiResponse {
+    mixnodes: Vec<SkimmedNode>,
+    gateways: Vec<SkimmedNode>,
+    unfiltered: NymTopology,
+}
+
+/// Selects the response whose topology at least `quorum` of the responses agree on.
+/// If more than a single topology reaches the quorum, none of them is selected.
+fn select_agreed_topology<T>(
+    responses: Vec<T>,
+    quorum: usize,
+    topology: impl Fn(&T) -> &NymTopology,
+) -> Result<T, QuorumTopologyError> {
+    let received = responses.len();
+    if received < quorum {
+        return Err(QuorumTopologyError::InsufficientResponses { received, quorum });
+    }
+
+    let mut views: Vec<(TopologyFingerprint, T, usize)> = Vec::new();
+    for response in responses {
+        let fingerprint = TopologyFingerprint::new(topology(&response));
+        match views.iter_mut().find(|(known, _, _)| *known == fingerprint) {
+            Some((_, _, count)) => *count += 1,
+            None => views.push((fingerprint, response, 1)),
+        }
+    }
+
+    let distinct_views = views.len();
+    let agreeing_views = views.iter().filter(|view| view.2 >= quorum).count();
+    if agreeing_views > 1 {
+        return Err(QuorumTopologyError::AmbiguousQuorum {
+            agreeing_views,
+            quorum,
+        });
+    }
+
+    let Some((_, agreed, largest_agreement)) = views.into_iter().max_by_key(|view| view.2) else {
+        return Err(QuorumTopologyError::InsufficientResponses { received, quorum });
+    };
+
+    if largest_agreement < quorum {
+        return Err(QuorumTopologyError::TopologyDivergence {
+            distinct_views,
+            largest_agreement,
+            quorum,
+        });
+    }
+
+    Ok(agreed)
+}
+
+/// Topology provider that queries multiple nym-api endpoints and only accepts the topology
+/// once the configured quorum of them returned the same set of nodes and keys.
+pub struct QuorumTopologyProvider {
+    config: Config,
+
+    nym_api_clients: Vec<(Url, NymApiClient)>,
+
+    client_version: String,
+}
+
+impl QuorumTopologyProvider {
+    pub fn new(
+        config: Config,
+        nym_api_urls: Vec<Url>,
+        client_version: String,
+    ) -> Result<Self, QuorumTopologyError> {
+        if config.quorum == 0 || config.quorum > nym_api_urls.len() {
+            return Err(QuorumTopologyError::UnreachableQuorum {
+                quorum: config.quorum,
+                available: nym_api_urls.len(),
+            });
+        }
+
+        Ok(QuorumTopologyProvider {
+            config,
+            nym_api_clients: nym_api_urls
+                .into_iter()
+                .map(|url| (url.clone(), NymApiClient::new(url)))
+                .collect(),
+            client_version,
+        })
+    }
+
+    async fn get_topology_from(&self, url: &Url, client: &NymApiClient) -> Option<ApiResponse> {
+        let mixnodes = match client
+            .get_basic_mixnodes(Some(self.client_version.clone()))
+            .await
+        {
+            Err(err) => {
+                warn!("failed to get network mixnodes from {url} - {err}");
+                return None;
+            }
+            Ok(mixes) => mixes,
+        };
+
+        let gateways = match client
+            .get_basic_gateways(Some(self.client_version.clone()))
+            .await
+        {
+            Err(err) => {
+                warn!("failed to get network gateways from {url} - {err}");
+                return None;
+            }
+            Ok(gateways) => gateways,
+        };
+
+        debug!(
+            "{url} returned {} mixnodes and {} gateways (before performance filtering)",
+            mixnodes.len(),
+            gateways.len()
+        );
+
+        let unfiltered = NymTopology::from_unordered(mixnodes.iter(), gateways.iter());
+        Some(ApiResponse {
+            mixnodes,
+            gateways,
+            unfiltered,
+        })
+    }
+
+    /// Queries all the nym-api endpoints and returns the topology the quorum of them agreed on.
+    pub async fn get_quorum_topology(&self) -> Result<NymTopology, QuorumTopologyError> {
+        let responses = join_all(
+            self.nym_api_clients
+                .iter()
+                .map(|(url, client)| self.get_topology_from(url, client)),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let agreed = select_agreed_topology(responses, self.config.quorum, |response| {
+            &response.unfiltered
+        })?;
+
+        // the performance is only filtered on once the apis agreed on the set of nodes,
+        // as otherwise tiny differences in the reported scores would have prevented the quorum
+        let topology = NymTopology::from_unordered(
+            agreed.mixnodes.iter().filter(|m| {
+                m.performance.round_to_integer() >= self.config.min_mixnode_performance
+            }),
+            agreed.gateways.iter().filter(|g| {
+                g.performance.round_to_integer() >= self.config.min_gateway_performance
+            }),
+        );
+
+        // same check as the one performed by the `NymApiTopologyProvider`
+        topology.ensure_even_layer_distribution(0.15, 0.66)?;
+        Ok(topology)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl TopologyProvider for QuorumTopologyProvider {
+    async fn get_new_topology(&mut self) -> Option<NymTopology> {
+        self.get_quorum_topology()
+            .await
+            .tap_err(|err| error!("failed to obtain quorum topology: {err}"))
+            .ok()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+impl TopologyProvider for QuorumTopologyProvider {
+    async fn get_new_topology(&mut self) -> Option<NymTopology> {
+        self.get_quorum_topology()
+            .await
+            .tap_err(|err| error!("failed to obtain quorum topology: {err}"))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::encryption;
+    use nym_topology::mix;
+    use nym_topology::testing::{IDENTITY_KEY, SPHINX_KEY};
+    use std::collections::BTreeMap;
+
+    fn mixnode(mix_id: MixId, sphinx_key: &str) -> mix::Node {
+        mix::Node {
+            sphinx_key: encryption::PublicKey::from_base58_string(sphinx_key).unwrap(),
+            ..nym_topology::testing::mixnode(mix_id)
+        }
+    }
+
+    fn topology(nodes: Vec<mix::Node>) -> NymTopology {
+        let mut mixes = BTreeMap::new();
+        mixes.insert(1, nodes);
+        NymTopology::new(mixes, vec![])
+    }
+
+    const KEY_A: &str = SPHINX_KEY;
+    // any valid x25519 key different from the above
+    const KEY_B: &str = IDENTITY_KEY;
+
+    #[test]
+    fn accepts_topology_agreed_on_by_quorum() {
+        let honest = topology(vec![mixnode(1, KEY_A), mixnode(2, KEY_A)]);
+        // ordering of the nodes doesn't matter
+        let reordered = topology(vec![mixnode(2, KEY_A), mixnode(1, KEY_A)]);
+        let malicious = topology(vec![mixnode(1, KEY_B), mixnode(2, KEY_A)]);
+
+        let agreed =
+            select_agreed_topology(vec![malicious, honest.clone(), reordered], 2, |t| t).unwrap();
+        assert_eq!(
+            TopologyFingerprint::new(&agreed),
+            TopologyFingerprint::new(&honest)
+        );
+    }
+
+    #[test]
+    fn reports_divergence() {
+        let first = topology(vec![mixnode(1, KEY_A)]);
+        let second = topology(vec![mixnode(1, KEY_B)]);
+        let third = topology(vec![mixnode(2, KEY_A)]);
+
+        assert!(matches!(
+            select_agreed_topology(vec![first, second, third], 2, |t| t),
+            Err(QuorumTopologyError::TopologyDivergence {
+                distinct_views: 3,
+                largest_agreement: 1,
+                quorum: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_multiple_topologies_reaching_quorum() {
+        let first = topology(vec![mixnode(1, KEY_A)]);
+        let second = topology(vec![mixnode(1, KEY_B)]);
+
+        // both views reach the quorum of 2 - neither of them can be trusted
+        for responses in [
+            vec![first.clone(), first.clone(), second.clone(), second.clone()],
+            vec![second.clone(), second.clone(), first.clone(), first.clone()],
+        ] {
+            assert!(matches!(
+                select_agreed_topology(responses, 2, |t| t),
+                Err(QuorumTopologyError::AmbiguousQuorum {
+                    agreeing_views: 2,
+                    quorum: 2
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn network_addresses_are_part_of_the_agreement() {
+        let honest = topology(vec![mixnode(1, KEY_A)]);
+        let mut redirected_node = mixnode(1, KEY_A);
+        redirected_node.mix_host = "6.6.6.6:1789".parse().unwrap();
+        let redirected = topology(vec![redirected_node]);
+
+        assert!(matches!(
+            select_agreed_topology(vec![honest, redirected], 2, |t| t),
+            Err(QuorumTopologyError::TopologyDivergence {
+                distinct_views: 2,
+                largest_agreement: 1,
+                quorum: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn requires_enough_responses() {
+        let first = topology(vec![mixnode(1, KEY_A)]);
+        assert!(matches!(
+            select_agreed_topology(vec![first], 2, |t| t),
+            Err(QuorumTopologyError::InsufficientResponses {
+                received: 1,
+                quorum: 2
+            })
+        ));
+    }
+}
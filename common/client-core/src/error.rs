@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client::mix_traffic::transceiver::ErasedGatewayError;
+use crate::client::topology_control::quorum_provider::QuorumTopologyError;
 use nym_crypto::asymmetric::identity::Ed25519RecoveryError;
 use nym_gateway_client::error::GatewayClientError;
 use nym_topology::gateway::GatewayConversionError;
-use nym_topology::NymTopologyError;
+use nym_topology::{NymTopologyError, TopologySnapshotError};
 use nym_validator_client::ValidatorClientError;
 use std::error::Error;
 use std::path::PathBuf;
@@ -58,6 +59,9 @@ pub enum ClientCoreError {
         source: Box<dyn Error + Send + Sync>,
     },
 
+    #[error("the passphrase for the client keys has not been provided. please set the '{env_var}' environment variable")]
+    MissingKeysPassphrase { env_var: String },
+
     #[error("experienced a failure with our gateways details storage: {source}")]
     GatewaysDetailsStoreError {
         source: Box<dyn Error + Send + Sync>,
@@ -151,6 +155,24 @@ pub enum ClientCoreError {
     #[error("the specified gateway '{gateway}' does not support the wss protocol")]
     UnsupportedWssProtocol { gateway: String },
 
+    #[error("failed to set up the quorum topology provider: {source}")]
+    QuorumTopologyProviderFailure {
+        #[from]
+        source: QuorumTopologyError,
+    },
+
+    #[error("the configured topology snapshot signer is malformed: {source}")]
+    MalformedTopologySnapshotSigner {
+        #[source]
+        source: Ed25519RecoveryError,
+    },
+
+    #[error("failed to load the topology snapshot: {source}")]
+    TopologySnapshotFailure {
+        #[from]
+        source: TopologySnapshotError,
+    },
+
     #[error(
     "failed to load custom topology using path '{}'. detailed message: {source}", file_path.display()
     )]
@@ -206,6 +228,12 @@ pub enum ClientCoreError {
 
     #[error("this client has already registered with gateway {gateway_id}")]
     AlreadyRegistered { gateway_id: String },
+
+    #[error("failed to fail over from gateway {failed_gateway} after {attempts} attempt(s)")]
+    GatewayFailoverFailure {
+        failed_gateway: String,
+        attempts: usize,
+    },
 }
 
 /// Set of messages that the client can send to listeners via the task manager
@@ -2,15 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::ClientCoreError;
-use crate::init::types::RegistrationResult;
+use crate::init::types::{GatewaySelectionSpecification, RegistrationResult};
 use futures::{SinkExt, StreamExt};
 use log::{debug, info, trace, warn};
 use nym_crypto::asymmetric::identity;
 use nym_gateway_client::GatewayClient;
-use nym_topology::{filter::VersionFilterable, gateway, mix};
+use nym_topology::{choose_by_latency, filter::VersionFilterable, gateway, mix};
 use nym_validator_client::client::IdentityKeyRef;
 use rand::{seq::SliceRandom, Rng};
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tungstenite::Message;
 use url::Url;
 
@@ -59,6 +59,24 @@ impl<'a> GatewayWithLatency<'a> {
 pub async fn current_gateways<R: Rng>(
     rng: &mut R,
     nym_apis: &[Url],
+) -> Result<Vec<gateway::Node>, ClientCoreError> {
+    fetch_gateways(rng, nym_apis, false).await
+}
+
+/// Same as [`current_gateways`], but if the provided specification relies on the latencies
+/// reported by the network, they're also going to be attached to the returned gateways.
+pub async fn current_gateways_for_selection<R: Rng>(
+    rng: &mut R,
+    nym_apis: &[Url],
+    specification: &GatewaySelectionSpecification,
+) -> Result<Vec<gateway::Node>, ClientCoreError> {
+    fetch_gateways(rng, nym_apis, specification.uses_reported_latency()).await
+}
+
+async fn fetch_gateways<R: Rng>(
+    rng: &mut R,
+    nym_apis: &[Url],
+    with_reported_latencies: bool,
 ) -> Result<Vec<gateway::Node>, ClientCoreError> {
     let nym_api = nym_apis
         .choose(rng)
@@ -71,13 +89,33 @@ pub async fn current_gateways<R: Rng>(
     log::debug!("Found {} gateways", gateways.len());
     log::trace!("Gateways: {:#?}", gateways);
 
-    let valid_gateways = gateways
+    let mut valid_gateways = gateways
         .into_iter()
         .filter_map(|gateway| gateway.try_into().ok())
         .collect::<Vec<gateway::Node>>();
     log::debug!("Ater checking validity: {}", valid_gateways.len());
     log::trace!("Valid gateways: {:#?}", valid_gateways);
 
+    // the latency data is only exposed by the skimmed endpoint, so attach it if it's available
+    if with_reported_latencies {
+        match client.get_basic_gateways(None).await {
+            Ok(skimmed) => {
+                let latencies = skimmed
+                    .into_iter()
+                    .filter_map(|node| {
+                        node.latency
+                            .map(|l| (node.ed25519_identity_pubkey, l.median_rtt_micros))
+                    })
+                    .collect::<HashMap<_, _>>();
+                for gateway in &mut valid_gateways {
+                    gateway.latency = latencies
+                        .get(&gateway.identity_key.to_base58_string())
+                        .map(|micros| Duration::from_micros(*micros));
+                }
+            }
+            Err(err) => log::debug!("failed to retrieve gateway latencies: {err}"),
+        }
+    }
     // we were always filtering by version so I'm not removing that 'feature'
     let filtered_gateways = valid_gateways.filter_by_version(env!("CARGO_PKG_VERSION"));
     log::debug!("After filtering for version: {}", filtered_gateways.len());
@@ -243,7 +281,7 @@ fn filter_by_tls(
     }
 }
 
-pub(super) fn uniformly_random_gateway<R: Rng>(
+pub(crate) fn uniformly_random_gateway<R: Rng>(
     rng: &mut R,
     gateways: &[gateway::Node],
     must_use_tls: bool,
@@ -254,6 +292,26 @@ pub(super) fn uniformly_random_gateway<R: Rng>(
         .map(|&r| r.clone())
 }
 
+pub(crate) fn latency_weighted_gateway<R: Rng>(
+    rng: &mut R,
+    gateways: &[gateway::Node],
+    must_use_tls: bool,
+    anonymity_budget: u8,
+) -> Result<gateway::Node, ClientCoreError> {
+    let gateways = filter_by_tls(gateways, must_use_tls)?;
+    let budget = f64::from(anonymity_budget.min(100)) / 100.;
+
+    let chosen = choose_by_latency(rng, &gateways, budget, |gateway| gateway.latency)
+        .ok_or(ClientCoreError::NoGatewaysOnNetwork)?;
+
+    info!(
+        "chose gateway {} with reported latency of {:?}",
+        chosen.identity_key, chosen.latency
+    );
+
+    Ok((*chosen).clone())
+}
+
 pub(super) fn get_specified_gateway(
     gateway_identity: IdentityKeyRef,
     gateways: &[gateway::Node],
@@ -277,7 +335,7 @@ pub(super) fn get_specified_gateway(
     Ok(gateway.clone())
 }
 
-pub(super) async fn register_with_gateway(
+pub(crate) async fn register_with_gateway(
     gateway_id: identity::PublicKey,
     gateway_listener: Url,
     our_identity: Arc<identity::KeyPair>,
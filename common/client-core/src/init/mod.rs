@@ -11,7 +11,8 @@ use crate::client::key_manager::persistence::KeyStore;
 use crate::client::key_manager::ClientKeys;
 use crate::error::ClientCoreError;
 use crate::init::helpers::{
-    choose_gateway_by_latency, get_specified_gateway, uniformly_random_gateway,
+    choose_gateway_by_latency, get_specified_gateway, latency_weighted_gateway,
+    uniformly_random_gateway,
 };
 use crate::init::types::{
     GatewaySelectionSpecification, GatewaySetup, InitialisationResult, SelectedGateway,
@@ -77,6 +78,18 @@ where
                 choose_gateway_by_latency(&mut rng, &available_gateways, must_use_tls).await?;
             SelectedGateway::from_topology_node(gateway, wg_tun_ip_address, must_use_tls)?
         }
+        GatewaySelectionSpecification::RemoteByReportedLatency {
+            must_use_tls,
+            anonymity_budget,
+        } => {
+            let gateway = latency_weighted_gateway(
+                &mut rng,
+                &available_gateways,
+                must_use_tls,
+                anonymity_budget,
+            )?;
+            SelectedGateway::from_topology_node(gateway, wg_tun_ip_address, must_use_tls)?
+        }
         GatewaySelectionSpecification::Specified {
             must_use_tls,
             identity,
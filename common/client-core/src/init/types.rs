@@ -195,6 +195,15 @@ pub enum GatewaySelectionSpecification {
     /// Should the new, remote, gateway be selected based on latency.
     RemoteByLatency { must_use_tls: bool },
 
+    /// Choose a remote gateway preferring the ones with lower round-trip-time as reported by the network
+    /// (rather than measured by the client itself).
+    /// `anonymity_budget` is the percentage (0-100) of the selection probability that gets distributed
+    /// based on the latency, the remainder is spread uniformly across all gateways.
+    RemoteByReportedLatency {
+        must_use_tls: bool,
+        anonymity_budget: u8,
+    },
+
     /// Gateway with this specific identity should be chosen.
     // JS: I don't really like the name of this enum variant but couldn't think of anything better at the time
     Specified {
@@ -222,6 +231,7 @@ impl GatewaySelectionSpecification {
     pub fn new(
         gateway_identity: Option<String>,
         latency_based_selection: Option<bool>,
+        reported_latency_anonymity_budget: Option<u8>,
         must_use_tls: bool,
     ) -> Self {
         if let Some(identity) = gateway_identity {
@@ -231,10 +241,22 @@ impl GatewaySelectionSpecification {
             }
         } else if let Some(true) = latency_based_selection {
             GatewaySelectionSpecification::RemoteByLatency { must_use_tls }
+        } else if let Some(anonymity_budget) = reported_latency_anonymity_budget {
+            GatewaySelectionSpecification::RemoteByReportedLatency {
+                must_use_tls,
+                anonymity_budget,
+            }
         } else {
             GatewaySelectionSpecification::UniformRemote { must_use_tls }
         }
     }
+    /// Whether the selection relies on the gateway latencies reported by the network.
+    pub fn uses_reported_latency(&self) -> bool {
+        matches!(
+            self,
+            GatewaySelectionSpecification::RemoteByReportedLatency { .. }
+        )
+    }
 }
 
 pub enum GatewaySetup {
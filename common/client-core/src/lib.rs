@@ -16,6 +16,22 @@ pub use nym_topology::{
     SerializableTopologyError, TopologyProvider,
 };
 
+/// Bound for types that have to be shared between threads on native targets,
+/// but are not required to be in wasm, where everything runs on a single thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + Sync> MaybeSendSync for T {}
+
+/// Bound for types that have to be shared between threads on native targets,
+/// but are not required to be in wasm, where everything runs on a single thread.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSendSync {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSendSync for T {}
+
 #[cfg(target_arch = "wasm32")]
 pub(crate) fn spawn_future<F>(future: F)
 where
@@ -4,6 +4,7 @@
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
+use nym_noise::{upgrade_noise_initiator, Connection, NoiseConfig};
 use nym_sphinx::addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx::framing::codec::NymCodec;
 use nym_sphinx::framing::packet::FramedNymPacket;
@@ -25,6 +26,7 @@ pub struct Config {
     initial_connection_timeout: Duration,
     maximum_connection_buffer_size: usize,
     use_legacy_version: bool,
+    noise_config: Option<NoiseConfig>,
 }
 
 impl Config {
@@ -41,8 +43,16 @@ impl Config {
             initial_connection_timeout,
             maximum_connection_buffer_size,
             use_legacy_version,
+            noise_config: None,
         }
     }
+
+    /// Attempt to encrypt all outbound connections to nodes supporting it with noise.
+    #[must_use]
+    pub fn with_noise(mut self, noise_config: NoiseConfig) -> Self {
+        self.noise_config = Some(noise_config);
+        self
+    }
 }
 
 pub trait SendWithoutResponse {
@@ -87,17 +97,16 @@ impl Client {
         address: SocketAddr,
         receiver: mpsc::Receiver<FramedNymPacket>,
         connection_timeout: Duration,
+        noise_config: Option<NoiseConfig>,
         current_reconnection: &AtomicU32,
     ) {
         let connection_fut = TcpStream::connect(address);
 
-        let conn = match tokio::time::timeout(connection_timeout, connection_fut).await {
+        let stream = match tokio::time::timeout(connection_timeout, connection_fut).await {
             Ok(stream_res) => match stream_res {
                 Ok(stream) => {
                     debug!("Managed to establish connection to {}", address);
-                    // if we managed to connect, reset the reconnection count (whatever it might have been)
-                    current_reconnection.store(0, Ordering::Release);
-                    Framed::new(stream, NymCodec)
+                    stream
                 }
                 Err(err) => {
                     debug!(
@@ -119,6 +128,24 @@ impl Client {
             }
         };
 
+        let conn = match noise_config {
+            Some(noise_config) => match upgrade_noise_initiator(stream, &noise_config).await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    warn!("failed to perform noise handshake with {address} - {err}");
+
+                    // treat it the same way as failing to connect in the first place
+                    current_reconnection.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+            },
+            None => Connection::Tcp(stream),
+        };
+
+        // if we managed to connect, reset the reconnection count (whatever it might have been)
+        current_reconnection.store(0, Ordering::Release);
+        let conn = Framed::new(conn, NymCodec);
+
         // Take whatever the receiver channel produces and put it on the connection.
         // We could have as well used conn.send_all(receiver.map(Ok)), but considering we don't care
         // about neither receiver nor the connection, it doesn't matter which one gets consumed
@@ -174,6 +201,7 @@ impl Client {
 
         // copy the value before moving into another task
         let initial_connection_timeout = self.config.initial_connection_timeout;
+        let noise_config = self.config.noise_config.clone();
 
         tokio::spawn(async move {
             // before executing the manager, wait for what was specified, if anything
@@ -186,6 +214,7 @@ impl Client {
                 address.into(),
                 receiver,
                 initial_connection_timeout,
+                noise_config,
                 &current_reconnection_attempt,
             )
             .await
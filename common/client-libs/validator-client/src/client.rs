@@ -18,7 +18,7 @@ use nym_api_requests::models::{
     GatewayCoreStatusResponse, MixnodeCoreStatusResponse, MixnodeStatusResponse,
     RewardEstimationResponse, StakeSaturationResponse,
 };
-use nym_api_requests::nym_nodes::SkimmedNode;
+use nym_api_requests::nym_nodes::{NodesDelta, SemiSkimmedNode, SkimmedNode};
 use nym_network_defaults::NymNetworkDetails;
 use url::Url;
 
@@ -288,6 +288,91 @@ impl NymApiClient {
             .nodes)
     }
 
+    /// Retrieves the set of active mixnodes unless it hasn't changed since the response tagged with the provided `etag`.
+    pub async fn get_basic_mixnodes_if_none_match(
+        &self,
+        semver_compatibility: Option<String>,
+        etag: Option<String>,
+    ) -> Result<nym_api::Conditional<Vec<SkimmedNode>>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_basic_mixnodes_if_none_match(semver_compatibility, etag)
+            .await?
+            .map(|res| res.nodes))
+    }
+
+    /// Retrieves the set of gateways unless it hasn't changed since the response tagged with the provided `etag`.
+    pub async fn get_basic_gateways_if_none_match(
+        &self,
+        semver_compatibility: Option<String>,
+        etag: Option<String>,
+    ) -> Result<nym_api::Conditional<Vec<SkimmedNode>>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_basic_gateways_if_none_match(semver_compatibility, etag)
+            .await?
+            .map(|res| res.nodes))
+    }
+
+    /// Retrieves changes to the set of active mixnodes since the provided revision.
+    /// If the revision is not provided (or is no longer known by the nym-api), the returned delta
+    /// contains the full set of nodes.
+    pub async fn get_basic_mixnodes_delta(
+        &self,
+        since: Option<String>,
+        semver_compatibility: Option<String>,
+    ) -> Result<NodesDelta<SkimmedNode>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_basic_mixnodes_delta(since, semver_compatibility)
+            .await?)
+    }
+
+    /// Retrieves changes to the set of gateways since the provided revision.
+    /// If the revision is not provided (or is no longer known by the nym-api), the returned delta
+    /// contains the full set of nodes.
+    pub async fn get_basic_gateways_delta(
+        &self,
+        since: Option<String>,
+        semver_compatibility: Option<String>,
+    ) -> Result<NodesDelta<SkimmedNode>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_basic_gateways_delta(since, semver_compatibility)
+            .await?)
+    }
+
+    /// Subscribes to the stream of changes in the network state, such as epoch transitions,
+    /// pushed by the nym-api as they happen.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn subscribe_network_events(
+        &self,
+    ) -> Result<nym_api::events::NetworkEventStream, ValidatorClientError> {
+        Ok(nym_api::events::NetworkEventStream::subscribe(self.api_url()).await?)
+    }
+
+    pub async fn get_expanded_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<SemiSkimmedNode>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_expanded_mixnodes(semver_compatibility)
+            .await?
+            .nodes)
+    }
+
+    pub async fn get_expanded_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<SemiSkimmedNode>, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .get_expanded_gateways(semver_compatibility)
+            .await?
+            .nodes)
+    }
+
     pub async fn get_cached_active_mixnodes(
         &self,
     ) -> Result<Vec<MixNodeDetails>, ValidatorClientError> {
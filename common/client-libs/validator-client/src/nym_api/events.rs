@@ -0,0 +1,161 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Subscription to the server-sent events stream of network changes exposed by the nym-api.
+
+use crate::nym_api::error::NymAPIError;
+use crate::nym_api::routes;
+use nym_api_requests::events::NetworkEvent;
+use nym_http_api_client::{sanitize_url, NO_PARAMS};
+use reqwest::header::ACCEPT;
+use reqwest::Response;
+use std::collections::VecDeque;
+use url::Url;
+
+const EVENT_STREAM_CONTENT_TYPE: &str = "text/event-stream";
+
+/// A single message received over the server-sent events stream.
+#[derive(Debug, Default, PartialEq)]
+struct SseMessage {
+    event: Option<String>,
+    data: String,
+}
+
+/// Minimal incremental parser of the server-sent events wire format.
+/// It only understands the fields the nym-api is actually sending, i.e. `event` and `data`.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) {
+        // normalise the line endings so that we'd only have to look for '\n'
+        self.buffer.extend(chunk.iter().filter(|b| **b != b'\r'));
+    }
+
+    fn next_message(&mut self) -> Option<SseMessage> {
+        loop {
+            let end = self.buffer.windows(2).position(|w| w == b"\n\n")?;
+            let raw = self.buffer.drain(..end + 2).collect::<Vec<_>>();
+            let raw = String::from_utf8_lossy(&raw);
+
+            let mut message = SseMessage::default();
+            let mut data_lines = Vec::new();
+            for line in raw.lines() {
+                // empty lines separate messages and lines starting with ':' are comments
+                // (used as keep-alive heartbeats)
+                if line.is_empty() || line.starts_with(':') {
+                    continue;
+                }
+                let (field, value) = line.split_once(':').unwrap_or((line, ""));
+                let value = value.strip_prefix(' ').unwrap_or(value);
+                match field {
+                    "event" => message.event = Some(value.to_string()),
+                    "data" => data_lines.push(value),
+                    _ => (),
+                }
+            }
+
+            // messages without any data are not dispatched
+            if !data_lines.is_empty() {
+                message.data = data_lines.join("\n");
+                return Some(message);
+            }
+        }
+    }
+}
+
+/// Stream of [`NetworkEvent`]s pushed by the nym-api.
+pub struct NetworkEventStream {
+    response: Response,
+    parser: SseParser,
+    pending: VecDeque<SseMessage>,
+}
+
+impl NetworkEventStream {
+    /// Opens a new subscription to the network events of the nym-api at the provided address.
+    pub async fn subscribe(nym_api: &Url) -> Result<Self, NymAPIError> {
+        let url = sanitize_url(
+            nym_api,
+            &[routes::API_VERSION, routes::UNSTABLE, routes::EVENTS],
+            NO_PARAMS,
+        );
+
+        // note: we're explicitly not using the shared client here as it has an overall
+        // request timeout set whilst this connection is meant to stay open indefinitely
+        let response = reqwest::Client::new()
+            .get(url)
+            .header(ACCEPT, EVENT_STREAM_CONTENT_TYPE)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(NymAPIError::RequestFailure { status });
+        }
+
+        Ok(NetworkEventStream {
+            response,
+            parser: SseParser::default(),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Waits for the next event. Returns `None` once the nym-api has closed the stream.
+    pub async fn next(&mut self) -> Option<Result<NetworkEvent, NymAPIError>> {
+        loop {
+            if let Some(message) = self.pending.pop_front() {
+                return Some(serde_json::from_str(&message.data).map_err(|err| {
+                    NymAPIError::GenericRequestFailure(format!(
+                        "received malformed network event {:?}: {err}",
+                        message.event
+                    ))
+                }));
+            }
+
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    self.parser.push(&chunk);
+                    while let Some(message) = self.parser.next_message() {
+                        self.pending.push_back(message)
+                    }
+                }
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_sse_messages() {
+        let mut parser = SseParser::default();
+        assert!(parser.next_message().is_none());
+
+        // heartbeats and partial messages don't produce anything
+        parser.push(b":\n\nevent: lagged\r\ndata: {\"type\":");
+        assert!(parser.next_message().is_none());
+
+        parser.push(b"\"lagged\",\"missed\":3}\r\n\r\nevent:foo\ndata:bar\ndata:baz\n\n");
+        assert_eq!(
+            parser.next_message(),
+            Some(SseMessage {
+                event: Some("lagged".to_string()),
+                data: "{\"type\":\"lagged\",\"missed\":3}".to_string(),
+            })
+        );
+        assert_eq!(
+            parser.next_message(),
+            Some(SseMessage {
+                event: Some("foo".to_string()),
+                data: "bar\nbaz".to_string(),
+            })
+        );
+        assert!(parser.next_message().is_none());
+    }
+}
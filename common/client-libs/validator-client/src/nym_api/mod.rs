@@ -27,12 +27,14 @@ use nym_mixnet_contract_common::mixnode::MixNodeDetails;
 use nym_mixnet_contract_common::{GatewayBond, IdentityKeyRef, MixId};
 
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod events;
 pub mod routes;
 
 use nym_api_requests::coconut::models::FreePassNonceResponse;
 use nym_api_requests::coconut::FreePassRequest;
-use nym_api_requests::nym_nodes::{CachedNodesResponse, SkimmedNode};
-pub use nym_http_api_client::Client;
+use nym_api_requests::nym_nodes::{CachedNodesResponse, NodesDelta, SemiSkimmedNode, SkimmedNode};
+pub use nym_http_api_client::{Client, Conditional};
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -142,6 +144,162 @@ pub trait NymApiClientExt: ApiClient {
         .await
     }
 
+    /// Retrieves the set of active mixnodes unless it hasn't changed since the response tagged with the provided `etag`.
+    /// The `ETag` doesn't depend on the refresh time, so `refreshed_at` of the previously
+    /// received response should not be relied on after getting [`Conditional::NotModified`].
+    async fn get_basic_mixnodes_if_none_match(
+        &self,
+        semver_compatibility: Option<String>,
+        etag: Option<String>,
+    ) -> Result<Conditional<CachedNodesResponse<SkimmedNode>>, NymAPIError> {
+        let params = if let Some(semver_compatibility) = &semver_compatibility {
+            vec![("semver_compatibility", semver_compatibility.as_str())]
+        } else {
+            vec![]
+        };
+
+        self.get_json_if_none_match(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "mixnodes",
+                "skimmed",
+            ],
+            &params,
+            etag.as_deref(),
+        )
+        .await
+    }
+
+    /// Retrieves the set of gateways unless it hasn't changed since the response tagged with the provided `etag`.
+    /// The `ETag` doesn't depend on the refresh time, so `refreshed_at` of the previously
+    /// received response should not be relied on after getting [`Conditional::NotModified`].
+    async fn get_basic_gateways_if_none_match(
+        &self,
+        semver_compatibility: Option<String>,
+        etag: Option<String>,
+    ) -> Result<Conditional<CachedNodesResponse<SkimmedNode>>, NymAPIError> {
+        let params = if let Some(semver_compatibility) = &semver_compatibility {
+            vec![("semver_compatibility", semver_compatibility.as_str())]
+        } else {
+            vec![]
+        };
+
+        self.get_json_if_none_match(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "gateways",
+                "skimmed",
+            ],
+            &params,
+            etag.as_deref(),
+        )
+        .await
+    }
+
+    async fn get_basic_mixnodes_delta(
+        &self,
+        since: Option<String>,
+        semver_compatibility: Option<String>,
+    ) -> Result<NodesDelta<SkimmedNode>, NymAPIError> {
+        let mut params = Vec::new();
+        if let Some(since) = &since {
+            params.push((SINCE_ARG, since.as_str()));
+        }
+        if let Some(semver_compatibility) = &semver_compatibility {
+            params.push(("semver_compatibility", semver_compatibility.as_str()));
+        }
+
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "mixnodes",
+                "skimmed",
+                "delta",
+            ],
+            &params,
+        )
+        .await
+    }
+
+    async fn get_basic_gateways_delta(
+        &self,
+        since: Option<String>,
+        semver_compatibility: Option<String>,
+    ) -> Result<NodesDelta<SkimmedNode>, NymAPIError> {
+        let mut params = Vec::new();
+        if let Some(since) = &since {
+            params.push((SINCE_ARG, since.as_str()));
+        }
+        if let Some(semver_compatibility) = &semver_compatibility {
+            params.push(("semver_compatibility", semver_compatibility.as_str()));
+        }
+
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "gateways",
+                "skimmed",
+                "delta",
+            ],
+            &params,
+        )
+        .await
+    }
+
+    async fn get_expanded_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<CachedNodesResponse<SemiSkimmedNode>, NymAPIError> {
+        let params = if let Some(semver_compatibility) = &semver_compatibility {
+            vec![("semver_compatibility", semver_compatibility.as_str())]
+        } else {
+            vec![]
+        };
+
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "mixnodes",
+                "semi-skimmed",
+            ],
+            &params,
+        )
+        .await
+    }
+
+    async fn get_expanded_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<CachedNodesResponse<SemiSkimmedNode>, NymAPIError> {
+        let params = if let Some(semver_compatibility) = &semver_compatibility {
+            vec![("semver_compatibility", semver_compatibility.as_str())]
+        } else {
+            vec![]
+        };
+
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "gateways",
+                "semi-skimmed",
+            ],
+            &params,
+        )
+        .await
+    }
+
     async fn get_active_mixnodes(&self) -> Result<Vec<MixNodeDetails>, NymAPIError> {
         self.get_json(
             &[routes::API_VERSION, routes::MIXNODES, routes::ACTIVE],
@@ -40,3 +40,6 @@ pub const STAKE_SATURATION: &str = "stake-saturation";
 pub const INCLUSION_CHANCE: &str = "inclusion-probability";
 
 pub const SERVICE_PROVIDERS: &str = "services";
+
+pub const UNSTABLE: &str = "unstable";
+pub const EVENTS: &str = "events";
@@ -3,5 +3,6 @@
 
 pub mod coconut;
 pub mod context;
+pub mod topology;
 pub mod utils;
 pub mod validator;
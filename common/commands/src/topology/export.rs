@@ -0,0 +1,48 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::QueryClientWithNyxd;
+use anyhow::bail;
+use clap::Parser;
+use log::info;
+use nym_crypto::asymmetric::identity;
+use nym_topology::{NymTopology, TopologySnapshot};
+use nym_validator_client::client::NymApiClientExt;
+use nym_validator_client::nyxd::contract_traits::MixnetQueryClient;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Path to the ed25519 private key (in PEM format) used for signing the snapshot
+    #[clap(long)]
+    pub private_key: PathBuf,
+
+    /// Path to the output file of the signed snapshot
+    #[clap(long)]
+    pub output: PathBuf,
+}
+
+pub async fn execute(args: Args, client: QueryClientWithNyxd) -> anyhow::Result<()> {
+    let private_key: identity::PrivateKey = nym_pemstore::load_key(&args.private_key)?;
+
+    let interval = client.nyxd.get_current_interval_details().await?.interval;
+    let mixnodes = client.nym_api.get_basic_mixnodes(None).await?.nodes;
+    let gateways = client.nym_api.get_basic_gateways(None).await?.nodes;
+
+    let topology = NymTopology::from_unordered(mixnodes.iter(), gateways.iter());
+    if topology.mixes().is_empty() || topology.gateways().is_empty() {
+        bail!("the retrieved network topology is empty")
+    }
+
+    let epoch_id = interval.current_epoch_absolute_id();
+    let signed =
+        TopologySnapshot::new(topology, epoch_id, interval.current_epoch_end()).sign(&private_key);
+    signed.save_to_file(&args.output)?;
+
+    info!(
+        "exported topology snapshot of epoch {epoch_id} signed by {} to {}",
+        signed.signer,
+        args.output.display()
+    );
+    Ok(())
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_trait::async_trait;
-use reqwest::header::HeaderValue;
+use reqwest::header::{HeaderValue, ETAG, IF_NONE_MATCH};
 use reqwest::{RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -55,6 +55,31 @@ pub enum HttpClientError<E: Display = String> {
     RequestTimeout,
 }
 
+/// Outcome of a request made with the `If-None-Match` header.
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// The resource has changed (or the server doesn't support conditional requests).
+    Modified { value: T, etag: Option<String> },
+
+    /// The resource still corresponds to the provided entity tag.
+    NotModified,
+}
+
+impl<T> Conditional<T> {
+    pub fn map<U, F>(self, f: F) -> Conditional<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Conditional::Modified { value, etag } => Conditional::Modified {
+                value: f(value),
+                etag,
+            },
+            Conditional::NotModified => Conditional::NotModified,
+        }
+    }
+}
+
 pub struct ClientBuilder {
     url: Url,
     timeout: Option<Duration>,
@@ -286,6 +311,47 @@ impl Client {
         parse_response(res, false).await
     }
 
+    /// 'get' json data from the segment-defined path unless it still corresponds to the provided entity tag.
+    pub async fn get_json_if_none_match<T, K, V, E>(
+        &self,
+        path: PathSegments<'_>,
+        params: Params<'_, K, V>,
+        etag: Option<&str>,
+    ) -> Result<Conditional<T>, HttpClientError<E>>
+    where
+        for<'a> T: Deserialize<'a>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+        E: Display + DeserializeOwned,
+    {
+        let mut request = self.create_get_request(path, params);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        let res = {
+            wasmtimer::tokio::timeout(self.request_timeout, request.send())
+                .await
+                .map_err(|_timeout| HttpClientError::RequestTimeout)??
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let res = request.send().await?;
+
+        if res.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
+
+        let etag = res
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToString::to_string);
+        let value = parse_response(res, false).await?;
+        Ok(Conditional::Modified { value, etag })
+    }
+
     pub async fn post_json<B, T, K, V, E>(
         &self,
         path: PathSegments<'_>,
@@ -386,6 +452,20 @@ pub trait ApiClient {
         V: AsRef<str> + Sync,
         E: Display + DeserializeOwned;
 
+    /// 'get' json data from the segment-defined path unless it still corresponds to the provided entity tag,
+    /// i.e. the value of the `ETag` header returned alongside the previous response.
+    async fn get_json_if_none_match<T, K, V, E>(
+        &self,
+        path: PathSegments<'_>,
+        params: Params<'_, K, V>,
+        etag: Option<&str>,
+    ) -> Result<Conditional<T>, HttpClientError<E>>
+    where
+        for<'a> T: Deserialize<'a>,
+        K: AsRef<str> + Sync,
+        V: AsRef<str> + Sync,
+        E: Display + DeserializeOwned;
+
     async fn post_json<B, T, K, V, E>(
         &self,
         path: PathSegments<'_>,
@@ -435,6 +515,21 @@ impl ApiClient for Client {
         self.get_json(path, params).await
     }
 
+    async fn get_json_if_none_match<T, K, V, E>(
+        &self,
+        path: PathSegments<'_>,
+        params: Params<'_, K, V>,
+        etag: Option<&str>,
+    ) -> Result<Conditional<T>, HttpClientError<E>>
+    where
+        for<'a> T: Deserialize<'a>,
+        K: AsRef<str> + Sync,
+        V: AsRef<str> + Sync,
+        E: Display + DeserializeOwned,
+    {
+        self.get_json_if_none_match(path, params, etag).await
+    }
+
     async fn post_json<B, T, K, V, E>(
         &self,
         path: PathSegments<'_>,
@@ -3,3 +3,4 @@
 
 pub mod error;
 pub mod processor;
+pub mod replay_protection;
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::packet_processor::error::MixProcessingError;
+use crate::packet_processor::replay_protection::{ReplayDetector, ReplayProtectionConfig};
 use log::*;
-use nym_metrics::nanos;
+use nym_metrics::{inc, nanos};
 use nym_sphinx_acknowledgements::surb_ack::SurbAck;
 use nym_sphinx_addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx_forwarding::packet::MixPacket;
@@ -38,14 +39,41 @@ pub enum MixProcessingResult {
 pub struct SphinxPacketProcessor {
     /// Private sphinx key of this node required to unwrap received sphinx packet.
     sphinx_key: Arc<PrivateKey>,
+
+    /// Filter of all packets processed with the current sphinx key used for rejecting replays.
+    /// Since it's tied to the key, it gets reset whenever the key (and thus the processor) changes.
+    replay_detector: Option<Arc<ReplayDetector>>,
 }
 
 impl SphinxPacketProcessor {
     /// Creates new instance of `CachedPacketProcessor`
-    pub fn new(sphinx_key: PrivateKey) -> Self {
+    pub fn new(sphinx_key: PrivateKey, replay_protection: ReplayProtectionConfig) -> Self {
+        let replay_detector = if replay_protection.enabled {
+            Some(Arc::new(ReplayDetector::new(replay_protection)))
+        } else {
+            warn!("replay protection is disabled - replayed packets are going to be forwarded");
+            None
+        };
+
         SphinxPacketProcessor {
             sphinx_key: Arc::new(sphinx_key),
+            replay_detector,
+        }
+    }
+
+    /// Ensures the packet with the provided tag hasn't been processed before.
+    /// Note: it must only be called for packets that have been successfully unwrapped,
+    /// otherwise anyone could pollute the filter with garbage.
+    fn check_replay(&self, replay_tag: Option<Vec<u8>>) -> Result<(), MixProcessingError> {
+        let (Some(detector), Some(tag)) = (&self.replay_detector, replay_tag) else {
+            return Ok(());
+        };
+
+        if detector.check_and_insert(&tag) {
+            inc!("replayed_packets_detected");
+            return Err(MixProcessingError::ReplayedPacket);
         }
+        Ok(())
     }
 
     /// Performs a fresh sphinx unwrapping using no cache.
@@ -61,14 +89,22 @@ impl SphinxPacketProcessor {
         })
     }
 
-    /// Takes the received framed packet and tries to unwrap it from the sphinx encryption.
+    /// Takes the received framed packet and tries to unwrap it from the sphinx encryption,
+    /// rejecting it if it has already been seen before.
     fn perform_initial_unwrapping(
         &self,
         received: FramedNymPacket,
     ) -> Result<NymProcessedPacket, MixProcessingError> {
         nanos!("perform_initial_unwrapping", {
             let packet = received.into_inner();
-            self.perform_initial_packet_processing(packet)
+            let replay_tag = self
+                .replay_detector
+                .as_ref()
+                .and_then(|_| packet.replay_tag().map(|tag| tag.to_vec()));
+
+            let processed = self.perform_initial_packet_processing(packet)?;
+            self.check_replay(replay_tag)?;
+            Ok(processed)
         })
     }
 
@@ -243,10 +279,173 @@ impl SphinxPacketProcessor {
 mod tests {
     use super::*;
     use nym_sphinx_types::crypto::keygen;
+    use nym_sphinx_types::{
+        Destination, Node, PublicKey, DESTINATION_ADDRESS_LENGTH, IDENTIFIER_LENGTH,
+    };
+    use std::net::SocketAddr;
+
+    fn test_replay_protection() -> ReplayProtectionConfig {
+        ReplayProtectionConfig {
+            enabled: true,
+            memory_budget: 64 * 1024,
+            false_positive_rate: 1e-6,
+        }
+    }
 
     fn fixture() -> SphinxPacketProcessor {
         let local_keys = keygen();
-        SphinxPacketProcessor::new(local_keys.0)
+        SphinxPacketProcessor::new(local_keys.0, test_replay_protection())
+    }
+
+    fn local_keys() -> (PrivateKey, [u8; 32]) {
+        let (private_key, public_key) = keygen();
+        (private_key, *public_key.as_bytes())
+    }
+
+    fn hop_address(hop: usize) -> SocketAddr {
+        format!("1.2.3.{}:1789", hop + 1).parse().unwrap()
+    }
+
+    fn test_route(first_hop: [u8; 32], hops: usize) -> Vec<Node> {
+        (0..hops)
+            .map(|i| {
+                let address = NymNodeRoutingAddress::from(hop_address(i))
+                    .try_into()
+                    .unwrap();
+                let key = if i == 0 {
+                    PublicKey::from(first_hop)
+                } else {
+                    keygen().1
+                };
+                Node::new(address, key)
+            })
+            .collect()
+    }
+
+    fn test_destination() -> Destination {
+        Destination::new(
+            DestinationAddressBytes::from_bytes([3u8; DESTINATION_ADDRESS_LENGTH]),
+            [4u8; IDENTIFIER_LENGTH],
+        )
+    }
+
+    fn make_forward_sphinx_packet(first_hop: [u8; 32]) -> MixPacket {
+        let route = test_route(first_hop, 3);
+        let delays = vec![SphinxDelay::new_from_nanos(42); 3];
+        let packet = NymPacket::sphinx_build(
+            PacketSize::RegularPacket.payload_size(),
+            b"foomp",
+            &route,
+            &test_destination(),
+            &delays,
+        )
+        .unwrap();
+
+        let next_hop = NymNodeRoutingAddress::from(hop_address(0));
+        MixPacket::new(next_hop, packet, PacketType::Mix)
+    }
+
+    fn make_forward_outfox_packet(first_hop: [u8; 32]) -> MixPacket {
+        let route = test_route(first_hop, 4);
+        let packet = NymPacket::outfox_build(
+            vec![1; 48],
+            &route,
+            &test_destination(),
+            Some(PacketSize::OutfoxRegularPacket.plaintext_size()),
+        )
+        .unwrap();
+
+        let next_hop = NymNodeRoutingAddress::from(hop_address(0));
+        MixPacket::new(next_hop, packet, PacketType::Outfox)
+    }
+
+    fn duplicate(packet: MixPacket) -> (FramedNymPacket, FramedNymPacket) {
+        let packet_type = packet.packet_type();
+        let bytes = packet.into_bytes().unwrap();
+        let first = MixPacket::try_from_bytes(&bytes).unwrap();
+        let second = MixPacket::try_from_bytes(&bytes).unwrap();
+
+        (
+            FramedNymPacket::new(first.into_packet(), packet_type, false),
+            FramedNymPacket::new(second.into_packet(), packet_type, false),
+        )
+    }
+
+    #[test]
+    fn replayed_sphinx_packet_is_rejected() {
+        let (local_private, local_public) = local_keys();
+        let processor = SphinxPacketProcessor::new(local_private, test_replay_protection());
+
+        let (original, replay) = duplicate(make_forward_sphinx_packet(local_public));
+
+        assert!(matches!(
+            processor.process_received(original),
+            Ok(MixProcessingResult::ForwardHop(..))
+        ));
+        assert!(matches!(
+            processor.process_received(replay),
+            Err(MixProcessingError::ReplayedPacket)
+        ));
+    }
+
+    #[test]
+    fn replayed_outfox_packet_is_rejected() {
+        let (local_private, local_public) = local_keys();
+        let processor = SphinxPacketProcessor::new(local_private, test_replay_protection());
+
+        let (original, replay) = duplicate(make_forward_outfox_packet(local_public));
+
+        assert!(matches!(
+            processor.process_received(original),
+            Ok(MixProcessingResult::ForwardHop(..))
+        ));
+        assert!(matches!(
+            processor.process_received(replay),
+            Err(MixProcessingError::ReplayedPacket)
+        ));
+    }
+
+    #[test]
+    fn replay_is_detected_across_processor_clones() {
+        let (local_private, local_public) = local_keys();
+        let processor = SphinxPacketProcessor::new(local_private, test_replay_protection());
+        let cloned = processor.clone();
+
+        let (original, replay) = duplicate(make_forward_sphinx_packet(local_public));
+
+        assert!(processor.process_received(original).is_ok());
+        assert!(matches!(
+            cloned.process_received(replay),
+            Err(MixProcessingError::ReplayedPacket)
+        ));
+    }
+
+    #[test]
+    fn replays_are_forwarded_with_protection_disabled() {
+        let (local_private, local_public) = local_keys();
+        let processor = SphinxPacketProcessor::new(
+            local_private,
+            ReplayProtectionConfig {
+                enabled: false,
+                ..test_replay_protection()
+            },
+        );
+
+        let (original, replay) = duplicate(make_forward_sphinx_packet(local_public));
+
+        assert!(processor.process_received(original).is_ok());
+        assert!(processor.process_received(replay).is_ok());
+    }
+
+    #[test]
+    fn distinct_packets_are_not_considered_replays() {
+        let (local_private, local_public) = local_keys();
+        let processor = SphinxPacketProcessor::new(local_private, test_replay_protection());
+
+        for _ in 0..10 {
+            let (packet, _) = duplicate(make_forward_sphinx_packet(local_public));
+            assert!(processor.process_received(packet).is_ok());
+        }
     }
 
     #[tokio::test]
@@ -0,0 +1,287 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::{debug, warn};
+use nym_metrics::inc;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+
+const DEFAULT_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+const DEFAULT_FALSE_POSITIVE_RATE: f64 = 1e-5;
+
+// beyond this point there's no meaningful reduction in the false positive rate,
+// but every lookup becomes more expensive
+const MAX_HASH_FUNCTIONS: u32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayProtectionConfig {
+    /// Specifies whether received packets should be checked for being replayed.
+    pub enabled: bool,
+
+    /// Maximum amount of memory (in bytes) that can be used by the replay detection filters.
+    pub memory_budget: usize,
+
+    /// Target probability of a fresh packet being incorrectly rejected as a replay.
+    pub false_positive_rate: f64,
+}
+
+impl Default for ReplayProtectionConfig {
+    fn default() -> Self {
+        ReplayProtectionConfig {
+            enabled: true,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            false_positive_rate: DEFAULT_FALSE_POSITIVE_RATE,
+        }
+    }
+}
+
+impl ReplayProtectionConfig {
+    /// Number of bits available to a single filter generation.
+    fn bits_per_filter(&self) -> u64 {
+        // we keep two generations around at any given time
+        ((self.memory_budget / 2) as u64 * 8).max(64)
+    }
+
+    /// Optimal number of hash functions for the target false positive rate.
+    fn hash_functions(&self) -> u32 {
+        let k = (-self.false_positive_rate.log2()).ceil();
+        if k.is_nan() {
+            return 1;
+        }
+        (k as u32).clamp(1, MAX_HASH_FUNCTIONS)
+    }
+
+    /// Number of tags that can be inserted into a single generation before its false positive
+    /// rate exceeds the configured target.
+    fn generation_capacity(&self) -> usize {
+        let bits = self.bits_per_filter() as f64;
+        let capacity = bits * std::f64::consts::LN_2.powi(2) / -self.false_positive_rate.ln();
+        if capacity.is_nan() {
+            return 1;
+        }
+        (capacity as usize).max(1)
+    }
+}
+
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    items: usize,
+}
+
+impl BloomFilter {
+    fn new(num_bits: u64, num_hashes: u32) -> Self {
+        let words = num_bits.div_ceil(64) as usize;
+        BloomFilter {
+            bits: vec![0; words],
+            num_bits: words as u64 * 64,
+            num_hashes,
+            items: 0,
+        }
+    }
+
+    // uses Kirsch-Mitzenmacher double hashing to derive all bit positions from just two hashes
+    fn bit_positions(
+        num_bits: u64,
+        num_hashes: u32,
+        h1: u64,
+        h2: u64,
+    ) -> impl Iterator<Item = (usize, u64)> {
+        (0..num_hashes as u64).map(move |i| {
+            let idx = h1.wrapping_add(i.wrapping_mul(h2)) % num_bits;
+            ((idx / 64) as usize, 1 << (idx % 64))
+        })
+    }
+
+    fn contains(&self, h1: u64, h2: u64) -> bool {
+        Self::bit_positions(self.num_bits, self.num_hashes, h1, h2)
+            .all(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    /// Inserts the item into the filter and returns whether it was (probably) already present.
+    fn check_and_insert(&mut self, h1: u64, h2: u64) -> bool {
+        let mut present = true;
+        for (word, mask) in Self::bit_positions(self.num_bits, self.num_hashes, h1, h2) {
+            if self.bits[word] & mask == 0 {
+                present = false;
+                self.bits[word] |= mask;
+            }
+        }
+
+        if !present {
+            self.items += 1;
+        }
+        present
+    }
+
+    fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+        self.items = 0;
+    }
+}
+
+struct Generations {
+    current: BloomFilter,
+    previous: BloomFilter,
+}
+
+/// Rotating bloom filter of replay tags of all packets successfully processed with the current
+/// sphinx key.
+///
+/// Once the current generation reaches its capacity it replaces the previous one, so that
+/// tags are remembered for at least a full generation while memory usage stays bounded.
+/// Note that tags are only meaningful for a particular sphinx key, so the detector must be
+/// reset whenever the key gets rotated.
+pub struct ReplayDetector {
+    generations: Mutex<Generations>,
+    generation_capacity: usize,
+
+    // keyed hashers, so that remote parties couldn't craft tags deliberately colliding
+    // with other packets
+    primary_hasher: RandomState,
+    secondary_hasher: RandomState,
+}
+
+impl ReplayDetector {
+    pub fn new(config: ReplayProtectionConfig) -> Self {
+        let num_bits = config.bits_per_filter();
+        let num_hashes = config.hash_functions();
+        let generation_capacity = config.generation_capacity();
+
+        debug!(
+            "creating replay detector with {num_bits} bits and {num_hashes} hashes per generation (capacity: {generation_capacity} packets)"
+        );
+
+        ReplayDetector {
+            generations: Mutex::new(Generations {
+                current: BloomFilter::new(num_bits, num_hashes),
+                previous: BloomFilter::new(num_bits, num_hashes),
+            }),
+            generation_capacity,
+            primary_hasher: RandomState::new(),
+            secondary_hasher: RandomState::new(),
+        }
+    }
+
+    fn hashes(&self, tag: &[u8]) -> (u64, u64) {
+        let h1 = self.primary_hasher.hash_one(tag);
+        // make sure the step is never zero so that we'd always check `num_hashes` distinct bits
+        let h2 = self.secondary_hasher.hash_one(tag) | 1;
+        (h1, h2)
+    }
+
+    /// Records the provided tag and returns whether it has (probably) been seen before.
+    pub fn check_and_insert(&self, tag: &[u8]) -> bool {
+        let (h1, h2) = self.hashes(tag);
+
+        // the lock is only ever held for the duration of few bit operations, so even if some thread
+        // panicked while holding it, the data is still going to be consistent
+        let mut guard = match self.generations.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if guard.previous.contains(h1, h2) {
+            return true;
+        }
+
+        if guard.current.check_and_insert(h1, h2) {
+            return true;
+        }
+
+        if guard.current.items >= self.generation_capacity {
+            debug!("rotating the replay detection filter");
+            inc!("replay_filter_rotations");
+            let generations = &mut *guard;
+            std::mem::swap(&mut generations.current, &mut generations.previous);
+            generations.current.clear();
+        }
+
+        false
+    }
+
+    /// Forgets all previously seen tags. It must be called whenever the sphinx key gets rotated.
+    pub fn reset(&self) {
+        let mut guard = match self.generations.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("the replay detection lock got poisoned");
+                poisoned.into_inner()
+            }
+        };
+        guard.current.clear();
+        guard.previous.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_config() -> ReplayProtectionConfig {
+        ReplayProtectionConfig {
+            enabled: true,
+            memory_budget: 4096,
+            false_positive_rate: 1e-6,
+        }
+    }
+
+    #[test]
+    fn detects_repeated_tags() {
+        let detector = ReplayDetector::new(small_config());
+
+        assert!(!detector.check_and_insert(&[1u8; 32]));
+        assert!(!detector.check_and_insert(&[2u8; 32]));
+        assert!(detector.check_and_insert(&[1u8; 32]));
+        assert!(detector.check_and_insert(&[2u8; 32]));
+    }
+
+    #[test]
+    fn remembers_tags_from_previous_generation() {
+        let config = small_config();
+        let capacity = config.generation_capacity();
+        let detector = ReplayDetector::new(config);
+
+        let first = 0u64.to_be_bytes();
+        assert!(!detector.check_and_insert(&first));
+
+        // fill up the first generation to force rotation
+        for i in 1..capacity as u64 {
+            detector.check_and_insert(&i.to_be_bytes());
+        }
+        assert!(detector.check_and_insert(&first));
+
+        // and the subsequent one, after which the original tag is forgotten
+        for i in capacity as u64..(3 * capacity) as u64 {
+            detector.check_and_insert(&i.to_be_bytes());
+        }
+        assert!(!detector.check_and_insert(&first));
+    }
+
+    #[test]
+    fn reset_forgets_all_tags() {
+        let detector = ReplayDetector::new(small_config());
+
+        assert!(!detector.check_and_insert(&[1u8; 32]));
+        detector.reset();
+        assert!(!detector.check_and_insert(&[1u8; 32]));
+    }
+
+    #[test]
+    fn filter_parameters_are_derived_from_config() {
+        let config = ReplayProtectionConfig {
+            enabled: true,
+            memory_budget: 1024 * 1024,
+            false_positive_rate: 1e-6,
+        };
+
+        assert_eq!(config.bits_per_filter(), 4 * 1024 * 1024);
+        assert_eq!(config.hash_functions(), 20);
+        // ~28.8 bits per element
+        assert_eq!(config.generation_capacity(), 145_862);
+    }
+}
@@ -34,7 +34,7 @@ pub(crate) mod sender;
 // TODO: MUST BE UPDATED BEFORE ACTUAL RELEASE!!
 pub const MINIMUM_NODE_VERSION: &str = "0.10.1";
 
-// by default all of those are overwritten by config data from mixnodes directly
+// by default all of those are overwritten by config data from nodes directly
 const DEFAULT_VERLOC_PORT: u16 = 1790;
 const DEFAULT_PACKETS_PER_NODE: usize = 100;
 const DEFAULT_PACKET_TIMEOUT: Duration = Duration::from_millis(1500);
@@ -311,7 +311,6 @@ impl VerlocMeasurer {
 
         while !self.shutdown_listener.is_shutdown() {
             info!("Starting verloc measurements");
-            // TODO: should we also measure gateways?
 
             let all_mixes = match self.validator_client.get_cached_mixnodes().await {
                 Ok(nodes) => nodes,
@@ -325,38 +324,71 @@ impl VerlocMeasurer {
                     continue;
                 }
             };
-            if all_mixes.is_empty() {
+            let all_gateways = match self.validator_client.get_cached_described_gateways().await {
+                Ok(nodes) => nodes,
+                Err(err) => {
+                    warn!("failed to obtain list of gateways from the validator - {err}. Only mixnodes are going to be measured in this run");
+                    Vec::new()
+                }
+            };
+            if all_mixes.is_empty() && all_gateways.is_empty() {
                 warn!("There does not seem there are any nodes to measure...")
             }
 
             // we only care about address and identity
-            let tested_nodes = all_mixes
-                .into_iter()
-                .filter_map(|node| {
-                    let mix_node = node.bond_information.mix_node;
-                    // check if the node has sufficient version to be able to understand the packets
-                    let node_version = parse_version(&mix_node.version).ok()?;
-                    if node_version < self.config.minimum_compatible_node_version {
-                        return None;
-                    }
+            let tested_mixnodes = all_mixes.into_iter().filter_map(|node| {
+                let mix_node = node.bond_information.mix_node;
+                // check if the node has sufficient version to be able to understand the packets
+                let node_version = parse_version(&mix_node.version).ok()?;
+                if node_version < self.config.minimum_compatible_node_version {
+                    return None;
+                }
 
-                    // try to parse the identity and host
-                    let node_identity =
-                        identity::PublicKey::from_base58_string(mix_node.identity_key).ok()?;
-
-                    let verloc_host = (&*mix_node.host, mix_node.verloc_port)
-                        .to_socket_addrs()
-                        .ok()?
-                        .next()?;
-
-                    // TODO: possible problem in the future, this does name resolution and theoretically
-                    // if a lot of nodes maliciously mis-configured themselves, it might take a while to resolve them all
-                    // However, maybe it's not a problem as if they are misconfigured, they will eventually be
-                    // pushed out of the network and on top of that, verloc is done in separate task that runs
-                    // only every few hours.
-                    Some(TestedNode::new(verloc_host, node_identity))
-                })
-                .collect::<Vec<_>>();
+                // try to parse the identity and host
+                let node_identity =
+                    identity::PublicKey::from_base58_string(mix_node.identity_key).ok()?;
+
+                let verloc_host = (&*mix_node.host, mix_node.verloc_port)
+                    .to_socket_addrs()
+                    .ok()?
+                    .next()?;
+
+                // TODO: possible problem in the future, this does name resolution and theoretically
+                // if a lot of nodes maliciously mis-configured themselves, it might take a while to resolve them all
+                // However, maybe it's not a problem as if they are misconfigured, they will eventually be
+                // pushed out of the network and on top of that, verloc is done in separate task that runs
+                // only every few hours.
+                Some(TestedNode::new(verloc_host, node_identity))
+            });
+
+            // legacy gateways are not listening for the echo packets at all, so only bother with
+            // the nym-nodes that have announced their verloc port in the self-described API
+            let tested_gateways = all_gateways.into_iter().filter_map(|node| {
+                let verloc_port = node
+                    .self_described
+                    .as_ref()?
+                    .auxiliary_details
+                    .announced_verloc_port?;
+
+                let gateway = node.bond.gateway;
+                // check if the node has sufficient version to be able to understand the packets
+                let node_version = parse_version(&gateway.version).ok()?;
+                if node_version < self.config.minimum_compatible_node_version {
+                    return None;
+                }
+
+                let node_identity =
+                    identity::PublicKey::from_base58_string(gateway.identity_key).ok()?;
+
+                let verloc_host = (&*gateway.host, verloc_port)
+                    .to_socket_addrs()
+                    .ok()?
+                    .next()?;
+
+                Some(TestedNode::new(verloc_host, node_identity))
+            });
+
+            let tested_nodes = tested_mixnodes.chain(tested_gateways).collect::<Vec<_>>();
 
             // on start of each run remove old results
             self.state.start_new_measurements(tested_nodes.len()).await;
@@ -78,12 +78,12 @@ where
         let fragment = self.message_receiver.recover_fragment(plaintext)?;
 
         // test messages must consist of a single fragment
-        let (serialized, _) = self
+        let recovered = self
             .message_receiver
             .insert_new_fragment(fragment)?
             .ok_or(NetworkTestingError::NonReconstructablePacket)?;
 
-        TestMessage::try_recover(serialized)
+        TestMessage::try_recover(recovered.message)
     }
 
     pub fn process_ack(
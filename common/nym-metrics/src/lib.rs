@@ -4,7 +4,9 @@ use log::{debug, warn};
 use std::fmt;
 pub use std::time::Instant;
 
-use prometheus::{core::Collector, Encoder as _, IntCounter, IntGauge, Registry, TextEncoder};
+use prometheus::{
+    core::Collector, Encoder as _, Gauge, IntCounter, IntGauge, Registry, TextEncoder,
+};
 
 #[macro_export]
 macro_rules! prepend_package_name {
@@ -35,6 +37,13 @@ macro_rules! inc {
     };
 }
 
+#[macro_export]
+macro_rules! set_float {
+    ($name:literal, $x:expr) => {
+        $crate::REGISTRY.set_float($crate::prepend_package_name!($name), $x as f64);
+    };
+}
+
 #[macro_export]
 macro_rules! metrics {
     () => {
@@ -68,6 +77,7 @@ pub struct MetricsController {
 enum Metric {
     C(Box<IntCounter>),
     G(Box<IntGauge>),
+    F(Box<Gauge>),
 }
 
 fn fq_name(c: &dyn Collector) -> String {
@@ -83,6 +93,7 @@ impl Metric {
         match self {
             Metric::C(c) => fq_name(c.as_ref()),
             Metric::G(g) => fq_name(g.as_ref()),
+            Metric::F(f) => fq_name(f.as_ref()),
         }
     }
 
@@ -91,6 +102,7 @@ impl Metric {
         match self {
             Metric::C(c) => c.inc(),
             Metric::G(g) => g.inc(),
+            Metric::F(f) => f.inc(),
         }
     }
 
@@ -99,6 +111,7 @@ impl Metric {
         match self {
             Metric::C(c) => c.inc_by(value as u64),
             Metric::G(g) => g.add(value),
+            Metric::F(f) => f.add(value as f64),
         }
     }
 
@@ -109,6 +122,20 @@ impl Metric {
                 warn!("Cannot set value for counter {:?}", self.fq_name());
             }
             Metric::G(g) => g.set(value),
+            Metric::F(f) => f.set(value as f64),
+        }
+    }
+
+    #[inline(always)]
+    fn set_float(&self, value: f64) {
+        match self {
+            Metric::F(f) => f.set(value),
+            _ => {
+                warn!(
+                    "Cannot set float value for integer metric {:?}",
+                    self.fq_name()
+                );
+            }
         }
     }
 }
@@ -161,6 +188,22 @@ impl MetricsController {
         }
     }
 
+    pub fn set_float(&self, name: &str, value: f64) {
+        if let Some(metric) = self.registry_index.get(name) {
+            metric.set_float(value);
+        } else {
+            let gauge = match Gauge::new(sanitize_metric_name(name), name) {
+                Ok(g) => g,
+                Err(e) => {
+                    debug!("Failed to create gauge {:?}:\n{}", name, e);
+                    return;
+                }
+            };
+            self.register_float_gauge(Box::new(gauge));
+            self.set_float(name, value)
+        }
+    }
+
     pub fn inc(&self, name: &str) {
         if let Some(metric) = self.registry_index.get(name) {
             metric.inc();
@@ -215,6 +258,28 @@ impl MetricsController {
         }
     }
 
+    fn register_float_gauge(&self, metric: Box<Gauge>) {
+        let fq_name = metric
+            .desc()
+            .first()
+            .map(|d| d.fq_name.clone())
+            .unwrap_or_default();
+
+        if self.registry_index.contains_key(&fq_name) {
+            return;
+        }
+
+        match self.registry.register(metric.clone()) {
+            Ok(_) => {
+                self.registry_index
+                    .insert(fq_name, Metric::F(metric.clone()));
+            }
+            Err(e) => {
+                debug!("Failed to register {:?}:\n{}", fq_name, e)
+            }
+        }
+    }
+
     fn register_counter(&self, metric: Box<IntCounter>) {
         let fq_name = metric
             .desc()
@@ -275,4 +340,15 @@ mod tests {
             "packets_sent_34_242_65_133:1789"
         )
     }
+
+    #[test]
+    fn float_gauges_keep_latest_value() {
+        let controller = MetricsController::default();
+        controller.set_float("packets_sent_rate", 1.5);
+        controller.set_float("packets_sent_rate", 2.25);
+
+        let exported = controller.to_string();
+        assert!(exported.contains("# TYPE packets_sent_rate gauge"));
+        assert!(exported.contains("packets_sent_rate 2.25"));
+    }
 }
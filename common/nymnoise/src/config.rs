@@ -0,0 +1,160 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::warn;
+use nym_crypto::asymmetric::encryption;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Noise keys of all nodes in the network, as advertised in their self-described data,
+/// indexed by their mixnet listening addresses (so that multiple nodes could share the same ip).
+///
+/// Nodes that do not have an entry in here are assumed not to support the noise protocol.
+#[derive(Clone, Default)]
+pub struct NoiseNetworkView {
+    keys: Arc<RwLock<HashMap<SocketAddr, encryption::PublicKey>>>,
+}
+
+impl NoiseNetworkView {
+    pub fn new_empty() -> Self {
+        Default::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<SocketAddr, encryption::PublicKey>> {
+        // the lock is never held across any fallible operations, so the data can't be corrupted
+        match self.keys.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<SocketAddr, encryption::PublicKey>> {
+        match self.keys.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("the noise network view lock got poisoned");
+                poisoned.into_inner()
+            }
+        }
+    }
+
+    /// Replaces the entire known network with the provided one.
+    pub fn swap_view(&self, new: HashMap<SocketAddr, encryption::PublicKey>) {
+        *self.write() = new
+    }
+
+    pub fn get_noise_key(&self, address: &SocketAddr) -> Option<encryption::PublicKey> {
+        self.read().get(address).copied()
+    }
+
+    /// Checks whether the provided key has been advertised by any node listening on the provided ip.
+    /// It's used by the responders that only learn the (ephemeral) source address of the remote.
+    pub fn is_known_noise_key(&self, ip: IpAddr, key: &encryption::PublicKey) -> bool {
+        let ip = canonical_ip(ip);
+        self.read()
+            .iter()
+            .any(|(address, known)| canonical_ip(address.ip()) == ip && known == key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// dual-stack listeners see ipv4 peers as ipv4-mapped ipv6 addresses
+fn canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        ip => ip,
+    }
+}
+
+#[derive(Clone)]
+pub struct NoiseConfig {
+    pub(crate) local_key: Arc<encryption::KeyPair>,
+    pub(crate) network: NoiseNetworkView,
+    pub(crate) handshake_timeout: Duration,
+
+    /// Specifies whether the noise protocol should be skipped for all outbound connections,
+    /// i.e. they should be established in plaintext.
+    /// Note that inbound noise connections are still going to be accepted.
+    pub(crate) unsafe_disabled: bool,
+}
+
+impl NoiseConfig {
+    pub fn new(local_key: Arc<encryption::KeyPair>, network: NoiseNetworkView) -> Self {
+        NoiseConfig {
+            local_key,
+            network,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            unsafe_disabled: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_unsafe_disabled(mut self, unsafe_disabled: bool) -> Self {
+        self.unsafe_disabled = unsafe_disabled;
+        self
+    }
+
+    pub fn network(&self) -> &NoiseNetworkView {
+        &self.network
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nodes_sharing_ip_address_keep_their_own_keys() {
+        let mut rng = rand::thread_rng();
+        let first = *encryption::KeyPair::new(&mut rng).public_key();
+        let second = *encryption::KeyPair::new(&mut rng).public_key();
+
+        let first_address: SocketAddr = "1.2.3.4:1789".parse().unwrap();
+        let second_address: SocketAddr = "1.2.3.4:1790".parse().unwrap();
+
+        let view = NoiseNetworkView::new_empty();
+        view.swap_view(HashMap::from([
+            (first_address, first),
+            (second_address, second),
+        ]));
+
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.get_noise_key(&first_address), Some(first));
+        assert_eq!(view.get_noise_key(&second_address), Some(second));
+        assert!(view
+            .get_noise_key(&"1.2.3.4:1791".parse().unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn noise_keys_of_inbound_peers_are_matched_by_ip() {
+        let mut rng = rand::thread_rng();
+        let known = *encryption::KeyPair::new(&mut rng).public_key();
+        let unknown = *encryption::KeyPair::new(&mut rng).public_key();
+
+        let view = NoiseNetworkView::new_empty();
+        view.swap_view(HashMap::from([("1.2.3.4:1789".parse().unwrap(), known)]));
+
+        assert!(view.is_known_noise_key("1.2.3.4".parse().unwrap(), &known));
+        assert!(view.is_known_noise_key("::ffff:1.2.3.4".parse().unwrap(), &known));
+        assert!(!view.is_known_noise_key("1.2.3.5".parse().unwrap(), &known));
+        assert!(!view.is_known_noise_key("1.2.3.4".parse().unwrap(), &unknown));
+    }
+}
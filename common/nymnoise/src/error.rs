@@ -0,0 +1,33 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io;
+use std::net::IpAddr;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NoiseError {
+    #[error("encountered a noise protocol error: {0}")]
+    ProtocolError(#[from] snow::Error),
+
+    #[error("encountered an io error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("the remote has sent an unexpected noise preamble")]
+    InvalidPreamble,
+
+    #[error("the remote uses an unsupported version of the noise handshake: {received}")]
+    UnsupportedVersion { received: u8 },
+
+    #[error("the handshake did not complete within {0:?}")]
+    HandshakeTimeout(Duration),
+
+    #[error("the noise handshake has not been completed")]
+    IncompleteHandshake,
+
+    #[error(
+        "the remote at {remote} has authenticated with a key that doesn't belong to any known node"
+    )]
+    UnknownRemoteKey { remote: IpAddr },
+}
@@ -0,0 +1,169 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Noise XK encryption of the links between nodes in the mixnet.
+//!
+//! The initiator must know the static x25519 key of the responder in advance (as advertised
+//! in its self-described data), while the responder learns the key of the initiator during
+//! the handshake and checks it against the known network. If the initiator does not know
+//! the key of the remote, the connection falls back to plaintext so that nodes that do not
+//! support noise yet could still be reached.
+
+use crate::stream::{receive_handshake_message, send_handshake_message, NoiseStream};
+use log::debug;
+use nym_crypto::asymmetric::encryption;
+use snow::{Builder, HandshakeState};
+use std::future::Future;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+pub mod config;
+pub mod connection;
+pub mod error;
+pub mod stream;
+
+pub use config::{NoiseConfig, NoiseNetworkView};
+pub use connection::Connection;
+pub use error::NoiseError;
+
+const NOISE_PATTERN: &str = "Noise_XK_25519_ChaChaPoly_SHA256";
+
+// The first byte of the preamble is chosen so that it could never be a valid first byte of a
+// plaintext mix packet, i.e. it doesn't correspond to any known packet size (legacy header)
+// nor any packet version we've ever used. This allows responders to accept both noise
+// and plaintext connections on the same listener.
+const NOISE_MARKER: u8 = 0xff;
+const NOISE_VERSION: u8 = 1;
+const NOISE_PREAMBLE: [u8; 4] = [NOISE_MARKER, b'N', b'Z', NOISE_VERSION];
+
+fn handshake_builder(local_private_key: &[u8]) -> Builder<'_> {
+    // the pattern is a valid constant, so this can't fail
+    #[allow(clippy::unwrap_used)]
+    let params = NOISE_PATTERN.parse().unwrap();
+
+    Builder::new(params)
+        .local_private_key(local_private_key)
+        // bind the handshake to the preamble so that it couldn't have been tampered with
+        .prologue(&NOISE_PREAMBLE)
+}
+
+fn into_transport(conn: TcpStream, handshake: HandshakeState) -> Result<Connection, NoiseError> {
+    if !handshake.is_handshake_finished() {
+        return Err(NoiseError::IncompleteHandshake);
+    }
+    let transport = handshake.into_transport_mode()?;
+    let stream = NoiseStream::new(conn, transport);
+    Ok(Connection::Noise(Box::new(stream)))
+}
+
+async fn with_timeout<F>(config: &NoiseConfig, fut: F) -> Result<Connection, NoiseError>
+where
+    F: Future<Output = Result<Connection, NoiseError>>,
+{
+    match tokio::time::timeout(config.handshake_timeout, fut).await {
+        Ok(res) => res,
+        Err(_) => Err(NoiseError::HandshakeTimeout(config.handshake_timeout)),
+    }
+}
+
+async fn initiator_handshake(
+    mut conn: TcpStream,
+    config: &NoiseConfig,
+    remote_key: encryption::PublicKey,
+) -> Result<Connection, NoiseError> {
+    let local_private_key = config.local_key.private_key().to_bytes();
+    let remote_public_key = remote_key.to_bytes();
+    let mut handshake = handshake_builder(&local_private_key)
+        .remote_public_key(&remote_public_key)
+        .build_initiator()?;
+
+    conn.write_all(&NOISE_PREAMBLE).await?;
+
+    // -> e, es
+    send_handshake_message(&mut conn, &mut handshake).await?;
+    // <- e, ee
+    receive_handshake_message(&mut conn, &mut handshake).await?;
+    // -> s, se
+    send_handshake_message(&mut conn, &mut handshake).await?;
+
+    into_transport(conn, handshake)
+}
+
+async fn responder_handshake(
+    mut conn: TcpStream,
+    config: &NoiseConfig,
+) -> Result<Connection, NoiseError> {
+    let mut marker = [0u8; 1];
+    if conn.peek(&mut marker).await? == 0 || marker[0] != NOISE_MARKER {
+        // either the connection got closed already or the remote is sending plaintext packets
+        debug!("the remote hasn't initiated the noise handshake - falling back to plaintext");
+        return Ok(Connection::Tcp(conn));
+    }
+
+    let mut preamble = [0u8; NOISE_PREAMBLE.len()];
+    conn.read_exact(&mut preamble).await?;
+    if preamble[..3] != NOISE_PREAMBLE[..3] {
+        return Err(NoiseError::InvalidPreamble);
+    }
+    if preamble[3] != NOISE_VERSION {
+        return Err(NoiseError::UnsupportedVersion {
+            received: preamble[3],
+        });
+    }
+
+    let local_private_key = config.local_key.private_key().to_bytes();
+    let mut handshake = handshake_builder(&local_private_key).build_responder()?;
+
+    // -> e, es
+    receive_handshake_message(&mut conn, &mut handshake).await?;
+    // <- e, ee
+    send_handshake_message(&mut conn, &mut handshake).await?;
+    // -> s, se
+    receive_handshake_message(&mut conn, &mut handshake).await?;
+
+    // only the nodes present in the network are allowed to establish noise connections
+    let remote = conn.peer_addr()?.ip();
+    let remote_key = handshake
+        .get_remote_static()
+        .and_then(|key| encryption::PublicKey::from_bytes(key).ok());
+    match remote_key {
+        Some(key) if config.network.is_known_noise_key(remote, &key) => {}
+        _ => return Err(NoiseError::UnknownRemoteKey { remote }),
+    }
+
+    into_transport(conn, handshake)
+}
+
+/// Attempts to upgrade the newly established outbound connection to use noise.
+///
+/// If noise has been disabled or the key of the remote is unknown, the connection is left
+/// in plaintext.
+pub async fn upgrade_noise_initiator(
+    conn: TcpStream,
+    config: &NoiseConfig,
+) -> Result<Connection, NoiseError> {
+    if config.unsafe_disabled {
+        debug!("noise is disabled - the connection is going to be established in plaintext");
+        return Ok(Connection::Tcp(conn));
+    }
+
+    let remote = conn.peer_addr()?;
+    let Some(remote_key) = config.network.get_noise_key(&remote) else {
+        debug!("{remote} doesn't support noise - falling back to plaintext");
+        return Ok(Connection::Tcp(conn));
+    };
+
+    with_timeout(config, initiator_handshake(conn, config, remote_key)).await
+}
+
+/// Attempts to upgrade the newly accepted inbound connection to use noise.
+///
+/// The noise handshake is only performed if the remote has initiated it. Otherwise the
+/// connection is left in plaintext. The handshake is rejected if the static key of the remote
+/// hasn't been advertised by any node at its ip address.
+pub async fn upgrade_noise_responder(
+    conn: TcpStream,
+    config: &NoiseConfig,
+) -> Result<Connection, NoiseError> {
+    with_timeout(config, responder_handshake(conn, config)).await
+}
@@ -0,0 +1,209 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::NoiseError;
+use bytes::{Buf, BufMut, BytesMut};
+use snow::{HandshakeState, TransportState};
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::TcpStream;
+
+// every noise message is prefixed with its length encoded as big endian u16
+const FRAME_LEN_PREFIX: usize = 2;
+const MAX_NOISE_MESSAGE_LEN: usize = 65535;
+const TAG_LEN: usize = 16;
+const MAX_FRAME_PAYLOAD_LEN: usize = MAX_NOISE_MESSAGE_LEN - TAG_LEN;
+
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Wrapper around an underlying connection (normally a TCP stream) encrypting and decrypting
+/// all data going through it using an already established noise transport state.
+pub struct NoiseStream<C = TcpStream> {
+    inner: C,
+    transport: TransportState,
+
+    // raw, still encrypted, bytes received from the remote
+    read_buf: BytesMut,
+
+    // decrypted bytes that haven't been consumed yet
+    decrypted: BytesMut,
+
+    // encrypted frames that haven't yet been written to the underlying connection
+    write_buf: BytesMut,
+}
+
+impl<C> NoiseStream<C>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    pub(crate) fn new(inner: C, transport: TransportState) -> Self {
+        NoiseStream {
+            inner,
+            transport,
+            read_buf: BytesMut::new(),
+            decrypted: BytesMut::new(),
+            write_buf: BytesMut::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns the static public key of the remote, if it was learned during the handshake.
+    pub fn remote_static(&self) -> Option<&[u8]> {
+        self.transport.get_remote_static()
+    }
+
+    /// Attempts to decrypt the next complete frame from the buffered data.
+    /// Returns whether any frame got decrypted.
+    fn try_decrypt_frame(&mut self) -> io::Result<bool> {
+        if self.read_buf.len() < FRAME_LEN_PREFIX {
+            return Ok(false);
+        }
+
+        let frame_len = u16::from_be_bytes([self.read_buf[0], self.read_buf[1]]) as usize;
+        if self.read_buf.len() < FRAME_LEN_PREFIX + frame_len {
+            self.read_buf
+                .reserve(FRAME_LEN_PREFIX + frame_len - self.read_buf.len());
+            return Ok(false);
+        }
+
+        self.read_buf.advance(FRAME_LEN_PREFIX);
+        let frame = self.read_buf.split_to(frame_len);
+
+        let mut plaintext = vec![0u8; frame_len];
+        let n = self
+            .transport
+            .read_message(&frame, &mut plaintext)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.decrypted.extend_from_slice(&plaintext[..n]);
+        Ok(true)
+    }
+
+    fn poll_drain_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buf.advance(n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<C> AsyncRead for NoiseStream<C>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if !this.decrypted.is_empty() {
+                let n = this.decrypted.len().min(buf.remaining());
+                buf.put_slice(&this.decrypted.split_to(n));
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.try_decrypt_frame()? {
+                continue;
+            }
+
+            let mut chunk = [0u8; READ_CHUNK_SIZE];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut chunk_buf))?;
+
+            let filled = chunk_buf.filled();
+            if filled.is_empty() {
+                // the remote has closed the connection
+                return if this.read_buf.is_empty() {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()))
+                };
+            }
+            this.read_buf.extend_from_slice(filled);
+        }
+    }
+}
+
+impl<C> AsyncWrite for NoiseStream<C>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // make sure we don't keep buffering data if the remote isn't reading it
+        ready!(this.poll_drain_write_buf(cx))?;
+
+        let payload_len = buf.len().min(MAX_FRAME_PAYLOAD_LEN);
+        let mut ciphertext = vec![0u8; payload_len + TAG_LEN];
+        let n = this
+            .transport
+            .write_message(&buf[..payload_len], &mut ciphertext)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        this.write_buf.reserve(FRAME_LEN_PREFIX + n);
+        this.write_buf.put_u16(n as u16);
+        this.write_buf.put_slice(&ciphertext[..n]);
+
+        Poll::Ready(Ok(payload_len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain_write_buf(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain_write_buf(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+pub(crate) async fn send_handshake_message<C>(
+    conn: &mut C,
+    handshake: &mut HandshakeState,
+) -> Result<(), NoiseError>
+where
+    C: AsyncWrite + Unpin,
+{
+    let mut buf = vec![0u8; MAX_NOISE_MESSAGE_LEN];
+    let len = handshake.write_message(&[], &mut buf)?;
+
+    conn.write_u16(len as u16).await?;
+    conn.write_all(&buf[..len]).await?;
+    conn.flush().await?;
+    Ok(())
+}
+
+pub(crate) async fn receive_handshake_message<C>(
+    conn: &mut C,
+    handshake: &mut HandshakeState,
+) -> Result<(), NoiseError>
+where
+    C: AsyncRead + Unpin,
+{
+    let len = conn.read_u16().await? as usize;
+    let mut message = vec![0u8; len];
+    conn.read_exact(&mut message).await?;
+
+    let mut payload = vec![0u8; MAX_NOISE_MESSAGE_LEN];
+    handshake.read_message(&message, &mut payload)?;
+    Ok(())
+}
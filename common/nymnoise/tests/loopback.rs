@@ -0,0 +1,213 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_crypto::asymmetric::encryption;
+use nym_noise::{
+    upgrade_noise_initiator, upgrade_noise_responder, Connection, NoiseConfig, NoiseError,
+    NoiseNetworkView,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+struct Node {
+    keys: Arc<encryption::KeyPair>,
+    network: NoiseNetworkView,
+}
+
+impl Node {
+    fn new() -> Self {
+        let mut rng = rand::thread_rng();
+        Node {
+            keys: Arc::new(encryption::KeyPair::new(&mut rng)),
+            network: NoiseNetworkView::new_empty(),
+        }
+    }
+
+    fn config(&self) -> NoiseConfig {
+        NoiseConfig::new(self.keys.clone(), self.network.clone())
+    }
+
+    fn learn(&self, remote: SocketAddr, key: encryption::PublicKey) {
+        self.network.swap_view(HashMap::from([(remote, key)]));
+    }
+
+    // inbound connections come from ephemeral ports, so any port on the loopback will do
+    fn learn_local(&self, key: encryption::PublicKey) {
+        self.learn("127.0.0.1:1789".parse().unwrap(), key)
+    }
+}
+
+// sends the payload from the initiator and echoes it back from the responder
+async fn exchange(
+    initiator: &Node,
+    responder: &Node,
+    know_responder: bool,
+    payload: &[u8],
+) -> (bool, bool, Vec<u8>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    if know_responder {
+        initiator.learn(address, *responder.keys.public_key());
+    }
+    responder.learn_local(*initiator.keys.public_key());
+
+    let responder_config = responder.config();
+    let expected_len = payload.len();
+    let responder_task = tokio::spawn(async move {
+        let (conn, _) = listener.accept().await.unwrap();
+        let mut conn = upgrade_noise_responder(conn, &responder_config)
+            .await
+            .unwrap();
+        let mut received = vec![0u8; expected_len];
+        conn.read_exact(&mut received).await.unwrap();
+        conn.write_all(&received).await.unwrap();
+        conn.flush().await.unwrap();
+        conn.is_noise()
+    });
+
+    let conn = TcpStream::connect(address).await.unwrap();
+    let mut conn: Connection = upgrade_noise_initiator(conn, &initiator.config())
+        .await
+        .unwrap();
+    conn.write_all(payload).await.unwrap();
+    conn.flush().await.unwrap();
+
+    let mut echoed = vec![0u8; payload.len()];
+    conn.read_exact(&mut echoed).await.unwrap();
+
+    let responder_noise = responder_task.await.unwrap();
+    (conn.is_noise(), responder_noise, echoed)
+}
+
+#[tokio::test]
+async fn connection_is_encrypted_if_responder_key_is_known() {
+    let initiator = Node::new();
+    let responder = Node::new();
+
+    let payload = b"hello noise".to_vec();
+    let (initiator_noise, responder_noise, echoed) =
+        exchange(&initiator, &responder, true, &payload).await;
+
+    assert!(initiator_noise);
+    assert!(responder_noise);
+    assert_eq!(echoed, payload);
+}
+
+#[tokio::test]
+async fn payloads_larger_than_single_noise_message_are_supported() {
+    let initiator = Node::new();
+    let responder = Node::new();
+
+    let payload: Vec<u8> = (0..200_000).map(|i| i as u8).collect();
+    let (initiator_noise, responder_noise, echoed) =
+        exchange(&initiator, &responder, true, &payload).await;
+
+    assert!(initiator_noise);
+    assert!(responder_noise);
+    assert_eq!(echoed, payload);
+}
+
+#[tokio::test]
+async fn connection_falls_back_to_plaintext_if_responder_key_is_unknown() {
+    let initiator = Node::new();
+    let responder = Node::new();
+
+    let payload = b"hello plaintext".to_vec();
+    let (initiator_noise, responder_noise, echoed) =
+        exchange(&initiator, &responder, false, &payload).await;
+
+    assert!(!initiator_noise);
+    assert!(!responder_noise);
+    assert_eq!(echoed, payload);
+}
+
+#[tokio::test]
+async fn connection_is_plaintext_if_noise_is_disabled() {
+    let initiator = Node::new();
+    let responder = Node::new();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    initiator.learn(address, *responder.keys.public_key());
+
+    let responder_config = responder.config();
+    let responder_task = tokio::spawn(async move {
+        let (conn, _) = listener.accept().await.unwrap();
+        let mut conn = upgrade_noise_responder(conn, &responder_config)
+            .await
+            .unwrap();
+        let mut received = [0u8; 4];
+        conn.read_exact(&mut received).await.unwrap();
+        conn.is_noise()
+    });
+
+    let config = initiator.config().with_unsafe_disabled(true);
+    let conn = TcpStream::connect(address).await.unwrap();
+    let mut conn = upgrade_noise_initiator(conn, &config).await.unwrap();
+    conn.write_all(&[1, 2, 3, 4]).await.unwrap();
+
+    assert!(!conn.is_noise());
+    assert!(!responder_task.await.unwrap());
+}
+
+#[tokio::test]
+async fn handshake_fails_with_wrong_responder_key() {
+    let initiator = Node::new();
+    let responder = Node::new();
+    let impostor = Node::new();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    initiator.learn(address, *impostor.keys.public_key());
+
+    let responder_config = responder.config();
+    let responder_task = tokio::spawn(async move {
+        let (conn, _) = listener.accept().await.unwrap();
+        upgrade_noise_responder(conn, &responder_config)
+            .await
+            .is_err()
+    });
+
+    let conn = TcpStream::connect(address).await.unwrap();
+    let initiator_res = upgrade_noise_initiator(conn, &initiator.config()).await;
+
+    // the responder can't decrypt the very first message since it was addressed to a different key
+    assert!(responder_task.await.unwrap());
+    assert!(initiator_res.is_err());
+}
+
+#[tokio::test]
+async fn handshake_fails_with_unknown_initiator_key() {
+    let initiator = Node::new();
+    let responder = Node::new();
+    let impostor = Node::new();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    initiator.learn(address, *responder.keys.public_key());
+    responder.learn_local(*impostor.keys.public_key());
+
+    let responder_config = responder.config();
+    let responder_task = tokio::spawn(async move {
+        let (conn, _) = listener.accept().await.unwrap();
+        upgrade_noise_responder(conn, &responder_config).await
+    });
+
+    let conn = TcpStream::connect(address).await.unwrap();
+    let mut conn = upgrade_noise_initiator(conn, &initiator.config())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        responder_task.await.unwrap(),
+        Err(NoiseError::UnknownRemoteKey { .. })
+    ));
+
+    // the responder has dropped the connection without ever talking to us
+    let _ = conn.write_all(b"hello").await;
+    let mut buf = [0u8; 1];
+    assert!(!matches!(conn.read(&mut buf).await, Ok(n) if n > 0));
+}
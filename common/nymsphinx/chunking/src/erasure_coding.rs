@@ -0,0 +1,265 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fragment::{erasure_coded_fragment_payload_len, Fragment};
+use crate::set::{generate_set_id, FragmentSet};
+use rand::Rng;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+
+/// Erasure coded sets follow the same restriction as the regular ones, i.e. they can't contain
+/// more than 255 fragments in total (including the parity fragments).
+const MAX_SET_FRAGMENTS: usize = u8::MAX as usize;
+
+/// Maximum supported ratio of parity fragments to data fragments.
+pub const MAX_REDUNDANCY: f64 = 4.0;
+
+fn sanitize_redundancy(redundancy: f64) -> f64 {
+    if redundancy.is_finite() {
+        redundancy.clamp(0., MAX_REDUNDANCY)
+    } else {
+        0.
+    }
+}
+
+/// Number of parity fragments created for a set with the specified number of data fragments.
+/// There's always at least a single parity fragment.
+pub fn parity_fragments(data_fragments: usize, redundancy: f64) -> usize {
+    let parity = (data_fragments as f64 * sanitize_redundancy(redundancy)).ceil() as usize;
+    parity.max(1)
+}
+
+/// Maximum number of data fragments in a single set so that, alongside its parity fragments,
+/// it would not exceed the maximum set size.
+pub fn max_data_fragments_per_set(redundancy: f64) -> usize {
+    (1..MAX_SET_FRAGMENTS)
+        .rev()
+        .find(|&data_fragments| {
+            data_fragments + parity_fragments(data_fragments, redundancy) <= MAX_SET_FRAGMENTS
+        })
+        .unwrap_or(1)
+}
+
+/// Returns number of fragments (both data and parity) the message will be split into.
+pub fn number_of_required_erasure_coded_fragments(
+    message_len: usize,
+    max_plaintext_size: usize,
+    redundancy: f64,
+) -> usize {
+    let payload_len = erasure_coded_fragment_payload_len(max_plaintext_size);
+    let data_fragments = usize::max(1, (message_len as f64 / payload_len as f64).ceil() as usize);
+    let per_set = max_data_fragments_per_set(redundancy);
+
+    let full_sets = data_fragments / per_set;
+    let remaining = data_fragments % per_set;
+
+    let mut total = full_sets * (per_set + parity_fragments(per_set, redundancy));
+    if remaining > 0 {
+        total += remaining + parity_fragments(remaining, redundancy);
+    }
+    total
+}
+
+fn prepare_erasure_coded_set(
+    data: &[u8],
+    id: i32,
+    previous_link_id: Option<i32>,
+    next_link_id: Option<i32>,
+    max_plaintext_size: usize,
+    redundancy: f64,
+) -> FragmentSet {
+    let payload_len = erasure_coded_fragment_payload_len(max_plaintext_size);
+
+    // all shards have to be of equal length, so the final one is padded with zeroes
+    let mut shards: Vec<_> = data
+        .chunks(payload_len)
+        .map(|chunk| {
+            let mut shard = chunk.to_vec();
+            shard.resize(payload_len, 0);
+            shard
+        })
+        .collect();
+    if shards.is_empty() {
+        shards.push(vec![0; payload_len]);
+    }
+
+    let data_fragments = shards.len();
+    let parity = parity_fragments(data_fragments, redundancy);
+    debug_assert!(data_fragments + parity <= MAX_SET_FRAGMENTS);
+    shards.resize(data_fragments + parity, vec![0; payload_len]);
+
+    // the number of shards is always within the bounds and all of them have the same, non-zero, length
+    let encoder = ReedSolomon::new(data_fragments, parity).unwrap();
+    encoder.encode(&mut shards).unwrap();
+
+    let total_fragments = shards.len() as u8;
+    shards
+        .iter()
+        .enumerate()
+        .map(|(i, shard)| {
+            Fragment::try_new_erasure_coded(
+                shard,
+                id,
+                total_fragments,
+                i as u8 + 1,
+                data_fragments as u8,
+                previous_link_id,
+                next_link_id,
+                max_plaintext_size,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Splits the message into erasure coded `FragmentSet`s. Each set consists of data fragments
+/// followed by parity fragments and it can be recovered from any of its fragments as long as their
+/// number is equal to the number of the data fragments.
+///
+/// Note that unlike `split_into_sets`, the final data fragment is padded with zeroes,
+/// so the recipient has to be able to tell the padding apart from the underlying message.
+pub fn split_into_erasure_coded_sets<R: Rng>(
+    rng: &mut R,
+    message: &[u8],
+    max_plaintext_size: usize,
+    redundancy: f64,
+) -> Vec<FragmentSet> {
+    let set_payload_len = erasure_coded_fragment_payload_len(max_plaintext_size)
+        * max_data_fragments_per_set(redundancy);
+
+    let mut set_payloads: Vec<_> = message.chunks(set_payload_len).collect();
+    if set_payloads.is_empty() {
+        set_payloads.push(&[]);
+    }
+
+    let set_ids: Vec<_> = std::iter::repeat_with(|| generate_set_id(rng))
+        .take(set_payloads.len())
+        .collect();
+
+    set_payloads
+        .into_iter()
+        .enumerate()
+        .map(|(i, payload)| {
+            prepare_erasure_coded_set(
+                payload,
+                set_ids[i],
+                i.checked_sub(1).map(|previous| set_ids[previous]),
+                set_ids.get(i + 1).copied(),
+                max_plaintext_size,
+                redundancy,
+            )
+        })
+        .collect()
+}
+
+/// Attempts to recover the data of an erasure coded set out of its received fragment payloads.
+/// At least `data_fragments` of them have to be present.
+pub(crate) fn recover_set_data(
+    mut shards: Vec<Option<Vec<u8>>>,
+    data_fragments: usize,
+) -> Result<Vec<u8>, reed_solomon_erasure::Error> {
+    let parity = shards.len().saturating_sub(data_fragments);
+    let decoder = ReedSolomon::new(data_fragments, parity)?;
+    decoder.reconstruct_data(&mut shards)?;
+
+    Ok(shards
+        .into_iter()
+        .take(data_fragments)
+        .flatten()
+        .flatten()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, RngCore};
+
+    const AVAILABLE_PLAINTEXT_SIZE: usize = 1024;
+
+    fn random_message(len: usize) -> Vec<u8> {
+        let mut message = vec![0u8; len];
+        thread_rng().fill_bytes(&mut message);
+        message
+    }
+
+    #[test]
+    fn sets_never_exceed_maximum_size() {
+        for redundancy in [0., 0.1, 0.25, 0.5, 1., 2.5, 4., 100., f64::NAN] {
+            let per_set = max_data_fragments_per_set(redundancy);
+            assert!(per_set >= 1);
+            assert!(per_set + parity_fragments(per_set, redundancy) <= MAX_SET_FRAGMENTS);
+        }
+        assert_eq!(max_data_fragments_per_set(0.), 254);
+        assert_eq!(max_data_fragments_per_set(1.), 127);
+    }
+
+    #[test]
+    fn produces_expected_number_of_fragments() {
+        let payload_len = erasure_coded_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE);
+        for (len, redundancy) in [
+            (0, 0.25),
+            (10, 0.25),
+            (payload_len * 10, 0.25),
+            (payload_len * 10 + 1, 0.5),
+            (payload_len * 1000, 0.25),
+        ] {
+            let sets = split_into_erasure_coded_sets(
+                &mut thread_rng(),
+                &random_message(len),
+                AVAILABLE_PLAINTEXT_SIZE,
+                redundancy,
+            );
+            let fragments: usize = sets.iter().map(|set| set.len()).sum();
+            assert_eq!(
+                fragments,
+                number_of_required_erasure_coded_fragments(
+                    len,
+                    AVAILABLE_PLAINTEXT_SIZE,
+                    redundancy
+                )
+            );
+            for set in sets {
+                assert!(set.len() <= MAX_SET_FRAGMENTS);
+                for fragment in set {
+                    assert_eq!(fragment.into_bytes().len(), AVAILABLE_PLAINTEXT_SIZE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_data_can_be_recovered_from_any_sufficient_subset_of_fragments() {
+        let payload_len = erasure_coded_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE);
+        let message = random_message(payload_len * 8 - 10);
+
+        let mut sets = split_into_erasure_coded_sets(
+            &mut thread_rng(),
+            &message,
+            AVAILABLE_PLAINTEXT_SIZE,
+            0.5,
+        );
+        assert_eq!(sets.len(), 1);
+        let set = sets.pop().unwrap();
+        // 8 data fragments + 4 parity fragments
+        assert_eq!(set.len(), 12);
+
+        let payloads: Vec<_> = set.into_iter().map(|f| f.extract_payload()).collect();
+
+        // lose some data and some parity fragments
+        let mut shards: Vec<_> = payloads.iter().cloned().map(Some).collect();
+        shards[0] = None;
+        shards[5] = None;
+        shards[7] = None;
+        shards[10] = None;
+        let recovered = recover_set_data(shards, 8).unwrap();
+        assert_eq!(&recovered[..message.len()], message);
+        assert!(recovered[message.len()..].iter().all(|b| *b == 0));
+
+        // losing too many fragments makes the set unrecoverable
+        let mut shards: Vec<_> = payloads.into_iter().map(Some).collect();
+        for shard in shards.iter_mut().take(5) {
+            *shard = None;
+        }
+        assert!(recover_set_data(shards, 8).is_err());
+    }
+}
@@ -34,6 +34,19 @@ pub const UNLINKED_FRAGMENTED_HEADER_LEN: usize = 7;
 /// `Fragment` in a `FragmentSet`.
 pub const LINKED_FRAGMENTED_HEADER_LEN: usize = 10;
 
+/// Header of a `Fragment` belonging to an erasure coded `FragmentSet`. Apart from the standard
+/// 4 bytes for set id and 2 bytes for total and current fragments, it contains 1 byte marker
+/// indicating the set is erasure coded, 1 byte representing the number of data fragments
+/// (i.e. the minimum number of fragments required to recover the set) and 4 bytes for each
+/// id of the previous and the next set (or zeroes if the set is not linked).
+/// The links are included in every fragment as any of them might end up getting lost.
+pub const ERASURE_CODED_FRAGMENT_HEADER_LEN: usize = 16;
+
+/// Value of the byte directly following the fragment position that marks the header as belonging
+/// to an erasure coded set. It can't be confused with either of the existing headers as those
+/// have either a zero byte or a linked id with the highest bit set at that position.
+const ERASURE_CODED_MARKER: u8 = 1;
+
 /// Maximum size of payload of each fragment is always the maximum amount of plaintext data
 /// we can put into a sphinx packet minus length of respective fragment header.
 pub const fn unlinked_fragment_payload_max_len(max_plaintext_size: usize) -> usize {
@@ -46,6 +59,12 @@ pub const fn linked_fragment_payload_max_len(max_plaintext_size: usize) -> usize
     max_plaintext_size - LINKED_FRAGMENTED_HEADER_LEN
 }
 
+/// Size of payload of each erasure coded fragment. Note that unlike for the other fragment kinds
+/// it's always constant as all fragments in an erasure coded set have to be of equal length.
+pub const fn erasure_coded_fragment_payload_len(max_plaintext_size: usize) -> usize {
+    max_plaintext_size - ERASURE_CODED_FRAGMENT_HEADER_LEN
+}
+
 // TODO: should this be defined in this module or in `cover`? I can see arguments for both options...
 /// A special `FragmentIdentifier` that is not valid in all cases unless if it's used in a loop
 /// cover message.
@@ -75,6 +94,11 @@ impl fmt::Display for FragmentIdentifier {
 }
 
 impl FragmentIdentifier {
+    /// Id of the `FragmentSet` the fragment belongs to.
+    pub fn set_id(&self) -> i32 {
+        self.set_id
+    }
+
     pub fn to_bytes(self) -> SerializedFragmentIdentifier {
         debug_assert_eq!(FRAG_ID_LEN, 5);
 
@@ -191,6 +215,43 @@ impl Fragment {
         })
     }
 
+    /// Tries to encapsulate provided payload slice and metadata into a `Fragment` belonging
+    /// to an erasure coded `FragmentSet`. The payload has to have exactly the length
+    /// of `erasure_coded_fragment_payload_len`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_new_erasure_coded(
+        payload: &[u8],
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+        data_fragments: u8,
+        previous_fragments_set_id: Option<i32>,
+        next_fragments_set_id: Option<i32>,
+        max_plaintext_size: usize,
+    ) -> Result<Self, ChunkingError> {
+        let header = FragmentHeader::try_new_erasure_coded(
+            id,
+            total_fragments,
+            current_fragment,
+            data_fragments,
+            previous_fragments_set_id,
+            next_fragments_set_id,
+        )?;
+
+        let expected = erasure_coded_fragment_payload_len(max_plaintext_size);
+        if payload.len() != expected {
+            return Err(ChunkingError::InvalidPayloadLengthError {
+                received: payload.len(),
+                expected,
+            });
+        }
+
+        Ok(Fragment {
+            header,
+            payload: payload.to_vec(),
+        })
+    }
+
     /// based on the size of the embedded data, determines which predefined `PacketSize`
     /// was used for construction of this `Fragment`
     pub fn serialized_size(&self) -> usize {
@@ -247,6 +308,17 @@ impl Fragment {
         self.header.next_fragments_set_id
     }
 
+    /// If this `Fragment` belongs to an erasure coded `FragmentSet`, extracts the number of
+    /// fragments required to recover the set.
+    pub fn data_fragments(&self) -> Option<u8> {
+        self.header.data_fragments
+    }
+
+    /// Checks whether this `Fragment` belongs to an erasure coded `FragmentSet`.
+    pub fn is_erasure_coded(&self) -> bool {
+        self.header.data_fragments.is_some()
+    }
+
     /// Consumes `self` to obtain payload (i.e. part of original message) associated with this
     /// `Fragment`.
     pub(crate) fn extract_payload(self) -> Vec<u8> {
@@ -297,6 +369,11 @@ impl Fragment {
 /// there is 7 bytes of overhead inside each sphinx packet sent
 /// and for the longest messages, without upper bound, there is usually also only 7 bytes
 /// of overhead apart from first and last fragments in each set that instead have 10 bytes of overhead.
+///
+/// Finally, fragments of erasure coded sets use the following 16 byte sequence:
+/// '1'bit || 31-bit ID || 1-byte TF || 1 byte CF || '1'byte || 1-byte DF || 4-byte previous LID || 4-byte next LID
+/// where DF is the number of data fragments in the set and the remaining ones contain parity data.
+/// Zeroed LID means there's no link in given direction.
 #[derive(PartialEq, Clone, Debug)]
 pub(crate) struct FragmentHeader {
     /// ID associated with `FragmentSet` to which this particular `Fragment` belongs.
@@ -320,7 +397,12 @@ pub(crate) struct FragmentHeader {
 
     /// Optional ID of next `FragmentSet` into which the original message was split.
     /// Note, this option is only valid of `current_fragment == total_fragments == u8::max_value()`
+    /// unless the set is erasure coded.
     next_fragments_set_id: Option<i32>,
+
+    /// If the set is erasure coded, the number of data fragments in it, i.e. any `data_fragments`
+    /// out of `total_fragments` fragments are sufficient to recover the set.
+    data_fragments: Option<u8>,
 }
 
 impl FragmentHeader {
@@ -363,6 +445,46 @@ impl FragmentHeader {
             current_fragment,
             previous_fragments_set_id,
             next_fragments_set_id,
+            data_fragments: None,
+        })
+    }
+
+    /// Tries to create a new `FragmentHeader` of an erasure coded set. Unlike in the regular sets,
+    /// every fragment carries the links to both the previous and the next set.
+    fn try_new_erasure_coded(
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+        data_fragments: u8,
+        previous_fragments_set_id: Option<i32>,
+        next_fragments_set_id: Option<i32>,
+    ) -> Result<Self, ChunkingError> {
+        if id <= 0 || total_fragments == 0 || current_fragment == 0 {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        if total_fragments < current_fragment {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        // there has to be at least a single parity fragment
+        if data_fragments == 0 || data_fragments >= total_fragments {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        for linked_id in [previous_fragments_set_id, next_fragments_set_id]
+            .into_iter()
+            .flatten()
+        {
+            if linked_id <= 0 || linked_id == id {
+                return Err(ChunkingError::MalformedHeaderError);
+            }
+        }
+
+        Ok(FragmentHeader {
+            id,
+            total_fragments,
+            current_fragment,
+            previous_fragments_set_id,
+            next_fragments_set_id,
+            data_fragments: Some(data_fragments),
         })
     }
 
@@ -391,6 +513,10 @@ impl FragmentHeader {
             return Err(ChunkingError::MalformedHeaderError);
         }
 
+        if b[6] == ERASURE_CODED_MARKER {
+            return Self::try_erasure_coded_from_bytes(id, total_fragments, current_fragment, b);
+        }
+
         let mut previous_fragments_set_id = None;
         let mut next_fragments_set_id = None;
 
@@ -437,6 +563,44 @@ impl FragmentHeader {
         ))
     }
 
+    fn try_erasure_coded_from_bytes(
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+        b: &[u8],
+    ) -> Result<(Self, usize), ChunkingError> {
+        if b.len() < ERASURE_CODED_FRAGMENT_HEADER_LEN {
+            return Err(ChunkingError::TooShortFragmentHeader {
+                received: b.len(),
+                expected: ERASURE_CODED_FRAGMENT_HEADER_LEN,
+            });
+        }
+
+        let data_fragments = b[7];
+        let parse_link = |bytes: &[u8]| {
+            let mut id_bytes = [0u8; 4];
+            id_bytes.copy_from_slice(bytes);
+            match i32::from_be_bytes(id_bytes) {
+                0 => None,
+                linked_id => Some(linked_id),
+            }
+        };
+        let previous_fragments_set_id = parse_link(&b[8..12]);
+        let next_fragments_set_id = parse_link(&b[12..16]);
+
+        Ok((
+            Self::try_new_erasure_coded(
+                id,
+                total_fragments,
+                current_fragment,
+                data_fragments,
+                previous_fragments_set_id,
+                next_fragments_set_id,
+            )?,
+            ERASURE_CODED_FRAGMENT_HEADER_LEN,
+        ))
+    }
+
     /// Marshal this `FragmentHeader` into vector of bytes which can be put into a sphinx packet.
     fn to_bytes(&self) -> Vec<u8> {
         let frag_id = self.id | (1 << 31);
@@ -446,6 +610,16 @@ impl FragmentHeader {
             .chain(std::iter::once(self.total_fragments))
             .chain(std::iter::once(self.current_fragment));
 
+        if let Some(data_fragments) = self.data_fragments {
+            let previous = self.previous_fragments_set_id.unwrap_or_default();
+            let next = self.next_fragments_set_id.unwrap_or_default();
+            return bytes_prefix_iter
+                .chain([ERASURE_CODED_MARKER, data_fragments])
+                .chain(previous.to_be_bytes())
+                .chain(next.to_be_bytes())
+                .collect();
+        }
+
         let is_linked =
             self.previous_fragments_set_id.is_some() || self.next_fragments_set_id.is_some();
         if is_linked {
@@ -1027,6 +1201,7 @@ mod fragment_header {
                 current_fragment: 11,
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
+                data_fragments: None,
             };
             let header_bytes = header.to_bytes();
             assert!(FragmentHeader::try_from_bytes(&header_bytes).is_err());
@@ -1041,6 +1216,7 @@ mod fragment_header {
                 current_fragment: 0,
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
+                data_fragments: None,
             };
             let header_bytes = header.to_bytes();
             assert!(FragmentHeader::try_from_bytes(&header_bytes).is_err());
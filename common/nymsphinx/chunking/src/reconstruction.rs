@@ -1,9 +1,21 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
+use crate::erasure_coding::recover_set_data;
 use crate::fragment::Fragment;
 use crate::ChunkingError;
 use log::*;
+use nym_sphinx_params::reconstruction::{
+    DEFAULT_MAXIMUM_BUFFERED_FRAGMENTS_SIZE, DEFAULT_MAXIMUM_BUFFERED_FRAGMENT_SETS,
+    DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE,
+};
 use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
 
 // TODO: perhaps a more sophisticated approach with writing to disk periodically in case
 // we're receiving fast & furious in uncompressed 4K - we don't want to keep that in memory;
@@ -20,17 +32,30 @@ struct ReconstructionBuffer {
     is_complete: bool,
 
     /// Once all fragments are received, the value of `previous_fragments_set_id` is copied
-    /// from the first `Fragment` in the set.
+    /// from the first `Fragment` in the set (or any `Fragment` if the set is erasure coded).
     previous_fragments_set_id: Option<i32>,
     /// Once all fragments are received, the value of `next_fragments_set_id` is copied
     /// from the last `Fragment` in the set (assuming the set is full, i.e. it contains
-    /// `u8::max_value()` elements).
+    /// `u8::max_value()` elements), or any `Fragment` if the set is erasure coded.
     next_fragments_set_id: Option<i32>,
 
+    /// If the set is erasure coded, the number of `Fragment`s required to recover it.
+    /// It's determined based on the first received `Fragment`.
+    data_fragments: Option<u8>,
+
+    /// Data of an erasure coded set recovered once sufficient number of `Fragment`s got received.
+    recovered_data: Option<Vec<u8>>,
+
     /// The actual `Fragment` data held by the `ReconstructionBuffer`. When created it is already
     /// appropriately resized and all missing fragments are set to a `None`, thus keeping
     /// everything in order the whole time, allowing for O(1) insertions and O(n) reconstruction.
     fragments: Vec<Option<Fragment>>,
+
+    /// Total size of the payloads of all the fragments currently held by the buffer.
+    buffered_bytes: usize,
+
+    /// The time at which the buffer has received its most recent `Fragment`.
+    last_updated: Instant,
 }
 
 /// Type alias representing fully reconstructed message - its original data and list of all
@@ -55,10 +80,19 @@ impl ReconstructionBuffer {
             is_complete: false,
             previous_fragments_set_id: None,
             next_fragments_set_id: None,
+            data_fragments: None,
+            recovered_data: None,
             fragments: fragments_buffer,
+            buffered_bytes: 0,
+            last_updated: Instant::now(),
         }
     }
 
+    /// Returns the number of `Fragment`s the buffer has already received.
+    fn received_fragments(&self) -> usize {
+        self.fragments.iter().filter(|frag| frag.is_some()).count()
+    }
+
     /// After receiving all data, consumes `self` in order to recover original data
     /// encapsulated in this particular set.
     fn reconstruct_set_data(self) -> Vec<u8> {
@@ -66,6 +100,10 @@ impl ReconstructionBuffer {
         // if the set is complete.
         debug_assert!(self.is_complete);
 
+        if let Some(recovered_data) = self.recovered_data {
+            return recovered_data;
+        }
+
         self.fragments
             .into_iter()
             .map(|fragment| fragment.unwrap().extract_payload())
@@ -78,8 +116,38 @@ impl ReconstructionBuffer {
     // we might have false positives if somehow we receive a duplicate
     /// Checks if `self` is done receiving `Fragment` data by checking if there are still
     /// any `None` elements in the `fragments` vector.
+    /// For erasure coded sets it's sufficient to have received the number of data fragments.
     fn is_done_receiving(&self) -> bool {
-        !self.fragments.contains(&None)
+        match self.data_fragments {
+            Some(data_fragments) => self.received_fragments() >= data_fragments as usize,
+            None => !self.fragments.contains(&None),
+        }
+    }
+
+    /// Attempts to recover the data of an erasure coded set from the received `Fragment`s.
+    fn recover_erasure_coded_data(&mut self, data_fragments: u8) {
+        let shards = self
+            .fragments
+            .iter()
+            .map(|fragment| fragment.clone().map(|f| f.extract_payload()))
+            .collect();
+
+        match recover_set_data(shards, data_fragments as usize) {
+            Ok(recovered_data) => {
+                // all fragments of erasure coded sets contain the links
+                let received = self.fragments.iter().flatten().next();
+                self.previous_fragments_set_id =
+                    received.and_then(|fragment| fragment.previous_fragments_set_id());
+                self.next_fragments_set_id =
+                    received.and_then(|fragment| fragment.next_fragments_set_id());
+                self.recovered_data = Some(recovered_data);
+                self.is_complete = true;
+            }
+            Err(err) => {
+                // this could only happen if we received malformed fragments
+                warn!("failed to recover data of an erasure coded set: {err}")
+            }
+        }
     }
 
     /// Inserts new `Fragment` data into an appropriate position in the buffer.
@@ -104,6 +172,20 @@ impl ReconstructionBuffer {
             }
         });
 
+        if self.received_fragments() == 0 {
+            self.data_fragments = fragment.data_fragments();
+        }
+        if fragment.total_fragments() as usize != self.fragments.len()
+            || fragment.data_fragments() != self.data_fragments
+        {
+            warn!(
+                "received fragment {} (set id: {}) that is inconsistent with the rest of its set",
+                fragment.current_fragment(),
+                fragment.id()
+            );
+            return;
+        }
+
         let fragment_index = fragment.current_fragment() as usize - 1;
         if self.fragments[fragment_index].is_some() {
             // TODO: what to do in that case? give up on the message? overwrite it? panic?
@@ -115,8 +197,15 @@ impl ReconstructionBuffer {
                 fragment.id()
             );
         }
-        self.fragments[fragment_index] = Some(fragment);
-        if self.is_done_receiving() {
+        self.buffered_bytes += fragment.payload_size();
+        if let Some(replaced) = self.fragments[fragment_index].replace(fragment) {
+            self.buffered_bytes -= replaced.payload_size();
+        }
+        if let Some(data_fragments) = self.data_fragments {
+            if !self.is_complete && self.is_done_receiving() {
+                self.recover_erasure_coded_data(data_fragments)
+            }
+        } else if self.is_done_receiving() {
             self.is_complete = true;
             self.previous_fragments_set_id = self.fragments[0]
                 .as_ref()
@@ -134,19 +223,75 @@ impl ReconstructionBuffer {
     }
 }
 
+/// Bounds on the amount of partially received data kept by the [`MessageReconstructor`].
+/// Without them, anyone could keep on sending incomplete sets and grow the memory of the receiver
+/// indefinitely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconstructionLimits {
+    /// Maximum amount of time a set can go without receiving any new `Fragment`s
+    /// before it's assumed the remaining ones were lost and the set gets abandoned.
+    pub max_incomplete_set_age: Duration,
+
+    /// Maximum number of sets that can be buffered at once.
+    /// Once exceeded, the least recently updated incomplete sets get abandoned.
+    pub max_buffered_sets: usize,
+
+    /// Maximum total size of the payloads of all the buffered `Fragment`s.
+    /// Once exceeded, the least recently updated incomplete sets get abandoned.
+    pub max_buffered_bytes: usize,
+}
+
+impl Default for ReconstructionLimits {
+    fn default() -> Self {
+        ReconstructionLimits {
+            max_incomplete_set_age: DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE,
+            max_buffered_sets: DEFAULT_MAXIMUM_BUFFERED_FRAGMENT_SETS,
+            max_buffered_bytes: DEFAULT_MAXIMUM_BUFFERED_FRAGMENTS_SIZE,
+        }
+    }
+}
+
+/// Reason for abandoning reconstruction of a set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbandonmentReason {
+    /// The set has not received any new `Fragment`s for longer than the allowed maximum.
+    Expired,
+
+    /// The set was the least recently updated one when the buffering limits got exceeded.
+    Evicted,
+}
+
+/// Information about set whose reconstruction has been abandoned before all of its `Fragment`s
+/// have been received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbandonedSet {
+    pub id: i32,
+    pub received_fragments: usize,
+    pub total_fragments: usize,
+    pub buffered_bytes: usize,
+    pub reason: AbandonmentReason,
+}
+
 /// High level public structure used to buffer all received data `Fragment`s and eventually
 /// returning original messages that they encapsulate.
-#[derive(Default, PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct MessageReconstructor {
-    // TODO: some cleaner thread/routine that if message is incomplete and
-    // we haven't received any fragments in X time, we assume they
-    // were lost and message can't be restored.
-    // Perhaps add 'latest_fragment_timestamp' to each buffer
-    // and after N fragments received globally, check all of buffer timestamps.
-    // otherwise we are vulnerable to heap overflow attacks -> somebody can keep on sending
-    // maximum sized sets but without one of required fragments. All of the received
-    // data will be kept on the heap indefinitely in the current implementation.
     reconstructed_sets: HashMap<i32, ReconstructionBuffer>,
+
+    /// Bounds on the amount of buffered data.
+    limits: ReconstructionLimits,
+
+    /// Total size of the payloads of all the buffered `Fragment`s.
+    buffered_bytes: usize,
+
+    /// Sets abandoned since the last call to `take_abandoned_sets`.
+    abandoned_sets: Vec<AbandonedSet>,
+}
+
+impl Default for MessageReconstructor {
+    fn default() -> Self {
+        MessageReconstructor::new_with_limits(Default::default())
+    }
 }
 
 impl MessageReconstructor {
@@ -155,6 +300,80 @@ impl MessageReconstructor {
         Default::default()
     }
 
+    /// Creates an empty `MessageReconstructor` with the provided bounds on the amount of buffered data.
+    pub fn new_with_limits(limits: ReconstructionLimits) -> Self {
+        MessageReconstructor {
+            reconstructed_sets: HashMap::new(),
+            limits,
+            buffered_bytes: 0,
+            abandoned_sets: Vec::new(),
+        }
+    }
+
+    /// Changes the bounds on the amount of buffered data.
+    /// They are going to be enforced upon receiving the next `Fragment`.
+    pub fn set_limits(&mut self, limits: ReconstructionLimits) {
+        self.limits = limits
+    }
+
+    pub fn limits(&self) -> ReconstructionLimits {
+        self.limits
+    }
+
+    /// Returns (and clears) the information about all the sets abandoned since the last call.
+    pub fn take_abandoned_sets(&mut self) -> Vec<AbandonedSet> {
+        std::mem::take(&mut self.abandoned_sets)
+    }
+
+    /// Drops the buffer of the specified set and records it as abandoned.
+    fn abandon_set(&mut self, id: i32, reason: AbandonmentReason) {
+        if let Some(buf) = self.reconstructed_sets.remove(&id) {
+            self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes);
+            self.abandoned_sets.push(AbandonedSet {
+                id,
+                received_fragments: buf.received_fragments(),
+                total_fragments: buf.fragments.len(),
+                buffered_bytes: buf.buffered_bytes,
+                reason,
+            })
+        }
+    }
+
+    /// Abandons all the sets that have not been updated for longer than the allowed maximum
+    /// and, if the buffering limits are still exceeded, the least recently updated ones,
+    /// apart from the set identified by `current_set`.
+    /// Complete sets (still waiting for the other sets of their message) are only evicted
+    /// once there are no incomplete ones left.
+    fn enforce_limits(&mut self, current_set: i32, now: Instant) {
+        let max_age = self.limits.max_incomplete_set_age;
+        let expired: Vec<_> = self
+            .reconstructed_sets
+            .iter()
+            .filter(|(_, buf)| now.saturating_duration_since(buf.last_updated) > max_age)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in expired {
+            self.abandon_set(id, AbandonmentReason::Expired)
+        }
+
+        while self.reconstructed_sets.len() > self.limits.max_buffered_sets
+            || self.buffered_bytes > self.limits.max_buffered_bytes
+        {
+            let least_recently_updated = self
+                .reconstructed_sets
+                .iter()
+                .filter(|(id, _)| **id != current_set)
+                .min_by_key(|(_, buf)| (buf.is_complete, buf.last_updated))
+                .map(|(id, _)| *id);
+
+            let Some(id) = least_recently_updated else {
+                break;
+            };
+            self.abandon_set(id, AbandonmentReason::Evicted)
+        }
+    }
+
     /// Given fully received set of given `id`, if it has any post-linked sets, recursively
     /// checks if all of them were also fully received.
     fn check_front_chain(&self, id: i32) -> bool {
@@ -237,10 +456,9 @@ impl MessageReconstructor {
     /// Note, before you call this method, you *must* ensure set was fully received
     fn extract_set_payload(&mut self, set_id: i32) -> Vec<u8> {
         debug_assert!(self.is_set_fully_received(set_id));
-        self.reconstructed_sets
-            .remove(&set_id)
-            .unwrap()
-            .reconstruct_set_data()
+        let buf = self.reconstructed_sets.remove(&set_id).unwrap();
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes);
+        buf.reconstruct_set_data()
     }
 
     // Future consideration: perhaps for long messages, rather than return whole data allocated
@@ -267,7 +485,17 @@ impl MessageReconstructor {
     /// If a buffer does not exist, a new instance is created.
     /// If it was last remaining `Fragment` for the original message, the message is reconstructed
     /// and returned alongside all (if applicable) set ids used in the message.
+    /// Afterwards the buffering limits are enforced, which might result in some sets getting abandoned.
+    /// Information about them can be obtained with `take_abandoned_sets`.
     pub fn insert_new_fragment(&mut self, fragment: Fragment) -> Option<ReconstructedMessage> {
+        self.insert_new_fragment_at(fragment, Instant::now())
+    }
+
+    fn insert_new_fragment_at(
+        &mut self,
+        fragment: Fragment,
+        now: Instant,
+    ) -> Option<ReconstructedMessage> {
         let set_id = fragment.id();
         let set_len = fragment.total_fragments();
 
@@ -276,12 +504,20 @@ impl MessageReconstructor {
             .entry(set_id)
             .or_insert_with(|| ReconstructionBuffer::new(set_len));
 
+        let previously_buffered = buf.buffered_bytes;
         buf.insert_fragment(fragment);
-        if self.is_message_fully_received(set_id) {
+        buf.last_updated = now;
+        self.buffered_bytes =
+            self.buffered_bytes.saturating_sub(previously_buffered) + buf.buffered_bytes;
+
+        let reconstructed = if self.is_message_fully_received(set_id) {
             Some(self.reconstruct_message(set_id))
         } else {
             None
-        }
+        };
+
+        self.enforce_limits(set_id, now);
+        reconstructed
     }
 
     /// Given raw `Fragment` data, tries to decode and return it.
@@ -1847,4 +2083,254 @@ mod message_reconstruction {
             }
         }
     }
+
+    #[cfg(test)]
+    mod erasure_coded_split {
+        use super::*;
+        use crate::erasure_coding::max_data_fragments_per_set;
+        use crate::fragment::erasure_coded_fragment_payload_len;
+
+        fn reconstruct_with_losses(message: &[u8], redundancy: f64, expected_sets: usize) {
+            let mut rng = thread_rng();
+
+            let sets = crate::split_into_erasure_coded_sets(
+                &mut rand::rngs::OsRng,
+                message,
+                AVAILABLE_PLAINTEXT_SIZE,
+                redundancy,
+            );
+            assert_eq!(sets.len(), expected_sets);
+
+            // drop as many fragments of each set as it can tolerate
+            let mut fragments: Vec<_> = sets
+                .into_iter()
+                .flat_map(|mut set| {
+                    let data_fragments = set[0].data_fragments().unwrap() as usize;
+                    set.shuffle(&mut rng);
+                    set.truncate(data_fragments);
+                    set.into_iter()
+                })
+                .map(|x| x.into_bytes())
+                .collect();
+            fragments.shuffle(&mut rng);
+
+            let mut message_reconstructor = MessageReconstructor::default();
+            let mut reconstructed = None;
+            for fragment in fragments.into_iter() {
+                assert!(reconstructed.is_none());
+                reconstructed = message_reconstructor
+                    .insert_new_fragment(message_reconstructor.recover_fragment(fragment).unwrap());
+            }
+
+            let (content, set_ids) = reconstructed.unwrap();
+            assert_eq!(set_ids.len(), expected_sets);
+            assert_eq!(&content[..message.len()], message);
+            assert!(content[message.len()..].iter().all(|b| *b == 0));
+        }
+
+        #[test]
+        fn it_reconstructs_message_with_lost_fragments_in_single_set() {
+            let mut message =
+                vec![0u8; erasure_coded_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 20 + 42];
+            thread_rng().fill_bytes(&mut message);
+
+            reconstruct_with_losses(&message, 0.25, 1);
+        }
+
+        #[test]
+        fn it_reconstructs_message_with_lost_fragments_in_multiple_sets() {
+            let set_payload = erasure_coded_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE)
+                * max_data_fragments_per_set(0.5);
+            let mut message = vec![0u8; 2 * set_payload + 12345];
+            thread_rng().fill_bytes(&mut message);
+
+            reconstruct_with_losses(&message, 0.5, 3);
+        }
+
+        #[test]
+        fn it_ignores_fragments_received_after_set_got_recovered() {
+            let message =
+                vec![42u8; erasure_coded_fragment_payload_len(AVAILABLE_PLAINTEXT_SIZE) * 4];
+            let mut sets = crate::split_into_erasure_coded_sets(
+                &mut rand::rngs::OsRng,
+                &message,
+                AVAILABLE_PLAINTEXT_SIZE,
+                1.,
+            );
+            let set = sets.pop().unwrap();
+            assert_eq!(set.len(), 8);
+
+            let mut buf = ReconstructionBuffer::new(8);
+            for fragment in set.into_iter().rev() {
+                buf.insert_fragment(fragment);
+                if buf.received_fragments() < 4 {
+                    assert!(!buf.is_complete);
+                } else {
+                    assert!(buf.is_complete);
+                }
+            }
+            assert_eq!(buf.reconstruct_set_data(), message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod buffering_limits {
+    use super::*;
+    use crate::fragment::unlinked_fragment_payload_max_len;
+
+    // just some arbitrary value to use in tests
+    const AVAILABLE_PLAINTEXT_SIZE: usize = 1024;
+
+    // original message alongside its fragments, all belonging to a single set
+    fn two_fragment_message() -> (Vec<u8>, Vec<Fragment>) {
+        let message = vec![42u8; unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE) + 30];
+        let fragments: Vec<_> =
+            crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+                .into_iter()
+                .flat_map(|fragment_set| fragment_set.into_iter())
+                .collect();
+        assert_eq!(fragments.len(), 2);
+        (message, fragments)
+    }
+
+    fn limits(
+        max_incomplete_set_age: Duration,
+        max_buffered_sets: usize,
+        max_buffered_bytes: usize,
+    ) -> ReconstructionLimits {
+        ReconstructionLimits {
+            max_incomplete_set_age,
+            max_buffered_sets,
+            max_buffered_bytes,
+        }
+    }
+
+    #[test]
+    fn inactive_sets_expire() {
+        let mut reconstructor = MessageReconstructor::new_with_limits(limits(
+            Duration::from_secs(60),
+            usize::MAX,
+            usize::MAX,
+        ));
+        let (_, mut first) = two_fragment_message();
+        let (_, mut second) = two_fragment_message();
+        let first_id = first[0].id();
+
+        let start = Instant::now();
+        assert!(reconstructor
+            .insert_new_fragment_at(first.pop().unwrap(), start)
+            .is_none());
+        assert!(reconstructor
+            .insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(30))
+            .is_none());
+        assert!(reconstructor.take_abandoned_sets().is_empty());
+
+        assert!(reconstructor
+            .insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(61))
+            .is_some());
+        let abandoned = reconstructor.take_abandoned_sets();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].id, first_id);
+        assert_eq!(abandoned[0].received_fragments, 1);
+        assert_eq!(abandoned[0].total_fragments, 2);
+        assert_eq!(abandoned[0].reason, AbandonmentReason::Expired);
+
+        assert!(reconstructor.reconstructed_sets.is_empty());
+        assert_eq!(reconstructor.buffered_bytes, 0);
+        assert!(reconstructor.take_abandoned_sets().is_empty());
+    }
+
+    #[test]
+    fn least_recently_updated_sets_are_evicted_when_too_many_are_buffered() {
+        let mut reconstructor =
+            MessageReconstructor::new_with_limits(limits(Duration::from_secs(60), 2, usize::MAX));
+        let (_, mut first) = two_fragment_message();
+        let (second_message, mut second) = two_fragment_message();
+        let (_, mut third) = two_fragment_message();
+        let first_id = first[0].id();
+
+        let start = Instant::now();
+        reconstructor.insert_new_fragment_at(first.pop().unwrap(), start);
+        reconstructor.insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(1));
+        reconstructor.insert_new_fragment_at(third.pop().unwrap(), start + Duration::from_secs(2));
+
+        let abandoned = reconstructor.take_abandoned_sets();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].id, first_id);
+        assert_eq!(abandoned[0].reason, AbandonmentReason::Evicted);
+        assert_eq!(reconstructor.reconstructed_sets.len(), 2);
+
+        // the remaining sets can still be reconstructed
+        let (reconstructed, _) = reconstructor
+            .insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(reconstructed, second_message);
+    }
+
+    #[test]
+    fn least_recently_updated_sets_are_evicted_when_too_much_data_is_buffered() {
+        let (_, mut first) = two_fragment_message();
+        let (_, mut second) = two_fragment_message();
+        let first_fragment = first.pop().unwrap();
+        let second_fragment = second.pop().unwrap();
+        let first_id = first_fragment.id();
+        let second_id = second_fragment.id();
+        let second_fragment_size = second_fragment.payload_size();
+
+        let mut reconstructor = MessageReconstructor::new_with_limits(limits(
+            Duration::from_secs(60),
+            usize::MAX,
+            first_fragment.payload_size() + second_fragment_size - 1,
+        ));
+
+        let start = Instant::now();
+        reconstructor.insert_new_fragment_at(first_fragment, start);
+        assert!(reconstructor.take_abandoned_sets().is_empty());
+        reconstructor.insert_new_fragment_at(second_fragment, start + Duration::from_secs(1));
+
+        let abandoned = reconstructor.take_abandoned_sets();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].id, first_id);
+        assert_eq!(abandoned[0].reason, AbandonmentReason::Evicted);
+        assert!(reconstructor.reconstructed_sets.contains_key(&second_id));
+        assert_eq!(reconstructor.buffered_bytes, second_fragment_size);
+    }
+
+    #[test]
+    fn incomplete_sets_are_evicted_before_complete_ones() {
+        let mut reconstructor =
+            MessageReconstructor::new_with_limits(limits(Duration::from_secs(60), 2, usize::MAX));
+
+        // message spanning two sets, the first one of which is going to be fully received
+        let long_message = vec![42u8; 300 * AVAILABLE_PLAINTEXT_SIZE];
+        let mut long_sets = crate::split_into_sets(
+            &mut rand::rngs::OsRng,
+            &long_message,
+            AVAILABLE_PLAINTEXT_SIZE,
+        );
+        assert_eq!(long_sets.len(), 2);
+        let complete_set = long_sets.remove(0);
+        let complete_id = complete_set[0].id();
+
+        let (_, mut first) = two_fragment_message();
+        let (_, mut second) = two_fragment_message();
+        let first_id = first[0].id();
+
+        let start = Instant::now();
+        for fragment in complete_set {
+            assert!(reconstructor
+                .insert_new_fragment_at(fragment, start)
+                .is_none());
+        }
+        reconstructor.insert_new_fragment_at(first.pop().unwrap(), start + Duration::from_secs(1));
+        reconstructor.insert_new_fragment_at(second.pop().unwrap(), start + Duration::from_secs(2));
+
+        // even though the complete set is the least recently updated one, it's kept around
+        let abandoned = reconstructor.take_abandoned_sets();
+        assert_eq!(abandoned.len(), 1);
+        assert_eq!(abandoned[0].id, first_id);
+        assert_eq!(abandoned[0].reason, AbandonmentReason::Evicted);
+        assert!(reconstructor.reconstructed_sets.contains_key(&complete_id));
+    }
 }
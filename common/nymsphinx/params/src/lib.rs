@@ -8,12 +8,15 @@ use nym_crypto::ctr;
 type Aes128Ctr = ctr::Ctr64BE<Aes128>;
 
 // Re-export for ease of use
+pub use message_compression::{MessageCompression, MessageCompressionSettings};
 pub use packet_sizes::PacketSize;
 pub use packet_types::PacketType;
 
+pub mod message_compression;
 pub mod packet_sizes;
 pub mod packet_types;
 pub mod packet_version;
+pub mod reconstruction;
 
 // If somebody can provide an argument why it might be reasonable to have more than 255 mix hops,
 // I will change this to [`usize`]
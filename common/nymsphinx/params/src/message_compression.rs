@@ -0,0 +1,68 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// Messages smaller than this (in bytes) are not worth compressing.
+pub const DEFAULT_MESSAGE_COMPRESSION_THRESHOLD: usize = 512;
+
+#[derive(Error, Debug)]
+#[error("{received} is not a valid message compression tag")]
+pub struct InvalidMessageCompression {
+    received: u8,
+}
+
+/// Algorithm used for compressing the content of a message before it gets chunked into sphinx packets.
+/// The tag of the algorithm is included in the message framing, so new variants can be introduced
+/// without breaking the existing ones.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageCompression {
+    #[serde(rename = "deflate")]
+    Deflate = 1,
+}
+
+impl fmt::Display for MessageCompression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MessageCompression::Deflate => write!(f, "deflate"),
+        }
+    }
+}
+
+impl TryFrom<u8> for MessageCompression {
+    type Error = InvalidMessageCompression;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            _ if value == (MessageCompression::Deflate as u8) => Ok(Self::Deflate),
+            v => Err(InvalidMessageCompression { received: v }),
+        }
+    }
+}
+
+/// Specifies how (and whether) the outgoing messages should get compressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageCompressionSettings {
+    pub algorithm: MessageCompression,
+
+    /// Minimum size of the serialized message (in bytes) for the compression to be attempted.
+    pub threshold: usize,
+}
+
+impl MessageCompressionSettings {
+    pub fn new(algorithm: MessageCompression) -> Self {
+        MessageCompressionSettings {
+            algorithm,
+            threshold: DEFAULT_MESSAGE_COMPRESSION_THRESHOLD,
+        }
+    }
+
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
@@ -0,0 +1,15 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Default bounds on the amount of partially received data buffered during message reconstruction.
+
+use std::time::Duration;
+
+/// Maximum amount of time a set can go without receiving any new fragments before being abandoned.
+pub const DEFAULT_MAXIMUM_INCOMPLETE_MESSAGE_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Maximum number of sets that can be buffered at once.
+pub const DEFAULT_MAXIMUM_BUFFERED_FRAGMENT_SETS: usize = 1024;
+
+/// Maximum total size of the payloads of all the buffered fragments.
+pub const DEFAULT_MAXIMUM_BUFFERED_FRAGMENTS_SIZE: usize = 128 * 1024 * 1024; // 128MiB
@@ -0,0 +1,89 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use nym_sphinx_params::message_compression::InvalidMessageCompression;
+use nym_sphinx_params::MessageCompression;
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+/// Maximum size of the decompressed message. It protects the receiver from having to
+/// allocate arbitrary amounts of memory when processing maliciously crafted data.
+pub const MAX_DECOMPRESSED_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
+
+// algorithm || decompressed length
+const COMPRESSION_HEADER_SIZE: usize = 1 + 4;
+
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    #[error(transparent)]
+    UnknownAlgorithm(#[from] InvalidMessageCompression),
+
+    #[error("the compressed message is too short to contain a valid header")]
+    TruncatedHeader,
+
+    #[error("the compressed message claims to contain {declared} bytes while at most {MAX_DECOMPRESSED_MESSAGE_SIZE} are allowed")]
+    TooLarge { declared: usize },
+
+    #[error("the decompressed message has {actual} bytes while {declared} were declared")]
+    LengthMismatch { declared: usize, actual: usize },
+
+    #[error("failed to decompress the message: {source}")]
+    MalformedData {
+        #[from]
+        source: io::Error,
+    },
+}
+
+// the compressed content is in the format of:
+// algorithm || decompressed length (u32 BE) || compressed data
+pub(crate) fn compress(algorithm: MessageCompression, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(COMPRESSION_HEADER_SIZE + data.len() / 2);
+    out.push(algorithm as u8);
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    match algorithm {
+        MessageCompression::Deflate => {
+            let mut encoder = DeflateEncoder::new(out, Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+    }
+}
+
+pub(crate) fn decompress(bytes: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    if bytes.len() < COMPRESSION_HEADER_SIZE {
+        return Err(CompressionError::TruncatedHeader);
+    }
+
+    let algorithm = MessageCompression::try_from(bytes[0])?;
+    let mut declared_bytes = [0u8; 4];
+    declared_bytes.copy_from_slice(&bytes[1..COMPRESSION_HEADER_SIZE]);
+    let declared = u32::from_be_bytes(declared_bytes) as usize;
+    if declared > MAX_DECOMPRESSED_MESSAGE_SIZE {
+        return Err(CompressionError::TooLarge { declared });
+    }
+
+    let compressed = &bytes[COMPRESSION_HEADER_SIZE..];
+    let mut decompressed = Vec::new();
+    match algorithm {
+        MessageCompression::Deflate => {
+            // read at most a single byte more than declared so that we could detect the mismatch
+            // without decompressing the (potentially huge) remainder
+            DeflateDecoder::new(compressed)
+                .take(declared as u64 + 1)
+                .read_to_end(&mut decompressed)?;
+        }
+    }
+
+    if decompressed.len() != declared {
+        return Err(CompressionError::LengthMismatch {
+            declared,
+            actual: decompressed.len(),
+        });
+    }
+
+    Ok(decompressed)
+}
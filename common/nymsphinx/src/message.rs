@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::chunking;
+use crate::compression::{self, CompressionError, MAX_DECOMPRESSED_MESSAGE_SIZE};
 use nym_crypto::asymmetric::encryption;
 use nym_crypto::Digest;
 use nym_sphinx_addressing::clients::Recipient;
@@ -11,7 +12,9 @@ use nym_sphinx_anonymous_replies::requests::{
     ReplyMessageContent,
 };
 use nym_sphinx_chunking::fragment::Fragment;
-use nym_sphinx_params::{PacketSize, PacketType, ReplySurbKeyDigestAlgorithm};
+use nym_sphinx_params::{
+    MessageCompressionSettings, PacketSize, PacketType, ReplySurbKeyDigestAlgorithm,
+};
 use rand::Rng;
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
@@ -33,6 +36,12 @@ pub enum NymMessageError {
 
     #[error("Received empty message for deserialization")]
     EmptyMessage,
+
+    #[error("The received message could not be decompressed - {0}")]
+    MalformedCompressedMessage(#[from] CompressionError),
+
+    #[error("The received compressed message contained another compressed message")]
+    NestedCompression,
 }
 
 #[repr(u8)]
@@ -40,6 +49,7 @@ enum NymMessageType {
     Plain = 0,
     Repliable = 1,
     Reply = 2,
+    Compressed = 3,
 }
 
 impl TryFrom<u8> for NymMessageType {
@@ -50,6 +60,7 @@ impl TryFrom<u8> for NymMessageType {
             _ if value == (NymMessageType::Plain as u8) => Ok(Self::Plain),
             _ if value == (NymMessageType::Repliable as u8) => Ok(Self::Repliable),
             _ if value == (NymMessageType::Reply as u8) => Ok(Self::Reply),
+            _ if value == (NymMessageType::Compressed as u8) => Ok(Self::Compressed),
             val => Err(NymMessageError::InvalidMessageType { received: val }),
         }
     }
@@ -149,6 +160,47 @@ impl NymMessage {
             .collect()
     }
 
+    // if the compression is worth it, the message is in the format of:
+    // COMPRESSED || algorithm || decompressed length || compressed(typ || msg)
+    // otherwise it's the same as `into_bytes`
+    fn into_compressed_bytes(self, settings: MessageCompressionSettings) -> Vec<u8> {
+        let bytes = self.into_bytes();
+        if bytes.len() < settings.threshold || bytes.len() > MAX_DECOMPRESSED_MESSAGE_SIZE {
+            return bytes;
+        }
+
+        let compressed = match compression::compress(settings.algorithm, &bytes) {
+            Ok(compressed) => compressed,
+            Err(err) => {
+                log::warn!(
+                    "failed to compress the message using {}: {err}. It's going to be sent uncompressed",
+                    settings.algorithm
+                );
+                return bytes;
+            }
+        };
+
+        // (+1 for the additional type tag)
+        if compressed.len() + 1 >= bytes.len() {
+            log::trace!(
+                "compressing {} bytes using {} wouldn't reduce the message size",
+                bytes.len(),
+                settings.algorithm
+            );
+            return bytes;
+        }
+
+        log::trace!(
+            "compressed {} bytes into {} using {}",
+            bytes.len(),
+            compressed.len() + 1,
+            settings.algorithm
+        );
+        std::iter::once(NymMessageType::Compressed as u8)
+            .chain(compressed)
+            .collect()
+    }
+
     fn try_from_bytes(bytes: &[u8], num_mix_hops: u8) -> Result<Self, NymMessageError> {
         if bytes.is_empty() {
             return Err(NymMessageError::EmptyMessage);
@@ -163,6 +215,13 @@ impl NymMessage {
             NymMessageType::Reply => Ok(NymMessage::Reply(ReplyMessage::try_from_bytes(
                 &bytes[1..],
             )?)),
+            NymMessageType::Compressed => {
+                let decompressed = compression::decompress(&bytes[1..])?;
+                if decompressed.first() == Some(&(NymMessageType::Compressed as u8)) {
+                    return Err(NymMessageError::NestedCompression);
+                }
+                Self::try_from_bytes(&decompressed, num_mix_hops)
+            }
         }
     }
 
@@ -176,29 +235,133 @@ impl NymMessage {
         message_type_size + inner_size
     }
 
+    fn is_reply(&self) -> bool {
+        matches!(self, NymMessage::Reply(_))
+    }
+
     /// Length of plaintext (from the **sphinx** point of view) data that is available per sphinx
     /// packet.
     pub fn available_sphinx_plaintext_per_packet(&self, packet_size: PacketSize) -> usize {
-        let variant_overhead = match self {
-            // each plain or repliable packet attaches an ephemeral public key so that the recipient
-            // could perform diffie-hellman with its own keys followed by a kdf to re-derive
-            // the packet encryption key
-            NymMessage::Plain(_) | NymMessage::Repliable(_) => encryption::PUBLIC_KEY_SIZE,
-            // each reply attaches the digest of the encryption key so that the recipient could
-            // lookup correct key for decryption,
-            NymMessage::Reply(_) => ReplySurbKeyDigestAlgorithm::output_size(),
+        available_sphinx_plaintext_per_packet(self.is_reply(), packet_size)
+    }
+
+    /// Length of the actual (from the **message** point of view) data that is available in each packet.
+    pub fn true_available_plaintext_per_packet(&self, packet_size: PacketSize) -> usize {
+        let sphinx_plaintext = self.available_sphinx_plaintext_per_packet(packet_size);
+        sphinx_plaintext - chunking::MIN_PADDING_OVERHEAD
+    }
+
+    /// Determines the number of required packets of the provided size for the split message.
+    pub fn required_packets(&self, packet_size: PacketSize, num_mix_hops: u8) -> usize {
+        let plaintext_per_packet = self.true_available_plaintext_per_packet(packet_size);
+        required_packets(
+            self.serialized_size(num_mix_hops),
+            packet_size,
+            plaintext_per_packet,
+        )
+    }
+
+    /// Serializes the message and, if the compression settings are provided, attempts to compress it.
+    /// The message only gets compressed if it's above the specified threshold and the compression
+    /// actually reduces its size.
+    /// Note that the recipient has to understand the compressed framing.
+    pub fn serialize(
+        self,
+        compression_settings: Option<MessageCompressionSettings>,
+    ) -> SerializedMessage {
+        let is_reply = self.is_reply();
+        let description = self.to_string();
+        let bytes = match compression_settings {
+            Some(settings) => self.into_compressed_bytes(settings),
+            None => self.into_bytes(),
         };
 
-        let packet_type = PacketType::from(packet_size);
+        SerializedMessage {
+            is_reply,
+            description,
+            bytes,
+        }
+    }
 
-        // each packet will contain an ack + variant specific data (as described above)
-        match packet_type {
-            PacketType::Outfox => {
-                packet_size.plaintext_size() - OUTFOX_ACK_OVERHEAD - variant_overhead
-            }
-            _ => packet_size.plaintext_size() - ACK_OVERHEAD - variant_overhead,
+    /// Pads the message so that after it gets chunked, it will occupy exactly N sphinx packets.
+    /// Produces new_message = message || 1 || 0000....
+    pub fn pad_to_full_packet_lengths(self, plaintext_per_packet: usize) -> PaddedMessage {
+        self.serialize(None)
+            .pad_to_full_packet_lengths(plaintext_per_packet)
+    }
+}
+
+fn available_sphinx_plaintext_per_packet(is_reply: bool, packet_size: PacketSize) -> usize {
+    let variant_overhead = if is_reply {
+        // each reply attaches the digest of the encryption key so that the recipient could
+        // lookup correct key for decryption,
+        ReplySurbKeyDigestAlgorithm::output_size()
+    } else {
+        // each plain or repliable packet attaches an ephemeral public key so that the recipient
+        // could perform diffie-hellman with its own keys followed by a kdf to re-derive
+        // the packet encryption key
+        encryption::PUBLIC_KEY_SIZE
+    };
+
+    let packet_type = PacketType::from(packet_size);
+
+    // each packet will contain an ack + variant specific data (as described above)
+    match packet_type {
+        PacketType::Outfox => packet_size.plaintext_size() - OUTFOX_ACK_OVERHEAD - variant_overhead,
+        _ => packet_size.plaintext_size() - ACK_OVERHEAD - variant_overhead,
+    }
+}
+
+fn required_packets(
+    serialized_len: usize,
+    packet_size: PacketSize,
+    plaintext_per_packet: usize,
+) -> usize {
+    let (num_fragments, _) =
+        chunking::number_of_required_fragments(serialized_len, plaintext_per_packet);
+
+    // by chunking I mean that currently the fragments hold variable amount of plaintext in them (I wish I had time to rewrite it...)
+    log::trace!(
+        "this message will use {serialized_len} bytes of PLAINTEXT (This does not account for Ack or chunking overhead). \
+        With {packet_size:?} PacketSize ({plaintext_per_packet} of usable plaintext available) it will require {num_fragments} packet(s).",
+    );
+
+    num_fragments
+}
+
+/// [`NymMessage`] that has already been serialized (and possibly compressed),
+/// so that its final size would be known before deciding on the packet size used for sending it.
+pub struct SerializedMessage {
+    is_reply: bool,
+    description: String,
+    bytes: Vec<u8>,
+}
+
+impl Display for SerializedMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_compressed() {
+            write!(
+                f,
+                "{} (compressed into {:.2} kiB)",
+                self.description,
+                self.bytes.len() as f64 / 1024.0
+            )
+        } else {
+            self.description.fmt(f)
         }
     }
+}
+
+impl SerializedMessage {
+    pub fn is_compressed(&self) -> bool {
+        self.bytes.first() == Some(&(NymMessageType::Compressed as u8))
+    }
+
+    /// Length of plaintext (from the **sphinx** point of view) data that is available per sphinx
+    /// packet.
+    pub fn available_sphinx_plaintext_per_packet(&self, packet_size: PacketSize) -> usize {
+        available_sphinx_plaintext_per_packet(self.is_reply, packet_size)
+    }
 
     /// Length of the actual (from the **message** point of view) data that is available in each packet.
     pub fn true_available_plaintext_per_packet(&self, packet_size: PacketSize) -> usize {
@@ -207,28 +370,24 @@ impl NymMessage {
     }
 
     /// Determines the number of required packets of the provided size for the split message.
-    pub fn required_packets(&self, packet_size: PacketSize, num_mix_hops: u8) -> usize {
+    pub fn required_packets(&self, packet_size: PacketSize) -> usize {
         let plaintext_per_packet = self.true_available_plaintext_per_packet(packet_size);
-        let serialized_len = self.serialized_size(num_mix_hops);
-
-        let (num_fragments, _) =
-            chunking::number_of_required_fragments(serialized_len, plaintext_per_packet);
-
-        // by chunking I mean that currently the fragments hold variable amount of plaintext in them (I wish I had time to rewrite it...)
-        log::trace!(
-            "this message will use {serialized_len} bytes of PLAINTEXT (This does not account for Ack or chunking overhead). \
-            With {packet_size:?} PacketSize ({plaintext_per_packet} of usable plaintext available) it will require {num_fragments} packet(s).",
-        );
+        required_packets(self.bytes.len(), packet_size, plaintext_per_packet)
+    }
 
-        num_fragments
+    /// Appends the single byte of padding required to distinguish the message from the zeroes
+    /// the final erasure coded fragment is going to be padded with.
+    /// Produces new_message = message || 1
+    pub fn pad_for_erasure_coding(mut self) -> PaddedMessage {
+        self.bytes.push(1);
+        self.bytes.into()
     }
 
     /// Pads the message so that after it gets chunked, it will occupy exactly N sphinx packets.
     /// Produces new_message = message || 1 || 0000....
     pub fn pad_to_full_packet_lengths(self, plaintext_per_packet: usize) -> PaddedMessage {
         let self_display = self.to_string();
-
-        let bytes = self.into_bytes();
+        let bytes = self.bytes;
 
         // 1 (chunking::MIN_PADDING_OVERHEAD) is added as there will always have to be at least a single byte of padding (1) added
         // to be able to later distinguish the actual padding from the underlying message
@@ -265,6 +424,11 @@ impl PaddedMessage {
         PaddedMessage(bytes)
     }
 
+    /// Indicates whether the sender has compressed the underlying message.
+    pub fn is_compressed(&self) -> bool {
+        self.0.first() == Some(&(NymMessageType::Compressed as u8))
+    }
+
     /// Splits the padded message into [`Fragment`] that when serialized are going to become
     /// sphinx packet payloads.
     pub fn split_into_fragments<R: Rng>(
@@ -278,6 +442,21 @@ impl PaddedMessage {
             .collect()
     }
 
+    /// Splits the padded message into erasure coded [`Fragment`]s, so that each set could be recovered
+    /// even if some of its fragments got lost. The amount of parity fragments is determined by
+    /// the provided redundancy, i.e. the ratio of parity to data fragments.
+    pub fn split_into_erasure_coded_fragments<R: Rng>(
+        self,
+        rng: &mut R,
+        plaintext_per_packet: usize,
+        redundancy: f64,
+    ) -> Vec<Fragment> {
+        chunking::split_into_erasure_coded_sets(rng, &self.0, plaintext_per_packet, redundancy)
+            .into_iter()
+            .flat_map(|fragment_set| fragment_set.into_iter())
+            .collect()
+    }
+
     // reverse of NymMessage::pad_to_full_packet_lengths
     pub fn remove_padding(self, num_mix_hops: u8) -> Result<NymMessage, NymMessageError> {
         // we are looking for first occurrence of 1 in the tail and we get its index
@@ -318,4 +497,221 @@ mod tests {
         let reply = NymMessage::new_reply(ReplyMessage::new_data_message(vec![1, 2, 3, 4, 5]));
         assert_eq!(reply.serialized_size(3), reply.into_bytes().len());
     }
+
+    mod message_compression {
+        use super::*;
+        use nym_sphinx_chunking::reconstruction::MessageReconstructor;
+        use nym_sphinx_params::MessageCompression;
+        use rand::{thread_rng, RngCore};
+
+        fn packet_sizes() -> Vec<PacketSize> {
+            #[allow(unused_mut)]
+            let mut sizes = vec![
+                PacketSize::RegularPacket,
+                PacketSize::ExtendedPacket8,
+                PacketSize::ExtendedPacket16,
+                PacketSize::ExtendedPacket32,
+            ];
+            #[cfg(feature = "outfox")]
+            sizes.push(PacketSize::OutfoxRegularPacket);
+            sizes
+        }
+
+        fn settings() -> MessageCompressionSettings {
+            MessageCompressionSettings::new(MessageCompression::Deflate)
+        }
+
+        fn json_payload(len: usize) -> Vec<u8> {
+            let mut payload = Vec::with_capacity(len);
+            let mut id = 0;
+            while payload.len() < len {
+                payload.extend_from_slice(
+                    format!(
+                        r#"{{"jsonrpc":"2.0","id":{id},"method":"eth_getBalance","params":["0x407d73d8a49eeb85d32cf465507dd71d507100c1","latest"]}}"#
+                    )
+                    .as_bytes(),
+                );
+                id += 1;
+            }
+            payload.truncate(len);
+            payload
+        }
+
+        // pads, splits and reconstructs the message returning it alongside number of used fragments
+        fn send_and_receive(
+            message: NymMessage,
+            packet_size: PacketSize,
+            compression_settings: Option<MessageCompressionSettings>,
+        ) -> (NymMessage, usize) {
+            let message = message.serialize(compression_settings);
+            let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
+            let required_packets = message.required_packets(packet_size);
+            let fragments = message
+                .pad_to_full_packet_lengths(plaintext_per_packet)
+                .split_into_fragments(&mut thread_rng(), plaintext_per_packet);
+            let used_fragments = fragments.len();
+            assert_eq!(used_fragments, required_packets);
+
+            let mut reconstructor = MessageReconstructor::default();
+            let mut reconstructed = None;
+            for fragment in fragments {
+                if let Some((message, _)) = reconstructor.insert_new_fragment(fragment) {
+                    reconstructed = Some(message)
+                }
+            }
+
+            let message = PaddedMessage::new_reconstructed(reconstructed.unwrap())
+                .remove_padding(3)
+                .unwrap();
+            (message, used_fragments)
+        }
+
+        #[test]
+        fn compressible_messages_use_fewer_packets() {
+            let payload = json_payload(50_000);
+            for packet_size in packet_sizes() {
+                let (uncompressed, uncompressed_fragments) =
+                    send_and_receive(NymMessage::new_plain(payload.clone()), packet_size, None);
+                let (compressed, compressed_fragments) = send_and_receive(
+                    NymMessage::new_plain(payload.clone()),
+                    packet_size,
+                    Some(settings()),
+                );
+
+                assert_eq!(uncompressed.into_inner_data(), payload);
+                assert_eq!(compressed.into_inner_data(), payload);
+                assert!(
+                    compressed_fragments < uncompressed_fragments,
+                    "{packet_size:?}: {compressed_fragments} compressed fragments vs {uncompressed_fragments} uncompressed"
+                );
+            }
+        }
+
+        #[test]
+        fn compressed_replies_and_repliable_messages() {
+            let payload = json_payload(10_000);
+            for packet_size in packet_sizes() {
+                let repliable = NymMessage::new_repliable(RepliableMessage::new_data(
+                    payload.clone(),
+                    [42u8; 16].into(),
+                    vec![],
+                ));
+                let (received, _) = send_and_receive(repliable, packet_size, Some(settings()));
+                assert!(matches!(received, NymMessage::Repliable(_)));
+                assert_eq!(received.into_inner_data(), payload);
+
+                let reply = NymMessage::new_reply(ReplyMessage::new_data_message(payload.clone()));
+                let (received, _) = send_and_receive(reply, packet_size, Some(settings()));
+                assert!(matches!(received, NymMessage::Reply(_)));
+                assert_eq!(received.into_inner_data(), payload);
+            }
+        }
+
+        #[test]
+        fn small_or_incompressible_messages_are_not_compressed() {
+            let plaintext_per_packet = NymMessage::new_plain(vec![])
+                .available_sphinx_plaintext_per_packet(PacketSize::RegularPacket);
+
+            let small = NymMessage::new_plain(json_payload(settings().threshold - 10));
+            let serialized = small.serialize(Some(settings()));
+            assert!(!serialized.is_compressed());
+            let padded = serialized.pad_to_full_packet_lengths(plaintext_per_packet);
+            assert!(!padded.is_compressed());
+            assert_eq!(padded.0[0], NymMessageType::Plain as u8);
+
+            let mut random = vec![0u8; 10_000];
+            thread_rng().fill_bytes(&mut random);
+            for packet_size in packet_sizes() {
+                let (uncompressed, uncompressed_fragments) =
+                    send_and_receive(NymMessage::new_plain(random.clone()), packet_size, None);
+                let (received, fragments) = send_and_receive(
+                    NymMessage::new_plain(random.clone()),
+                    packet_size,
+                    Some(settings()),
+                );
+                assert_eq!(uncompressed.into_inner_data(), random);
+                assert_eq!(received.into_inner_data(), random);
+                assert_eq!(fragments, uncompressed_fragments);
+            }
+        }
+
+        #[test]
+        fn malformed_compressed_messages_are_rejected() {
+            let payload = NymMessage::new_plain(json_payload(1000)).into_bytes();
+
+            // lying about the decompressed length
+            let mut compressed =
+                crate::compression::compress(MessageCompression::Deflate, &payload).unwrap();
+            compressed[1..5].copy_from_slice(&100u32.to_be_bytes());
+            let bytes: Vec<_> = std::iter::once(NymMessageType::Compressed as u8)
+                .chain(compressed)
+                .collect();
+            assert!(matches!(
+                NymMessage::try_from_bytes(&bytes, 3),
+                Err(NymMessageError::MalformedCompressedMessage(
+                    CompressionError::LengthMismatch { .. }
+                ))
+            ));
+
+            // unknown algorithm
+            let bytes = vec![NymMessageType::Compressed as u8, 255, 0, 0, 0, 1, 42];
+            assert!(matches!(
+                NymMessage::try_from_bytes(&bytes, 3),
+                Err(NymMessageError::MalformedCompressedMessage(
+                    CompressionError::UnknownAlgorithm(_)
+                ))
+            ));
+
+            // compressed message within compressed message
+            let inner = NymMessage::new_plain(json_payload(1000)).into_compressed_bytes(settings());
+            let nested = crate::compression::compress(MessageCompression::Deflate, &inner).unwrap();
+            let bytes: Vec<_> = std::iter::once(NymMessageType::Compressed as u8)
+                .chain(nested)
+                .collect();
+            assert!(matches!(
+                NymMessage::try_from_bytes(&bytes, 3),
+                Err(NymMessageError::NestedCompression)
+            ));
+        }
+    }
+
+    mod erasure_coding {
+        use super::*;
+        use nym_sphinx_chunking::reconstruction::MessageReconstructor;
+        use rand::seq::SliceRandom;
+        use rand::{thread_rng, RngCore};
+
+        #[test]
+        fn message_is_recovered_despite_lost_fragments() {
+            let mut payload = vec![0u8; 100_000];
+            thread_rng().fill_bytes(&mut payload);
+            let message = NymMessage::new_plain(payload.clone());
+
+            let plaintext_per_packet =
+                message.available_sphinx_plaintext_per_packet(PacketSize::RegularPacket);
+            let mut fragments = message
+                .serialize(None)
+                .pad_for_erasure_coding()
+                .split_into_erasure_coded_fragments(&mut thread_rng(), plaintext_per_packet, 0.25);
+            let data_fragments = fragments[0].data_fragments().unwrap() as usize;
+            assert!(fragments.len() > data_fragments);
+
+            // lose all of the redundant fragments
+            fragments.shuffle(&mut thread_rng());
+            fragments.truncate(data_fragments);
+
+            let mut reconstructor = MessageReconstructor::default();
+            let mut reconstructed = None;
+            for fragment in fragments {
+                if let Some((message, _)) = reconstructor.insert_new_fragment(fragment) {
+                    reconstructed = Some(message)
+                }
+            }
+
+            let message = PaddedMessage::new_reconstructed(reconstructed.unwrap())
+                .remove_padding(3)
+                .unwrap();
+            assert_eq!(message.into_inner_data(), payload);
+        }
+    }
 }
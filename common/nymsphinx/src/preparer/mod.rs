@@ -1,7 +1,7 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::message::{NymMessage, ACK_OVERHEAD, OUTFOX_ACK_OVERHEAD};
+use crate::message::{NymMessage, SerializedMessage, ACK_OVERHEAD, OUTFOX_ACK_OVERHEAD};
 use crate::NymPayloadBuilder;
 use nym_crypto::asymmetric::encryption;
 use nym_crypto::Digest;
@@ -289,6 +289,31 @@ pub trait FragmentPreparer {
             .pad_to_full_packet_lengths(plaintext_per_packet)
             .split_into_fragments(self.rng(), plaintext_per_packet)
     }
+
+    fn pad_and_split_serialized_message(
+        &mut self,
+        message: SerializedMessage,
+        packet_size: PacketSize,
+    ) -> Vec<Fragment> {
+        let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
+
+        message
+            .pad_to_full_packet_lengths(plaintext_per_packet)
+            .split_into_fragments(self.rng(), plaintext_per_packet)
+    }
+
+    fn pad_and_split_erasure_coded_message(
+        &mut self,
+        message: SerializedMessage,
+        packet_size: PacketSize,
+        redundancy: f64,
+    ) -> Vec<Fragment> {
+        let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
+
+        message
+            .pad_for_erasure_coding()
+            .split_into_erasure_coded_fragments(self.rng(), plaintext_per_packet, redundancy)
+    }
 }
 
 /// Prepares the message that is to be sent through the mix network by attaching
@@ -434,6 +459,28 @@ where
     ) -> Vec<Fragment> {
         <Self as FragmentPreparer>::pad_and_split_message(self, message, packet_size)
     }
+
+    pub fn pad_and_split_serialized_message(
+        &mut self,
+        message: SerializedMessage,
+        packet_size: PacketSize,
+    ) -> Vec<Fragment> {
+        <Self as FragmentPreparer>::pad_and_split_serialized_message(self, message, packet_size)
+    }
+
+    pub fn pad_and_split_erasure_coded_message(
+        &mut self,
+        message: SerializedMessage,
+        packet_size: PacketSize,
+        redundancy: f64,
+    ) -> Vec<Fragment> {
+        <Self as FragmentPreparer>::pad_and_split_erasure_coded_message(
+            self,
+            message,
+            packet_size,
+            redundancy,
+        )
+    }
 }
 
 impl<R: CryptoRng + Rng> FragmentPreparer for MessagePreparer<R> {
@@ -57,6 +57,19 @@ impl From<PlainMessage> for ReconstructedMessage {
     }
 }
 
+/// [`NymMessage`] recovered from all of its received fragments.
+#[derive(Debug)]
+pub struct RecoveredMessage {
+    pub message: NymMessage,
+
+    /// Indicates whether the sender has compressed the message,
+    /// i.e. whether it understands the compressed framing itself.
+    pub compressed: bool,
+
+    /// Ids of all the fragment sets the message was recovered from.
+    pub used_sets: Vec<i32>,
+}
+
 #[derive(Debug, Error)]
 pub enum MessageRecoveryError {
     #[error("The received message did not contain enough bytes to recover the ephemeral public key. Got {provided}. required: {required}")]
@@ -141,10 +154,16 @@ pub trait MessageReceiver {
     fn insert_new_fragment(
         &mut self,
         fragment: Fragment,
-    ) -> Result<Option<(NymMessage, Vec<i32>)>, MessageRecoveryError> {
+    ) -> Result<Option<RecoveredMessage>, MessageRecoveryError> {
         if let Some((message, used_sets)) = self.reconstructor().insert_new_fragment(fragment) {
-            match PaddedMessage::new_reconstructed(message).remove_padding(self.num_mix_hops()) {
-                Ok(message) => Ok(Some((message, used_sets))),
+            let padded = PaddedMessage::new_reconstructed(message);
+            let compressed = padded.is_compressed();
+            match padded.remove_padding(self.num_mix_hops()) {
+                Ok(message) => Ok(Some(RecoveredMessage {
+                    message,
+                    compressed,
+                    used_sets,
+                })),
                 Err(err) => Err(MessageRecoveryError::MalformedReconstructedMessage {
                     source: err,
                     used_sets,
@@ -247,6 +266,10 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::One,
+                performance: mix::Performance::hundred(),
+                stake_saturation: mix::Decimal::zero(),
+                family: None,
+                latency: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -267,6 +290,10 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::Two,
+                performance: mix::Performance::hundred(),
+                stake_saturation: mix::Decimal::zero(),
+                family: None,
+                latency: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -287,6 +314,10 @@ mod message_receiver {
                 )
                 .unwrap(),
                 layer: Layer::Three,
+                performance: mix::Performance::hundred(),
+                stake_saturation: mix::Decimal::zero(),
+                family: None,
+                latency: None,
                 version: "0.8.0-dev".into(),
             }],
         );
@@ -300,6 +331,7 @@ mod message_receiver {
                 mix_host: "1.2.3.4:1789".parse().unwrap(),
                 clients_ws_port: 9000,
                 clients_wss_port: None,
+                latency: None,
                 identity_key: identity::PublicKey::from_base58_string(
                     "FioFa8nMmPpQnYi7JyojoTuwGLeyNS8BF4ChPr29zUML",
                 )
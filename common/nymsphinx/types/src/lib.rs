@@ -131,6 +131,20 @@ impl NymPacket {
         self.len() == 0
     }
 
+    /// Returns bytes uniquely identifying this packet at the current hop, i.e. the public group
+    /// element used for deriving the shared secret with the processing node.
+    /// Seeing the same tag twice under the same node key implies the packet got replayed.
+    pub fn replay_tag(&self) -> Option<&[u8]> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sphinx")]
+            NymPacket::Sphinx(packet) => Some(&packet.header.shared_secret.as_bytes()[..]),
+            #[cfg(feature = "outfox")]
+            NymPacket::Outfox(packet) => packet.replay_tag(),
+            _ => None,
+        }
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, NymPacketError> {
         #[allow(unreachable_patterns)]
         match self {
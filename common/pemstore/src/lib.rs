@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::traits::{PemStorableKey, PemStorableKeyPair};
-use pem::Pem;
 use std::fs::File;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
 pub mod traits;
 
+pub use pem::Pem;
+
 #[derive(Debug)]
 pub struct KeyPairPath {
     pub private_key_path: PathBuf,
@@ -75,14 +76,17 @@ where
     write_pem_file(path, key.to_bytes(), T::pem_type())
 }
 
-fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
+/// Reads and parses the raw PEM file without making any assumptions about its tag.
+pub fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
     let mut pem_bytes = File::open(filepath)?;
     let mut buf = Vec::new();
     pem_bytes.read_to_end(&mut buf)?;
     pem::parse(&buf).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
 }
 
-fn write_pem_file<P: AsRef<Path>>(filepath: P, data: Vec<u8>, tag: &str) -> io::Result<()> {
+/// Writes the provided data to a PEM file with the specified tag.
+/// On unix systems the permissions of the file are going to be set to `0600`.
+pub fn write_pem_file<P: AsRef<Path>>(filepath: P, data: Vec<u8>, tag: &str) -> io::Result<()> {
     // ensure the whole directory structure exists
     if let Some(parent_dir) = filepath.as_ref().parent() {
         std::fs::create_dir_all(parent_dir)?;
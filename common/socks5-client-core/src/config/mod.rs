@@ -148,6 +148,8 @@ impl Socks5 {
             ),
             provider_mix_address: provider_mix_address.into(),
             provider_interface_version: ProviderInterfaceVersion::Legacy,
+            // UDP ASSOCIATE and BIND requests are going to use the required version regardless,
+            // so that plain connections would still work with network requesters that haven't been upgraded
             socks5_protocol_version: Socks5ProtocolVersion::Legacy,
             send_anonymously: false,
             socks5_debug: Default::default(),
@@ -69,6 +69,7 @@ impl<S> NymClient<S>
 where
     S: MixnetClientStorage + 'static,
     S::ReplyStore: Send + Sync,
+    S::GatewaysDetailsStore: Send + Sync,
     <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
     <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
     <S::GatewaysDetailsStore as GatewaysDetailsStore>::StorageError: Sync + Send,
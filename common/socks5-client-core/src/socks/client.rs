@@ -2,20 +2,23 @@
 
 use super::authentication::{AuthenticationMethods, Authenticator, User};
 use super::request::{SocksCommand, SocksRequest};
-use super::types::{ResponseCodeV4, ResponseCodeV5, SocksProxyError};
+use super::types::{AddrType, ResponseCodeV4, ResponseCodeV5, SocksProxyError};
+use super::udp::UdpAssociation;
 use super::{SocksVersion, RESERVED, SOCKS4_VERSION, SOCKS5_VERSION};
 use crate::config;
 use futures::channel::mpsc;
 use futures::task::{Context, Poll};
+use futures::StreamExt;
 use log::*;
 use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
 use nym_service_providers_common::interface::{ProviderInterfaceVersion, RequestVersion};
 use nym_socks5_proxy_helpers::connection_controller::{
-    ConnectionReceiver, ControllerCommand, ControllerSender,
+    BindEventReceiver, ConnectionReceiver, ConnectionSender, ControllerCommand, ControllerSender,
 };
 use nym_socks5_proxy_helpers::proxy_runner::ProxyRunner;
 use nym_socks5_requests::{
-    ConnectionId, RemoteAddress, Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request,
+    BindEvent, ConnectionId, RemoteAddress, Socks5ProtocolVersion, Socks5ProviderRequest,
+    Socks5Request, BIND_INTERFACE_VERSION, DATAGRAM_INTERFACE_VERSION,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::params::PacketSize;
@@ -25,11 +28,16 @@ use nym_task::TaskClient;
 use pin_project::pin_project;
 use rand::RngCore;
 use std::io;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::net::TcpStream;
 
+// the service provider gives up on accepting the inbound connection after 2 minutes,
+// so give it a bit of extra time for the notification to get through the mixnet
+const BIND_EVENT_TIMEOUT: Duration = Duration::from_secs(180);
+
 #[pin_project(project = StateProject)]
 enum StreamState {
     Available(TcpStream),
@@ -160,14 +168,35 @@ impl Config {
         }
     }
 
-    fn request_version(&self) -> RequestVersion<Socks5Request> {
+    /// Version of the socks5 protocol to use for requests that are only understood by service
+    /// providers implementing at least the specified version of the interface.
+    /// The configured version is kept whenever it's recent enough.
+    fn protocol_version_at_least(&self, minimum: u8) -> Socks5ProtocolVersion {
+        match self.socks5_protocol_version.as_u8() {
+            Some(version) if version >= minimum => self.socks5_protocol_version,
+            _ => Socks5ProtocolVersion::new_versioned(minimum),
+        }
+    }
+
+    fn request_version(
+        &self,
+        provider_protocol: Socks5ProtocolVersion,
+    ) -> RequestVersion<Socks5Request> {
         RequestVersion {
             provider_interface: self.provider_interface_version,
-            provider_protocol: self.socks5_protocol_version,
+            provider_protocol,
         }
     }
 }
 
+fn parse_bind_address(address: &str) -> Result<SocketAddr, SocksProxyError> {
+    address
+        .parse()
+        .map_err(|_| SocksProxyError::InvalidBindAddress {
+            address: address.to_string(),
+        })
+}
+
 /// A client connecting to the Socks proxy server, because
 /// it wants to make a Nym-protected outbound request. Typically, this is
 /// something like e.g. a wallet app running on your laptop connecting to
@@ -184,6 +213,7 @@ pub(crate) struct SocksClient {
     service_provider: Recipient,
     self_address: Recipient,
     started_proxy: bool,
+    started_association: bool,
     lane_queue_lengths: LaneQueueLengths,
     shutdown_listener: TaskClient,
     packet_type: Option<PacketType>,
@@ -200,6 +230,13 @@ impl Drop for SocksClient {
                 })
                 .unwrap();
         }
+        if self.started_association {
+            self.controller_sender
+                .unbounded_send(ControllerCommand::RemoveAssociation {
+                    association_id: self.connection_id,
+                })
+                .unwrap();
+        }
     }
 }
 
@@ -234,6 +271,7 @@ impl SocksClient {
             service_provider: *service_provider,
             self_address: *self_address,
             started_proxy: false,
+            started_association: false,
             lane_queue_lengths,
             shutdown_listener,
             packet_type,
@@ -258,7 +296,12 @@ impl SocksClient {
                 self.send_error_v4(response).await
             }
             SocksVersion::V5 => {
-                let response = if error_text.contains("Host") {
+                let response = if matches!(
+                    err,
+                    SocksProxyError::UdpNotSupported | SocksProxyError::BindNotSupported
+                ) {
+                    ResponseCodeV5::CommandNotSupported
+                } else if error_text.contains("Host") {
                     ResponseCodeV5::HostUnreachable
                 } else if error_text.contains("Network") {
                     ResponseCodeV5::NetworkUnreachable
@@ -393,10 +436,77 @@ impl SocksClient {
         }
     }
 
+    async fn send_anonymous_bind_to_mixnet(&mut self, expected_peer: RemoteAddress) {
+        let req = Socks5Request::new_bind(
+            self.config
+                .protocol_version_at_least(BIND_INTERFACE_VERSION),
+            self.connection_id,
+            expected_peer,
+            None,
+        );
+        let msg =
+            Socks5ProviderRequest::new_provider_data(self.config.provider_interface_version, req);
+
+        let input_message = InputMessage::new_anonymous(
+            self.service_provider,
+            msg.into_bytes(),
+            self.config.connection_start_surbs,
+            TransmissionLane::ConnectionId(self.connection_id),
+            self.packet_type,
+        );
+        self.input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn send_bind_to_mixnet_with_return_address(&mut self, expected_peer: RemoteAddress) {
+        let req = Socks5Request::new_bind(
+            self.config
+                .protocol_version_at_least(BIND_INTERFACE_VERSION),
+            self.connection_id,
+            expected_peer,
+            Some(self.self_address),
+        );
+        let msg =
+            Socks5ProviderRequest::new_provider_data(self.config.provider_interface_version, req);
+
+        let input_message = InputMessage::new_regular(
+            self.service_provider,
+            msg.into_bytes(),
+            TransmissionLane::ConnectionId(self.connection_id),
+            self.packet_type,
+        );
+        self.input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn send_bind_to_mixnet(&mut self, expected_peer: RemoteAddress) {
+        if self.config.use_surbs_for_responses {
+            self.send_anonymous_bind_to_mixnet(expected_peer).await
+        } else {
+            self.send_bind_to_mixnet_with_return_address(expected_peer)
+                .await
+        }
+    }
+
     async fn run_proxy(&mut self, conn_receiver: ConnectionReceiver, remote_proxy_target: String) {
         self.send_connect_to_mixnet(remote_proxy_target.clone())
             .await;
+        let provider_protocol = self.config.socks5_protocol_version;
+        self.proxy_connection(conn_receiver, remote_proxy_target, provider_protocol)
+            .await
+    }
 
+    /// Runs the proxy between the local stream and the already established remote connection.
+    async fn proxy_connection(
+        &mut self,
+        conn_receiver: ConnectionReceiver,
+        remote_proxy_target: String,
+        provider_protocol: Socks5ProtocolVersion,
+    ) {
         let stream = self.stream.run_proxy();
         let peer_addr = match stream.peer_addr() {
             Ok(peer_addr) => peer_addr,
@@ -411,7 +521,7 @@ impl SocksClient {
         let input_sender = self.input_sender.clone();
         let anonymous = self.config.use_surbs_for_responses;
         let per_request_surbs = self.config.per_request_surbs;
-        let request_version = self.config.request_version();
+        let request_version = self.config.request_version(provider_protocol);
 
         let recipient = self.service_provider;
         let packet_type = self.packet_type;
@@ -507,13 +617,229 @@ impl SocksClient {
                 );
             }
 
-            SocksCommand::Bind => return Err(SocksProxyError::BindNotSupported), // not handled
-            SocksCommand::UdpAssociate => return Err(SocksProxyError::UdpNotSupported),
+            SocksCommand::Bind => {
+                // as per the RFC, we have to be able to send two replies, which SOCKS4 does not do
+                if *version != SocksVersion::V5 {
+                    return Err(SocksProxyError::BindNotSupported);
+                }
+                self.run_bind(mix_sender, mix_receiver, remote_address)
+                    .await?;
+            }
+            SocksCommand::UdpAssociate => {
+                // SOCKS4 has no notion of UDP
+                if *version != SocksVersion::V5 {
+                    return Err(SocksProxyError::UdpNotSupported);
+                }
+                self.run_udp_association().await?;
+            }
+        };
+
+        Ok(())
+    }
+
+    async fn next_bind_event(
+        &mut self,
+        events: &mut BindEventReceiver,
+    ) -> Result<BindEvent, SocksProxyError> {
+        tokio::select! {
+            biased;
+            _ = self.shutdown_listener.recv() => {
+                trace!("SocksClient {}: received shutdown while waiting for bind event", self.connection_id);
+                Err(SocksProxyError::BindFailure { message: "received shutdown".to_string() })
+            }
+            event = tokio::time::timeout(BIND_EVENT_TIMEOUT, events.next()) => match event {
+                Err(_timeout) => Err(SocksProxyError::BindTimeout),
+                Ok(None) => Err(SocksProxyError::BindFailure { message: "the bind request got dropped".to_string() }),
+                Ok(Some(BindEvent::Failed { message })) => Err(SocksProxyError::BindFailure { message }),
+                Ok(Some(event)) => Ok(event),
+            }
+        }
+    }
+
+    /// Asks the service provider to start listening for an inbound connection
+    /// and, once it has been accepted, proxies it back to the requesting client.
+    async fn run_bind(
+        &mut self,
+        conn_sender: ConnectionSender,
+        conn_receiver: ConnectionReceiver,
+        expected_peer: RemoteAddress,
+    ) -> Result<(), SocksProxyError> {
+        let (event_sender, mut event_receiver) = mpsc::unbounded();
+
+        // the bind listener has to be registered before the connection so that
+        // the controller would get rid of it once the connection is removed
+        self.controller_sender
+            .unbounded_send(ControllerCommand::InsertBindListener {
+                connection_id: self.connection_id,
+                event_sender,
+            })
+            .unwrap();
+        self.started_proxy = true;
+        self.controller_sender
+            .unbounded_send(ControllerCommand::Insert {
+                connection_id: self.connection_id,
+                connection_sender: conn_sender,
+            })
+            .unwrap();
+
+        self.send_bind_to_mixnet(expected_peer.clone()).await;
+
+        // first reply: the address the service provider is listening on
+        let BindEvent::Listening { bound_addr } = self.next_bind_event(&mut event_receiver).await?
+        else {
+            return Err(SocksProxyError::BindFailure {
+                message: "received inbound connection before the listener address".to_string(),
+            });
+        };
+        let bound_socket_addr = parse_bind_address(&bound_addr)?;
+        self.acknowledge_socks5_with_address(bound_socket_addr)
+            .await?;
+        info!(
+            "Listening for inbound connection from {expected_peer} on {bound_addr} (id: {})",
+            self.connection_id
+        );
+
+        // second reply: the address of the connected peer
+        let BindEvent::Accepted { peer_addr } = self.next_bind_event(&mut event_receiver).await?
+        else {
+            return Err(SocksProxyError::BindFailure {
+                message: "received duplicate listener address".to_string(),
+            });
         };
+        let peer_socket_addr = parse_bind_address(&peer_addr)?;
+        self.acknowledge_socks5_with_address(peer_socket_addr)
+            .await?;
 
+        info!(
+            "Starting proxy for inbound connection from {peer_addr} (id: {})",
+            self.connection_id
+        );
+        let provider_protocol = self
+            .config
+            .protocol_version_at_least(BIND_INTERFACE_VERSION);
+        self.proxy_connection(conn_receiver, peer_addr.clone(), provider_protocol)
+            .await;
+        info!(
+            "Proxy for inbound connection from {peer_addr} is finished (id: {})",
+            self.connection_id
+        );
         Ok(())
     }
 
+    async fn run_udp_association(&mut self) -> Result<(), SocksProxyError> {
+        let (datagram_sender, datagram_receiver) = mpsc::unbounded();
+
+        let stream = self.stream.run_proxy();
+        let association = UdpAssociation::new(
+            self.connection_id,
+            &stream,
+            datagram_receiver,
+            self.input_sender.clone(),
+            self.shutdown_listener.clone(),
+        )
+        .await;
+        self.stream.finish_proxy(stream);
+
+        let association = association?;
+        let relay_address = association.local_addr()?;
+        self.acknowledge_socks5_with_address(relay_address).await?;
+
+        self.started_association = true;
+        self.controller_sender
+            .unbounded_send(ControllerCommand::InsertAssociation {
+                association_id: self.connection_id,
+                datagram_sender,
+            })
+            .unwrap();
+
+        info!(
+            "Starting UDP association on {relay_address} (id: {})",
+            self.connection_id
+        );
+
+        let association_id = self.connection_id;
+        let anonymous = self.config.use_surbs_for_responses;
+        let return_address = (!anonymous).then_some(self.self_address);
+        let connection_start_surbs = self.config.connection_start_surbs;
+        let per_request_surbs = self.config.per_request_surbs;
+        let request_version = self.config.request_version(
+            self.config
+                .protocol_version_at_least(DATAGRAM_INTERFACE_VERSION),
+        );
+        let recipient = self.service_provider;
+        let packet_type = self.packet_type;
+        let mut is_first = true;
+
+        let mut stream = self.stream.run_proxy();
+        association
+            .run(&mut stream, move |remote_address, data| {
+                let lane = TransmissionLane::ConnectionId(association_id);
+                let provider_request = Socks5Request::new_datagram(
+                    request_version.provider_protocol,
+                    association_id,
+                    remote_address,
+                    return_address,
+                    data,
+                );
+                let provider_message = Socks5ProviderRequest::new_provider_data(
+                    request_version.provider_interface,
+                    provider_request,
+                );
+                if anonymous {
+                    let reply_surbs = if is_first {
+                        connection_start_surbs
+                    } else {
+                        per_request_surbs
+                    };
+                    is_first = false;
+                    InputMessage::new_anonymous(
+                        recipient,
+                        provider_message.into_bytes(),
+                        reply_surbs,
+                        lane,
+                        packet_type,
+                    )
+                } else {
+                    InputMessage::new_regular(
+                        recipient,
+                        provider_message.into_bytes(),
+                        lane,
+                        packet_type,
+                    )
+                }
+            })
+            .await;
+        self.stream.finish_proxy(stream);
+
+        info!("UDP association is finished (id: {})", self.connection_id);
+        Ok(())
+    }
+
+    /// Writes a Socks5 header back to the requesting client's TCP stream, including the specified
+    /// address, i.e. the UDP relay for UDP ASSOCIATE or the listener and peer addresses for BIND.
+    async fn acknowledge_socks5_with_address(
+        &mut self,
+        address: SocketAddr,
+    ) -> Result<(), SocksProxyError> {
+        let mut response = vec![SOCKS5_VERSION, ResponseCodeV5::Success as u8, RESERVED];
+        match address.ip() {
+            IpAddr::V4(ip) => {
+                response.push(AddrType::V4 as u8);
+                response.extend_from_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                response.push(AddrType::V6 as u8);
+                response.extend_from_slice(&ip.octets());
+            }
+        }
+        response.extend_from_slice(&address.port().to_be_bytes());
+
+        self.stream
+            .write_all(&response)
+            .await
+            .map_err(|source| SocksProxyError::SocketWriteError { source })
+    }
+
     /// Writes a Socks5 header back to the requesting client's TCP stream,
     /// basically saying "I acknowledge your request and am dealing with it".
     async fn acknowledge_socks5(&mut self) {
@@ -693,3 +1019,47 @@ impl SocksClient {
         Ok(methods)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(socks5_protocol_version: Socks5ProtocolVersion) -> Config {
+        Config::new(
+            PacketSize::RegularPacket,
+            ProviderInterfaceVersion::Legacy,
+            socks5_protocol_version,
+            false,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn protocol_version_is_only_bumped_when_required() {
+        let legacy = config(Socks5ProtocolVersion::Legacy);
+        assert_eq!(
+            legacy.protocol_version_at_least(DATAGRAM_INTERFACE_VERSION),
+            Socks5ProtocolVersion::new_versioned(DATAGRAM_INTERFACE_VERSION)
+        );
+        assert_eq!(
+            legacy.protocol_version_at_least(BIND_INTERFACE_VERSION),
+            Socks5ProtocolVersion::new_versioned(BIND_INTERFACE_VERSION)
+        );
+        assert_eq!(
+            legacy
+                .request_version(legacy.socks5_protocol_version)
+                .provider_protocol,
+            Socks5ProtocolVersion::Legacy
+        );
+
+        let current = config(Socks5ProtocolVersion::new_current());
+        assert_eq!(
+            current.protocol_version_at_least(DATAGRAM_INTERFACE_VERSION),
+            Socks5ProtocolVersion::new_current()
+        );
+        assert_eq!(
+            current.protocol_version_at_least(BIND_INTERFACE_VERSION),
+            Socks5ProtocolVersion::new_current()
+        );
+    }
+}
@@ -86,6 +86,18 @@ impl MixnetResponseListener {
                     .unwrap();
                 Ok(())
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::new_send_datagram(datagram))
+                    .unwrap();
+                Ok(())
+            }
+            Socks5ResponseContent::Bind(bind) => {
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::new_send_bind_event(bind))
+                    .unwrap();
+                Ok(())
+            }
             Socks5ResponseContent::Query(response) => {
                 error!("received a query response which we don't know how to handle yet!");
                 error!("got: {:?}", response);
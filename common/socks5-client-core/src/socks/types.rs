@@ -84,11 +84,32 @@ pub enum SocksProxyError {
         source: Socks5RequestError,
     },
 
-    #[error("SOCKS5 UDP not (yet) supported")]
+    #[error("UDP ASSOCIATE is only supported over SOCKS5")]
     UdpNotSupported,
 
-    #[error("SOCKS5 BIND not (yet) supported")]
+    #[error("failed to bind the UDP relay socket: {source}")]
+    UdpRelayBindFailure {
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("received malformed SOCKS5 UDP request header")]
+    MalformedUdpHeader,
+
+    #[error("received fragmented SOCKS5 UDP datagram (fragment {frag}) which is not supported")]
+    FragmentedDatagram { frag: u8 },
+
+    #[error("BIND is only supported over SOCKS5")]
     BindNotSupported,
+
+    #[error("the service provider failed to complete the BIND request: {message}")]
+    BindFailure { message: String },
+
+    #[error("the service provider has returned an invalid address for the BIND request: {address}")]
+    InvalidBindAddress { address: String },
+
+    #[error("timed out while waiting for the inbound connection of the BIND request")]
+    BindTimeout,
 }
 
 /// DST.addr variant types
@@ -0,0 +1,340 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Relaying of UDP datagrams for SOCKS5 UDP ASSOCIATE.
+//! From: https://www.rfc-editor.org/rfc/rfc1928#section-7
+
+use super::types::{AddrType, SocksProxyError};
+use super::utils as socks_utils;
+use futures::StreamExt;
+use log::*;
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_socks5_proxy_helpers::connection_controller::DatagramReceiver;
+use nym_socks5_requests::{ConnectionId, DatagramResponse, RemoteAddress};
+use nym_task::TaskClient;
+use std::net::{IpAddr, SocketAddr};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+// maximum size of a UDP payload
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+/// Parses the SOCKS5 UDP request header and returns the destination address alongside the
+/// actual payload of the datagram.
+///
+/// +----+------+------+----------+----------+----------+
+/// |RSV | FRAG | ATYP | DST.ADDR | DST.PORT |   DATA   |
+/// +----+------+------+----------+----------+----------+
+/// | 2  |  1   |  1   | Variable |    2     | Variable |
+/// +----+------+------+----------+----------+----------+
+pub(crate) fn parse_udp_request(
+    datagram: &[u8],
+) -> Result<(RemoteAddress, &[u8]), SocksProxyError> {
+    if datagram.len() < 4 {
+        return Err(SocksProxyError::MalformedUdpHeader);
+    }
+
+    // we don't support reassembly of fragments so, as per the RFC, they have to be dropped
+    let frag = datagram[2];
+    if frag != 0 {
+        return Err(SocksProxyError::FragmentedDatagram { frag });
+    }
+
+    let addr_type =
+        AddrType::from(datagram[3] as usize).ok_or(SocksProxyError::MalformedUdpHeader)?;
+    let rest = &datagram[4..];
+
+    let (address, rest) = match addr_type {
+        AddrType::V4 => {
+            if rest.len() < 4 {
+                return Err(SocksProxyError::MalformedUdpHeader);
+            }
+            let address = socks_utils::pretty_print_addr(&addr_type, &rest[..4]);
+            (address, &rest[4..])
+        }
+        AddrType::V6 => {
+            if rest.len() < 16 {
+                return Err(SocksProxyError::MalformedUdpHeader);
+            }
+            // make sure the address is bracketed so that the port could be unambiguously appended
+            let address = format!(
+                "[{}]",
+                socks_utils::pretty_print_addr(&addr_type, &rest[..16])
+            );
+            (address, &rest[16..])
+        }
+        AddrType::Domain => {
+            let Some(&domain_len) = rest.first() else {
+                return Err(SocksProxyError::MalformedUdpHeader);
+            };
+            let domain_len = domain_len as usize;
+            if rest.len() < 1 + domain_len {
+                return Err(SocksProxyError::MalformedUdpHeader);
+            }
+            let address = socks_utils::pretty_print_addr(&addr_type, &rest[1..1 + domain_len]);
+            (address, &rest[1 + domain_len..])
+        }
+    };
+
+    if rest.len() < 2 {
+        return Err(SocksProxyError::MalformedUdpHeader);
+    }
+    let port = u16::from_be_bytes([rest[0], rest[1]]);
+
+    Ok((format!("{address}:{port}"), &rest[2..]))
+}
+
+/// Prepends the SOCKS5 UDP header, with the address of the source of the datagram,
+/// to the received payload.
+pub(crate) fn encode_udp_response(source: &str, data: &[u8]) -> Vec<u8> {
+    // RSV || FRAG
+    let mut datagram = vec![0, 0, 0];
+
+    if let Ok(address) = source.parse::<SocketAddr>() {
+        match address.ip() {
+            IpAddr::V4(ip) => {
+                datagram.push(AddrType::V4 as u8);
+                datagram.extend_from_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                datagram.push(AddrType::V6 as u8);
+                datagram.extend_from_slice(&ip.octets());
+            }
+        }
+        datagram.extend_from_slice(&address.port().to_be_bytes());
+    } else {
+        // it wasn't an ip address, so treat it as a domain
+        let (host, port) = source
+            .rsplit_once(':')
+            .and_then(|(host, port)| port.parse::<u16>().ok().map(|port| (host, port)))
+            .unwrap_or((source, 0));
+        let host = &host.as_bytes()[..host.len().min(u8::MAX as usize)];
+
+        datagram.push(AddrType::Domain as u8);
+        datagram.push(host.len() as u8);
+        datagram.extend_from_slice(host);
+        datagram.extend_from_slice(&port.to_be_bytes());
+    }
+
+    datagram.extend_from_slice(data);
+    datagram
+}
+
+/// Local side of a SOCKS5 UDP association. It relays datagrams between the local socket
+/// and the mixnet for as long as the TCP control connection remains open.
+pub(crate) struct UdpAssociation {
+    association_id: ConnectionId,
+    socket: UdpSocket,
+
+    // the only host allowed to send datagrams through this association,
+    // i.e. the one that has established the control connection
+    client_ip: IpAddr,
+
+    // the actual address of the client is learned from the first datagram it sends
+    client_addr: Option<SocketAddr>,
+
+    datagram_receiver: DatagramReceiver,
+    input_sender: InputMessageSender,
+    shutdown_listener: TaskClient,
+}
+
+impl UdpAssociation {
+    pub(crate) async fn new(
+        association_id: ConnectionId,
+        control_stream: &TcpStream,
+        datagram_receiver: DatagramReceiver,
+        input_sender: InputMessageSender,
+        shutdown_listener: TaskClient,
+    ) -> Result<Self, SocksProxyError> {
+        let local_ip = control_stream
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })?
+            .ip();
+        let client_ip = control_stream
+            .peer_addr()
+            .map_err(|source| SocksProxyError::PeerAddrExtractionFailure { source })?
+            .ip();
+
+        let socket = UdpSocket::bind((local_ip, 0))
+            .await
+            .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })?;
+
+        Ok(UdpAssociation {
+            association_id,
+            socket,
+            client_ip,
+            client_addr: None,
+            datagram_receiver,
+            input_sender,
+            shutdown_listener,
+        })
+    }
+
+    pub(crate) fn local_addr(&self) -> Result<SocketAddr, SocksProxyError> {
+        self.socket
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })
+    }
+
+    async fn handle_local_datagram<F>(
+        &mut self,
+        source: SocketAddr,
+        datagram: &[u8],
+        make_message: &mut F,
+    ) where
+        F: FnMut(RemoteAddress, Vec<u8>) -> InputMessage,
+    {
+        if source.ip() != self.client_ip {
+            warn!(
+                "received a datagram from {source} that is not associated with {} - dropping it",
+                self.association_id
+            );
+            return;
+        }
+        if self.client_addr.is_none() {
+            self.client_addr = Some(source);
+        }
+
+        let (remote_address, payload) = match parse_udp_request(datagram) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                debug!("dropping datagram from {source}: {err}");
+                return;
+            }
+        };
+
+        trace!(
+            "sending {} bytes to {remote_address} (association: {})",
+            payload.len(),
+            self.association_id
+        );
+        let input_message = make_message(remote_address, payload.to_vec());
+        self.input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn handle_mixnet_datagram(&self, datagram: DatagramResponse) {
+        let Some(client_addr) = self.client_addr else {
+            debug!(
+                "received a datagram for association {} before the client has sent anything - dropping it",
+                self.association_id
+            );
+            return;
+        };
+
+        let response = encode_udp_response(&datagram.source_addr, &datagram.data);
+        if let Err(err) = self.socket.send_to(&response, client_addr).await {
+            warn!("failed to forward datagram to {client_addr}: {err}")
+        }
+    }
+
+    /// Relays the datagrams until either the control connection gets closed or we receive
+    /// a shutdown signal.
+    pub(crate) async fn run<F>(mut self, control_stream: &mut TcpStream, mut make_message: F)
+    where
+        F: FnMut(RemoteAddress, Vec<u8>) -> InputMessage,
+    {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut control_buf = [0u8; 64];
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.shutdown_listener.recv() => {
+                    trace!("UdpAssociation {}: received shutdown", self.association_id);
+                    break;
+                }
+                read = control_stream.read(&mut control_buf) => match read {
+                    // as per the RFC, the association terminates when the TCP connection terminates
+                    Ok(0) | Err(_) => {
+                        debug!("control connection of UDP association {} got closed", self.association_id);
+                        break;
+                    }
+                    Ok(_) => trace!("ignoring data received on the control connection of UDP association {}", self.association_id),
+                },
+                received = self.socket.recv_from(&mut buf) => match received {
+                    Ok((n, source)) => {
+                        self.handle_local_datagram(source, &buf[..n], &mut make_message).await
+                    }
+                    Err(err) => {
+                        warn!("failed to receive datagram on UDP association {}: {err}", self.association_id);
+                        break;
+                    }
+                },
+                datagram = self.datagram_receiver.next() => match datagram {
+                    Some(datagram) => self.handle_mixnet_datagram(datagram).await,
+                    None => {
+                        debug!("the datagram channel of UDP association {} got closed", self.association_id);
+                        break;
+                    }
+                },
+            }
+        }
+        self.shutdown_listener.mark_as_success();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_ipv4_request() {
+        let datagram = [0, 0, 0, 1, 1, 1, 1, 1, 0, 53, 42, 42];
+        let (address, payload) = parse_udp_request(&datagram).unwrap();
+        assert_eq!(address, "1.1.1.1:53");
+        assert_eq!(payload, &[42, 42]);
+    }
+
+    #[test]
+    fn parsing_domain_request() {
+        let datagram = [0, 0, 0, 3, 7, 102, 111, 111, 46, 99, 111, 109, 0, 80, 1];
+        let (address, payload) = parse_udp_request(&datagram).unwrap();
+        assert_eq!(address, "foo.com:80");
+        assert_eq!(payload, &[1]);
+    }
+
+    #[test]
+    fn parsing_ipv6_request_produces_valid_socket_address() {
+        let mut datagram = vec![0, 0, 0, 4];
+        datagram.extend_from_slice(&[0; 15]);
+        datagram.push(1);
+        datagram.extend_from_slice(&[0, 53]);
+
+        let (address, payload) = parse_udp_request(&datagram).unwrap();
+        assert!(address.parse::<SocketAddr>().is_ok());
+        assert!(payload.is_empty());
+    }
+
+    #[test]
+    fn parsing_rejects_fragments_and_malformed_headers() {
+        assert!(matches!(
+            parse_udp_request(&[0, 0, 1, 1, 1, 1, 1, 1, 0, 53]).unwrap_err(),
+            SocksProxyError::FragmentedDatagram { frag: 1 }
+        ));
+        assert!(matches!(
+            parse_udp_request(&[0, 0, 0, 1, 1, 1]).unwrap_err(),
+            SocksProxyError::MalformedUdpHeader
+        ));
+        assert!(matches!(
+            parse_udp_request(&[0, 0, 0, 9, 1, 1, 1, 1, 0, 53]).unwrap_err(),
+            SocksProxyError::MalformedUdpHeader
+        ));
+    }
+
+    #[test]
+    fn response_encoding_is_reversible() {
+        let encoded = encode_udp_response("1.1.1.1:53", &[42]);
+        assert_eq!(encoded, vec![0, 0, 0, 1, 1, 1, 1, 1, 0, 53, 42]);
+        let (address, payload) = parse_udp_request(&encoded).unwrap();
+        assert_eq!(address, "1.1.1.1:53");
+        assert_eq!(payload, &[42]);
+
+        let encoded = encode_udp_response("foo.com:80", &[1, 2]);
+        let (address, payload) = parse_udp_request(&encoded).unwrap();
+        assert_eq!(address, "foo.com:80");
+        assert_eq!(payload, &[1, 2]);
+    }
+}
@@ -5,7 +5,7 @@ use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
 use nym_ordered_buffer::{OrderedMessageBuffer, ReadContiguousData};
-use nym_socks5_requests::{ConnectionId, SocketData};
+use nym_socks5_requests::{BindEvent, BindResponse, ConnectionId, DatagramResponse, SocketData};
 use nym_task::connections::{ConnectionCommand, ConnectionCommandSender};
 use nym_task::TaskClient;
 use std::collections::{HashMap, HashSet};
@@ -28,6 +28,18 @@ pub type ConnectionSender = mpsc::UnboundedSender<ConnectionMessage>;
 /// Receiver part of the [`ConnectionSender`]
 pub type ConnectionReceiver = mpsc::UnboundedReceiver<ConnectionMessage>;
 
+/// Channel responsible for sending datagrams that were received from mix network into particular UDP association.
+pub type DatagramSender = mpsc::UnboundedSender<DatagramResponse>;
+
+/// Receiver part of the [`DatagramSender`]
+pub type DatagramReceiver = mpsc::UnboundedReceiver<DatagramResponse>;
+
+/// Channel responsible for informing particular SOCKS5 BIND request about the progress of its remote listener.
+pub type BindEventSender = mpsc::UnboundedSender<BindEvent>;
+
+/// Receiver part of the [`BindEventSender`]
+pub type BindEventReceiver = mpsc::UnboundedReceiver<BindEvent>;
+
 pub type ControllerSender = mpsc::UnboundedSender<ControllerCommand>;
 pub type ControllerReceiver = mpsc::UnboundedReceiver<ControllerCommand>;
 
@@ -42,12 +54,37 @@ pub enum ControllerCommand {
     Send {
         data: SocketData,
     },
+    InsertAssociation {
+        association_id: ConnectionId,
+        datagram_sender: DatagramSender,
+    },
+    RemoveAssociation {
+        association_id: ConnectionId,
+    },
+    SendDatagram {
+        datagram: DatagramResponse,
+    },
+    InsertBindListener {
+        connection_id: ConnectionId,
+        event_sender: BindEventSender,
+    },
+    SendBindEvent {
+        response: BindResponse,
+    },
 }
 
 impl ControllerCommand {
     pub fn new_send(data: SocketData) -> Self {
         ControllerCommand::Send { data }
     }
+
+    pub fn new_send_datagram(datagram: DatagramResponse) -> Self {
+        ControllerCommand::SendDatagram { datagram }
+    }
+
+    pub fn new_send_bind_event(response: BindResponse) -> Self {
+        ControllerCommand::SendBindEvent { response }
+    }
 }
 
 struct ActiveConnection {
@@ -82,6 +119,13 @@ pub enum BroadcastActiveConnections {
 /// proxy.
 pub struct Controller {
     active_connections: HashMap<ConnectionId, ActiveConnection>,
+
+    // unlike connections, datagrams are not ordered nor buffered - if the association is gone,
+    // the datagram is simply dropped
+    active_associations: HashMap<ConnectionId, DatagramSender>,
+
+    // BIND requests that are still waiting for their inbound connection to get accepted
+    pending_binds: HashMap<ConnectionId, BindEventSender>,
     receiver: ControllerReceiver,
 
     // TODO: this will need to be either completely removed (from code) or periodically cleaned
@@ -110,6 +154,8 @@ impl Controller {
         (
             Controller {
                 active_connections: HashMap::new(),
+                active_associations: HashMap::new(),
+                pending_binds: HashMap::new(),
                 receiver,
                 recently_closed: HashSet::new(),
                 client_connection_tx,
@@ -142,7 +188,12 @@ impl Controller {
 
     fn remove_connection(&mut self, conn_id: ConnectionId) {
         debug!("Removing {conn_id} from controller");
-        if self.active_connections.remove(&conn_id).is_none() {
+        self.pending_binds.remove(&conn_id);
+
+        // the connection might have never been established (for example if the remote
+        // was unreachable), but we might have already buffered some data for it
+        let had_pending = self.pending_messages.remove(&conn_id).is_some();
+        if self.active_connections.remove(&conn_id).is_none() && !had_pending {
             error!("tried to remove non-existing connection with id: {conn_id}",)
         }
         self.recently_closed.insert(conn_id);
@@ -160,6 +211,80 @@ impl Controller {
         }
     }
 
+    fn insert_association(
+        &mut self,
+        association_id: ConnectionId,
+        datagram_sender: DatagramSender,
+    ) {
+        if self
+            .active_associations
+            .insert(association_id, datagram_sender)
+            .is_some()
+        {
+            error!("Received a duplicate UDP association {association_id}!")
+        }
+    }
+
+    fn remove_association(&mut self, association_id: ConnectionId) {
+        debug!("Removing UDP association {association_id} from controller");
+        if self.active_associations.remove(&association_id).is_none() {
+            error!("tried to remove non-existing UDP association with id: {association_id}")
+        }
+
+        // the association is using its own transmission lane, so announce it's gone
+        if let Err(err) = self
+            .client_connection_tx
+            .unbounded_send(ConnectionCommand::Close(association_id))
+        {
+            if self.shutdown.is_shutdown_poll() {
+                log::debug!("Failed to send: {err}");
+            } else {
+                log::error!("Failed to send: {err}");
+            }
+        }
+    }
+
+    fn send_to_association(&mut self, datagram: DatagramResponse) {
+        let association_id = datagram.association_id;
+        if let Some(datagram_sender) = self.active_associations.get(&association_id) {
+            if let Err(err) = datagram_sender.unbounded_send(datagram) {
+                error!("failed to send on the UDP association channel: {err}");
+            }
+        } else {
+            debug!(
+                "Received a datagram for unknown UDP association {association_id} ({} bytes were dropped)",
+                datagram.data.len()
+            )
+        }
+    }
+
+    fn insert_bind_listener(&mut self, conn_id: ConnectionId, event_sender: BindEventSender) {
+        if self.pending_binds.insert(conn_id, event_sender).is_some() {
+            error!("Received a duplicate 'Bind' for {conn_id}!")
+        }
+    }
+
+    fn send_bind_event(&mut self, response: BindResponse) {
+        let conn_id = response.connection_id;
+
+        // once the inbound connection got accepted (or the whole thing failed),
+        // there are not going to be any more events for this request
+        let is_final = !matches!(response.event, BindEvent::Listening { .. });
+        let event_sender = if is_final {
+            self.pending_binds.remove(&conn_id)
+        } else {
+            self.pending_binds.get(&conn_id).cloned()
+        };
+
+        if let Some(event_sender) = event_sender {
+            if let Err(err) = event_sender.unbounded_send(response.event) {
+                error!("failed to send on the bind event channel: {err}");
+            }
+        } else {
+            debug!("Received a bind event for unknown request {conn_id}")
+        }
+    }
+
     fn send_to_connection(&mut self, message: SocketData) {
         let hdr = message.header;
         if let Some(active_connection) = self.active_connections.get_mut(&hdr.connection_id) {
@@ -220,6 +345,17 @@ impl Controller {
                         self.insert_connection(connection_id, connection_sender)
                     }
                     Some(ControllerCommand::Remove{ connection_id }) => self.remove_connection(connection_id),
+                    Some(ControllerCommand::SendDatagram{ datagram }) => {
+                        self.send_to_association(datagram)
+                    }
+                    Some(ControllerCommand::InsertAssociation{ association_id, datagram_sender }) => {
+                        self.insert_association(association_id, datagram_sender)
+                    }
+                    Some(ControllerCommand::RemoveAssociation{ association_id }) => self.remove_association(association_id),
+                    Some(ControllerCommand::InsertBindListener{ connection_id, event_sender }) => {
+                        self.insert_bind_listener(connection_id, event_sender)
+                    }
+                    Some(ControllerCommand::SendBindEvent{ response }) => self.send_bind_event(response),
                     None => {
                         log::trace!("SOCKS5 Controller: Stopping since channel closed");
                         break;
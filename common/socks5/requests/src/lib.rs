@@ -213,6 +213,16 @@ pub enum Socks5RequestError {
     UnsupportedProtocolVersion {
         protocol_version: <Socks5Request as interface::ServiceProviderRequest>::ProtocolVersion,
     },
+
+    #[error("received a datagram request using protocol version {protocol_version} that does not support datagrams")]
+    DatagramsNotSupported {
+        protocol_version: <Socks5Request as interface::ServiceProviderRequest>::ProtocolVersion,
+    },
+
+    #[error("received a bind request using protocol version {protocol_version} that does not support binding")]
+    BindNotSupported {
+        protocol_version: <Socks5Request as interface::ServiceProviderRequest>::ProtocolVersion,
+    },
 }
 
 fn make_bincode_serializer() -> impl bincode::Options {
@@ -22,6 +22,8 @@ pub enum RequestFlag {
     Connect = 0,
     Send = 1,
     Query = 2,
+    Datagram = 3,
+    Bind = 4,
 }
 
 impl TryFrom<u8> for RequestFlag {
@@ -32,6 +34,8 @@ impl TryFrom<u8> for RequestFlag {
             _ if value == (RequestFlag::Connect as u8) => Ok(Self::Connect),
             _ if value == (RequestFlag::Send as u8) => Ok(Self::Send),
             _ if value == (RequestFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (RequestFlag::Datagram as u8) => Ok(Self::Datagram),
+            _ if value == (RequestFlag::Bind as u8) => Ok(Self::Bind),
             value => Err(RequestDeserializationError::UnknownRequestFlag { value }),
         }
     }
@@ -60,6 +64,9 @@ pub enum RequestDeserializationError {
     #[error("malformed return address - {0}")]
     MalformedReturnAddress(RecipientFormattingError),
 
+    #[error("{value} is not a valid return address marker")]
+    InvalidReturnAddressMarker { value: u8 },
+
     #[error("failed to deserialize query request: {source}")]
     QueryDeserializationError {
         #[from]
@@ -102,6 +109,54 @@ pub struct SendRequest {
     pub data: SocketData,
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct BindRequest {
+    pub conn_id: ConnectionId,
+
+    /// Address of the peer that is expected to connect to the bound socket.
+    pub expected_peer: RemoteAddress,
+    pub return_address: Option<Recipient>,
+}
+
+impl Debug for BindRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BindRequest")
+            .field("conn_id", &self.conn_id)
+            .field("expected_peer", &self.expected_peer)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatagramRequest {
+    /// Identifier of the UDP association this datagram belongs to.
+    pub association_id: ConnectionId,
+    pub remote_addr: RemoteAddress,
+
+    // datagrams are not ordered nor are they guaranteed to be delivered,
+    // so each of them has to carry the information on how to get back to the sender
+    pub return_address: Option<Recipient>,
+    pub data: Vec<u8>,
+}
+
+impl Debug for DatagramRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DatagramRequest")
+            .field("association_id", &self.association_id)
+            .field("remote_addr", &self.remote_addr)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum QueryRequest {
@@ -151,9 +206,17 @@ impl Serializable for Socks5Request {
         // TODO: handle the case then protocol version if less then the current one. Then we should
         // make sure to only respond with the same version
 
+        let content = Socks5RequestContent::try_from_bytes(&b[1..])?;
+        if content.is_datagram() && !protocol_version.supports_datagrams() {
+            return Err(Socks5RequestError::DatagramsNotSupported { protocol_version });
+        }
+        if content.is_bind() && !protocol_version.supports_bind() {
+            return Err(Socks5RequestError::BindNotSupported { protocol_version });
+        }
+
         Ok(Socks5Request {
             protocol_version,
-            content: Socks5RequestContent::try_from_bytes(&b[1..])?,
+            content,
         })
     }
 }
@@ -205,6 +268,36 @@ impl Socks5Request {
         }
     }
 
+    pub fn new_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        association_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+        data: Vec<u8>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_datagram(
+                association_id,
+                remote_addr,
+                return_address,
+                data,
+            ),
+        }
+    }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        conn_id: ConnectionId,
+        expected_peer: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_bind(conn_id, expected_peer, return_address),
+        }
+    }
+
     pub fn new_query(
         protocol_version: Socks5ProtocolVersion,
         query: QueryRequest,
@@ -229,6 +322,14 @@ pub enum Socks5RequestContent {
     Send(SendRequest),
 
     Query(QueryRequest),
+
+    /// Send the UDP datagram to the specified `RemoteAddress` on behalf of the given association.
+    /// Any datagrams received in response should come back to the specified `Recipient`
+    Datagram(Box<DatagramRequest>),
+
+    /// Start listening for a new inbound TCP connection from the specified `RemoteAddress`.
+    /// Once accepted, the connection behaves exactly as if it was established with `Connect`.
+    Bind(Box<BindRequest>),
 }
 
 impl Socks5RequestContent {
@@ -250,6 +351,42 @@ impl Socks5RequestContent {
         Socks5RequestContent::Send(SendRequest { data })
     }
 
+    /// Construct a new Request::Datagram instance
+    pub fn new_datagram(
+        association_id: ConnectionId,
+        remote_addr: RemoteAddress,
+        return_address: Option<Recipient>,
+        data: Vec<u8>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::Datagram(Box::new(DatagramRequest {
+            association_id,
+            remote_addr,
+            return_address,
+            data,
+        }))
+    }
+
+    pub fn is_datagram(&self) -> bool {
+        matches!(self, Socks5RequestContent::Datagram(_))
+    }
+
+    /// Construct a new Request::Bind instance
+    pub fn new_bind(
+        conn_id: ConnectionId,
+        expected_peer: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::Bind(Box::new(BindRequest {
+            conn_id,
+            expected_peer,
+            return_address,
+        }))
+    }
+
+    pub fn is_bind(&self) -> bool {
+        matches!(self, Socks5RequestContent::Bind(_))
+    }
+
     /// Deserialize the request type, connection id, destination address and port,
     /// and the request body from bytes.
     ///
@@ -271,6 +408,12 @@ impl Socks5RequestContent {
     // send:
     // RequestFlag::Send || CONN_ID || LOCAL_CLOSED || DATA
     // where DATA: SEQ || TRUE_DATA
+    //
+    // datagram:
+    // RequestFlag::Datagram || ASSOCIATION_ID || ADDR_LEN || ADDR || HAS_RETURN || <RETURN_ADDR> || DATA
+    //
+    // bind:
+    // RequestFlag::Bind || CONN_ID || ADDR_LEN || ADDR || <RETURN_ADDR>
 
     pub fn try_from_bytes(b: &[u8]) -> Result<Socks5RequestContent, RequestDeserializationError> {
         // each request needs to at least contain flag and ConnectionId
@@ -280,63 +423,85 @@ impl Socks5RequestContent {
 
         match RequestFlag::try_from(b[0])? {
             RequestFlag::Connect => {
+                let (conn_id, remote_address, return_address) = parse_connection_target(&b[1..])?;
+                Ok(Socks5RequestContent::new_connect(
+                    conn_id,
+                    remote_address,
+                    return_address,
+                ))
+            }
+            RequestFlag::Bind => {
+                let (conn_id, expected_peer, return_address) = parse_connection_target(&b[1..])?;
+                Ok(Socks5RequestContent::new_bind(
+                    conn_id,
+                    expected_peer,
+                    return_address,
+                ))
+            }
+            RequestFlag::Send => Ok(Socks5RequestContent::Send(SendRequest {
+                data: SocketData::try_from_request_bytes(&b[1..])?,
+            })),
+            RequestFlag::Query => {
+                use bincode::Options;
+                let query = make_bincode_serializer().deserialize(&b[1..])?;
+                Ok(Socks5RequestContent::Query(query))
+            }
+            RequestFlag::Datagram => {
                 if b.len() < 9 {
                     return Err(RequestDeserializationError::ConnectionIdTooShort);
                 }
-                let conn_id = u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
-
-                let connect_request_bytes = &b[9..];
+                let association_id =
+                    u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
 
-                // we need to be able to read at least 2 bytes that specify address length
-                if connect_request_bytes.len() < 2 {
+                let datagram_bytes = &b[9..];
+                if datagram_bytes.len() < 2 {
                     return Err(RequestDeserializationError::AddressLengthTooShort);
                 }
 
                 let address_length =
-                    u16::from_be_bytes([connect_request_bytes[0], connect_request_bytes[1]])
-                        as usize;
+                    u16::from_be_bytes([datagram_bytes[0], datagram_bytes[1]]) as usize;
 
-                if connect_request_bytes.len() < 2 + address_length {
+                // we need the address itself alongside the return address marker
+                if datagram_bytes.len() < 2 + address_length + 1 {
                     return Err(RequestDeserializationError::AddressTooShort);
                 }
 
                 let address_start = 2;
                 let address_end = address_start + address_length;
-                let address_bytes = &connect_request_bytes[address_start..address_end];
-                let remote_address = String::from_utf8_lossy(address_bytes).to_string();
-
-                // just a temporary reference to mid-slice for ease of use
-                let recipient_data_bytes = &connect_request_bytes[address_end..];
-
-                let return_address = if recipient_data_bytes.is_empty() {
-                    None
-                } else {
-                    if recipient_data_bytes.len() != Recipient::LEN {
-                        return Err(RequestDeserializationError::ReturnAddressTooShort);
+                let remote_address =
+                    String::from_utf8_lossy(&datagram_bytes[address_start..address_end])
+                        .to_string();
+
+                let (return_address, data) = match datagram_bytes[address_end] {
+                    0 => (None, &datagram_bytes[address_end + 1..]),
+                    1 => {
+                        let recipient_start = address_end + 1;
+                        let recipient_end = recipient_start + Recipient::LEN;
+                        if datagram_bytes.len() < recipient_end {
+                            return Err(RequestDeserializationError::ReturnAddressTooShort);
+                        }
+
+                        let mut return_bytes = [0u8; Recipient::LEN];
+                        return_bytes
+                            .copy_from_slice(&datagram_bytes[recipient_start..recipient_end]);
+                        let return_address = Recipient::try_from_bytes(return_bytes)
+                            .map_err(RequestDeserializationError::MalformedReturnAddress)?;
+                        (Some(return_address), &datagram_bytes[recipient_end..])
+                    }
+                    value => {
+                        return Err(RequestDeserializationError::InvalidReturnAddressMarker {
+                            value,
+                        })
                     }
-
-                    let mut return_bytes = [0u8; Recipient::LEN];
-                    return_bytes.copy_from_slice(&recipient_data_bytes[..Recipient::LEN]);
-                    Some(
-                        Recipient::try_from_bytes(return_bytes)
-                            .map_err(RequestDeserializationError::MalformedReturnAddress)?,
-                    )
                 };
 
-                Ok(Socks5RequestContent::new_connect(
-                    conn_id,
+                Ok(Socks5RequestContent::new_datagram(
+                    association_id,
                     remote_address,
                     return_address,
+                    data.to_vec(),
                 ))
             }
-            RequestFlag::Send => Ok(Socks5RequestContent::Send(SendRequest {
-                data: SocketData::try_from_request_bytes(&b[1..])?,
-            })),
-            RequestFlag::Query => {
-                use bincode::Options;
-                let query = make_bincode_serializer().deserialize(&b[1..])?;
-                Ok(Socks5RequestContent::Query(query))
-            }
         }
     }
 
@@ -346,21 +511,20 @@ impl Socks5RequestContent {
     pub fn into_bytes(self) -> Vec<u8> {
         match self {
             // connect is: CONN_FLAG || CONN_ID || REMOTE_LEN || REMOTE || RETURN
-            Socks5RequestContent::Connect(req) => {
-                let remote_address_bytes = req.remote_addr.into_bytes();
-                let remote_address_bytes_len = remote_address_bytes.len() as u16;
-
-                let iter = std::iter::once(RequestFlag::Connect as u8)
-                    .chain(req.conn_id.to_be_bytes())
-                    .chain(remote_address_bytes_len.to_be_bytes())
-                    .chain(remote_address_bytes);
-
-                if let Some(return_address) = req.return_address {
-                    iter.chain(return_address.to_bytes()).collect()
-                } else {
-                    iter.collect()
-                }
-            }
+            Socks5RequestContent::Connect(req) => connection_target_bytes(
+                RequestFlag::Connect,
+                req.conn_id,
+                req.remote_addr,
+                req.return_address,
+            ),
+
+            // bind is: BIND_FLAG || CONN_ID || EXPECTED_PEER_LEN || EXPECTED_PEER || RETURN
+            Socks5RequestContent::Bind(req) => connection_target_bytes(
+                RequestFlag::Bind,
+                req.conn_id,
+                req.expected_peer,
+                req.return_address,
+            ),
             Socks5RequestContent::Send(req) => std::iter::once(RequestFlag::Send as u8)
                 .chain(req.data.into_request_bytes_iter())
                 .collect(),
@@ -377,10 +541,100 @@ impl Socks5RequestContent {
                     .chain(query_bytes)
                     .collect()
             }
+
+            // datagram is: DATAGRAM_FLAG || ASSOCIATION_ID || REMOTE_LEN || REMOTE || HAS_RETURN || RETURN || DATA
+            Socks5RequestContent::Datagram(req) => {
+                let remote_address_bytes = req.remote_addr.into_bytes();
+                let remote_address_bytes_len = remote_address_bytes.len() as u16;
+
+                let iter = std::iter::once(RequestFlag::Datagram as u8)
+                    .chain(req.association_id.to_be_bytes())
+                    .chain(remote_address_bytes_len.to_be_bytes())
+                    .chain(remote_address_bytes);
+
+                if let Some(return_address) = req.return_address {
+                    iter.chain(std::iter::once(1))
+                        .chain(return_address.to_bytes())
+                        .chain(req.data)
+                        .collect()
+                } else {
+                    iter.chain(std::iter::once(0)).chain(req.data).collect()
+                }
+            }
         }
     }
 }
 
+// CONN_ID || ADDR_LEN || ADDR || <RETURN_ADDR>
+fn parse_connection_target(
+    b: &[u8],
+) -> Result<(ConnectionId, RemoteAddress, Option<Recipient>), RequestDeserializationError> {
+    if b.len() < 8 {
+        return Err(RequestDeserializationError::ConnectionIdTooShort);
+    }
+    let conn_id = u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+
+    let connect_request_bytes = &b[8..];
+
+    // we need to be able to read at least 2 bytes that specify address length
+    if connect_request_bytes.len() < 2 {
+        return Err(RequestDeserializationError::AddressLengthTooShort);
+    }
+
+    let address_length =
+        u16::from_be_bytes([connect_request_bytes[0], connect_request_bytes[1]]) as usize;
+
+    if connect_request_bytes.len() < 2 + address_length {
+        return Err(RequestDeserializationError::AddressTooShort);
+    }
+
+    let address_start = 2;
+    let address_end = address_start + address_length;
+    let address_bytes = &connect_request_bytes[address_start..address_end];
+    let remote_address = String::from_utf8_lossy(address_bytes).to_string();
+
+    // just a temporary reference to mid-slice for ease of use
+    let recipient_data_bytes = &connect_request_bytes[address_end..];
+
+    let return_address = if recipient_data_bytes.is_empty() {
+        None
+    } else {
+        if recipient_data_bytes.len() != Recipient::LEN {
+            return Err(RequestDeserializationError::ReturnAddressTooShort);
+        }
+
+        let mut return_bytes = [0u8; Recipient::LEN];
+        return_bytes.copy_from_slice(&recipient_data_bytes[..Recipient::LEN]);
+        Some(
+            Recipient::try_from_bytes(return_bytes)
+                .map_err(RequestDeserializationError::MalformedReturnAddress)?,
+        )
+    };
+
+    Ok((conn_id, remote_address, return_address))
+}
+
+fn connection_target_bytes(
+    flag: RequestFlag,
+    conn_id: ConnectionId,
+    remote_addr: RemoteAddress,
+    return_address: Option<Recipient>,
+) -> Vec<u8> {
+    let remote_address_bytes = remote_addr.into_bytes();
+    let remote_address_bytes_len = remote_address_bytes.len() as u16;
+
+    let iter = std::iter::once(flag as u8)
+        .chain(conn_id.to_be_bytes())
+        .chain(remote_address_bytes_len.to_be_bytes())
+        .chain(remote_address_bytes);
+
+    if let Some(return_address) = return_address {
+        iter.chain(return_address.to_bytes()).collect()
+    } else {
+        iter.collect()
+    }
+}
+
 #[cfg(test)]
 mod request_deserialization_tests {
     use super::*;
@@ -715,4 +969,125 @@ mod request_deserialization_tests {
             assert_eq!(description, description2);
         }
     }
+
+    #[cfg(test)]
+    mod sending_datagrams {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let anonymous =
+                Socks5RequestContent::new_datagram(42, "1.1.1.1:53".to_string(), None, vec![1, 2]);
+            let bytes_anonymous = anonymous.clone().into_bytes();
+            assert_eq!(
+                bytes_anonymous,
+                vec![
+                    3, 0, 0, 0, 0, 0, 0, 0, 42, 0, 10, 49, 46, 49, 46, 49, 46, 49, 58, 53, 51, 0,
+                    1, 2
+                ]
+            );
+
+            let with_return = Socks5RequestContent::new_datagram(
+                42,
+                "1.1.1.1:53".to_string(),
+                Some(recipient),
+                vec![1, 2],
+            );
+            let bytes_with_return = with_return.clone().into_bytes();
+
+            let anonymous2 = Socks5RequestContent::try_from_bytes(&bytes_anonymous).unwrap();
+            let with_return2 = Socks5RequestContent::try_from_bytes(&bytes_with_return).unwrap();
+
+            assert_eq!(anonymous, anonymous2);
+            assert_eq!(with_return, with_return2);
+        }
+
+        #[test]
+        fn returns_error_for_invalid_return_address_marker() {
+            let request_bytes = [
+                RequestFlag::Datagram as u8,
+                1,
+                2,
+                3,
+                4,
+                5,
+                6,
+                7,
+                8,
+                0,
+                1,
+                102,
+                2,
+            ];
+            match Socks5RequestContent::try_from_bytes(&request_bytes).unwrap_err() {
+                RequestDeserializationError::InvalidReturnAddressMarker { value } => {
+                    assert_eq!(value, 2)
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        #[test]
+        fn is_rejected_for_older_protocol_versions() {
+            let datagram =
+                Socks5RequestContent::new_datagram(42, "1.1.1.1:53".to_string(), None, vec![1, 2]);
+
+            let old = Socks5Request::new(Socks5ProtocolVersion::new_versioned(3), datagram.clone());
+            assert!(matches!(
+                Socks5Request::try_from_bytes(&old.into_bytes()).unwrap_err(),
+                Socks5RequestError::DatagramsNotSupported { .. }
+            ));
+
+            let current = Socks5Request::new(Socks5ProtocolVersion::new_current(), datagram);
+            assert!(Socks5Request::try_from_bytes(&current.into_bytes()).is_ok());
+        }
+    }
+
+    #[cfg(test)]
+    mod binding_inbound_connections {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let anonymous = Socks5RequestContent::new_bind(42, "foo.com:21".to_string(), None);
+            let bytes_anonymous = anonymous.clone().into_bytes();
+            assert_eq!(
+                bytes_anonymous,
+                vec![
+                    4, 0, 0, 0, 0, 0, 0, 0, 42, 0, 10, 102, 111, 111, 46, 99, 111, 109, 58, 50, 49
+                ]
+            );
+
+            let with_return =
+                Socks5RequestContent::new_bind(42, "foo.com:21".to_string(), Some(recipient));
+            let bytes_with_return = with_return.clone().into_bytes();
+
+            let anonymous2 = Socks5RequestContent::try_from_bytes(&bytes_anonymous).unwrap();
+            let with_return2 = Socks5RequestContent::try_from_bytes(&bytes_with_return).unwrap();
+
+            assert_eq!(anonymous, anonymous2);
+            assert_eq!(with_return, with_return2);
+        }
+
+        #[test]
+        fn is_rejected_for_older_protocol_versions() {
+            let bind = Socks5RequestContent::new_bind(42, "foo.com:21".to_string(), None);
+
+            let old = Socks5Request::new(
+                Socks5ProtocolVersion::new_versioned(crate::DATAGRAM_INTERFACE_VERSION),
+                bind.clone(),
+            );
+            assert!(matches!(
+                Socks5Request::try_from_bytes(&old.into_bytes()).unwrap_err(),
+                Socks5RequestError::BindNotSupported { .. }
+            ));
+
+            let current = Socks5Request::new(Socks5ProtocolVersion::new_current(), bind);
+            assert!(Socks5Request::try_from_bytes(&current.into_bytes()).is_ok());
+        }
+    }
 }
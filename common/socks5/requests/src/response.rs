@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    make_bincode_serializer, ConnectionId, InsufficientSocketDataError, SocketData,
+    make_bincode_serializer, ConnectionId, InsufficientSocketDataError, RemoteAddress, SocketData,
     Socks5ProtocolVersion, Socks5RequestError,
 };
 use nym_exit_policy::ExitPolicy;
@@ -20,6 +20,8 @@ pub enum ResponseFlag {
     NetworkData = 1,
     ConnectionError = 2,
     Query = 3,
+    Datagram = 4,
+    Bind = 5,
 }
 
 impl TryFrom<u8> for ResponseFlag {
@@ -30,6 +32,8 @@ impl TryFrom<u8> for ResponseFlag {
             _ if value == (ResponseFlag::NetworkData as u8) => Ok(Self::NetworkData),
             _ if value == (ResponseFlag::ConnectionError as u8) => Ok(Self::ConnectionError),
             _ if value == (ResponseFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (ResponseFlag::Datagram as u8) => Ok(Self::Datagram),
+            _ if value == (ResponseFlag::Bind as u8) => Ok(Self::Bind),
             value => Err(ResponseDeserializationError::UnknownResponseFlag { value }),
         }
     }
@@ -46,6 +50,15 @@ pub enum ResponseDeserializationError {
     #[error("not enough bytes to recover the connection id")]
     ConnectionIdTooShort,
 
+    #[error("not enough bytes to recover the source address of the datagram")]
+    DatagramAddressTooShort,
+
+    #[error("{value} is not a valid bind event")]
+    UnknownBindEvent { value: u8 },
+
+    #[error("not enough bytes to recover the bind event")]
+    BindEventTooShort,
+
     #[error("{value} is not a valid response flag")]
     UnknownResponseFlag { value: u8 },
 
@@ -147,6 +160,33 @@ impl Socks5Response {
         }
     }
 
+    pub fn new_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        association_id: ConnectionId,
+        source_addr: RemoteAddress,
+        data: Vec<u8>,
+    ) -> Socks5Response {
+        Socks5Response {
+            protocol_version,
+            content: Socks5ResponseContent::Datagram(DatagramResponse::new(
+                association_id,
+                source_addr,
+                data,
+            )),
+        }
+    }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        connection_id: ConnectionId,
+        event: BindEvent,
+    ) -> Socks5Response {
+        Socks5Response {
+            protocol_version,
+            content: Socks5ResponseContent::Bind(BindResponse::new(connection_id, event)),
+        }
+    }
+
     pub fn new_query(
         protocol_version: Socks5ProtocolVersion,
         query_response: QueryResponse,
@@ -175,6 +215,8 @@ pub enum Socks5ResponseContent {
     NetworkData { content: SocketData },
     ConnectionError(ConnectionError),
     Query(QueryResponse),
+    Datagram(DatagramResponse),
+    Bind(BindResponse),
 }
 
 impl Socks5ResponseContent {
@@ -220,6 +262,14 @@ impl Socks5ResponseContent {
                     .chain(query_bytes)
                     .collect()
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                std::iter::once(ResponseFlag::Datagram as u8)
+                    .chain(datagram.into_bytes())
+                    .collect()
+            }
+            Socks5ResponseContent::Bind(bind) => std::iter::once(ResponseFlag::Bind as u8)
+                .chain(bind.into_bytes())
+                .collect(),
         }
     }
 
@@ -243,6 +293,12 @@ impl Socks5ResponseContent {
                 let query = make_bincode_serializer().deserialize(&b[1..])?;
                 Ok(Socks5ResponseContent::Query(query))
             }
+            ResponseFlag::Datagram => Ok(Socks5ResponseContent::Datagram(
+                DatagramResponse::try_from_bytes(&b[1..])?,
+            )),
+            ResponseFlag::Bind => Ok(Socks5ResponseContent::Bind(BindResponse::try_from_bytes(
+                &b[1..],
+            )?)),
         }
     }
 
@@ -308,6 +364,154 @@ impl ConnectionError {
     }
 }
 
+/// UDP datagram received by the service provider on behalf of the given association.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatagramResponse {
+    pub association_id: ConnectionId,
+    pub source_addr: RemoteAddress,
+    pub data: Vec<u8>,
+}
+
+impl DatagramResponse {
+    pub fn new(association_id: ConnectionId, source_addr: RemoteAddress, data: Vec<u8>) -> Self {
+        DatagramResponse {
+            association_id,
+            source_addr,
+            data,
+        }
+    }
+
+    // ASSOCIATION_ID || ADDR_LEN || ADDR || DATA
+    pub fn try_from_bytes(b: &[u8]) -> Result<DatagramResponse, ResponseDeserializationError> {
+        if b.is_empty() {
+            return Err(ResponseDeserializationError::NoData);
+        }
+
+        if b.len() < 8 {
+            return Err(ResponseDeserializationError::ConnectionIdTooShort);
+        }
+
+        // the unwrap here is fine as we just ensured we have enough bytes
+        let association_id = ConnectionId::from_be_bytes(b[..8].try_into().unwrap());
+
+        let address_bytes = &b[8..];
+        if address_bytes.len() < 2 {
+            return Err(ResponseDeserializationError::DatagramAddressTooShort);
+        }
+        let address_len = u16::from_be_bytes([address_bytes[0], address_bytes[1]]) as usize;
+        if address_bytes.len() < 2 + address_len {
+            return Err(ResponseDeserializationError::DatagramAddressTooShort);
+        }
+
+        let source_addr = String::from_utf8(address_bytes[2..2 + address_len].to_vec())?;
+        let data = address_bytes[2 + address_len..].to_vec();
+
+        Ok(DatagramResponse {
+            association_id,
+            source_addr,
+            data,
+        })
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        let source_addr_bytes = self.source_addr.into_bytes();
+        let source_addr_len = source_addr_bytes.len() as u16;
+
+        self.association_id
+            .to_be_bytes()
+            .into_iter()
+            .chain(source_addr_len.to_be_bytes())
+            .chain(source_addr_bytes)
+            .chain(self.data)
+            .collect()
+    }
+}
+
+/// Progress of the SOCKS5 BIND request. As per RFC1928, the client is informed once the socket
+/// starts listening and once the inbound connection has been accepted (or if either has failed).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BindEvent {
+    /// The service provider is listening for the inbound connection on the specified address.
+    Listening { bound_addr: RemoteAddress },
+
+    /// The inbound connection from the specified address has been accepted.
+    Accepted { peer_addr: RemoteAddress },
+
+    /// The service provider could not complete the request.
+    Failed { message: String },
+}
+
+impl BindEvent {
+    const LISTENING: u8 = 0;
+    const ACCEPTED: u8 = 1;
+    const FAILED: u8 = 2;
+
+    fn tag_and_value(self) -> (u8, String) {
+        match self {
+            BindEvent::Listening { bound_addr } => (Self::LISTENING, bound_addr),
+            BindEvent::Accepted { peer_addr } => (Self::ACCEPTED, peer_addr),
+            BindEvent::Failed { message } => (Self::FAILED, message),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindResponse {
+    pub connection_id: ConnectionId,
+    pub event: BindEvent,
+}
+
+impl BindResponse {
+    pub fn new(connection_id: ConnectionId, event: BindEvent) -> Self {
+        BindResponse {
+            connection_id,
+            event,
+        }
+    }
+
+    // CONN_ID || EVENT || VALUE
+    pub fn try_from_bytes(b: &[u8]) -> Result<BindResponse, ResponseDeserializationError> {
+        if b.is_empty() {
+            return Err(ResponseDeserializationError::NoData);
+        }
+
+        if b.len() < 8 {
+            return Err(ResponseDeserializationError::ConnectionIdTooShort);
+        }
+
+        if b.len() < 9 {
+            return Err(ResponseDeserializationError::BindEventTooShort);
+        }
+
+        // the unwrap here is fine as we just ensured we have enough bytes
+        let connection_id = ConnectionId::from_be_bytes(b[..8].try_into().unwrap());
+        let value = String::from_utf8(b[9..].to_vec())?;
+
+        let event = match b[8] {
+            BindEvent::LISTENING => BindEvent::Listening { bound_addr: value },
+            BindEvent::ACCEPTED => BindEvent::Accepted { peer_addr: value },
+            BindEvent::FAILED => BindEvent::Failed { message: value },
+            value => return Err(ResponseDeserializationError::UnknownBindEvent { value }),
+        };
+
+        Ok(BindResponse {
+            connection_id,
+            event,
+        })
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        let (tag, value) = self.event.tag_and_value();
+
+        self.connection_id
+            .to_be_bytes()
+            .into_iter()
+            .chain(std::iter::once(tag))
+            .chain(value.into_bytes())
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum QueryResponse {
@@ -371,6 +575,106 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod datagram_response_serde_tests {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let datagram = Socks5ResponseContent::Datagram(DatagramResponse::new(
+                42,
+                "1.1.1.1:53".to_string(),
+                vec![1, 2, 3],
+            ));
+            let bytes = datagram.clone().into_bytes();
+            assert_eq!(
+                bytes,
+                vec![
+                    4, 0, 0, 0, 0, 0, 0, 0, 42, 0, 10, 49, 46, 49, 46, 49, 46, 49, 58, 53, 51, 1,
+                    2, 3
+                ]
+            );
+
+            let datagram2 = Socks5ResponseContent::try_from_bytes(&bytes).unwrap();
+            assert_eq!(datagram, datagram2);
+        }
+
+        #[test]
+        fn deserialization_errors() {
+            let err = DatagramResponse::try_from_bytes(&[]).err().unwrap();
+            assert!(matches!(err, ResponseDeserializationError::NoData));
+
+            let err = DatagramResponse::try_from_bytes(&[1, 2, 3]).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::ConnectionIdTooShort
+            ));
+
+            let bytes: Vec<u8> = 42u64.to_be_bytes().into_iter().chain([0, 5, 1]).collect();
+            let err = DatagramResponse::try_from_bytes(&bytes).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::DatagramAddressTooShort
+            ));
+        }
+    }
+
+    #[cfg(test)]
+    mod bind_response_serde_tests {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let events = [
+                BindEvent::Listening {
+                    bound_addr: "1.2.3.4:5678".to_string(),
+                },
+                BindEvent::Accepted {
+                    peer_addr: "5.6.7.8:20".to_string(),
+                },
+                BindEvent::Failed {
+                    message: "this is an error".to_string(),
+                },
+            ];
+
+            for event in events {
+                let bind = Socks5ResponseContent::Bind(BindResponse::new(42, event));
+                let bytes = bind.clone().into_bytes();
+                assert_eq!(bytes[0], ResponseFlag::Bind as u8);
+
+                let bind2 = Socks5ResponseContent::try_from_bytes(&bytes).unwrap();
+                assert_eq!(bind, bind2);
+            }
+        }
+
+        #[test]
+        fn deserialization_errors() {
+            let err = BindResponse::try_from_bytes(&[]).err().unwrap();
+            assert!(matches!(err, ResponseDeserializationError::NoData));
+
+            let err = BindResponse::try_from_bytes(&[1, 2, 3]).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::ConnectionIdTooShort
+            ));
+
+            let err = BindResponse::try_from_bytes(&42u64.to_be_bytes())
+                .err()
+                .unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::BindEventTooShort
+            ));
+
+            let bytes: Vec<u8> = 42u64.to_be_bytes().into_iter().chain([7]).collect();
+            let err = BindResponse::try_from_bytes(&bytes).err().unwrap();
+            assert!(matches!(
+                err,
+                ResponseDeserializationError::UnknownBindEvent { value: 7 }
+            ));
+        }
+    }
+
     #[cfg(test)]
     mod serialize_query_response {
         use super::*;
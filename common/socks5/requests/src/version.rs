@@ -12,10 +12,36 @@ pub const INITIAL_INTERFACE_VERSION: u8 = 3;
 /// Defines the current version of the communication interface between socks5 clients and
 /// network requesters (socks5).
 /// It has to be incremented for any breaking change.
-pub const INTERFACE_VERSION: u8 = 3;
+pub const INTERFACE_VERSION: u8 = 5;
+
+/// Defines the first version of the communication interface that supports relaying UDP datagrams,
+/// i.e. SOCKS5 UDP ASSOCIATE.
+pub const DATAGRAM_INTERFACE_VERSION: u8 = 4;
+
+/// Defines the first version of the communication interface that supports accepting inbound
+/// connections, i.e. SOCKS5 BIND.
+pub const BIND_INTERFACE_VERSION: u8 = 5;
 
 define_simple_version!(
     Socks5ProtocolVersion,
     INITIAL_INTERFACE_VERSION,
     INTERFACE_VERSION
 );
+
+impl Socks5ProtocolVersion {
+    /// Checks whether this version of the interface allows sending and receiving UDP datagrams.
+    pub const fn supports_datagrams(&self) -> bool {
+        match self {
+            Socks5ProtocolVersion::Legacy => false,
+            Socks5ProtocolVersion::Versioned(version) => *version >= DATAGRAM_INTERFACE_VERSION,
+        }
+    }
+
+    /// Checks whether this version of the interface allows requesting inbound connections.
+    pub const fn supports_bind(&self) -> bool {
+        match self {
+            Socks5ProtocolVersion::Legacy => false,
+            Socks5ProtocolVersion::Versioned(version) => *version >= BIND_INTERFACE_VERSION,
+        }
+    }
+}
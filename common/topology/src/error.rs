@@ -30,6 +30,9 @@ pub enum NymTopologyError {
     #[error("No mixnodes available on layer {layer}")]
     EmptyMixLayer { layer: MixLayer },
 
+    #[error("Could not find a mixnode on layer {layer} that wouldn't share a family (or a subnet) with the other nodes of the route")]
+    UnsatisfiableRouteConstraints { layer: MixLayer },
+
     #[error("Uneven layer distribution. Layer {layer} has {nodes} on it, while we expected a value between {lower_bound} and {upper_bound} as we have {total_nodes} nodes in total. Full breakdown: {layer_distribution:?}")]
     UnevenLayerDistribution {
         layer: MixLayer,
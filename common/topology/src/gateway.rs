@@ -16,6 +16,7 @@ use std::fmt::Formatter;
 use std::io;
 use std::net::AddrParseError;
 use std::net::SocketAddr;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -64,6 +65,9 @@ pub struct Node {
     pub identity_key: identity::PublicKey,
     pub sphinx_key: encryption::PublicKey, // TODO: or nymsphinx::PublicKey? both are x25519
 
+    /// Round-trip-time of the node as measured by other nodes in the network, if known.
+    pub latency: Option<Duration>,
+
     // to be removed:
     pub owner: Option<String>,
     pub version: NodeVersion,
@@ -79,6 +83,7 @@ impl std::fmt::Debug for Node {
             .field("clients_wss_port", &self.clients_wss_port)
             .field("identity_key", &self.identity_key.to_base58_string())
             .field("sphinx_key", &self.sphinx_key.to_base58_string())
+            .field("latency", &self.latency)
             .field("version", &self.version)
             .finish()
     }
@@ -165,6 +170,7 @@ impl<'a> TryFrom<&'a GatewayBond> for Node {
             mix_host,
             clients_ws_port: bond.gateway.clients_port,
             clients_wss_port: None,
+            latency: None,
             identity_key: identity::PublicKey::from_base58_string(&bond.gateway.identity_key)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&bond.gateway.sphinx_key)?,
             version: bond.gateway.version.as_str().into(),
@@ -210,6 +216,7 @@ impl<'a> TryFrom<&'a DescribedGateway> for Node {
             mix_host,
             clients_ws_port: self_described.mixnet_websockets.ws_port,
             clients_wss_port: self_described.mixnet_websockets.wss_port,
+            latency: None,
             identity_key: identity::PublicKey::from_base58_string(
                 &self_described.host_information.keys.ed25519,
             )?,
@@ -254,6 +261,9 @@ impl<'a> TryFrom<&'a SkimmedNode> for Node {
             mix_host: SocketAddr::new(*ip, value.mix_port),
             clients_ws_port: entry_details.ws_port,
             clients_wss_port: entry_details.wss_port,
+            latency: value
+                .latency
+                .map(|latency| Duration::from_micros(latency.median_rtt_micros)),
             identity_key: value.ed25519_identity_pubkey.parse()?,
             sphinx_key: value.x25519_sphinx_pubkey.parse()?,
             owner: None,
@@ -30,6 +30,10 @@ pub mod filter;
 pub mod gateway;
 pub mod mix;
 pub mod random_route_provider;
+pub mod route_selection;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 #[cfg(feature = "provider-trait")]
 pub mod provider_trait;
@@ -37,13 +41,23 @@ pub mod provider_trait;
 #[cfg(feature = "serializable")]
 pub(crate) mod serde;
 
+#[cfg(feature = "serializable")]
+pub mod snapshot;
+
 #[cfg(feature = "serializable")]
 pub use crate::serde::{
     SerializableGateway, SerializableMixNode, SerializableNymTopology, SerializableTopologyError,
 };
 
+#[cfg(feature = "serializable")]
+pub use crate::snapshot::{
+    SignedSnapshotTopologyProvider, SignedTopologySnapshot, TopologySnapshot,
+    TopologySnapshotError, TopologySnapshotMetadata,
+};
+
 #[cfg(feature = "provider-trait")]
 pub use provider_trait::{HardcodedTopologyProvider, TopologyProvider};
+pub use route_selection::{choose_by_latency, RouteConstraints, RouteSelectionStrategy};
 
 #[derive(Debug, Default, Clone)]
 pub enum NodeVersion {
@@ -120,11 +134,22 @@ pub type MixLayer = u8;
 pub struct NymTopology {
     mixes: BTreeMap<MixLayer, Vec<mix::Node>>,
     gateways: Vec<gateway::Node>,
+    route_selection: RouteSelectionStrategy,
+    route_constraints: RouteConstraints,
 }
 
+// the number of times we attempt to construct a route satisfying the constraints
+// before giving up
+const MAX_ROUTE_CONSTRUCTION_ATTEMPTS: usize = 8;
+
 impl NymTopology {
     pub fn new(mixes: BTreeMap<MixLayer, Vec<mix::Node>>, gateways: Vec<gateway::Node>) -> Self {
-        NymTopology { mixes, gateways }
+        NymTopology {
+            mixes,
+            gateways,
+            route_selection: Default::default(),
+            route_constraints: Default::default(),
+        }
     }
 
     pub fn new_unordered(unordered_mixes: Vec<mix::Node>, gateways: Vec<gateway::Node>) -> Self {
@@ -135,7 +160,7 @@ impl NymTopology {
             layer_entry.push(node)
         }
 
-        NymTopology { mixes, gateways }
+        NymTopology::new(mixes, gateways)
     }
 
     pub fn from_unordered<MI, GI, M, G>(unordered_mixes: MI, unordered_gateways: GI) -> Self
@@ -183,6 +208,34 @@ impl NymTopology {
         nym_topology_from_detailed(mix_details, gateway_bonds)
     }
 
+    #[must_use]
+    pub fn with_route_selection(mut self, route_selection: RouteSelectionStrategy) -> Self {
+        self.route_selection = route_selection;
+        self
+    }
+
+    pub fn set_route_selection(&mut self, route_selection: RouteSelectionStrategy) {
+        self.route_selection = route_selection
+    }
+
+    pub fn route_selection(&self) -> &RouteSelectionStrategy {
+        &self.route_selection
+    }
+
+    #[must_use]
+    pub fn with_route_constraints(mut self, route_constraints: RouteConstraints) -> Self {
+        self.route_constraints = route_constraints;
+        self
+    }
+
+    pub fn set_route_constraints(&mut self, route_constraints: RouteConstraints) {
+        self.route_constraints = route_constraints
+    }
+
+    pub fn route_constraints(&self) -> RouteConstraints {
+        self.route_constraints
+    }
+
     pub fn find_mix(&self, mix_id: MixId) -> Option<&mix::Node> {
         for nodes in self.mixes.values() {
             for node in nodes {
@@ -264,22 +317,14 @@ impl NymTopology {
             .ok_or(NymTopologyError::NoGatewaysAvailable)
     }
 
-    /// Returns a vec of size of `num_mix_hops` of mixnodes, such that each subsequent node is on
-    /// next layer, starting from layer 1
-    pub fn random_mix_route<R>(
+    fn try_random_mix_route<R>(
         &self,
         rng: &mut R,
         num_mix_hops: u8,
-    ) -> Result<Vec<SphinxNode>, NymTopologyError>
+    ) -> Result<Vec<&mix::Node>, NymTopologyError>
     where
         R: Rng + CryptoRng + ?Sized,
     {
-        if self.mixes.len() < num_mix_hops as usize {
-            return Err(NymTopologyError::InvalidNumberOfHopsError {
-                available: self.mixes.len(),
-                requested: num_mix_hops as usize,
-            });
-        }
         let mut route = Vec::with_capacity(num_mix_hops as usize);
 
         // there is no "layer 0"
@@ -288,19 +333,63 @@ impl NymTopology {
             let layer_mixes = self
                 .mixes
                 .get(&layer)
+                .filter(|mixes| !mixes.is_empty())
                 .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
 
-            // choose a random mix from the above list
-            // this can return a 'None' only if slice is empty
-            let random_mix = layer_mixes
-                .choose(rng)
-                .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
-            route.push(random_mix.into());
+            // only consider mixes that could be put alongside the already chosen ones
+            let candidates = layer_mixes
+                .iter()
+                .filter(|node| self.route_constraints.permits(&route, node))
+                .collect::<Vec<_>>();
+
+            // choose a random mix from the above list according to the selection strategy
+            // this can return a 'None' only if there are no valid candidates
+            let random_mix = self
+                .route_selection
+                .choose(rng, &candidates)
+                .ok_or(NymTopologyError::UnsatisfiableRouteConstraints { layer })?;
+            route.push(*random_mix);
         }
 
         Ok(route)
     }
 
+    /// Returns a vec of size of `num_mix_hops` of mixnodes, such that each subsequent node is on
+    /// next layer, starting from layer 1.
+    /// Nodes within each layer are chosen according to the configured `RouteSelectionStrategy`
+    /// and no two nodes of the route belong to the same family
+    /// (or the same subnet, if required by the `RouteConstraints`).
+    pub fn random_mix_route<R>(
+        &self,
+        rng: &mut R,
+        num_mix_hops: u8,
+    ) -> Result<Vec<SphinxNode>, NymTopologyError>
+    where
+        R: Rng + CryptoRng + ?Sized,
+    {
+        if self.mixes.len() < num_mix_hops as usize {
+            return Err(NymTopologyError::InvalidNumberOfHopsError {
+                available: self.mixes.len(),
+                requested: num_mix_hops as usize,
+            });
+        }
+
+        // the nodes are chosen greedily layer by layer, so an unlucky choice on an earlier layer
+        // might make it impossible to satisfy the constraints even though a valid route exists
+        let mut attempt = 1;
+        loop {
+            match self.try_random_mix_route(rng, num_mix_hops) {
+                Ok(route) => return Ok(route.into_iter().map(Into::into).collect()),
+                Err(NymTopologyError::UnsatisfiableRouteConstraints { .. })
+                    if attempt < MAX_ROUTE_CONSTRUCTION_ATTEMPTS =>
+                {
+                    attempt += 1
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
     /// Tries to create a route to the specified gateway, such that it goes through mixnode on layer 1,
     /// mixnode on layer2, .... mixnode on layer n and finally the target gateway
     pub fn random_route_to_gateway<R>(
@@ -416,6 +505,8 @@ impl NymTopology {
         NymTopology {
             mixes: self.mixes.filter_by_version(expected_mix_version),
             gateways: self.gateways.clone(),
+            route_selection: self.route_selection.clone(),
+            route_constraints: self.route_constraints,
         }
     }
 }
@@ -515,28 +606,15 @@ mod converting_mixes_to_vec {
 
     #[cfg(test)]
     mod when_nodes_exist {
-        use nym_crypto::asymmetric::{encryption, identity};
-
         use super::*;
-        use nym_mixnet_contract_common::Layer;
+        use crate::testing::mixnode;
 
         #[test]
         fn returns_a_vec_with_hashmap_values() {
             let node1 = mix::Node {
-                mix_id: 42,
                 owner: Some("N/A".to_string()),
-                host: "3.3.3.3".parse().unwrap(),
-                mix_host: "3.3.3.3:1789".parse().unwrap(),
-                identity_key: identity::PublicKey::from_base58_string(
-                    "3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7",
-                )
-                .unwrap(),
-                sphinx_key: encryption::PublicKey::from_base58_string(
-                    "C7cown6dYCLZpLiMFC1PaBmhvLvmJmLDJGeRTbPD45bX",
-                )
-                .unwrap(),
-                layer: Layer::One,
                 version: "0.2.0".into(),
+                ..mixnode(42)
             };
 
             let node2 = mix::Node {
@@ -573,3 +651,82 @@ mod converting_mixes_to_vec {
         }
     }
 }
+
+#[cfg(test)]
+mod constructing_mix_routes {
+    use super::*;
+    use crate::mix::{FamilyHead, Layer};
+    use crate::testing::mixnode;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn node(mix_id: MixId, layer: Layer, host: &str, family: Option<&str>) -> mix::Node {
+        mix::Node {
+            host: host.parse().unwrap(),
+            mix_host: format!("{host}:1789").parse().unwrap(),
+            layer,
+            family: family.map(FamilyHead::new),
+            ..mixnode(mix_id)
+        }
+    }
+
+    fn topology(nodes: Vec<mix::Node>) -> NymTopology {
+        NymTopology::new_unordered(nodes, vec![])
+    }
+
+    #[test]
+    fn never_includes_multiple_nodes_of_the_same_family() {
+        let topology = topology(vec![
+            node(1, Layer::One, "1.1.1.1", Some("family")),
+            node(2, Layer::One, "2.2.2.2", None),
+            node(3, Layer::Two, "3.3.3.3", Some("family")),
+            node(4, Layer::Two, "4.4.4.4", None),
+            node(5, Layer::Three, "5.5.5.5", Some("family")),
+            node(6, Layer::Three, "6.6.6.6", Some("other-family")),
+        ]);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let route = topology.try_random_mix_route(&mut rng, 3).unwrap();
+            let family_members = route
+                .iter()
+                .filter(|node| node.family == Some(FamilyHead::new("family")))
+                .count();
+            assert!(family_members <= 1);
+        }
+    }
+
+    #[test]
+    fn optionally_avoids_the_same_subnet() {
+        let topology = topology(vec![
+            node(1, Layer::One, "10.0.1.1", None),
+            node(2, Layer::Two, "10.0.2.2", None),
+            node(3, Layer::Two, "20.0.2.2", None),
+            node(4, Layer::Three, "10.0.3.3", None),
+            node(5, Layer::Three, "30.0.3.3", None),
+        ])
+        .with_route_constraints(RouteConstraints::new(true));
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let route = topology.try_random_mix_route(&mut rng, 3).unwrap();
+            let ids = route.iter().map(|node| node.mix_id).collect::<Vec<_>>();
+            assert_eq!(ids, vec![1, 3, 5]);
+        }
+    }
+
+    #[test]
+    fn fails_when_constraints_cannot_be_satisfied() {
+        let topology = topology(vec![
+            node(1, Layer::One, "1.1.1.1", Some("family")),
+            node(2, Layer::Two, "2.2.2.2", Some("family")),
+            node(3, Layer::Three, "3.3.3.3", None),
+        ]);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        assert!(matches!(
+            topology.random_mix_route(&mut rng, 3),
+            Err(NymTopologyError::UnsatisfiableRouteConstraints { layer: 2 })
+        ));
+    }
+}
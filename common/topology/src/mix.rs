@@ -3,7 +3,9 @@
 
 use crate::{filter, NetworkAddress, NodeVersion};
 use nym_crypto::asymmetric::{encryption, identity};
-pub use nym_mixnet_contract_common::Layer;
+pub use nym_mixnet_contract_common::families::FamilyHead;
+pub use nym_mixnet_contract_common::reward_params::Performance;
+pub use nym_mixnet_contract_common::{Decimal, Layer};
 use nym_mixnet_contract_common::{MixId, MixNodeBond};
 use nym_sphinx_addressing::nodes::NymNodeRoutingAddress;
 use nym_sphinx_types::Node as SphinxNode;
@@ -14,6 +16,7 @@ use rand::thread_rng;
 use std::fmt::Formatter;
 use std::io;
 use std::net::SocketAddr;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -52,6 +55,20 @@ pub struct Node {
     pub sphinx_key: encryption::PublicKey, // TODO: or nymsphinx::PublicKey? both are x25519
     pub layer: Layer,
 
+    /// Average performance of the node. It's used for weighting the route selection.
+    pub performance: Performance,
+
+    /// Stake saturation of the node. It's used for weighting the route selection.
+    pub stake_saturation: Decimal,
+
+    /// Head of the family the node belongs to, if any.
+    /// A single route never contains more than one node from the same family.
+    pub family: Option<FamilyHead>,
+
+    /// Round-trip-time of the node as measured by other nodes in the network, if known.
+    /// It's used for weighting the latency-aware route selection.
+    pub latency: Option<Duration>,
+
     // to be removed:
     pub version: NodeVersion,
     pub owner: Option<String>,
@@ -67,6 +84,10 @@ impl std::fmt::Debug for Node {
             .field("identity_key", &self.identity_key.to_base58_string())
             .field("sphinx_key", &self.sphinx_key.to_base58_string())
             .field("layer", &self.layer)
+            .field("performance", &self.performance)
+            .field("stake_saturation", &self.stake_saturation)
+            .field("family", &self.family)
+            .field("latency", &self.latency)
             .field("version", &self.version)
             .finish()
     }
@@ -127,6 +148,11 @@ impl<'a> TryFrom<&'a MixNodeBond> for Node {
             identity_key: identity::PublicKey::from_base58_string(&bond.mix_node.identity_key)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&bond.mix_node.sphinx_key)?,
             layer: bond.layer,
+            // the bond on its own does not contain any information about the performance or the stake
+            performance: Performance::hundred(),
+            stake_saturation: Decimal::zero(),
+            family: None,
+            latency: None,
             version: bond.mix_node.version.as_str().into(),
         })
     }
@@ -162,6 +188,12 @@ impl<'a> TryFrom<&'a SkimmedNode> for Node {
             identity_key: value.ed25519_identity_pubkey.parse()?,
             sphinx_key: value.x25519_sphinx_pubkey.parse()?,
             layer,
+            performance: value.performance,
+            stake_saturation: value.stake_saturation,
+            family: value.family.clone(),
+            latency: value
+                .latency
+                .map(|latency| Duration::from_micros(latency.median_rtt_micros)),
             owner: None,
             version: NodeVersion::Unknown,
         })
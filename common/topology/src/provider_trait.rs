@@ -9,12 +9,26 @@ pub use async_trait::async_trait;
 #[async_trait]
 pub trait TopologyProvider: Send {
     async fn get_new_topology(&mut self) -> Option<NymTopology>;
+
+    /// Resolves once the provider has learned that the network topology has changed
+    /// and should be re-fetched ahead of the regular refresh.
+    /// By default providers are never notified of any changes.
+    async fn wait_for_update(&mut self) {
+        std::future::pending::<()>().await
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
 #[async_trait(?Send)]
 pub trait TopologyProvider {
     async fn get_new_topology(&mut self) -> Option<NymTopology>;
+
+    /// Resolves once the provider has learned that the network topology has changed
+    /// and should be re-fetched ahead of the regular refresh.
+    /// By default providers are never notified of any changes.
+    async fn wait_for_update(&mut self) {
+        std::future::pending::<()>().await
+    }
 }
 
 pub struct HardcodedTopologyProvider {
@@ -0,0 +1,395 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mix::{self, Decimal};
+use rand::seq::SliceRandom;
+use rand::{CryptoRng, Rng};
+use std::borrow::Borrow;
+use std::fmt::{self, Debug, Formatter};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type CustomWeightFn = Arc<dyn Fn(&mix::Node) -> f64 + Send + Sync>;
+
+/// Defines how a mixnode is chosen from each layer when constructing a route.
+#[derive(Clone, Default)]
+pub enum RouteSelectionStrategy {
+    /// Every node within a layer is equally likely to get chosen.
+    #[default]
+    Uniform,
+
+    /// Nodes are chosen proportionally to their performance.
+    PerformanceWeighted,
+
+    /// Nodes are chosen proportionally to their stake saturation.
+    StakeWeighted,
+
+    /// Nodes are chosen proportionally to the weight returned by the provided function.
+    /// Negative and non-finite weights are treated as zero.
+    Custom(CustomWeightFn),
+
+    /// Nodes with lower measured round-trip-time are preferred.
+    /// `anonymity_budget` (clamped to `[0, 1]`) is the fraction of the selection probability
+    /// that gets distributed proportionally to the inverse of the latency,
+    /// the remainder is spread uniformly so that every node keeps a minimum chance of being chosen.
+    LatencyWeighted { anonymity_budget: f64 },
+}
+
+impl Debug for RouteSelectionStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteSelectionStrategy::Uniform => write!(f, "Uniform"),
+            RouteSelectionStrategy::PerformanceWeighted => write!(f, "PerformanceWeighted"),
+            RouteSelectionStrategy::StakeWeighted => write!(f, "StakeWeighted"),
+            RouteSelectionStrategy::Custom(_) => write!(f, "Custom"),
+            RouteSelectionStrategy::LatencyWeighted { anonymity_budget } => {
+                write!(
+                    f,
+                    "LatencyWeighted {{ anonymity_budget: {anonymity_budget} }}"
+                )
+            }
+        }
+    }
+}
+
+fn decimal_to_f64(value: Decimal) -> f64 {
+    value.atomics().u128() as f64 / 10f64.powi(Decimal::DECIMAL_PLACES as i32)
+}
+
+impl RouteSelectionStrategy {
+    pub fn custom<F>(weight: F) -> Self
+    where
+        F: Fn(&mix::Node) -> f64 + Send + Sync + 'static,
+    {
+        RouteSelectionStrategy::Custom(Arc::new(weight))
+    }
+
+    fn weight(&self, node: &mix::Node) -> f64 {
+        let weight = match self {
+            RouteSelectionStrategy::Uniform => 1.,
+            RouteSelectionStrategy::PerformanceWeighted => decimal_to_f64(node.performance.value()),
+            RouteSelectionStrategy::StakeWeighted => decimal_to_f64(node.stake_saturation),
+            RouteSelectionStrategy::Custom(weight_fn) => weight_fn(node),
+            // the latency weights are relative to the other nodes in the layer,
+            // so they're handled separately in `choose`
+            RouteSelectionStrategy::LatencyWeighted { .. } => 1.,
+        };
+
+        if weight.is_finite() && weight > 0. {
+            weight
+        } else {
+            0.
+        }
+    }
+
+    /// Chooses a node out of the provided slice according to the strategy.
+    /// If none of the nodes has a positive weight, the choice falls back to the uniform selection.
+    /// It returns `None` only if the slice is empty.
+    pub fn choose<'a, R, N>(&self, rng: &mut R, nodes: &'a [N]) -> Option<&'a N>
+    where
+        R: Rng + CryptoRng + ?Sized,
+        N: Borrow<mix::Node>,
+    {
+        match self {
+            RouteSelectionStrategy::Uniform => return nodes.choose(rng),
+            RouteSelectionStrategy::LatencyWeighted { anonymity_budget } => {
+                return choose_by_latency(rng, nodes, *anonymity_budget, |node| {
+                    let node: &mix::Node = node.borrow();
+                    node.latency
+                })
+            }
+            _ => (),
+        }
+
+        nodes
+            .choose_weighted(rng, |node| self.weight(node.borrow()))
+            .ok()
+            .or_else(|| nodes.choose(rng))
+    }
+}
+
+/// Chooses a node out of the provided slice preferring the ones with lower latency.
+/// The `anonymity_budget` (clamped to `[0, 1]`) is the fraction of the selection probability
+/// distributed proportionally to the inverse of the latency, while the remainder is spread uniformly,
+/// meaning each node is chosen with the probability of at least `(1 - anonymity_budget) / n`.
+/// Nodes with unknown latency are treated as having the median latency of the measured ones.
+/// If no latency is known at all, the choice falls back to the uniform selection.
+/// It returns `None` only if the slice is empty.
+pub fn choose_by_latency<'a, R, N, F>(
+    rng: &mut R,
+    nodes: &'a [N],
+    anonymity_budget: f64,
+    latency: F,
+) -> Option<&'a N>
+where
+    R: Rng + ?Sized,
+    F: Fn(&N) -> Option<Duration>,
+{
+    let budget = if anonymity_budget.is_finite() {
+        anonymity_budget.clamp(0., 1.)
+    } else {
+        0.
+    };
+
+    let inverse_latencies = nodes
+        .iter()
+        .map(|node| {
+            latency(node)
+                .map(|latency| latency.as_secs_f64())
+                .filter(|latency| *latency > 0.)
+                .map(|latency| 1. / latency)
+        })
+        .collect::<Vec<_>>();
+
+    let mut known = inverse_latencies
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    if budget == 0. || known.is_empty() {
+        return nodes.choose(rng);
+    }
+
+    known.sort_by(|a, b| a.total_cmp(b));
+    let median = known[known.len() / 2];
+
+    let weights = inverse_latencies
+        .into_iter()
+        .map(|weight| weight.unwrap_or(median))
+        .collect::<Vec<_>>();
+    let total: f64 = weights.iter().sum();
+    let uniform_share = (1. - budget) / nodes.len() as f64;
+
+    let indices = (0..nodes.len()).collect::<Vec<_>>();
+    indices
+        .choose_weighted(rng, |&i| uniform_share + budget * weights[i] / total)
+        .ok()
+        .map(|&i| &nodes[i])
+        .or_else(|| nodes.choose(rng))
+}
+
+/// Constraints every constructed route has to satisfy.
+/// Regardless of the settings, a route never contains more than a single node from the same family.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteConstraints {
+    /// Specifies whether a route is allowed to contain at most a single node
+    /// from the same /16 (IPv4) or /32 (IPv6) subnet.
+    pub distinct_subnets: bool,
+}
+
+fn same_subnet(a: IpAddr, b: IpAddr) -> bool {
+    match (a, b) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => a.octets()[..2] == b.octets()[..2],
+        (IpAddr::V6(a), IpAddr::V6(b)) => a.segments()[..2] == b.segments()[..2],
+        _ => false,
+    }
+}
+
+impl RouteConstraints {
+    pub fn new(distinct_subnets: bool) -> Self {
+        RouteConstraints { distinct_subnets }
+    }
+
+    /// Checks whether the candidate node can be put in the same route as the already chosen nodes.
+    pub fn permits(&self, route: &[&mix::Node], candidate: &mix::Node) -> bool {
+        route.iter().all(|node| {
+            if let (Some(family), Some(candidate_family)) = (&node.family, &candidate.family) {
+                if family == candidate_family {
+                    return false;
+                }
+            }
+
+            !(self.distinct_subnets && same_subnet(node.mix_host.ip(), candidate.mix_host.ip()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mix::Performance;
+    use crate::testing::mixnode;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    const SAMPLES: usize = 100_000;
+    // maximum allowed difference between the expected and the observed selection frequency
+    const TOLERANCE: f64 = 0.01;
+
+    fn node(mix_id: u32, performance: u64, stake_saturation: u64) -> mix::Node {
+        mix::Node {
+            performance: Performance::from_percentage_value(performance).unwrap(),
+            stake_saturation: Decimal::percent(stake_saturation),
+            ..mixnode(mix_id)
+        }
+    }
+
+    fn nodes() -> Vec<mix::Node> {
+        vec![
+            node(1, 100, 10),
+            node(2, 50, 30),
+            node(3, 25, 60),
+            node(4, 0, 0),
+        ]
+    }
+
+    fn selection_frequencies(
+        strategy: &RouteSelectionStrategy,
+        nodes: &[mix::Node],
+    ) -> HashMap<u32, f64> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = HashMap::new();
+        for _ in 0..SAMPLES {
+            let chosen = strategy.choose(&mut rng, nodes).unwrap();
+            *counts.entry(chosen.mix_id).or_insert(0usize) += 1;
+        }
+
+        counts
+            .into_iter()
+            .map(|(mix_id, count)| (mix_id, count as f64 / SAMPLES as f64))
+            .collect()
+    }
+
+    fn assert_distribution(observed: &HashMap<u32, f64>, expected: &[(u32, f64)]) {
+        for (mix_id, expected_frequency) in expected {
+            let observed_frequency = observed.get(mix_id).copied().unwrap_or_default();
+            assert!(
+                (observed_frequency - expected_frequency).abs() < TOLERANCE,
+                "node {mix_id} was chosen with frequency {observed_frequency} while {expected_frequency} was expected"
+            );
+        }
+    }
+
+    #[test]
+    fn uniform_selection() {
+        let observed = selection_frequencies(&RouteSelectionStrategy::Uniform, &nodes());
+        assert_distribution(&observed, &[(1, 0.25), (2, 0.25), (3, 0.25), (4, 0.25)]);
+    }
+
+    #[test]
+    fn performance_weighted_selection() {
+        let observed =
+            selection_frequencies(&RouteSelectionStrategy::PerformanceWeighted, &nodes());
+        // total weight: 1 + 0.5 + 0.25 + 0 = 1.75
+        assert_distribution(
+            &observed,
+            &[(1, 1. / 1.75), (2, 0.5 / 1.75), (3, 0.25 / 1.75), (4, 0.)],
+        );
+    }
+
+    #[test]
+    fn stake_weighted_selection() {
+        let observed = selection_frequencies(&RouteSelectionStrategy::StakeWeighted, &nodes());
+        // total weight: 0.1 + 0.3 + 0.6 + 0 = 1
+        assert_distribution(&observed, &[(1, 0.1), (2, 0.3), (3, 0.6), (4, 0.)]);
+    }
+
+    #[test]
+    fn custom_weighted_selection() {
+        let strategy =
+            RouteSelectionStrategy::custom(|node| if node.mix_id % 2 == 0 { 3. } else { 1. });
+        let observed = selection_frequencies(&strategy, &nodes());
+        assert_distribution(&observed, &[(1, 0.125), (2, 0.375), (3, 0.125), (4, 0.375)]);
+    }
+
+    #[test]
+    fn falls_back_to_uniform_selection_without_positive_weights() {
+        let nodes = vec![node(1, 0, 0), node(2, 0, 0)];
+        let observed = selection_frequencies(&RouteSelectionStrategy::StakeWeighted, &nodes);
+        assert_distribution(&observed, &[(1, 0.5), (2, 0.5)]);
+
+        let strategy = RouteSelectionStrategy::custom(|_| f64::NAN);
+        let observed = selection_frequencies(&strategy, &nodes);
+        assert_distribution(&observed, &[(1, 0.5), (2, 0.5)]);
+    }
+
+    fn nodes_with_latencies() -> Vec<mix::Node> {
+        let mut nodes = nodes();
+        nodes[0].latency = Some(Duration::from_millis(10));
+        nodes[1].latency = Some(Duration::from_millis(20));
+        nodes[2].latency = Some(Duration::from_millis(40));
+        nodes
+    }
+
+    #[test]
+    fn latency_weighted_selection() {
+        let strategy = RouteSelectionStrategy::LatencyWeighted {
+            anonymity_budget: 0.5,
+        };
+        let observed = selection_frequencies(&strategy, &nodes_with_latencies());
+        // inverse latencies: 100, 50, 25 and the median (50) for the unmeasured node => total 225
+        // each node gets 0.5 / 4 uniformly and the rest proportionally to its inverse latency
+        assert_distribution(
+            &observed,
+            &[
+                (1, 0.125 + 0.5 * 100. / 225.),
+                (2, 0.125 + 0.5 * 50. / 225.),
+                (3, 0.125 + 0.5 * 25. / 225.),
+                (4, 0.125 + 0.5 * 50. / 225.),
+            ],
+        );
+    }
+
+    #[test]
+    fn latency_weighted_selection_without_budget_is_uniform() {
+        let strategy = RouteSelectionStrategy::LatencyWeighted {
+            anonymity_budget: 0.,
+        };
+        let observed = selection_frequencies(&strategy, &nodes_with_latencies());
+        assert_distribution(&observed, &[(1, 0.25), (2, 0.25), (3, 0.25), (4, 0.25)]);
+
+        let strategy = RouteSelectionStrategy::LatencyWeighted {
+            anonymity_budget: f64::NAN,
+        };
+        let observed = selection_frequencies(&strategy, &nodes_with_latencies());
+        assert_distribution(&observed, &[(1, 0.25), (2, 0.25), (3, 0.25), (4, 0.25)]);
+    }
+
+    #[test]
+    fn latency_weighted_selection_falls_back_to_uniform_without_measurements() {
+        let strategy = RouteSelectionStrategy::LatencyWeighted {
+            anonymity_budget: 1.,
+        };
+        let observed = selection_frequencies(&strategy, &nodes());
+        assert_distribution(&observed, &[(1, 0.25), (2, 0.25), (3, 0.25), (4, 0.25)]);
+    }
+
+    #[test]
+    fn empty_layer() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let nodes: Vec<mix::Node> = Vec::new();
+        assert!(RouteSelectionStrategy::StakeWeighted
+            .choose(&mut rng, &nodes)
+            .is_none());
+    }
+
+    #[test]
+    fn route_constraints() {
+        let family = mix::FamilyHead::new("3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7");
+        let mut first = node(1, 100, 0);
+        first.family = Some(family.clone());
+        first.mix_host = "1.2.3.4:1789".parse().unwrap();
+
+        let mut same_family = node(2, 100, 0);
+        same_family.family = Some(family);
+        same_family.mix_host = "5.6.7.8:1789".parse().unwrap();
+
+        let mut same_subnet = node(3, 100, 0);
+        same_subnet.mix_host = "1.2.30.40:1789".parse().unwrap();
+
+        let mut unrelated = node(4, 100, 0);
+        unrelated.mix_host = "9.9.9.9:1789".parse().unwrap();
+
+        let lenient = RouteConstraints::default();
+        assert!(!lenient.permits(&[&first], &same_family));
+        assert!(lenient.permits(&[&first], &same_subnet));
+        assert!(lenient.permits(&[&first], &unrelated));
+
+        let strict = RouteConstraints::new(true);
+        assert!(!strict.permits(&[&first], &same_family));
+        assert!(!strict.permits(&[&first], &same_subnet));
+        assert!(strict.permits(&[&first, &same_subnet], &unrelated));
+    }
+}
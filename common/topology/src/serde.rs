@@ -42,6 +42,9 @@ pub enum SerializableTopologyError {
 
     #[error("The provided gateway list was malformed: {msg}")]
     MalformedGatewayList { msg: String },
+
+    #[error("'{value}' is not a valid route selection weight")]
+    MalformedSelectionWeight { value: String },
 }
 
 #[cfg(feature = "wasm-serde-types")]
@@ -127,6 +130,21 @@ pub struct SerializableMixNode {
     // this is a `MixLayer` but due to typescript issue, we're using u8 directly.
     pub layer: u8,
 
+    // decimal value within 0 - 1 range
+    #[cfg_attr(feature = "wasm-serde-types", tsify(optional))]
+    #[serde(default)]
+    pub performance: Option<String>,
+
+    #[cfg_attr(feature = "wasm-serde-types", tsify(optional))]
+    #[serde(default)]
+    #[serde(alias = "stake_saturation")]
+    pub stake_saturation: Option<String>,
+
+    // identity key of the family head
+    #[cfg_attr(feature = "wasm-serde-types", tsify(optional))]
+    #[serde(default)]
+    pub family: Option<String>,
+
     #[cfg_attr(feature = "wasm-serde-types", tsify(optional))]
     pub version: Option<String>,
 }
@@ -140,6 +158,19 @@ impl TryFrom<SerializableMixNode> for mix::Node {
         let mix_port = value.mix_port.unwrap_or(DEFAULT_MIX_LISTENING_PORT);
         let version = value.version.map(|v| v.as_str().into()).unwrap_or_default();
 
+        let performance = match value.performance {
+            Some(raw) => raw
+                .parse()
+                .map_err(|_| SerializableTopologyError::MalformedSelectionWeight { value: raw })?,
+            None => mix::Performance::hundred(),
+        };
+        let stake_saturation = match value.stake_saturation {
+            Some(raw) => raw
+                .parse()
+                .map_err(|_| SerializableTopologyError::MalformedSelectionWeight { value: raw })?,
+            None => mix::Decimal::zero(),
+        };
+
         // try to completely resolve the host in the mix situation to avoid doing it every
         // single time we want to construct a path
         let mix_host = mix::Node::extract_mix_host(&host, mix_port)?;
@@ -155,6 +186,10 @@ impl TryFrom<SerializableMixNode> for mix::Node {
                 .map_err(MixnodeConversionError::from)?,
             layer: mix::Layer::try_from(value.layer)
                 .map_err(|_| SerializableTopologyError::InvalidMixLayer { value: value.layer })?,
+            performance,
+            stake_saturation,
+            family: value.family.map(mix::FamilyHead::new),
+            latency: None,
             version,
         })
     }
@@ -170,6 +205,9 @@ impl<'a> From<&'a mix::Node> for SerializableMixNode {
             identity_key: value.identity_key.to_base58_string(),
             sphinx_key: value.sphinx_key.to_base58_string(),
             layer: value.layer.into(),
+            performance: Some(value.performance.value().to_string()),
+            stake_saturation: Some(value.stake_saturation.to_string()),
+            family: value.family.as_ref().map(ToString::to_string),
             version: Some(value.version.to_string()),
         }
     }
@@ -241,6 +279,7 @@ impl TryFrom<SerializableGateway> for gateway::Node {
             mix_host,
             clients_ws_port,
             clients_wss_port: value.clients_wss_port,
+            latency: None,
             identity_key: identity::PublicKey::from_base58_string(&value.identity_key)
                 .map_err(GatewayConversionError::from)?,
             sphinx_key: encryption::PublicKey::from_base58_string(&value.sphinx_key)
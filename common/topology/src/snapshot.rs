@@ -0,0 +1,380 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NymTopology, SerializableNymTopology, SerializableTopologyError};
+use log::error;
+use nym_crypto::asymmetric::identity;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+use time::OffsetDateTime;
+
+#[cfg(feature = "provider-trait")]
+use crate::provider_trait::{async_trait, TopologyProvider};
+
+/// Maximum difference between our clock and the clock of the snapshot signer
+/// before the snapshot is considered to have been created in the future.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Error)]
+pub enum TopologySnapshotError {
+    #[error("failed to access the topology snapshot at '{}': {source}", path.display())]
+    IoError {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("the topology snapshot is malformed: {source}")]
+    MalformedSnapshot {
+        #[from]
+        source: serde_json::Error,
+    },
+
+    #[error("the topology snapshot contains invalid topology: {source}")]
+    InvalidTopology {
+        #[from]
+        source: SerializableTopologyError,
+    },
+
+    #[error("the topology snapshot has been signed by an untrusted key {signer}")]
+    UntrustedSigner { signer: String },
+
+    #[error("the signature on the topology snapshot is malformed: {source}")]
+    MalformedSignature {
+        #[source]
+        source: identity::Ed25519RecoveryError,
+    },
+
+    #[error("the signature on the topology snapshot is invalid")]
+    InvalidSignature,
+
+    #[error("the topology snapshot has been created at {created_at} which is more than {}s ago", max_age.as_secs())]
+    StaleSnapshot {
+        created_at: OffsetDateTime,
+        max_age: Duration,
+    },
+
+    #[error("the topology snapshot claims to have been created in the future ({created_at})")]
+    CreatedInTheFuture { created_at: OffsetDateTime },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct TopologySnapshotMetadata {
+    /// Absolute id of the epoch during which the snapshot has been captured.
+    pub epoch_id: u32,
+
+    /// The time at which the epoch of the snapshot was scheduled to finish.
+    #[serde(with = "time::serde::rfc3339")]
+    pub epoch_end: OffsetDateTime,
+
+    /// The time at which the snapshot has been captured.
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct TopologySnapshot {
+    pub metadata: TopologySnapshotMetadata,
+    pub topology: SerializableNymTopology,
+}
+
+impl TopologySnapshot {
+    pub fn new(topology: NymTopology, epoch_id: u32, epoch_end: OffsetDateTime) -> Self {
+        TopologySnapshot {
+            metadata: TopologySnapshotMetadata {
+                epoch_id,
+                epoch_end,
+                created_at: OffsetDateTime::now_utc(),
+            },
+            topology: topology.into(),
+        }
+    }
+
+    pub fn sign(self, private_key: &identity::PrivateKey) -> SignedTopologySnapshot {
+        SignedTopologySnapshot::new(self, private_key)
+    }
+
+    fn serialize(&self) -> String {
+        // safety: the snapshot only consists of plain data types that always serialize successfully
+        #[allow(clippy::expect_used)]
+        serde_json::to_string(self).expect("topology snapshot serialization failure")
+    }
+
+    /// Checks whether the snapshot is not older than the provided maximum age.
+    pub fn ensure_fresh(&self, max_age: Duration) -> Result<(), TopologySnapshotError> {
+        let created_at = self.metadata.created_at;
+        let now = OffsetDateTime::now_utc();
+
+        if created_at > now + MAX_CLOCK_SKEW {
+            return Err(TopologySnapshotError::CreatedInTheFuture { created_at });
+        }
+        if now - created_at > max_age {
+            return Err(TopologySnapshotError::StaleSnapshot {
+                created_at,
+                max_age,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Topology snapshot alongside the ed25519 signature of its signer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct SignedTopologySnapshot {
+    /// The json-serialized [`TopologySnapshot`], exactly as it has been signed.
+    /// It's kept as-is, as re-serializing the deserialized snapshot is not guaranteed
+    /// to produce identical bytes.
+    pub payload: String,
+
+    /// Base58-encoded ed25519 public key of the signer.
+    pub signer: String,
+
+    /// Base58-encoded ed25519 signature on the payload.
+    pub signature: String,
+}
+
+impl SignedTopologySnapshot {
+    pub fn new(snapshot: TopologySnapshot, private_key: &identity::PrivateKey) -> Self {
+        let payload = snapshot.serialize();
+        let signature = private_key.sign(payload.as_bytes()).to_base58_string();
+        let signer = identity::PublicKey::from(private_key).to_base58_string();
+
+        SignedTopologySnapshot {
+            payload,
+            signer,
+            signature,
+        }
+    }
+
+    /// Deserializes the signed snapshot. Note that this does not verify the signature.
+    pub fn snapshot(&self) -> Result<TopologySnapshot, TopologySnapshotError> {
+        Ok(serde_json::from_str(&self.payload)?)
+    }
+
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, TopologySnapshotError> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|source| TopologySnapshotError::IoError {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), TopologySnapshotError> {
+        let path = path.as_ref();
+        let file =
+            std::fs::File::create(path).map_err(|source| TopologySnapshotError::IoError {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Ok(serde_json::to_writer_pretty(file, self)?)
+    }
+
+    /// Verifies whether the snapshot has been signed by the provided key.
+    pub fn verify(&self, trusted_key: &identity::PublicKey) -> Result<(), TopologySnapshotError> {
+        if self.signer != trusted_key.to_base58_string() {
+            return Err(TopologySnapshotError::UntrustedSigner {
+                signer: self.signer.clone(),
+            });
+        }
+
+        let signature = identity::Signature::from_base58_string(&self.signature)
+            .map_err(|source| TopologySnapshotError::MalformedSignature { source })?;
+
+        trusted_key
+            .verify(self.payload.as_bytes(), &signature)
+            .map_err(|_| TopologySnapshotError::InvalidSignature)
+    }
+
+    /// Verifies the signature and the freshness of the snapshot and returns it.
+    pub fn into_verified_snapshot(
+        self,
+        trusted_key: &identity::PublicKey,
+        max_age: Duration,
+    ) -> Result<TopologySnapshot, TopologySnapshotError> {
+        self.verify(trusted_key)?;
+        let snapshot = self.snapshot()?;
+        snapshot.ensure_fresh(max_age)?;
+        Ok(snapshot)
+    }
+
+    /// Verifies the signature and the freshness of the snapshot and returns the underlying topology.
+    pub fn into_verified_topology(
+        self,
+        trusted_key: &identity::PublicKey,
+        max_age: Duration,
+    ) -> Result<NymTopology, TopologySnapshotError> {
+        Ok(self
+            .into_verified_snapshot(trusted_key, max_age)?
+            .topology
+            .try_into()?)
+    }
+}
+
+/// Topology provider serving the topology from a signed snapshot.
+/// The signature is verified when the snapshot is loaded, while the freshness is checked
+/// every time the topology is requested.
+pub struct SignedSnapshotTopologyProvider {
+    metadata: TopologySnapshotMetadata,
+    topology: NymTopology,
+    max_age: Duration,
+}
+
+impl SignedSnapshotTopologyProvider {
+    pub fn new_from_file<P: AsRef<Path>>(
+        path: P,
+        trusted_key: &identity::PublicKey,
+        max_age: Duration,
+    ) -> Result<Self, TopologySnapshotError> {
+        let snapshot = SignedTopologySnapshot::load_from_file(path)?
+            .into_verified_snapshot(trusted_key, max_age)?;
+
+        Ok(SignedSnapshotTopologyProvider {
+            metadata: snapshot.metadata,
+            topology: snapshot.topology.try_into()?,
+            max_age,
+        })
+    }
+
+    pub fn metadata(&self) -> &TopologySnapshotMetadata {
+        &self.metadata
+    }
+
+    fn current_topology(&self) -> Option<NymTopology> {
+        let now = OffsetDateTime::now_utc();
+        if now - self.metadata.created_at > self.max_age {
+            error!(
+                "{}",
+                TopologySnapshotError::StaleSnapshot {
+                    created_at: self.metadata.created_at,
+                    max_age: self.max_age,
+                }
+            );
+            return None;
+        }
+        Some(self.topology.clone())
+    }
+}
+
+#[cfg(all(feature = "provider-trait", not(target_arch = "wasm32")))]
+#[async_trait]
+impl TopologyProvider for SignedSnapshotTopologyProvider {
+    async fn get_new_topology(&mut self) -> Option<NymTopology> {
+        self.current_topology()
+    }
+}
+
+#[cfg(all(feature = "provider-trait", target_arch = "wasm32"))]
+#[async_trait(?Send)]
+impl TopologyProvider for SignedSnapshotTopologyProvider {
+    async fn get_new_topology(&mut self) -> Option<NymTopology> {
+        self.current_topology()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use std::collections::BTreeMap;
+
+    fn test_snapshot() -> TopologySnapshot {
+        TopologySnapshot::new(
+            NymTopology::new(BTreeMap::new(), vec![]),
+            42,
+            OffsetDateTime::now_utc() + Duration::from_secs(3600),
+        )
+    }
+
+    #[test]
+    fn signed_snapshot_roundtrip() {
+        let keys = identity::KeyPair::new(&mut thread_rng());
+        let signed = test_snapshot().sign(keys.private_key());
+
+        let serialised = serde_json::to_string(&signed).unwrap();
+        let deserialised: SignedTopologySnapshot = serde_json::from_str(&serialised).unwrap();
+
+        assert!(deserialised.verify(keys.public_key()).is_ok());
+        assert!(deserialised
+            .into_verified_topology(keys.public_key(), Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_untrusted_or_tampered_snapshots() {
+        let keys = identity::KeyPair::new(&mut thread_rng());
+        let other_keys = identity::KeyPair::new(&mut thread_rng());
+        let signed = test_snapshot().sign(keys.private_key());
+
+        assert!(matches!(
+            signed.verify(other_keys.public_key()),
+            Err(TopologySnapshotError::UntrustedSigner { .. })
+        ));
+
+        let mut tampered_snapshot = signed.snapshot().unwrap();
+        tampered_snapshot.metadata.epoch_id += 1;
+        let mut tampered = signed.clone();
+        tampered.payload = serde_json::to_string(&tampered_snapshot).unwrap();
+        assert!(matches!(
+            tampered.verify(keys.public_key()),
+            Err(TopologySnapshotError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn rejects_stale_snapshots() {
+        let keys = identity::KeyPair::new(&mut thread_rng());
+        let mut snapshot = test_snapshot();
+        snapshot.metadata.created_at -= Duration::from_secs(120);
+        let signed = snapshot.sign(keys.private_key());
+
+        assert!(signed.verify(keys.public_key()).is_ok());
+        assert!(matches!(
+            signed.into_verified_topology(keys.public_key(), Duration::from_secs(60)),
+            Err(TopologySnapshotError::StaleSnapshot { .. })
+        ));
+    }
+
+    #[test]
+    fn verifies_the_signed_bytes_rather_than_the_reserialized_snapshot() {
+        let keys = identity::KeyPair::new(&mut thread_rng());
+        let mut snapshot = test_snapshot();
+        snapshot.metadata.created_at -= Duration::from_secs(1);
+
+        // sign a payload that is not in the canonical form our serializer would have produced
+        let payload = serde_json::to_string_pretty(&snapshot).unwrap();
+        let signed = SignedTopologySnapshot {
+            signature: keys
+                .private_key()
+                .sign(payload.as_bytes())
+                .to_base58_string(),
+            signer: keys.public_key().to_base58_string(),
+            payload,
+        };
+
+        assert!(signed
+            .into_verified_topology(keys.public_key(), Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn tolerates_small_clock_skew() {
+        let mut snapshot = test_snapshot();
+        snapshot.metadata.created_at += Duration::from_secs(30);
+        assert!(snapshot.ensure_fresh(Duration::from_secs(60)).is_ok());
+
+        snapshot.metadata.created_at += MAX_CLOCK_SKEW;
+        assert!(matches!(
+            snapshot.ensure_fresh(Duration::from_secs(60)),
+            Err(TopologySnapshotError::CreatedInTheFuture { .. })
+        ));
+    }
+}
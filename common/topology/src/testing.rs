@@ -0,0 +1,31 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fixtures shared by the tests of this crate and of the crates depending on it.
+
+use crate::mix;
+use nym_crypto::asymmetric::{encryption, identity};
+use nym_mixnet_contract_common::MixId;
+
+pub const IDENTITY_KEY: &str = "3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7";
+pub const SPHINX_KEY: &str = "C7cown6dYCLZpLiMFC1PaBmhvLvmJmLDJGeRTbPD45bX";
+
+/// Creates a layer one mixnode with the specified id, listening on `3.3.3.3:1789`,
+/// with perfect performance and no stake.
+/// Any other values can be set using the struct update syntax.
+pub fn mixnode(mix_id: MixId) -> mix::Node {
+    mix::Node {
+        mix_id,
+        owner: None,
+        host: "3.3.3.3".parse().unwrap(),
+        mix_host: "3.3.3.3:1789".parse().unwrap(),
+        identity_key: identity::PublicKey::from_base58_string(IDENTITY_KEY).unwrap(),
+        sphinx_key: encryption::PublicKey::from_base58_string(SPHINX_KEY).unwrap(),
+        layer: mix::Layer::One,
+        performance: mix::Performance::hundred(),
+        stake_saturation: mix::Decimal::zero(),
+        family: None,
+        latency: None,
+        version: "1.1.0".into(),
+    }
+}
@@ -120,9 +120,13 @@ impl From<DebugWasm> for ConfigDebug {
             traffic: debug.traffic.into(),
             cover_traffic: debug.cover_traffic.into(),
             gateway_connection: debug.gateway_connection.into(),
+            gateway_failover: Default::default(),
             acknowledgements: debug.acknowledgements.into(),
             topology: debug.topology.into(),
             reply_surbs: debug.reply_surbs.into(),
+            message_reconstruction: Default::default(),
+            erasure_coding: Default::default(),
+            metrics: Default::default(),
         }
     }
 }
@@ -195,6 +199,7 @@ impl From<TrafficWasm> for ConfigTraffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: use_extended_packet_size,
             packet_type,
+            ..Default::default()
         }
     }
 }
@@ -392,6 +397,9 @@ impl From<TopologyWasm> for ConfigTopology {
             topology_structure: Default::default(),
             minimum_mixnode_performance: topology.minimum_mixnode_performance,
             minimum_gateway_performance: topology.minimum_gateway_performance,
+            route_selection: Default::default(),
+            distinct_route_subnets: false,
+            subscribe_to_network_events: false,
         }
     }
 }
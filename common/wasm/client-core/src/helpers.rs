@@ -101,7 +101,7 @@ pub async fn setup_gateway_wasm(
         GatewaySetup::MustLoad { gateway_id: None }
     } else {
         let selection_spec =
-            GatewaySelectionSpecification::new(chosen_gateway.clone(), None, force_tls);
+            GatewaySelectionSpecification::new(chosen_gateway.clone(), None, None, force_tls);
 
         GatewaySetup::New {
             specification: selection_spec,
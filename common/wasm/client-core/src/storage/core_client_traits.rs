@@ -43,8 +43,18 @@ impl MixnetClientStorage for FullWasmClientStorage {
 
     type GatewaysDetailsStore = ClientStorage;
 
-    fn into_runtime_stores(self) -> (Self::ReplyStore, Self::CredentialStore) {
-        (self.reply_storage, self.credential_storage)
+    fn into_runtime_stores(
+        self,
+    ) -> (
+        Self::ReplyStore,
+        Self::CredentialStore,
+        Self::GatewaysDetailsStore,
+    ) {
+        (
+            self.reply_storage,
+            self.credential_storage,
+            self.keys_and_gateway_store,
+        )
     }
 
     fn key_store(&self) -> &Self::KeyStore {
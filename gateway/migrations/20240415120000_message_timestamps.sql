@@ -0,0 +1,12 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+ALTER TABLE message_store
+ADD COLUMN timestamp TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT '1970-01-01 00:00:00';
+
+-- we don't know when the already stored messages have been received, so start their lifetime now
+UPDATE message_store SET timestamp = CURRENT_TIMESTAMP;
+
+CREATE INDEX `message_store_timestamp_index` ON `message_store` (`timestamp`);
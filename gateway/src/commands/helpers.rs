@@ -20,8 +20,8 @@ use nym_network_defaults::var_names::NYXD;
 use nym_network_defaults::var_names::{BECH32_PREFIX, NYM_API, STATISTICS_SERVICE_DOMAIN_ADDRESS};
 
 use nym_network_requester::{
-    generate_new_client_keys, set_active_gateway, setup_fs_gateways_storage, setup_gateway,
-    GatewaySetup, OnDiskKeys,
+    generate_new_client_keys, keys_passphrase_from_env, set_active_gateway,
+    setup_fs_gateways_storage, setup_gateway, GatewaySetup, OnDiskKeys, KEYS_PASSPHRASE_ENV_VAR,
 };
 use nym_types::gateway::{GatewayIpPacketRouterDetails, GatewayNetworkRequesterDetails};
 use nym_validator_client::nyxd::AccountId;
@@ -168,7 +168,12 @@ pub(crate) async fn initialise_local_network_requester(
     let mut nr_cfg = nym_network_requester::Config::new(&nr_id).with_data_directory(nr_data_dir);
     nr_cfg = override_network_requester_config(nr_cfg, Some(opts));
 
-    let key_store = OnDiskKeys::new(nr_cfg.storage_paths.common_paths.keys.clone());
+    // if the keys are encrypted at rest, their passphrase must be provided via the default env variable
+    let keys_passphrase = keys_passphrase_from_env(KEYS_PASSPHRASE_ENV_VAR);
+    let key_store = OnDiskKeys::new_with_optional_passphrase(
+        nr_cfg.storage_paths.common_paths.keys.clone(),
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    );
     let details_store =
         setup_fs_gateways_storage(&nr_cfg.storage_paths.common_paths.gateway_registrations).await?;
 
@@ -230,7 +235,12 @@ pub(crate) async fn initialise_local_ip_packet_router(
     let mut ip_cfg = nym_ip_packet_router::Config::new(&ip_id).with_data_directory(ip_data_dir);
     ip_cfg = override_ip_packet_router_config(ip_cfg, Some(opts));
 
-    let key_store = OnDiskKeys::new(ip_cfg.storage_paths.common_paths.keys.clone());
+    // if the keys are encrypted at rest, their passphrase must be provided via the default env variable
+    let keys_passphrase = keys_passphrase_from_env(KEYS_PASSPHRASE_ENV_VAR);
+    let key_store = OnDiskKeys::new_with_optional_passphrase(
+        ip_cfg.storage_paths.common_paths.keys.clone(),
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    );
     let details_store =
         setup_fs_gateways_storage(&ip_cfg.storage_paths.common_paths.gateway_registrations).await?;
 
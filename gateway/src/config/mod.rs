@@ -21,6 +21,8 @@ use url::Url;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 pub use crate::config::persistence::paths::GatewayPaths;
+pub use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
+pub use nym_mixnode_common::verloc::Config as VerlocConfig;
 
 pub mod old_config_v1_1_20;
 pub mod old_config_v1_1_28;
@@ -43,6 +45,11 @@ const DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE: usize = 2000;
 const DEFAULT_STORED_MESSAGE_FILENAME_LENGTH: u16 = 16;
 const DEFAULT_MESSAGE_RETRIEVAL_LIMIT: i64 = 100;
 
+const DEFAULT_MAX_CLIENT_INBOX_MESSAGES: u32 = 20_000;
+const DEFAULT_MAX_CLIENT_INBOX_BYTES: u64 = 64 * 1024 * 1024; // 64MiB
+const DEFAULT_MAX_INBOX_MESSAGE_AGE: Duration = Duration::from_secs(48 * 60 * 60);
+const DEFAULT_INBOX_PRUNING_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
 const DEFAULT_CLIENT_BANDWIDTH_MAX_FLUSHING_RATE: Duration = Duration::from_millis(5);
 const DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT: i64 = 512 * 1024; // 512kB
 
@@ -93,6 +100,9 @@ pub struct Config {
     #[serde(default)]
     pub ip_packet_router: IpPacketRouter,
 
+    #[serde(default)]
+    pub inbox: Inbox,
+
     #[serde(default)]
     pub logging: LoggingSettings,
 
@@ -121,6 +131,7 @@ impl Config {
             storage_paths: GatewayPaths::new_default(id.as_ref()),
             network_requester: Default::default(),
             ip_packet_router: Default::default(),
+            inbox: Default::default(),
             logging: Default::default(),
             debug: Default::default(),
         }
@@ -134,6 +145,7 @@ impl Config {
         storage_paths: impl Into<GatewayPaths>,
         network_requester: impl Into<NetworkRequester>,
         ip_packet_router: impl Into<IpPacketRouter>,
+        inbox: impl Into<Inbox>,
         logging: impl Into<LoggingSettings>,
         debug: impl Into<Debug>,
     ) -> Self {
@@ -145,6 +157,7 @@ impl Config {
             storage_paths: storage_paths.into(),
             network_requester: network_requester.into(),
             ip_packet_router: ip_packet_router.into(),
+            inbox: inbox.into(),
             logging: logging.into(),
             debug: debug.into(),
         }
@@ -445,6 +458,40 @@ impl Default for IpPacketRouter {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct Inbox {
+    /// Maximum number of messages stored for a single offline client.
+    /// Once it's reached, the oldest messages get evicted to make room for the new ones.
+    /// Setting it to 0 disables the limit.
+    pub max_client_messages: u32,
+
+    /// Maximum total size (in bytes) of messages stored for a single offline client.
+    /// Once it's reached, the oldest messages get evicted to make room for the new ones.
+    /// Setting it to 0 disables the limit.
+    pub max_client_bytes: u64,
+
+    /// Maximum age of a stored message after which it gets removed from the storage.
+    /// Setting it to 0 disables the expiration.
+    #[serde(with = "humantime_serde")]
+    pub max_message_age: Duration,
+
+    /// Delay between subsequent runs of the task removing expired messages.
+    #[serde(with = "humantime_serde")]
+    pub pruning_interval: Duration,
+}
+
+impl Default for Inbox {
+    fn default() -> Self {
+        Inbox {
+            max_client_messages: DEFAULT_MAX_CLIENT_INBOX_MESSAGES,
+            max_client_bytes: DEFAULT_MAX_CLIENT_INBOX_BYTES,
+            max_message_age: DEFAULT_MAX_INBOX_MESSAGE_AGE,
+            pruning_interval: DEFAULT_INBOX_PRUNING_INTERVAL,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct Debug {
@@ -489,6 +536,9 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
 }
 
 impl Default for Debug {
@@ -505,6 +555,7 @@ impl Default for Debug {
             client_bandwidth_max_delta_flushing_amount:
                 DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT,
             use_legacy_framed_packet_version: false,
+            replay_protection: Default::default(),
         }
     }
 }
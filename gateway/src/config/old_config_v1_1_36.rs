@@ -146,6 +146,7 @@ impl From<ConfigV1_1_36> for Config {
             },
             // \/ ADDED
             ip_packet_router: Default::default(),
+            inbox: Default::default(),
             // /\ ADDED
             logging: LoggingSettings {
                 // no fields (yet)
@@ -86,6 +86,24 @@ enabled = {{ network_requester.enabled }}
 # Specifies whether ip packet router service is enabled in this process.
 enabled = {{ ip_packet_router.enabled }}
 
+[inbox]
+# Maximum number of messages stored for a single offline client.
+# Once it's reached, the oldest messages get evicted to make room for the new ones.
+# (0 - unlimited)
+max_client_messages = {{ inbox.max_client_messages }}
+
+# Maximum total size (in bytes) of messages stored for a single offline client.
+# Once it's reached, the oldest messages get evicted to make room for the new ones.
+# (0 - unlimited)
+max_client_bytes = {{ inbox.max_client_bytes }}
+
+# Maximum age of a stored message after which it gets removed from the storage.
+# (0s - messages never expire)
+max_message_age = '{{ inbox.max_message_age }}'
+
+# Delay between subsequent runs of the task removing expired messages.
+pruning_interval = '{{ inbox.pruning_interval }}'
+
 [storage_paths] 
 
 # Path to file containing private identity key.
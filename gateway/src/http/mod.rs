@@ -177,16 +177,16 @@ impl<'a> HttpApiBuilder<'a> {
             return self;
         };
 
-        // we can cheat here a bit since we're not refreshing the exit policy
+        // we can cheat here a bit since the exit policy is only ever refreshed on an explicit
+        // request from the node operator
         // thus:
         // - we can ignore the Arc pointer and clone the inner value
         // - we can set the last refresh time to the current time
         //
-        // once we start refreshing it, we'll have to change it, but at that point
-        // the allow list will be probably be completely removed and thus the pointer management
-        // will be much easier
+        // if we start refreshing it periodically, we'll have to change it
 
-        let upstream = request_filter.current_exit_policy_filter().upstream();
+        let exit_policy_filter = request_filter.current_exit_policy_filter();
+        let upstream = exit_policy_filter.upstream();
 
         // if there's no upstream (i.e. open proxy), we couldn't have possibly updated it : )
         let last_updated = if upstream.is_some() {
@@ -203,7 +203,7 @@ impl<'a> HttpApiBuilder<'a> {
             enabled: true,
             upstream_source: upstream.map(|u| u.to_string()).unwrap_or_default(),
             last_updated,
-            policy: Some(request_filter.current_exit_policy_filter().policy().clone()),
+            policy: Some(exit_policy_filter.policy().clone()),
         });
 
         self
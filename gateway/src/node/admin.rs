@@ -0,0 +1,174 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::client_handling::active_clients::ActiveClientsStore;
+use log::{info, warn};
+use nym_node_http_api::api::api_requests::v1::admin::models::{
+    ActiveClient, ActiveClientKind, ActiveClients, DrainState,
+};
+use nym_node_http_api::api::api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy;
+use nym_node_http_api::state::admin::AdminRequestError;
+use nym_sphinx::DestinationAddressBytes;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Handle allowing the node operator to control the running gateway, for example through the admin API.
+#[derive(Clone)]
+pub struct GatewayAdminHandle {
+    active_clients_store: ActiveClientsStore,
+
+    /// Indicates whether the gateway should stop accepting new client connections.
+    draining: Arc<AtomicBool>,
+
+    /// Request filters used by the embedded service providers, if they're running.
+    network_requester_filter: Arc<OnceLock<nym_network_requester::RequestFilter>>,
+    ip_packet_router_filter: Arc<OnceLock<nym_ip_packet_router::request_filter::RequestFilter>>,
+}
+
+impl Default for GatewayAdminHandle {
+    fn default() -> Self {
+        GatewayAdminHandle::new()
+    }
+}
+
+impl GatewayAdminHandle {
+    pub fn new() -> Self {
+        GatewayAdminHandle {
+            active_clients_store: ActiveClientsStore::new(),
+            draining: Arc::new(AtomicBool::new(false)),
+            network_requester_filter: Arc::new(OnceLock::new()),
+            ip_packet_router_filter: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub(crate) fn active_clients_store(&self) -> ActiveClientsStore {
+        self.active_clients_store.clone()
+    }
+
+    pub(crate) fn set_network_requester_filter(
+        &self,
+        request_filter: nym_network_requester::RequestFilter,
+    ) {
+        if self.network_requester_filter.set(request_filter).is_err() {
+            warn!("the network requester request filter has already been set")
+        }
+    }
+
+    pub(crate) fn set_ip_packet_router_filter(
+        &self,
+        request_filter: nym_ip_packet_router::request_filter::RequestFilter,
+    ) {
+        if self.ip_packet_router_filter.set(request_filter).is_err() {
+            warn!("the ip packet router request filter has already been set")
+        }
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    pub fn set_draining(&self, draining: bool) -> DrainState {
+        if self.draining.swap(draining, Ordering::Relaxed) != draining {
+            if draining {
+                info!("the gateway is being drained - no new clients are going to be accepted")
+            } else {
+                info!("the gateway is accepting new clients again")
+            }
+        }
+        self.drain_state()
+    }
+
+    pub fn drain_state(&self) -> DrainState {
+        let active_clients = self
+            .active_clients_store
+            .clients()
+            .into_iter()
+            .filter(|(_, kind)| *kind == ActiveClientKind::Remote)
+            .count();
+
+        DrainState {
+            draining: self.is_draining(),
+            active_clients,
+        }
+    }
+
+    pub fn active_clients(&self) -> ActiveClients {
+        ActiveClients {
+            clients: self
+                .active_clients_store
+                .clients()
+                .into_iter()
+                .map(|(address, kind)| ActiveClient {
+                    address: address.as_base58_string(),
+                    kind,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn disconnect_client(&self, address: &str) -> Result<(), AdminRequestError> {
+        let client = DestinationAddressBytes::try_from_base58_string(address).map_err(|_| {
+            AdminRequestError::MalformedClientAddress {
+                address: address.to_string(),
+            }
+        })?;
+
+        if !self.active_clients_store.force_disconnect(client) {
+            return Err(AdminRequestError::UnknownClient {
+                address: address.to_string(),
+            });
+        }
+        info!("disconnected client {address}");
+        Ok(())
+    }
+
+    /// Reloads the exit policies of all embedded service providers and returns the one
+    /// used by the network requester.
+    pub async fn reload_exit_policy(&self) -> Result<UsedExitPolicy, AdminRequestError> {
+        let nr_filter = self.network_requester_filter.get();
+        let ipr_filter = self.ip_packet_router_filter.get();
+        if nr_filter.is_none() && ipr_filter.is_none() {
+            return Err(AdminRequestError::UnsupportedOperation);
+        }
+
+        if let Some(ipr_filter) = ipr_filter {
+            ipr_filter
+                .reload_exit_policy()
+                .await
+                .map_err(|err| AdminRequestError::Failure {
+                    message: format!("failed to reload the ip packet router exit policy: {err}"),
+                })?;
+        }
+
+        let Some(nr_filter) = nr_filter else {
+            return Ok(UsedExitPolicy::default());
+        };
+        let reloaded =
+            nr_filter
+                .reload_exit_policy()
+                .await
+                .map_err(|err| AdminRequestError::Failure {
+                    message: format!("failed to reload the network requester exit policy: {err}"),
+                })?;
+
+        let last_updated = if reloaded.upstream().is_some() {
+            #[allow(clippy::expect_used)]
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system clock is set to before the unix epoch")
+                .as_secs()
+        } else {
+            0
+        };
+
+        Ok(UsedExitPolicy {
+            enabled: true,
+            upstream_source: reloaded
+                .upstream()
+                .map(|u| u.to_string())
+                .unwrap_or_default(),
+            last_updated,
+            policy: Some(reloaded.policy().clone()),
+        })
+    }
+}
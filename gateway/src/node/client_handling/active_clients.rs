@@ -5,6 +5,7 @@ use super::websocket::message_receiver::{IsActiveRequestSender, MixMessageSender
 use crate::node::client_handling::embedded_clients::LocalEmbeddedClientHandle;
 use dashmap::DashMap;
 use log::warn;
+use nym_node_http_api::api::api_requests::v1::admin::models::ActiveClientKind;
 use nym_sphinx::DestinationAddressBytes;
 use std::sync::Arc;
 
@@ -24,6 +25,13 @@ impl ActiveClient {
         }
     }
 
+    fn kind(&self) -> ActiveClientKind {
+        match self {
+            ActiveClient::Remote(_) => ActiveClientKind::Remote,
+            ActiveClient::Embedded(_) => ActiveClientKind::Embedded,
+        }
+    }
+
     fn get_sender(&self) -> MixMessageSender {
         match self {
             ActiveClient::Remote(remote) => remote.mix_message_sender.clone(),
@@ -128,6 +136,27 @@ impl ActiveClientsStore {
         self.inner.remove(&client);
     }
 
+    /// Forcefully disconnects the remotely connected client by closing its mix message channel,
+    /// which in turn terminates its connection handler. Embedded clients can't be disconnected.
+    /// Returns whether there was an active remote client with the specified address.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: address of the client to disconnect.
+    pub(crate) fn force_disconnect(&self, client: DestinationAddressBytes) -> bool {
+        let Some((_, entry)) = self
+            .inner
+            .remove_if(&client, |_, entry| matches!(entry, ActiveClient::Remote(_)))
+        else {
+            return false;
+        };
+
+        let sender = entry.get_sender_ref();
+        let was_active = !sender.is_closed();
+        sender.close_channel();
+        was_active
+    }
+
     /// Insert new client handle into the store.
     ///
     /// # Arguments
@@ -164,4 +193,13 @@ impl ActiveClientsStore {
     pub(crate) fn size(&self) -> usize {
         self.inner.len()
     }
+
+    /// Get addresses of all clients that are still connected alongside their kind.
+    pub(crate) fn clients(&self) -> Vec<(DestinationAddressBytes, ActiveClientKind)> {
+        self.inner
+            .iter()
+            .filter(|entry| !entry.value().get_sender_ref().is_closed())
+            .map(|entry| (*entry.key(), entry.value().kind()))
+            .collect()
+    }
 }
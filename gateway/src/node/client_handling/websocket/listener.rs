@@ -1,6 +1,7 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::node::admin::GatewayAdminHandle;
 use crate::node::client_handling::active_clients::ActiveClientsStore;
 use crate::node::client_handling::websocket::common_state::CommonHandlerState;
 use crate::node::client_handling::websocket::connection_handler::FreshHandler;
@@ -15,13 +16,21 @@ use tokio::task::JoinHandle;
 pub(crate) struct Listener {
     address: SocketAddr,
     shared_state: CommonHandlerState,
+
+    // used for checking whether the gateway is being drained and thus shouldn't accept new clients
+    admin_handle: GatewayAdminHandle,
 }
 
 impl Listener {
-    pub(crate) fn new(address: SocketAddr, shared_state: CommonHandlerState) -> Self {
+    pub(crate) fn new(
+        address: SocketAddr,
+        shared_state: CommonHandlerState,
+        admin_handle: GatewayAdminHandle,
+    ) -> Self {
         Listener {
             address,
             shared_state,
+            admin_handle,
         }
     }
 
@@ -55,6 +64,10 @@ impl Listener {
                     match connection {
                         Ok((socket, remote_addr)) => {
                             trace!("received a socket connection from {remote_addr}");
+                            if self.admin_handle.is_draining() {
+                                debug!("the gateway is being drained - rejecting the connection from {remote_addr}");
+                                continue;
+                            }
                             // TODO: I think we *REALLY* need a mechanism for having a maximum number of connected
                             // clients or spawned tokio tasks -> perhaps a worker system?
                             let handle = FreshHandler::new(
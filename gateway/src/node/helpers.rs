@@ -70,8 +70,9 @@ pub(crate) async fn initialise_main_storage(
 ) -> Result<PersistentStorage, GatewayError> {
     let path = &config.storage_paths.clients_storage;
     let retrieval_limit = config.debug.message_retrieval_limit;
+    let inbox_quotas = (&config.inbox).into();
 
-    Ok(PersistentStorage::init(path, retrieval_limit).await?)
+    Ok(PersistentStorage::init(path, retrieval_limit, inbox_quotas).await?)
 }
 
 pub fn load_keypair<T: PemStorableKeyPair>(
@@ -11,6 +11,7 @@ use futures::StreamExt;
 use log::*;
 use nym_mixnet_client::forwarder::MixForwardingSender;
 use nym_mixnode_common::packet_processor::processor::ProcessedFinalHop;
+use nym_noise::{upgrade_noise_responder, Connection, NoiseConfig};
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::framing::codec::NymCodec;
 use nym_sphinx::framing::packet::FramedNymPacket;
@@ -40,6 +41,7 @@ pub(crate) struct ConnectionHandler<St: Storage> {
     active_clients_store: ActiveClientsStore,
     storage: St,
     ack_sender: MixForwardingSender,
+    noise_config: Option<NoiseConfig>,
 }
 
 impl<St: Storage + Clone> Clone for ConnectionHandler<St> {
@@ -58,6 +60,7 @@ impl<St: Storage + Clone> Clone for ConnectionHandler<St> {
             active_clients_store: self.active_clients_store.clone(),
             storage: self.storage.clone(),
             ack_sender: self.ack_sender.clone(),
+            noise_config: self.noise_config.clone(),
         }
     }
 }
@@ -68,6 +71,7 @@ impl<St: Storage> ConnectionHandler<St> {
         storage: St,
         ack_sender: MixForwardingSender,
         active_clients_store: ActiveClientsStore,
+        noise_config: Option<NoiseConfig>,
     ) -> Self {
         ConnectionHandler {
             packet_processor,
@@ -75,6 +79,7 @@ impl<St: Storage> ConnectionHandler<St> {
             storage,
             active_clients_store,
             ack_sender,
+            noise_config,
         }
     }
 
@@ -179,11 +184,7 @@ impl<St: Storage> ConnectionHandler<St> {
         &mut self,
         framed_sphinx_packet: FramedNymPacket,
     ) -> Result<(), CriticalPacketProcessingError> {
-        //
-        // TODO: here be replay attack detection - it will require similar key cache to the one in
-        // packet processor for vpn packets,
-        // question: can it also be per connection vs global?
-        //
+        // note: replay detection is performed by the packet processor that's shared between all connections
 
         let processed_final_hop = match self.packet_processor.process_received(framed_sphinx_packet)
         {
@@ -205,6 +206,18 @@ impl<St: Storage> ConnectionHandler<St> {
     ) {
         debug!("Starting connection handler for {:?}", remote);
         shutdown.mark_as_success();
+
+        let conn = match &self.noise_config {
+            Some(noise_config) => match upgrade_noise_responder(conn, noise_config).await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    warn!("{remote} - failed to complete the noise handshake: {err}");
+                    return;
+                }
+            },
+            None => Connection::Tcp(conn),
+        };
+
         let mut framed_conn = Framed::new(conn, NymCodec);
         while !shutdown.is_shutdown() {
             tokio::select! {
@@ -5,6 +5,7 @@ use nym_crypto::asymmetric::encryption;
 use nym_mixnode_common::packet_processor::error::MixProcessingError;
 pub use nym_mixnode_common::packet_processor::processor::MixProcessingResult;
 use nym_mixnode_common::packet_processor::processor::{ProcessedFinalHop, SphinxPacketProcessor};
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 use nym_sphinx::framing::packet::FramedNymPacket;
 use thiserror::Error;
 
@@ -24,9 +25,12 @@ pub struct PacketProcessor {
 }
 
 impl PacketProcessor {
-    pub(crate) fn new(encryption_key: &encryption::PrivateKey) -> Self {
+    pub(crate) fn new(
+        encryption_key: &encryption::PrivateKey,
+        replay_protection: ReplayProtectionConfig,
+    ) -> Self {
         PacketProcessor {
-            inner_processor: SphinxPacketProcessor::new(encryption_key.into()),
+            inner_processor: SphinxPacketProcessor::new(encryption_key.into(), replay_protection),
         }
     }
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use self::helpers::load_ip_packet_router_config;
-use crate::config::Config;
+use crate::config::{Config, VerlocConfig};
 use crate::error::GatewayError;
 use crate::helpers::{
     load_identity_keys, override_ip_packet_router_config, override_network_requester_config,
@@ -16,12 +16,17 @@ use crate::node::client_handling::websocket::connection_handler::coconut::Coconu
 use crate::node::helpers::{initialise_main_storage, load_network_requester_config};
 use crate::node::mixnet_handling::receiver::connection_handler::ConnectionHandler;
 use crate::node::statistics::collector::GatewayStatisticsCollector;
+use crate::node::storage::inbox_pruner::InboxPruner;
 use futures::channel::{mpsc, oneshot};
 use log::*;
 use nym_crypto::asymmetric::{encryption, identity};
 use nym_mixnet_client::forwarder::{MixForwardingSender, PacketForwarder};
+use nym_mixnode_common::verloc::VerlocMeasurer;
 use nym_network_defaults::NymNetworkDetails;
 use nym_network_requester::{LocalGateway, NRServiceProviderBuilder, RequestFilter};
+use nym_node_http_api::state::ip_packet_router::SharedIpPoolStats;
+use nym_node_http_api::state::metrics::SharedVerlocStats;
+use nym_noise::NoiseConfig;
 use nym_statistics_common::collector::StatisticsSender;
 use nym_task::{TaskClient, TaskHandle, TaskManager};
 use nym_types::gateway::GatewayNodeDetailsResponse;
@@ -33,13 +38,15 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+pub(crate) mod admin;
 pub(crate) mod client_handling;
 pub(crate) mod helpers;
 pub(crate) mod mixnet_handling;
 pub(crate) mod statistics;
 pub(crate) mod storage;
 
-pub use storage::{InMemStorage, PersistentStorage, Storage};
+pub use admin::GatewayAdminHandle;
+pub use storage::{InMemStorage, InboxQuotas, PersistentStorage, Storage};
 
 // TODO: should this struct live here?
 struct StartedNetworkRequester {
@@ -93,6 +100,7 @@ pub async fn create_gateway(
     let ip_opts = ip_packet_router_config.map(|config| LocalIpPacketRouterOpts {
         config,
         custom_mixnet_path: custom_mixnet,
+        ip_pool_stats: Default::default(),
     });
 
     Gateway::new(config, nr_opts, ip_opts, storage)
@@ -110,6 +118,8 @@ pub struct LocalIpPacketRouterOpts {
     pub config: nym_ip_packet_router::Config,
 
     pub custom_mixnet_path: Option<PathBuf>,
+
+    pub ip_pool_stats: SharedIpPoolStats,
 }
 
 pub struct Gateway<St = PersistentStorage> {
@@ -132,6 +142,12 @@ pub struct Gateway<St = PersistentStorage> {
 
     run_http_server: bool,
     task_client: Option<TaskClient>,
+    noise_config: Option<NoiseConfig>,
+    admin_handle: GatewayAdminHandle,
+
+    /// If specified, the gateway is going to perform verloc measurements of other nodes in the network.
+    verloc_config: Option<VerlocConfig>,
+    verloc_stats: Option<SharedVerlocStats>,
 }
 
 impl<St> Gateway<St> {
@@ -153,6 +169,10 @@ impl<St> Gateway<St> {
             wireguard_data: None,
             run_http_server: true,
             task_client: None,
+            noise_config: None,
+            admin_handle: GatewayAdminHandle::new(),
+            verloc_config: None,
+            verloc_stats: None,
         })
     }
 
@@ -175,6 +195,10 @@ impl<St> Gateway<St> {
             wireguard_data: None,
             run_http_server: true,
             task_client: None,
+            noise_config: None,
+            admin_handle: GatewayAdminHandle::new(),
+            verloc_config: None,
+            verloc_stats: None,
         }
     }
 
@@ -186,6 +210,22 @@ impl<St> Gateway<St> {
         self.task_client = Some(task_client)
     }
 
+    pub fn set_noise_config(&mut self, noise_config: NoiseConfig) {
+        self.noise_config = Some(noise_config)
+    }
+
+    pub fn set_admin_handle(&mut self, admin_handle: GatewayAdminHandle) {
+        self.admin_handle = admin_handle
+    }
+
+    pub fn set_verloc_config(&mut self, verloc_config: VerlocConfig) {
+        self.verloc_config = Some(verloc_config)
+    }
+
+    pub fn set_verloc_stats(&mut self, verloc_stats: SharedVerlocStats) {
+        self.verloc_stats = Some(verloc_stats)
+    }
+
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     pub fn set_wireguard_data(&mut self, wireguard_data: nym_wireguard::WireguardData) {
         self.wireguard_data = Some(wireguard_data)
@@ -206,14 +246,17 @@ impl<St> Gateway<St> {
     {
         info!("Starting mix socket listener...");
 
-        let packet_processor =
-            mixnet_handling::PacketProcessor::new(self.sphinx_keypair.private_key());
+        let packet_processor = mixnet_handling::PacketProcessor::new(
+            self.sphinx_keypair.private_key(),
+            self.config.debug.replay_protection,
+        );
 
         let connection_handler = ConnectionHandler::new(
             packet_processor,
             self.storage.clone(),
             ack_sender,
             active_clients_store,
+            self.noise_config.clone(),
         );
 
         let listening_address = SocketAddr::new(
@@ -224,6 +267,42 @@ impl<St> Gateway<St> {
         mixnet_handling::Listener::new(listening_address, shutdown).start(connection_handler);
     }
 
+    fn start_verloc_measurements(&mut self, verloc_config: VerlocConfig, shutdown: TaskClient) {
+        info!("Starting the round-trip-time measurer...");
+
+        let verloc_state = self.verloc_stats.take().unwrap_or_default();
+        let mut verloc_measurer =
+            VerlocMeasurer::new(verloc_config, Arc::clone(&self.identity_keypair), shutdown);
+        verloc_measurer.set_shared_state(verloc_state);
+
+        tokio::spawn(async move { verloc_measurer.run().await });
+    }
+
+    fn start_inbox_pruner(&self, shutdown: &TaskHandle)
+    where
+        St: Storage + Clone + 'static,
+    {
+        let inbox_config = &self.config.inbox;
+        if inbox_config.max_message_age.is_zero() {
+            info!("stored client messages never expire");
+            return;
+        }
+        if inbox_config.pruning_interval.is_zero() {
+            warn!(
+                "inbox pruning interval is set to 0 - expired client messages will not be removed"
+            );
+            return;
+        }
+
+        info!("Starting inbox pruner...");
+        InboxPruner::new(
+            self.storage.clone(),
+            inbox_config,
+            shutdown.fork("InboxPruner"),
+        )
+        .start();
+    }
+
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     async fn start_wireguard(
         &mut self,
@@ -264,7 +343,7 @@ impl<St> Gateway<St> {
             bandwidth_cfg: (&self.config).into(),
         };
 
-        websocket::Listener::new(listening_address, shared_state).start(
+        websocket::Listener::new(listening_address, shared_state, self.admin_handle.clone()).start(
             forwarding_channel,
             self.storage.clone(),
             active_clients_store,
@@ -281,6 +360,7 @@ impl<St> Gateway<St> {
             self.config.debug.initial_connection_timeout,
             self.config.debug.maximum_connection_buffer_size,
             self.config.debug.use_legacy_framed_packet_version,
+            self.noise_config.clone(),
             shutdown,
         );
 
@@ -382,7 +462,8 @@ impl<St> Gateway<St> {
                 .with_shutdown(shutdown)
                 .with_custom_gateway_transceiver(Box::new(transceiver))
                 .with_wait_for_gateway(true)
-                .with_on_start(on_start_tx);
+                .with_on_start(on_start_tx)
+                .with_ip_pool_stats(ip_opts.ip_pool_stats.clone());
 
         if let Some(custom_mixnet) = &ip_opts.custom_mixnet_path {
             ip_packet_router = ip_packet_router.with_stored_topology(custom_mixnet)?
@@ -408,6 +489,8 @@ impl<St> Gateway<St> {
 
         MessageRouter::new(ipr_mix_receiver, packet_router).start_with_shutdown(router_shutdown);
         let address = start_data.address;
+        self.admin_handle
+            .set_ip_packet_router_filter(start_data.request_filter);
 
         info!("the local ip packet router is running on {address}");
         Ok(LocalEmbeddedClientHandle::new(address, ipr_mix_sender))
@@ -498,7 +581,13 @@ impl<St> Gateway<St> {
 
         let mix_forwarding_channel = self.start_packet_forwarder(shutdown.fork("PacketForwarder"));
 
-        let active_clients_store = ActiveClientsStore::new();
+        self.start_inbox_pruner(&shutdown);
+
+        if let Some(verloc_config) = self.verloc_config.take() {
+            self.start_verloc_measurements(verloc_config, shutdown.fork("VerlocMeasurer"));
+        }
+
+        let active_clients_store = self.admin_handle.active_clients_store();
         self.start_mix_socket_listener(
             mix_forwarding_channel.clone(),
             active_clients_store.clone(),
@@ -534,6 +623,8 @@ impl<St> Gateway<St> {
                 .await?;
             // insert information about embedded NR to the active clients store
             active_clients_store.insert_embedded(embedded_nr.handle);
+            self.admin_handle
+                .set_network_requester_filter(embedded_nr.used_request_filter.clone());
             Some(embedded_nr.used_request_filter)
         } else {
             info!("embedded network requester is disabled");
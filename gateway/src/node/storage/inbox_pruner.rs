@@ -0,0 +1,68 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config;
+use crate::node::storage::Storage;
+use log::{debug, error, info, trace};
+use nym_metrics::inc_by;
+use nym_task::TaskClient;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Background task periodically removing messages that have been stored for longer than
+/// the configured maximum age.
+pub(crate) struct InboxPruner<St> {
+    storage: St,
+    max_message_age: Duration,
+    pruning_interval: Duration,
+    shutdown: TaskClient,
+}
+
+impl<St> InboxPruner<St>
+where
+    St: Storage + 'static,
+{
+    pub(crate) fn new(storage: St, config: &config::Inbox, shutdown: TaskClient) -> Self {
+        InboxPruner {
+            storage,
+            max_message_age: config.max_message_age,
+            pruning_interval: config.pruning_interval,
+            shutdown,
+        }
+    }
+
+    async fn prune(&self) {
+        let cutoff = OffsetDateTime::now_utc() - self.max_message_age;
+        match self.storage.remove_stale_messages(cutoff).await {
+            Ok(0) => trace!("there were no expired messages to remove"),
+            Ok(removed) => {
+                debug!("removed {removed} messages stored before {cutoff}");
+                inc_by!("inbox_messages_expired", removed);
+            }
+            Err(err) => error!("failed to remove expired messages: {err}"),
+        }
+    }
+
+    pub(crate) async fn run(&mut self) {
+        info!(
+            "messages older than {}s will be periodically removed from the inboxes",
+            self.max_message_age.as_secs()
+        );
+
+        let mut interval = tokio::time::interval(self.pruning_interval);
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("InboxPruner: received shutdown");
+                }
+                _ = interval.tick() => self.prune().await,
+            }
+        }
+        debug!("InboxPruner: exiting");
+    }
+
+    pub(crate) fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
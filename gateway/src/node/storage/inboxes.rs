@@ -1,7 +1,47 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::config;
 use crate::node::storage::models::StoredMessage;
+use log::{debug, warn};
+use nym_metrics::{inc, inc_by};
+use time::OffsetDateTime;
+
+/// Limits on the amount of data stored for a single offline client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboxQuotas {
+    /// Maximum number of messages stored for a single client.
+    pub max_messages: Option<u32>,
+
+    /// Maximum total size (in bytes) of messages stored for a single client.
+    pub max_bytes: Option<u64>,
+}
+
+impl From<&config::Inbox> for InboxQuotas {
+    fn from(value: &config::Inbox) -> Self {
+        InboxQuotas {
+            max_messages: (value.max_client_messages != 0).then_some(value.max_client_messages),
+            max_bytes: (value.max_client_bytes != 0).then_some(value.max_client_bytes),
+        }
+    }
+}
+
+impl InboxQuotas {
+    fn is_unlimited(&self) -> bool {
+        self.max_messages.is_none() && self.max_bytes.is_none()
+    }
+
+    // sqlite integers are signed
+    fn max_messages_value(&self) -> i64 {
+        self.max_messages.map(i64::from).unwrap_or(i64::MAX)
+    }
+
+    fn max_bytes_value(&self) -> i64 {
+        self.max_bytes
+            .map(|bytes| i64::try_from(bytes).unwrap_or(i64::MAX))
+            .unwrap_or(i64::MAX)
+    }
+}
 
 #[derive(Clone)]
 pub(crate) struct InboxManager {
@@ -10,6 +50,9 @@ pub(crate) struct InboxManager {
     /// It is used to prevent out of memory errors in the case of client receiving a lot of data while
     /// offline and then loading it all at once when he comes back online.
     retrieval_limit: i64,
+
+    /// Limits on the amount of data stored for each client.
+    quotas: InboxQuotas,
 }
 
 impl InboxManager {
@@ -18,7 +61,13 @@ impl InboxManager {
     /// # Arguments
     ///
     /// * `connection_pool`: database connection pool to use.
-    pub(crate) fn new(connection_pool: sqlx::SqlitePool, mut retrieval_limit: i64) -> Self {
+    /// * `retrieval_limit`: maximum number of messages that can be obtained from the database per operation.
+    /// * `quotas`: limits on the amount of data stored for each client.
+    pub(crate) fn new(
+        connection_pool: sqlx::SqlitePool,
+        mut retrieval_limit: i64,
+        quotas: InboxQuotas,
+    ) -> Self {
         // TODO: make this into a hard error instead
         if retrieval_limit == 0 {
             retrieval_limit = 100;
@@ -27,10 +76,13 @@ impl InboxManager {
         InboxManager {
             connection_pool,
             retrieval_limit,
+            quotas,
         }
     }
 
     /// Inserts new message to the storage for an offline client for future retrieval.
+    /// If storing the message would exceed the client's quota, the oldest messages of that client
+    /// are evicted. Messages that on their own exceed the byte quota are dropped.
     ///
     /// # Arguments
     ///
@@ -41,16 +93,90 @@ impl InboxManager {
         client_address_bs58: &str,
         content: Vec<u8>,
     ) -> Result<(), sqlx::Error> {
+        if content.len() as u64 > self.quotas.max_bytes.unwrap_or(u64::MAX) {
+            warn!(
+                "dropping message of {} bytes for {client_address_bs58} as it exceeds the inbox quota",
+                content.len()
+            );
+            inc!("inbox_messages_dropped");
+            return Ok(());
+        }
+
+        let timestamp = OffsetDateTime::now_utc();
         sqlx::query!(
-            "INSERT INTO message_store(client_address_bs58, content) VALUES (?, ?)",
+            "INSERT INTO message_store(client_address_bs58, content, timestamp) VALUES (?, ?, ?)",
             client_address_bs58,
             content,
+            timestamp,
         )
         .execute(&self.connection_pool)
         .await?;
+
+        if !self.quotas.is_unlimited() {
+            let evicted = self.evict_over_quota(client_address_bs58).await?;
+            if evicted > 0 {
+                debug!("evicted {evicted} oldest messages of {client_address_bs58} to stay within the inbox quota");
+                inc_by!("inbox_messages_evicted", evicted);
+            }
+        }
         Ok(())
     }
 
+    /// Removes the oldest messages of the particular client so that the remaining ones
+    /// fit within the configured quotas.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_address_bs58`: base58-encoded address of the client
+    ///
+    /// returns the number of removed messages.
+    async fn evict_over_quota(&self, client_address_bs58: &str) -> Result<u64, sqlx::Error> {
+        let max_messages = self.quotas.max_messages_value();
+        let max_bytes = self.quotas.max_bytes_value();
+
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM message_store
+                WHERE id IN (
+                    SELECT id FROM (
+                        SELECT
+                            id,
+                            ROW_NUMBER() OVER newest_first AS position,
+                            SUM(LENGTH(content)) OVER newest_first AS total_size
+                        FROM message_store
+                        WHERE client_address_bs58 = ?
+                        WINDOW newest_first AS (ORDER BY id DESC)
+                    )
+                    WHERE position > ? OR total_size > ?
+                );
+            "#,
+            client_address_bs58,
+            max_messages,
+            max_bytes
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    /// Removes all messages, of all clients, that have been stored before the provided cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: the time before which the messages are considered expired
+    ///
+    /// returns the number of removed messages.
+    pub(crate) async fn remove_stale_messages(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query!("DELETE FROM message_store WHERE timestamp < ?", cutoff)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
+
     /// Retrieves messages stored for the particular client specified by the provided address.
     ///
     /// It also respects the specified retrieval limit. If there are more messages stored than allowed
@@ -134,3 +260,106 @@ impl InboxManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::time::Duration;
+
+    const CLIENT: &str = "client";
+
+    async fn inbox_manager(quotas: InboxQuotas) -> InboxManager {
+        // every in-memory connection would have created a separate database
+        let connection_pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations")
+            .run(&connection_pool)
+            .await
+            .unwrap();
+
+        InboxManager::new(connection_pool, 100, quotas)
+    }
+
+    async fn stored_contents(inbox_manager: &InboxManager) -> Vec<Vec<u8>> {
+        let (messages, _) = inbox_manager.get_messages(CLIENT, None).await.unwrap();
+        messages.into_iter().map(|m| m.content).collect()
+    }
+
+    #[tokio::test]
+    async fn evicts_oldest_messages_over_the_quota() {
+        let inbox_manager = inbox_manager(InboxQuotas {
+            max_messages: Some(3),
+            max_bytes: Some(10),
+        })
+        .await;
+
+        for i in 0..5u8 {
+            inbox_manager.insert_message(CLIENT, vec![i]).await.unwrap();
+        }
+        assert_eq!(
+            stored_contents(&inbox_manager).await,
+            vec![vec![2], vec![3], vec![4]]
+        );
+
+        // 4 + 4 + 4 bytes no longer fit within the 10 bytes quota
+        inbox_manager
+            .insert_message(CLIENT, vec![5; 4])
+            .await
+            .unwrap();
+        inbox_manager
+            .insert_message(CLIENT, vec![6; 4])
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_contents(&inbox_manager).await,
+            vec![vec![4], vec![5; 4], vec![6; 4]]
+        );
+        inbox_manager
+            .insert_message(CLIENT, vec![7; 4])
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_contents(&inbox_manager).await,
+            vec![vec![6; 4], vec![7; 4]]
+        );
+
+        // messages that exceed the quota on their own are dropped without affecting the stored ones
+        inbox_manager
+            .insert_message(CLIENT, vec![8; 11])
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_contents(&inbox_manager).await,
+            vec![vec![6; 4], vec![7; 4]]
+        );
+    }
+
+    #[tokio::test]
+    async fn removes_stale_messages() {
+        let inbox_manager = inbox_manager(InboxQuotas::default()).await;
+        for i in 0..3u8 {
+            inbox_manager.insert_message(CLIENT, vec![i]).await.unwrap();
+        }
+
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(
+            inbox_manager
+                .remove_stale_messages(now - Duration::from_secs(60))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            inbox_manager
+                .remove_stale_messages(now + Duration::from_secs(60))
+                .await
+                .unwrap(),
+            3
+        );
+        assert!(stored_contents(&inbox_manager).await.is_empty());
+    }
+}
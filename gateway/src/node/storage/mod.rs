@@ -17,10 +17,13 @@ use time::OffsetDateTime;
 
 mod bandwidth;
 pub(crate) mod error;
+pub(crate) mod inbox_pruner;
 mod inboxes;
 mod models;
 mod shared_keys;
 
+pub use inboxes::InboxQuotas;
+
 #[async_trait]
 pub trait Storage: Send + Sync {
     /// Inserts provided derived shared keys into the database.
@@ -93,6 +96,15 @@ pub trait Storage: Send + Sync {
     /// * `ids`: ids of the messages to remove
     async fn remove_messages(&self, ids: Vec<i64>) -> Result<(), StorageError>;
 
+    /// Removes all messages that have been stored before the provided cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: the time before which the messages are considered expired
+    ///
+    /// returns the number of removed messages.
+    async fn remove_stale_messages(&self, cutoff: OffsetDateTime) -> Result<u64, StorageError>;
+
     /// Creates a new bandwidth entry for the particular client.
     ///
     /// # Arguments
@@ -186,9 +198,11 @@ impl PersistentStorage {
     ///
     /// * `database_path`: path to the database.
     /// * `message_retrieval_limit`: maximum number of stored client messages that can be retrieved at once.
+    /// * `inbox_quotas`: limits on the amount of messages stored for each offline client.
     pub async fn init<P: AsRef<Path> + Send>(
         database_path: P,
         message_retrieval_limit: i64,
+        inbox_quotas: InboxQuotas,
     ) -> Result<Self, StorageError> {
         debug!(
             "Attempting to connect to database {:?}",
@@ -221,7 +235,11 @@ impl PersistentStorage {
         // the cloning here are cheap as connection pool is stored behind an Arc
         Ok(PersistentStorage {
             shared_key_manager: SharedKeysManager::new(connection_pool.clone()),
-            inbox_manager: InboxManager::new(connection_pool.clone(), message_retrieval_limit),
+            inbox_manager: InboxManager::new(
+                connection_pool.clone(),
+                message_retrieval_limit,
+                inbox_quotas,
+            ),
             bandwidth_manager: BandwidthManager::new(connection_pool),
         })
     }
@@ -296,6 +314,10 @@ impl Storage for PersistentStorage {
         Ok(())
     }
 
+    async fn remove_stale_messages(&self, cutoff: OffsetDateTime) -> Result<u64, StorageError> {
+        Ok(self.inbox_manager.remove_stale_messages(cutoff).await?)
+    }
+
     async fn create_bandwidth_entry(
         &self,
         client_address: DestinationAddressBytes,
@@ -434,6 +456,11 @@ impl Storage for InMemStorage {
         todo!()
     }
 
+    async fn remove_stale_messages(&self, _cutoff: OffsetDateTime) -> Result<u64, StorageError> {
+        // nothing is ever stored, so there's nothing to prune either
+        Ok(0)
+    }
+
     async fn create_bandwidth_entry(
         &self,
         _client_address: DestinationAddressBytes,
@@ -28,6 +28,7 @@ pub mod persistence;
 mod template;
 
 pub use crate::config::persistence::paths::MixNodePaths;
+pub use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 
 const DEFAULT_MIXNODES_DIR: &str = "mixnodes";
 
@@ -391,6 +392,9 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
 }
 
 impl Default for Debug {
@@ -403,6 +407,7 @@ impl Default for Debug {
             initial_connection_timeout: DEFAULT_INITIAL_CONNECTION_TIMEOUT,
             maximum_connection_buffer_size: DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
             use_legacy_framed_packet_version: false,
+            replay_protection: Default::default(),
         }
     }
 }
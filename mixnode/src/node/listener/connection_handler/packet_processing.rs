@@ -6,6 +6,7 @@ use nym_crypto::asymmetric::encryption;
 use nym_mixnode_common::packet_processor::error::MixProcessingError;
 pub use nym_mixnode_common::packet_processor::processor::MixProcessingResult;
 use nym_mixnode_common::packet_processor::processor::SphinxPacketProcessor;
+use nym_mixnode_common::packet_processor::replay_protection::ReplayProtectionConfig;
 use nym_sphinx::framing::packet::FramedNymPacket;
 
 // PacketProcessor contains all data required to correctly unwrap and forward sphinx packets
@@ -21,10 +22,11 @@ pub struct PacketProcessor {
 impl PacketProcessor {
     pub(crate) fn new(
         encryption_key: &encryption::PrivateKey,
+        replay_protection: ReplayProtectionConfig,
         node_stats_update_sender: node_statistics::UpdateSender,
     ) -> Self {
         PacketProcessor {
-            inner_processor: SphinxPacketProcessor::new(encryption_key.into()),
+            inner_processor: SphinxPacketProcessor::new(encryption_key.into(), replay_protection),
             node_stats_update_sender,
         }
     }
@@ -16,6 +16,7 @@ use nym_crypto::asymmetric::{encryption, identity};
 use nym_mixnode_common::verloc;
 use nym_mixnode_common::verloc::VerlocMeasurer;
 use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
+use nym_noise::NoiseConfig;
 use nym_task::{TaskClient, TaskHandle};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -39,6 +40,7 @@ pub struct MixNode {
 
     run_http_server: bool,
     task_client: Option<TaskClient>,
+    noise_config: Option<NoiseConfig>,
     mixing_stats: Option<SharedMixingStats>,
     verloc_stats: Option<SharedVerlocStats>,
 }
@@ -52,6 +54,7 @@ impl MixNode {
             sphinx_keypair: Arc::new(load_sphinx_keys(&config)?),
             config,
             task_client: None,
+            noise_config: None,
             mixing_stats: None,
             verloc_stats: None,
         })
@@ -66,6 +69,7 @@ impl MixNode {
         MixNode {
             run_http_server: true,
             task_client: None,
+            noise_config: None,
             config,
             descriptor,
             identity_keypair,
@@ -83,6 +87,10 @@ impl MixNode {
         self.task_client = Some(task_client)
     }
 
+    pub fn set_noise_config(&mut self, noise_config: NoiseConfig) {
+        self.noise_config = Some(noise_config)
+    }
+
     pub fn set_mixing_stats(&mut self, mixing_stats: SharedMixingStats) {
         self.mixing_stats = Some(mixing_stats);
     }
@@ -151,10 +159,17 @@ impl MixNode {
     ) {
         info!("Starting socket listener...");
 
-        let packet_processor =
-            PacketProcessor::new(self.sphinx_keypair.private_key(), node_stats_update_sender);
+        let packet_processor = PacketProcessor::new(
+            self.sphinx_keypair.private_key(),
+            self.config.debug.replay_protection,
+            node_stats_update_sender,
+        );
 
-        let connection_handler = ConnectionHandler::new(packet_processor, delay_forwarding_channel);
+        let connection_handler = ConnectionHandler::new(
+            packet_processor,
+            delay_forwarding_channel,
+            self.noise_config.clone(),
+        );
 
         let listening_address = SocketAddr::new(
             self.config.mixnode.listening_address,
@@ -171,13 +186,16 @@ impl MixNode {
     ) -> PacketDelayForwardSender {
         info!("Starting packet delay-forwarder...");
 
-        let client_config = nym_mixnet_client::Config::new(
+        let mut client_config = nym_mixnet_client::Config::new(
             self.config.debug.packet_forwarding_initial_backoff,
             self.config.debug.packet_forwarding_maximum_backoff,
             self.config.debug.initial_connection_timeout,
             self.config.debug.maximum_connection_buffer_size,
             self.config.debug.use_legacy_framed_packet_version,
         );
+        if let Some(noise_config) = &self.noise_config {
+            client_config = client_config.with_noise(noise_config.clone());
+        }
 
         let mut packet_forwarder = DelayForwarder::new(
             nym_mixnet_client::Client::new(client_config),
@@ -0,0 +1,89 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::models::OffsetDateTimeJsonSchemaWrapper;
+use nym_mixnet_contract_common::{EpochId, IntervalId, MixId};
+use serde::{Deserialize, Serialize};
+
+/// Change in the state of the network pushed by the nym-api to its subscribers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NetworkEvent {
+    /// The network has moved to a new epoch.
+    EpochTransition {
+        interval_id: IntervalId,
+        epoch_id: EpochId,
+        absolute_epoch_id: EpochId,
+        epoch_start: OffsetDateTimeJsonSchemaWrapper,
+        epoch_end: OffsetDateTimeJsonSchemaWrapper,
+    },
+
+    /// Composition (or the layer assignment) of the rewarded set has changed.
+    RewardedSetChanged {
+        added: Vec<MixId>,
+        removed: Vec<MixId>,
+        layer_changed: Vec<MixId>,
+    },
+
+    /// Nodes got added to or removed from the blacklist.
+    BlacklistChanged {
+        mixnodes_added: Vec<MixId>,
+        mixnodes_removed: Vec<MixId>,
+        gateways_added: Vec<String>,
+        gateways_removed: Vec<String>,
+    },
+
+    /// The subscriber was too slow to keep up and missed some events.
+    /// Any state derived from the events should be re-fetched.
+    Lagged { missed: u64 },
+}
+
+impl NetworkEvent {
+    /// Name of the event as used in the `event` field of the server-sent events.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkEvent::EpochTransition { .. } => "epoch_transition",
+            NetworkEvent::RewardedSetChanged { .. } => "rewarded_set_changed",
+            NetworkEvent::BlacklistChanged { .. } => "blacklist_changed",
+            NetworkEvent::Lagged { .. } => "lagged",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_name_matches_serialised_tag() {
+        let events = [
+            NetworkEvent::EpochTransition {
+                interval_id: 1,
+                epoch_id: 2,
+                absolute_epoch_id: 3,
+                epoch_start: Default::default(),
+                epoch_end: Default::default(),
+            },
+            NetworkEvent::RewardedSetChanged {
+                added: vec![1],
+                removed: vec![],
+                layer_changed: vec![2],
+            },
+            NetworkEvent::BlacklistChanged {
+                mixnodes_added: vec![],
+                mixnodes_removed: vec![1],
+                gateways_added: vec!["foo".to_string()],
+                gateways_removed: vec![],
+            },
+            NetworkEvent::Lagged { missed: 42 },
+        ];
+
+        for event in events {
+            let serialised = serde_json::to_value(&event).unwrap();
+            assert_eq!(serialised["type"], event.name());
+
+            let deserialised: NetworkEvent = serde_json::from_value(serialised).unwrap();
+            assert_eq!(deserialised, event);
+        }
+    }
+}
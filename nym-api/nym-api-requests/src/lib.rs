@@ -5,6 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub mod coconut;
+pub mod events;
 pub mod models;
 pub mod nym_nodes;
 pub mod pagination;
@@ -389,6 +389,11 @@ impl From<nym_node_requests::api::v1::node::models::HostInformation> for HostInf
 pub struct HostKeys {
     pub ed25519: String,
     pub x25519: String,
+
+    /// Base58-encoded x25519 key used for establishing noise connections with this node.
+    /// Empty if the node does not support noise.
+    #[serde(default)]
+    pub x25519_noise: String,
 }
 
 impl From<nym_node_requests::api::v1::node::models::HostKeys> for HostKeys {
@@ -396,6 +401,7 @@ impl From<nym_node_requests::api::v1::node::models::HostKeys> for HostKeys {
         HostKeys {
             ed25519: value.ed25519_identity,
             x25519: value.x25519_sphinx,
+            x25519_noise: value.x25519_noise,
         }
     }
 }
@@ -607,6 +613,66 @@ pub struct NymNodeDescription {
 
     // for now we only care about their ws/wss situation, nothing more
     pub mixnet_websockets: WebSockets,
+
+    /// Results of the most recent complete verloc measurement run performed by this node, if available.
+    #[serde(default)]
+    pub verloc: Option<NodeVerlocResults>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodeVerlocResults {
+    pub nodes_tested: usize,
+
+    pub run_started: OffsetDateTimeJsonSchemaWrapper,
+
+    pub run_finished: Option<OffsetDateTimeJsonSchemaWrapper>,
+
+    /// Round-trip-time measurements of all the nodes that have successfully replied to the echo packets.
+    pub results: Vec<VerlocNodeMeasurement>,
+}
+
+impl From<nym_node_requests::api::v1::metrics::models::VerlocResultData> for NodeVerlocResults {
+    fn from(value: nym_node_requests::api::v1::metrics::models::VerlocResultData) -> Self {
+        NodeVerlocResults {
+            nodes_tested: value.nodes_tested,
+            run_started: value.run_started.into(),
+            run_finished: value.run_finished.map(Into::into),
+            results: value
+                .results
+                .into_iter()
+                .filter_map(|result| {
+                    result
+                        .latest_measurement
+                        .map(|measurement| VerlocNodeMeasurement {
+                            node_identity: result.node_identity.to_base58_string(),
+                            minimum_micros: measurement.minimum.as_micros() as u64,
+                            mean_micros: measurement.mean.as_micros() as u64,
+                            maximum_micros: measurement.maximum.as_micros() as u64,
+                            standard_deviation_micros: measurement.standard_deviation.as_micros()
+                                as u64,
+                        })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct VerlocNodeMeasurement {
+    /// Base58-encoded ed25519 identity of the measured node.
+    pub node_identity: String,
+
+    /// Minimum RTT, in microseconds, it took to receive an echo packet.
+    pub minimum_micros: u64,
+
+    /// Average RTT, in microseconds, it took to receive the echo packets.
+    pub mean_micros: u64,
+
+    /// Maximum RTT, in microseconds, it took to receive an echo packet.
+    pub maximum_micros: u64,
+
+    /// The standard deviation, in microseconds, of the RTT it took to receive the echo packets.
+    pub standard_deviation_micros: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -2,19 +2,54 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::models::{
-    GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription, OffsetDateTimeJsonSchemaWrapper,
+    GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription,
+    OffsetDateTimeJsonSchemaWrapper, StakeSaturation,
 };
+use nym_mixnet_contract_common::families::FamilyHead;
 use nym_mixnet_contract_common::reward_params::Performance;
-use nym_mixnet_contract_common::MixId;
+use nym_mixnet_contract_common::{Decimal, MixId};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CachedNodesResponse<T> {
+    /// Time of the last refresh of the underlying data.
+    /// Note that the `ETag` of the skimmed responses only changes alongside the nodes themselves,
+    /// so this value is not updated by a `304 Not Modified` and can't be used to tell how fresh
+    /// the previously received nodes are.
     pub refreshed_at: OffsetDateTimeJsonSchemaWrapper,
     pub nodes: Vec<T>,
 }
 
+/// Changes to the set of nodes that happened since the specified revision.
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodesDelta<T> {
+    pub refreshed_at: OffsetDateTimeJsonSchemaWrapper,
+
+    /// Opaque revision of the node set after applying this delta.
+    /// It should be provided as the `since` argument in the subsequent request.
+    pub revision: String,
+
+    /// Indicates whether the requested revision was unknown (or not provided),
+    /// in which case `added` contains the full set of nodes and any previously held data should be discarded.
+    pub full: bool,
+
+    /// Nodes that have appeared since the specified revision.
+    pub added: Vec<T>,
+
+    /// Nodes whose information has changed since the specified revision.
+    pub changed: Vec<T>,
+
+    /// Identity keys of nodes that are no longer present.
+    pub removed: Vec<String>,
+}
+
+impl<T> NodesDelta<T> {
+    pub fn is_empty(&self) -> bool {
+        !self.full && self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "request-parsing", derive(rocket::form::FromFormField))]
@@ -28,7 +63,7 @@ pub enum NodeRoleQueryParam {
     ExitGateway,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub enum NodeRole {
     // a properly active mixnode
     Mixnode {
@@ -47,7 +82,7 @@ pub enum NodeRole {
     Inactive,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct BasicEntryInformation {
     pub hostname: Option<String>,
 
@@ -57,8 +92,18 @@ pub struct BasicEntryInformation {
 
 type NodeId = MixId;
 
+/// Round-trip-time of a node aggregated from the verloc measurements performed by other nodes in the network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodeLatency {
+    /// Median of the average RTTs, in microseconds, reported by the measuring nodes.
+    pub median_rtt_micros: u64,
+
+    /// Number of nodes that have reported their measurements of this node.
+    pub reporters: usize,
+}
+
 // the bare minimum information needed to construct sphinx packets
-#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SkimmedNode {
     // in directory v3 all nodes (mixnodes AND gateways) will have a unique id
     // but to keep structure consistent, introduce this field now
@@ -75,9 +120,27 @@ pub struct SkimmedNode {
 
     /// Average node performance in last 24h period
     pub performance: Performance,
+
+    /// Stake saturation of the node. Always zero for gateways.
+    #[serde(default)]
+    pub stake_saturation: StakeSaturation,
+
+    /// Head of the family the node belongs to, if any. Always empty for gateways.
+    #[serde(default)]
+    pub family: Option<FamilyHead>,
+
+    /// Latency of the node as measured by other nodes in the network, if available.
+    #[serde(default)]
+    pub latency: Option<NodeLatency>,
 }
 
 impl SkimmedNode {
+    #[must_use]
+    pub fn with_latency(mut self, latency: Option<NodeLatency>) -> Self {
+        self.latency = latency;
+        self
+    }
+
     pub fn from_described_gateway(
         annotated: &GatewayBondAnnotated,
         description: Option<&NymNodeDescription>,
@@ -118,6 +181,9 @@ impl<'a> From<&'a MixNodeBondAnnotated> for SkimmedNode {
             },
             entry: None,
             performance: value.node_performance.last_24h,
+            stake_saturation: value.stake_saturation,
+            family: value.family.clone(),
+            latency: None,
         }
     }
 }
@@ -137,6 +203,9 @@ impl<'a> From<&'a GatewayBondAnnotated> for SkimmedNode {
                 wss_port: None,
             }),
             performance: value.node_performance.last_24h,
+            stake_saturation: Decimal::zero(),
+            family: None,
+            latency: None,
         }
     }
 }
@@ -150,6 +219,51 @@ pub struct SemiSkimmedNode {
     // pub location:
 }
 
+impl SemiSkimmedNode {
+    #[must_use]
+    pub fn with_latency(mut self, latency: Option<NodeLatency>) -> Self {
+        self.basic.latency = latency;
+        self
+    }
+
+    pub fn from_described_gateway(
+        annotated: &GatewayBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        SemiSkimmedNode {
+            basic: SkimmedNode::from_described_gateway(annotated, description),
+            x25519_noise_pubkey: description
+                .map(|d| d.host_information.keys.x25519_noise.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn from_described_mixnode(
+        annotated: &MixNodeBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        let mut basic: SkimmedNode = annotated.into();
+        let Some(description) = description else {
+            return SemiSkimmedNode {
+                basic,
+                x25519_noise_pubkey: String::new(),
+            };
+        };
+
+        // always prefer self-described data
+        if !description.host_information.ip_address.is_empty() {
+            basic
+                .ip_addresses
+                .clone_from(&description.host_information.ip_address)
+        }
+
+        SemiSkimmedNode {
+            basic,
+            x25519_noise_pubkey: description.host_information.keys.x25519_noise.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FullFatNode {
     pub expanded: SemiSkimmedNode,
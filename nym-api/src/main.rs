@@ -9,8 +9,10 @@ use crate::coconut::dkg::controller::keys::{
 };
 use crate::epoch_operations::RewardedSetUpdater;
 use crate::network::models::NetworkDetails;
+use crate::network_events::NetworkEvents;
 use crate::node_describe_cache::DescribedNodes;
 use crate::node_status_api::uptime_updater::HistoricalUptimeUpdater;
+use crate::nym_nodes::history::SkimmedNodesHistory;
 use crate::support::caching::cache::SharedCache;
 use crate::support::cli;
 use crate::support::config::Config;
@@ -33,6 +35,7 @@ mod circulating_supply_api;
 mod coconut;
 mod epoch_operations;
 pub(crate) mod network;
+mod network_events;
 mod network_monitor;
 pub(crate) mod node_describe_cache;
 pub(crate) mod node_status_api;
@@ -109,6 +112,8 @@ async fn start_nym_api_tasks(config: Config) -> anyhow::Result<ShutdownHandles>
     let circulating_supply_cache_state = rocket.state::<CirculatingSupplyCache>().unwrap();
     let maybe_storage = rocket.state::<NymApiStorage>();
     let described_nodes_state = rocket.state::<SharedCache<DescribedNodes>>().unwrap();
+    let network_events_state = rocket.state::<NetworkEvents>().unwrap();
+    let skimmed_history_state = rocket.state::<SkimmedNodesHistory>().unwrap();
 
     // start note describe cache refresher
     // we should be doing the below, but can't due to our current startup structure
@@ -136,6 +141,9 @@ async fn start_nym_api_tasks(config: Config) -> anyhow::Result<ShutdownHandles>
         node_status_cache_state,
         maybe_storage,
         nym_contract_cache_listener,
+        network_events_state,
+        described_nodes_state,
+        skimmed_history_state,
         &shutdown,
     );
     circulating_supply_api::start_cache_refresh(
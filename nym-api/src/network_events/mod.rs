@@ -0,0 +1,257 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_api_requests::events::NetworkEvent;
+use nym_mixnet_contract_common::{EpochId, IdentityKey, Interval, Layer, MixId};
+use rocket::Route;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex};
+
+mod routes;
+
+// if a subscriber falls behind by more than that many events, it will be told to resync
+const EVENTS_CHANNEL_CAPACITY: usize = 64;
+
+// the event stream is not (yet) documented in the openapi spec as it's not a json response
+pub(crate) fn network_events_routes() -> Vec<Route> {
+    routes![routes::network_events]
+}
+
+#[derive(Default)]
+struct ObservedState {
+    absolute_epoch_id: Option<EpochId>,
+    rewarded_set: Option<HashMap<MixId, Layer>>,
+    mixnodes_blacklist: Option<HashSet<MixId>>,
+    gateways_blacklist: Option<HashSet<IdentityKey>>,
+}
+
+fn sorted_difference<T: Clone + Ord + Hash>(a: &HashSet<T>, b: &HashSet<T>) -> Vec<T> {
+    let mut difference = a.difference(b).cloned().collect::<Vec<_>>();
+    difference.sort();
+    difference
+}
+
+/// Broadcasts changes in the network state to all the subscribers of the event stream.
+/// The changes are derived from the data observed by the various nym-api tasks,
+/// so that the same change is never announced twice.
+#[derive(Clone)]
+pub(crate) struct NetworkEvents {
+    sender: broadcast::Sender<NetworkEvent>,
+    observed: Arc<Mutex<ObservedState>>,
+}
+
+impl NetworkEvents {
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CHANNEL_CAPACITY);
+        NetworkEvents {
+            sender,
+            observed: Arc::new(Mutex::new(ObservedState::default())),
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<NetworkEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: NetworkEvent) {
+        debug!("publishing network event: {event:?}");
+        // it's perfectly fine if nobody is currently subscribed
+        let _ = self.sender.send(event);
+    }
+
+    /// Records the currently observed interval and announces the transition
+    /// if the epoch has changed since it has been last observed.
+    pub(crate) async fn observe_interval(&self, interval: &Interval) {
+        let absolute_epoch_id = interval.current_epoch_absolute_id();
+
+        let mut observed = self.observed.lock().await;
+        let is_transition = observed
+            .absolute_epoch_id
+            .map(|previous| absolute_epoch_id > previous)
+            .unwrap_or_default();
+        if is_transition || observed.absolute_epoch_id.is_none() {
+            observed.absolute_epoch_id = Some(absolute_epoch_id);
+        }
+
+        if is_transition {
+            self.publish(NetworkEvent::EpochTransition {
+                interval_id: interval.current_interval_id(),
+                epoch_id: interval.current_epoch_id(),
+                absolute_epoch_id,
+                epoch_start: interval.current_epoch_start().into(),
+                epoch_end: interval.current_epoch_end().into(),
+            })
+        }
+    }
+
+    /// Records the currently observed rewarded set and announces any changes
+    /// since it has been last observed.
+    pub(crate) async fn observe_rewarded_set<I>(&self, rewarded_set: I)
+    where
+        I: IntoIterator<Item = (MixId, Layer)>,
+    {
+        let rewarded_set = rewarded_set.into_iter().collect::<HashMap<_, _>>();
+
+        let mut observed = self.observed.lock().await;
+        let Some(previous) = observed.rewarded_set.replace(rewarded_set.clone()) else {
+            return;
+        };
+
+        let current_ids = rewarded_set.keys().copied().collect::<HashSet<_>>();
+        let previous_ids = previous.keys().copied().collect::<HashSet<_>>();
+
+        let added = sorted_difference(&current_ids, &previous_ids);
+        let removed = sorted_difference(&previous_ids, &current_ids);
+        let mut layer_changed = rewarded_set
+            .iter()
+            .filter(|(mix_id, layer)| {
+                previous
+                    .get(mix_id)
+                    .map(|previous_layer| previous_layer != *layer)
+                    .unwrap_or_default()
+            })
+            .map(|(mix_id, _)| *mix_id)
+            .collect::<Vec<_>>();
+        layer_changed.sort();
+
+        if !added.is_empty() || !removed.is_empty() || !layer_changed.is_empty() {
+            self.publish(NetworkEvent::RewardedSetChanged {
+                added,
+                removed,
+                layer_changed,
+            })
+        }
+    }
+
+    /// Records the currently observed blacklists and announces any changes
+    /// since they have been last observed.
+    pub(crate) async fn observe_blacklists(
+        &self,
+        mixnodes_blacklist: &HashSet<MixId>,
+        gateways_blacklist: &HashSet<IdentityKey>,
+    ) {
+        let mut observed = self.observed.lock().await;
+        let previous_mixnodes = observed
+            .mixnodes_blacklist
+            .replace(mixnodes_blacklist.clone());
+        let previous_gateways = observed
+            .gateways_blacklist
+            .replace(gateways_blacklist.clone());
+
+        let (Some(previous_mixnodes), Some(previous_gateways)) =
+            (previous_mixnodes, previous_gateways)
+        else {
+            return;
+        };
+
+        let mixnodes_added = sorted_difference(mixnodes_blacklist, &previous_mixnodes);
+        let mixnodes_removed = sorted_difference(&previous_mixnodes, mixnodes_blacklist);
+        let gateways_added = sorted_difference(gateways_blacklist, &previous_gateways);
+        let gateways_removed = sorted_difference(&previous_gateways, gateways_blacklist);
+
+        if !mixnodes_added.is_empty()
+            || !mixnodes_removed.is_empty()
+            || !gateways_added.is_empty()
+            || !gateways_removed.is_empty()
+        {
+            self.publish(NetworkEvent::BlacklistChanged {
+                mixnodes_added,
+                mixnodes_removed,
+                gateways_added,
+                gateways_removed,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    #[tokio::test]
+    async fn rewarded_set_changes() {
+        let events = NetworkEvents::new();
+        let mut receiver = events.subscribe();
+
+        // nothing to compare against yet
+        events
+            .observe_rewarded_set([(1, Layer::One), (2, Layer::Two), (3, Layer::Three)])
+            .await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        // nothing has changed
+        events
+            .observe_rewarded_set([(1, Layer::One), (2, Layer::Two), (3, Layer::Three)])
+            .await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        events
+            .observe_rewarded_set([(1, Layer::Two), (2, Layer::Two), (4, Layer::Three)])
+            .await;
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            NetworkEvent::RewardedSetChanged {
+                added: vec![4],
+                removed: vec![3],
+                layer_changed: vec![1],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn epoch_transitions() {
+        let events = NetworkEvents::new();
+        let mut receiver = events.subscribe();
+
+        let interval =
+            Interval::init_interval(24, std::time::Duration::from_secs(3600), &mock_env());
+
+        // nothing to compare against yet
+        events.observe_interval(&interval).await;
+        events.observe_interval(&interval).await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        let next = interval.advance_epoch();
+        events.observe_interval(&next).await;
+        assert!(matches!(
+            receiver.try_recv().unwrap(),
+            NetworkEvent::EpochTransition { absolute_epoch_id, .. } if absolute_epoch_id == next.current_epoch_absolute_id()
+        ));
+
+        // going back (e.g. due to a stale cache) is not a transition
+        events.observe_interval(&interval).await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[tokio::test]
+    async fn blacklist_changes() {
+        let events = NetworkEvents::new();
+        let mut receiver = events.subscribe();
+
+        let gateways = HashSet::from(["foo".to_string()]);
+        events
+            .observe_blacklists(&HashSet::from([1, 2]), &gateways)
+            .await;
+        events
+            .observe_blacklists(&HashSet::from([1, 2]), &gateways)
+            .await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        events
+            .observe_blacklists(&HashSet::from([2, 3]), &HashSet::new())
+            .await;
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            NetworkEvent::BlacklistChanged {
+                mixnodes_added: vec![3],
+                mixnodes_removed: vec![1],
+                gateways_added: vec![],
+                gateways_removed: vec!["foo".to_string()],
+            }
+        );
+    }
+}
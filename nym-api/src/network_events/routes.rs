@@ -0,0 +1,37 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::network_events::NetworkEvents;
+use nym_api_requests::events::NetworkEvent;
+use rocket::response::stream::{Event, EventStream};
+use rocket::{Shutdown, State};
+use tokio::sync::broadcast::error::RecvError;
+
+fn to_sse_event(event: &NetworkEvent) -> Event {
+    Event::json(event).event(event.name())
+}
+
+/// Server-sent events stream of the changes in the network state,
+/// such as epoch transitions, rewarded set changes or blacklist updates.
+#[get("/events")]
+pub(crate) fn network_events(
+    events: &State<NetworkEvents>,
+    mut shutdown: Shutdown,
+) -> EventStream![] {
+    let mut receiver = events.subscribe();
+
+    EventStream! {
+        loop {
+            let event = tokio::select! {
+                received = receiver.recv() => match received {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(missed)) => NetworkEvent::Lagged { missed },
+                },
+                _ = &mut shutdown => break,
+            };
+
+            yield to_sse_event(&event);
+        }
+    }
+}
@@ -8,18 +8,75 @@ use crate::support::config;
 use crate::support::config::DEFAULT_NODE_DESCRIBE_BATCH_SIZE;
 use futures::{stream, StreamExt};
 use nym_api_requests::models::{
-    IpPacketRouterDetails, NetworkRequesterDetails, NymNodeDescription,
+    IpPacketRouterDetails, NetworkRequesterDetails, NodeVerlocResults, NymNodeDescription,
 };
+use nym_api_requests::nym_nodes::NodeLatency;
 use nym_config::defaults::{mainnet, DEFAULT_NYM_NODE_HTTP_PORT};
 use nym_contracts_common::IdentityKey;
-use nym_mixnet_contract_common::Gateway;
 use nym_node_requests::api::client::{NymNodeApiClientError, NymNodeApiClientExt};
+use nym_node_requests::api::v1::metrics::models::{VerlocResult, VerlocStats};
 use std::collections::HashMap;
 use thiserror::Error;
 use time::OffsetDateTime;
 
-// type alias for ease of use
-pub type DescribedNodes = HashMap<IdentityKey, NymNodeDescription>;
+pub struct DescribedNodes {
+    nodes: HashMap<IdentityKey, NymNodeDescription>,
+
+    /// Latencies of all the nodes aggregated from the verloc results reported by the described nodes.
+    latencies: HashMap<IdentityKey, NodeLatency>,
+}
+
+impl DescribedNodes {
+    pub fn new(nodes: HashMap<IdentityKey, NymNodeDescription>) -> Self {
+        DescribedNodes {
+            latencies: aggregate_latencies(&nodes),
+            nodes,
+        }
+    }
+
+    pub fn get_description(&self, node: &str) -> Option<&NymNodeDescription> {
+        self.nodes.get(node)
+    }
+
+    pub fn get_latency(&self, node: &str) -> Option<NodeLatency> {
+        self.latencies.get(node).copied()
+    }
+}
+
+// for every measured node, take the median of the mean RTTs reported by other nodes,
+// so that a minority of misreporting nodes couldn't skew the result much
+fn aggregate_latencies(
+    nodes: &HashMap<IdentityKey, NymNodeDescription>,
+) -> HashMap<IdentityKey, NodeLatency> {
+    let mut reported: HashMap<&str, Vec<u64>> = HashMap::new();
+    for (reporter, description) in nodes {
+        let Some(verloc) = &description.verloc else {
+            continue;
+        };
+        for measurement in &verloc.results {
+            // nodes don't get to vouch for themselves
+            if &measurement.node_identity == reporter {
+                continue;
+            }
+            reported
+                .entry(measurement.node_identity.as_str())
+                .or_default()
+                .push(measurement.mean_micros)
+        }
+    }
+
+    reported
+        .into_iter()
+        .map(|(node, mut rtts)| {
+            rtts.sort_unstable();
+            let latency = NodeLatency {
+                median_rtt_micros: rtts[rtts.len() / 2],
+                reporters: rtts.len(),
+            };
+            (node.to_owned(), latency)
+        })
+        .collect()
+}
 
 #[derive(Debug, Error)]
 pub enum NodeDescribeCacheError {
@@ -29,35 +86,42 @@ pub enum NodeDescribeCacheError {
         source: UninitialisedCache,
     },
 
-    #[error("gateway {gateway} has provided malformed host information ({host}: {source}")]
+    #[error("node {node} has provided malformed host information ({host}: {source}")]
     MalformedHost {
         host: String,
 
-        gateway: IdentityKey,
+        node: IdentityKey,
 
         #[source]
         source: NymNodeApiClientError,
     },
 
-    #[error("gateway '{gateway}' with host '{host}' doesn't seem to expose any of the standard API ports, i.e.: 80, 443 or {}", DEFAULT_NYM_NODE_HTTP_PORT)]
-    NoHttpPortsAvailable { host: String, gateway: IdentityKey },
+    #[error("node '{node}' with host '{host}' doesn't seem to expose any of the standard API ports, i.e.: 80, 443 or {}", DEFAULT_NYM_NODE_HTTP_PORT)]
+    NoHttpPortsAvailable { host: String, node: IdentityKey },
 
-    #[error("failed to query gateway '{gateway}': {source}")]
+    #[error("failed to query node '{node}': {source}")]
     ApiFailure {
-        gateway: IdentityKey,
+        node: IdentityKey,
 
         #[source]
         source: NymNodeApiClientError,
     },
 
     // TODO: perhaps include more details here like whether key/signature/payload was malformed
-    #[error("could not verify signed host information for gateway '{gateway}'")]
-    MissignedHostInformation { gateway: IdentityKey },
+    #[error("could not verify signed host information for node '{node}'")]
+    MissignedHostInformation { node: IdentityKey },
+}
+
+// the minimal information required for querying the self-described endpoints of a bonded node
+struct DescribedNodeTarget {
+    identity_key: IdentityKey,
+    host: String,
+
+    // legacy mixnodes might be exposing their API on a custom port
+    custom_http_port: Option<u16>,
 }
 
 pub struct NodeDescriptionProvider {
-    // for now we only care about gateways, nothing more
-    // network_gateways: SharedCache<Vec<GatewayBond>>,
     contract_cache: NymContractCache,
 
     batch_size: usize,
@@ -79,18 +143,23 @@ impl NodeDescriptionProvider {
 }
 
 async fn try_get_client(
-    gateway: &Gateway,
+    node: &DescribedNodeTarget,
 ) -> Result<nym_node_requests::api::Client, NodeDescribeCacheError> {
-    let gateway_host = &gateway.host;
+    let node_host = &node.host;
 
     // first try the standard port in case the operator didn't put the node behind the proxy,
     // then default https (443)
     // finally default http (80)
-    let addresses_to_try = vec![
-        format!("http://{gateway_host}:{DEFAULT_NYM_NODE_HTTP_PORT}"),
-        format!("https://{gateway_host}"),
-        format!("http://{gateway_host}"),
+    let mut addresses_to_try = vec![
+        format!("http://{node_host}:{DEFAULT_NYM_NODE_HTTP_PORT}"),
+        format!("https://{node_host}"),
+        format!("http://{node_host}"),
     ];
+    if let Some(custom_port) = node.custom_http_port {
+        if custom_port != DEFAULT_NYM_NODE_HTTP_PORT {
+            addresses_to_try.push(format!("http://{node_host}:{custom_port}"))
+        }
+    }
 
     for address in addresses_to_try {
         // if provided host was malformed, no point in continuing
@@ -98,8 +167,8 @@ async fn try_get_client(
             Ok(client) => client,
             Err(err) => {
                 return Err(NodeDescribeCacheError::MalformedHost {
-                    host: gateway_host.clone(),
-                    gateway: gateway.identity_key.clone(),
+                    host: node_host.clone(),
+                    node: node.identity_key.clone(),
                     source: err,
                 });
             }
@@ -112,28 +181,38 @@ async fn try_get_client(
     }
 
     Err(NodeDescribeCacheError::NoHttpPortsAvailable {
-        host: gateway_host.clone(),
-        gateway: gateway.identity_key.clone(),
+        host: node_host.clone(),
+        node: node.identity_key.clone(),
     })
 }
 
-async fn get_gateway_description(
-    gateway: Gateway,
+// returns the results of the most recent complete measurement run
+fn latest_verloc_results(stats: VerlocStats) -> Option<NodeVerlocResults> {
+    [stats.current, stats.previous]
+        .into_iter()
+        .find_map(|result| match result {
+            VerlocResult::Data(data) if data.run_finished() => Some(data.into()),
+            _ => None,
+        })
+}
+
+async fn get_node_description(
+    node: DescribedNodeTarget,
 ) -> Result<(IdentityKey, NymNodeDescription), NodeDescribeCacheError> {
-    let client = try_get_client(&gateway).await?;
+    let client = try_get_client(&node).await?;
 
     let host_info =
         client
             .get_host_information()
             .await
             .map_err(|err| NodeDescribeCacheError::ApiFailure {
-                gateway: gateway.identity_key.clone(),
+                node: node.identity_key.clone(),
                 source: err,
             })?;
 
     if !host_info.verify_host_information() {
         return Err(NodeDescribeCacheError::MissignedHostInformation {
-            gateway: gateway.identity_key,
+            node: node.identity_key,
         });
     }
 
@@ -142,13 +221,13 @@ async fn get_gateway_description(
             .get_build_information()
             .await
             .map_err(|err| NodeDescribeCacheError::ApiFailure {
-                gateway: gateway.identity_key.clone(),
+                node: node.identity_key.clone(),
                 source: err,
             })?;
 
     // this can be an old node that hasn't yet exposed this
     let auxiliary_details = client.get_auxiliary_details().await.inspect_err(|err| {
-        debug!("could not obtain auxiliary details of gateway {}: {err} is it running an old version?", gateway.identity_key);
+        debug!("could not obtain auxiliary details of node {}: {err} is it running an old version?", node.identity_key);
     }).unwrap_or_default();
 
     let websockets =
@@ -156,7 +235,7 @@ async fn get_gateway_description(
             .get_mixnet_websockets()
             .await
             .map_err(|err| NodeDescribeCacheError::ApiFailure {
-                gateway: gateway.identity_key.clone(),
+                node: node.identity_key.clone(),
                 source: err,
             })?;
 
@@ -164,7 +243,7 @@ async fn get_gateway_description(
         if let Ok(nr) = client.get_network_requester().await {
             let exit_policy = client.get_exit_policy().await.map_err(|err| {
                 NodeDescribeCacheError::ApiFailure {
-                    gateway: gateway.identity_key.clone(),
+                    node: node.identity_key.clone(),
                     source: err,
                 }
             })?;
@@ -186,6 +265,19 @@ async fn get_gateway_description(
         None
     };
 
+    // this can be an old node that hasn't yet performed measurements in all modes
+    let verloc = client
+        .get_verloc_stats()
+        .await
+        .inspect_err(|err| {
+            debug!(
+                "could not obtain verloc results of node {}: {err}",
+                node.identity_key
+            );
+        })
+        .ok()
+        .and_then(latest_verloc_results);
+
     let description = NymNodeDescription {
         host_information: host_info.data.into(),
         last_polled: OffsetDateTime::now_utc().into(),
@@ -194,14 +286,15 @@ async fn get_gateway_description(
         ip_packet_router,
         mixnet_websockets: websockets.into(),
         auxiliary_details,
+        verloc,
     };
 
-    Ok((gateway.identity_key, description))
+    Ok((node.identity_key, description))
 }
 
 #[async_trait]
 impl CacheItemProvider for NodeDescriptionProvider {
-    type Item = HashMap<IdentityKey, NymNodeDescription>;
+    type Item = DescribedNodes;
     type Error = NodeDescribeCacheError;
 
     async fn wait_until_ready(&self) {
@@ -210,37 +303,44 @@ impl CacheItemProvider for NodeDescriptionProvider {
 
     async fn try_refresh(&self) -> Result<Self::Item, Self::Error> {
         let gateways = self.contract_cache.gateways_all().await;
+        let mixnodes = self.contract_cache.mixnodes_all().await;
 
-        // let guard = self.network_gateways.get().await?;
-        // let gateways = &*guard;
-
-        if gateways.is_empty() {
-            return Ok(HashMap::new());
+        if gateways.is_empty() && mixnodes.is_empty() {
+            return Ok(DescribedNodes::new(HashMap::new()));
         }
 
-        // TODO: somehow bypass the 'higher-ranked lifetime error' and remove that redundant clone
-        let node_description = stream::iter(
-            gateways
-                // .deref()
-                // .clone()
-                .into_iter()
-                .map(|bond| bond.gateway)
-                .map(get_gateway_description),
-        )
-        .buffer_unordered(self.batch_size)
-        .filter_map(|res| async move {
-            match res {
-                Ok((identity, description)) => Some((identity, description)),
-                Err(err) => {
-                    debug!("failed to obtain gateway self-described data: {err}");
-                    None
+        // we need mixnodes' self-described data for their noise keys
+        let targets = gateways
+            .into_iter()
+            .map(|bond| DescribedNodeTarget {
+                identity_key: bond.gateway.identity_key,
+                host: bond.gateway.host,
+                custom_http_port: None,
+            })
+            .chain(mixnodes.into_iter().map(|details| {
+                let mix_node = details.bond_information.mix_node;
+                DescribedNodeTarget {
+                    identity_key: mix_node.identity_key,
+                    host: mix_node.host,
+                    custom_http_port: Some(mix_node.http_api_port),
                 }
-            }
-        })
-        .collect::<HashMap<_, _>>()
-        .await;
+            }));
+
+        let node_description = stream::iter(targets.map(get_node_description))
+            .buffer_unordered(self.batch_size)
+            .filter_map(|res| async move {
+                match res {
+                    Ok((identity, description)) => Some((identity, description)),
+                    Err(err) => {
+                        debug!("failed to obtain node self-described data: {err}");
+                        None
+                    }
+                }
+            })
+            .collect::<HashMap<_, _>>()
+            .await;
 
-        Ok(node_description)
+        Ok(DescribedNodes::new(node_description))
     }
 }
 
@@ -277,3 +377,100 @@ pub(crate) fn new_refresher_with_initial_value(
         initial,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_api_requests::models::{HostInformation, HostKeys, VerlocNodeMeasurement, WebSockets};
+
+    fn description(identity: &str, measured: &[(&str, u64)]) -> NymNodeDescription {
+        let verloc = (!measured.is_empty()).then(|| NodeVerlocResults {
+            nodes_tested: measured.len(),
+            run_started: OffsetDateTime::now_utc().into(),
+            run_finished: Some(OffsetDateTime::now_utc().into()),
+            results: measured
+                .iter()
+                .map(|(node, mean_micros)| VerlocNodeMeasurement {
+                    node_identity: node.to_string(),
+                    minimum_micros: mean_micros / 2,
+                    mean_micros: *mean_micros,
+                    maximum_micros: mean_micros * 2,
+                    standard_deviation_micros: 0,
+                })
+                .collect(),
+        });
+
+        NymNodeDescription {
+            last_polled: Default::default(),
+            host_information: HostInformation {
+                ip_address: vec!["1.2.3.4".parse().unwrap()],
+                hostname: None,
+                keys: HostKeys {
+                    ed25519: identity.to_string(),
+                    x25519: "sphinx".to_string(),
+                    x25519_noise: "noise".to_string(),
+                },
+            },
+            auxiliary_details: Default::default(),
+            build_information: nym_bin_common::bin_info_owned!(),
+            network_requester: None,
+            ip_packet_router: None,
+            mixnet_websockets: WebSockets {
+                ws_port: 9000,
+                wss_port: None,
+            },
+            verloc,
+        }
+    }
+
+    fn described(nodes: Vec<NymNodeDescription>) -> HashMap<IdentityKey, NymNodeDescription> {
+        nodes
+            .into_iter()
+            .map(|n| (n.host_information.keys.ed25519.clone(), n))
+            .collect()
+    }
+
+    #[test]
+    fn latency_is_the_median_of_reported_means() {
+        let nodes = described(vec![
+            description("a", &[("d", 300)]),
+            description("b", &[("d", 100)]),
+            description("c", &[("d", 200)]),
+            description("d", &[]),
+        ]);
+
+        let latencies = aggregate_latencies(&nodes);
+        assert_eq!(latencies.len(), 1);
+        assert_eq!(
+            latencies["d"],
+            NodeLatency {
+                median_rtt_micros: 200,
+                reporters: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn self_reported_measurements_are_ignored() {
+        let nodes = described(vec![
+            description("a", &[("a", 1), ("b", 500)]),
+            description("b", &[("b", 1)]),
+        ]);
+
+        let latencies = aggregate_latencies(&nodes);
+        assert!(!latencies.contains_key("a"));
+        assert_eq!(
+            latencies["b"],
+            NodeLatency {
+                median_rtt_micros: 500,
+                reporters: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn nodes_without_verloc_results_produce_no_latencies() {
+        let nodes = described(vec![description("a", &[]), description("b", &[])]);
+        assert!(aggregate_latencies(&nodes).is_empty());
+    }
+}
@@ -28,6 +28,9 @@ enum NodeStatusCacheError {
 
     #[error("the current interval information is not available at the moment")]
     SourceDataMissing,
+
+    #[error("failed to acquire the write lock on the cache in time")]
+    UpdateTimeout,
 }
 
 /// A node status cache suitable for caching values computed in one sweep, such as active set
@@ -61,7 +64,7 @@ impl NodeStatusCache {
         active_set: Vec<MixNodeBondAnnotated>,
         gateways: HashMap<IdentityKey, GatewayBondAnnotated>,
         inclusion_probabilities: InclusionProbabilities,
-    ) {
+    ) -> Result<(), NodeStatusCacheError> {
         match time::timeout(Duration::from_millis(CACHE_TIMEOUT_MS), self.inner.write()).await {
             Ok(mut cache) => {
                 cache.mixnodes_annotated.unchecked_update(mixnodes);
@@ -71,8 +74,12 @@ impl NodeStatusCache {
                 cache
                     .inclusion_probabilities
                     .unchecked_update(inclusion_probabilities);
+                Ok(())
+            }
+            Err(e) => {
+                error!("{e}");
+                Err(NodeStatusCacheError::UpdateTimeout)
             }
-            Err(e) => error!("{e}"),
         }
     }
 
@@ -3,6 +3,8 @@
 
 use super::NodeStatusCache;
 use crate::{
+    network_events::NetworkEvents,
+    node_describe_cache::DescribedNodes,
     node_status_api::cache::{
         inclusion_probabilities::InclusionProbabilities,
         node_sets::{
@@ -12,8 +14,12 @@ use crate::{
         NodeStatusCacheError,
     },
     nym_contract_cache::cache::NymContractCache,
+    nym_nodes::{
+        history::SkimmedNodesHistory,
+        unstable_routes::{gateways_snapshot, mixnodes_snapshot},
+    },
     storage::NymApiStorage,
-    support::caching::CacheNotification,
+    support::caching::{cache::SharedCache, CacheNotification},
 };
 use nym_task::TaskClient;
 use std::time::Duration;
@@ -30,15 +36,25 @@ pub struct NodeStatusCacheRefresher {
     contract_cache: NymContractCache,
     contract_cache_listener: watch::Receiver<CacheNotification>,
     storage: Option<NymApiStorage>,
+
+    // Changes observed while refreshing get pushed to the event stream subscribers,
+    // but only once the skimmed nodes served to the clients reflect them
+    events: NetworkEvents,
+    described_nodes: SharedCache<DescribedNodes>,
+    skimmed_history: SkimmedNodesHistory,
 }
 
 impl NodeStatusCacheRefresher {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         cache: NodeStatusCache,
         fallback_caching_interval: Duration,
         contract_cache: NymContractCache,
         contract_cache_listener: watch::Receiver<CacheNotification>,
         storage: Option<NymApiStorage>,
+        events: NetworkEvents,
+        described_nodes: SharedCache<DescribedNodes>,
+        skimmed_history: SkimmedNodesHistory,
     ) -> Self {
         Self {
             cache,
@@ -46,6 +62,9 @@ impl NodeStatusCacheRefresher {
             contract_cache,
             contract_cache_listener,
             storage,
+            events,
+            described_nodes,
+            skimmed_history,
         }
     }
 
@@ -152,7 +171,7 @@ impl NodeStatusCacheRefresher {
         .await;
 
         // Create the annotated rewarded and active sets
-        let (rewarded_set, active_set) =
+        let (rewarded_set_annotated, active_set_annotated) =
             split_into_active_and_rewarded_set(&mixnodes_annotated, &rewarded_set_node_status);
 
         let gateways_annotated = annotate_gateways_with_details(
@@ -164,15 +183,50 @@ impl NodeStatusCacheRefresher {
         .await;
 
         // Update the cache
+        let served_revisions = self.skimmed_revisions().await;
         self.cache
             .update(
                 mixnodes_annotated,
-                rewarded_set,
-                active_set,
+                rewarded_set_annotated,
+                active_set_annotated,
                 gateways_annotated,
                 inclusion_probabilities,
             )
-            .await;
+            .await?;
+
+        // Push out any changes since the last refresh, but only once the clients reacting to them
+        // are able to retrieve the updated nodes. Otherwise they're going to get announced
+        // alongside the next change.
+        if self.skimmed_revisions().await == served_revisions {
+            log::trace!("the skimmed nodes haven't changed - not announcing anything");
+            return Ok(());
+        }
+
+        self.events.observe_interval(&current_interval).await;
+        // (an unpopulated rewarded set means the contract cache couldn't be read)
+        if rewarded_set.revision() > 0 {
+            self.events
+                .observe_rewarded_set(rewarded_set.iter().map(|m| (m.mix_id(), m.layer())))
+                .await;
+        }
+        // (and the same holds for the blacklists, which come back empty if their lock couldn't be acquired)
+        if mixnodes_blacklist.revision() > 0 && gateways_blacklist.revision() > 0 {
+            self.events
+                .observe_blacklists(&mixnodes_blacklist, &gateways_blacklist)
+                .await;
+        }
         Ok(())
     }
+
+    /// Revisions of the skimmed mixnodes and gateways as they'd be currently served by the api.
+    async fn skimmed_revisions(&self) -> (Option<String>, Option<String>) {
+        let mixnodes =
+            mixnodes_snapshot(&self.cache, &self.described_nodes, &self.skimmed_history).await;
+        let gateways =
+            gateways_snapshot(&self.cache, &self.described_nodes, &self.skimmed_history).await;
+        (
+            mixnodes.ok().map(|snapshot| snapshot.revision()),
+            gateways.ok().map(|snapshot| snapshot.revision()),
+        )
+    }
 }
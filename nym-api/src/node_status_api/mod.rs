@@ -4,8 +4,11 @@
 use self::cache::refresher::NodeStatusCacheRefresher;
 use crate::support::config;
 use crate::{
+    network_events::NetworkEvents,
+    node_describe_cache::DescribedNodes,
     nym_contract_cache::cache::NymContractCache,
-    support::{self, storage},
+    nym_nodes::history::SkimmedNodesHistory,
+    support::{self, caching::cache::SharedCache, storage},
 };
 pub(crate) use cache::NodeStatusCache;
 use nym_task::TaskManager;
@@ -75,12 +78,16 @@ pub(crate) fn node_status_routes(
 ///
 /// It is primarily refreshed in-sync with the nym contract cache, however provide a fallback
 /// caching interval that is twice the nym contract cache
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn start_cache_refresh(
     config: &config::NodeStatusAPI,
     nym_contract_cache_state: &NymContractCache,
     node_status_cache_state: &NodeStatusCache,
     storage: Option<&storage::NymApiStorage>,
     nym_contract_cache_listener: tokio::sync::watch::Receiver<support::caching::CacheNotification>,
+    network_events: &NetworkEvents,
+    described_nodes: &SharedCache<DescribedNodes>,
+    skimmed_history: &SkimmedNodesHistory,
     shutdown: &TaskManager,
 ) {
     let mut nym_api_cache_refresher = NodeStatusCacheRefresher::new(
@@ -89,6 +96,9 @@ pub(crate) fn start_cache_refresh(
         nym_contract_cache_state.to_owned(),
         nym_contract_cache_listener,
         storage.cloned(),
+        network_events.clone(),
+        described_nodes.clone(),
+        skimmed_history.clone(),
     );
     let shutdown_listener = shutdown.subscribe();
     tokio::spawn(async move { nym_api_cache_refresher.run(shutdown_listener).await });
@@ -0,0 +1,389 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_api_requests::nym_nodes::{NodesDelta, SkimmedNode};
+use nym_bin_common::version_checker;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tokio::sync::RwLock;
+
+// how many past revisions of the node set are kept around for the purposes of computing deltas.
+// with the default cache refresh rates this covers well over an hour of changes
+const MAX_RETAINED_REVISIONS: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HistoricalNode {
+    pub(crate) node: SkimmedNode,
+
+    // we need to keep the version around to be able to apply the semver filter
+    pub(crate) version: String,
+}
+
+impl HistoricalNode {
+    pub(crate) fn new(node: SkimmedNode, version: impl Into<String>) -> Self {
+        HistoricalNode {
+            node,
+            version: version.into(),
+        }
+    }
+
+    pub(crate) fn is_compatible(&self, semver_compatibility: Option<&String>) -> bool {
+        match semver_compatibility {
+            Some(semver_compatibility) => {
+                version_checker::is_minor_version_compatible(&self.version, semver_compatibility)
+            }
+            None => true,
+        }
+    }
+}
+
+pub(crate) struct NodesSnapshot {
+    epoch: i64,
+    revision: u64,
+    pub(crate) refreshed_at: OffsetDateTime,
+    pub(crate) nodes: BTreeMap<String, HistoricalNode>,
+}
+
+impl NodesSnapshot {
+    /// Opaque revision of this snapshot. It's used both as the `ETag`
+    /// of the skimmed responses and as the base for the delta requests.
+    pub(crate) fn revision(&self) -> String {
+        format!("{}-{}", self.epoch, self.revision)
+    }
+
+    pub(crate) fn compatible_nodes<'a>(
+        &'a self,
+        semver_compatibility: Option<&'a String>,
+    ) -> impl Iterator<Item = &'a SkimmedNode> + 'a {
+        self.nodes
+            .values()
+            .filter(move |node| node.is_compatible(semver_compatibility))
+            .map(|node| &node.node)
+    }
+
+    fn delta_from(
+        &self,
+        base: Option<&NodesSnapshot>,
+        semver_compatibility: Option<&String>,
+    ) -> NodesDelta<SkimmedNode> {
+        let mut delta = NodesDelta {
+            refreshed_at: self.refreshed_at.into(),
+            revision: self.revision(),
+            full: base.is_none(),
+            added: vec![],
+            changed: vec![],
+            removed: vec![],
+        };
+
+        let Some(base) = base else {
+            delta.added = self
+                .compatible_nodes(semver_compatibility)
+                .cloned()
+                .collect();
+            return delta;
+        };
+
+        let previous = |identity: &String| {
+            base.nodes
+                .get(identity)
+                .filter(|node| node.is_compatible(semver_compatibility))
+        };
+
+        for (identity, node) in &self.nodes {
+            if !node.is_compatible(semver_compatibility) {
+                continue;
+            }
+            match previous(identity) {
+                None => delta.added.push(node.node.clone()),
+                Some(old) if old.node != node.node => delta.changed.push(node.node.clone()),
+                Some(_) => (),
+            }
+        }
+
+        for identity in base.nodes.keys() {
+            if previous(identity).is_none() {
+                continue;
+            }
+            let still_present = self
+                .nodes
+                .get(identity)
+                .map(|node| node.is_compatible(semver_compatibility))
+                .unwrap_or_default();
+            if !still_present {
+                delta.removed.push(identity.clone())
+            }
+        }
+
+        delta
+    }
+}
+
+// revisions of the underlying caches (if available) a snapshot has been built from
+pub(crate) type SourceRevisions = (u64, Option<u64>);
+
+#[derive(Default)]
+struct NodesHistoryInner {
+    source_revisions: Option<SourceRevisions>,
+    snapshots: VecDeque<Arc<NodesSnapshot>>,
+}
+
+/// Keeps track of the recent revisions of a set of nodes (built out of multiple underlying caches)
+/// so that the clients could only retrieve the changes since the last time they have queried it.
+#[derive(Clone)]
+pub(crate) struct NodesHistory {
+    // allows to distinguish revisions created by different instances (or runs) of the nym-api
+    epoch: i64,
+    inner: Arc<RwLock<NodesHistoryInner>>,
+}
+
+impl NodesHistory {
+    fn new(epoch: i64) -> Self {
+        NodesHistory {
+            epoch,
+            inner: Arc::new(RwLock::new(NodesHistoryInner::default())),
+        }
+    }
+
+    /// Returns the latest snapshot of the node set. If any of the underlying caches has been updated
+    /// since the latest snapshot has been created, the node set is rebuilt and, if it differs
+    /// from the previous one, stored under a new revision.
+    pub(crate) async fn latest<F>(
+        &self,
+        source_revisions: SourceRevisions,
+        refreshed_at: OffsetDateTime,
+        build: F,
+    ) -> Arc<NodesSnapshot>
+    where
+        F: FnOnce() -> Vec<HistoricalNode>,
+    {
+        {
+            let guard = self.inner.read().await;
+            if guard.source_revisions == Some(source_revisions) {
+                if let Some(latest) = guard.snapshots.back() {
+                    return Arc::clone(latest);
+                }
+            }
+        }
+
+        let mut guard = self.inner.write().await;
+        // somebody might have already rebuilt it while we were waiting for the lock
+        if guard.source_revisions == Some(source_revisions) {
+            if let Some(latest) = guard.snapshots.back() {
+                return Arc::clone(latest);
+            }
+        }
+
+        let nodes = build()
+            .into_iter()
+            .map(|node| (node.node.ed25519_identity_pubkey.clone(), node))
+            .collect::<BTreeMap<_, _>>();
+
+        let latest = guard.snapshots.back();
+        let unchanged = latest
+            .map(|latest| latest.nodes == nodes)
+            .unwrap_or_default();
+        let revision = match latest {
+            Some(latest) if unchanged => latest.revision,
+            Some(latest) => latest.revision + 1,
+            None => 0,
+        };
+
+        let snapshot = Arc::new(NodesSnapshot {
+            epoch: self.epoch,
+            revision,
+            refreshed_at,
+            nodes,
+        });
+
+        // if the node set hasn't changed, just bump the refresh timestamp
+        if unchanged {
+            guard.snapshots.pop_back();
+        }
+        guard.snapshots.push_back(Arc::clone(&snapshot));
+        while guard.snapshots.len() > MAX_RETAINED_REVISIONS {
+            guard.snapshots.pop_front();
+        }
+        guard.source_revisions = Some(source_revisions);
+
+        snapshot
+    }
+
+    /// Computes changes between the provided revision and the latest snapshot.
+    /// If the revision is unknown, the delta contains the full set of nodes.
+    pub(crate) async fn delta(
+        &self,
+        latest: &NodesSnapshot,
+        since: Option<&str>,
+        semver_compatibility: Option<&String>,
+    ) -> NodesDelta<SkimmedNode> {
+        let guard = self.inner.read().await;
+        let base = since.and_then(|since| {
+            guard
+                .snapshots
+                .iter()
+                .find(|snapshot| snapshot.revision() == since)
+        });
+
+        latest.delta_from(base.map(|b| b.as_ref()), semver_compatibility)
+    }
+}
+
+/// Recent revisions of the skimmed mixnodes and gateways.
+#[derive(Clone)]
+pub(crate) struct SkimmedNodesHistory {
+    pub(crate) mixnodes: NodesHistory,
+    pub(crate) gateways: NodesHistory,
+}
+
+impl SkimmedNodesHistory {
+    pub(crate) fn new() -> Self {
+        let epoch = OffsetDateTime::now_utc().unix_timestamp();
+        SkimmedNodesHistory {
+            mixnodes: NodesHistory::new(epoch),
+            gateways: NodesHistory::new(epoch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_api_requests::nym_nodes::NodeRole;
+    use nym_mixnet_contract_common::reward_params::Performance;
+
+    fn node(identity: &str, performance: u64, version: &str) -> HistoricalNode {
+        HistoricalNode::new(
+            SkimmedNode {
+                node_id: 1,
+                ed25519_identity_pubkey: identity.to_string(),
+                ip_addresses: vec!["1.2.3.4".parse().unwrap()],
+                mix_port: 1789,
+                x25519_sphinx_pubkey: "sphinx".to_string(),
+                role: NodeRole::Mixnode { layer: 1 },
+                entry: None,
+                performance: Performance::from_percentage_value(performance).unwrap(),
+                stake_saturation: Default::default(),
+                family: None,
+                latency: None,
+            },
+            version,
+        )
+    }
+
+    fn identities(nodes: &[SkimmedNode]) -> Vec<&str> {
+        let mut identities = nodes
+            .iter()
+            .map(|n| n.ed25519_identity_pubkey.as_str())
+            .collect::<Vec<_>>();
+        identities.sort();
+        identities
+    }
+
+    #[tokio::test]
+    async fn revision_is_only_bumped_on_changes() {
+        let history = NodesHistory::new(42);
+        let now = OffsetDateTime::now_utc();
+
+        let first = history
+            .latest((0, Some(0)), now, || vec![node("a", 100, "1.1.0")])
+            .await;
+        assert_eq!(first.revision(), "42-0");
+
+        // the same source revisions don't even attempt to rebuild the set
+        let same = history
+            .latest((0, Some(0)), now, || panic!("should not have been called"))
+            .await;
+        assert_eq!(same.revision(), "42-0");
+
+        // caches got refreshed, but the nodes are the same
+        let unchanged = history
+            .latest((1, Some(0)), now, || vec![node("a", 100, "1.1.0")])
+            .await;
+        assert_eq!(unchanged.revision(), "42-0");
+
+        let changed = history
+            .latest((1, Some(1)), now, || vec![node("a", 90, "1.1.0")])
+            .await;
+        assert_eq!(changed.revision(), "42-1");
+    }
+
+    #[tokio::test]
+    async fn computing_deltas() {
+        let history = NodesHistory::new(42);
+        let now = OffsetDateTime::now_utc();
+
+        let first = history
+            .latest((0, Some(0)), now, || {
+                vec![
+                    node("a", 100, "1.1.0"),
+                    node("b", 100, "1.1.0"),
+                    node("c", 100, "1.1.0"),
+                ]
+            })
+            .await;
+        let second = history
+            .latest((1, Some(0)), now, || {
+                vec![
+                    node("a", 100, "1.1.0"),
+                    node("b", 50, "1.1.0"),
+                    node("d", 100, "1.1.0"),
+                ]
+            })
+            .await;
+
+        let delta = history
+            .delta(&second, Some(first.revision().as_str()), None)
+            .await;
+        assert!(!delta.full);
+        assert_eq!(delta.revision, second.revision());
+        assert_eq!(identities(&delta.added), vec!["d"]);
+        assert_eq!(identities(&delta.changed), vec!["b"]);
+        assert_eq!(delta.removed, vec!["c".to_string()]);
+
+        let up_to_date = history
+            .delta(&second, Some(second.revision().as_str()), None)
+            .await;
+        assert!(up_to_date.is_empty());
+
+        // unknown revisions result in the full set
+        for since in [None, Some("41-0"), Some("foomp")] {
+            let delta = history.delta(&second, since, None).await;
+            assert!(delta.full);
+            assert_eq!(identities(&delta.added), vec!["a", "b", "d"]);
+            assert!(delta.changed.is_empty());
+            assert!(delta.removed.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn deltas_respect_semver_compatibility() {
+        let history = NodesHistory::new(42);
+        let now = OffsetDateTime::now_utc();
+
+        let first = history
+            .latest((0, Some(0)), now, || {
+                vec![node("a", 100, "1.1.0"), node("b", 100, "1.1.0")]
+            })
+            .await;
+        let second = history
+            .latest((1, Some(0)), now, || {
+                vec![
+                    node("a", 100, "1.2.0"),
+                    node("b", 50, "1.1.0"),
+                    node("c", 100, "1.2.0"),
+                ]
+            })
+            .await;
+
+        // from the point of view of the older client, 'a' has disappeared
+        // and 'c' has never been there
+        let semver = "1.1.0".to_string();
+        let delta = history
+            .delta(&second, Some(first.revision().as_str()), Some(&semver))
+            .await;
+        assert!(delta.added.is_empty());
+        assert_eq!(identities(&delta.changed), vec!["b"]);
+        assert_eq!(delta.removed, vec!["a".to_string()]);
+    }
+}
@@ -6,8 +6,9 @@ use rocket::Route;
 use rocket_okapi::openapi_get_routes_spec;
 use rocket_okapi::settings::OpenApiSettings;
 
+pub(crate) mod history;
 pub(crate) mod routes;
-mod unstable_routes;
+pub(crate) mod unstable_routes;
 
 /// Merges the routes with http information and returns it to Rocket for serving
 pub(crate) fn nym_node_routes(settings: &OpenApiSettings) -> (Vec<Route>, OpenApi) {
@@ -23,9 +24,11 @@ pub(crate) fn nym_node_routes_next(settings: &OpenApiSettings) -> (Vec<Route>, O
         unstable_routes::nodes_expanded,
         unstable_routes::nodes_detailed,
         unstable_routes::gateways_basic,
+        unstable_routes::gateways_basic_delta,
         unstable_routes::gateways_expanded,
         unstable_routes::gateways_detailed,
         unstable_routes::mixnodes_basic,
+        unstable_routes::mixnodes_basic_delta,
         unstable_routes::mixnodes_expanded,
         unstable_routes::mixnodes_detailed,
     ]
@@ -8,7 +8,6 @@ use nym_api_requests::models::DescribedGateway;
 use rocket::serde::json::Json;
 use rocket::State;
 use rocket_okapi::openapi;
-use std::ops::Deref;
 
 // obviously this should get refactored later on because gateways will go away.
 // unless maybe this will be filtering based on which nodes got assigned gateway role? TBD
@@ -36,7 +35,7 @@ pub async fn get_gateways_described(
         gateways
             .into_iter()
             .map(|bond| DescribedGateway {
-                self_described: self_descriptions.deref().get(bond.identity()).cloned(),
+                self_described: self_descriptions.get_description(bond.identity()).cloned(),
                 bond,
             })
             .collect(),
@@ -4,9 +4,11 @@
 use crate::node_describe_cache::DescribedNodes;
 use crate::node_status_api::models::ErrorResponse;
 use crate::node_status_api::NodeStatusCache;
+use crate::nym_nodes::history::{HistoricalNode, NodesSnapshot, SkimmedNodesHistory};
 use crate::support::caching::cache::SharedCache;
+use crate::support::http::conditional::{Conditional, IfNoneMatch};
 use nym_api_requests::nym_nodes::{
-    CachedNodesResponse, FullFatNode, NodeRoleQueryParam, SemiSkimmedNode, SkimmedNode,
+    CachedNodesResponse, FullFatNode, NodeRoleQueryParam, NodesDelta, SemiSkimmedNode, SkimmedNode,
 };
 use nym_bin_common::version_checker;
 use rocket::http::Status;
@@ -14,7 +16,7 @@ use rocket::serde::json::Json;
 use rocket::State;
 use rocket_okapi::openapi;
 use std::cmp::min;
-use std::ops::Deref;
+use std::sync::Arc;
 
 /*
    routes:
@@ -29,6 +31,11 @@ use std::ops::Deref;
    /mixnodes/<tier> => only returns mixnode role data
    /gateway/<tier> => only returns (entry) gateway role data
 
+   // the skimmed data is additionally versioned:
+   // the responses include an `ETag` (honouring `If-None-Match`) that only changes with the nodes,
+   // so `refreshed_at` of a cached response is not updated by a `304 Not Modified`, and
+   // /<role>/skimmed/delta?since=<revision> => only returns the changes since the specified revision
+
 
 */
 
@@ -37,16 +44,32 @@ use std::ops::Deref;
 pub async fn nodes_basic(
     status_cache: &State<NodeStatusCache>,
     describe_cache: &State<SharedCache<DescribedNodes>>,
+    history: &State<SkimmedNodesHistory>,
+    if_none_match: IfNoneMatch,
     role: Option<NodeRoleQueryParam>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
+) -> Result<Conditional<Json<CachedNodesResponse<SkimmedNode>>>, ErrorResponse> {
     if let Some(role) = role {
         match role {
             NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_basic(status_cache, semver_compatibility).await
+                return mixnodes_basic(
+                    status_cache,
+                    describe_cache,
+                    history,
+                    if_none_match,
+                    semver_compatibility,
+                )
+                .await
             }
             NodeRoleQueryParam::EntryGateway => {
-                return gateways_basic(status_cache, describe_cache, semver_compatibility).await
+                return gateways_basic(
+                    status_cache,
+                    describe_cache,
+                    history,
+                    if_none_match,
+                    semver_compatibility,
+                )
+                .await
             }
             _ => {}
         }
@@ -58,17 +81,18 @@ pub async fn nodes_basic(
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/semi-skimmed?<role>&<semver_compatibility>")]
 pub async fn nodes_expanded(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     role: Option<NodeRoleQueryParam>,
     semver_compatibility: Option<String>,
 ) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
     if let Some(role) = role {
         match role {
             NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_expanded(cache, semver_compatibility).await
+                return mixnodes_expanded(status_cache, describe_cache, semver_compatibility).await
             }
             NodeRoleQueryParam::EntryGateway => {
-                return gateways_expanded(cache, semver_compatibility).await
+                return gateways_expanded(status_cache, describe_cache, semver_compatibility).await
             }
             _ => {}
         }
@@ -99,13 +123,114 @@ pub async fn nodes_detailed(
     Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
 }
 
+pub(crate) async fn gateways_snapshot(
+    status_cache: &NodeStatusCache,
+    describe_cache: &SharedCache<DescribedNodes>,
+    history: &SkimmedNodesHistory,
+) -> Result<Arc<NodesSnapshot>, ErrorResponse> {
+    let gateways_cache = status_cache
+        .gateways_cache()
+        .await
+        .ok_or(ErrorResponse::new(
+            "could not obtain gateways cache",
+            Status::InternalServerError,
+        ))?;
+
+    // if the self describe cache is unavailable don't try to use self-describe data
+    let self_descriptions = describe_cache.get().await.ok();
+    let (describe_revision, refreshed_at) = match &self_descriptions {
+        Some(descriptions) => (
+            Some(descriptions.revision()),
+            min(gateways_cache.timestamp(), descriptions.timestamp()),
+        ),
+        None => (None, gateways_cache.timestamp()),
+    };
+
+    let source_revisions = (gateways_cache.revision(), describe_revision);
+    Ok(history
+        .gateways
+        .latest(source_revisions, refreshed_at, || {
+            // the same comment holds as with `get_gateways_described`.
+            // this is inefficient and will have to get refactored with directory v3
+            gateways_cache
+                .values()
+                .map(|annotated_bond| {
+                    let descriptions = self_descriptions.as_ref();
+                    let node = SkimmedNode::from_described_gateway(
+                        annotated_bond,
+                        descriptions.and_then(|d| d.get_description(annotated_bond.identity())),
+                    )
+                    .with_latency(
+                        descriptions.and_then(|d| d.get_latency(annotated_bond.identity())),
+                    );
+                    HistoricalNode::new(node, &annotated_bond.gateway_bond.gateway.version)
+                })
+                .collect()
+        })
+        .await)
+}
+
+fn skimmed_response(
+    snapshot: &NodesSnapshot,
+    if_none_match: &IfNoneMatch,
+    semver_compatibility: Option<&String>,
+) -> Conditional<Json<CachedNodesResponse<SkimmedNode>>> {
+    let etag = format!("\"{}\"", snapshot.revision());
+    Conditional::new(if_none_match, etag, || {
+        Json(CachedNodesResponse {
+            refreshed_at: snapshot.refreshed_at.into(),
+            nodes: snapshot
+                .compatible_nodes(semver_compatibility)
+                .cloned()
+                .collect(),
+        })
+    })
+}
+
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/gateways/skimmed?<semver_compatibility>")]
 pub async fn gateways_basic(
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+    history: &State<SkimmedNodesHistory>,
+    if_none_match: IfNoneMatch,
+    semver_compatibility: Option<String>,
+) -> Result<Conditional<Json<CachedNodesResponse<SkimmedNode>>>, ErrorResponse> {
+    let snapshot = gateways_snapshot(status_cache, describe_cache, history).await?;
+    Ok(skimmed_response(
+        &snapshot,
+        &if_none_match,
+        semver_compatibility.as_ref(),
+    ))
+}
+
+/// Returns changes to the set of gateways since the provided revision.
+/// If the revision is not provided or is no longer known, the full set is returned instead.
+#[openapi(tag = "Unstable Nym Nodes")]
+#[get("/gateways/skimmed/delta?<since>&<semver_compatibility>")]
+pub async fn gateways_basic_delta(
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+    history: &State<SkimmedNodesHistory>,
+    since: Option<String>,
+    semver_compatibility: Option<String>,
+) -> Result<Json<NodesDelta<SkimmedNode>>, ErrorResponse> {
+    let snapshot = gateways_snapshot(status_cache, describe_cache, history).await?;
+    Ok(Json(
+        history
+            .gateways
+            .delta(&snapshot, since.as_deref(), semver_compatibility.as_ref())
+            .await,
+    ))
+}
+
+#[openapi(tag = "Unstable Nym Nodes")]
+#[get("/gateways/semi-skimmed?<semver_compatibility>")]
+pub async fn gateways_expanded(
     status_cache: &State<NodeStatusCache>,
     describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
+) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
     let gateways_cache = status_cache
         .gateways_cache()
         .await
@@ -114,25 +239,14 @@ pub async fn gateways_basic(
             Status::InternalServerError,
         ))?;
 
-    if gateways_cache.is_empty() {
-        return Ok(Json(CachedNodesResponse {
-            refreshed_at: gateways_cache.timestamp().into(),
-            nodes: vec![],
-        }));
-    }
-
-    // if the self describe cache is unavailable don't try to use self-describe data
-    let Ok(self_descriptions) = describe_cache.get().await else {
-        return Ok(Json(CachedNodesResponse {
-            refreshed_at: gateways_cache.timestamp().into(),
-            nodes: gateways_cache.values().map(Into::into).collect(),
-        }));
+    // without the self-described data we don't know any noise keys, but we can still
+    // return the basic information
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(descriptions) => min(gateways_cache.timestamp(), descriptions.timestamp()),
+        None => gateways_cache.timestamp(),
     };
 
-    let refreshed_at = min(gateways_cache.timestamp(), self_descriptions.timestamp());
-
-    // the same comment holds as with `get_gateways_described`.
-    // this is inefficient and will have to get refactored with directory v3
     Ok(Json(CachedNodesResponse {
         refreshed_at: refreshed_at.into(),
         nodes: gateways_cache
@@ -148,26 +262,22 @@ pub async fn gateways_basic(
                 }
             })
             .map(|annotated_bond| {
-                SkimmedNode::from_described_gateway(
+                SemiSkimmedNode::from_described_gateway(
                     annotated_bond,
-                    self_descriptions.deref().get(annotated_bond.identity()),
+                    self_descriptions
+                        .as_ref()
+                        .and_then(|d| d.get_description(annotated_bond.identity())),
+                )
+                .with_latency(
+                    self_descriptions
+                        .as_ref()
+                        .and_then(|d| d.get_latency(annotated_bond.identity())),
                 )
             })
             .collect(),
     }))
 }
 
-#[openapi(tag = "Unstable Nym Nodes")]
-#[get("/gateways/semi-skimmed?<semver_compatibility>")]
-pub async fn gateways_expanded(
-    cache: &State<NodeStatusCache>,
-    semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
-}
-
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/gateways/full-fat?<semver_compatibility>")]
 pub async fn gateways_detailed(
@@ -179,21 +289,118 @@ pub async fn gateways_detailed(
     Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
 }
 
+pub(crate) async fn mixnodes_snapshot(
+    status_cache: &NodeStatusCache,
+    describe_cache: &SharedCache<DescribedNodes>,
+    history: &SkimmedNodesHistory,
+) -> Result<Arc<NodesSnapshot>, ErrorResponse> {
+    let mixnodes_cache = status_cache
+        .active_mixnodes_cache()
+        .await
+        .ok_or(ErrorResponse::new(
+            "could not obtain mixnodes cache",
+            Status::InternalServerError,
+        ))?;
+
+    // the self-described data is only used for the latency information,
+    // so if it's unavailable, just return the basic information
+    let self_descriptions = describe_cache.get().await.ok();
+    let (describe_revision, refreshed_at) = match &self_descriptions {
+        Some(descriptions) => (
+            Some(descriptions.revision()),
+            min(mixnodes_cache.timestamp(), descriptions.timestamp()),
+        ),
+        None => (None, mixnodes_cache.timestamp()),
+    };
+
+    let source_revisions = (mixnodes_cache.revision(), describe_revision);
+    Ok(history
+        .mixnodes
+        .latest(source_revisions, refreshed_at, || {
+            mixnodes_cache
+                .iter()
+                .map(|annotated_bond| {
+                    let node = SkimmedNode::from(annotated_bond).with_latency(
+                        self_descriptions
+                            .as_ref()
+                            .and_then(|d| d.get_latency(annotated_bond.identity_key())),
+                    );
+                    HistoricalNode::new(
+                        node,
+                        &annotated_bond
+                            .mixnode_details
+                            .bond_information
+                            .mix_node
+                            .version,
+                    )
+                })
+                .collect()
+        })
+        .await)
+}
+
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/mixnodes/skimmed?<semver_compatibility>")]
 pub async fn mixnodes_basic(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+    history: &State<SkimmedNodesHistory>,
+    if_none_match: IfNoneMatch,
+    semver_compatibility: Option<String>,
+) -> Result<Conditional<Json<CachedNodesResponse<SkimmedNode>>>, ErrorResponse> {
+    let snapshot = mixnodes_snapshot(status_cache, describe_cache, history).await?;
+    Ok(skimmed_response(
+        &snapshot,
+        &if_none_match,
+        semver_compatibility.as_ref(),
+    ))
+}
+
+/// Returns changes to the set of active mixnodes since the provided revision.
+/// If the revision is not provided or is no longer known, the full set is returned instead.
+#[openapi(tag = "Unstable Nym Nodes")]
+#[get("/mixnodes/skimmed/delta?<since>&<semver_compatibility>")]
+pub async fn mixnodes_basic_delta(
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+    history: &State<SkimmedNodesHistory>,
+    since: Option<String>,
+    semver_compatibility: Option<String>,
+) -> Result<Json<NodesDelta<SkimmedNode>>, ErrorResponse> {
+    let snapshot = mixnodes_snapshot(status_cache, describe_cache, history).await?;
+    Ok(Json(
+        history
+            .mixnodes
+            .delta(&snapshot, since.as_deref(), semver_compatibility.as_ref())
+            .await,
+    ))
+}
+
+#[openapi(tag = "Unstable Nym Nodes")]
+#[get("/mixnodes/semi-skimmed?<semver_compatibility>")]
+pub async fn mixnodes_expanded(
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
-    let mixnodes_cache = cache
+) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
+    let mixnodes_cache = status_cache
         .active_mixnodes_cache()
         .await
         .ok_or(ErrorResponse::new(
             "could not obtain mixnodes cache",
             Status::InternalServerError,
         ))?;
+
+    // without the self-described data we don't know any noise keys, but we can still
+    // return the basic information
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(descriptions) => min(mixnodes_cache.timestamp(), descriptions.timestamp()),
+        None => mixnodes_cache.timestamp(),
+    };
+
     Ok(Json(CachedNodesResponse {
-        refreshed_at: mixnodes_cache.timestamp().into(),
+        refreshed_at: refreshed_at.into(),
         nodes: mixnodes_cache
             .iter()
             .filter(|annotated_bond| {
@@ -210,22 +417,23 @@ pub async fn mixnodes_basic(
                     true
                 }
             })
-            .map(Into::into)
+            .map(|annotated_bond| {
+                SemiSkimmedNode::from_described_mixnode(
+                    annotated_bond,
+                    self_descriptions
+                        .as_ref()
+                        .and_then(|d| d.get_description(annotated_bond.identity_key())),
+                )
+                .with_latency(
+                    self_descriptions
+                        .as_ref()
+                        .and_then(|d| d.get_latency(annotated_bond.identity_key())),
+                )
+            })
             .collect(),
     }))
 }
 
-#[openapi(tag = "Unstable Nym Nodes")]
-#[get("/mixnodes/semi-skimmed?<semver_compatibility>")]
-pub async fn mixnodes_expanded(
-    cache: &State<NodeStatusCache>,
-    semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
-}
-
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/mixnodes/full-fat?<semver_compatibility>")]
 pub async fn mixnodes_detailed(
@@ -83,6 +83,10 @@ impl<T> From<Cache<T>> for CachedItem<T> {
 pub struct Cache<T> {
     value: T,
     as_at: OffsetDateTime,
+
+    // incremented on every update so that the consumers could cheaply tell whether the value
+    // has changed since they've last looked at it
+    revision: u64,
 }
 
 impl<T> Cache<T> {
@@ -91,6 +95,7 @@ impl<T> Cache<T> {
         Cache {
             value,
             as_at: OffsetDateTime::now_utc(),
+            revision: 0,
         }
     }
 
@@ -103,6 +108,7 @@ impl<T> Cache<T> {
         Cache {
             value: f(this.value),
             as_at: this.as_at,
+            revision: this.revision,
         }
     }
 
@@ -114,13 +120,15 @@ impl<T> Cache<T> {
         Cache {
             value: self.value.clone(),
             as_at: self.as_at,
+            revision: self.revision,
         }
     }
 
     // ugh. I hate to expose it, but it'd have broken pre-existing code
     pub(crate) fn unchecked_update(&mut self, value: T) {
         self.value = value;
-        self.as_at = OffsetDateTime::now_utc()
+        self.as_at = OffsetDateTime::now_utc();
+        self.revision += 1;
     }
 
     #[allow(dead_code)]
@@ -135,6 +143,10 @@ impl<T> Cache<T> {
         self.as_at
     }
 
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     #[allow(dead_code)]
     pub fn into_inner(self) -> T {
         self.value
@@ -157,6 +169,7 @@ where
         Cache {
             value: T::default(),
             as_at: OffsetDateTime::UNIX_EPOCH,
+            revision: 0,
         }
     }
 }
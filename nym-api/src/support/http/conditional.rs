@@ -0,0 +1,122 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use okapi::openapi3::Responses;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+use rocket_okapi::response::OpenApiResponderInner;
+use rocket_okapi::util::ensure_status_code_exists;
+use std::convert::Infallible;
+
+const IF_NONE_MATCH_HEADER: &str = "If-None-Match";
+const ETAG_HEADER: &str = "ETag";
+
+/// Request guard extracting the value of the `If-None-Match` header, if present.
+pub(crate) struct IfNoneMatch(Option<String>);
+
+impl IfNoneMatch {
+    /// Checks whether the provided (quoted) entity tag matches any of the tags sent by the client.
+    pub(crate) fn matches(&self, etag: &str) -> bool {
+        let Some(header) = &self.0 else {
+            return false;
+        };
+
+        header
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfNoneMatch {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(IfNoneMatch(
+            request
+                .headers()
+                .get_one(IF_NONE_MATCH_HEADER)
+                .map(ToString::to_string),
+        ))
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for IfNoneMatch {
+    fn from_request_input(
+        _gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::None)
+    }
+}
+
+/// Response tagged with an `ETag` that is replaced with an empty `304 Not Modified`
+/// if the client already has the current version of the resource.
+pub(crate) enum Conditional<T> {
+    Modified { value: T, etag: String },
+    NotModified { etag: String },
+}
+
+impl<T> Conditional<T> {
+    /// Wraps the value produced by the provided closure unless the entity tag matches
+    /// the `If-None-Match` header sent by the client.
+    pub(crate) fn new<F>(if_none_match: &IfNoneMatch, etag: String, value: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        if if_none_match.matches(&etag) {
+            Conditional::NotModified { etag }
+        } else {
+            Conditional::Modified {
+                value: value(),
+                etag,
+            }
+        }
+    }
+}
+
+impl<'r, 'o: 'r, T: Responder<'r, 'o>> Responder<'r, 'o> for Conditional<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            Conditional::Modified { value, etag } => Response::build_from(value.respond_to(req)?)
+                .raw_header(ETAG_HEADER, etag)
+                .ok(),
+            Conditional::NotModified { etag } => Response::build()
+                .status(Status::NotModified)
+                .raw_header(ETAG_HEADER, etag)
+                .ok(),
+        }
+    }
+}
+
+impl<T: OpenApiResponderInner> OpenApiResponderInner for Conditional<T> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = T::responses(gen)?;
+        ensure_status_code_exists(&mut responses, 304);
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_entity_tags() {
+        let etag = "\"1718000000-3\"";
+
+        assert!(!IfNoneMatch(None).matches(etag));
+        assert!(IfNoneMatch(Some("*".into())).matches(etag));
+        assert!(IfNoneMatch(Some(etag.into())).matches(etag));
+        assert!(IfNoneMatch(Some(format!("W/{etag}"))).matches(etag));
+        assert!(IfNoneMatch(Some(format!("\"foo\", {etag}"))).matches(etag));
+        assert!(!IfNoneMatch(Some("\"1718000000-2\"".into())).matches(etag));
+        assert!(!IfNoneMatch(Some("1718000000-3".into())).matches(etag));
+    }
+}
@@ -6,10 +6,12 @@ use crate::coconut::client::Client;
 use crate::coconut::{self, comm::QueryCommunicationChannel};
 use crate::network::models::NetworkDetails;
 use crate::network::network_routes;
+use crate::network_events::{network_events_routes, NetworkEvents};
 use crate::node_describe_cache::DescribedNodes;
 use crate::node_status_api::routes::unstable;
 use crate::node_status_api::{self, NodeStatusCache};
 use crate::nym_contract_cache::cache::NymContractCache;
+use crate::nym_nodes::history::SkimmedNodesHistory;
 use crate::nym_nodes::nym_node_routes_next;
 use crate::status::{api_status_routes, ApiStatusState, SignerState};
 use crate::support::caching::cache::SharedCache;
@@ -25,6 +27,7 @@ use rocket_cors::{AllowedHeaders, AllowedOrigins, Cors};
 use rocket_okapi::mount_endpoints_and_merged_docs;
 use rocket_okapi::swagger_ui::make_swagger_ui;
 
+pub(crate) mod conditional;
 pub(crate) mod helpers;
 pub(crate) mod openapi;
 
@@ -60,6 +63,9 @@ pub(crate) async fn setup_rocket(
     let rocket = rocket
         .manage(network_details)
         .manage(SharedCache::<DescribedNodes>::new())
+        .manage(SkimmedNodesHistory::new())
+        .manage(NetworkEvents::new())
+        .mount("/v1/unstable", network_events_routes())
         .mount("/swagger", make_swagger_ui(&openapi::get_docs()))
         .attach(setup_cors()?)
         .attach(NymContractCache::stage())
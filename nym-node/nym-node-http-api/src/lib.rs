@@ -16,7 +16,7 @@ pub mod router;
 pub mod state;
 
 pub use error::NymNodeHttpError;
-pub use router::{api, landing_page, Config, NymNodeRouter};
+pub use router::{admin::AdminRouter, api, landing_page, Config, NymNodeRouter};
 
 // I guess this wasn't really meant to be extracted into separate type haha
 type InnerService = IntoMakeServiceWithConnectInfo<Router, SocketAddr>;
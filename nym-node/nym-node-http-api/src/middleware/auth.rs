@@ -0,0 +1,33 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::state::admin::AdminAppState;
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::TypedHeader;
+use headers::{authorization::Bearer, Authorization};
+use subtle::ConstantTimeEq;
+
+/// Rejects all requests that do not present the admin access token.
+pub async fn require_admin_token(
+    State(state): State<AdminAppState>,
+    authorization: Option<TypedHeader<Authorization<Bearer>>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(TypedHeader(authorization)) = authorization else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    // compare in constant time so that the token couldn't be guessed from the response times
+    let token = authorization.token().as_bytes();
+    if token.is_empty() || !bool::from(token.ct_eq(state.access_token.as_bytes())) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(next.run(req).await)
+}
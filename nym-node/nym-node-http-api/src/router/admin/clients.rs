@@ -0,0 +1,34 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::api::{FormattedResponse, OutputParams};
+use crate::router::types::RequestError;
+use crate::state::admin::{AdminAppState, AdminRequest};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use nym_node_requests::api::v1::admin::models::ActiveClients;
+
+/// Returns all clients currently connected to this gateway.
+pub(crate) async fn active_clients(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<ActiveClientsResponse, RequestError> {
+    let output = output.output.unwrap_or_default();
+    let clients = state
+        .request(|response| AdminRequest::ListClients { response })
+        .await?;
+    Ok(output.to_response(clients))
+}
+
+pub type ActiveClientsResponse = FormattedResponse<ActiveClients>;
+
+/// Forcefully terminates the connection of the specified client.
+pub(crate) async fn disconnect_client(
+    Path(address): Path<String>,
+    State(state): State<AdminAppState>,
+) -> Result<StatusCode, RequestError> {
+    state
+        .request(|response| AdminRequest::DisconnectClient { address, response })
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,50 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::api::{FormattedResponse, OutputParams};
+use crate::router::types::RequestError;
+use crate::state::admin::{AdminAppState, AdminRequest};
+use axum::extract::{Query, State};
+use nym_node_requests::api::v1::admin::models::DrainState;
+
+/// Returns whether the node is currently being drained alongside the number of still connected clients.
+pub(crate) async fn drain_state(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<DrainStateResponse, RequestError> {
+    let output = output.output.unwrap_or_default();
+    let drain_state = state
+        .request(|response| AdminRequest::GetDrainState { response })
+        .await?;
+    Ok(output.to_response(drain_state))
+}
+
+/// Stops accepting new clients. Already established connections are not affected.
+pub(crate) async fn start_draining(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<DrainStateResponse, RequestError> {
+    set_draining(state, output, true).await
+}
+
+/// Starts accepting new clients again.
+pub(crate) async fn stop_draining(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<DrainStateResponse, RequestError> {
+    set_draining(state, output, false).await
+}
+
+async fn set_draining(
+    state: AdminAppState,
+    output: OutputParams,
+    draining: bool,
+) -> Result<DrainStateResponse, RequestError> {
+    let output = output.output.unwrap_or_default();
+    let drain_state = state
+        .request(|response| AdminRequest::SetDraining { draining, response })
+        .await?;
+    Ok(output.to_response(drain_state))
+}
+
+pub type DrainStateResponse = FormattedResponse<DrainState>;
@@ -0,0 +1,22 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::api::{FormattedResponse, OutputParams};
+use crate::router::types::RequestError;
+use crate::state::admin::{AdminAppState, AdminRequest};
+use axum::extract::{Query, State};
+use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
+
+/// Retrieves the exit policy from its upstream again and starts enforcing it immediately.
+pub(crate) async fn reload_exit_policy(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<ReloadExitPolicyResponse, RequestError> {
+    let output = output.output.unwrap_or_default();
+    let policy = state
+        .request(|response| AdminRequest::ReloadExitPolicy { response })
+        .await?;
+    Ok(output.to_response(policy))
+}
+
+pub type ReloadExitPolicyResponse = FormattedResponse<UsedExitPolicy>;
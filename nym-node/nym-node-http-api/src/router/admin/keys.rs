@@ -0,0 +1,23 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::api::{FormattedResponse, OutputParams};
+use crate::router::types::RequestError;
+use crate::state::admin::{AdminAppState, AdminRequest};
+use axum::extract::{Query, State};
+use nym_node_requests::api::SignedHostInformation;
+
+/// Reloads the node keys from the disk and announces them in the signed host information
+/// exposed by the public API.
+pub(crate) async fn announce_keys(
+    State(state): State<AdminAppState>,
+    Query(output): Query<OutputParams>,
+) -> Result<AnnounceKeysResponse, RequestError> {
+    let output = output.output.unwrap_or_default();
+    let host_information = state
+        .request(|response| AdminRequest::AnnounceKeys { response })
+        .await?;
+    Ok(output.to_response(host_information))
+}
+
+pub type AnnounceKeysResponse = FormattedResponse<SignedHostInformation>;
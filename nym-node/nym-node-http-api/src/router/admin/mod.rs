@@ -0,0 +1,240 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymNodeHttpError;
+use crate::middleware::{auth, logging};
+use crate::state::admin::AdminAppState;
+use crate::NymNodeHTTPServer;
+use axum::routing::{delete, get, post};
+use axum::Router;
+use nym_node_requests::routes;
+use nym_node_requests::routes::api::v1::admin;
+use std::net::SocketAddr;
+
+pub mod clients;
+pub mod drain;
+pub mod exit_policy;
+pub mod keys;
+
+/// Router exposing operator-only endpoints for controlling the running node.
+/// It's meant to be served on its own listener, separate from the public API,
+/// and every request has to present the configured bearer token.
+pub struct AdminRouter {
+    inner: Router,
+}
+
+impl AdminRouter {
+    pub fn new(state: AdminAppState) -> AdminRouter {
+        let admin_routes = Router::new()
+            .route(
+                admin::EXIT_POLICY_RELOAD,
+                post(exit_policy::reload_exit_policy),
+            )
+            .route(
+                admin::DRAIN,
+                get(drain::drain_state)
+                    .post(drain::start_draining)
+                    .delete(drain::stop_draining),
+            )
+            .route(admin::CLIENTS, get(clients::active_clients))
+            .route(
+                &format!("{}/:address", admin::CLIENTS),
+                delete(clients::disconnect_client),
+            )
+            .route(admin::KEYS_ANNOUNCE, post(keys::announce_keys));
+
+        AdminRouter {
+            inner: Router::new()
+                .nest(&routes::api::v1::admin_absolute(), admin_routes)
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    auth::require_admin_token,
+                ))
+                .layer(axum::middleware::from_fn(logging::logger))
+                .with_state(state),
+        }
+    }
+
+    pub async fn build_server(
+        self,
+        bind_address: &SocketAddr,
+    ) -> Result<NymNodeHTTPServer, NymNodeHttpError> {
+        let listener = tokio::net::TcpListener::bind(bind_address)
+            .await
+            .map_err(|source| NymNodeHttpError::HttpBindFailure {
+                bind_address: *bind_address,
+                source,
+            })?;
+
+        let axum_server = axum::serve(
+            listener,
+            self.inner
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        );
+
+        Ok(NymNodeHTTPServer::new(axum_server))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::admin::{AdminRequest, AdminRequestError};
+    use axum::body::Body;
+    use axum::extract::connect_info::MockConnectInfo;
+    use axum::http::{header, Method, Request, StatusCode};
+    use std::mem::discriminant;
+    use tokio::sync::{mpsc, oneshot};
+    use tower::ServiceExt;
+
+    const ACCESS_TOKEN: &str = "super-secret-token";
+
+    // the node rejects every request so that we could tell it apart from being unauthorized
+    fn reject<T>(response: oneshot::Sender<Result<T, AdminRequestError>>) {
+        response
+            .send(Err(AdminRequestError::UnsupportedOperation))
+            .ok();
+    }
+
+    fn dummy_response<T>() -> oneshot::Sender<Result<T, AdminRequestError>> {
+        oneshot::channel().0
+    }
+
+    // every admin route alongside the request it's expected to forward to the node
+    fn admin_routes() -> Vec<(Method, String, AdminRequest)> {
+        vec![
+            (
+                Method::POST,
+                admin::exit_policy_reload_absolute(),
+                AdminRequest::ReloadExitPolicy {
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::GET,
+                admin::drain_absolute(),
+                AdminRequest::GetDrainState {
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::POST,
+                admin::drain_absolute(),
+                AdminRequest::SetDraining {
+                    draining: true,
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::DELETE,
+                admin::drain_absolute(),
+                AdminRequest::SetDraining {
+                    draining: false,
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::GET,
+                admin::clients_absolute(),
+                AdminRequest::ListClients {
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::DELETE,
+                format!("{}/some-client", admin::clients_absolute()),
+                AdminRequest::DisconnectClient {
+                    address: "some-client".to_string(),
+                    response: dummy_response(),
+                },
+            ),
+            (
+                Method::POST,
+                admin::keys_announce_absolute(),
+                AdminRequest::AnnounceKeys {
+                    response: dummy_response(),
+                },
+            ),
+        ]
+    }
+
+    fn admin_router() -> (Router, mpsc::UnboundedReceiver<AdminRequest>) {
+        let (requests_tx, requests_rx) = mpsc::unbounded_channel();
+        let router = AdminRouter::new(AdminAppState::new(ACCESS_TOKEN, requests_tx))
+            .inner
+            .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 1234))));
+        (router, requests_rx)
+    }
+
+    async fn call(router: &Router, method: Method, uri: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let request = request.body(Body::empty()).unwrap();
+
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn requests_without_valid_token_are_rejected() {
+        let (router, mut requests) = admin_router();
+
+        for (method, uri, _) in admin_routes() {
+            for token in [None, Some(""), Some("wrong-token"), Some("super-secret")] {
+                assert_eq!(
+                    call(&router, method.clone(), &uri, token).await,
+                    StatusCode::UNAUTHORIZED,
+                    "{method} {uri} with token {token:?}"
+                );
+            }
+        }
+
+        // none of them should have ever reached the node
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn requests_with_valid_token_reach_the_node() {
+        let (router, mut requests) = admin_router();
+
+        for (method, uri, expected) in admin_routes() {
+            let call = tokio::spawn({
+                let router = router.clone();
+                let (method, uri) = (method.clone(), uri.clone());
+                async move { call(&router, method, &uri, Some(ACCESS_TOKEN)).await }
+            });
+
+            let request = requests.recv().await.unwrap();
+            assert_eq!(discriminant(&request), discriminant(&expected));
+            match (&request, &expected) {
+                (
+                    AdminRequest::SetDraining { draining, .. },
+                    AdminRequest::SetDraining {
+                        draining: expected, ..
+                    },
+                ) => assert_eq!(draining, expected),
+                (
+                    AdminRequest::DisconnectClient { address, .. },
+                    AdminRequest::DisconnectClient {
+                        address: expected, ..
+                    },
+                ) => assert_eq!(address, expected),
+                _ => (),
+            }
+            match request {
+                AdminRequest::ReloadExitPolicy { response } => reject(response),
+                AdminRequest::GetDrainState { response } => reject(response),
+                AdminRequest::SetDraining { response, .. } => reject(response),
+                AdminRequest::ListClients { response } => reject(response),
+                AdminRequest::DisconnectClient { response, .. } => reject(response),
+                AdminRequest::AnnounceKeys { response } => reject(response),
+            }
+            assert_eq!(
+                call.await.unwrap(),
+                StatusCode::NOT_IMPLEMENTED,
+                "{method} {uri}"
+            );
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::router::api::{FormattedResponse, OutputParams};
+use crate::state::ip_packet_router::IpPacketRouterAppState;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use nym_node_requests::api::v1::ip_packet_router::models::IpPoolUtilisation;
+
+/// Returns utilisation of the address pool used for assigning IPs to the clients.
+#[utoipa::path(
+    get,
+    path = "/ip-pool",
+    context_path = "/api/v1/ip-packet-router",
+    tag = "IP Packet Router",
+    responses(
+        (status = 501, description = "the ip packet router is not running on this node"),
+        (status = 200, content(
+            ("application/json" = IpPoolUtilisation),
+            ("application/yaml" = IpPoolUtilisation)
+        ))
+    ),
+    params(OutputParams)
+)]
+pub(crate) async fn ip_pool_utilisation(
+    Query(output): Query<OutputParams>,
+    State(state): State<IpPacketRouterAppState>,
+) -> Result<IpPoolUtilisationResponse, StatusCode> {
+    let utilisation = state
+        .ip_pool
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::NOT_IMPLEMENTED)?;
+    let output = output.output.unwrap_or_default();
+    Ok(output.to_response(utilisation))
+}
+
+pub type IpPoolUtilisationResponse = FormattedResponse<IpPoolUtilisation>;
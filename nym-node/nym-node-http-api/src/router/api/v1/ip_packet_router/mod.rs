@@ -1,10 +1,15 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::api::v1::ip_packet_router::ip_pool::ip_pool_utilisation;
+use crate::state::ip_packet_router::IpPacketRouterAppState;
+use axum::extract::FromRef;
 use axum::routing::get;
 use axum::Router;
 use nym_node_requests::api::v1::ip_packet_router::models;
+use nym_node_requests::routes::api::v1::ip_packet_router;
 
+pub mod ip_pool;
 pub mod root;
 
 #[derive(Debug, Clone, Default)]
@@ -12,12 +17,18 @@ pub struct Config {
     pub details: Option<models::IpPacketRouter>,
 }
 
-pub(crate) fn routes<S: Send + Sync + 'static + Clone>(config: Config) -> Router<S> {
-    Router::new().route(
-        "/",
-        get({
-            let ip_packet_router_details = config.details;
-            move |query| root::root_ip_packet_router(ip_packet_router_details, query)
-        }),
-    )
+pub(crate) fn routes<S>(config: Config) -> Router<S>
+where
+    S: Send + Sync + 'static + Clone,
+    IpPacketRouterAppState: FromRef<S>,
+{
+    Router::new()
+        .route(
+            "/",
+            get({
+                let ip_packet_router_details = config.details;
+                move |query| root::root_ip_packet_router(ip_packet_router_details, query)
+            }),
+        )
+        .route(ip_packet_router::IP_POOL, get(ip_pool_utilisation))
 }
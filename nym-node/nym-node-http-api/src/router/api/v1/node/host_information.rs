@@ -4,6 +4,32 @@
 use crate::api::{FormattedResponse, OutputParams};
 use axum::extract::Query;
 use nym_node_requests::api::v1::node::models::SignedHostInformation;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+
+/// Host information of this node that might get re-signed, for example after its keys got rotated.
+#[derive(Debug, Clone)]
+pub struct SharedHostInformation {
+    inner: Arc<RwLock<SignedHostInformation>>,
+}
+
+impl From<SignedHostInformation> for SharedHostInformation {
+    fn from(host_information: SignedHostInformation) -> Self {
+        SharedHostInformation {
+            inner: Arc::new(RwLock::new(host_information)),
+        }
+    }
+}
+
+impl SharedHostInformation {
+    pub async fn read(&self) -> RwLockReadGuard<'_, SignedHostInformation> {
+        self.inner.read().await
+    }
+
+    pub async fn update(&self, host_information: SignedHostInformation) {
+        *self.inner.write().await = host_information
+    }
+}
 
 /// Returns host information of this node.
 #[utoipa::path(
@@ -20,11 +46,11 @@ use nym_node_requests::api::v1::node::models::SignedHostInformation;
     params(OutputParams)
 )]
 pub(crate) async fn host_information(
-    host_information: SignedHostInformation,
+    host_information: SharedHostInformation,
     Query(output): Query<OutputParams>,
 ) -> HostInformationResponse {
     let output = output.output.unwrap_or_default();
-    output.to_response(host_information)
+    output.to_response(host_information.read().await.clone())
 }
 
 pub type HostInformationResponse = FormattedResponse<SignedHostInformation>;
@@ -5,7 +5,7 @@ use crate::api::v1::node::auxiliary::auxiliary;
 use crate::api::v1::node::build_information::build_information;
 use crate::api::v1::node::description::description;
 use crate::api::v1::node::hardware::host_system;
-use crate::api::v1::node::host_information::host_information;
+use crate::api::v1::node::host_information::{host_information, SharedHostInformation};
 use crate::api::v1::node::roles::roles;
 use axum::routing::get;
 use axum::Router;
@@ -22,7 +22,7 @@ pub mod roles;
 #[derive(Debug, Clone)]
 pub struct Config {
     pub build_information: models::BinaryBuildInformationOwned,
-    pub host_information: models::SignedHostInformation,
+    pub host_information: SharedHostInformation,
     pub system_info: Option<models::HostSystem>,
     pub roles: models::NodeRoles,
     pub description: models::NodeDescription,
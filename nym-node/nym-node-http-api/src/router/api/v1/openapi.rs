@@ -35,6 +35,7 @@ use utoipa_swagger_ui::SwaggerUi;
         api::v1::network_requester::root::root_network_requester,
         api::v1::network_requester::exit_policy::node_exit_policy,
         api::v1::ip_packet_router::root::root_ip_packet_router,
+        api::v1::ip_packet_router::ip_pool::ip_pool_utilisation,
     ),
     components(
         schemas(
@@ -77,6 +78,7 @@ use utoipa_swagger_ui::SwaggerUi;
             api_requests::v1::network_requester::exit_policy::models::PortRange,
             api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy,
             api_requests::v1::ip_packet_router::models::IpPacketRouter,
+            api_requests::v1::ip_packet_router::models::IpPoolUtilisation,
         ),
         responses(RequestError),
     ),
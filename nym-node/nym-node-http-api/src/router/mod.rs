@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub use crate::api::v1::gateway::client_interfaces::wireguard::WireguardAppState;
+pub use crate::api::v1::node::host_information::SharedHostInformation;
 use crate::error::NymNodeHttpError;
 use crate::middleware::logging;
 use crate::state::AppState;
@@ -16,12 +17,12 @@ use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExit
 use nym_node_requests::api::v1::network_requester::models::NetworkRequester;
 use nym_node_requests::api::v1::node::models;
 use nym_node_requests::api::v1::node::models::{AuxiliaryDetails, HostSystem, NodeDescription};
-use nym_node_requests::api::SignedHostInformation;
 use nym_node_requests::routes;
 use std::net::SocketAddr;
 use std::path::Path;
 use tracing::warn;
 
+pub mod admin;
 pub mod api;
 pub mod landing_page;
 pub mod types;
@@ -35,7 +36,7 @@ pub struct Config {
 impl Config {
     pub fn new(
         build_information: models::BinaryBuildInformationOwned,
-        host_information: SignedHostInformation,
+        host_information: impl Into<SharedHostInformation>,
     ) -> Self {
         Config {
             landing: Default::default(),
@@ -43,7 +44,7 @@ impl Config {
                 v1_config: api::v1::Config {
                     node: api::v1::node::Config {
                         build_information,
-                        host_information,
+                        host_information: host_information.into(),
                         system_info: None,
                         roles: Default::default(),
                         description: Default::default(),
@@ -0,0 +1,116 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::router::types::RequestError;
+use axum::http::StatusCode;
+use nym_node_requests::api::v1::admin::models::{ActiveClients, DrainState};
+use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
+use nym_node_requests::api::SignedHostInformation;
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+
+pub type AdminRequestSender = mpsc::UnboundedSender<AdminRequest>;
+pub type AdminRequestReceiver = mpsc::UnboundedReceiver<AdminRequest>;
+pub type AdminResponder<T> = oneshot::Sender<Result<T, AdminRequestError>>;
+
+/// Requests issued by the admin API that have to be fulfilled by the node itself.
+#[derive(Debug)]
+pub enum AdminRequest {
+    /// Retrieve the exit policy from its upstream again and start using it.
+    ReloadExitPolicy {
+        response: AdminResponder<UsedExitPolicy>,
+    },
+
+    GetDrainState {
+        response: AdminResponder<DrainState>,
+    },
+
+    /// Start (or stop) rejecting new clients whilst keeping the existing connections alive.
+    SetDraining {
+        draining: bool,
+        response: AdminResponder<DrainState>,
+    },
+
+    ListClients {
+        response: AdminResponder<ActiveClients>,
+    },
+
+    DisconnectClient {
+        address: String,
+        response: AdminResponder<()>,
+    },
+
+    /// Reload the node keys from the disk and announce them in the signed host information.
+    AnnounceKeys {
+        response: AdminResponder<SignedHostInformation>,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum AdminRequestError {
+    #[error("this operation is not supported in the current mode of the node")]
+    UnsupportedOperation,
+
+    #[error("'{address}' is not a valid client address")]
+    MalformedClientAddress { address: String },
+
+    #[error("client {address} is not connected to this node")]
+    UnknownClient { address: String },
+
+    #[error("{message}")]
+    Failure { message: String },
+}
+
+impl AdminRequestError {
+    fn status(&self) -> StatusCode {
+        match self {
+            AdminRequestError::UnsupportedOperation => StatusCode::NOT_IMPLEMENTED,
+            AdminRequestError::MalformedClientAddress { .. } => StatusCode::BAD_REQUEST,
+            AdminRequestError::UnknownClient { .. } => StatusCode::NOT_FOUND,
+            AdminRequestError::Failure { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<AdminRequestError> for RequestError {
+    fn from(err: AdminRequestError) -> Self {
+        let status = err.status();
+        RequestError::from_err(err, status)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminAppState {
+    pub(crate) access_token: String,
+
+    requests: AdminRequestSender,
+}
+
+impl AdminAppState {
+    pub fn new(access_token: impl Into<String>, requests: AdminRequestSender) -> Self {
+        AdminAppState {
+            access_token: access_token.into(),
+            requests,
+        }
+    }
+
+    /// Forwards the request to the node and waits for its response.
+    pub(crate) async fn request<T>(
+        &self,
+        request: impl FnOnce(AdminResponder<T>) -> AdminRequest,
+    ) -> Result<T, RequestError> {
+        let unavailable = || {
+            RequestError::new(
+                "the node is not processing any admin requests",
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+        };
+
+        let (response_tx, response_rx) = oneshot::channel();
+        self.requests
+            .send(request(response_tx))
+            .map_err(|_| unavailable())?;
+
+        Ok(response_rx.await.map_err(|_| unavailable())??)
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::state::AppState;
+use axum::extract::FromRef;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+pub use nym_node_requests::api::v1::ip_packet_router::models::IpPoolUtilisation;
+
+#[derive(Clone, Debug, Default)]
+pub struct SharedIpPoolStats {
+    inner: Arc<RwLock<Option<IpPoolUtilisation>>>,
+}
+
+impl SharedIpPoolStats {
+    pub fn new() -> SharedIpPoolStats {
+        SharedIpPoolStats::default()
+    }
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, Option<IpPoolUtilisation>> {
+        self.inner.read().await
+    }
+
+    pub async fn write(&self) -> RwLockWriteGuard<'_, Option<IpPoolUtilisation>> {
+        self.inner.write().await
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IpPacketRouterAppState {
+    pub(crate) ip_pool: SharedIpPoolStats,
+}
+
+impl FromRef<AppState> for IpPacketRouterAppState {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.ip_packet_router.clone()
+    }
+}
@@ -1,9 +1,12 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::state::ip_packet_router::{IpPacketRouterAppState, SharedIpPoolStats};
 use crate::state::metrics::{MetricsAppState, SharedMixingStats, SharedVerlocStats};
 use tokio::time::Instant;
 
+pub mod admin;
+pub mod ip_packet_router;
 pub mod metrics;
 
 #[derive(Debug, Clone)]
@@ -11,6 +14,8 @@ pub struct AppState {
     pub(crate) startup_time: Instant,
 
     pub(crate) metrics: MetricsAppState,
+
+    pub(crate) ip_packet_router: IpPacketRouterAppState,
 }
 
 impl AppState {
@@ -23,6 +28,7 @@ impl AppState {
             // also no.
             startup_time: Instant::now(),
             metrics: Default::default(),
+            ip_packet_router: Default::default(),
         }
     }
 
@@ -38,6 +44,12 @@ impl AppState {
         self
     }
 
+    #[must_use]
+    pub fn with_ip_pool_stats(mut self, ip_pool_stats: SharedIpPoolStats) -> Self {
+        self.ip_packet_router.ip_pool = ip_pool_stats;
+        self
+    }
+
     #[must_use]
     pub fn with_metrics_key(mut self, bearer_token: impl Into<Option<String>>) -> Self {
         self.metrics.prometheus_access_token = bearer_token.into();
@@ -12,6 +12,7 @@ use nym_wireguard_types::{ClientMessage, ClientRegistrationResponse};
 
 use crate::api::v1::health::models::NodeHealth;
 use crate::api::v1::ip_packet_router::models::IpPacketRouter;
+use crate::api::v1::metrics::models::VerlocStats;
 use crate::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use crate::api::v1::network_requester::models::NetworkRequester;
 pub use nym_http_api_client::Client;
@@ -50,6 +51,11 @@ pub trait NymNodeApiClientExt: ApiClient {
         .await
     }
 
+    async fn get_verloc_stats(&self) -> Result<VerlocStats, NymNodeApiClientError> {
+        self.get_json_from(routes::api::v1::metrics::verloc_absolute())
+            .await
+    }
+
     async fn get_network_requester(&self) -> Result<NetworkRequester, NymNodeApiClientError> {
         self.get_json_from(routes::api::v1::network_requester_absolute())
             .await
@@ -0,0 +1,41 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveClientKind {
+    /// Client connected to the gateway via a websocket.
+    Remote,
+
+    /// Service provider running inside the node itself.
+    Embedded,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ActiveClient {
+    /// Base58-encoded destination address of the client.
+    pub address: String,
+
+    pub kind: ActiveClientKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ActiveClients {
+    pub clients: Vec<ActiveClient>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DrainState {
+    /// Indicates whether the node has stopped accepting new clients.
+    pub draining: bool,
+
+    /// Number of clients still connected to the node.
+    pub active_clients: usize,
+}
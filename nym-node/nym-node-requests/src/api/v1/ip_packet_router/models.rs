@@ -16,3 +16,25 @@ pub struct IpPacketRouter {
     /// Nym address of this ip packet router.
     pub address: String,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IpPoolUtilisation {
+    /// IPv4 subnet from which addresses are assigned to the clients.
+    pub ipv4_subnet: String,
+
+    /// IPv6 subnet from which addresses are assigned to the clients.
+    pub ipv6_subnet: String,
+
+    /// Total number of address pairs that can be assigned to the clients.
+    pub capacity: u64,
+
+    /// Number of address pairs assigned to currently connected clients.
+    pub active: u64,
+
+    /// Number of address pairs still reserved for recently disconnected clients.
+    pub leased: u64,
+
+    /// Number of allocations (either active or leased) explicitly requested by the clients.
+    pub static_allocations: u64,
+}
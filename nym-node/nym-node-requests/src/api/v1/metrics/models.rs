@@ -133,19 +133,19 @@ impl Ord for VerlocNodeResult {
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VerlocMeasurement {
     /// Minimum RTT duration it took to receive an echo packet.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub minimum: Duration,
 
     /// Average RTT duration it took to receive the echo packets.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub mean: Duration,
 
     /// Maximum RTT duration it took to receive an echo packet.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub maximum: Duration,
 
     /// The standard deviation of the RTT duration it took to receive the echo packets.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub standard_deviation: Duration,
 }
 
@@ -276,4 +276,18 @@ mod tests {
         let expected_sorted = vec![low_min, higher_min, no_measurement, no_measurement];
         assert_eq!(expected_sorted, vec_verloc);
     }
+
+    #[test]
+    fn verloc_measurement_serde_roundtrip() {
+        let measurement = VerlocMeasurement {
+            minimum: Duration::from_micros(42_123),
+            mean: Duration::from_millis(43),
+            maximum: Duration::from_secs(1),
+            standard_deviation: Duration::from_micros(450),
+        };
+
+        let serialized = serde_json::to_string(&measurement).unwrap();
+        let deserialized: VerlocMeasurement = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(measurement, deserialized);
+    }
 }
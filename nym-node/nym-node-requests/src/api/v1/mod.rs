@@ -1,6 +1,7 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod admin;
 pub mod gateway;
 pub mod health;
 pub mod ip_packet_router;
@@ -180,4 +180,9 @@ pub struct AuxiliaryDetails {
     // make sure to include the default deserialisation as this field hasn't existed when the struct was first created
     #[serde(default)]
     pub accepted_toc: bool,
+
+    /// Port on which the node is listening for the verloc echo packets.
+    /// Gateways don't announce it anywhere else, as opposed to mixnodes which include it in their bonds.
+    #[serde(default)]
+    pub announced_verloc_port: Option<u16>,
 }
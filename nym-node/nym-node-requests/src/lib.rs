@@ -37,6 +37,7 @@ pub mod routes {
             pub const HEALTH: &str = "/health";
             pub const SWAGGER: &str = "/swagger";
 
+            pub const ADMIN: &str = "/admin";
             pub const GATEWAY: &str = "/gateway";
             pub const MIXNODE: &str = "/mixnode";
             pub const METRICS: &str = "/metrics";
@@ -52,6 +53,7 @@ pub mod routes {
             absolute_route!(description_absolute, v1_absolute(), NODE_DESCRIPTION);
             absolute_route!(auxiliary_absolute, v1_absolute(), AUXILIARY);
 
+            absolute_route!(admin_absolute, v1_absolute(), ADMIN);
             absolute_route!(gateway_absolute, v1_absolute(), GATEWAY);
             absolute_route!(mixnode_absolute, v1_absolute(), MIXNODE);
             absolute_route!(metrics_absolute, v1_absolute(), METRICS);
@@ -71,6 +73,24 @@ pub mod routes {
                 absolute_route!(prometheus_absolute, metrics_absolute(), PROMETHEUS);
             }
 
+            pub mod admin {
+                use super::*;
+
+                pub const EXIT_POLICY_RELOAD: &str = "/exit-policy/reload";
+                pub const DRAIN: &str = "/drain";
+                pub const CLIENTS: &str = "/clients";
+                pub const KEYS_ANNOUNCE: &str = "/keys/announce";
+
+                absolute_route!(
+                    exit_policy_reload_absolute,
+                    admin_absolute(),
+                    EXIT_POLICY_RELOAD
+                );
+                absolute_route!(drain_absolute, admin_absolute(), DRAIN);
+                absolute_route!(clients_absolute, admin_absolute(), CLIENTS);
+                absolute_route!(keys_announce_absolute, admin_absolute(), KEYS_ANNOUNCE);
+            }
+
             pub mod gateway {
                 use super::*;
 
@@ -124,7 +144,11 @@ pub mod routes {
             }
 
             pub mod ip_packet_router {
-                // use super::*;
+                use super::*;
+
+                pub const IP_POOL: &str = "/ip-pool";
+
+                absolute_route!(ip_pool_absolute, ip_packet_router_absolute(), IP_POOL);
             }
         }
     }
@@ -162,6 +186,16 @@ mod tests {
             routes::api::v1::gateway::client_interfaces::mixnet_websockets_absolute()
         );
 
+        assert_eq!("/api/v1/admin", routes::api::v1::admin_absolute());
+        assert_eq!(
+            "/api/v1/admin/exit-policy/reload",
+            routes::api::v1::admin::exit_policy_reload_absolute()
+        );
+        assert_eq!(
+            "/api/v1/admin/clients",
+            routes::api::v1::admin::clients_absolute()
+        );
+
         assert_eq!("/api/v1/mixnode", routes::api::v1::mixnode_absolute());
         assert_eq!(
             "/api/v1/network-requester",
@@ -171,5 +205,9 @@ mod tests {
             "/api/v1/ip-packet-router",
             routes::api::v1::ip_packet_router_absolute()
         );
+        assert_eq!(
+            "/api/v1/ip-packet-router/ip-pool",
+            routes::api::v1::ip_packet_router::ip_pool_absolute()
+        );
     }
 }
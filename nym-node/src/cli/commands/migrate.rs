@@ -227,6 +227,7 @@ async fn migrate_mixnode(mut args: Args) -> Result<(), NymNodeError> {
                 packet_forwarding_maximum_backoff: cfg.debug.packet_forwarding_maximum_backoff,
                 initial_connection_timeout: cfg.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: cfg.debug.maximum_connection_buffer_size,
+                replay_protection: cfg.debug.replay_protection,
                 ..Default::default()
             },
             ..Default::default()
@@ -392,6 +393,7 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                 packet_forwarding_maximum_backoff: cfg.debug.packet_forwarding_maximum_backoff,
                 initial_connection_timeout: cfg.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: cfg.debug.maximum_connection_buffer_size,
+                replay_protection: cfg.debug.replay_protection,
                 ..Default::default()
             },
         }))
@@ -409,6 +411,7 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                 bind_address: SocketAddr::new(ip, cfg.gateway.clients_port),
                 announce_ws_port: None,
                 announce_wss_port: cfg.gateway.clients_wss_port,
+                inbox: cfg.inbox,
                 debug: config::entry_gateway::Debug {
                     message_retrieval_limit: cfg.debug.message_retrieval_limit,
                 },
@@ -439,6 +442,10 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                         },
                     },
                     ip_packet_router: config::exit_gateway::IpPacketRouter {
+                        ip_pool: ipr_cfg
+                            .as_ref()
+                            .map(|c| c.ip_packet_router.ip_pool.clone())
+                            .unwrap_or_default(),
                         debug: config::exit_gateway::IpPacketRouterDebug {
                             enabled: cfg.ip_packet_router.enabled,
                             disable_poisson_rate: ipr_cfg
@@ -147,6 +147,29 @@ pub(crate) struct HttpArgs {
         env = NYMNODE_HTTP_EXPOSE_CRYPTO_HARDWARE_ARG
     )]
     pub(crate) expose_crypto_hardware: Option<bool>,
+
+    /// Specifies whether the admin API, allowing to control the running node, should be exposed.
+    /// default: false
+    #[clap(
+        long,
+        env = NYMNODE_HTTP_ADMIN_ENABLED_ARG
+    )]
+    pub(crate) admin_api_enabled: Option<bool>,
+
+    /// Socket address this node will use for binding its admin API.
+    /// default: `127.0.0.1:8081`
+    #[clap(
+        long,
+        env = NYMNODE_HTTP_ADMIN_BIND_ADDRESS_ARG
+    )]
+    pub(crate) admin_api_bind_address: Option<SocketAddr>,
+
+    /// Bearer token required for accessing any of the admin endpoints.
+    #[clap(
+        long,
+        env = NYMNODE_HTTP_ADMIN_ACCESS_TOKEN_ARG
+    )]
+    pub(crate) admin_api_access_token: Option<String>,
 }
 
 impl HttpArgs {
@@ -174,6 +197,15 @@ impl HttpArgs {
         if let Some(expose_crypto_hardware) = self.expose_crypto_hardware {
             section.expose_crypto_hardware = expose_crypto_hardware
         }
+        if let Some(admin_api_enabled) = self.admin_api_enabled {
+            section.admin.enabled = admin_api_enabled
+        }
+        if let Some(admin_api_bind_address) = self.admin_api_bind_address {
+            section.admin.bind_address = admin_api_bind_address
+        }
+        if let Some(admin_api_access_token) = self.admin_api_access_token {
+            section.admin.access_token = Some(admin_api_access_token)
+        }
         section
     }
 }
@@ -204,7 +236,7 @@ pub(crate) struct MixnetArgs {
     )]
     pub(crate) nyxd_urls: Option<Vec<Url>>,
 
-    /// Specifies whether this node should **NOT** use noise protocol in the connections (currently not implemented)
+    /// Specifies whether this node should **NOT** use noise protocol in the connections to other nodes.
     #[clap(
         hide = true,
         long,
@@ -38,6 +38,10 @@ pub struct EntryGatewayConfig {
     #[serde(deserialize_with = "de_maybe_port")]
     pub announce_wss_port: Option<u16>,
 
+    /// Limits on the messages stored for offline clients.
+    #[serde(default)]
+    pub inbox: nym_gateway::config::Inbox,
+
     #[serde(default)]
     pub debug: Debug,
 }
@@ -69,6 +73,7 @@ impl EntryGatewayConfig {
             bind_address: SocketAddr::new(inaddr_any(), DEFAULT_WS_PORT),
             announce_ws_port: None,
             announce_wss_port: None,
+            inbox: Default::default(),
             debug: Default::default(),
         }
     }
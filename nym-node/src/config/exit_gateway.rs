@@ -93,6 +93,10 @@ impl Default for NetworkRequesterDebug {
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 pub struct IpPacketRouter {
+    /// Configuration of the pool of addresses assigned to the connecting clients.
+    #[serde(default)]
+    pub ip_pool: nym_ip_packet_router::config::IpPool,
+
     #[serde(default)]
     pub debug: IpPacketRouterDebug,
 }
@@ -101,6 +105,7 @@ pub struct IpPacketRouter {
 impl Default for IpPacketRouter {
     fn default() -> Self {
         IpPacketRouter {
+            ip_pool: Default::default(),
             debug: Default::default(),
         }
     }
@@ -149,6 +154,7 @@ fn base_client_config(config: &Config) -> nym_client_core_config_types::Client {
         disabled_credentials_mode: true,
         nyxd_urls: config.mixnet.nyxd_urls.clone(),
         nym_api_urls: config.mixnet.nym_api_urls.clone(),
+        topology_snapshot: None,
     }
 }
 
@@ -175,6 +181,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                bind_announce_ip: config.host.public_ips.first().copied(),
             },
             storage_paths: nym_network_requester::config::NetworkRequesterPaths {
                 common_paths: config
@@ -217,6 +224,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                ip_pool: config.exit_gateway.ip_packet_router.ip_pool.clone(),
             },
             storage_paths: nym_ip_packet_router::config::IpPacketRouterPaths {
                 common_paths: config
@@ -225,11 +233,19 @@ pub fn ephemeral_exit_gateway_config(
                     .ip_packet_router
                     .to_common_client_paths(),
                 ip_packet_router_description: Default::default(),
+                ip_pool_allocations: Some(
+                    config
+                        .exit_gateway
+                        .storage_paths
+                        .ip_packet_router
+                        .ip_pool_allocations(),
+                ),
             },
 
             logging: config.logging,
         },
         custom_mixnet_path: None,
+        ip_pool_stats: Default::default(),
     };
 
     if ipr_opts.config.ip_packet_router.disable_poisson_rate {
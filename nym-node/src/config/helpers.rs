@@ -64,6 +64,7 @@ pub fn ephemeral_gateway_config(
         nym_gateway::config::GatewayPaths::new_empty(),
         nym_gateway::config::NetworkRequester { enabled: false },
         nym_gateway::config::IpPacketRouter { enabled: false },
+        config.entry_gateway.inbox,
         config.logging,
         nym_gateway::config::Debug {
             packet_forwarding_initial_backoff: config
@@ -78,7 +79,26 @@ pub fn ephemeral_gateway_config(
             maximum_connection_buffer_size: config.mixnet.debug.maximum_connection_buffer_size,
             message_retrieval_limit: config.entry_gateway.debug.message_retrieval_limit,
             use_legacy_framed_packet_version: false,
+            replay_protection: config.mixnet.debug.replay_protection,
             ..Default::default()
         },
     ))
 }
+
+// a temporary solution until all nodes are even more tightly integrated
+pub fn ephemeral_gateway_verloc_config(config: &Config) -> nym_gateway::config::VerlocConfig {
+    // the verloc settings are currently defined in the mixnode section,
+    // but they are used in all modes of the node
+    let verloc = &config.mixnode.verloc;
+    nym_gateway::config::VerlocConfig::build()
+        .listening_address(verloc.bind_address)
+        .packets_per_node(verloc.debug.packets_per_node)
+        .connection_timeout(verloc.debug.connection_timeout)
+        .packet_timeout(verloc.debug.packet_timeout)
+        .delay_between_packets(verloc.debug.delay_between_packets)
+        .tested_nodes_batch_size(verloc.debug.tested_nodes_batch_size)
+        .testing_interval(verloc.debug.testing_interval)
+        .retry_timeout(verloc.debug.retry_timeout)
+        .nym_api_urls(config.mixnet.nym_api_urls.clone())
+        .build()
+}
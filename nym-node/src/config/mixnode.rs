@@ -34,6 +34,8 @@ impl MixnodeConfig {
     }
 }
 
+/// Verloc settings of this node.
+/// Note: despite living in the mixnode section, they're used in all modes of the node.
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Verloc {
@@ -209,6 +211,7 @@ pub fn ephemeral_mixnode_config(
             initial_connection_timeout: config.mixnet.debug.initial_connection_timeout,
             maximum_connection_buffer_size: config.mixnet.debug.maximum_connection_buffer_size,
             use_legacy_framed_packet_version: false,
+            replay_protection: config.mixnet.debug.replay_protection,
         },
     ))
 }
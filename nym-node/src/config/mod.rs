@@ -16,6 +16,7 @@ use nym_config::{
     must_get_home, parse_urls, read_config_from_toml_file, save_formatted_config_to_file,
     NymConfigTemplate, DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode::config::ReplayProtectionConfig;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt::{Display, Formatter};
@@ -43,6 +44,7 @@ pub const DEFAULT_WIREGUARD_PORT: u16 = WG_PORT;
 pub const DEFAULT_WIREGUARD_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1));
 pub const DEFAULT_WIREGUARD_PREFIX: u8 = 16;
 pub const DEFAULT_HTTP_PORT: u16 = DEFAULT_NYM_NODE_HTTP_PORT;
+pub const DEFAULT_ADMIN_HTTP_PORT: u16 = 8081;
 pub const DEFAULT_MIXNET_PORT: u16 = DEFAULT_MIX_LISTENING_PORT;
 
 /// Derive default path to nym-node's config directory.
@@ -384,6 +386,9 @@ pub struct Http {
     /// This option is superseded by `expose_system_hardware`
     /// default: true
     pub expose_crypto_hardware: bool,
+
+    /// Settings of the operator-only admin API.
+    pub admin: AdminHttp,
 }
 
 impl Default for Http {
@@ -395,6 +400,36 @@ impl Default for Http {
             expose_system_info: true,
             expose_system_hardware: true,
             expose_crypto_hardware: true,
+            admin: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct AdminHttp {
+    /// Specifies whether the admin API, allowing to control the running node, should be exposed.
+    /// default: false
+    pub enabled: bool,
+
+    /// Socket address this node will use for binding its admin API.
+    /// It should never be reachable by anyone but the node operator.
+    /// default: `127.0.0.1:8081`
+    pub bind_address: SocketAddr,
+
+    /// Bearer token required for accessing any of the admin endpoints.
+    /// The admin API is not going to be started without it.
+    #[serde(deserialize_with = "de_maybe_stringified")]
+    pub access_token: Option<String>,
+}
+
+impl Default for AdminHttp {
+    fn default() -> Self {
+        AdminHttp {
+            enabled: false,
+            bind_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), DEFAULT_ADMIN_HTTP_PORT),
+            access_token: None,
         }
     }
 }
@@ -438,8 +473,11 @@ pub struct MixnetDebug {
     /// Maximum number of packets that can be stored waiting to get sent to a particular connection.
     pub maximum_connection_buffer_size: usize,
 
-    /// Specifies whether this node should **NOT** use noise protocol in the connections (currently not implemented)
+    /// Specifies whether this node should **NOT** use noise protocol in the connections to other nodes.
     pub unsafe_disable_noise: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
 }
 
 impl MixnetDebug {
@@ -456,8 +494,8 @@ impl Default for MixnetDebug {
             packet_forwarding_maximum_backoff: Self::DEFAULT_PACKET_FORWARDING_MAXIMUM_BACKOFF,
             initial_connection_timeout: Self::DEFAULT_INITIAL_CONNECTION_TIMEOUT,
             maximum_connection_buffer_size: Self::DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
-            // to be changed by @SW once the implementation is there
-            unsafe_disable_noise: true,
+            unsafe_disable_noise: false,
+            replay_protection: Default::default(),
         }
     }
 }
@@ -42,6 +42,7 @@ pub const DEFAULT_X25519_IPR_PUBLIC_DH_KEY_FILENAME: &str = "x25519_ipr_dh.pub";
 pub const DEFAULT_IPR_ACK_KEY_FILENAME: &str = "aes128ctr_ipr_ack";
 pub const DEFAULT_IPR_REPLY_SURB_DB_FILENAME: &str = "ipr_persistent_reply_store.sqlite";
 pub const DEFAULT_IPR_GATEWAYS_DB_FILENAME: &str = "ipr_gateways_info_store.sqlite";
+pub const DEFAULT_IPR_IP_POOL_ALLOCATIONS_FILENAME: &str = "ipr_ip_pool_allocations.json";
 
 // Wireguard
 pub const DEFAULT_X25519_WG_DH_KEY_FILENAME: &str = "x25519_wg_dh";
@@ -325,6 +326,16 @@ impl IpPacketRouterPaths {
         }
     }
 
+    /// Location of the static ip allocations of the ip packet router.
+    /// It's derived from the location of the other data files, so that it wouldn't require
+    /// changes to the existing configs.
+    pub fn ip_pool_allocations(&self) -> PathBuf {
+        self.ack_key_file
+            .parent()
+            .map(|dir| dir.join(DEFAULT_IPR_IP_POOL_ALLOCATIONS_FILENAME))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_IPR_IP_POOL_ALLOCATIONS_FILENAME))
+    }
+
     pub fn to_common_client_paths(&self) -> CommonClientPaths {
         CommonClientPaths {
             keys: ClientKeysPaths {
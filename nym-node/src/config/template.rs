@@ -105,6 +105,20 @@ expose_system_hardware = {{ http.expose_system_hardware }}
 # default: true
 expose_crypto_hardware = {{ http.expose_crypto_hardware }}
 
+[http.admin]
+# Specifies whether the admin API, allowing to control the running node, should be exposed.
+# default: false
+enabled = {{ http.admin.enabled }}
+
+# Socket address this node will use for binding its admin API.
+# It should never be reachable by anyone but the node operator.
+# default: `127.0.0.1:8081`
+bind_address = '{{ http.admin.bind_address }}'
+
+# Bearer token required for accessing any of the admin endpoints.
+# The admin API is not going to be started without it.
+access_token = '{{ http.admin.access_token }}'
+
 ##### wireguard-API nym-node config options #####
 
 [wireguard]
@@ -140,6 +154,7 @@ public_diffie_hellman_key_file = '{{ wireguard.storage_paths.public_diffie_hellm
 [mixnode]
 
 [mixnode.verloc]
+# Note: verloc measurements are performed in all modes of the node, not just as a mixnode.
 # Socket address this node will use for binding its verloc API.
 # default: `0.0.0.0:1790`
 bind_address = '{{ mixnode.verloc.bind_address }}'
@@ -167,6 +182,24 @@ announce_ws_port = {{#if entry_gateway.announce_ws_port }} {{ entry_gateway.anno
 # (default: 0 - disabled)
 announce_wss_port = {{#if entry_gateway.announce_wss_port }} {{ entry_gateway.announce_wss_port }} {{else}} 0 {{/if}}
 
+[entry_gateway.inbox]
+# Maximum number of messages stored for a single offline client.
+# Once it's reached, the oldest messages get evicted to make room for the new ones.
+# (0 - unlimited)
+max_client_messages = {{ entry_gateway.inbox.max_client_messages }}
+
+# Maximum total size (in bytes) of messages stored for a single offline client.
+# Once it's reached, the oldest messages get evicted to make room for the new ones.
+# (0 - unlimited)
+max_client_bytes = {{ entry_gateway.inbox.max_client_bytes }}
+
+# Maximum age of a stored message after which it gets removed from the storage.
+# (0s - messages never expire)
+max_message_age = '{{ entry_gateway.inbox.max_message_age }}'
+
+# Delay between subsequent runs of the task removing expired messages.
+pruning_interval = '{{ entry_gateway.inbox.pruning_interval }}'
+
 [entry_gateway.storage_paths]
 # Path to sqlite database containing all persistent data: messages for offline clients,
 # derived shared keys and available client bandwidths.
@@ -190,7 +223,19 @@ upstream_exit_policy_url = '{{ exit_gateway.upstream_exit_policy_url }}'
 # currently empty (there are some debug options one might want to configure)
 
 [exit_gateway.ip_packet_router]
-# currently empty (there are some debug options one might want to configure)
+
+[exit_gateway.ip_packet_router.ip_pool]
+# IPv4 subnet from which addresses are assigned to the connecting clients.
+# The first host address of the subnet is used by the tun device itself.
+ipv4_subnet = '{{ exit_gateway.ip_packet_router.ip_pool.ipv4_subnet }}'
+
+# IPv6 subnet from which addresses are assigned to the connecting clients.
+# The first host address of the subnet is used by the tun device itself.
+ipv6_subnet = '{{ exit_gateway.ip_packet_router.ip_pool.ipv6_subnet }}'
+
+# Specifies for how long the addresses of a disconnected client are kept reserved,
+# so that it could get the same ones back upon reconnecting.
+lease_duration = '{{ exit_gateway.ip_packet_router.ip_pool.lease_duration }}'
 
 [exit_gateway.storage_paths]
 
@@ -32,6 +32,9 @@ pub mod vars {
     pub const NYMNODE_HTTP_EXPOSE_SYSTEM_INFO_ARG: &str = "NYMNODE_HTTP_EXPOSE_SYSTEM_INFO";
     pub const NYMNODE_HTTP_EXPOSE_SYSTEM_HARDWARE_ARG: &str = "NYMNODE_HTTP_EXPOSE_SYSTEM_HARDWARE";
     pub const NYMNODE_HTTP_EXPOSE_CRYPTO_HARDWARE_ARG: &str = "NYMNODE_HTTP_EXPOSE_CRYPTO_HARDWARE";
+    pub const NYMNODE_HTTP_ADMIN_ENABLED_ARG: &str = "NYMNODE_HTTP_ADMIN_ENABLED";
+    pub const NYMNODE_HTTP_ADMIN_BIND_ADDRESS_ARG: &str = "NYMNODE_HTTP_ADMIN_BIND_ADDRESS";
+    pub const NYMNODE_HTTP_ADMIN_ACCESS_TOKEN_ARG: &str = "NYMNODE_HTTP_ADMIN_ACCESS_TOKEN";
 
     // mixnet:
     pub const NYMNODE_MIXNET_BIND_ADDRESS_ARG: &str = "NYMNODE_MIXNET_BIND_ADDRESS";
@@ -101,6 +101,9 @@ pub enum NymNodeError {
         source: io::Error,
     },
 
+    #[error("the admin API has been enabled without specifying its access token. Please modify [http.admin] section of your config")]
+    MissingAdminAccessToken,
+
     #[error("this node hasn't set any valid public addresses to announce. Please modify [host.public_ips] section of your config")]
     NoPublicIps,
 
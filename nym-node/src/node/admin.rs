@@ -0,0 +1,147 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::helpers::{load_x25519_noise_keypair, load_x25519_sphinx_keypair};
+use crate::node::http::sign_host_details;
+use nym_crypto::asymmetric::{ed25519, x25519};
+use nym_gateway::node::GatewayAdminHandle;
+use nym_node::config::Config;
+use nym_node::error::NymNodeError;
+use nym_node_http_api::api::api_requests::SignedHostInformation;
+use nym_node_http_api::router::SharedHostInformation;
+use nym_node_http_api::state::admin::{AdminRequest, AdminRequestError, AdminRequestReceiver};
+use nym_task::TaskClient;
+use std::sync::Arc;
+use tracing::{debug, info, trace, warn};
+
+fn failure(err: NymNodeError) -> AdminRequestError {
+    AdminRequestError::Failure {
+        message: err.to_string(),
+    }
+}
+
+/// Fulfills requests received by the admin API.
+pub(crate) struct AdminRequestHandler {
+    config: Config,
+
+    ed25519_identity_keys: Arc<ed25519::KeyPair>,
+    x25519_sphinx_key: x25519::PublicKey,
+    x25519_noise_key: x25519::PublicKey,
+    host_information: SharedHostInformation,
+
+    /// Handle to the running gateway, if the node is running in one of the gateway modes.
+    gateway: Option<GatewayAdminHandle>,
+
+    requests: AdminRequestReceiver,
+    task_client: TaskClient,
+}
+
+impl AdminRequestHandler {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        config: Config,
+        ed25519_identity_keys: Arc<ed25519::KeyPair>,
+        x25519_sphinx_key: x25519::PublicKey,
+        x25519_noise_key: x25519::PublicKey,
+        host_information: SharedHostInformation,
+        gateway: Option<GatewayAdminHandle>,
+        requests: AdminRequestReceiver,
+        task_client: TaskClient,
+    ) -> Self {
+        AdminRequestHandler {
+            config,
+            ed25519_identity_keys,
+            x25519_sphinx_key,
+            x25519_noise_key,
+            host_information,
+            gateway,
+            requests,
+            task_client,
+        }
+    }
+
+    fn gateway(&self) -> Result<&GatewayAdminHandle, AdminRequestError> {
+        self.gateway
+            .as_ref()
+            .ok_or(AdminRequestError::UnsupportedOperation)
+    }
+
+    async fn announce_keys(&self) -> Result<SignedHostInformation, AdminRequestError> {
+        let x25519_sphinx_keys = load_x25519_sphinx_keypair(
+            self.config.storage_paths.keys.x25519_sphinx_storage_paths(),
+        )
+        .map_err(failure)?;
+        let x25519_noise_keys =
+            load_x25519_noise_keypair(self.config.storage_paths.keys.x25519_noise_storage_paths())
+                .map_err(failure)?;
+
+        if x25519_sphinx_keys.public_key() != &self.x25519_sphinx_key
+            || x25519_noise_keys.public_key() != &self.x25519_noise_key
+        {
+            warn!("announcing rotated keys - they're only going to be used for processing packets after the node is restarted");
+        }
+
+        let host_information = sign_host_details(
+            &self.config,
+            x25519_sphinx_keys.public_key(),
+            x25519_noise_keys.public_key(),
+            &self.ed25519_identity_keys,
+        )
+        .map_err(failure)?;
+
+        self.host_information.update(host_information.clone()).await;
+        info!("announced the current node keys");
+        Ok(host_information)
+    }
+
+    async fn handle_request(&self, request: AdminRequest) {
+        // if the requester is gone, there's nobody to send the response to
+        match request {
+            AdminRequest::ReloadExitPolicy { response } => {
+                let res = match self.gateway() {
+                    Ok(gateway) => gateway.reload_exit_policy().await,
+                    Err(err) => Err(err),
+                };
+                let _ = response.send(res);
+            }
+            AdminRequest::GetDrainState { response } => {
+                let _ = response.send(self.gateway().map(|g| g.drain_state()));
+            }
+            AdminRequest::SetDraining { draining, response } => {
+                let _ = response.send(self.gateway().map(|g| g.set_draining(draining)));
+            }
+            AdminRequest::ListClients { response } => {
+                let _ = response.send(self.gateway().map(|g| g.active_clients()));
+            }
+            AdminRequest::DisconnectClient { address, response } => {
+                let _ = response.send(self.gateway().and_then(|g| g.disconnect_client(&address)));
+            }
+            AdminRequest::AnnounceKeys { response } => {
+                let _ = response.send(self.announce_keys().await);
+            }
+        }
+    }
+
+    async fn run(&mut self) {
+        while !self.task_client.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.task_client.recv() => {
+                    trace!("AdminRequestHandler: Received shutdown");
+                }
+                request = self.requests.recv() => {
+                    let Some(request) = request else {
+                        debug!("the admin API has stopped sending requests");
+                        break;
+                    };
+                    self.handle_request(request).await
+                }
+            }
+        }
+        trace!("AdminRequestHandler: Exiting");
+    }
+
+    pub(crate) fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
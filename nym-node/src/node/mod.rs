@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::node::admin::AdminRequestHandler;
 use crate::node::description::{load_node_description, save_node_description};
 use crate::node::helpers::{
     load_ed25519_identity_keypair, load_key, load_x25519_noise_keypair, load_x25519_sphinx_keypair,
@@ -8,9 +9,11 @@ use crate::node::helpers::{
     store_x25519_sphinx_keypair, DisplayDetails,
 };
 use crate::node::http::{sign_host_details, system_info::get_system_info};
+use crate::node::noise_network::NoiseNetworkRefresher;
 use ipnetwork::IpNetwork;
 use nym_bin_common::bin_info_owned;
 use nym_crypto::asymmetric::{ed25519, x25519};
+use nym_gateway::node::GatewayAdminHandle;
 use nym_gateway::Gateway;
 use nym_mixnode::MixNode;
 use nym_network_requester::{
@@ -19,6 +22,7 @@ use nym_network_requester::{
 };
 use nym_node::config::entry_gateway::ephemeral_entry_gateway_config;
 use nym_node::config::exit_gateway::ephemeral_exit_gateway_config;
+use nym_node::config::helpers::ephemeral_gateway_verloc_config;
 use nym_node::config::mixnode::ephemeral_mixnode_config;
 use nym_node::config::{
     Config, EntryGatewayConfig, ExitGatewayConfig, MixnodeConfig, NodeMode, Wireguard,
@@ -26,10 +30,13 @@ use nym_node::config::{
 use nym_node::error::{EntryGatewayError, ExitGatewayError, MixnodeError, NymNodeError};
 use nym_node_http_api::api::api_requests;
 use nym_node_http_api::api::api_requests::v1::node::models::NodeDescription;
-use nym_node_http_api::router::WireguardAppState;
+use nym_node_http_api::router::{SharedHostInformation, WireguardAppState};
+use nym_node_http_api::state::admin::AdminAppState;
+use nym_node_http_api::state::ip_packet_router::SharedIpPoolStats;
 use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
 use nym_node_http_api::state::AppState;
-use nym_node_http_api::{NymNodeHTTPServer, NymNodeRouter};
+use nym_node_http_api::{AdminRouter, NymNodeHTTPServer, NymNodeRouter};
+use nym_noise::{NoiseConfig, NoiseNetworkView};
 use nym_sphinx_acknowledgements::AckKey;
 use nym_sphinx_addressing::Recipient;
 use nym_task::{TaskClient, TaskManager};
@@ -38,16 +45,19 @@ use rand::rngs::OsRng;
 use rand::{CryptoRng, RngCore};
 use std::path::Path;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tokio::sync::mpsc::UnboundedReceiver;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 use zeroize::Zeroizing;
 
 use self::helpers::load_x25519_wireguard_keypair;
 
+mod admin;
 pub mod bonding_information;
 pub mod description;
 pub mod helpers;
 pub(crate) mod http;
+mod noise_network;
 
 pub struct MixnodeData {
     mixing_stats: SharedMixingStats,
@@ -95,6 +105,7 @@ impl EntryGatewayData {
             client_storage: nym_gateway::node::PersistentStorage::init(
                 &config.storage_paths.clients_storage,
                 config.debug.message_retrieval_limit,
+                (&config.inbox).into(),
             )
             .await
             .map_err(nym_gateway::GatewayError::from)?,
@@ -113,6 +124,8 @@ pub struct ExitGatewayData {
 
     ipr_ed25519: ed25519::PublicKey,
     ipr_x25519: x25519::PublicKey,
+
+    ip_pool_stats: SharedIpPoolStats,
 }
 
 impl ExitGatewayData {
@@ -248,6 +261,7 @@ impl ExitGatewayData {
             nr_x25519,
             ipr_ed25519,
             ipr_x25519,
+            ip_pool_stats: SharedIpPoolStats::new(),
         })
     }
 }
@@ -297,7 +311,6 @@ pub(crate) struct NymNode {
 
     description: NodeDescription,
 
-    // TODO: currently we're only making measurements in 'mixnode' mode; this should be changed
     verloc_stats: SharedVerlocStats,
 
     #[allow(dead_code)]
@@ -312,9 +325,6 @@ pub(crate) struct NymNode {
 
     ed25519_identity_keys: Arc<ed25519::KeyPair>,
     x25519_sphinx_keys: Arc<x25519::KeyPair>,
-
-    // to be used when noise is integrated
-    #[allow(dead_code)]
     x25519_noise_keys: Arc<x25519::KeyPair>,
 }
 
@@ -452,7 +462,29 @@ impl NymNode {
         self.x25519_noise_keys.public_key()
     }
 
-    fn start_mixnode(self, task_client: TaskClient) -> Result<(), NymNodeError> {
+    fn start_noise_network_refresher(&self, task_manager: &TaskManager) -> NoiseConfig {
+        let network = NoiseNetworkView::new_empty();
+
+        if self.config.mixnet.debug.unsafe_disable_noise {
+            warn!("noise is disabled - all outbound mixnet connections are going to be established in plaintext");
+        } else {
+            NoiseNetworkRefresher::new(
+                self.config.mixnet.nym_api_urls.clone(),
+                network.clone(),
+                task_manager.subscribe_named("noise-network"),
+            )
+            .start();
+        }
+
+        NoiseConfig::new(self.x25519_noise_keys.clone(), network)
+            .with_unsafe_disabled(self.config.mixnet.debug.unsafe_disable_noise)
+    }
+
+    fn start_mixnode(
+        self,
+        noise_config: NoiseConfig,
+        task_client: TaskClient,
+    ) -> Result<(), NymNodeError> {
         info!("going to start the nym-node in MIXNODE mode");
 
         let config = ephemeral_mixnode_config(self.config.clone())?;
@@ -464,6 +496,7 @@ impl NymNode {
         );
         mixnode.disable_http_server();
         mixnode.set_task_client(task_client);
+        mixnode.set_noise_config(noise_config);
         mixnode.set_mixing_stats(self.mixnode.mixing_stats.clone());
         mixnode.set_verloc_stats(self.verloc_stats.clone());
 
@@ -475,7 +508,12 @@ impl NymNode {
         Ok(())
     }
 
-    fn start_entry_gateway(self, task_client: TaskClient) -> Result<(), NymNodeError> {
+    fn start_entry_gateway(
+        self,
+        noise_config: NoiseConfig,
+        admin_handle: GatewayAdminHandle,
+        task_client: TaskClient,
+    ) -> Result<(), NymNodeError> {
         info!("going to start the nym-node in ENTRY GATEWAY mode");
 
         let config =
@@ -490,6 +528,10 @@ impl NymNode {
         );
         entry_gateway.disable_http_server();
         entry_gateway.set_task_client(task_client);
+        entry_gateway.set_noise_config(noise_config);
+        entry_gateway.set_admin_handle(admin_handle);
+        entry_gateway.set_verloc_config(ephemeral_gateway_verloc_config(&self.config));
+        entry_gateway.set_verloc_stats(self.verloc_stats.clone());
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         entry_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -501,11 +543,17 @@ impl NymNode {
         Ok(())
     }
 
-    fn start_exit_gateway(self, task_client: TaskClient) -> Result<(), NymNodeError> {
+    fn start_exit_gateway(
+        self,
+        noise_config: NoiseConfig,
+        admin_handle: GatewayAdminHandle,
+        task_client: TaskClient,
+    ) -> Result<(), NymNodeError> {
         info!("going to start the nym-node in EXIT GATEWAY mode");
 
-        let config =
+        let mut config =
             ephemeral_exit_gateway_config(self.config.clone(), &self.entry_gateway.mnemonic)?;
+        config.ipr_opts.ip_pool_stats = self.exit_gateway.ip_pool_stats.clone();
 
         let mut exit_gateway = Gateway::new_loaded(
             config.gateway,
@@ -517,6 +565,10 @@ impl NymNode {
         );
         exit_gateway.disable_http_server();
         exit_gateway.set_task_client(task_client);
+        exit_gateway.set_noise_config(noise_config);
+        exit_gateway.set_admin_handle(admin_handle);
+        exit_gateway.set_verloc_config(ephemeral_gateway_verloc_config(&self.config));
+        exit_gateway.set_verloc_stats(self.verloc_stats.clone());
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         exit_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -528,17 +580,14 @@ impl NymNode {
         Ok(())
     }
 
-    pub(crate) async fn build_http_server(&self) -> Result<NymNodeHTTPServer, NymNodeError> {
-        let host_details = sign_host_details(
-            &self.config,
-            self.x25519_sphinx_keys.public_key(),
-            self.x25519_noise_keys.public_key(),
-            &self.ed25519_identity_keys,
-        )?;
-
+    pub(crate) async fn build_http_server(
+        &self,
+        host_information: SharedHostInformation,
+    ) -> Result<NymNodeHTTPServer, NymNodeError> {
         let auxiliary_details = api_requests::v1::node::models::AuxiliaryDetails {
             location: self.config.host.location,
             accepted_toc: self.accepted_toc,
+            announced_verloc_port: Some(self.config.mixnode.verloc.bind_address.port()),
         };
 
         // mixnode info
@@ -606,7 +655,7 @@ impl NymNode {
             wireguard_private_network,
         )?;
 
-        let mut config = nym_node_http_api::Config::new(bin_info_owned!(), host_details)
+        let mut config = nym_node_http_api::Config::new(bin_info_owned!(), host_information)
             .with_landing_page_assets(self.config.http.landing_page_assets_path.as_ref())
             .with_mixnode_details(mixnode_details)
             .with_gateway_details(gateway_details)
@@ -635,6 +684,7 @@ impl NymNode {
         let app_state = AppState::new()
             .with_mixing_stats(self.mixnode.mixing_stats.clone())
             .with_verloc_stats(self.verloc_stats.clone())
+            .with_ip_pool_stats(self.exit_gateway.ip_pool_stats.clone())
             .with_metrics_key(self.config.http.access_token.clone());
 
         Ok(NymNodeRouter::new(config, Some(app_state), Some(wg_state))
@@ -642,10 +692,57 @@ impl NymNode {
             .await?)
     }
 
+    async fn start_admin_server(
+        &self,
+        host_information: SharedHostInformation,
+        gateway_admin: GatewayAdminHandle,
+        task_manager: &TaskManager,
+    ) -> Result<(), NymNodeError> {
+        let Some(access_token) = self.config.http.admin.access_token.clone() else {
+            return Err(NymNodeError::MissingAdminAccessToken);
+        };
+
+        let gateway_admin = match self.config.mode {
+            NodeMode::Mixnode => None,
+            NodeMode::EntryGateway | NodeMode::ExitGateway => Some(gateway_admin),
+        };
+
+        let (requests_tx, requests_rx) = mpsc::unbounded_channel();
+        AdminRequestHandler::new(
+            self.config.clone(),
+            self.ed25519_identity_keys.clone(),
+            *self.x25519_sphinx_key(),
+            *self.x25519_noise_key(),
+            host_information,
+            gateway_admin,
+            requests_rx,
+            task_manager.subscribe_named("admin-request-handler"),
+        )
+        .start();
+
+        let bind_address = self.config.http.admin.bind_address;
+        let admin_server = AdminRouter::new(AdminAppState::new(access_token, requests_tx))
+            .build_server(&bind_address)
+            .await?
+            .with_task_client(task_manager.subscribe_named("admin-http-server"));
+        tokio::spawn(async move {
+            info!("Started the admin API on {bind_address}");
+            admin_server.run().await
+        });
+        Ok(())
+    }
+
     pub(crate) async fn run(self) -> Result<(), NymNodeError> {
         let mut task_manager = TaskManager::default().named("NymNode");
+        let host_information = SharedHostInformation::from(sign_host_details(
+            &self.config,
+            self.x25519_sphinx_keys.public_key(),
+            self.x25519_noise_keys.public_key(),
+            &self.ed25519_identity_keys,
+        )?);
+
         let http_server = self
-            .build_http_server()
+            .build_http_server(host_information.clone())
             .await?
             .with_task_client(task_manager.subscribe_named("http-server"));
         let bind_address = self.config.http.bind_address;
@@ -656,19 +753,35 @@ impl NymNode {
             }
         });
 
+        let gateway_admin = GatewayAdminHandle::new();
+        if self.config.http.admin.enabled {
+            self.start_admin_server(host_information, gateway_admin.clone(), &task_manager)
+                .await?;
+        }
+
+        let noise_config = self.start_noise_network_refresher(&task_manager);
+
         match self.config.mode {
             NodeMode::Mixnode => {
-                self.start_mixnode(task_manager.subscribe_named("mixnode"))?;
+                self.start_mixnode(noise_config, task_manager.subscribe_named("mixnode"))?;
                 let _ = task_manager.catch_interrupt().await;
                 Ok(())
             }
             NodeMode::EntryGateway => {
-                self.start_entry_gateway(task_manager.subscribe_named("entry-gateway"))?;
+                self.start_entry_gateway(
+                    noise_config,
+                    gateway_admin,
+                    task_manager.subscribe_named("entry-gateway"),
+                )?;
                 let _ = task_manager.catch_interrupt().await;
                 Ok(())
             }
             NodeMode::ExitGateway => {
-                self.start_exit_gateway(task_manager.subscribe_named("exit-gateway"))?;
+                self.start_exit_gateway(
+                    noise_config,
+                    gateway_admin,
+                    task_manager.subscribe_named("exit-gateway"),
+                )?;
                 let _ = task_manager.catch_interrupt().await;
                 Ok(())
             }
@@ -0,0 +1,110 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_crypto::asymmetric::x25519;
+use nym_noise::NoiseNetworkView;
+use nym_task::TaskClient;
+use nym_validator_client::nym_nodes::SemiSkimmedNode;
+use nym_validator_client::{NymApiClient, ValidatorClientError};
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tracing::{debug, trace, warn};
+use url::Url;
+
+const NOISE_NETWORK_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Periodically retrieves noise keys of all nodes in the network from nym-api
+/// and updates the shared view used when establishing (and accepting) mixnet connections.
+pub(crate) struct NoiseNetworkRefresher {
+    nym_apis: Vec<Url>,
+    network: NoiseNetworkView,
+    task_client: TaskClient,
+}
+
+impl NoiseNetworkRefresher {
+    pub(crate) fn new(
+        nym_apis: Vec<Url>,
+        network: NoiseNetworkView,
+        task_client: TaskClient,
+    ) -> Self {
+        NoiseNetworkRefresher {
+            nym_apis,
+            network,
+            task_client,
+        }
+    }
+
+    fn add_node_keys(keys: &mut HashMap<SocketAddr, x25519::PublicKey>, node: SemiSkimmedNode) {
+        if node.x25519_noise_pubkey.is_empty() {
+            // the node doesn't support noise
+            return;
+        }
+
+        let key = match x25519::PublicKey::from_base58_string(&node.x25519_noise_pubkey) {
+            Ok(key) => key,
+            Err(err) => {
+                warn!(
+                    "node {} has advertised malformed noise key: {err}",
+                    node.basic.ed25519_identity_pubkey
+                );
+                return;
+            }
+        };
+
+        let mix_port = node.basic.mix_port;
+        for ip in node.basic.ip_addresses {
+            keys.insert(SocketAddr::new(ip, mix_port), key);
+        }
+    }
+
+    async fn fetch_network(
+        &self,
+        nym_api: &Url,
+    ) -> Result<HashMap<SocketAddr, x25519::PublicKey>, ValidatorClientError> {
+        let client = NymApiClient::new(nym_api.clone());
+        let mixnodes = client.get_expanded_mixnodes(None).await?;
+        let gateways = client.get_expanded_gateways(None).await?;
+
+        let mut keys = HashMap::new();
+        for node in mixnodes.into_iter().chain(gateways) {
+            Self::add_node_keys(&mut keys, node)
+        }
+        Ok(keys)
+    }
+
+    async fn refresh(&self) {
+        let Some(nym_api) = self.nym_apis.choose(&mut thread_rng()) else {
+            warn!("no nym-api endpoints are available - can't retrieve noise keys of the network");
+            return;
+        };
+
+        match self.fetch_network(nym_api).await {
+            Ok(keys) => {
+                debug!("learned noise keys of {} network addresses", keys.len());
+                self.network.swap_view(keys)
+            }
+            Err(err) => warn!("failed to refresh noise keys of the network: {err}"),
+        }
+    }
+
+    async fn run(&mut self) {
+        let mut refresh_interval = tokio::time::interval(NOISE_NETWORK_REFRESH_INTERVAL);
+        while !self.task_client.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.task_client.recv() => {
+                    trace!("NoiseNetworkRefresher: Received shutdown");
+                }
+                _ = refresh_interval.tick() => self.refresh().await,
+            }
+        }
+        trace!("NoiseNetworkRefresher: Exiting");
+    }
+
+    pub(crate) fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
@@ -194,11 +194,7 @@ impl OutfoxPacket {
             .all(|x| x == &0)
     }
 
-    pub fn decode_next_layer(
-        &mut self,
-        mix_secret_key: &PrivateKey,
-    ) -> Result<[u8; 32], OutfoxError> {
-        let mix_secret_key = mix_secret_key.to_bytes();
+    fn next_layer(&self) -> usize {
         let routing_lenght_by_stage = self
             .mix_params()
             .routing_information_length_by_stage
@@ -210,6 +206,24 @@ impl OutfoxPacket {
                 break;
             }
         }
+        layer
+    }
+
+    /// Returns the public group element of the layer that is going to be decoded next.
+    /// It's unique per packet per hop and thus can be used for detecting replayed packets.
+    pub fn replay_tag(&self) -> Option<&[u8]> {
+        let (range, params) = self.stage_params(self.next_layer());
+        self.payload()
+            .get(range)
+            .and_then(|layer| layer.get(params.pub_element_range()))
+    }
+
+    pub fn decode_next_layer(
+        &mut self,
+        mix_secret_key: &PrivateKey,
+    ) -> Result<[u8; 32], OutfoxError> {
+        let mix_secret_key = mix_secret_key.to_bytes();
+        let layer = self.next_layer();
         self.decode_mix_layer(layer, &mix_secret_key)?;
         self.update_routing_information(layer)?;
         let (range, stage_params) = self.mix_params().get_stage_params(layer);
@@ -27,6 +27,14 @@ build_artifacts_and_link() {
   # ldd main
 }
 
+build_and_run_tests() {
+  cargo build --release &&
+  cd src/ &&
+  printf "compiling cpp tests \n" &&
+  g++ -std=c++11 -o test test.cpp -ldl -lpthread -L../target/release -lnym_cpp_ffi &&
+  LD_LIBRARY_PATH=../target/release:${LD_LIBRARY_PATH:-} ./test
+}
+
 if [ $(pwd | awk -F/ '{print $NF}') != ${PROJECT_NAME} ]
 then
   printf "please run from root dir of project"
@@ -43,8 +51,10 @@ else
     clean_artifacts;
     build_artifacts_and_link;
     ./main;
+  elif [ "$arg" == "test" ]; then
+    build_and_run_tests;
   else
-      printf "unknown optional argument - the only available optional argument is 'clean'"
+      printf "unknown optional argument - the only available optional arguments are 'clean' and 'test'"
       exit 1
   fi
 fi
@@ -4,8 +4,11 @@
 use nym_ffi_shared;
 use std::ffi::{c_char, c_int, CStr, CString};
 
+use nym_ffi_shared::clients::{self, ClientHandle, ClientOptions};
+use nym_sdk::mixnet::ReconstructedMessage;
 use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
 use std::mem::forget;
+use std::time::Duration;
 mod types;
 use crate::types::types::{CMessageCallback, CStringCallback, ReceivedMessage, StatusCode};
 
@@ -110,3 +113,197 @@ pub extern "C" fn listen_for_incoming(callback: CMessageCallback) -> c_int {
         Err(_) => StatusCode::ListenError as c_int,
     }
 }
+
+// helper for the optional string arguments: null pointers are treated as `None`
+unsafe fn optional_string(ptr: *const c_char) -> Result<Option<String>, ()> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(|s| Some(s.to_owned()))
+        .map_err(|_| ())
+}
+
+// unlike `listen_for_incoming`, the message data is only valid for the duration of the callback
+// (and is deallocated afterwards), so it has to be copied if it's needed later on
+fn trigger_message_callback(callback: CMessageCallback, received: ReconstructedMessage) {
+    let sender_tag = received
+        .sender_tag
+        .map(|tag| CString::new(tag.to_string()).expect("base58 encoding contains no null bytes"));
+    let rec_for_c = ReceivedMessage {
+        message: received.message.as_ptr(),
+        size: received.message.len(),
+        sender_tag: sender_tag
+            .as_ref()
+            .map(|tag| tag.as_ptr())
+            .unwrap_or(std::ptr::null()),
+    };
+    CMessageCallback::new(callback.callback).trigger(rec_for_c);
+}
+
+/// Creates a new client and writes its handle into `handle_out`.
+/// `storage_dir` and `gateway` are optional (can be null). If `storage_dir` is not provided,
+/// the client is ephemeral, otherwise its keys and data are persisted in the specified directory.
+#[no_mangle]
+pub extern "C" fn new_client(
+    storage_dir: *const c_char,
+    gateway: *const c_char,
+    force_tls: bool,
+    handle_out: *mut ClientHandle,
+) -> c_int {
+    if handle_out.is_null() {
+        return StatusCode::ClientInitError as c_int;
+    }
+    let Ok(storage_dir) = (unsafe { optional_string(storage_dir) }) else {
+        return StatusCode::ClientInitError as c_int;
+    };
+    let Ok(gateway) = (unsafe { optional_string(gateway) }) else {
+        return StatusCode::ClientInitError as c_int;
+    };
+
+    let options = ClientOptions {
+        storage_dir: storage_dir.map(Into::into),
+        gateway,
+        force_tls,
+    };
+    match clients::new_client_internal(options) {
+        Ok(handle) => {
+            unsafe { *handle_out = handle };
+            StatusCode::NoError as c_int
+        }
+        Err(_) => StatusCode::ClientInitError as c_int,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn client_get_address(handle: ClientHandle, callback: CStringCallback) -> c_int {
+    match clients::client_address_internal(handle) {
+        Ok(addr) => {
+            let c_ptr =
+                CString::new(addr.to_string()).expect("could not convert Nym address to CString");
+            CStringCallback::new(callback.callback).trigger(c_ptr.as_ptr());
+            StatusCode::NoError as c_int
+        }
+        Err(_) => StatusCode::SelfAddrError as c_int,
+    }
+}
+
+/// Sends binary message to the provided recipient. If `reply_surbs` is negative,
+/// the default number of reply SURBs is attached.
+#[no_mangle]
+pub extern "C" fn client_send(
+    handle: ClientHandle,
+    recipient: *const c_char,
+    message: *const u8,
+    size: usize,
+    reply_surbs: i64,
+) -> c_int {
+    if recipient.is_null() {
+        return StatusCode::RecipientNullError as c_int;
+    }
+    if message.is_null() && size != 0 {
+        return StatusCode::MessageNullError as c_int;
+    }
+    let recipient = match unsafe { CStr::from_ptr(recipient) }
+        .to_str()
+        .ok()
+        .and_then(|r_str| r_str.parse().ok())
+    {
+        Some(recipient) => recipient,
+        None => return StatusCode::InvalidRecipientError as c_int,
+    };
+    let message = if size == 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(message, size) }.to_vec()
+    };
+    let reply_surbs = u32::try_from(reply_surbs).ok();
+
+    match clients::client_send_internal(handle, recipient, message, reply_surbs) {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(_) => StatusCode::SendMsgError as c_int,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn client_reply(
+    handle: ClientHandle,
+    sender_tag: *const c_char,
+    message: *const u8,
+    size: usize,
+) -> c_int {
+    if sender_tag.is_null() {
+        return StatusCode::RecipientNullError as c_int;
+    }
+    if message.is_null() && size != 0 {
+        return StatusCode::MessageNullError as c_int;
+    }
+    let sender_tag = match unsafe { CStr::from_ptr(sender_tag) }
+        .to_str()
+        .ok()
+        .and_then(|tag| AnonymousSenderTag::try_from_base58_string(tag).ok())
+    {
+        Some(sender_tag) => sender_tag,
+        None => return StatusCode::InvalidSenderTagError as c_int,
+    };
+    let message = if size == 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(message, size) }.to_vec()
+    };
+
+    match clients::client_reply_internal(handle, sender_tag, message) {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(_) => StatusCode::ReplyError as c_int,
+    }
+}
+
+/// Waits for the next incoming message for up to `timeout_ms` milliseconds (or indefinitely if 0)
+/// and passes it to the callback.
+#[no_mangle]
+pub extern "C" fn client_receive(
+    handle: ClientHandle,
+    timeout_ms: u64,
+    callback: CMessageCallback,
+) -> c_int {
+    let timeout = (timeout_ms != 0).then(|| Duration::from_millis(timeout_ms));
+    match clients::client_receive_internal(handle, timeout) {
+        Ok(Some(received)) => {
+            trigger_message_callback(callback, received);
+            StatusCode::NoError as c_int
+        }
+        Ok(None) => StatusCode::ReceiveTimeoutError as c_int,
+        Err(_) => StatusCode::ListenError as c_int,
+    }
+}
+
+/// Starts invoking the callback (from a background thread) on every incoming message
+/// until `client_stop_listening` or `client_disconnect` is called.
+#[no_mangle]
+pub extern "C" fn client_start_listening(
+    handle: ClientHandle,
+    callback: CMessageCallback,
+) -> c_int {
+    let on_message = Box::new(move |received| trigger_message_callback(callback, received));
+    match clients::client_start_listening_internal(handle, on_message) {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(_) => StatusCode::ListenError as c_int,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn client_stop_listening(handle: ClientHandle) -> c_int {
+    match clients::client_stop_listening_internal(handle) {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(_) => StatusCode::ListenError as c_int,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn client_disconnect(handle: ClientHandle) -> c_int {
+    match clients::client_disconnect_internal(handle) {
+        Ok(_) => StatusCode::NoError as c_int,
+        Err(_) => StatusCode::ClientDisconnectError as c_int,
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+#include <atomic>
+#include <chrono>
+#include <condition_variable>
+#include <cstdint>
+#include <cstdlib>
+#include <cstring>
+#include <iostream>
+#include <mutex>
+#include <string>
+#include <vector>
+
+//Rust function & type signatures
+extern "C" {
+    struct ReceivedMessage {
+       const uint8_t* message;
+       size_t size;
+       const char* sender_tag;
+    };
+
+    char new_client(const char*, const char*, bool, uint64_t*);
+    char client_get_address(uint64_t, void (*callback)(const char*));
+    char client_send(uint64_t, const char*, const uint8_t*, size_t, int64_t);
+    char client_reply(uint64_t, const char*, const uint8_t*, size_t);
+    char client_receive(uint64_t, uint64_t, void (*callback)(ReceivedMessage));
+    char client_start_listening(uint64_t, void (*callback)(ReceivedMessage));
+    char client_stop_listening(uint64_t);
+    char client_disconnect(uint64_t);
+}
+
+// status codes as defined in types.rs
+const char NO_ERROR = 0;
+const char CLIENT_INIT_ERROR = -1;
+const char SELF_ADDR_ERROR = -3;
+const char LISTEN_ERROR = -6;
+const char RECIPIENT_NULL_ERROR = -7;
+const char INVALID_RECIPIENT_ERROR = -9;
+const char INVALID_SENDER_TAG_ERROR = -10;
+const char CLIENT_DISCONNECT_ERROR = -11;
+
+// handle that is never assigned to any client
+const uint64_t INVALID_HANDLE = 0;
+
+int failures = 0;
+
+void check(bool condition, const std::string& description) {
+    if (condition) {
+        std::cout << "(c++) [ok] " << description << std::endl;
+    } else {
+        std::cout << "(c++) [FAILED] " << description << std::endl;
+        failures++;
+    }
+}
+
+std::string last_address;
+
+void address_callback(const char* address) {
+    last_address = address;
+}
+
+// the data passed to the callbacks is only valid during the call so it has to be copied
+struct CopiedMessage {
+    std::vector<uint8_t> message;
+    std::string sender_tag;
+};
+
+std::mutex received_mutex;
+std::condition_variable received_cv;
+std::vector<CopiedMessage> received_messages;
+
+void store_message_callback(ReceivedMessage received) {
+    CopiedMessage copied;
+    copied.message.assign(received.message, received.message + received.size);
+    if (received.sender_tag != nullptr) {
+        copied.sender_tag = received.sender_tag;
+    }
+    {
+        std::lock_guard<std::mutex> lock(received_mutex);
+        received_messages.push_back(copied);
+    }
+    received_cv.notify_all();
+}
+
+bool wait_for_messages(size_t count, std::chrono::seconds timeout) {
+    std::unique_lock<std::mutex> lock(received_mutex);
+    return received_cv.wait_for(lock, timeout, [count] { return received_messages.size() >= count; });
+}
+
+void test_invalid_inputs() {
+    const uint8_t payload[3] = {1, 2, 3};
+
+    check(client_get_address(INVALID_HANDLE, address_callback) == SELF_ADDR_ERROR, "address of unknown client");
+    check(client_receive(INVALID_HANDLE, 10, store_message_callback) == LISTEN_ERROR, "receiving on unknown client");
+    check(client_stop_listening(INVALID_HANDLE) == LISTEN_ERROR, "stopping listener of unknown client");
+    check(client_disconnect(INVALID_HANDLE) == CLIENT_DISCONNECT_ERROR, "disconnecting unknown client");
+    check(new_client(nullptr, nullptr, false, nullptr) == CLIENT_INIT_ERROR, "creating client without handle output");
+    check(client_send(INVALID_HANDLE, nullptr, payload, 3, -1) == RECIPIENT_NULL_ERROR, "sending to null recipient");
+    check(client_send(INVALID_HANDLE, "not-an-address", payload, 3, -1) == INVALID_RECIPIENT_ERROR, "sending to malformed recipient");
+    check(client_reply(INVALID_HANDLE, "not-a-tag", payload, 3) == INVALID_SENDER_TAG_ERROR, "replying to malformed sender tag");
+}
+
+void test_binary_messages_between_concurrent_clients() {
+    uint64_t sender = 0;
+    uint64_t receiver = 0;
+    check(new_client(nullptr, nullptr, false, &sender) == NO_ERROR, "creating sender client");
+    check(new_client(nullptr, nullptr, false, &receiver) == NO_ERROR, "creating receiver client");
+    check(sender != receiver, "clients have distinct handles");
+
+    check(client_get_address(receiver, address_callback) == NO_ERROR, "getting receiver address");
+    std::string receiver_address = last_address;
+
+    check(client_start_listening(receiver, store_message_callback) == NO_ERROR, "starting listener");
+
+    // binary payload including null bytes
+    const uint8_t payload[6] = {0, 1, 2, 0, 255, 254};
+    check(client_send(sender, receiver_address.c_str(), payload, sizeof(payload), 5) == NO_ERROR, "sending binary message");
+
+    check(wait_for_messages(1, std::chrono::seconds(60)), "message received by the listener");
+    CopiedMessage received;
+    {
+        std::lock_guard<std::mutex> lock(received_mutex);
+        received = received_messages.back();
+    }
+    check(received.message == std::vector<uint8_t>(payload, payload + sizeof(payload)), "payload matches");
+    check(!received.sender_tag.empty(), "sender tag is present");
+
+    check(client_stop_listening(receiver) == NO_ERROR, "stopping listener");
+
+    const char reply_message[] = "reply";
+    check(client_reply(receiver, received.sender_tag.c_str(), reinterpret_cast<const uint8_t*>(reply_message), strlen(reply_message)) == NO_ERROR, "replying with SURBs");
+    check(client_receive(sender, 60000, store_message_callback) == NO_ERROR, "reply received");
+    {
+        std::lock_guard<std::mutex> lock(received_mutex);
+        const std::vector<uint8_t>& reply = received_messages.back().message;
+        check(std::string(reply.begin(), reply.end()) == reply_message, "reply matches");
+    }
+
+    check(client_disconnect(sender) == NO_ERROR, "disconnecting sender");
+    check(client_disconnect(receiver) == NO_ERROR, "disconnecting receiver");
+    check(client_get_address(sender, address_callback) == SELF_ADDR_ERROR, "handle invalid after disconnecting");
+}
+
+int main() {
+    test_invalid_inputs();
+
+    // the tests talking to the actual network are only run if this variable is set
+    if (std::getenv("NYM_FFI_NETWORK_TESTS") != nullptr) {
+        test_binary_messages_between_concurrent_clients();
+    } else {
+        std::cout << "(c++) set NYM_FFI_NETWORK_TESTS to run tests requiring connection to the mixnet" << std::endl;
+    }
+
+    if (failures != 0) {
+        std::cout << "(c++) " << failures << " check(s) failed" << std::endl;
+        return 1;
+    }
+    return 0;
+}
@@ -13,6 +13,10 @@ pub mod types {
         ListenError = -6,
         RecipientNullError = -7,
         MessageNullError = -8,
+        InvalidRecipientError = -9,
+        InvalidSenderTagError = -10,
+        ClientDisconnectError = -11,
+        ReceiveTimeoutError = -12,
     }
 
     #[repr(C)]
@@ -30,6 +34,7 @@ pub mod types {
     }
 
     #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct CMessageCallback {
         pub callback: extern "C" fn(ReceivedMessage),
     }
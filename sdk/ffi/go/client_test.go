@@ -0,0 +1,160 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"os"
+	"testing"
+	"time"
+
+	"nymffi/go-nym/bindings"
+)
+
+// handle that is never assigned to any client
+const invalidHandle uint64 = 0
+
+// the tests talking to the actual network are only run if this variable is set
+const networkTestsEnv = "NYM_FFI_NETWORK_TESTS"
+
+func requireNetwork(t *testing.T) {
+	if os.Getenv(networkTestsEnv) == "" {
+		t.Skipf("set %s to run tests requiring connection to the mixnet", networkTestsEnv)
+	}
+}
+
+func newEphemeralClient(t *testing.T) uint64 {
+	handle, err := bindings.NewClient(bindings.ClientOptions{})
+	if err != nil {
+		t.Fatalf("failed to create client: %v", err)
+	}
+	t.Cleanup(func() {
+		// the client might have already been disconnected by the test itself
+		_ = bindings.ClientDisconnect(handle)
+	})
+	return handle
+}
+
+type channelListener struct {
+	received chan bindings.ReceivedMessage
+}
+
+func (l channelListener) OnMessage(message bindings.ReceivedMessage) {
+	l.received <- message
+}
+
+func TestInvalidHandleIsRejected(t *testing.T) {
+	if _, err := bindings.ClientAddress(invalidHandle); !errors.Is(err, bindings.ErrGoWrapErrorSelfAddrError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+	timeout := uint64(10)
+	if _, err := bindings.ClientReceive(invalidHandle, &timeout); !errors.Is(err, bindings.ErrGoWrapErrorListenError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+	if err := bindings.ClientStopListening(invalidHandle); !errors.Is(err, bindings.ErrGoWrapErrorListenError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+	if err := bindings.ClientDisconnect(invalidHandle); !errors.Is(err, bindings.ErrGoWrapErrorClientDisconnectError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+}
+
+func TestMalformedInputsAreRejected(t *testing.T) {
+	if err := bindings.ClientSend(invalidHandle, "not-an-address", []byte{1, 2, 3}, nil); !errors.Is(err, bindings.ErrGoWrapErrorInvalidRecipientError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+	if err := bindings.ClientReply(invalidHandle, []byte{1, 2, 3}, []byte{1, 2, 3}); !errors.Is(err, bindings.ErrGoWrapErrorInvalidSenderTagError) {
+		t.Errorf("unexpected error: %v", err)
+	}
+}
+
+func TestBinaryMessagesBetweenConcurrentClients(t *testing.T) {
+	requireNetwork(t)
+
+	sender := newEphemeralClient(t)
+	receiver := newEphemeralClient(t)
+
+	receiverAddress, err := bindings.ClientAddress(receiver)
+	if err != nil {
+		t.Fatalf("failed to get address: %v", err)
+	}
+
+	listener := channelListener{received: make(chan bindings.ReceivedMessage, 1)}
+	if err := bindings.ClientStartListening(receiver, listener); err != nil {
+		t.Fatalf("failed to start listening: %v", err)
+	}
+
+	payload := []byte{0, 1, 2, 0, 255, 254}
+	surbs := uint32(5)
+	if err := bindings.ClientSend(sender, receiverAddress, payload, &surbs); err != nil {
+		t.Fatalf("failed to send message: %v", err)
+	}
+
+	var received bindings.ReceivedMessage
+	select {
+	case received = <-listener.received:
+	case <-time.After(60 * time.Second):
+		t.Fatal("timed out waiting for the message")
+	}
+	if !bytes.Equal(received.Message, payload) {
+		t.Fatalf("received unexpected payload: %v", received.Message)
+	}
+	if received.SenderTag == nil {
+		t.Fatal("message did not include sender tag")
+	}
+
+	// once the listener is stopped, the messages can be received directly
+	if err := bindings.ClientStopListening(receiver); err != nil {
+		t.Fatalf("failed to stop listening: %v", err)
+	}
+	if err := bindings.ClientReply(receiver, *received.SenderTag, []byte("reply")); err != nil {
+		t.Fatalf("failed to reply: %v", err)
+	}
+
+	timeout := uint64(60_000)
+	reply, err := bindings.ClientReceive(sender, &timeout)
+	if err != nil {
+		t.Fatalf("failed to receive reply: %v", err)
+	}
+	if reply == nil || string(reply.Message) != "reply" {
+		t.Fatalf("received unexpected reply: %v", reply)
+	}
+
+	if err := bindings.ClientDisconnect(sender); err != nil {
+		t.Fatalf("failed to disconnect: %v", err)
+	}
+	if _, err := bindings.ClientAddress(sender); err == nil {
+		t.Fatal("disconnected client handle is still valid")
+	}
+}
+
+func TestPersistentClientKeepsItsAddress(t *testing.T) {
+	requireNetwork(t)
+
+	storageDir := t.TempDir()
+	options := bindings.ClientOptions{StorageDir: &storageDir}
+
+	handle, err := bindings.NewClient(options)
+	if err != nil {
+		t.Fatalf("failed to create client: %v", err)
+	}
+	address, err := bindings.ClientAddress(handle)
+	if err != nil {
+		t.Fatalf("failed to get address: %v", err)
+	}
+	if err := bindings.ClientDisconnect(handle); err != nil {
+		t.Fatalf("failed to disconnect: %v", err)
+	}
+
+	handle, err = bindings.NewClient(options)
+	if err != nil {
+		t.Fatalf("failed to recreate client: %v", err)
+	}
+	defer bindings.ClientDisconnect(handle)
+	reloadedAddress, err := bindings.ClientAddress(handle)
+	if err != nil {
+		t.Fatalf("failed to get address: %v", err)
+	}
+	if address != reloadedAddress {
+		t.Fatalf("address changed from %s to %s", address, reloadedAddress)
+	}
+}
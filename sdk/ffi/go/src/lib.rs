@@ -1,8 +1,10 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use nym_sdk::mixnet::Recipient;
-use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
+use nym_ffi_shared::clients;
+use nym_sdk::mixnet::{Recipient, ReconstructedMessage};
+use nym_sphinx_anonymous_replies::requests::{AnonymousSenderTag, SENDER_TAG_SIZE};
+use std::time::Duration;
 uniffi::include_scaffolding!("bindings");
 
 #[derive(Debug, thiserror::Error)]
@@ -19,6 +21,12 @@ enum GoWrapError {
     ReplyError {},
     #[error("Could not start listening")]
     ListenError {},
+    #[error("Provided recipient is not a valid Nym address")]
+    InvalidRecipientError {},
+    #[error("Provided sender tag is malformed")]
+    InvalidSenderTagError {},
+    #[error("Error disconnecting client")]
+    ClientDisconnectError {},
 }
 
 #[no_mangle]
@@ -80,3 +88,105 @@ fn listen_for_incoming() -> Result<IncomingMessage, GoWrapError> {
         Err(_) => Err(GoWrapError::ListenError {}),
     }
 }
+
+pub struct ClientOptions {
+    storage_dir: Option<String>,
+    gateway: Option<String>,
+    force_tls: bool,
+}
+
+impl From<ClientOptions> for clients::ClientOptions {
+    fn from(options: ClientOptions) -> Self {
+        clients::ClientOptions {
+            storage_dir: options.storage_dir.map(Into::into),
+            gateway: options.gateway,
+            force_tls: options.force_tls,
+        }
+    }
+}
+
+pub struct ReceivedMessage {
+    message: Vec<u8>,
+    sender_tag: Option<Vec<u8>>,
+}
+
+impl From<ReconstructedMessage> for ReceivedMessage {
+    fn from(received: ReconstructedMessage) -> Self {
+        ReceivedMessage {
+            message: received.message,
+            sender_tag: received.sender_tag.map(|tag| tag.to_bytes().to_vec()),
+        }
+    }
+}
+
+pub trait MessageListener: Send + Sync + std::fmt::Debug {
+    fn on_message(&self, message: ReceivedMessage);
+}
+
+#[no_mangle]
+fn new_client(options: ClientOptions) -> Result<u64, GoWrapError> {
+    clients::new_client_internal(options.into()).map_err(|_| GoWrapError::ClientInitError {})
+}
+
+#[no_mangle]
+fn client_address(handle: u64) -> Result<String, GoWrapError> {
+    match clients::client_address_internal(handle) {
+        Ok(addr) => Ok(addr.to_string()),
+        Err(_) => Err(GoWrapError::SelfAddrError {}),
+    }
+}
+
+#[no_mangle]
+fn client_send(
+    handle: u64,
+    recipient: String,
+    message: Vec<u8>,
+    reply_surbs: Option<u32>,
+) -> Result<(), GoWrapError> {
+    let recipient = Recipient::try_from_base58_string(recipient)
+        .map_err(|_| GoWrapError::InvalidRecipientError {})?;
+    clients::client_send_internal(handle, recipient, message, reply_surbs)
+        .map_err(|_| GoWrapError::SendMsgError {})
+}
+
+#[no_mangle]
+fn client_reply(handle: u64, sender_tag: Vec<u8>, message: Vec<u8>) -> Result<(), GoWrapError> {
+    let sender_tag: [u8; SENDER_TAG_SIZE] = sender_tag
+        .try_into()
+        .map_err(|_| GoWrapError::InvalidSenderTagError {})?;
+    let sender_tag = AnonymousSenderTag::from_bytes(sender_tag);
+    clients::client_reply_internal(handle, sender_tag, message)
+        .map_err(|_| GoWrapError::ReplyError {})
+}
+
+#[no_mangle]
+fn client_receive(
+    handle: u64,
+    timeout_ms: Option<u64>,
+) -> Result<Option<ReceivedMessage>, GoWrapError> {
+    match clients::client_receive_internal(handle, timeout_ms.map(Duration::from_millis)) {
+        Ok(received) => Ok(received.map(Into::into)),
+        Err(_) => Err(GoWrapError::ListenError {}),
+    }
+}
+
+#[no_mangle]
+fn client_start_listening(
+    handle: u64,
+    listener: Box<dyn MessageListener>,
+) -> Result<(), GoWrapError> {
+    let callback =
+        Box::new(move |received: ReconstructedMessage| listener.on_message(received.into()));
+    clients::client_start_listening_internal(handle, callback)
+        .map_err(|_| GoWrapError::ListenError {})
+}
+
+#[no_mangle]
+fn client_stop_listening(handle: u64) -> Result<(), GoWrapError> {
+    clients::client_stop_listening_internal(handle).map_err(|_| GoWrapError::ListenError {})
+}
+
+#[no_mangle]
+fn client_disconnect(handle: u64) -> Result<(), GoWrapError> {
+    clients::client_disconnect_internal(handle).map_err(|_| GoWrapError::ClientDisconnectError {})
+}
@@ -0,0 +1,366 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+// Handle-based clients: as opposed to the single global client, any number of those can be running
+// at the same time. Each client is identified by an opaque handle returned on creation that
+// has to be passed to every subsequent call.
+
+use crate::RUNTIME;
+use anyhow::{anyhow, bail};
+use lazy_static::lazy_static;
+use nym_sdk::mixnet::{
+    IncludedSurbs, MixnetClient, MixnetClientBuilder, MixnetClientSender, MixnetMessageSender,
+    Recipient, ReconstructedMessage, StoragePaths,
+};
+use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+pub type ClientHandle = u64;
+
+pub type MessageCallback = Box<dyn Fn(ReconstructedMessage) + Send + Sync + 'static>;
+
+lazy_static! {
+    static ref CLIENTS: Mutex<HashMap<ClientHandle, ClientEntry>> = Mutex::new(HashMap::new());
+    // start from 1 so that 0 could be treated as an invalid handle by the bindings
+    static ref NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+}
+
+/// Options used for creating new clients.
+#[derive(Debug, Default, Clone)]
+pub struct ClientOptions {
+    /// Directory in which the client keys and data are persisted. If not specified,
+    /// an ephemeral client is created which is going to have a new identity on every start.
+    pub storage_dir: Option<PathBuf>,
+
+    /// Identity key of the gateway the client should register with. If not specified,
+    /// a gateway is chosen at random (or the previously registered one is used for persistent clients).
+    pub gateway: Option<String>,
+
+    /// Specifies whether the client must only connect to gateways using TLS.
+    pub force_tls: bool,
+}
+
+// the receiving half of the client alongside any messages that were received but not yet consumed
+struct ClientReceiver {
+    client: Option<MixnetClient>,
+    buffered: VecDeque<ReconstructedMessage>,
+}
+
+impl ClientReceiver {
+    async fn next_message(&mut self) -> Option<ReconstructedMessage> {
+        loop {
+            if let Some(message) = self.buffered.pop_front() {
+                return Some(message);
+            }
+            let client = self.client.as_mut()?;
+            self.buffered.extend(client.wait_for_messages().await?);
+        }
+    }
+}
+
+struct ClientEntry {
+    address: Recipient,
+    sender: MixnetClientSender,
+    receiver: Arc<tokio::sync::Mutex<ClientReceiver>>,
+    shutdown: watch::Sender<bool>,
+    listener: Option<Listener>,
+}
+
+struct Listener {
+    stop: watch::Sender<bool>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+impl Listener {
+    fn stop(self) {
+        let _ = self.stop.send(true);
+
+        // if we're stopping the listener from within its own callback, we can't wait for it
+        // (it will exit on its own once the callback returns)
+        if self.thread.thread().id() != std::thread::current().id() {
+            let _ = self.thread.join();
+        }
+    }
+}
+
+fn with_client<F, T>(handle: ClientHandle, f: F) -> anyhow::Result<T>
+where
+    F: FnOnce(&mut ClientEntry) -> anyhow::Result<T>,
+{
+    let mut clients = CLIENTS
+        .lock()
+        .map_err(|_| anyhow!("could not lock CLIENTS"))?;
+    let entry = clients
+        .get_mut(&handle)
+        .ok_or_else(|| anyhow!("there is no client with handle {handle}"))?;
+    f(entry)
+}
+
+async fn connect(options: ClientOptions) -> anyhow::Result<MixnetClient> {
+    let client = match options.storage_dir {
+        Some(storage_dir) => {
+            let storage_paths = StoragePaths::new_from_dir(storage_dir)?;
+            let mut builder = MixnetClientBuilder::new_with_default_storage(storage_paths)
+                .await?
+                .force_tls(options.force_tls);
+            if let Some(gateway) = options.gateway {
+                builder = builder.request_gateway(gateway);
+            }
+            builder.build()?.connect_to_mixnet().await?
+        }
+        None => {
+            let mut builder = MixnetClientBuilder::new_ephemeral().force_tls(options.force_tls);
+            if let Some(gateway) = options.gateway {
+                builder = builder.request_gateway(gateway);
+            }
+            builder.build()?.connect_to_mixnet().await?
+        }
+    };
+    Ok(client)
+}
+
+pub fn new_client_internal(options: ClientOptions) -> anyhow::Result<ClientHandle> {
+    let client = RUNTIME.block_on(connect(options))?;
+
+    let (shutdown, _) = watch::channel(false);
+    let entry = ClientEntry {
+        address: *client.nym_address(),
+        sender: client.split_sender(),
+        receiver: Arc::new(tokio::sync::Mutex::new(ClientReceiver {
+            client: Some(client),
+            buffered: VecDeque::new(),
+        })),
+        shutdown,
+        listener: None,
+    };
+
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    CLIENTS
+        .lock()
+        .map_err(|_| anyhow!("could not lock CLIENTS"))?
+        .insert(handle, entry);
+    Ok(handle)
+}
+
+pub fn client_address_internal(handle: ClientHandle) -> anyhow::Result<Recipient> {
+    with_client(handle, |entry| Ok(entry.address))
+}
+
+/// Sends the message to the provided recipient alongside the specified number of reply SURBs.
+/// If the number is not provided, the default amount is attached.
+pub fn client_send_internal(
+    handle: ClientHandle,
+    recipient: Recipient,
+    message: Vec<u8>,
+    reply_surbs: Option<u32>,
+) -> anyhow::Result<()> {
+    let sender = with_client(handle, |entry| Ok(entry.sender.clone()))?;
+    let surbs = reply_surbs.map(IncludedSurbs::new).unwrap_or_default();
+
+    RUNTIME.block_on(sender.send_message(recipient, message, surbs))?;
+    Ok(())
+}
+
+pub fn client_reply_internal(
+    handle: ClientHandle,
+    recipient: AnonymousSenderTag,
+    message: Vec<u8>,
+) -> anyhow::Result<()> {
+    let sender = with_client(handle, |entry| Ok(entry.sender.clone()))?;
+
+    RUNTIME.block_on(sender.send_reply(recipient, message))?;
+    Ok(())
+}
+
+/// Waits for the next incoming message for up to the specified amount of time
+/// (or indefinitely if not provided). Returns `None` if no message arrived in time.
+pub fn client_receive_internal(
+    handle: ClientHandle,
+    timeout: Option<Duration>,
+) -> anyhow::Result<Option<ReconstructedMessage>> {
+    let (receiver, mut shutdown) = with_client(handle, |entry| {
+        if entry.listener.is_some() {
+            bail!("client {handle} is already consuming its messages with a listener");
+        }
+        Ok((Arc::clone(&entry.receiver), entry.shutdown.subscribe()))
+    })?;
+
+    RUNTIME.block_on(async move {
+        let receive = async {
+            let mut receiver = receiver.lock().await;
+            tokio::select! {
+                message = receiver.next_message() => {
+                    message.ok_or_else(|| anyhow!("client {handle} has stopped receiving messages"))
+                }
+                _ = shutdown.changed() => bail!("client {handle} got disconnected"),
+            }
+        };
+
+        match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, receive).await {
+                Ok(received) => received.map(Some),
+                Err(_timeout) => Ok(None),
+            },
+            None => receive.await.map(Some),
+        }
+    })
+}
+
+/// Starts a background thread that invokes the provided callback on every incoming message
+/// until either `client_stop_listening_internal` is called or the client gets disconnected.
+/// The callback is executed outside the async runtime, so it's allowed to call back into
+/// any of the (blocking) client functions.
+pub fn client_start_listening_internal(
+    handle: ClientHandle,
+    callback: MessageCallback,
+) -> anyhow::Result<()> {
+    with_client(handle, |entry| {
+        if entry.listener.is_some() {
+            bail!("client {handle} already has an active listener");
+        }
+
+        let receiver = Arc::clone(&entry.receiver);
+        let mut shutdown = entry.shutdown.subscribe();
+        let (stop, mut stopped) = watch::channel(false);
+
+        let thread = std::thread::spawn(move || {
+            loop {
+                // note: the lock is not held while the callback is executing
+                let message = RUNTIME.block_on(async {
+                    let mut receiver = receiver.lock().await;
+                    tokio::select! {
+                        biased;
+                        _ = stopped.changed() => None,
+                        _ = shutdown.changed() => None,
+                        message = receiver.next_message() => message,
+                    }
+                });
+                match message {
+                    Some(message) => callback(message),
+                    None => break,
+                }
+            }
+        });
+
+        entry.listener = Some(Listener { stop, thread });
+        Ok(())
+    })
+}
+
+pub fn client_stop_listening_internal(handle: ClientHandle) -> anyhow::Result<()> {
+    let listener = with_client(handle, |entry| Ok(entry.listener.take()))?;
+    let Some(listener) = listener else {
+        bail!("client {handle} does not have an active listener");
+    };
+
+    listener.stop();
+    Ok(())
+}
+
+/// Stops any active listeners, interrupts pending receives and shuts down the client.
+/// The handle is no longer valid afterwards.
+pub fn client_disconnect_internal(handle: ClientHandle) -> anyhow::Result<()> {
+    let entry = CLIENTS
+        .lock()
+        .map_err(|_| anyhow!("could not lock CLIENTS"))?
+        .remove(&handle)
+        .ok_or_else(|| anyhow!("there is no client with handle {handle}"))?;
+
+    // wake up anyone waiting for messages so that the receiver lock could be released
+    let _ = entry.shutdown.send(true);
+    if let Some(listener) = entry.listener {
+        listener.stop();
+    }
+
+    RUNTIME.block_on(async move {
+        let client = entry.receiver.lock().await.client.take();
+        if let Some(client) = client {
+            client.disconnect().await;
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // handles start from 1, so this one is never assigned
+    const UNKNOWN_HANDLE: ClientHandle = 0;
+
+    fn recipient() -> Recipient {
+        Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap()
+    }
+
+    fn message(content: &[u8]) -> ReconstructedMessage {
+        ReconstructedMessage {
+            message: content.to_vec(),
+            sender_tag: None,
+        }
+    }
+
+    #[test]
+    fn unknown_handles_are_rejected() {
+        assert!(client_address_internal(UNKNOWN_HANDLE).is_err());
+        assert!(client_send_internal(UNKNOWN_HANDLE, recipient(), vec![1, 2, 3], None).is_err());
+        assert!(client_reply_internal(UNKNOWN_HANDLE, [42u8; 16].into(), vec![1, 2, 3]).is_err());
+        assert!(client_receive_internal(UNKNOWN_HANDLE, Some(Duration::from_millis(10))).is_err());
+        assert!(client_start_listening_internal(UNKNOWN_HANDLE, Box::new(|_| {})).is_err());
+        assert!(client_stop_listening_internal(UNKNOWN_HANDLE).is_err());
+        assert!(client_disconnect_internal(UNKNOWN_HANDLE).is_err());
+    }
+
+    #[test]
+    fn buffered_messages_are_returned_in_order() {
+        let mut receiver = ClientReceiver {
+            client: None,
+            buffered: VecDeque::from([message(b"first"), message(b"second")]),
+        };
+
+        RUNTIME.block_on(async {
+            assert_eq!(receiver.next_message().await.unwrap().message, b"first");
+            assert_eq!(receiver.next_message().await.unwrap().message, b"second");
+
+            // the client is already gone, so nothing else is ever going to arrive
+            assert!(receiver.next_message().await.is_none());
+        })
+    }
+
+    #[test]
+    fn stopping_listener_waits_for_its_thread() {
+        let (stop, mut stopped) = watch::channel(false);
+        let (finished_tx, finished_rx) = std::sync::mpsc::channel();
+
+        let thread = std::thread::spawn(move || {
+            RUNTIME.block_on(async {
+                let _ = stopped.changed().await;
+            });
+            finished_tx.send(()).unwrap();
+        });
+
+        Listener { stop, thread }.stop();
+        assert!(finished_rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn listener_can_be_stopped_from_its_own_thread() {
+        let (stop, _stopped) = watch::channel(false);
+        let (listener_tx, listener_rx) = std::sync::mpsc::channel::<Listener>();
+        let (finished_tx, finished_rx) = std::sync::mpsc::channel();
+
+        // this is what happens if `client_stop_listening_internal` is called from within the callback
+        let thread = std::thread::spawn(move || {
+            listener_rx.recv().unwrap().stop();
+            finished_tx.send(()).unwrap();
+        });
+        listener_tx.send(Listener { stop, thread }).unwrap();
+
+        // had the listener attempted to join its own thread, it would have never finished
+        assert!(finished_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
@@ -10,6 +10,8 @@ use nym_sphinx_anonymous_replies::requests::AnonymousSenderTag;
 use std::sync::{Arc, Mutex};
 use tokio::runtime::Runtime;
 
+pub mod clients;
+
 // NYM_CLIENT: Static reference (only init-ed once) to:
 //     - Arc: share ownership
 //     - Mutex: thread-safe way to share data between threads
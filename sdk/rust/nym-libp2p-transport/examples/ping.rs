@@ -0,0 +1,100 @@
+// Copyright 2018 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Ping example
+//!
+//! In the first terminal window, run:
+//!
+//! ```sh
+//! cargo run -p nym-libp2p-transport --example ping
+//! ```
+//!
+//! It will print the PeerId and the listening address, e.g. `Listening on
+//! "/dns/<nym address>"`
+//!
+//! In the second terminal window, start a new instance of the example with:
+//!
+//! ```sh
+//! cargo run -p nym-libp2p-transport --example ping -- /dns/<nym address>
+//! ```
+//!
+//! The two nodes establish a connection through the mixnet, negotiate the ping protocol
+//! and begin pinging each other.
+
+use futures::StreamExt;
+use libp2p::swarm::SwarmEvent;
+use libp2p::{identity, ping, Multiaddr, PeerId};
+use log::{info, LevelFilter};
+use nym_libp2p_transport::NymTransport;
+use nym_sdk::mixnet::MixnetClient;
+use std::error::Error;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    pretty_env_logger::formatted_timed_builder()
+        .filter_level(LevelFilter::Warn)
+        .filter(Some("ping"), LevelFilter::Debug)
+        .init();
+
+    let local_key = identity::Keypair::generate_ed25519();
+    let local_peer_id = PeerId::from(local_key.public());
+    info!("Local peer id: {local_peer_id:?}");
+
+    let client = MixnetClient::connect_new().await?;
+    let transport = NymTransport::new(client, local_key.clone()).await?;
+    let listen_addr = transport.listen_addr().clone();
+
+    let mut swarm = libp2p::SwarmBuilder::with_existing_identity(local_key)
+        .with_tokio()
+        .with_other_transport(|_| transport)?
+        .with_behaviour(|_| ping::Behaviour::default())?
+        // keep the connection alive, so that a continuous sequence of pings can be observed
+        .with_swarm_config(|cfg| cfg.with_idle_connection_timeout(Duration::from_secs(u64::MAX)))
+        .build();
+
+    swarm.listen_on(listen_addr)?;
+
+    // Dial the peer identified by the multi-address given as the second
+    // command-line argument, if any.
+    if let Some(addr) = std::env::args().nth(1) {
+        let remote: Multiaddr = addr.parse()?;
+        swarm.dial(remote)?;
+        info!("Dialed {addr}")
+    }
+
+    let mut total_ping_rtt: Duration = Duration::from_micros(0);
+    let mut counter: u128 = 0;
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::NewListenAddr { address, .. } => info!("Listening on {address:?}"),
+            SwarmEvent::Behaviour(ping::Event { peer, result, .. }) => {
+                let rtt = result?;
+                counter += 1;
+                total_ping_rtt += rtt;
+                let average_ping_rtt = Duration::from_micros(
+                    (total_ping_rtt.as_micros() / counter).try_into().unwrap(),
+                );
+                info!("Ping RTT with {peer}: {rtt:?} AVERAGE RTT: ({counter} pings): {average_ping_rtt:?}");
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use libp2p::core::{muxing::StreamMuxerEvent, PeerId, StreamMuxer};
+use libp2p::core::{muxing::StreamMuxerEvent, StreamMuxer};
+use libp2p::PeerId;
 use log::debug;
 use nym_sphinx::addressing::clients::Recipient;
 use std::{
@@ -15,12 +16,12 @@ use tokio::sync::{
     oneshot,
 };
 
-use super::error::Error;
-use super::message::{
+use crate::error::Error;
+use crate::message::{
     ConnectionId, Message, OutboundMessage, SubstreamId, SubstreamMessage, SubstreamMessageType,
     TransportMessage,
 };
-use super::substream::Substream;
+use crate::substream::Substream;
 
 /// Connection represents the result of a connection setup process.
 /// It implements `StreamMuxer` and thus has stream multiplexing built in.
@@ -92,6 +93,16 @@ impl Connection {
         }
     }
 
+    /// Returns the [`PeerId`] of the remote peer.
+    pub fn peer_id(&self) -> PeerId {
+        self.peer_id
+    }
+
+    /// Returns the Nym address of the remote peer.
+    pub fn remote_recipient(&self) -> Recipient {
+        self.remote_recipient
+    }
+
     fn new_outbound_substream(&mut self) -> Result<Substream, Error> {
         let substream_id = SubstreamId::generate();
         let nonce = self.message_nonce.fetch_add(1, Ordering::SeqCst);
@@ -153,8 +164,10 @@ impl Connection {
         }
 
         // notify substream that it's closed
-        let close_tx = self.substream_close_txs.remove(&substream_id);
-        close_tx.unwrap().send(()).unwrap();
+        // NOTE: this ignores send errors, which is fine because the substream might have been dropped
+        if let Some(close_tx) = self.substream_close_txs.remove(&substream_id) {
+            close_tx.send(()).ok();
+        }
 
         // notify poll_close that the substream is closed
         self.close_tx
@@ -240,10 +253,15 @@ impl StreamMuxer for Connection {
                 }
                 SubstreamMessageType::Data(data) => {
                     debug!("SubstreamMessageType::Data: {:?}", &data);
-                    let inbound_tx = self
-                        .substream_inbound_txs
-                        .get_mut(&msg.substream_id)
-                        .expect("must have a substream channel for substream");
+                    let Some(inbound_tx) = self.substream_inbound_txs.get_mut(&msg.substream_id)
+                    else {
+                        // the remote might still be sending data it wrote before it saw our close
+                        debug!(
+                            "SubstreamMessageType::Data no substream for ID: {:?}",
+                            &msg.substream_id
+                        );
+                        continue;
+                    };
 
                     // NOTE: this ignores channel closed errors, which is fine because the substream
                     // might have been closed/dropped
@@ -277,13 +295,217 @@ impl PendingConnection {
 
 #[cfg(test)]
 mod test {
-    use super::super::message::InboundMessage;
-    use super::super::mixnet::initialize_mixnet;
     use super::*;
+    use crate::message::InboundMessage;
+    use crate::mixnet::initialize_mixnet;
     use futures::future::poll_fn;
     use futures::{AsyncReadExt, AsyncWriteExt, FutureExt};
     use nym_sdk::mixnet::MixnetClient;
 
+    const SUBSTREAMS: usize = 4;
+
+    fn test_recipient() -> Recipient {
+        Recipient::try_from_base58_string("D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN").unwrap()
+    }
+
+    // forwards everything written by one side of the connection directly to the other one,
+    // i.e. it acts as a perfect mixnet that never delays nor reorders any messages
+    fn forward(
+        outbound_rx: &mut UnboundedReceiver<OutboundMessage>,
+        inbound_tx: &UnboundedSender<SubstreamMessage>,
+    ) {
+        while let Ok(outbound) = outbound_rx.try_recv() {
+            match outbound.message {
+                Message::TransportMessage(msg) => inbound_tx.send(msg.message).unwrap(),
+                other => panic!("unexpected message: {other:?}"),
+            }
+        }
+    }
+
+    async fn read_message(substream: &mut Substream, expected_len: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; expected_len];
+        substream.read_exact(&mut buf).await.unwrap();
+        buf
+    }
+
+    struct ConnectedPair {
+        dialer: Connection,
+        dialer_inbound_tx: UnboundedSender<SubstreamMessage>,
+        dialer_outbound_rx: UnboundedReceiver<OutboundMessage>,
+
+        listener: Connection,
+        listener_inbound_tx: UnboundedSender<SubstreamMessage>,
+        listener_outbound_rx: UnboundedReceiver<OutboundMessage>,
+    }
+
+    impl ConnectedPair {
+        fn new() -> Self {
+            let connection_id = ConnectionId::generate();
+
+            let (dialer_inbound_tx, dialer_inbound_rx) = unbounded_channel();
+            let (dialer_outbound_tx, dialer_outbound_rx) = unbounded_channel();
+            let (listener_inbound_tx, listener_inbound_rx) = unbounded_channel();
+            let (listener_outbound_tx, listener_outbound_rx) = unbounded_channel();
+
+            ConnectedPair {
+                dialer: Connection::new(
+                    PeerId::random(),
+                    test_recipient(),
+                    connection_id.clone(),
+                    dialer_inbound_rx,
+                    dialer_outbound_tx,
+                ),
+                dialer_inbound_tx,
+                dialer_outbound_rx,
+                listener: Connection::new(
+                    PeerId::random(),
+                    test_recipient(),
+                    connection_id,
+                    listener_inbound_rx,
+                    listener_outbound_tx,
+                ),
+                listener_inbound_tx,
+                listener_outbound_rx,
+            }
+        }
+
+        // deliver everything the dialer has sent and let the listener process it
+        fn deliver_to_listener(&mut self) {
+            forward(&mut self.dialer_outbound_rx, &self.listener_inbound_tx);
+            poll_fn(|cx| Pin::new(&mut self.listener).poll(cx)).now_or_never();
+        }
+
+        // deliver everything the listener has sent and let the dialer process it
+        fn deliver_to_dialer(&mut self) {
+            forward(&mut self.listener_outbound_rx, &self.dialer_inbound_tx);
+            poll_fn(|cx| Pin::new(&mut self.dialer).poll(cx)).now_or_never();
+        }
+
+        // opens `n` substreams from the dialer and returns them alongside their listener counterparts
+        async fn open_substreams(&mut self, n: usize) -> Vec<(Substream, Substream)> {
+            let mut dialer_substreams = Vec::with_capacity(n);
+            for _ in 0..n {
+                let substream = poll_fn(|cx| Pin::new(&mut self.dialer).poll_outbound(cx))
+                    .await
+                    .unwrap();
+                dialer_substreams.push(substream);
+            }
+            assert_eq!(self.dialer.pending_substreams.len(), n);
+
+            self.deliver_to_listener();
+            let mut listener_substreams = HashMap::new();
+            for _ in 0..n {
+                let substream = poll_fn(|cx| Pin::new(&mut self.listener).poll_inbound(cx))
+                    .now_or_never()
+                    .expect("the inbound substream should be ready")
+                    .unwrap();
+                listener_substreams.insert(substream.substream_id.clone(), substream);
+            }
+
+            self.deliver_to_dialer();
+            assert!(self.dialer.pending_substreams.is_empty());
+
+            dialer_substreams
+                .into_iter()
+                .map(|dialer_substream| {
+                    let listener_substream = listener_substreams
+                        .remove(&dialer_substream.substream_id)
+                        .expect("the listener should have a matching substream");
+                    (dialer_substream, listener_substream)
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_substream_multiplexing() {
+        let mut pair = ConnectedPair::new();
+        let mut substreams = pair.open_substreams(SUBSTREAMS).await;
+
+        // interleave the writes from all the substreams on the single connection
+        for round in 0..3 {
+            for (i, (dialer_substream, _)) in substreams.iter_mut().enumerate() {
+                let data = format!("round {round} substream {i}");
+                dialer_substream.write_all(data.as_bytes()).await.unwrap();
+            }
+        }
+        pair.deliver_to_listener();
+
+        // and make sure each of them only got its own data back in order
+        for (i, (_, listener_substream)) in substreams.iter_mut().enumerate() {
+            for round in 0..3 {
+                let expected = format!("round {round} substream {i}");
+                let received = read_message(listener_substream, expected.len()).await;
+                assert_eq!(received, expected.as_bytes());
+            }
+        }
+
+        // the same has to work in the other direction
+        for (i, (_, listener_substream)) in substreams.iter_mut().enumerate() {
+            let data = format!("response on substream {i}");
+            listener_substream.write_all(data.as_bytes()).await.unwrap();
+        }
+        pair.deliver_to_dialer();
+
+        for (i, (dialer_substream, _)) in substreams.iter_mut().enumerate() {
+            let expected = format!("response on substream {i}");
+            let received = read_message(dialer_substream, expected.len()).await;
+            assert_eq!(received, expected.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_substream_close_does_not_affect_others() {
+        let mut pair = ConnectedPair::new();
+        let mut substreams = pair.open_substreams(SUBSTREAMS).await;
+
+        // close the first substream from the dialer side
+        let (mut closed_dialer, mut closed_listener) = substreams.remove(0);
+        closed_dialer.close().await.unwrap();
+        pair.deliver_to_listener();
+
+        // the listener has to be notified about the closed substream
+        assert!(poll_fn(|cx| Pin::new(&mut pair.listener).poll_close(cx))
+            .now_or_never()
+            .unwrap()
+            .is_ok());
+        closed_listener.write_all(b"hello").await.unwrap_err();
+        closed_dialer.write_all(b"hello").await.unwrap_err();
+
+        // while all the remaining substreams are still fully operational
+        for (i, (dialer_substream, _)) in substreams.iter_mut().enumerate() {
+            let data = format!("still open {i}");
+            dialer_substream.write_all(data.as_bytes()).await.unwrap();
+        }
+        pair.deliver_to_listener();
+
+        for (i, (_, listener_substream)) in substreams.iter_mut().enumerate() {
+            let expected = format!("still open {i}");
+            let received = read_message(listener_substream, expected.len()).await;
+            assert_eq!(received, expected.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_ignores_data_for_unknown_substream() {
+        let mut pair = ConnectedPair::new();
+        let mut substreams = pair.open_substreams(1).await;
+
+        pair.listener_inbound_tx
+            .send(SubstreamMessage::new_with_data(
+                SubstreamId::generate(),
+                b"nobody is listening".to_vec(),
+            ))
+            .unwrap();
+
+        let (dialer_substream, listener_substream) = &mut substreams[0];
+        dialer_substream.write_all(b"hello").await.unwrap();
+        pair.deliver_to_listener();
+
+        let received = read_message(listener_substream, 5).await;
+        assert_eq!(received, b"hello");
+    }
+
     async fn inbound_receive_and_send(
         connection_id: ConnectionId,
         mixnet_inbound_rx: &mut UnboundedReceiver<InboundMessage>,
@@ -306,6 +528,7 @@ mod test {
     }
 
     #[tokio::test]
+    #[ignore] // requires connection to the mixnet
     async fn test_connection_stream_muxer() {
         let client = MixnetClient::connect_new().await.unwrap();
         let (sender_address, mut sender_mixnet_inbound_rx, sender_outbound_tx) =
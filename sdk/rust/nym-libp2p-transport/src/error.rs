@@ -1,16 +1,15 @@
-use libp2p::core::multiaddr;
 use nym_sphinx::addressing::clients::RecipientFormattingError;
 
-use super::message::SubstreamId;
+use crate::message::SubstreamId;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("unimplemented")]
-    Unimplemented,
-    #[error("failed to format multiaddress from nym address")]
-    FailedToFormatMultiaddr(#[from] multiaddr::Error),
     #[error("unexpected protocol in multiaddress")]
     InvalidProtocolForMultiaddr,
+    #[error("the transport is already listening on its nym address")]
+    AlreadyListening,
+    #[error("received ConnectionRequest but the transport is not listening")]
+    NotListening,
     #[error("failed to decode message")]
     InvalidMessageBytes,
     #[error("no connection found for ConnectionResponse")]
@@ -57,7 +56,7 @@ pub enum Error {
     InboundSendFailure(String),
     #[error("failed to send new connection; receiver dropped")]
     ConnectionSendFailure,
-    #[error("failed to send initial TransportEvent::NewAddress")]
+    #[error("failed to send TransportEvent::NewAddress")]
     SendErrorTransportEvent,
     #[error("dial timed out")]
     DialTimeout(#[from] tokio::time::error::Elapsed),
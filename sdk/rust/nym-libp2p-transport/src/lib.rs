@@ -0,0 +1,58 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! libp2p [`Transport`](libp2p::core::Transport) using the Nym mixnet.
+//!
+//! [`NymTransport`] wraps a connected [`MixnetClient`](nym_sdk::mixnet::MixnetClient) and lets
+//! any libp2p swarm dial and accept peers through the mixnet. Every established [`Connection`]
+//! implements [`StreamMuxer`](libp2p::core::StreamMuxer), so no additional upgrade
+//! (i.e. security or multiplexing protocol) is required on top of it.
+//!
+//! Since there is no dedicated multiaddress protocol for the Nym addresses, they are carried
+//! in a `/dns` component, e.g. `/dns/<identity>.<encryption>@<gateway>`. Use
+//! [`nym_address_to_multiaddress`] and [`multiaddress_to_nym_address`] to convert between the two.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use libp2p::identity::Keypair;
+//! use nym_libp2p_transport::NymTransport;
+//! use nym_sdk::mixnet::MixnetClient;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let local_key = Keypair::generate_ed25519();
+//!     let client = MixnetClient::connect_new().await.unwrap();
+//!     let transport = NymTransport::new(client, local_key.clone()).await.unwrap();
+//!     let listen_addr = transport.listen_addr().clone();
+//!
+//!     let mut swarm = libp2p::SwarmBuilder::with_existing_identity(local_key)
+//!         .with_tokio()
+//!         .with_other_transport(|_| transport)
+//!         .unwrap()
+//!         .with_behaviour(|_| libp2p::ping::Behaviour::default())
+//!         .unwrap()
+//!         .build();
+//!
+//!     swarm.listen_on(listen_addr).unwrap();
+//! }
+//! ```
+
+mod connection;
+mod error;
+mod message;
+mod mixnet;
+mod queue;
+mod substream;
+mod transport;
+
+pub use connection::Connection;
+pub use error::Error;
+pub use message::SubstreamId;
+pub use substream::Substream;
+pub use transport::{
+    multiaddress_to_nym_address, nym_address_to_multiaddress, NymTransport, Upgrade,
+};
+
+/// The default timeout secs for [`Upgrade`] future.
+const DEFAULT_HANDSHAKE_TIMEOUT_SECS: u64 = 5;
@@ -1,10 +1,10 @@
-use libp2p::core::PeerId;
+use libp2p::PeerId;
 use nym_sphinx::addressing::clients::Recipient;
 use rand::rngs::OsRng;
 use rand::RngCore;
 use std::fmt::{Debug, Formatter};
 
-use super::error::Error;
+use crate::error::Error;
 
 const RECIPIENT_LENGTH: usize = Recipient::LEN;
 const CONNECTION_ID_LENGTH: usize = 32;
@@ -1,16 +1,16 @@
-use futures::{pin_mut, select};
-use futures::{FutureExt, StreamExt};
-use log::debug;
+use futures::StreamExt;
+use log::{debug, warn};
 use nym_sdk::mixnet::{IncludedSurbs, MixnetClient, MixnetClientSender, MixnetMessageSender};
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::receiver::ReconstructedMessage;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
-use super::error::Error;
-use super::message::*;
+use crate::error::Error;
+use crate::message::*;
 
 /// initialize_mixnet initializes a read/write connection to a Nym websockets endpoint.
 /// It starts a task that listens for inbound messages from the endpoint and writes outbound messages to the endpoint.
+/// The task (and the client) is stopped once the transport is dropped or the client shuts down.
 pub(crate) async fn initialize_mixnet(
     client: MixnetClient,
     notify_inbound_tx: Option<UnboundedSender<()>>,
@@ -38,40 +38,49 @@ pub(crate) async fn initialize_mixnet(
 
     tokio::task::spawn(async move {
         loop {
-            let t1 = check_inbound(&mut stream, &inbound_tx, &notify_inbound_tx).fuse();
-            let t2 = check_outbound(&sink, &mut outbound_rx).fuse();
-
-            pin_mut!(t1, t2);
-
-            select! {
-                _ = t1 => {},
-                _ = t2 => {},
-            };
+            tokio::select! {
+                received = stream.next() => {
+                    let Some(received) = received else {
+                        debug!("the mixnet client has stopped");
+                        break;
+                    };
+                    if let Err(err) = check_inbound(received, &inbound_tx, &notify_inbound_tx) {
+                        warn!("failed to handle inbound mixnet message: {err}");
+                    }
+                }
+                outbound = outbound_rx.recv() => {
+                    let Some(outbound) = outbound else {
+                        debug!("the transport has been dropped");
+                        break;
+                    };
+                    if let Err(err) = write_bytes(&sink, outbound.recipient, &outbound.message.to_bytes()).await {
+                        warn!("failed to send message to the mixnet: {err}");
+                    }
+                }
+            }
         }
+
+        stream.disconnect().await;
     });
 
     Ok((recipient, inbound_rx, outbound_tx))
 }
 
-async fn check_inbound(
-    client: &mut MixnetClient,
+fn check_inbound(
+    msg: ReconstructedMessage,
     inbound_tx: &UnboundedSender<InboundMessage>,
     notify_inbound_tx: &Option<UnboundedSender<()>>,
 ) -> Result<(), Error> {
-    if let Some(msg) = client.next().await {
-        if let Some(notify_tx) = notify_inbound_tx {
-            notify_tx
-                .send(())
-                .map_err(|e| Error::InboundSendFailure(e.to_string()))?;
-        }
-
-        handle_inbound(msg, inbound_tx).await?;
+    if let Some(notify_tx) = notify_inbound_tx {
+        notify_tx
+            .send(())
+            .map_err(|e| Error::InboundSendFailure(e.to_string()))?;
     }
 
-    Err(Error::Unimplemented)
+    handle_inbound(msg, inbound_tx)
 }
 
-async fn handle_inbound(
+fn handle_inbound(
     msg: ReconstructedMessage,
     inbound_tx: &UnboundedSender<InboundMessage>,
 ) -> Result<(), Error> {
@@ -82,34 +91,15 @@ async fn handle_inbound(
     Ok(())
 }
 
-async fn check_outbound(
-    mixnet_sender: &MixnetClientSender,
-    outbound_rx: &mut UnboundedReceiver<OutboundMessage>,
-) -> Result<(), Error> {
-    match outbound_rx.recv().await {
-        Some(message) => {
-            write_bytes(
-                mixnet_sender,
-                message.recipient,
-                &message.message.to_bytes(),
-            )
-            .await
-        }
-        None => Err(Error::RecvFailure),
-    }
-}
-
 async fn write_bytes(
     mixnet_sender: &MixnetClientSender,
     recipient: Recipient,
     message: &[u8],
 ) -> Result<(), Error> {
-    if let Err(_err) = mixnet_sender
+    mixnet_sender
         .send_message(recipient, message, IncludedSurbs::ExposeSelfAddress)
         .await
-    {
-        return Err(Error::Unimplemented);
-    }
+        .map_err(|e| Error::OutboundSendFailure(e.to_string()))?;
 
     debug!(
         "wrote message to mixnet: recipient: {:?}",
@@ -120,14 +110,15 @@ async fn write_bytes(
 
 #[cfg(test)]
 mod test {
-    use super::super::message::{
+    use crate::message::{
         self, ConnectionId, Message, SubstreamId, SubstreamMessage, SubstreamMessageType,
         TransportMessage,
     };
-    use super::super::mixnet::initialize_mixnet;
+    use crate::mixnet::initialize_mixnet;
     use nym_sdk::mixnet::MixnetClient;
 
     #[tokio::test]
+    #[ignore] // requires connection to the mixnet
     async fn test_mixnet_poll_inbound_and_outbound() {
         let client = MixnetClient::connect_new().await.unwrap();
         let (self_address, mut inbound_rx, outbound_tx) =
@@ -1,7 +1,7 @@
 use log::{debug, warn};
 use std::collections::BTreeSet;
 
-use super::message::TransportMessage;
+use crate::message::TransportMessage;
 
 /// MessageQueue is a queue of messages, ordered by nonce, that we've
 /// received but are not yet able to process because we're waiting for
@@ -89,7 +89,7 @@ impl MessageQueue {
 
 #[cfg(test)]
 mod test {
-    use super::super::message::{ConnectionId, SubstreamId, SubstreamMessage};
+    use crate::message::{ConnectionId, SubstreamId, SubstreamMessage};
 
     use super::*;
 
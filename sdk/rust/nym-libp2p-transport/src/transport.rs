@@ -1,10 +1,11 @@
 use futures::prelude::*;
 use libp2p::core::{
-    identity::Keypair,
     multiaddr::{Multiaddr, Protocol},
     transport::{ListenerId, TransportError, TransportEvent},
-    PeerId, Transport,
+    Transport,
 };
+use libp2p::identity::Keypair;
+use libp2p::PeerId;
 use log::debug;
 use nym_sdk::mixnet::MixnetClient;
 use nym_sphinx::addressing::clients::Recipient;
@@ -23,29 +24,36 @@ use tokio::{
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use super::connection::{Connection, PendingConnection};
-use super::error::Error;
-use super::message::{
+use crate::connection::{Connection, PendingConnection};
+use crate::error::Error;
+use crate::message::{
     ConnectionId, ConnectionMessage, InboundMessage, Message, OutboundMessage, SubstreamMessage,
     TransportMessage,
 };
-use super::mixnet::initialize_mixnet;
-use super::queue::MessageQueue;
-use super::DEFAULT_HANDSHAKE_TIMEOUT_SECS;
+use crate::mixnet::initialize_mixnet;
+use crate::queue::MessageQueue;
+use crate::DEFAULT_HANDSHAKE_TIMEOUT_SECS;
 
 /// InboundTransportEvent represents an inbound event from the mixnet.
-pub enum InboundTransportEvent {
-    ConnectionRequest(Upgrade),
+pub(crate) enum InboundTransportEvent {
+    /// connection request alongside the address of the remote peer
+    ConnectionRequest(Upgrade, Multiaddr),
     ConnectionResponse,
     TransportMessage,
 }
 
 /// NymTransport implements the Transport trait using the Nym mixnet.
+///
+/// The transport can only listen on the multiaddress representing the Nym address
+/// of its underlying client, see [`NymTransport::listen_addr`].
 pub struct NymTransport {
     /// our Nym address
     self_address: Recipient,
     pub(crate) listen_addr: Multiaddr,
-    pub(crate) listener_id: ListenerId,
+
+    /// id of the listener assigned in `listen_on`.
+    /// inbound connection requests are rejected until it's set.
+    pub(crate) listener_id: Option<ListenerId>,
 
     /// our libp2p keypair; currently not really used
     keypair: Keypair,
@@ -80,13 +88,11 @@ pub struct NymTransport {
 
 impl NymTransport {
     /// New transport.
-    #[allow(unused)]
     pub async fn new(client: MixnetClient, keypair: Keypair) -> Result<Self, Error> {
         Self::new_maybe_with_notify_inbound(client, keypair, None, None).await
     }
 
     /// New transport with a timeout.
-    #[allow(dead_code)]
     pub async fn new_with_timeout(
         client: MixnetClient,
         keypair: Keypair,
@@ -96,12 +102,18 @@ impl NymTransport {
     }
 
     /// Add timeout to transport and return self.
-    #[allow(dead_code)]
+    #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.handshake_timeout = timeout;
         self
     }
 
+    /// The multiaddress of this transport, i.e. the only address it can listen on
+    /// and the one that the remote peers should dial.
+    pub fn listen_addr(&self) -> &Multiaddr {
+        &self.listen_addr
+    }
+
     async fn new_maybe_with_notify_inbound(
         client: MixnetClient,
         keypair: Keypair,
@@ -110,26 +122,35 @@ impl NymTransport {
     ) -> Result<Self, Error> {
         let (self_address, inbound_rx, outbound_tx) =
             initialize_mixnet(client, notify_inbound_tx).await?;
-        let listen_addr = nym_address_to_multiaddress(self_address)?;
-        let listener_id = ListenerId::new();
+        Ok(Self::new_with_channels(
+            self_address,
+            keypair,
+            inbound_rx,
+            outbound_tx,
+            timeout,
+        ))
+    }
 
-        let (poll_tx, poll_rx) = unbounded_channel::<TransportEvent<Upgrade, Error>>();
+    // creates the transport on top of already established channels to and from the mixnet
+    fn new_with_channels(
+        self_address: Recipient,
+        keypair: Keypair,
+        inbound_rx: UnboundedReceiver<InboundMessage>,
+        outbound_tx: UnboundedSender<OutboundMessage>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let listen_addr = nym_address_to_multiaddress(self_address);
 
-        poll_tx
-            .send(TransportEvent::NewAddress {
-                listener_id,
-                listen_addr: listen_addr.clone(),
-            })
-            .map_err(|_| Error::SendErrorTransportEvent)?;
+        let (poll_tx, poll_rx) = unbounded_channel::<TransportEvent<Upgrade, Error>>();
 
         let inbound_stream = UnboundedReceiverStream::new(inbound_rx);
         let handshake_timeout =
             timeout.unwrap_or_else(|| Duration::from_secs(DEFAULT_HANDSHAKE_TIMEOUT_SECS));
 
-        Ok(Self {
+        Self {
             self_address,
             listen_addr,
-            listener_id,
+            listener_id: None,
             keypair,
             connections: HashMap::new(),
             pending_dials: HashMap::new(),
@@ -140,7 +161,7 @@ impl NymTransport {
             poll_tx,
             waker: None,
             handshake_timeout,
-        })
+        }
     }
 
     pub(crate) fn peer_id(&self) -> PeerId {
@@ -222,6 +243,10 @@ impl NymTransport {
     /// handle_connection_request handles an incoming connection request, sends back a
     /// connection response, and finally completes the upgrade into a Connection.
     fn handle_connection_request(&mut self, msg: &ConnectionMessage) -> Result<Connection, Error> {
+        if self.listener_id.is_none() {
+            return Err(Error::NotListening);
+        }
+
         if msg.recipient.is_none() {
             return Err(Error::NoneRecipientInConnectionRequest);
         }
@@ -338,10 +363,14 @@ impl NymTransport {
                         let (connection_tx, connection_rx) =
                             oneshot::channel::<(PeerId, Connection)>();
                         let upgrade = Upgrade::new(connection_rx);
+                        let send_back_addr = nym_address_to_multiaddress(conn.remote_recipient);
                         connection_tx
                             .send((inner.peer_id, conn))
                             .map_err(|_| Error::ConnectionSendFailure)?;
-                        Ok(InboundTransportEvent::ConnectionRequest(upgrade))
+                        Ok(InboundTransportEvent::ConnectionRequest(
+                            upgrade,
+                            send_back_addr,
+                        ))
                     }
                     Err(e) => Err(e),
                 }
@@ -390,34 +419,66 @@ impl Transport for NymTransport {
     type ListenerUpgrade = Upgrade;
     type Dial = Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send>>;
 
-    fn listen_on(&mut self, _: Multiaddr) -> Result<ListenerId, TransportError<Self::Error>> {
-        // we should only allow listening on the multiaddress containing our Nym address
-        Ok(self.listener_id)
+    fn listen_on(
+        &mut self,
+        id: ListenerId,
+        addr: Multiaddr,
+    ) -> Result<(), TransportError<Self::Error>> {
+        // we only allow listening on the multiaddress containing our Nym address
+        match multiaddress_to_nym_address(&addr) {
+            Ok(recipient) if recipient == self.self_address => {}
+            _ => return Err(TransportError::MultiaddrNotSupported(addr)),
+        }
+
+        if self.listener_id.is_some() {
+            return Err(TransportError::Other(Error::AlreadyListening));
+        }
+
+        self.poll_tx
+            .send(TransportEvent::NewAddress {
+                listener_id: id,
+                listen_addr: self.listen_addr.clone(),
+            })
+            .map_err(|_| TransportError::Other(Error::SendErrorTransportEvent))?;
+        self.listener_id = Some(id);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+
+        Ok(())
     }
 
     fn remove_listener(&mut self, id: ListenerId) -> bool {
-        if self.listener_id != id {
+        if self.listener_id != Some(id) {
             return false;
         }
 
-        // TODO: close channels?
+        // established connections are not affected, we just stop accepting the new ones
+        self.listener_id = None;
         self.poll_tx
             .send(TransportEvent::ListenerClosed {
                 listener_id: id,
                 reason: Ok(()),
             })
             .expect("failed to send listener closed event");
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+
         true
     }
 
     fn dial(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
-        debug!("dialing {}", addr);
+        // create remote recipient address
+        let Ok(recipient) = multiaddress_to_nym_address(&addr) else {
+            return Err(TransportError::MultiaddrNotSupported(addr));
+        };
 
+        debug!("dialing {}", addr);
         let id = ConnectionId::generate();
 
-        // create remote recipient address
-        let recipient = multiaddress_to_nym_address(addr).map_err(TransportError::Other)?;
-
         // create pending conn structs and store
         let (connection_tx, connection_rx) = oneshot::channel::<Connection>();
 
@@ -467,7 +528,7 @@ impl Transport for NymTransport {
         cx: &mut Context<'_>,
     ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
         // new addresses + listener close events
-        if let Poll::Ready(Some(res)) = self.poll_rx.recv().boxed().poll_unpin(cx) {
+        if let Poll::Ready(Some(res)) = self.poll_rx.poll_recv(cx) {
             return Poll::Ready(res);
         }
 
@@ -475,13 +536,17 @@ impl Transport for NymTransport {
         while let Poll::Ready(Some(msg)) = self.inbound_stream.poll_next_unpin(cx) {
             match self.handle_inbound(msg.0) {
                 Ok(event) => match event {
-                    InboundTransportEvent::ConnectionRequest(upgrade) => {
+                    InboundTransportEvent::ConnectionRequest(upgrade, send_back_addr) => {
                         debug!("InboundTransportEvent::ConnectionRequest");
+                        // the request has only been accepted if we're listening
+                        let listener_id = self
+                            .listener_id
+                            .expect("accepted connection request without a listener");
                         return Poll::Ready(TransportEvent::Incoming {
-                            listener_id: self.listener_id,
+                            listener_id,
                             upgrade,
                             local_addr: self.listen_addr.clone(),
-                            send_back_addr: self.listen_addr.clone(),
+                            send_back_addr,
                         });
                     }
                     InboundTransportEvent::ConnectionResponse => {
@@ -491,12 +556,15 @@ impl Transport for NymTransport {
                         debug!("InboundTransportEvent::TransportMessage");
                     }
                 },
-                Err(e) => {
-                    return Poll::Ready(TransportEvent::ListenerError {
-                        listener_id: self.listener_id,
-                        error: e,
-                    });
-                }
+                Err(e) => match self.listener_id {
+                    Some(listener_id) => {
+                        return Poll::Ready(TransportEvent::ListenerError {
+                            listener_id,
+                            error: e,
+                        });
+                    }
+                    None => debug!("failed to handle inbound message: {e}"),
+                },
             };
         }
 
@@ -509,40 +577,99 @@ impl Transport for NymTransport {
     }
 }
 
-fn nym_address_to_multiaddress(addr: Recipient) -> Result<Multiaddr, Error> {
-    Multiaddr::from_str(&format!("/nym/{}", addr)).map_err(Error::FailedToFormatMultiaddr)
+/// Creates the multiaddress representing the provided Nym address, i.e. `/dns/<nym address>`.
+pub fn nym_address_to_multiaddress(addr: Recipient) -> Multiaddr {
+    Multiaddr::empty().with(Protocol::Dns(addr.to_string().into()))
 }
 
-fn multiaddress_to_nym_address(multiaddr: Multiaddr) -> Result<Recipient, Error> {
-    let mut multiaddr = multiaddr;
-    match multiaddr.pop().unwrap() {
-        Protocol::Nym(addr) => Recipient::from_str(&addr).map_err(Error::InvalidRecipientBytes),
+/// Recovers the Nym address from the provided multiaddress.
+/// The address might be followed by the `/p2p/<peer id>` component.
+pub fn multiaddress_to_nym_address(multiaddr: &Multiaddr) -> Result<Recipient, Error> {
+    let mut protocols = multiaddr.iter();
+    match (protocols.next(), protocols.next(), protocols.next()) {
+        (Some(Protocol::Dns(addr)), None | Some(Protocol::P2p(_)), None) => {
+            Recipient::from_str(&addr).map_err(Error::InvalidRecipientBytes)
+        }
         _ => Err(Error::InvalidProtocolForMultiaddr),
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::connection::Connection;
-    use super::super::error::Error;
-    use super::super::message::{
-        Message, OutboundMessage, SubstreamId, SubstreamMessage, SubstreamMessageType,
-        TransportMessage,
+    use super::{multiaddress_to_nym_address, nym_address_to_multiaddress, NymTransport};
+    use crate::connection::Connection;
+    use crate::error::Error;
+    use crate::message::{
+        InboundMessage, Message, OutboundMessage, SubstreamId, SubstreamMessage,
+        SubstreamMessageType, TransportMessage,
     };
-    use super::super::substream::Substream;
-    use super::{nym_address_to_multiaddress, NymTransport};
+    use crate::substream::Substream;
     use futures::{future::poll_fn, AsyncReadExt, AsyncWriteExt, FutureExt};
     use libp2p::core::{
-        identity::Keypair,
-        transport::{Transport, TransportEvent},
+        transport::{ListenerId, Transport, TransportError, TransportEvent},
         Multiaddr, StreamMuxer,
     };
+    use libp2p::identity::Keypair;
+    use libp2p::PeerId;
     use log::info;
     use nym_bin_common::logging::setup_logging;
     use nym_sdk::mixnet::MixnetClient;
+    use nym_sphinx::addressing::clients::Recipient;
     use std::{pin::Pin, str::FromStr, sync::atomic::Ordering};
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
+    const DIALER_ADDRESS: &str = "D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN";
+    const LISTENER_ADDRESS: &str = "Hmer6Ndt3PV13YW53HM8ri4NvqqtfDQUQBhzvKqb1dag.2g478dyxtrQXGWc1Mk2VEqdPcWXpz7EhAcjhdAJtVZdA@AnnYnEtBjB2a5sHmeRCnBq43qxyHDf95Bqd7cwQyKNLR";
+
+    // in-memory replacement for the mixnet that never loses, delays nor reorders any messages
+    #[derive(Default)]
+    struct FakeMixnet {
+        outbound: Vec<UnboundedReceiver<OutboundMessage>>,
+        inbound: Vec<(Recipient, UnboundedSender<InboundMessage>)>,
+    }
+
+    impl FakeMixnet {
+        fn new_transport(&mut self, address: &str) -> NymTransport {
+            let address = Recipient::try_from_base58_string(address).unwrap();
+            let (inbound_tx, inbound_rx) = unbounded_channel();
+            let (outbound_tx, outbound_rx) = unbounded_channel();
+            self.outbound.push(outbound_rx);
+            self.inbound.push((address, inbound_tx));
+
+            NymTransport::new_with_channels(
+                address,
+                Keypair::generate_ed25519(),
+                inbound_rx,
+                outbound_tx,
+                None,
+            )
+        }
+
+        // delivers all the messages sent so far to their recipients
+        fn route(&mut self) {
+            for outbound_rx in &mut self.outbound {
+                while let Ok(outbound) = outbound_rx.try_recv() {
+                    let (_, inbound_tx) = self
+                        .inbound
+                        .iter()
+                        .find(|(address, _)| *address == outbound.recipient)
+                        .expect("unknown recipient");
+                    inbound_tx.send(InboundMessage(outbound.message)).unwrap();
+                }
+            }
+        }
+    }
+
+    fn poll_transport(transport: &mut NymTransport) {
+        assert!(poll_fn(|cx| Pin::new(&mut *transport).poll(cx))
+            .now_or_never()
+            .is_none());
+    }
+
+    fn poll_connection(connection: &mut Connection) {
+        poll_fn(|cx| Pin::new(&mut *connection).poll(cx)).now_or_never();
+    }
+
     impl Connection {
         fn write(&self, msg: SubstreamMessage) -> Result<(), Error> {
             let nonce = self.message_nonce.fetch_add(1, Ordering::SeqCst);
@@ -572,6 +699,7 @@ mod test {
     }
 
     #[tokio::test]
+    #[ignore] // requires connection to the mixnet
     async fn test_transport_connection() {
         setup_logging();
 
@@ -588,10 +716,9 @@ mod test {
             NymTransport::new_with_notify_inbound(client2, listener_notify_inbound_tx)
                 .await
                 .unwrap();
-        let listener_multiaddr =
-            nym_address_to_multiaddress(listener_transport.self_address).unwrap();
-        assert_new_address_event(Pin::new(&mut dialer_transport)).await;
-        assert_new_address_event(Pin::new(&mut listener_transport)).await;
+        let listener_multiaddr = nym_address_to_multiaddress(listener_transport.self_address);
+        listen(&mut dialer_transport).await;
+        listen(&mut listener_transport).await;
 
         // dial the remote peer
         let mut dial = dialer_transport.dial(listener_multiaddr).unwrap();
@@ -611,9 +738,9 @@ mod test {
                 local_addr,
                 send_back_addr,
             } => {
-                assert_eq!(listener_id, listener_transport.listener_id);
+                assert_eq!(Some(listener_id), listener_transport.listener_id);
                 assert_eq!(local_addr, listener_transport.listen_addr);
-                assert_eq!(send_back_addr, listener_transport.listen_addr);
+                assert_eq!(send_back_addr, dialer_transport.listen_addr);
                 upgrade
             }
             _ => panic!("expected TransportEvent::Incoming, got {:?}", res),
@@ -666,17 +793,166 @@ mod test {
         .await;
     }
 
-    async fn assert_new_address_event(mut transport: Pin<&mut NymTransport>) {
-        match poll_fn(|cx| transport.as_mut().poll(cx)).await {
+    async fn listen(transport: &mut NymTransport) -> ListenerId {
+        let id = ListenerId::next();
+        transport
+            .listen_on(id, transport.listen_addr.clone())
+            .unwrap();
+
+        match poll_fn(|cx| Pin::new(&mut *transport).poll(cx)).await {
             TransportEvent::NewAddress {
                 listener_id,
                 listen_addr,
             } => {
-                assert_eq!(listener_id, transport.listener_id);
+                assert_eq!(listener_id, id);
+                assert_eq!(Some(listener_id), transport.listener_id);
                 assert_eq!(listen_addr, transport.listen_addr);
             }
             _ => panic!("expected TransportEvent::NewAddress"),
         }
+        id
+    }
+
+    #[test]
+    fn test_multiaddress_conversion() {
+        let address = Recipient::try_from_base58_string(LISTENER_ADDRESS).unwrap();
+        let multiaddr = nym_address_to_multiaddress(address);
+        assert_eq!(multiaddr.to_string(), format!("/dns/{LISTENER_ADDRESS}"));
+        assert_eq!(multiaddress_to_nym_address(&multiaddr).unwrap(), address);
+
+        // the swarm might append the peer id to the dialed address
+        let with_peer_id = multiaddr.clone().with_p2p(PeerId::random()).unwrap();
+        assert_eq!(multiaddress_to_nym_address(&with_peer_id).unwrap(), address);
+
+        for invalid in [
+            "/ip4/127.0.0.1/tcp/1789",
+            "/dns/nymtech.net",
+            &format!("/dns/{LISTENER_ADDRESS}/tcp/1789"),
+        ] {
+            let invalid = Multiaddr::from_str(invalid).unwrap();
+            assert!(multiaddress_to_nym_address(&invalid).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_listen_on() {
+        let mut mixnet = FakeMixnet::default();
+        let mut dialer = mixnet.new_transport(DIALER_ADDRESS);
+        let mut listener = mixnet.new_transport(LISTENER_ADDRESS);
+
+        // we can't listen on somebody else's address
+        let res = listener.listen_on(ListenerId::next(), dialer.listen_addr.clone());
+        assert!(matches!(res, Err(TransportError::MultiaddrNotSupported(_))));
+
+        // nor twice on our own
+        let listener_id = listen(&mut listener).await;
+        let res = listener.listen_on(ListenerId::next(), listener.listen_addr.clone());
+        assert!(matches!(
+            res,
+            Err(TransportError::Other(Error::AlreadyListening))
+        ));
+
+        // connection requests are ignored while we're not listening
+        assert!(listener.remove_listener(listener_id));
+        match poll_fn(|cx| Pin::new(&mut listener).poll(cx)).await {
+            TransportEvent::ListenerClosed {
+                listener_id: closed,
+                reason: Ok(()),
+            } => assert_eq!(closed, listener_id),
+            _ => panic!("expected TransportEvent::ListenerClosed"),
+        }
+
+        let mut dial = dialer.dial(listener.listen_addr.clone()).unwrap();
+        assert!(poll_fn(|cx| dial.poll_unpin(cx)).now_or_never().is_none());
+        mixnet.route();
+        poll_transport(&mut listener);
+        assert!(listener.connections.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transport_substream_multiplexing() {
+        let mut mixnet = FakeMixnet::default();
+        let mut dialer = mixnet.new_transport(DIALER_ADDRESS);
+        let mut listener = mixnet.new_transport(LISTENER_ADDRESS);
+        let listener_id = listen(&mut listener).await;
+
+        // send the connection request
+        let mut dial = dialer.dial(listener.listen_addr.clone()).unwrap();
+        assert!(poll_fn(|cx| dial.poll_unpin(cx)).now_or_never().is_none());
+        mixnet.route();
+
+        // accept it on the listener
+        let upgrade = match poll_fn(|cx| Pin::new(&mut listener).poll(cx))
+            .now_or_never()
+            .expect("the connection request should have been received")
+        {
+            TransportEvent::Incoming {
+                listener_id: id,
+                upgrade,
+                local_addr,
+                send_back_addr,
+            } => {
+                assert_eq!(id, listener_id);
+                assert_eq!(local_addr, listener.listen_addr);
+                assert_eq!(send_back_addr, dialer.listen_addr);
+                upgrade
+            }
+            _ => panic!("expected TransportEvent::Incoming"),
+        };
+        let (dialer_peer_id, mut listener_conn) = upgrade.now_or_never().unwrap().unwrap();
+        assert_eq!(dialer_peer_id, dialer.peer_id());
+
+        // and finalise the connection on the dialer
+        mixnet.route();
+        poll_transport(&mut dialer);
+        let (listener_peer_id, mut dialer_conn) = dial.now_or_never().unwrap().unwrap();
+        assert_eq!(listener_peer_id, listener.peer_id());
+
+        // open multiple substreams over the single connection
+        let mut dialer_substreams = Vec::new();
+        for _ in 0..3 {
+            let substream = poll_fn(|cx| Pin::new(&mut dialer_conn).poll_outbound(cx))
+                .await
+                .unwrap();
+            dialer_substreams.push(substream);
+        }
+        mixnet.route();
+        poll_transport(&mut listener);
+        poll_connection(&mut listener_conn);
+
+        let mut listener_substreams = Vec::new();
+        for _ in 0..3 {
+            let substream = poll_fn(|cx| Pin::new(&mut listener_conn).poll_inbound(cx))
+                .now_or_never()
+                .unwrap()
+                .unwrap();
+            listener_substreams.push(substream);
+        }
+        mixnet.route();
+        poll_transport(&mut dialer);
+        poll_connection(&mut dialer_conn);
+        assert!(dialer_conn.pending_substreams.is_empty());
+
+        // write to all of them in reverse order
+        for (i, substream) in dialer_substreams.iter_mut().enumerate().rev() {
+            let data = format!("hello from substream {i}");
+            substream.write_all(data.as_bytes()).await.unwrap();
+        }
+        mixnet.route();
+        poll_transport(&mut listener);
+        poll_connection(&mut listener_conn);
+
+        for (i, dialer_substream) in dialer_substreams.iter().enumerate() {
+            let listener_substream = listener_substreams
+                .iter_mut()
+                .find(|s| s.substream_id == dialer_substream.substream_id)
+                .unwrap();
+
+            let expected = format!("hello from substream {i}");
+            let mut buf = vec![0u8; expected.len()];
+            listener_substream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, expected.as_bytes());
+        }
     }
 
     async fn send_and_receive_over_conns(
@@ -709,6 +985,7 @@ mod test {
     }
 
     #[tokio::test]
+    #[ignore] // requires connection to the mixnet
     async fn test_transport_substream() {
         let client = MixnetClient::connect_new().await.unwrap();
 
@@ -725,10 +1002,9 @@ mod test {
             NymTransport::new_with_notify_inbound(client2, listener_notify_inbound_tx)
                 .await
                 .unwrap();
-        let listener_multiaddr =
-            nym_address_to_multiaddress(listener_transport.self_address).unwrap();
-        assert_new_address_event(Pin::new(&mut dialer_transport)).await;
-        assert_new_address_event(Pin::new(&mut listener_transport)).await;
+        let listener_multiaddr = nym_address_to_multiaddress(listener_transport.self_address);
+        listen(&mut dialer_transport).await;
+        listen(&mut listener_transport).await;
 
         // dial the remote peer
         let mut dial = dialer_transport.dial(listener_multiaddr).unwrap();
@@ -748,9 +1024,9 @@ mod test {
                 local_addr,
                 send_back_addr,
             } => {
-                assert_eq!(listener_id, listener_transport.listener_id);
+                assert_eq!(Some(listener_id), listener_transport.listener_id);
                 assert_eq!(local_addr, listener_transport.listen_addr);
-                assert_eq!(send_back_addr, listener_transport.listen_addr);
+                assert_eq!(send_back_addr, dialer_transport.listen_addr);
                 upgrade
             }
             _ => panic!("expected TransportEvent::Incoming, got {:?}", res),
@@ -872,6 +1148,7 @@ mod test {
     }
 
     #[tokio::test]
+    #[ignore] // requires connection to the mixnet
     async fn test_transport_timeout() {
         let client = MixnetClient::connect_new().await.unwrap();
 
@@ -884,7 +1161,7 @@ mod test {
 
         // mock a transport that will never resolve the connection.
         let empty_addr = Multiaddr::from_str(
-            "/nym/Hmer6Ndt3PV13YW53HM8ri4NvqqtfDQUQBhzvKqb1dag.2g478dyxtrQXGWc1Mk2VEqdPcWXpz7EhAcjhdAJtVZdA@AnnYnEtBjB2a5sHmeRCnBq43qxyHDf95Bqd7cwQyKNLR"
+            "/dns/Hmer6Ndt3PV13YW53HM8ri4NvqqtfDQUQBhzvKqb1dag.2g478dyxtrQXGWc1Mk2VEqdPcWXpz7EhAcjhdAJtVZdA@AnnYnEtBjB2a5sHmeRCnBq43qxyHDf95Bqd7cwQyKNLR"
         )
         .expect("unable to parse multiaddress");
 
@@ -31,6 +31,10 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::One,
+            performance: mix::Performance::hundred(),
+            stake_saturation: mix::Decimal::zero(),
+            family: None,
+            latency: None,
             version: "1.1.0".into(),
         }],
     );
@@ -48,6 +52,10 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::Two,
+            performance: mix::Performance::hundred(),
+            stake_saturation: mix::Decimal::zero(),
+            family: None,
+            latency: None,
             version: "1.1.0".into(),
         }],
     );
@@ -65,6 +73,10 @@ async fn main() {
                 .parse()
                 .unwrap(),
             layer: Layer::Three,
+            performance: mix::Performance::hundred(),
+            stake_saturation: mix::Decimal::zero(),
+            family: None,
+            latency: None,
             version: "1.1.0".into(),
         }],
     );
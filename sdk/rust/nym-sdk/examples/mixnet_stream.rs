@@ -0,0 +1,57 @@
+use nym_sdk::mixnet::{self, MixnetStreams};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::main]
+async fn main() {
+    nym_bin_common::logging::setup_logging();
+
+    println!("Connecting server client");
+    let server_client = mixnet::MixnetClient::connect_new().await.unwrap();
+    let mut server = MixnetStreams::new(server_client);
+    let server_address = *server.nym_address();
+
+    // echo every accepted stream back to its (anonymous) opener
+    let server_task = tokio::spawn(async move {
+        while let Some(stream) = server.accept().await {
+            println!("Accepted stream {} from {:?}", stream.id(), stream.peer());
+            tokio::spawn(async move {
+                let (mut reader, mut writer) = tokio::io::split(stream);
+                tokio::io::copy(&mut reader, &mut writer).await.ok();
+                writer.shutdown().await.ok();
+            });
+        }
+        server.disconnect().await;
+    });
+
+    println!("Connecting client");
+    let client_client = mixnet::MixnetClient::connect_new().await.unwrap();
+    let client = MixnetStreams::new(client_client);
+
+    // multiple streams share the same underlying client
+    let mut first = client.open(server_address).await.unwrap();
+    let mut second = client.open(server_address).await.unwrap();
+
+    first
+        .write_all(b"hello from the first stream")
+        .await
+        .unwrap();
+    second
+        .write_all(b"hello from the second stream")
+        .await
+        .unwrap();
+    first.shutdown().await.unwrap();
+    second.shutdown().await.unwrap();
+
+    for mut stream in [first, second] {
+        let mut echoed = Vec::new();
+        stream.read_to_end(&mut echoed).await.unwrap();
+        println!(
+            "Stream {} received: {}",
+            stream.id(),
+            String::from_utf8_lossy(&echoed)
+        );
+    }
+
+    client.disconnect().await;
+    server_task.abort();
+}
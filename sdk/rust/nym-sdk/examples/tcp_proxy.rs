@@ -0,0 +1,49 @@
+use nym_sdk::mixnet;
+use nym_sdk::tcp_proxy::{NymProxyClient, NymProxyServer};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const UPSTREAM_ADDRESS: &str = "127.0.0.1:9000";
+const LOCAL_PROXY_ADDRESS: &str = "127.0.0.1:9001";
+
+#[tokio::main]
+async fn main() {
+    nym_bin_common::logging::setup_logging();
+
+    // simple echo service we're going to expose over the mixnet
+    let upstream = TcpListener::bind(UPSTREAM_ADDRESS).await.unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = upstream.accept().await {
+            tokio::spawn(async move {
+                let (mut reader, mut writer) = stream.split();
+                tokio::io::copy(&mut reader, &mut writer).await.ok();
+            });
+        }
+    });
+
+    println!("Connecting proxy server");
+    let server_client = mixnet::MixnetClient::connect_new().await.unwrap();
+    let server_address = *server_client.nym_address();
+    let server = NymProxyServer::new(server_client, UPSTREAM_ADDRESS.parse().unwrap());
+    tokio::spawn(server.run());
+
+    println!("Connecting proxy client");
+    let client_client = mixnet::MixnetClient::connect_new().await.unwrap();
+    let client = NymProxyClient::new(
+        client_client,
+        server_address,
+        LOCAL_PROXY_ADDRESS.parse().unwrap(),
+    );
+    tokio::spawn(client.run());
+
+    // give the listener a moment to start
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let mut stream = TcpStream::connect(LOCAL_PROXY_ADDRESS).await.unwrap();
+    stream.write_all(b"hello through the mixnet").await.unwrap();
+
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await.unwrap();
+    println!("Received: {}", String::from_utf8_lossy(&buf[..n]));
+}
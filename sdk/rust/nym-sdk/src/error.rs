@@ -94,6 +94,9 @@ pub enum Error {
     #[error("failed to send the provided message")]
     MessageSendingFailure,
 
+    #[error("the registry of mixnet streams got poisoned")]
+    StreamRegistryPoisoned,
+
     #[error("this operation is currently unsupported: {details}")]
     Unsupported { details: String },
 }
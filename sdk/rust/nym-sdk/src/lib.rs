@@ -1,11 +1,13 @@
 //! Rust SDK for the Nym platform
 //!
 //! The main component currently is [`mixnet`].
+//! For exposing arbitrary TCP services over the mixnet, see [`tcp_proxy`].
 
 mod error;
 
 pub mod bandwidth;
 pub mod mixnet;
+pub mod tcp_proxy;
 
 pub use error::{Error, Result};
 pub use nym_client_core::client::mix_traffic::transceiver::*;
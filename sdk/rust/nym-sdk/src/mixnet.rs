@@ -36,6 +36,7 @@ mod connection_state;
 mod native_client;
 mod paths;
 mod socks5_client;
+mod stream;
 mod traits;
 
 pub use client::{DisconnectedMixnetClient, IncludedSurbs, MixnetClientBuilder};
@@ -59,6 +60,7 @@ pub use nym_client_core::{
             fs_backend::Backend as ReplyStorage, CombinedReplyStorage, Empty as EmptyReplyStorage,
             ReplyStorageBackend,
         },
+        self_address::SelfAddress,
         topology_control::geo_aware_provider::{CountryGroup, GeoAwareTopologyProvider},
     },
     config::GroupBy,
@@ -78,7 +80,8 @@ pub use nym_sphinx::{
     receiver::ReconstructedMessage,
 };
 pub use nym_task::connections::TransmissionLane;
-pub use nym_topology::{provider_trait::TopologyProvider, NymTopology};
+pub use nym_topology::{provider_trait::TopologyProvider, NymTopology, RouteSelectionStrategy};
 pub use paths::StoragePaths;
 pub use socks5_client::Socks5MixnetClient;
+pub use stream::{MixnetStream, MixnetStreams, StreamPeer};
 pub use traits::MixnetMessageSender;
@@ -25,7 +25,7 @@ use nym_client_core::client::{
 };
 use nym_client_core::config::DebugConfig;
 use nym_client_core::error::ClientCoreError;
-use nym_client_core::init::helpers::current_gateways;
+use nym_client_core::init::helpers::current_gateways_for_selection;
 use nym_client_core::init::setup_gateway;
 use nym_client_core::init::types::{GatewaySelectionSpecification, GatewaySetup};
 use nym_network_defaults::WG_TUN_DEVICE_IP_ADDRESS;
@@ -33,9 +33,10 @@ use nym_socks5_client_core::config::Socks5;
 use nym_task::manager::TaskStatus;
 use nym_task::{TaskClient, TaskHandle};
 use nym_topology::provider_trait::TopologyProvider;
+use nym_topology::RouteSelectionStrategy;
 use nym_validator_client::{nyxd, QueryHttpRpcNyxdClient};
 use rand::rngs::OsRng;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::path::PathBuf;
 use url::Url;
@@ -52,6 +53,7 @@ pub struct MixnetClientBuilder<S: MixnetClientStorage = Ephemeral> {
     wireguard_mode: bool,
     wait_for_gateway: bool,
     custom_topology_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
+    custom_route_selection: Option<RouteSelectionStrategy>,
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send + Sync>>,
     custom_shutdown: Option<TaskClient>,
     force_tls: bool,
@@ -87,6 +89,7 @@ impl MixnetClientBuilder<OnDiskPersistent> {
             wireguard_mode: false,
             wait_for_gateway: false,
             custom_topology_provider: None,
+            custom_route_selection: None,
             storage: storage_paths
                 .initialise_default_persistent_storage()
                 .await?,
@@ -102,7 +105,7 @@ impl<S> MixnetClientBuilder<S>
 where
     S: MixnetClientStorage + 'static,
     S::ReplyStore: Send + Sync,
-    S::GatewaysDetailsStore: Sync,
+    S::GatewaysDetailsStore: Send + Sync,
     <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
     <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
     <S::KeyStore as KeyStore>::StorageError: Send + Sync,
@@ -118,6 +121,7 @@ where
             wireguard_mode: false,
             wait_for_gateway: false,
             custom_topology_provider: None,
+            custom_route_selection: None,
             custom_gateway_transceiver: None,
             custom_shutdown: None,
             force_tls: false,
@@ -136,6 +140,7 @@ where
             wireguard_mode: self.wireguard_mode,
             wait_for_gateway: self.wait_for_gateway,
             custom_topology_provider: self.custom_topology_provider,
+            custom_route_selection: self.custom_route_selection,
             custom_gateway_transceiver: self.custom_gateway_transceiver,
             custom_shutdown: self.custom_shutdown,
             force_tls: self.force_tls,
@@ -167,6 +172,16 @@ where
         self
     }
 
+    /// Choose the gateway based on the round-trip-times reported by the network instead of uniformly.
+    /// `anonymity_budget` is the percentage (0-100) of the selection probability that gets distributed
+    /// based on the latency, the remainder is spread uniformly across all gateways.
+    /// It has no effect if a specific gateway has been requested.
+    #[must_use]
+    pub fn latency_weighted_gateway_selection(mut self, anonymity_budget: u8) -> Self {
+        self.config.latency_weighted_gateway_selection = Some(anonymity_budget);
+        self
+    }
+
     /// Attempt to only choose a gateway that supports wss protocol.
     #[must_use]
     pub fn force_tls(mut self, must_use_tls: bool) -> Self {
@@ -195,6 +210,23 @@ where
         self
     }
 
+    /// Serve the packet statistics in the Prometheus format on the `/metrics` endpoint
+    /// of a local HTTP server listening on the specified address.
+    #[must_use]
+    pub fn metrics_server(mut self, server_address: SocketAddr) -> Self {
+        self.config.debug_config.metrics.enable_server = true;
+        self.config.debug_config.metrics.server_address = server_address;
+        self
+    }
+
+    /// Automatically switch to a different gateway once the current one is assumed to be dead.
+    /// Changes of the client address are published via [`MixnetClient::address_updates`].
+    #[must_use]
+    pub fn gateway_failover(mut self, enabled: bool) -> Self {
+        self.config.debug_config.gateway_failover.enabled = enabled;
+        self
+    }
+
     /// Configure the SOCKS5 mode.
     #[must_use]
     pub fn socks5_config(mut self, socks5_config: Socks5) -> Self {
@@ -212,6 +244,14 @@ where
         self
     }
 
+    /// Use a custom strategy for choosing mixnodes when constructing packet routes.
+    /// It takes precedence over the route selection specified in the debug config.
+    #[must_use]
+    pub fn custom_route_selection(mut self, route_selection: RouteSelectionStrategy) -> Self {
+        self.custom_route_selection = Some(route_selection);
+        self
+    }
+
     /// Use an externally managed shutdown mechanism.
     #[must_use]
     pub fn custom_shutdown(mut self, shutdown: TaskClient) -> Self {
@@ -257,6 +297,7 @@ where
 
         client.custom_gateway_transceiver = self.custom_gateway_transceiver;
         client.custom_topology_provider = self.custom_topology_provider;
+        client.custom_route_selection = self.custom_route_selection;
         client.custom_shutdown = self.custom_shutdown;
         client.wireguard_mode = self.wireguard_mode;
         client.wait_for_gateway = self.wait_for_gateway;
@@ -295,6 +336,9 @@ where
     /// Alternative provider of network topology used for constructing sphinx packets.
     custom_topology_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
 
+    /// Alternative strategy for choosing mixnodes when constructing sphinx packets.
+    custom_route_selection: Option<RouteSelectionStrategy>,
+
     /// advanced usage of custom gateways
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send + Sync>>,
 
@@ -315,7 +359,7 @@ impl<S> DisconnectedMixnetClient<S>
 where
     S: MixnetClientStorage + 'static,
     S::ReplyStore: Send + Sync,
-    S::GatewaysDetailsStore: Sync,
+    S::GatewaysDetailsStore: Send + Sync,
     <S::ReplyStore as ReplyStorageBackend>::StorageError: Sync + Send,
     <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync,
     <S::KeyStore as KeyStore>::StorageError: Send + Sync,
@@ -353,6 +397,7 @@ where
             dkg_query_client,
             storage,
             custom_topology_provider: None,
+            custom_route_selection: None,
             custom_gateway_transceiver: None,
             wireguard_mode: false,
             wait_for_gateway: false,
@@ -455,11 +500,13 @@ where
         let selection_spec = GatewaySelectionSpecification::new(
             self.config.user_chosen_gateway.clone(),
             None,
+            self.config.latency_weighted_gateway_selection,
             self.force_tls,
         );
 
         let mut rng = OsRng;
-        let available_gateways = current_gateways(&mut rng, &nym_api_endpoints).await?;
+        let available_gateways =
+            current_gateways_for_selection(&mut rng, &nym_api_endpoints, &selection_spec).await?;
 
         Ok(GatewaySetup::New {
             specification: selection_spec,
@@ -616,6 +663,10 @@ where
             base_builder = base_builder.with_topology_provider(topology_provider);
         }
 
+        if let Some(route_selection) = self.custom_route_selection {
+            base_builder = base_builder.with_route_selection(route_selection);
+        }
+
         if let Some(custom_shutdown) = self.custom_shutdown {
             base_builder = base_builder.with_shutdown(custom_shutdown)
         }
@@ -11,6 +11,11 @@ pub struct Config {
     /// If the user has explicitly specified a gateway.
     pub user_chosen_gateway: Option<String>,
 
+    /// If specified, the gateway is going to be chosen based on the round-trip-times reported by the network
+    /// with the provided anonymity budget, i.e. the percentage (0-100) of the selection probability
+    /// that gets distributed based on the latency.
+    pub latency_weighted_gateway_selection: Option<u8>,
+
     /// The details of the network we're using. It defaults to the mainnet network.
     pub network_details: NymNetworkDetails,
 
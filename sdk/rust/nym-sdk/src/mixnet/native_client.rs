@@ -9,6 +9,7 @@ use nym_client_core::client::{
     base_client::{ClientInput, ClientOutput, ClientState},
     inbound_messages::InputMessage,
     received_buffer::ReconstructedMessagesReceiver,
+    self_address::SelfAddress,
 };
 use nym_crypto::asymmetric::identity;
 use nym_sphinx::addressing::clients::Recipient;
@@ -101,10 +102,19 @@ impl MixnetClient {
 
     /// Get the nym address for this client, if it is available. The nym address is composed of the
     /// client identity, the client encryption key, and the gateway identity.
+    ///
+    /// Note: this is the address the client has been started with. If gateway failover is enabled,
+    /// use [`MixnetClient::address_updates`] to learn about its changes.
     pub fn nym_address(&self) -> &Recipient {
         &self.nym_address
     }
 
+    /// Get a handle to the current nym address of this client that gets updated
+    /// whenever the client switches to a different gateway.
+    pub fn address_updates(&self) -> SelfAddress {
+        self.client_state.self_address.clone()
+    }
+
     /// Sign a message with the client's private identity key.
     pub fn sign(&self, data: &[u8]) -> identity::Signature {
         self.identity_keys.private_key().sign(data)
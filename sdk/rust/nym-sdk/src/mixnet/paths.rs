@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::{Error, Result};
+use nym_client_core::cli_helpers::client_encrypt_keys::{
+    keys_passphrase_from_env, KEYS_PASSPHRASE_ENV_VAR,
+};
 use nym_client_core::client::base_client::storage::OnDiskGatewaysDetails;
 use nym_client_core::client::base_client::{non_wasm_helpers, storage};
 use nym_client_core::client::key_manager::persistence::OnDiskKeys;
@@ -126,8 +129,14 @@ impl StoragePaths {
     }
 
     /// Instantiates default persistent key storage.
+    /// If the keys are encrypted at rest, their passphrase is read from the
+    /// [`KEYS_PASSPHRASE_ENV_VAR`] environment variable.
     pub fn on_disk_key_storage_spec(&self) -> OnDiskKeys {
-        OnDiskKeys::new(self.client_keys_paths())
+        let keys_passphrase = keys_passphrase_from_env(KEYS_PASSPHRASE_ENV_VAR);
+        OnDiskKeys::new_with_optional_passphrase(
+            self.client_keys_paths(),
+            keys_passphrase.as_ref().map(|p| p.as_bytes()),
+        )
     }
 
     pub async fn on_disk_gateway_details_storage(&self) -> Result<OnDiskGatewaysDetails, Error> {
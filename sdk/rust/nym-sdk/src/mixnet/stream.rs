@@ -0,0 +1,856 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bidirectional byte streams on top of the mixnet.
+//!
+//! ```no_run
+//! use nym_sdk::mixnet::{MixnetClient, MixnetStreams};
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut server = MixnetStreams::new(MixnetClient::connect_new().await.unwrap());
+//!     let client = MixnetStreams::new(MixnetClient::connect_new().await.unwrap());
+//!
+//!     let mut outgoing = client.open(*server.nym_address()).await.unwrap();
+//!     outgoing.write_all(b"hello there").await.unwrap();
+//!     outgoing.shutdown().await.unwrap();
+//!
+//!     let mut incoming = server.accept().await.unwrap();
+//!     let mut received = Vec::new();
+//!     incoming.read_to_end(&mut received).await.unwrap();
+//!     println!("Received: {}", String::from_utf8_lossy(&received));
+//!
+//!     client.disconnect().await;
+//!     server.disconnect().await;
+//! }
+//! ```
+
+use crate::mixnet::{AnonymousSenderTag, MixnetClient, Recipient};
+use crate::{Error, Result};
+use futures::channel::{mpsc, oneshot};
+use futures::{ready, StreamExt};
+use log::{debug, trace, warn};
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_ordered_buffer::OrderedMessageBuffer;
+use nym_socks5_requests::{ConnectionId, SocketData};
+use nym_sphinx::params::{PacketSize, PacketType};
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::{
+    ConnectionCommand, ConnectionCommandSender, LaneQueueLengths, TransmissionLane,
+};
+use rand::RngCore;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::task::JoinHandle;
+
+// the same defaults as used by the socks5 client
+const DEFAULT_STREAM_START_SURBS: u32 = 20;
+const DEFAULT_PER_MESSAGE_SURBS: u32 = 3;
+
+// how many ids of dropped streams we keep around to ignore any data that arrives for them late
+const MAX_REMEMBERED_FINISHED_STREAMS: usize = 1024;
+
+// the final messages of a dropped stream have to make their way to the `OutQueueControl`
+// before we can check whether its transmission lane got drained
+const LANE_SETTLING_DELAY: Duration = Duration::from_secs(2);
+const LANE_DRAINING_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_LANE_DRAINING_WAIT: Duration = Duration::from_secs(4 * 60);
+
+type PendingSend = Pin<Box<dyn Future<Output = io::Result<()>> + Send>>;
+
+/// The other side of a [`MixnetStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamPeer {
+    /// The stream was opened by us to the specified address.
+    Recipient(Box<Recipient>),
+
+    /// The stream was opened by an anonymous peer that can only be reached with the reply SURBs
+    /// it has sent us.
+    SenderTag(AnonymousSenderTag),
+}
+
+// the receiving side of a stream that is still alive
+struct RegisteredStream {
+    sender: mpsc::UnboundedSender<SocketData>,
+
+    // sender tag of the client that opened the stream. `None` if it was opened by us,
+    // as the replies sent with our SURBs do not carry any
+    remote_sender_tag: Option<AnonymousSenderTag>,
+}
+
+#[derive(Default)]
+struct StreamRegistry {
+    streams: HashMap<ConnectionId, RegisteredStream>,
+
+    // ids of the streams that have already been dropped so that any late data wouldn't reopen them
+    finished: FinishedStreams,
+}
+
+#[derive(Default)]
+struct FinishedStreams {
+    ids: HashSet<ConnectionId>,
+
+    // remembered ids, in order of their streams getting dropped
+    finishing_order: VecDeque<ConnectionId>,
+}
+
+impl FinishedStreams {
+    fn contains(&self, id: &ConnectionId) -> bool {
+        self.ids.contains(id)
+    }
+
+    fn insert(&mut self, id: ConnectionId) {
+        if !self.ids.insert(id) {
+            return;
+        }
+        self.finishing_order.push_back(id);
+
+        // by the time we forget about a stream, any of its retransmissions should be long gone
+        if self.finishing_order.len() > MAX_REMEMBERED_FINISHED_STREAMS {
+            if let Some(oldest) = self.finishing_order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+    }
+}
+
+// everything each stream needs in order to send its data
+#[derive(Clone)]
+struct StreamContext {
+    input_sender: InputMessageSender,
+    connection_command_sender: ConnectionCommandSender,
+    lane_queue_lengths: LaneQueueLengths,
+    packet_type: Option<PacketType>,
+    per_message_surbs: u32,
+    registry: Arc<Mutex<StreamRegistry>>,
+}
+
+impl StreamContext {
+    fn input_message(&self, peer: &StreamPeer, data: SocketData) -> InputMessage {
+        let lane = TransmissionLane::ConnectionId(data.header.connection_id);
+        match peer {
+            StreamPeer::Recipient(recipient) => InputMessage::new_anonymous(
+                **recipient,
+                data.into_request_bytes(),
+                self.per_message_surbs,
+                lane,
+                self.packet_type,
+            ),
+            StreamPeer::SenderTag(sender_tag) => InputMessage::new_reply(
+                *sender_tag,
+                data.into_request_bytes(),
+                lane,
+                self.packet_type,
+            ),
+        }
+    }
+
+    // discards the transmission lane of the stream in the `OutQueueControl`
+    // once everything that was sent on it has been transmitted
+    fn release_lane(&self, id: ConnectionId) {
+        let connection_command_sender = self.connection_command_sender.clone();
+        let close_lane = move || {
+            if connection_command_sender
+                .unbounded_send(ConnectionCommand::Close(id))
+                .is_err()
+            {
+                debug!("failed to release the transmission lane of stream {id}");
+            }
+        };
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            close_lane();
+            return;
+        };
+        let lane_queue_lengths = self.lane_queue_lengths.clone();
+        runtime.spawn(async move {
+            tokio::time::sleep(LANE_SETTLING_DELAY).await;
+            let lane = TransmissionLane::ConnectionId(id);
+            let drained = async {
+                while lane_queue_lengths.get(&lane).unwrap_or_default() > 0 {
+                    tokio::time::sleep(LANE_DRAINING_CHECK_INTERVAL).await;
+                }
+            };
+            if tokio::time::timeout(MAX_LANE_DRAINING_WAIT, drained)
+                .await
+                .is_err()
+            {
+                warn!("transmission lane of stream {id} did not get drained in time");
+            }
+            close_lane();
+        });
+    }
+}
+
+/// Bidirectional byte stream to another client, multiplexed with other streams over a single [`MixnetClient`].
+///
+/// Every stream uses its own [`TransmissionLane`] and the written data is reassembled in order
+/// on the other side. Calling [`shutdown`](tokio::io::AsyncWriteExt::shutdown) closes the writing
+/// half of the stream after all previously written data is sent. The other side is going to observe
+/// it as EOF once it has read all of that data. Dropping the stream without shutting it down
+/// discards any data that has not yet been flushed.
+pub struct MixnetStream {
+    id: ConnectionId,
+    peer: StreamPeer,
+    context: StreamContext,
+
+    incoming: mpsc::UnboundedReceiver<SocketData>,
+    ordered_buffer: OrderedMessageBuffer,
+    remote_closed_at: Option<u64>,
+    read_closed: bool,
+
+    // data that has already been put in order, but has not been read yet
+    readable: Vec<u8>,
+    read_position: usize,
+
+    next_sequence: u64,
+    pending_send: Option<PendingSend>,
+    write_closed: bool,
+}
+
+impl MixnetStream {
+    fn new(
+        id: ConnectionId,
+        peer: StreamPeer,
+        incoming: mpsc::UnboundedReceiver<SocketData>,
+        context: StreamContext,
+    ) -> Self {
+        MixnetStream {
+            id,
+            peer,
+            context,
+            incoming,
+            ordered_buffer: OrderedMessageBuffer::new(),
+            remote_closed_at: None,
+            read_closed: false,
+            readable: Vec::new(),
+            read_position: 0,
+            next_sequence: 0,
+            pending_send: None,
+            write_closed: false,
+        }
+    }
+
+    /// Id of the stream, shared by both of its sides.
+    pub fn id(&self) -> ConnectionId {
+        self.id
+    }
+
+    /// The other side of the stream.
+    pub fn peer(&self) -> &StreamPeer {
+        &self.peer
+    }
+
+    fn next_message(&mut self, data: Vec<u8>, closed: bool) -> InputMessage {
+        let socket_data = SocketData::new(self.next_sequence, self.id, closed, data);
+        self.next_sequence += 1;
+        self.context.input_message(&self.peer, socket_data)
+    }
+
+    fn start_send(&mut self, data: Vec<u8>, closed: bool) {
+        debug_assert!(self.pending_send.is_none());
+
+        let message = self.next_message(data, closed);
+        let input_sender = self.context.input_sender.clone();
+        self.pending_send = Some(Box::pin(async move {
+            input_sender.send(message).await.map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    Error::MessageSendingFailure.to_string(),
+                )
+            })
+        }));
+    }
+
+    fn poll_pending_send(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let Some(pending_send) = self.pending_send.as_mut() {
+            let res = ready!(pending_send.as_mut().poll(cx));
+            self.pending_send = None;
+            res?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn handle_incoming(&mut self, socket_data: SocketData) {
+        let seq = socket_data.header.seq;
+        if socket_data.header.local_socket_closed {
+            self.remote_closed_at = Some(seq);
+        }
+
+        if let Err(err) = self.ordered_buffer.write(seq, socket_data.data) {
+            // most likely a duplicate due to a retransmission
+            debug!("stream {}: {err}", self.id);
+            return;
+        }
+
+        if let Some(contiguous) = self.ordered_buffer.read() {
+            self.readable.extend(contiguous.data);
+            if let Some(closed_at) = self.remote_closed_at {
+                if contiguous.last_sequence >= closed_at {
+                    trace!("stream {} got closed by the remote", self.id);
+                    self.read_closed = true;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for MixnetStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.read_position < this.readable.len() {
+                let available = &this.readable[this.read_position..];
+                let n = available.len().min(buf.remaining());
+                buf.put_slice(&available[..n]);
+                this.read_position += n;
+                if this.read_position == this.readable.len() {
+                    this.readable.clear();
+                    this.read_position = 0;
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.read_closed {
+                // EOF
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(this.incoming.poll_next_unpin(cx)) {
+                Some(socket_data) => this.handle_incoming(socket_data),
+                None => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "the underlying mixnet client has stopped",
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for MixnetStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending_send(cx))?;
+
+        if this.write_closed {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the stream has been closed for writing",
+            )));
+        }
+
+        // FIXME: this does NOT include overhead due to acks or chunking
+        // (so actual true plaintext is smaller)
+        let n = buf.len().min(PacketSize::default().plaintext_size());
+        this.start_send(buf[..n].to_vec(), false);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_pending_send(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending_send(cx))?;
+
+        if !this.write_closed {
+            this.write_closed = true;
+            this.start_send(Vec::new(), true);
+            ready!(this.poll_pending_send(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for MixnetStream {
+    fn drop(&mut self) {
+        if !self.write_closed {
+            // best effort attempt at letting the other side know we're gone
+            let message = self.next_message(Vec::new(), true);
+            if self.context.input_sender.try_send(message).is_err() {
+                debug!(
+                    "failed to notify the remote about closing stream {}",
+                    self.id
+                );
+            }
+        }
+
+        self.context.release_lane(self.id);
+
+        if let Ok(mut registry) = self.context.registry.lock() {
+            registry.streams.remove(&self.id);
+            registry.finished.insert(self.id);
+        }
+    }
+}
+
+// demultiplexes messages received by the client into appropriate streams
+struct StreamRouter {
+    context: StreamContext,
+    accepted: mpsc::UnboundedSender<MixnetStream>,
+}
+
+impl StreamRouter {
+    fn route(&self, received: ReconstructedMessage) {
+        let socket_data = match SocketData::try_from_request_bytes(&received.message) {
+            Ok(socket_data) => socket_data,
+            Err(err) => {
+                warn!("received a message that does not belong to any stream: {err}");
+                return;
+            }
+        };
+        let id = socket_data.header.connection_id;
+
+        let Ok(mut registry) = self.context.registry.lock() else {
+            return;
+        };
+        if let Some(stream) = registry.streams.get(&id) {
+            if stream.remote_sender_tag != received.sender_tag {
+                warn!(
+                    "received data for stream {id} from somebody other than its peer - dropping it"
+                );
+                return;
+            }
+            if stream.sender.unbounded_send(socket_data).is_err() {
+                registry.streams.remove(&id);
+            }
+            return;
+        }
+        if registry.finished.contains(&id) {
+            trace!("received data for already finished stream {id}");
+            return;
+        }
+
+        // it's the first message of a new stream (not necessarily the first one that was sent)
+        let Some(sender_tag) = received.sender_tag else {
+            warn!(
+                "received data for unknown stream {id} without a sender tag - it can't be accepted"
+            );
+            return;
+        };
+        let (stream_sender, stream_receiver) = mpsc::unbounded();
+        // the receiver is still in scope so this can't fail
+        stream_sender.unbounded_send(socket_data).ok();
+        registry.streams.insert(
+            id,
+            RegisteredStream {
+                sender: stream_sender,
+                remote_sender_tag: Some(sender_tag),
+            },
+        );
+        drop(registry);
+
+        debug!("accepting new stream {id}");
+        let stream = MixnetStream::new(
+            id,
+            StreamPeer::SenderTag(sender_tag),
+            stream_receiver,
+            self.context.clone(),
+        );
+        if self.accepted.unbounded_send(stream).is_err() {
+            debug!("no longer accepting new streams - {id} is going to be dropped");
+        }
+    }
+
+    async fn run(self, mut client: MixnetClient, mut stop: oneshot::Receiver<()>) -> MixnetClient {
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut stop => {
+                    trace!("StreamRouter: received stop signal");
+                    break;
+                }
+                received = client.next() => match received {
+                    Some(received) => self.route(received),
+                    None => {
+                        debug!("StreamRouter: mixnet client has stopped");
+                        break;
+                    }
+                },
+            }
+        }
+
+        // make sure any reads on the remaining streams are going to get terminated
+        if let Ok(mut registry) = self.context.registry.lock() {
+            registry.streams.clear();
+        }
+        client
+    }
+}
+
+/// Multiplexes any number of [`MixnetStream`]s over a single [`MixnetClient`].
+///
+/// Note that it takes over all the messages received by the client, so any messages that are not
+/// part of a stream are discarded.
+pub struct MixnetStreams {
+    nym_address: Recipient,
+    context: StreamContext,
+    stream_start_surbs: u32,
+    accepted: mpsc::UnboundedReceiver<MixnetStream>,
+    stop_router: oneshot::Sender<()>,
+    router: JoinHandle<MixnetClient>,
+}
+
+impl MixnetStreams {
+    /// Starts routing messages received by the provided client into the appropriate streams.
+    pub fn new(client: MixnetClient) -> Self {
+        let context = StreamContext {
+            input_sender: client.client_input.input_sender.clone(),
+            connection_command_sender: client.connection_command_sender(),
+            lane_queue_lengths: client.shared_lane_queue_lengths(),
+            packet_type: client.packet_type,
+            per_message_surbs: DEFAULT_PER_MESSAGE_SURBS,
+            registry: Default::default(),
+        };
+        let (accepted_sender, accepted) = mpsc::unbounded();
+        let (stop_router, stop) = oneshot::channel();
+
+        let nym_address = *client.nym_address();
+        let router = StreamRouter {
+            context: context.clone(),
+            accepted: accepted_sender,
+        };
+
+        MixnetStreams {
+            nym_address,
+            context,
+            stream_start_surbs: DEFAULT_STREAM_START_SURBS,
+            accepted,
+            stop_router,
+            router: tokio::spawn(router.run(client, stop)),
+        }
+    }
+
+    /// Sets the number of reply SURBs attached to the message opening a new stream
+    /// and to every subsequent message sent on streams opened by us, respectively.
+    #[must_use]
+    pub fn with_reply_surbs(mut self, stream_start_surbs: u32, per_message_surbs: u32) -> Self {
+        self.stream_start_surbs = stream_start_surbs;
+        self.context.per_message_surbs = per_message_surbs;
+        self
+    }
+
+    /// The nym address of the underlying client.
+    pub fn nym_address(&self) -> &Recipient {
+        &self.nym_address
+    }
+
+    /// Opens a new stream to the specified recipient. The recipient is going to be able
+    /// to accept it as soon as the opening message reaches it.
+    pub async fn open(&self, recipient: Recipient) -> Result<MixnetStream> {
+        let id: ConnectionId = rand::rngs::OsRng.next_u64();
+        let (stream_sender, stream_receiver) = mpsc::unbounded();
+        self.context
+            .registry
+            .lock()
+            .map_err(|_| Error::StreamRegistryPoisoned)?
+            .streams
+            .insert(
+                id,
+                RegisteredStream {
+                    sender: stream_sender,
+                    remote_sender_tag: None,
+                },
+            );
+
+        let mut stream = MixnetStream::new(
+            id,
+            StreamPeer::Recipient(Box::new(recipient)),
+            stream_receiver,
+            self.context.clone(),
+        );
+
+        // announce the stream with an empty message carrying more reply SURBs than usual
+        // so that the other side could start responding straight away
+        let open = SocketData::new(stream.next_sequence, id, false, Vec::new());
+        stream.next_sequence += 1;
+        let open = InputMessage::new_anonymous(
+            recipient,
+            open.into_request_bytes(),
+            self.stream_start_surbs,
+            TransmissionLane::ConnectionId(id),
+            self.context.packet_type,
+        );
+        self.context
+            .input_sender
+            .send(open)
+            .await
+            .map_err(|_| Error::MessageSendingFailure)?;
+
+        debug!("opened new stream {id} to {recipient}");
+        Ok(stream)
+    }
+
+    /// Waits for the next stream opened by a remote client.
+    /// Returns `None` if the underlying client has stopped.
+    pub async fn accept(&mut self) -> Option<MixnetStream> {
+        self.accepted.next().await
+    }
+
+    /// Stops routing the received messages, terminating all remaining streams,
+    /// and disconnects the underlying client.
+    pub async fn disconnect(self) {
+        // the router might have already stopped on its own
+        let _ = self.stop_router.send(());
+        match self.router.await {
+            Ok(client) => client.disconnect().await,
+            Err(err) => warn!("the stream router has failed: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_task::connections::ConnectionCommandReceiver;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::time::timeout;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    const STREAM_ID: ConnectionId = 42;
+
+    struct TestStream {
+        stream: MixnetStream,
+        sent: tokio::sync::mpsc::Receiver<InputMessage>,
+        incoming: mpsc::UnboundedSender<SocketData>,
+        connection_commands: ConnectionCommandReceiver,
+        registry: Arc<Mutex<StreamRegistry>>,
+    }
+
+    fn test_context() -> (
+        StreamContext,
+        tokio::sync::mpsc::Receiver<InputMessage>,
+        ConnectionCommandReceiver,
+    ) {
+        let (input_sender, sent) = tokio::sync::mpsc::channel(16);
+        let (connection_command_sender, connection_commands) = mpsc::unbounded();
+        let context = StreamContext {
+            input_sender,
+            connection_command_sender,
+            lane_queue_lengths: LaneQueueLengths::new(),
+            packet_type: None,
+            per_message_surbs: DEFAULT_PER_MESSAGE_SURBS,
+            registry: Default::default(),
+        };
+        (context, sent, connection_commands)
+    }
+
+    fn test_stream() -> TestStream {
+        let (context, sent, connection_commands) = test_context();
+        let registry = context.registry.clone();
+        let (incoming, stream_receiver) = mpsc::unbounded();
+        let stream = MixnetStream::new(
+            STREAM_ID,
+            StreamPeer::SenderTag(AnonymousSenderTag::from([1u8; 16])),
+            stream_receiver,
+            context,
+        );
+        TestStream {
+            stream,
+            sent,
+            incoming,
+            connection_commands,
+            registry,
+        }
+    }
+
+    fn unwrap_socket_data(message: InputMessage) -> SocketData {
+        match message {
+            InputMessage::Reply { data, lane, .. } => {
+                assert_eq!(lane, TransmissionLane::ConnectionId(STREAM_ID));
+                SocketData::try_from_request_bytes(&data).unwrap()
+            }
+            other => panic!("unexpected input message: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn written_data_is_sent_in_sequence() {
+        let mut test = test_stream();
+
+        test.stream.write_all(b"foo").await.unwrap();
+        test.stream.write_all(b"bar").await.unwrap();
+        test.stream.shutdown().await.unwrap();
+
+        for (seq, data) in [b"foo".to_vec(), b"bar".to_vec()].into_iter().enumerate() {
+            let sent = unwrap_socket_data(test.sent.recv().await.unwrap());
+            assert_eq!(sent.header.seq, seq as u64);
+            assert_eq!(sent.header.connection_id, STREAM_ID);
+            assert!(!sent.header.local_socket_closed);
+            assert_eq!(sent.data, data);
+        }
+        let closing = unwrap_socket_data(test.sent.recv().await.unwrap());
+        assert_eq!(closing.header.seq, 2);
+        assert!(closing.header.local_socket_closed);
+        assert!(closing.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn writing_after_shutdown_fails() {
+        let mut test = test_stream();
+
+        test.stream.shutdown().await.unwrap();
+        let err = test.stream.write_all(b"foo").await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[tokio::test]
+    async fn received_data_is_read_in_order_until_remote_closes() {
+        let mut test = test_stream();
+
+        let out_of_order = [
+            SocketData::new(2, STREAM_ID, true, b"baz".to_vec()),
+            SocketData::new(0, STREAM_ID, false, b"foo".to_vec()),
+            // duplicate due to a retransmission
+            SocketData::new(0, STREAM_ID, false, b"foo".to_vec()),
+            SocketData::new(1, STREAM_ID, false, b"bar".to_vec()),
+        ];
+        for socket_data in out_of_order {
+            test.incoming.unbounded_send(socket_data).unwrap();
+        }
+
+        let mut received = Vec::new();
+        timeout(TIMEOUT, test.stream.read_to_end(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received, b"foobarbaz");
+    }
+
+    #[tokio::test]
+    async fn reading_fails_once_the_client_stops() {
+        let mut test = test_stream();
+
+        test.incoming
+            .unbounded_send(SocketData::new(0, STREAM_ID, false, b"foo".to_vec()))
+            .unwrap();
+        drop(test.incoming);
+
+        let mut received = Vec::new();
+        let err = test.stream.read_to_end(&mut received).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted);
+        assert_eq!(received, b"foo");
+    }
+
+    #[tokio::test]
+    async fn dropping_stream_notifies_remote_and_releases_its_lane() {
+        let mut test = test_stream();
+
+        test.stream.write_all(b"foo").await.unwrap();
+        test.stream.flush().await.unwrap();
+        drop(test.stream);
+
+        let data = unwrap_socket_data(test.sent.recv().await.unwrap());
+        assert!(!data.header.local_socket_closed);
+        let closing = unwrap_socket_data(test.sent.recv().await.unwrap());
+        assert_eq!(closing.header.seq, 1);
+        assert!(closing.header.local_socket_closed);
+
+        let command = timeout(TIMEOUT, test.connection_commands.next())
+            .await
+            .unwrap();
+        assert!(matches!(command, Some(ConnectionCommand::Close(STREAM_ID))));
+        assert!(test.registry.lock().unwrap().finished.contains(&STREAM_ID));
+    }
+
+    #[tokio::test]
+    async fn dropping_shut_down_stream_does_not_close_it_again() {
+        let mut test = test_stream();
+
+        test.stream.shutdown().await.unwrap();
+        drop(test.stream);
+
+        let closing = unwrap_socket_data(test.sent.recv().await.unwrap());
+        assert!(closing.header.local_socket_closed);
+        assert!(test.sent.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn router_accepts_new_streams_and_ignores_finished_ones() {
+        let (context, _sent, _connection_commands) = test_context();
+        let (accepted_sender, mut accepted) = mpsc::unbounded();
+        let router = StreamRouter {
+            context: context.clone(),
+            accepted: accepted_sender,
+        };
+        let sender_tag = AnonymousSenderTag::from([1u8; 16]);
+        let message = |seq| ReconstructedMessage {
+            message: SocketData::new(seq, STREAM_ID, false, Vec::new()).into_request_bytes(),
+            sender_tag: Some(sender_tag),
+        };
+
+        router.route(message(0));
+        let stream = accepted.try_recv().unwrap();
+        assert_eq!(stream.id(), STREAM_ID);
+        assert_eq!(stream.peer(), &StreamPeer::SenderTag(sender_tag));
+
+        drop(stream);
+        router.route(message(1));
+        assert!(accepted.try_recv().is_err());
+        assert!(context.registry.lock().unwrap().streams.is_empty());
+    }
+
+    #[tokio::test]
+    async fn router_only_accepts_data_from_the_stream_peer() {
+        let (context, _sent, _connection_commands) = test_context();
+        let (accepted_sender, mut accepted) = mpsc::unbounded();
+        let router = StreamRouter {
+            context: context.clone(),
+            accepted: accepted_sender,
+        };
+        let peer = AnonymousSenderTag::from([1u8; 16]);
+        let intruder = AnonymousSenderTag::from([2u8; 16]);
+        let message = |seq, closed, data: &[u8], sender_tag| ReconstructedMessage {
+            message: SocketData::new(seq, STREAM_ID, closed, data.to_vec()).into_request_bytes(),
+            sender_tag,
+        };
+
+        router.route(message(0, false, b"foo", Some(peer)));
+        let mut stream = accepted.try_recv().unwrap();
+
+        router.route(message(1, true, b"injected", Some(intruder)));
+        router.route(message(1, true, b"injected", None));
+        router.route(message(1, true, b"bar", Some(peer)));
+
+        let mut received = Vec::new();
+        timeout(TIMEOUT, stream.read_to_end(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(received, b"foobar");
+    }
+
+    #[test]
+    fn finished_streams_are_bounded() {
+        let mut finished = FinishedStreams::default();
+        for id in 0..MAX_REMEMBERED_FINISHED_STREAMS as ConnectionId + 10 {
+            finished.insert(id);
+        }
+
+        assert_eq!(finished.ids.len(), MAX_REMEMBERED_FINISHED_STREAMS);
+        assert_eq!(
+            finished.finishing_order.len(),
+            MAX_REMEMBERED_FINISHED_STREAMS
+        );
+        assert!(!finished.contains(&9));
+        assert!(finished.contains(&10));
+    }
+}
@@ -0,0 +1,261 @@
+//! TCP proxying over the Nym mixnet for applications that do not speak SOCKS5.
+//!
+//! A [`NymProxyClient`] listens on a local TCP socket and tunnels every accepted connection
+//! to a fixed Nym address. On the other end, a [`NymProxyServer`] running behind that address
+//! opens a connection to its upstream service for every new session and streams the data back.
+//! Each session uses its own [`TransmissionLane`](crate::mixnet::TransmissionLane) and the data
+//! is reassembled in order on both sides, so any TCP service can be exposed through the mixnet.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nym_sdk::mixnet::MixnetClient;
+//! use nym_sdk::tcp_proxy::{NymProxyClient, NymProxyServer};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // expose the service listening on port 8000 over the mixnet
+//!     let server_client = MixnetClient::connect_new().await.unwrap();
+//!     let server_address = *server_client.nym_address();
+//!     let server = NymProxyServer::new(server_client, "127.0.0.1:8000".parse().unwrap());
+//!     tokio::spawn(server.run());
+//!
+//!     // and make it available locally on port 8080
+//!     let client_client = MixnetClient::connect_new().await.unwrap();
+//!     let client = NymProxyClient::new(
+//!         client_client,
+//!         server_address,
+//!         "127.0.0.1:8080".parse().unwrap(),
+//!     );
+//!     client.run().await.unwrap();
+//! }
+//! ```
+
+mod client;
+mod message;
+mod server;
+
+pub use client::NymProxyClient;
+pub use server::NymProxyServer;
+
+#[cfg(test)]
+mod tests {
+    use super::message::ProxiedMessage;
+    use super::{client, server};
+    use crate::mixnet::{AnonymousSenderTag, Recipient};
+    use futures::channel::mpsc;
+    use nym_client_core::client::inbound_messages::InputMessage;
+    use nym_socks5_proxy_helpers::connection_controller::{Controller, ControllerSender};
+    use nym_socks5_requests::SocketData;
+    use nym_sphinx::receiver::ReconstructedMessage;
+    use nym_task::connections::{ConnectionCommandReceiver, LaneQueueLengths};
+    use nym_task::TaskClient;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::time::timeout;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn server_address() -> Recipient {
+        Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap()
+    }
+
+    fn start_controller() -> (ControllerSender, ConnectionCommandReceiver) {
+        let (connection_command_sender, connection_command_receiver) = mpsc::unbounded();
+        let (mut controller, controller_sender) =
+            Controller::new(connection_command_sender, TaskClient::dummy());
+        tokio::spawn(async move { controller.run().await });
+        (controller_sender, connection_command_receiver)
+    }
+
+    fn unwrap_data(message: InputMessage) -> Vec<u8> {
+        match message {
+            InputMessage::Anonymous { data, .. } | InputMessage::Reply { data, .. } => data,
+            other => panic!("unexpected input message: {other:?}"),
+        }
+    }
+
+    // starts the client and the server side of the proxy, with the mixnet between them
+    // replaced by a pair of channels. returns the address the client is listening on
+    async fn start_proxy(upstream_address: SocketAddr) -> SocketAddr {
+        let (client_controller, client_commands) = start_controller();
+        let (server_controller, server_commands) = start_controller();
+        let (client_input_sender, mut client_input) = tokio::sync::mpsc::channel(128);
+        let (server_input_sender, mut server_input) = tokio::sync::mpsc::channel(128);
+
+        let client_context = client::SessionContext {
+            server_address: server_address(),
+            input_sender: client_input_sender,
+            controller_sender: client_controller.clone(),
+            lane_queue_lengths: LaneQueueLengths::new(),
+            packet_type: None,
+            connection_start_surbs: 0,
+            per_request_surbs: 0,
+        };
+        let server_context = server::SessionContext {
+            upstream_address,
+            input_sender: server_input_sender,
+            controller_sender: server_controller,
+            lane_queue_lengths: LaneQueueLengths::new(),
+            packet_type: None,
+            sessions: server::SessionOwners::default(),
+        };
+
+        // "mixnet"
+        tokio::spawn(async move {
+            // keep the connection command channels alive
+            let _commands = (client_commands, server_commands);
+            let sender_tag = AnonymousSenderTag::new_random(&mut rand::thread_rng());
+            let shutdown = TaskClient::dummy();
+            loop {
+                tokio::select! {
+                    Some(message) = client_input.recv() => {
+                        let received = ReconstructedMessage {
+                            message: unwrap_data(message),
+                            sender_tag: Some(sender_tag),
+                        };
+                        server::handle_client_message(&server_context, received, &shutdown)
+                    }
+                    Some(message) = server_input.recv() => {
+                        let received = ReconstructedMessage {
+                            message: unwrap_data(message),
+                            sender_tag: None,
+                        };
+                        client::handle_server_message(&client_controller, received)
+                    }
+                    else => break,
+                }
+            }
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let listen_address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, peer)) = listener.accept().await {
+                tokio::spawn(client::run_session(
+                    client_context.clone(),
+                    stream,
+                    peer,
+                    TaskClient::dummy(),
+                ));
+            }
+        });
+
+        listen_address
+    }
+
+    async fn start_echo_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = stream.split();
+                    tokio::io::copy(&mut reader, &mut writer).await.ok();
+                });
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn data_makes_a_round_trip_through_the_proxy() {
+        let upstream = start_echo_server().await;
+        let proxy = start_proxy(upstream).await;
+
+        let mut stream = TcpStream::connect(proxy).await.unwrap();
+
+        // big enough to span multiple proxied messages
+        let sent = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+        stream.write_all(&sent).await.unwrap();
+
+        let mut received = vec![0; sent.len()];
+        timeout(TIMEOUT, stream.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sent, received);
+
+        // and the sessions are independent of each other
+        let mut another = TcpStream::connect(proxy).await.unwrap();
+        another.write_all(b"hello mixnet").await.unwrap();
+        let mut received = [0; 12];
+        timeout(TIMEOUT, another.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&received, b"hello mixnet");
+    }
+
+    #[tokio::test]
+    async fn session_is_closed_if_upstream_is_unreachable() {
+        // grab some port nothing is going to listen on
+        let upstream = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let proxy = start_proxy(upstream).await;
+
+        let mut stream = TcpStream::connect(proxy).await.unwrap();
+        stream.write_all(b"anyone there?").await.unwrap();
+
+        let mut buf = [0; 16];
+        let read = timeout(TIMEOUT, stream.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        assert_eq!(read, 0);
+    }
+
+    #[tokio::test]
+    async fn sessions_are_bound_to_the_client_that_opened_them() {
+        let upstream = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (server_controller, _server_commands) = start_controller();
+        let (server_input_sender, _server_input) = tokio::sync::mpsc::channel(128);
+        let context = server::SessionContext {
+            upstream_address: upstream.local_addr().unwrap(),
+            input_sender: server_input_sender,
+            controller_sender: server_controller,
+            lane_queue_lengths: LaneQueueLengths::new(),
+            packet_type: None,
+            sessions: server::SessionOwners::default(),
+        };
+        let shutdown = TaskClient::dummy();
+
+        let owner = AnonymousSenderTag::new_random(&mut rand::thread_rng());
+        let intruder = AnonymousSenderTag::new_random(&mut rand::thread_rng());
+        let deliver = |message: ProxiedMessage, sender_tag| {
+            let received = ReconstructedMessage {
+                message: message.into_bytes(),
+                sender_tag: Some(sender_tag),
+            };
+            server::handle_client_message(&context, received, &shutdown)
+        };
+
+        deliver(ProxiedMessage::Open { connection_id: 42 }, owner);
+        let (mut session, _) = timeout(TIMEOUT, upstream.accept()).await.unwrap().unwrap();
+
+        // neither the owner nor anybody else can open the same session again
+        deliver(ProxiedMessage::Open { connection_id: 42 }, owner);
+        deliver(ProxiedMessage::Open { connection_id: 42 }, intruder);
+        assert!(timeout(Duration::from_millis(200), upstream.accept())
+            .await
+            .is_err());
+
+        // and only the owner's data makes it upstream
+        let data =
+            |payload: &[u8]| ProxiedMessage::Data(SocketData::new(0, 42, false, payload.to_vec()));
+        deliver(data(b"injected"), intruder);
+        deliver(data(b"genuine!"), owner);
+
+        let mut received = [0; 8];
+        timeout(TIMEOUT, session.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&received, b"genuine!");
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixnet::{MixnetClient, Recipient, TransmissionLane};
+use crate::tcp_proxy::message::ProxiedMessage;
+use crate::Result;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::{debug, error, info, trace, warn};
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_socks5_proxy_helpers::connection_controller::{
+    Controller, ControllerCommand, ControllerSender,
+};
+use nym_socks5_proxy_helpers::proxy_runner::ProxyRunner;
+use nym_socks5_requests::{ConnectionId, SocketData};
+use nym_sphinx::params::{PacketSize, PacketType};
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::LaneQueueLengths;
+use nym_task::TaskClient;
+use rand::RngCore;
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+
+// the same defaults as used by the socks5 client
+const DEFAULT_CONNECTION_START_SURBS: u32 = 20;
+const DEFAULT_PER_REQUEST_SURBS: u32 = 3;
+
+/// Local TCP listener that tunnels every accepted connection through the mixnet
+/// to the [`NymProxyServer`](super::NymProxyServer) running behind the specified address.
+pub struct NymProxyClient {
+    mixnet_client: MixnetClient,
+    server_address: Recipient,
+    listen_address: SocketAddr,
+    connection_start_surbs: u32,
+    per_request_surbs: u32,
+}
+
+// everything each tunnelled connection needs to run its side of the proxy
+#[derive(Clone)]
+pub(super) struct SessionContext {
+    pub(super) server_address: Recipient,
+    pub(super) input_sender: InputMessageSender,
+    pub(super) controller_sender: ControllerSender,
+    pub(super) lane_queue_lengths: LaneQueueLengths,
+    pub(super) packet_type: Option<PacketType>,
+    pub(super) connection_start_surbs: u32,
+    pub(super) per_request_surbs: u32,
+}
+
+impl NymProxyClient {
+    /// Creates new proxy client that is going to accept connections on `listen_address`
+    /// and forward them to the [`NymProxyServer`](super::NymProxyServer) at `server_address`.
+    pub fn new(
+        mixnet_client: MixnetClient,
+        server_address: Recipient,
+        listen_address: SocketAddr,
+    ) -> Self {
+        NymProxyClient {
+            mixnet_client,
+            server_address,
+            listen_address,
+            connection_start_surbs: DEFAULT_CONNECTION_START_SURBS,
+            per_request_surbs: DEFAULT_PER_REQUEST_SURBS,
+        }
+    }
+
+    /// Sets the number of reply SURBs attached to the message opening a new session
+    /// and to every subsequent data message, respectively.
+    #[must_use]
+    pub fn with_reply_surbs(mut self, connection_start_surbs: u32, per_request_surbs: u32) -> Self {
+        self.connection_start_surbs = connection_start_surbs;
+        self.per_request_surbs = per_request_surbs;
+        self
+    }
+
+    /// Accepts and proxies the connections until the underlying mixnet client is shut down.
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(self.listen_address).await?;
+        info!(
+            "Proxying connections from {} to {}",
+            self.listen_address, self.server_address
+        );
+
+        let mut mixnet_client = self.mixnet_client;
+        let mut shutdown = mixnet_client.task_handle.get_handle();
+
+        let (mut controller, controller_sender) = Controller::new(
+            mixnet_client.connection_command_sender(),
+            shutdown.fork("nym_socks5_proxy_helpers::connection_controller::Controller"),
+        );
+        tokio::spawn(async move {
+            controller.run().await;
+        });
+
+        let context = SessionContext {
+            server_address: self.server_address,
+            input_sender: mixnet_client.client_input.input_sender.clone(),
+            controller_sender,
+            lane_queue_lengths: mixnet_client.shared_lane_queue_lengths(),
+            packet_type: mixnet_client.packet_type,
+            connection_start_surbs: self.connection_start_surbs,
+            per_request_surbs: self.per_request_surbs,
+        };
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("NymProxyClient: received shutdown");
+                    break;
+                }
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        let context = context.clone();
+                        let shutdown = shutdown.fork("session");
+                        tokio::spawn(async move {
+                            run_session(context, stream, peer, shutdown).await
+                        });
+                    }
+                    Err(err) => warn!("failed to accept incoming connection: {err}"),
+                },
+                received = mixnet_client.next() => match received {
+                    Some(received) => handle_server_message(&context.controller_sender, received),
+                    None => {
+                        debug!("NymProxyClient: mixnet client has stopped");
+                        break;
+                    }
+                },
+            }
+        }
+
+        // make sure our handle is gone so that it wouldn't block the shutdown of the client
+        shutdown.mark_as_success();
+        drop(shutdown);
+
+        mixnet_client.disconnect().await;
+        Ok(())
+    }
+}
+
+pub(super) fn handle_server_message(
+    controller_sender: &ControllerSender,
+    received: ReconstructedMessage,
+) {
+    match SocketData::try_from_response_bytes(&received.message) {
+        Ok(socket_data) => {
+            if let Err(err) =
+                controller_sender.unbounded_send(ControllerCommand::new_send(socket_data))
+            {
+                debug!("failed to forward the proxied data: {err}");
+            }
+        }
+        Err(err) => warn!("received malformed message from the proxy server: {err}"),
+    }
+}
+
+pub(super) async fn run_session(
+    context: SessionContext,
+    stream: TcpStream,
+    peer: SocketAddr,
+    mut shutdown: TaskClient,
+) {
+    let connection_id: ConnectionId = rand::rngs::OsRng.next_u64();
+
+    let (mix_sender, mix_receiver) = mpsc::unbounded();
+    if context
+        .controller_sender
+        .unbounded_send(ControllerCommand::Insert {
+            connection_id,
+            connection_sender: mix_sender,
+        })
+        .is_err()
+    {
+        debug!("failed to register session for {peer} - the controller has stopped");
+        shutdown.disarm();
+        return;
+    }
+
+    let lane = TransmissionLane::ConnectionId(connection_id);
+    let open = InputMessage::new_anonymous(
+        context.server_address,
+        ProxiedMessage::Open { connection_id }.into_bytes(),
+        context.connection_start_surbs,
+        lane,
+        context.packet_type,
+    );
+    if context.input_sender.send(open).await.is_err() {
+        error!("failed to open session {connection_id} - the mixnet client has stopped");
+        context
+            .controller_sender
+            .unbounded_send(ControllerCommand::Remove { connection_id })
+            .ok();
+        shutdown.disarm();
+        return;
+    }
+
+    info!("Starting proxy for {peer} (id: {connection_id})");
+    let server_address = context.server_address;
+    let per_request_surbs = context.per_request_surbs;
+    let packet_type = context.packet_type;
+    ProxyRunner::new(
+        stream,
+        peer.to_string(),
+        server_address.to_string(),
+        mix_receiver,
+        context.input_sender,
+        // FIXME: this does NOT include overhead due to acks or chunking
+        // (so actual true plaintext is smaller)
+        PacketSize::default().plaintext_size(),
+        connection_id,
+        Some(context.lane_queue_lengths),
+        shutdown,
+    )
+    .run(move |socket_data| {
+        InputMessage::new_anonymous(
+            server_address,
+            ProxiedMessage::Data(socket_data).into_bytes(),
+            per_request_surbs,
+            TransmissionLane::ConnectionId(connection_id),
+            packet_type,
+        )
+    })
+    .await
+    .into_inner();
+    info!("Proxy for {peer} is finished (id: {connection_id})");
+
+    context
+        .controller_sender
+        .unbounded_send(ControllerCommand::Remove { connection_id })
+        .ok();
+}
@@ -0,0 +1,73 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_socks5_requests::{ConnectionId, InsufficientSocketDataError, SocketData};
+use std::mem;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ProxiedMessageError {
+    #[error("the received message was empty")]
+    NoData,
+
+    #[error("{value} is not a valid proxied message type")]
+    UnknownMessageType { value: u8 },
+
+    #[error("not enough bytes to recover the session id")]
+    SessionIdTooShort,
+
+    #[error(transparent)]
+    MalformedSocketData(#[from] InsufficientSocketDataError),
+}
+
+#[repr(u8)]
+enum MessageType {
+    Open = 0,
+    Data = 1,
+}
+
+/// Messages sent by the [`NymProxyClient`](super::NymProxyClient) to the
+/// [`NymProxyServer`](super::NymProxyServer). The responses are sent back as plain [`SocketData`].
+#[derive(Debug)]
+pub(crate) enum ProxiedMessage {
+    /// Request to open a new session to the upstream service.
+    Open { connection_id: ConnectionId },
+
+    /// Data belonging to an already requested session.
+    Data(SocketData),
+}
+
+impl ProxiedMessage {
+    // TYPE || CONNECTION_ID
+    // TYPE || SOCKET_DATA
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        match self {
+            ProxiedMessage::Open { connection_id } => std::iter::once(MessageType::Open as u8)
+                .chain(connection_id.to_be_bytes())
+                .collect(),
+            ProxiedMessage::Data(socket_data) => std::iter::once(MessageType::Data as u8)
+                .chain(socket_data.into_request_bytes_iter())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn try_from_bytes(b: &[u8]) -> Result<Self, ProxiedMessageError> {
+        let Some((&message_type, rest)) = b.split_first() else {
+            return Err(ProxiedMessageError::NoData);
+        };
+
+        match message_type {
+            t if t == MessageType::Open as u8 => {
+                if rest.len() != mem::size_of::<ConnectionId>() {
+                    return Err(ProxiedMessageError::SessionIdTooShort);
+                }
+                // the unwrap here is fine as we just ensured we have the exact amount of bytes we need
+                let connection_id = ConnectionId::from_be_bytes(rest.try_into().unwrap());
+                Ok(ProxiedMessage::Open { connection_id })
+            }
+            t if t == MessageType::Data as u8 => Ok(ProxiedMessage::Data(
+                SocketData::try_from_request_bytes(rest)?,
+            )),
+            value => Err(ProxiedMessageError::UnknownMessageType { value }),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixnet::{AnonymousSenderTag, MixnetClient, TransmissionLane};
+use crate::tcp_proxy::message::ProxiedMessage;
+use crate::Result;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::{debug, error, info, trace, warn};
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_socks5_proxy_helpers::connection_controller::{
+    Controller, ControllerCommand, ControllerSender,
+};
+use nym_socks5_proxy_helpers::proxy_runner::ProxyRunner;
+use nym_socks5_requests::{ConnectionId, SocketData};
+use nym_sphinx::params::{PacketSize, PacketType};
+use nym_sphinx::receiver::ReconstructedMessage;
+use nym_task::connections::LaneQueueLengths;
+use nym_task::TaskClient;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpStream;
+
+/// Receives sessions opened by [`NymProxyClient`](super::NymProxyClient)s and forwards them
+/// to the upstream service. All the data is sent back using the reply SURBs provided by the clients,
+/// so their addresses are never revealed.
+pub struct NymProxyServer {
+    mixnet_client: MixnetClient,
+    upstream_address: SocketAddr,
+}
+
+// everything each session needs to run its side of the proxy
+#[derive(Clone)]
+pub(super) struct SessionContext {
+    pub(super) upstream_address: SocketAddr,
+    pub(super) input_sender: InputMessageSender,
+    pub(super) controller_sender: ControllerSender,
+    pub(super) lane_queue_lengths: LaneQueueLengths,
+    pub(super) packet_type: Option<PacketType>,
+    pub(super) sessions: SessionOwners,
+}
+
+struct SessionOwner {
+    sender_tag: AnonymousSenderTag,
+    opened: bool,
+}
+
+/// Keeps track of which client (identified by its sender tag) owns each session,
+/// so that nobody else could open it again or inject data into it.
+#[derive(Clone, Default)]
+pub(super) struct SessionOwners {
+    inner: Arc<Mutex<HashMap<ConnectionId, SessionOwner>>>,
+}
+
+impl SessionOwners {
+    fn owners(&self) -> std::sync::MutexGuard<'_, HashMap<ConnectionId, SessionOwner>> {
+        // the map is always left in a consistent state, so it's fine to recover it
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Attempts to claim the session for the provided sender. Returns `false` if the session
+    /// has already been opened or if it belongs to somebody else.
+    fn open(&self, connection_id: ConnectionId, sender_tag: AnonymousSenderTag) -> bool {
+        let mut owners = self.owners();
+        let owner = owners.entry(connection_id).or_insert(SessionOwner {
+            sender_tag,
+            opened: false,
+        });
+        if owner.sender_tag != sender_tag || owner.opened {
+            return false;
+        }
+        owner.opened = true;
+        true
+    }
+
+    /// Checks whether the provided sender is allowed to send data for the session.
+    /// Note that the data might arrive before the corresponding `Open`, in which case
+    /// the session gets claimed by whoever sent it.
+    fn accepts_data(&self, connection_id: ConnectionId, sender_tag: AnonymousSenderTag) -> bool {
+        self.owners()
+            .entry(connection_id)
+            .or_insert(SessionOwner {
+                sender_tag,
+                opened: false,
+            })
+            .sender_tag
+            == sender_tag
+    }
+
+    fn remove(&self, connection_id: ConnectionId) {
+        self.owners().remove(&connection_id);
+    }
+}
+
+impl NymProxyServer {
+    /// Creates new proxy server that is going to forward all sessions received by the provided
+    /// mixnet client to `upstream_address`.
+    pub fn new(mixnet_client: MixnetClient, upstream_address: SocketAddr) -> Self {
+        NymProxyServer {
+            mixnet_client,
+            upstream_address,
+        }
+    }
+
+    /// Handles the incoming sessions until the underlying mixnet client is shut down.
+    pub async fn run(self) -> Result<()> {
+        info!(
+            "Forwarding sessions received on {} to {}",
+            self.mixnet_client.nym_address(),
+            self.upstream_address
+        );
+
+        let mut mixnet_client = self.mixnet_client;
+        let mut shutdown = mixnet_client.task_handle.get_handle();
+
+        let (mut controller, controller_sender) = Controller::new(
+            mixnet_client.connection_command_sender(),
+            shutdown.fork("nym_socks5_proxy_helpers::connection_controller::Controller"),
+        );
+        tokio::spawn(async move {
+            controller.run().await;
+        });
+
+        let context = SessionContext {
+            upstream_address: self.upstream_address,
+            input_sender: mixnet_client.client_input.input_sender.clone(),
+            controller_sender,
+            lane_queue_lengths: mixnet_client.shared_lane_queue_lengths(),
+            packet_type: mixnet_client.packet_type,
+            sessions: SessionOwners::default(),
+        };
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("NymProxyServer: received shutdown");
+                    break;
+                }
+                received = mixnet_client.next() => match received {
+                    Some(received) => handle_client_message(&context, received, &shutdown),
+                    None => {
+                        debug!("NymProxyServer: mixnet client has stopped");
+                        break;
+                    }
+                },
+            }
+        }
+
+        // make sure our handle is gone so that it wouldn't block the shutdown of the client
+        shutdown.mark_as_success();
+        drop(shutdown);
+
+        mixnet_client.disconnect().await;
+        Ok(())
+    }
+}
+
+pub(super) fn handle_client_message(
+    context: &SessionContext,
+    received: ReconstructedMessage,
+    shutdown: &TaskClient,
+) {
+    let Some(sender_tag) = received.sender_tag else {
+        warn!("received a proxy message without any reply SURBs - there's no way to respond to it");
+        return;
+    };
+
+    match ProxiedMessage::try_from_bytes(&received.message) {
+        Ok(ProxiedMessage::Open { connection_id }) => {
+            if !context.sessions.open(connection_id, sender_tag) {
+                warn!("session {connection_id} has already been opened - ignoring the request");
+                return;
+            }
+            let context = context.clone();
+            let shutdown = shutdown.fork("session");
+            tokio::spawn(
+                async move { run_session(context, connection_id, sender_tag, shutdown).await },
+            );
+        }
+        Ok(ProxiedMessage::Data(socket_data)) => {
+            let connection_id = socket_data.header.connection_id;
+            if !context.sessions.accepts_data(connection_id, sender_tag) {
+                warn!("received data for session {connection_id} from a client that doesn't own it - dropping it");
+                return;
+            }
+            if let Err(err) = context
+                .controller_sender
+                .unbounded_send(ControllerCommand::new_send(socket_data))
+            {
+                if shutdown.is_shutdown_poll() {
+                    debug!("failed to forward the proxied data: {err}");
+                } else {
+                    error!("failed to forward the proxied data: {err}");
+                }
+            }
+        }
+        Err(err) => warn!("received malformed message from the proxy client: {err}"),
+    }
+}
+
+pub(super) async fn run_session(
+    context: SessionContext,
+    connection_id: ConnectionId,
+    sender_tag: AnonymousSenderTag,
+    mut shutdown: TaskClient,
+) {
+    let lane = TransmissionLane::ConnectionId(connection_id);
+    let stream = match TcpStream::connect(context.upstream_address).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            error!(
+                "failed to connect to {} for session {connection_id}: {err}",
+                context.upstream_address
+            );
+            shutdown.disarm();
+            context.sessions.remove(connection_id);
+
+            // make sure any data that has arrived (or is going to arrive) for this session
+            // is not buffered forever
+            context
+                .controller_sender
+                .unbounded_send(ControllerCommand::Remove { connection_id })
+                .ok();
+
+            // inform the client that the session is closed before it even was established
+            let closed = SocketData::new(0, connection_id, true, Vec::new());
+            let reply = InputMessage::new_reply(
+                sender_tag,
+                closed.into_response_bytes(),
+                lane,
+                context.packet_type,
+            );
+            if context.input_sender.send(reply).await.is_err() {
+                debug!("failed to notify the client about the closed session {connection_id}");
+            }
+            return;
+        }
+    };
+
+    // it's a fresh session - register it with our controller
+    let (mix_sender, mix_receiver) = mpsc::unbounded();
+    if context
+        .controller_sender
+        .unbounded_send(ControllerCommand::Insert {
+            connection_id,
+            connection_sender: mix_sender,
+        })
+        .is_err()
+    {
+        debug!("failed to register session {connection_id} - the controller has stopped");
+        shutdown.disarm();
+        context.sessions.remove(connection_id);
+        return;
+    }
+
+    info!("Starting proxy for session {connection_id}");
+    let packet_type = context.packet_type;
+    ProxyRunner::new(
+        stream,
+        context.upstream_address.to_string(),
+        "???".to_string(), // we don't know the address of the client
+        mix_receiver,
+        context.input_sender,
+        // FIXME: this does NOT include overhead due to acks or chunking
+        // (so actual true plaintext is smaller)
+        PacketSize::default().plaintext_size(),
+        connection_id,
+        Some(context.lane_queue_lengths),
+        shutdown,
+    )
+    .run(move |socket_data| {
+        InputMessage::new_reply(
+            sender_tag,
+            socket_data.into_response_bytes(),
+            TransmissionLane::ConnectionId(connection_id),
+            packet_type,
+        )
+    })
+    .await
+    .into_inner();
+    info!("Proxy for session {connection_id} is finished");
+
+    // proxy is done - remove the access channel from the controller
+    context
+        .controller_sender
+        .unbounded_send(ControllerCommand::Remove { connection_id })
+        .ok();
+    context.sessions.remove(connection_id);
+}
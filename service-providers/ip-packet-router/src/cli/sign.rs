@@ -1,6 +1,7 @@
 use crate::cli::{try_load_current_config, version_check};
 use clap::Args;
 use nym_bin_common::output_format::OutputFormat;
+use nym_client_core::cli_helpers::client_encrypt_keys::CommonClientKeysPassphraseArgs;
 use nym_client_core::client::key_manager::persistence::OnDiskKeys;
 use nym_client_core::error::ClientCoreError;
 use nym_crypto::asymmetric::identity;
@@ -17,6 +18,9 @@ pub(crate) struct Sign {
     #[arg(long)]
     contract_msg: String,
 
+    #[command(flatten)]
+    keys_passphrase: CommonClientKeysPassphraseArgs,
+
     #[arg(short, long, default_value_t = OutputFormat::default())]
     output: OutputFormat,
 }
@@ -59,7 +63,11 @@ pub(crate) async fn execute(args: &Sign) -> Result<(), IpPacketRouterError> {
         return Err(IpPacketRouterError::FailedLocalVersionCheck);
     }
 
-    let key_store = OnDiskKeys::new(config.storage_paths.common_paths.keys);
+    let keys_passphrase = args.keys_passphrase.passphrase();
+    let key_store = OnDiskKeys::new_with_optional_passphrase(
+        config.storage_paths.common_paths.keys,
+        keys_passphrase.as_ref().map(|p| p.as_bytes()),
+    );
     let identity_keypair = key_store.load_identity_keypair().map_err(|source| {
         IpPacketRouterError::ClientCoreError(ClientCoreError::KeyStoreError {
             source: Box::new(source),
@@ -1,5 +1,6 @@
 pub use nym_client_core::config::Config as BaseClientConfig;
 
+use ipnetwork::{Ipv4Network, Ipv6Network};
 use nym_bin_common::logging::LoggingSettings;
 use nym_client_core::{cli_helpers::CliClientConfig, config::disk_persistence::CommonClientPaths};
 use nym_config::{
@@ -11,8 +12,10 @@ use nym_service_providers_common::DEFAULT_SERVICE_PROVIDERS_DIR;
 use serde::{Deserialize, Serialize};
 use std::{
     io,
+    net::{Ipv4Addr, Ipv6Addr},
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -27,6 +30,12 @@ mod template;
 
 const DEFAULT_IP_PACKET_ROUTER_DIR: &str = "ip-packet-router";
 
+const DEFAULT_IP_POOL_IPV4_NETWORK: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 0);
+const DEFAULT_IP_POOL_IPV4_PREFIX: u8 = 16;
+const DEFAULT_IP_POOL_IPV6_NETWORK: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0xa160, 0, 0, 0, 0, 0);
+const DEFAULT_IP_POOL_IPV6_PREFIX: u8 = 112;
+const DEFAULT_IP_POOL_LEASE_DURATION: Duration = Duration::from_secs(5 * 60);
+
 /// Derive default path to ip packet routers' config directory.
 /// It should get resolved to `$HOME/.nym/service-providers/ip-packet-router/<id>/config`
 pub fn default_config_directory<P: AsRef<Path>>(id: P) -> PathBuf {
@@ -193,6 +202,9 @@ pub struct IpPacketRouter {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Configuration of the pool of addresses assigned to the connecting clients.
+    pub ip_pool: IpPool,
 }
 
 impl Default for IpPacketRouter {
@@ -204,6 +216,42 @@ impl Default for IpPacketRouter {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            ip_pool: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IpPool {
+    /// IPv4 subnet from which addresses are assigned to the connecting clients.
+    /// The first host address of the subnet is used by the tun device itself.
+    pub ipv4_subnet: Ipv4Network,
+
+    /// IPv6 subnet from which addresses are assigned to the connecting clients.
+    /// The first host address of the subnet is used by the tun device itself.
+    pub ipv6_subnet: Ipv6Network,
+
+    /// Specifies for how long the addresses of a disconnected client are kept reserved,
+    /// so that it could get the same ones back upon reconnecting.
+    #[serde(with = "humantime_serde")]
+    pub lease_duration: Duration,
+}
+
+impl Default for IpPool {
+    fn default() -> Self {
+        IpPool {
+            ipv4_subnet: Ipv4Network::new(
+                DEFAULT_IP_POOL_IPV4_NETWORK,
+                DEFAULT_IP_POOL_IPV4_PREFIX,
+            )
+            .expect("invalid default ipv4 pool subnet"),
+            ipv6_subnet: Ipv6Network::new(
+                DEFAULT_IP_POOL_IPV6_NETWORK,
+                DEFAULT_IP_POOL_IPV6_PREFIX,
+            )
+            .expect("invalid default ipv6 pool subnet"),
+            lease_duration: DEFAULT_IP_POOL_LEASE_DURATION,
         }
     }
 }
@@ -1,7 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::persistence::IpPacketRouterPaths;
+use crate::config::persistence::{IpPacketRouterPaths, DEFAULT_IP_POOL_ALLOCATIONS_FILENAME};
 use crate::config::Config;
 use crate::config::{default_config_filepath, IpPacketRouter};
 use crate::error::IpPacketRouterError;
@@ -54,6 +54,11 @@ impl ConfigV1 {
             ip_packet_router: self.ip_packet_router.into(),
             storage_paths: IpPacketRouterPaths {
                 common_paths: self.storage_paths.common_paths.upgrade_default()?,
+                ip_pool_allocations: self
+                    .storage_paths
+                    .ip_packet_router_description
+                    .parent()
+                    .map(|dir| dir.join(DEFAULT_IP_POOL_ALLOCATIONS_FILENAME)),
                 ip_packet_router_description: self.storage_paths.ip_packet_router_description,
             },
             logging: self.logging,
@@ -90,6 +95,7 @@ impl From<IpPacketRouterV1> for IpPacketRouter {
         IpPacketRouter {
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            ip_pool: Default::default(),
         }
     }
 }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 pub const DEFAULT_DESCRIPTION_FILENAME: &str = "description.toml";
+pub const DEFAULT_IP_POOL_ALLOCATIONS_FILENAME: &str = "ip_pool_allocations.json";
 
 #[derive(Debug, Deserialize, PartialEq, Eq, Serialize, Clone)]
 pub struct IpPacketRouterPaths {
@@ -14,6 +15,11 @@ pub struct IpPacketRouterPaths {
 
     /// Location of the file containing our description
     pub ip_packet_router_description: PathBuf,
+
+    /// Location of the file containing the static ip allocations that should survive restarts.
+    /// If not specified, the allocations are only kept in memory.
+    #[serde(default)]
+    pub ip_pool_allocations: Option<PathBuf>,
 }
 
 impl IpPacketRouterPaths {
@@ -23,6 +29,7 @@ impl IpPacketRouterPaths {
         Self {
             common_paths: CommonClientPaths::new_base(base_dir),
             ip_packet_router_description: base_dir.join(DEFAULT_DESCRIPTION_FILENAME),
+            ip_pool_allocations: Some(base_dir.join(DEFAULT_IP_POOL_ALLOCATIONS_FILENAME)),
         }
     }
 }
@@ -74,6 +74,9 @@ unknown_list_location = '{{ storage_paths.unknown_list_location }}'
 # Path to file containing description of this network-requester.
 ip_packet_router_description = '{{ storage_paths.ip_packet_router_description }}'
 
+# Path to file containing static ip allocations of the clients.
+{{#if storage_paths.ip_pool_allocations }}ip_pool_allocations = '{{ storage_paths.ip_pool_allocations }}'{{/if}}
+
 
 ##### logging configuration options #####
 
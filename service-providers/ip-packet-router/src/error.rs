@@ -1,5 +1,6 @@
 use std::net::SocketAddr;
 
+pub use crate::ip_pool::IpPoolError;
 pub use nym_client_core::error::ClientCoreError;
 use nym_exit_policy::PolicyError;
 use nym_id::NymIdError;
@@ -80,6 +81,9 @@ pub enum IpPacketRouterError {
     #[error("no recipient in response packet")]
     NoRecipientInResponse,
 
+    #[error("failed to set up the ip pool: {0}")]
+    IpPoolFailure(#[from] IpPoolError),
+
     #[error("failed to update client activity")]
     FailedToUpdateClientActivity,
 
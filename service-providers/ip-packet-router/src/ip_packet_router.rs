@@ -8,12 +8,14 @@ use nym_client_core::{
     client::mix_traffic::transceiver::GatewayTransceiver, HardcodedTopologyProvider,
     TopologyProvider,
 };
+use nym_node_http_api::state::ip_packet_router::SharedIpPoolStats;
 use nym_sdk::mixnet::Recipient;
 use nym_task::{TaskClient, TaskHandle};
 
 use crate::{
     config::Config,
     error::IpPacketRouterError,
+    ip_pool::IpPool,
     request_filter::{self, RequestFilter},
 };
 
@@ -41,6 +43,7 @@ pub struct IpPacketRouter {
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send + Sync>>,
     shutdown: Option<TaskClient>,
     on_start: Option<oneshot::Sender<OnStartData>>,
+    ip_pool_stats: Option<SharedIpPoolStats>,
 }
 
 impl IpPacketRouter {
@@ -52,6 +55,7 @@ impl IpPacketRouter {
             custom_gateway_transceiver: None,
             shutdown: None,
             on_start: None,
+            ip_pool_stats: None,
         }
     }
 
@@ -86,6 +90,13 @@ impl IpPacketRouter {
         self
     }
 
+    #[must_use]
+    #[allow(unused)]
+    pub fn with_ip_pool_stats(mut self, ip_pool_stats: SharedIpPoolStats) -> Self {
+        self.ip_pool_stats = Some(ip_pool_stats);
+        self
+    }
+
     #[must_use]
     #[allow(unused)]
     pub fn with_custom_topology_provider(
@@ -123,6 +134,15 @@ impl IpPacketRouter {
         use crate::{mixnet_listener, tun_listener};
         let task_handle: TaskHandle = self.shutdown.map(Into::into).unwrap_or_default();
 
+        // Set up the pool of addresses assigned to the clients
+        let mut ip_pool = IpPool::new(
+            &self.config.ip_packet_router.ip_pool,
+            self.config.storage_paths.ip_pool_allocations.clone(),
+        )?;
+        if let Some(ip_pool_stats) = self.ip_pool_stats {
+            ip_pool = ip_pool.with_stats(ip_pool_stats);
+        }
+
         // Connect to the mixnet
         let mixnet_client = crate::mixnet_client::create_mixnet_client(
             &self.config.base,
@@ -137,19 +157,21 @@ impl IpPacketRouter {
         let self_address = *mixnet_client.nym_address();
 
         // Create the TUN device that we interact with the rest of the world with
+        let tun_ips = ip_pool.tun_ips();
         let config = nym_tun::tun_device::TunDeviceConfig {
             base_name: crate::constants::TUN_BASE_NAME.to_string(),
-            ipv4: crate::constants::TUN_DEVICE_ADDRESS_V4,
-            netmaskv4: crate::constants::TUN_DEVICE_NETMASK_V4,
-            ipv6: crate::constants::TUN_DEVICE_ADDRESS_V6,
-            netmaskv6: crate::constants::TUN_DEVICE_NETMASK_V6.to_string(),
+            ipv4: tun_ips.ipv4,
+            netmaskv4: ip_pool.ipv4_netmask(),
+            ipv6: tun_ips.ipv6,
+            netmaskv6: ip_pool.ipv6_prefix().to_string(),
         };
         let (tun_reader, tun_writer) =
             tokio::io::split(nym_tun::tun_device::TunDevice::new_device_only(config)?);
 
         // Channel used by the IpPacketRouter to signal connected and disconnected clients to the
         // TunListener
-        let (connected_clients, connected_clients_rx) = mixnet_listener::ConnectedClients::new();
+        let (connected_clients, connected_clients_rx) =
+            mixnet_listener::ConnectedClients::new(ip_pool);
 
         let tun_listener = tun_listener::TunListener {
             tun_reader,
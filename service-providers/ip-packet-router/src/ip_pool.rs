@@ -0,0 +1,596 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config;
+use ipnetwork::{Ipv4Network, Ipv6Network};
+use nym_ip_packet_requests::IpPair;
+use nym_node_http_api::state::ip_packet_router::{IpPoolUtilisation, SharedIpPoolStats};
+use nym_sdk::mixnet::Recipient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// index of the addresses assigned to the tun device itself
+const TUN_DEVICE_INDEX: u32 = 1;
+
+// index of the first addresses that can be assigned to the clients
+const FIRST_CLIENT_INDEX: u32 = 2;
+
+#[derive(thiserror::Error, Debug)]
+pub enum IpPoolError {
+    #[error("the ipv4 subnet {subnet} is too small to assign any addresses to clients")]
+    Ipv4SubnetTooSmall { subnet: Ipv4Network },
+
+    #[error("the ipv6 subnet {subnet} is too small to assign any addresses to clients")]
+    Ipv6SubnetTooSmall { subnet: Ipv6Network },
+
+    #[error("the requested addresses {ips} are not part of the ip pool")]
+    NotInPool { ips: IpPair },
+
+    #[error("the requested addresses {ips} are already assigned to another client")]
+    IpsAlreadyInUse { ips: IpPair },
+
+    #[error("the client already has other addresses assigned to it: {ips}")]
+    ClientAlreadyConnected { ips: IpPair },
+
+    #[error("failed to load static ip allocations from {}: {source}", path.display())]
+    AllocationsLoadFailure {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to store static ip allocations at {}: {source}", path.display())]
+    AllocationsStoreFailure {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to serialize static ip allocations: {source}")]
+    AllocationsSerializationFailure {
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("static ip allocations stored at {} are malformed: {source}", path.display())]
+    MalformedAllocations {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AllocationKind {
+    // addresses chosen by the ip packet router
+    Dynamic,
+
+    // addresses explicitly requested by the client
+    Static,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AllocationState {
+    // the client is currently connected
+    Active,
+
+    // the client has disconnected, but the addresses are still reserved for it
+    Leased { expires_at: Instant },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Allocation {
+    owner: Recipient,
+    kind: AllocationKind,
+    state: AllocationState,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedAllocation {
+    nym_address: String,
+    ipv4: Ipv4Addr,
+    ipv6: Ipv6Addr,
+}
+
+/// Pool of the address pairs that are assigned to the connected clients.
+/// Both subnets are indexed in lockstep, i.e. the n-th host address of the ipv4 subnet
+/// is always paired with the n-th host address of the ipv6 subnet, so that the allocation
+/// is fully deterministic.
+pub(crate) struct IpPool {
+    ipv4_subnet: Ipv4Network,
+    ipv6_subnet: Ipv6Network,
+
+    // the highest index that can be assigned to a client
+    last_client_index: u32,
+
+    lease_duration: Duration,
+
+    allocations: HashMap<u32, Allocation>,
+
+    // location of the persisted static allocations, if any
+    allocations_file: Option<PathBuf>,
+
+    stats: Option<SharedIpPoolStats>,
+}
+
+impl IpPool {
+    pub(crate) fn new(
+        config: &config::IpPool,
+        allocations_file: Option<PathBuf>,
+    ) -> Result<Self, IpPoolError> {
+        // we can't use the network address nor the broadcast address
+        let ipv4_host_bits = 32 - config.ipv4_subnet.prefix() as u32;
+        let ipv4_last_index = if ipv4_host_bits >= 32 {
+            u32::MAX - 1
+        } else {
+            (1u32 << ipv4_host_bits).saturating_sub(2)
+        };
+
+        // ipv6 has no broadcast address, but we still skip the network (subnet-router anycast) one
+        let ipv6_host_bits = 128 - config.ipv6_subnet.prefix() as u32;
+        let ipv6_last_index = if ipv6_host_bits >= 32 {
+            u32::MAX
+        } else {
+            (1u32 << ipv6_host_bits) - 1
+        };
+
+        if ipv4_last_index < FIRST_CLIENT_INDEX {
+            return Err(IpPoolError::Ipv4SubnetTooSmall {
+                subnet: config.ipv4_subnet,
+            });
+        }
+        if ipv6_last_index < FIRST_CLIENT_INDEX {
+            return Err(IpPoolError::Ipv6SubnetTooSmall {
+                subnet: config.ipv6_subnet,
+            });
+        }
+
+        let mut pool = IpPool {
+            ipv4_subnet: config.ipv4_subnet,
+            ipv6_subnet: config.ipv6_subnet,
+            last_client_index: ipv4_last_index.min(ipv6_last_index),
+            lease_duration: config.lease_duration,
+            allocations: HashMap::new(),
+            allocations_file,
+            stats: None,
+        };
+        pool.restore_static_allocations()?;
+        Ok(pool)
+    }
+
+    #[must_use]
+    pub(crate) fn with_stats(mut self, stats: SharedIpPoolStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    fn ips_at(&self, index: u32) -> IpPair {
+        let ipv4 = u32::from(self.ipv4_subnet.network()) + index;
+        let ipv6 = u128::from(self.ipv6_subnet.network()) + index as u128;
+        IpPair::new(ipv4.into(), ipv6.into())
+    }
+
+    fn index_of(&self, ips: IpPair) -> Option<u32> {
+        if !self.ipv4_subnet.contains(ips.ipv4) || !self.ipv6_subnet.contains(ips.ipv6) {
+            return None;
+        }
+
+        let ipv4_index = u32::from(ips.ipv4) - u32::from(self.ipv4_subnet.network());
+        let ipv6_index = u128::from(ips.ipv6) - u128::from(self.ipv6_subnet.network());
+
+        if ipv4_index as u128 != ipv6_index
+            || ipv4_index < FIRST_CLIENT_INDEX
+            || ipv4_index > self.last_client_index
+        {
+            return None;
+        }
+        Some(ipv4_index)
+    }
+
+    fn owned_allocation(&self, owner: &Recipient) -> Option<(u32, Allocation)> {
+        self.allocations
+            .iter()
+            .find(|(_, allocation)| allocation.owner == *owner)
+            .map(|(index, allocation)| (*index, *allocation))
+    }
+
+    /// Addresses used by the tun device itself.
+    pub(crate) fn tun_ips(&self) -> IpPair {
+        self.ips_at(TUN_DEVICE_INDEX)
+    }
+
+    pub(crate) fn ipv4_netmask(&self) -> Ipv4Addr {
+        self.ipv4_subnet.mask()
+    }
+
+    pub(crate) fn ipv6_prefix(&self) -> u8 {
+        self.ipv6_subnet.prefix()
+    }
+
+    pub(crate) fn capacity(&self) -> u32 {
+        self.last_client_index - FIRST_CLIENT_INDEX + 1
+    }
+
+    /// Assigns addresses to the provided client. If the client still holds a lease,
+    /// it gets its previous addresses back. Otherwise, the lowest available pair is used.
+    pub(crate) fn allocate_dynamic(&mut self, owner: Recipient) -> Option<IpPair> {
+        if let Some((index, _)) = self.owned_allocation(&owner) {
+            if let Some(allocation) = self.allocations.get_mut(&index) {
+                allocation.state = AllocationState::Active;
+            }
+            return Some(self.ips_at(index));
+        }
+
+        let index = (FIRST_CLIENT_INDEX..=self.last_client_index)
+            .find(|index| !self.allocations.contains_key(index))?;
+
+        self.allocations.insert(
+            index,
+            Allocation {
+                owner,
+                kind: AllocationKind::Dynamic,
+                state: AllocationState::Active,
+            },
+        );
+        Some(self.ips_at(index))
+    }
+
+    /// Assigns the explicitly requested addresses to the provided client.
+    pub(crate) fn allocate_static(
+        &mut self,
+        owner: Recipient,
+        ips: IpPair,
+    ) -> Result<(), IpPoolError> {
+        let index = self.index_of(ips).ok_or(IpPoolError::NotInPool { ips })?;
+
+        if let Some(existing) = self.allocations.get(&index) {
+            if existing.owner != owner {
+                return Err(IpPoolError::IpsAlreadyInUse { ips });
+            }
+        }
+
+        if let Some((owned_index, allocation)) = self.owned_allocation(&owner) {
+            if owned_index != index {
+                if allocation.state == AllocationState::Active {
+                    return Err(IpPoolError::ClientAlreadyConnected {
+                        ips: self.ips_at(owned_index),
+                    });
+                }
+                // the client wants different addresses than the ones it used to have
+                self.allocations.remove(&owned_index);
+            }
+        }
+
+        self.allocations.insert(
+            index,
+            Allocation {
+                owner,
+                kind: AllocationKind::Static,
+                state: AllocationState::Active,
+            },
+        );
+        self.persist_static_allocations();
+        Ok(())
+    }
+
+    /// Marks the addresses as no longer used. They remain reserved for their previous owner
+    /// until the lease expires.
+    pub(crate) fn release(&mut self, ips: IpPair) {
+        let Some(index) = self.index_of(ips) else {
+            return;
+        };
+        if let Some(allocation) = self.allocations.get_mut(&index) {
+            allocation.state = AllocationState::Leased {
+                expires_at: Instant::now() + self.lease_duration,
+            };
+        }
+    }
+
+    /// Returns all addresses whose leases have expired back to the pool.
+    pub(crate) fn remove_expired_leases(&mut self) {
+        let now = Instant::now();
+        let mut removed_static = false;
+        self.allocations
+            .retain(|_, allocation| match allocation.state {
+                AllocationState::Leased { expires_at } if expires_at <= now => {
+                    if allocation.kind == AllocationKind::Static {
+                        removed_static = true;
+                    }
+                    false
+                }
+                _ => true,
+            });
+
+        if removed_static {
+            self.persist_static_allocations();
+        }
+    }
+
+    pub(crate) fn utilisation(&self) -> IpPoolUtilisation {
+        let mut active = 0;
+        let mut leased = 0;
+        let mut static_allocations = 0;
+        for allocation in self.allocations.values() {
+            match allocation.state {
+                AllocationState::Active => active += 1,
+                AllocationState::Leased { .. } => leased += 1,
+            }
+            if allocation.kind == AllocationKind::Static {
+                static_allocations += 1;
+            }
+        }
+
+        IpPoolUtilisation {
+            ipv4_subnet: self.ipv4_subnet.to_string(),
+            ipv6_subnet: self.ipv6_subnet.to_string(),
+            capacity: self.capacity() as u64,
+            active,
+            leased,
+            static_allocations,
+        }
+    }
+
+    pub(crate) async fn publish_utilisation(&self) {
+        if let Some(stats) = &self.stats {
+            *stats.write().await = Some(self.utilisation());
+        }
+    }
+
+    // static allocations from the previous run are restored as leases, so that the clients
+    // could reclaim them after reconnecting
+    fn restore_static_allocations(&mut self) -> Result<(), IpPoolError> {
+        let Some(path) = self.allocations_file.clone() else {
+            return Ok(());
+        };
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let expires_at = Instant::now() + self.lease_duration;
+        for persisted in load_allocations(&path)? {
+            let ips = IpPair::new(persisted.ipv4, persisted.ipv6);
+            let owner = match Recipient::try_from_base58_string(&persisted.nym_address) {
+                Ok(owner) => owner,
+                Err(err) => {
+                    log::warn!("ignoring static allocation of {ips} with invalid owner: {err}");
+                    continue;
+                }
+            };
+            let Some(index) = self.index_of(ips) else {
+                log::warn!("ignoring static allocation of {ips} as it's outside the ip pool");
+                continue;
+            };
+            self.allocations.insert(
+                index,
+                Allocation {
+                    owner,
+                    kind: AllocationKind::Static,
+                    state: AllocationState::Leased { expires_at },
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn persist_static_allocations(&self) {
+        let Some(path) = &self.allocations_file else {
+            return;
+        };
+
+        let allocations = self
+            .allocations
+            .iter()
+            .filter(|(_, allocation)| allocation.kind == AllocationKind::Static)
+            .map(|(index, allocation)| {
+                let ips = self.ips_at(*index);
+                PersistedAllocation {
+                    nym_address: allocation.owner.to_string(),
+                    ipv4: ips.ipv4,
+                    ipv6: ips.ipv6,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(err) = store_allocations(path, &allocations) {
+            log::error!("{err}");
+        }
+    }
+}
+
+fn load_allocations(path: &Path) -> Result<Vec<PersistedAllocation>, IpPoolError> {
+    let content = fs::read(path).map_err(|source| IpPoolError::AllocationsLoadFailure {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_slice(&content).map_err(|source| IpPoolError::MalformedAllocations {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn store_allocations(path: &Path, allocations: &[PersistedAllocation]) -> Result<(), IpPoolError> {
+    let store_err = |source| IpPoolError::AllocationsStoreFailure {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(store_err)?;
+    }
+    let content = serde_json::to_vec_pretty(allocations)
+        .map_err(|source| IpPoolError::AllocationsSerializationFailure { source })?;
+    fs::write(path, content).map_err(store_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::{encryption, identity};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn test_recipient(seed: u64) -> Recipient {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let client_identity = identity::KeyPair::new(&mut rng);
+        let client_encryption = encryption::KeyPair::new(&mut rng);
+        let gateway_identity = identity::KeyPair::new(&mut rng);
+        Recipient::new(
+            *client_identity.public_key(),
+            *client_encryption.public_key(),
+            *gateway_identity.public_key(),
+        )
+    }
+
+    fn test_config(ipv4_subnet: &str, ipv6_subnet: &str) -> config::IpPool {
+        config::IpPool {
+            ipv4_subnet: ipv4_subnet.parse().unwrap(),
+            ipv6_subnet: ipv6_subnet.parse().unwrap(),
+            lease_duration: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn default_pool_matches_previous_addressing() {
+        let pool = IpPool::new(&config::IpPool::default(), None).unwrap();
+        assert_eq!(
+            pool.tun_ips(),
+            IpPair::new(
+                Ipv4Addr::new(10, 0, 0, 1),
+                "2001:db8:a160::1".parse().unwrap()
+            )
+        );
+        assert_eq!(pool.ipv4_netmask(), Ipv4Addr::new(255, 255, 0, 0));
+        assert_eq!(pool.ipv6_prefix(), 112);
+        assert_eq!(pool.capacity(), 65533);
+    }
+
+    #[test]
+    fn dynamic_allocation_is_deterministic() {
+        let mut pool = IpPool::new(&config::IpPool::default(), None).unwrap();
+
+        let first = pool.allocate_dynamic(test_recipient(1)).unwrap();
+        let second = pool.allocate_dynamic(test_recipient(2)).unwrap();
+        assert_eq!(
+            first,
+            IpPair::new(
+                Ipv4Addr::new(10, 0, 0, 2),
+                "2001:db8:a160::2".parse().unwrap()
+            )
+        );
+        assert_eq!(
+            second,
+            IpPair::new(
+                Ipv4Addr::new(10, 0, 0, 3),
+                "2001:db8:a160::3".parse().unwrap()
+            )
+        );
+
+        // the same client always gets its addresses back
+        assert_eq!(pool.allocate_dynamic(test_recipient(1)).unwrap(), first);
+    }
+
+    #[test]
+    fn leases_keep_addresses_reserved() {
+        let mut pool = IpPool::new(&test_config("10.1.0.0/29", "fd00::/125"), None).unwrap();
+        assert_eq!(pool.capacity(), 5);
+
+        let ips = pool.allocate_dynamic(test_recipient(1)).unwrap();
+        pool.release(ips);
+        pool.remove_expired_leases();
+
+        // another client can't take the leased addresses
+        assert_ne!(pool.allocate_dynamic(test_recipient(2)).unwrap(), ips);
+        assert!(matches!(
+            pool.allocate_static(test_recipient(3), ips),
+            Err(IpPoolError::IpsAlreadyInUse { .. })
+        ));
+
+        // but the original owner can
+        assert_eq!(pool.allocate_dynamic(test_recipient(1)).unwrap(), ips);
+
+        let utilisation = pool.utilisation();
+        assert_eq!(utilisation.active, 2);
+        assert_eq!(utilisation.leased, 0);
+    }
+
+    #[test]
+    fn expired_leases_are_returned_to_the_pool() {
+        let mut config = test_config("10.1.0.0/29", "fd00::/125");
+        config.lease_duration = Duration::ZERO;
+        let mut pool = IpPool::new(&config, None).unwrap();
+
+        let ips = pool.allocate_dynamic(test_recipient(1)).unwrap();
+        pool.release(ips);
+        pool.remove_expired_leases();
+
+        assert_eq!(pool.allocate_dynamic(test_recipient(2)).unwrap(), ips);
+    }
+
+    #[test]
+    fn pool_can_be_exhausted() {
+        let mut pool = IpPool::new(&test_config("10.1.0.0/29", "fd00::/125"), None).unwrap();
+        for seed in 0..5 {
+            assert!(pool.allocate_dynamic(test_recipient(seed)).is_some());
+        }
+        assert!(pool.allocate_dynamic(test_recipient(42)).is_none());
+    }
+
+    #[test]
+    fn static_allocation_validation() {
+        let mut pool = IpPool::new(&config::IpPool::default(), None).unwrap();
+
+        let outside = IpPair::new(
+            Ipv4Addr::new(192, 168, 0, 2),
+            "2001:db8:a160::2".parse().unwrap(),
+        );
+        let mismatched = IpPair::new(
+            Ipv4Addr::new(10, 0, 0, 2),
+            "2001:db8:a160::3".parse().unwrap(),
+        );
+        let tun = pool.tun_ips();
+        for ips in [outside, mismatched, tun] {
+            assert!(matches!(
+                pool.allocate_static(test_recipient(1), ips),
+                Err(IpPoolError::NotInPool { .. })
+            ));
+        }
+
+        let ips = IpPair::new(
+            Ipv4Addr::new(10, 0, 1, 2),
+            "2001:db8:a160::102".parse().unwrap(),
+        );
+        pool.allocate_static(test_recipient(1), ips).unwrap();
+        assert!(matches!(
+            pool.allocate_static(test_recipient(2), ips),
+            Err(IpPoolError::IpsAlreadyInUse { .. })
+        ));
+    }
+
+    #[test]
+    fn static_allocations_survive_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let allocations_file = dir.path().join("allocations.json");
+        let config = config::IpPool::default();
+
+        let ips = IpPair::new(
+            Ipv4Addr::new(10, 0, 0, 5),
+            "2001:db8:a160::5".parse().unwrap(),
+        );
+        let mut pool = IpPool::new(&config, Some(allocations_file.clone())).unwrap();
+        pool.allocate_static(test_recipient(1), ips).unwrap();
+        pool.allocate_dynamic(test_recipient(2)).unwrap();
+        drop(pool);
+
+        let mut pool = IpPool::new(&config, Some(allocations_file)).unwrap();
+        let utilisation = pool.utilisation();
+        assert_eq!(utilisation.leased, 1);
+        assert_eq!(utilisation.static_allocations, 1);
+
+        assert!(pool.allocate_static(test_recipient(3), ips).is_err());
+        pool.allocate_static(test_recipient(1), ips).unwrap();
+    }
+}
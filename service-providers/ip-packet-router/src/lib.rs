@@ -9,6 +9,7 @@ mod connected_client_handler;
 mod constants;
 pub mod error;
 mod ip_packet_router;
+mod ip_pool;
 mod mixnet_client;
 mod mixnet_listener;
 pub mod request_filter;
@@ -36,10 +36,10 @@ use crate::{
     config::Config,
     connected_client_handler,
     constants::{CLIENT_MIXNET_INACTIVITY_TIMEOUT, DISCONNECT_TIMER_INTERVAL},
-    error::{IpPacketRouterError, Result},
+    error::{IpPacketRouterError, IpPoolError, Result},
+    ip_pool::IpPool,
     request_filter::{self},
     tun_listener,
-    util::generate_new_ip,
     util::{
         create_message::create_input_message,
         parse_ip::{parse_packet, ParsedPacket},
@@ -51,17 +51,21 @@ pub(crate) struct ConnectedClients {
     clients_ipv4_mapping: HashMap<Ipv4Addr, ConnectedClient>,
     clients_ipv6_mapping: HashMap<Ipv6Addr, ConnectedClient>,
 
+    // The pool of addresses that we assign to the clients
+    ip_pool: IpPool,
+
     // Notify the tun listener when a new client connects or disconnects
     tun_listener_connected_client_tx: tokio::sync::mpsc::UnboundedSender<ConnectedClientEvent>,
 }
 
 impl ConnectedClients {
-    pub(crate) fn new() -> (Self, tun_listener::ConnectedClientsListener) {
+    pub(crate) fn new(ip_pool: IpPool) -> (Self, tun_listener::ConnectedClientsListener) {
         let (connected_client_tx, connected_client_rx) = tokio::sync::mpsc::unbounded_channel();
         (
             Self {
                 clients_ipv4_mapping: Default::default(),
                 clients_ipv6_mapping: Default::default(),
+                ip_pool,
                 tun_listener_connected_client_tx: connected_client_tx,
             },
             tun_listener::ConnectedClientsListener::new(connected_client_rx),
@@ -195,6 +199,7 @@ impl ConnectedClients {
             log::info!("Disconnect stopped client: {ips}");
             self.clients_ipv4_mapping.remove(&ips.ipv4);
             self.clients_ipv6_mapping.remove(&ips.ipv6);
+            self.ip_pool.release(*ips);
             self.tun_listener_connected_client_tx
                 .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
                 .tap_err(|err| {
@@ -209,6 +214,7 @@ impl ConnectedClients {
             log::info!("Disconnect inactive client: {ips}");
             self.clients_ipv4_mapping.remove(&ips.ipv4);
             self.clients_ipv6_mapping.remove(&ips.ipv6);
+            self.ip_pool.release(*ips);
             self.tun_listener_connected_client_tx
                 .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
                 .tap_err(|err| {
@@ -218,8 +224,24 @@ impl ConnectedClients {
         }
     }
 
-    fn find_new_ip(&self) -> Option<IpPair> {
-        generate_new_ip::find_new_ips(&self.clients_ipv4_mapping, &self.clients_ipv6_mapping)
+    fn allocate_dynamic_ips(&mut self, nym_address: Recipient) -> Option<IpPair> {
+        self.ip_pool.allocate_dynamic(nym_address)
+    }
+
+    fn allocate_static_ips(
+        &mut self,
+        nym_address: Recipient,
+        ips: IpPair,
+    ) -> std::result::Result<(), IpPoolError> {
+        self.ip_pool.allocate_static(nym_address, ips)
+    }
+
+    fn remove_expired_leases(&mut self) {
+        self.ip_pool.remove_expired_leases()
+    }
+
+    async fn publish_ip_pool_utilisation(&self) {
+        self.ip_pool.publish_utilisation().await
     }
 }
 
@@ -335,6 +357,26 @@ impl MixnetListener {
             (false, false) => {
                 log::info!("Connecting a new client");
 
+                // Reserve the requested addresses in the pool
+                if let Err(err) = self
+                    .connected_clients
+                    .allocate_static_ips(reply_to, requested_ips)
+                {
+                    log::info!("Failed to allocate the requested IPs: {err}");
+                    let reason = match err {
+                        IpPoolError::IpsAlreadyInUse { .. } => {
+                            StaticConnectFailureReason::RequestedIpAlreadyInUse
+                        }
+                        IpPoolError::ClientAlreadyConnected { .. } => {
+                            StaticConnectFailureReason::RequestedNymAddressAlreadyInUse
+                        }
+                        other => StaticConnectFailureReason::Other(other.to_string()),
+                    };
+                    return Ok(Some(IpPacketResponse::new_static_connect_failure(
+                        request_id, reply_to, reason,
+                    )));
+                }
+
                 // Spawn the ConnectedClientHandler for the new client
                 let (forward_from_tun_tx, close_tx, handle) =
                     connected_client_handler::ConnectedClientHandler::start(
@@ -413,7 +455,7 @@ impl MixnetListener {
             )));
         }
 
-        let Some(new_ips) = self.connected_clients.find_new_ip() else {
+        let Some(new_ips) = self.connected_clients.allocate_dynamic_ips(reply_to) else {
             log::info!("No available IP address");
             return Ok(Some(IpPacketResponse::new_dynamic_connect_failure(
                 request_id,
@@ -601,6 +643,9 @@ impl MixnetListener {
             .disconnect_stopped_client_handlers(stopped_clients);
         self.connected_clients
             .disconnect_inactive_clients(inactive_clients);
+
+        self.connected_clients.remove_expired_leases();
+        self.connected_clients.publish_ip_pool_utilisation().await;
     }
 
     // When an incoming mixnet message triggers a response that we send back, such as during
@@ -10,7 +10,6 @@ use reqwest::IntoUrl;
 use url::Url;
 
 pub struct ExitPolicyRequestFilter {
-    #[allow(unused)]
     upstream: Option<Url>,
     policy: ExitPolicy,
 }
@@ -40,7 +39,6 @@ impl ExitPolicyRequestFilter {
         &self.policy
     }
 
-    #[allow(unused)]
     pub fn upstream(&self) -> Option<&Url> {
         self.upstream.as_ref()
     }
@@ -5,13 +5,17 @@ use crate::config::Config;
 use crate::error::IpPacketRouterError;
 use crate::request_filter::exit_policy::ExitPolicyRequestFilter;
 use log::{info, warn};
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
 
 pub mod exit_policy;
 
 enum RequestFilterInner {
     ExitPolicy {
-        policy_filter: ExitPolicyRequestFilter,
+        // the filter gets swapped out as a whole whenever the exit policy is reloaded
+        policy_filter: RwLock<Arc<ExitPolicyRequestFilter>>,
     },
 }
 
@@ -26,10 +30,33 @@ impl RequestFilter {
         Self::new_exit_policy_filter(config).await
     }
 
-    #[allow(unused)]
-    pub fn current_exit_policy_filter(&self) -> Option<&ExitPolicyRequestFilter> {
+    pub fn current_exit_policy_filter(&self) -> Option<Arc<ExitPolicyRequestFilter>> {
         match &*self.inner {
-            RequestFilterInner::ExitPolicy { policy_filter } => Some(policy_filter),
+            RequestFilterInner::ExitPolicy { policy_filter } => Some(load_filter(policy_filter)),
+        }
+    }
+
+    /// Attempts to retrieve the exit policy from its upstream again and replaces the currently used one.
+    pub async fn reload_exit_policy(
+        &self,
+    ) -> Result<Option<Arc<ExitPolicyRequestFilter>>, IpPacketRouterError> {
+        match &*self.inner {
+            RequestFilterInner::ExitPolicy { policy_filter } => {
+                let current = load_filter(policy_filter);
+                let Some(upstream) = current.upstream() else {
+                    return Ok(Some(current));
+                };
+
+                info!("reloading the exit policy from {upstream}");
+                let reloaded =
+                    Arc::new(ExitPolicyRequestFilter::new_upstream(upstream.clone()).await?);
+
+                match policy_filter.write() {
+                    Ok(mut guard) => *guard = Arc::clone(&reloaded),
+                    Err(poisoned) => *poisoned.into_inner() = Arc::clone(&reloaded),
+                }
+                Ok(Some(reloaded))
+            }
         }
     }
 
@@ -49,14 +76,16 @@ impl RequestFilter {
             .ok_or(IpPacketRouterError::NoUpstreamExitPolicy)?;
         let policy_filter = ExitPolicyRequestFilter::new_upstream(upstream_url.clone()).await?;
         Ok(RequestFilter {
-            inner: Arc::new(RequestFilterInner::ExitPolicy { policy_filter }),
+            inner: Arc::new(RequestFilterInner::ExitPolicy {
+                policy_filter: RwLock::new(Arc::new(policy_filter)),
+            }),
         })
     }
 
     pub(crate) async fn check_address(&self, address: &SocketAddr) -> bool {
         match &*self.inner {
             RequestFilterInner::ExitPolicy { policy_filter } => {
-                match policy_filter.check(address).await {
+                match load_filter(policy_filter).check(address).await {
                     Err(err) => {
                         warn!("failed to validate '{address}' against the exit policy: {err}");
                         false
@@ -67,3 +96,13 @@ impl RequestFilter {
         }
     }
 }
+
+fn load_filter(
+    policy_filter: &RwLock<Arc<ExitPolicyRequestFilter>>,
+) -> Arc<ExitPolicyRequestFilter> {
+    // the lock is never held across any operation that could panic
+    match policy_filter.read() {
+        Ok(guard) => Arc::clone(&guard),
+        Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+    }
+}
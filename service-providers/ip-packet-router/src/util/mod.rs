@@ -1,3 +1,2 @@
 pub(crate) mod create_message;
-pub(crate) mod generate_new_ip;
 pub(crate) mod parse_ip;
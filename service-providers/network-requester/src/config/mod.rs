@@ -14,6 +14,7 @@ use nym_network_defaults::mainnet;
 use nym_service_providers_common::DEFAULT_SERVICE_PROVIDERS_DIR;
 use serde::{Deserialize, Serialize};
 use std::io;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
@@ -234,6 +235,11 @@ pub struct NetworkRequester {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Specifies the address announced to clients for listeners created via SOCKS5 BIND requests.
+    /// If not set, the address of the local interface used for reaching the expected peer is used instead.
+    #[serde(deserialize_with = "de_maybe_stringified")]
+    pub bind_announce_ip: Option<IpAddr>,
 }
 
 impl Default for NetworkRequester {
@@ -248,6 +254,7 @@ impl Default for NetworkRequester {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            bind_announce_ip: None,
         }
     }
 }
@@ -116,6 +116,7 @@ impl From<NetworkRequesterV5> for NetworkRequester {
             statistics_recipient: value.statistics_recipient,
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            bind_announce_ip: None,
         }
     }
 }
@@ -94,6 +94,10 @@ disable_poisson_rate = {{ network_requester.disable_poisson_rate }}
 # Specifies the url for an upstream source of the exit policy used by this node.
 upstream_exit_policy_url = '{{ network_requester.upstream_exit_policy_url }}'
 
+# Specifies the address announced to clients for listeners created via SOCKS5 BIND requests.
+# If not set, the address of the local interface used for reaching the expected peer is used instead.
+bind_announce_ip = '{{ network_requester.bind_announce_ip }}'
+
 ##### logging configuration options #####
 
 [logging]
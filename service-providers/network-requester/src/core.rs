@@ -26,9 +26,9 @@ use nym_socks5_proxy_helpers::connection_controller::{
 };
 use nym_socks5_proxy_helpers::proxy_runner::{MixProxyReader, MixProxySender};
 use nym_socks5_requests::{
-    ConnectRequest, ConnectionId, QueryRequest, QueryResponse, SendRequest, SocketData,
-    Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request, Socks5RequestContent,
-    Socks5Response,
+    BindEvent, BindRequest, ConnectRequest, ConnectionId, DatagramRequest, QueryRequest,
+    QueryResponse, SendRequest, SocketData, Socks5ProtocolVersion, Socks5ProviderRequest,
+    Socks5Request, Socks5RequestContent, Socks5Response,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
@@ -38,8 +38,11 @@ use nym_statistics_common::collector::StatisticsSender;
 use nym_task::connections::LaneQueueLengths;
 use nym_task::manager::TaskHandle;
 use nym_task::TaskClient;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::net::TcpStream;
 
 // Since it's an atomic, it's safe to be kept static and shared across threads
 static ACTIVE_PROXIES: AtomicUsize = AtomicUsize::new(0);
@@ -86,6 +89,9 @@ pub struct NRServiceProvider {
     mixnet_client: nym_sdk::mixnet::MixnetClient,
     controller_sender: ControllerSender,
 
+    // senders for all currently active UDP associations
+    udp_associations: HashMap<ConnectionId, socks5::udp::DatagramSender>,
+
     mix_input_sender: MixProxySender<MixnetMessage>,
     stats_collector: Option<ServiceStatisticsCollector>,
     shutdown: TaskHandle,
@@ -172,6 +178,26 @@ impl ServiceProvider<Socks5Request> for NRServiceProvider {
                 }
                 self.handle_proxy_send(req)
             }
+            Socks5RequestContent::Datagram(req) => {
+                if let Some(stats_collector) = &self.stats_collector {
+                    stats_collector
+                        .request_stats_data
+                        .write()
+                        .await
+                        .processed(&req.remote_addr, req.data.len() as u32);
+                }
+                self.handle_datagram(request_version, sender, req)
+            }
+            Socks5RequestContent::Bind(req) => {
+                if let Some(stats_collector) = &self.stats_collector {
+                    stats_collector
+                        .connected_services
+                        .write()
+                        .await
+                        .insert(req.conn_id, req.expected_peer.clone());
+                }
+                self.handle_proxy_bind(request_version, sender, req)
+            }
             Socks5RequestContent::Query(query) => return self.handle_query(query),
         }
 
@@ -333,6 +359,7 @@ impl NRServiceProviderBuilder {
             request_filter: request_filter.clone(),
             mixnet_client,
             controller_sender,
+            udp_associations: HashMap::new(),
             mix_input_sender,
             stats_collector,
             shutdown,
@@ -446,7 +473,7 @@ impl NRServiceProvider {
         lane_queue_lengths: LaneQueueLengths,
         mut shutdown: TaskClient,
     ) {
-        let mut conn = match socks5::tcp::Connection::new(
+        let conn = match socks5::tcp::Connection::new(
             connection_id,
             remote_addr.clone(),
             return_address.clone(),
@@ -475,7 +502,33 @@ impl NRServiceProvider {
             }
         };
 
-        // Connect implies it's a fresh connection - register it with our controller
+        Self::proxy_connection(
+            conn,
+            remote_version,
+            connection_id,
+            remote_addr,
+            biggest_packet_size,
+            controller_sender,
+            mix_input_sender,
+            lane_queue_lengths,
+            shutdown,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn proxy_connection(
+        mut conn: socks5::tcp::Connection,
+        remote_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        remote_addr: String,
+        biggest_packet_size: PacketSize,
+        controller_sender: ControllerSender,
+        mix_input_sender: MixProxySender<MixnetMessage>,
+        lane_queue_lengths: LaneQueueLengths,
+        shutdown: TaskClient,
+    ) {
+        // it's a fresh connection - register it with our controller
         let (mix_sender, mix_receiver) = mpsc::unbounded();
         controller_sender
             .unbounded_send(ControllerCommand::Insert {
@@ -581,12 +634,220 @@ impl NRServiceProvider {
         });
     }
 
+    async fn accept_bound_connection(
+        remote_version: RequestVersion<Socks5Request>,
+        bind_req: BindRequest,
+        return_address: reply::MixnetAddress,
+        announce_ip: Option<IpAddr>,
+        request_filter: RequestFilter,
+        mix_input_sender: &MixProxySender<MixnetMessage>,
+    ) -> Result<(TcpStream, SocketAddr), String> {
+        let conn_id = bind_req.conn_id;
+        let expected_peer = bind_req.expected_peer;
+
+        if !request_filter.check_address(&expected_peer).await {
+            return Err(format!("Domain {expected_peer:?} failed filter check"));
+        }
+
+        let listener = socks5::bind::BindListener::new(&expected_peer, announce_ip, request_filter)
+            .await
+            .map_err(|err| format!("failed to start listening for {expected_peer}: {err}"))?;
+
+        let bound_addr = listener.announced_addr();
+        log::info!("Listening for inbound connection from {expected_peer} on {bound_addr}");
+        let listening = MixnetMessage::new_bind_response(
+            return_address,
+            remote_version,
+            conn_id,
+            BindEvent::Listening {
+                bound_addr: bound_addr.to_string(),
+            },
+        );
+        mix_input_sender
+            .send(listening)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+
+        listener.accept().await.map_err(|err| {
+            format!(
+                "failed to accept inbound connection from {expected_peer} on {bound_addr}: {err}"
+            )
+        })
+    }
+
+    fn handle_proxy_bind(
+        &self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        bind_req: Box<BindRequest>,
+    ) {
+        let Some(return_address) = reply::MixnetAddress::new(bind_req.return_address, sender_tag)
+        else {
+            log::warn!(
+                "attempted to bind inbound connection with no way of returning data back to the sender"
+            );
+            return;
+        };
+
+        let conn_id = bind_req.conn_id;
+        let traffic_config = self.config.base.debug.traffic;
+        let packet_size = traffic_config
+            .secondary_packet_size
+            .unwrap_or(traffic_config.primary_packet_size);
+        let announce_ip = self.config.network_requester.bind_announce_ip;
+
+        let controller_sender_clone = self.controller_sender.clone();
+        let mix_input_sender_clone = self.mix_input_sender.clone();
+        let lane_queue_lengths_clone = self.mixnet_client.shared_lane_queue_lengths();
+        let mut shutdown = self.shutdown.get_handle();
+        let request_filter = self.request_filter.clone();
+
+        // waiting for the inbound connection can take a while, so don't block other requests
+        tokio::spawn(async move {
+            let accepted = tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    log::trace!("received shutdown while waiting for inbound connection of {conn_id}");
+                    return;
+                }
+                accepted = Self::accept_bound_connection(
+                    remote_version.clone(),
+                    *bind_req,
+                    return_address.clone(),
+                    announce_ip,
+                    request_filter,
+                    &mix_input_sender_clone,
+                ) => accepted,
+            };
+
+            let (stream, peer_addr) = match accepted {
+                Ok(accepted) => accepted,
+                Err(message) => {
+                    log::info!("{message}");
+                    let failed = MixnetMessage::new_bind_response(
+                        return_address,
+                        remote_version,
+                        conn_id,
+                        BindEvent::Failed { message },
+                    );
+                    mix_input_sender_clone
+                        .send(failed)
+                        .await
+                        .expect("InputMessageReceiver has stopped receiving!");
+                    shutdown.mark_as_success();
+                    return;
+                }
+            };
+
+            let peer_addr = peer_addr.to_string();
+            let accepted = MixnetMessage::new_bind_response(
+                return_address.clone(),
+                remote_version.clone(),
+                conn_id,
+                BindEvent::Accepted {
+                    peer_addr: peer_addr.clone(),
+                },
+            );
+            mix_input_sender_clone
+                .send(accepted)
+                .await
+                .expect("InputMessageReceiver has stopped receiving!");
+
+            let conn = socks5::tcp::Connection::from_accepted(
+                conn_id,
+                peer_addr.clone(),
+                stream,
+                return_address,
+            );
+            Self::proxy_connection(
+                conn,
+                remote_version,
+                conn_id,
+                peer_addr,
+                packet_size,
+                controller_sender_clone,
+                mix_input_sender_clone,
+                lane_queue_lengths_clone,
+                shutdown,
+            )
+            .await
+        });
+    }
+
     fn handle_proxy_send(&mut self, req: SendRequest) {
         self.controller_sender
             .unbounded_send(ControllerCommand::new_send(req.data))
             .unwrap()
     }
 
+    fn handle_datagram(
+        &mut self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        datagram: Box<DatagramRequest>,
+    ) {
+        let association_id = datagram.association_id;
+
+        // get rid of associations that have already terminated
+        self.udp_associations
+            .retain(|_, datagram_sender| !datagram_sender.is_closed());
+
+        if let Some(datagram_sender) = self.udp_associations.get(&association_id) {
+            if datagram_sender.unbounded_send(*datagram).is_err() {
+                log::debug!(
+                    "UDP association {association_id} has just terminated - dropping the datagram"
+                );
+            }
+            return;
+        }
+
+        let Some(return_address) = reply::MixnetAddress::new(datagram.return_address, sender_tag)
+        else {
+            log::warn!(
+                "attempted to start UDP association with no way of returning data back to the sender"
+            );
+            return;
+        };
+
+        let (datagram_sender, datagram_receiver) = mpsc::unbounded();
+        // this can't fail as we've just created the channel
+        datagram_sender.unbounded_send(*datagram).unwrap();
+        self.udp_associations
+            .insert(association_id, datagram_sender);
+
+        let request_filter = self.request_filter.clone();
+        let mix_input_sender = self.mix_input_sender.clone();
+        let mut shutdown = self.shutdown.get_handle();
+
+        tokio::spawn(async move {
+            let association = match socks5::udp::Association::new(
+                association_id,
+                return_address,
+                remote_version,
+                request_filter,
+                datagram_receiver,
+                mix_input_sender,
+                shutdown.clone(),
+            )
+            .await
+            {
+                Ok(association) => association,
+                Err(err) => {
+                    log::error!(
+                        "failed to create socket for UDP association {association_id}: {err}"
+                    );
+                    shutdown.disarm();
+                    return;
+                }
+            };
+
+            log::info!("Starting UDP association {association_id}");
+            association.run().await;
+            log::info!("UDP association {association_id} is finished");
+            shutdown.mark_as_success();
+        });
+    }
+
     fn handle_query(
         &self,
         query: QueryRequest,
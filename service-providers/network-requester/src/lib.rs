@@ -12,6 +12,7 @@ mod statistics;
 pub use crate::core::{NRServiceProvider, NRServiceProviderBuilder};
 pub use config::Config;
 pub use nym_client_core::{
+    cli_helpers::client_encrypt_keys::{keys_passphrase_from_env, KEYS_PASSPHRASE_ENV_VAR},
     client::{
         base_client::{
             non_wasm_helpers::{setup_fs_gateways_storage, setup_fs_reply_surb_backend},
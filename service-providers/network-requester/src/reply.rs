@@ -6,8 +6,9 @@ use nym_service_providers_common::interface::{
     ControlRequest, ControlResponse, ProviderInterfaceVersion, RequestVersion,
 };
 use nym_socks5_requests::{
-    ConnectionId, SocketData, Socks5ProviderRequest, Socks5ProviderResponse, Socks5Request,
-    Socks5RequestContent, Socks5Response, Socks5ResponseContent,
+    BindEvent, ConnectionId, RemoteAddress, SocketData, Socks5ProviderRequest,
+    Socks5ProviderResponse, Socks5Request, Socks5RequestContent, Socks5Response,
+    Socks5ResponseContent,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
@@ -132,6 +133,38 @@ impl MixnetMessage {
         Self::new_provider_response(address, connection_id, msg)
     }
 
+    pub(crate) fn new_datagram_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        association_id: ConnectionId,
+        source_addr: RemoteAddress,
+        data: Vec<u8>,
+    ) -> Self {
+        let res = Socks5Response::new_datagram(
+            request_version.provider_protocol,
+            association_id,
+            source_addr,
+            data,
+        );
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, association_id, msg)
+    }
+
+    pub(crate) fn new_bind_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        event: BindEvent,
+    ) -> Self {
+        let res = Socks5Response::new_bind(request_version.provider_protocol, connection_id, event);
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, connection_id, msg)
+    }
+
     // TODO: the naming is awful, but naming things is difficult...
     pub(crate) fn new_network_data_response_content(
         address: MixnetAddress,
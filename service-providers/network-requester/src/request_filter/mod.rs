@@ -3,9 +3,9 @@
 
 use crate::config::Config;
 use crate::error::NetworkRequesterError;
-use log::warn;
+use log::{info, warn};
 use nym_socks5_requests::RemoteAddress;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 pub mod exit_policy;
 
@@ -13,24 +13,54 @@ pub use exit_policy::ExitPolicyRequestFilter;
 
 #[derive(Clone)]
 pub struct RequestFilter {
-    inner: Arc<ExitPolicyRequestFilter>,
+    // the filter gets swapped out as a whole whenever the exit policy is reloaded
+    inner: Arc<RwLock<Arc<ExitPolicyRequestFilter>>>,
 }
 
 impl RequestFilter {
     pub(crate) async fn new(config: &Config) -> Result<Self, NetworkRequesterError> {
         Ok(RequestFilter {
-            inner: Arc::new(ExitPolicyRequestFilter::new(config).await?),
+            inner: Arc::new(RwLock::new(Arc::new(
+                ExitPolicyRequestFilter::new(config).await?,
+            ))),
         })
     }
 
-    pub fn current_exit_policy_filter(&self) -> &ExitPolicyRequestFilter {
-        &self.inner
+    pub fn current_exit_policy_filter(&self) -> Arc<ExitPolicyRequestFilter> {
+        // the lock is never held across any operation that could panic
+        match self.inner.read() {
+            Ok(guard) => Arc::clone(&guard),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Attempts to retrieve the exit policy from its upstream again and replaces the currently used one.
+    /// If the network requester is running as an open proxy, there's nothing to reload.
+    pub async fn reload_exit_policy(
+        &self,
+    ) -> Result<Arc<ExitPolicyRequestFilter>, NetworkRequesterError> {
+        let current = self.current_exit_policy_filter();
+        let Some(upstream) = current.upstream() else {
+            return Ok(current);
+        };
+
+        info!("reloading the exit policy from {upstream}");
+        let reloaded = Arc::new(ExitPolicyRequestFilter::new_upstream(upstream.clone()).await?);
+
+        match self.inner.write() {
+            Ok(mut guard) => *guard = Arc::clone(&reloaded),
+            Err(poisoned) => *poisoned.into_inner() = Arc::clone(&reloaded),
+        }
+        Ok(reloaded)
     }
 
     pub(crate) async fn check_address(&self, address: &RemoteAddress) -> bool {
-        self.inner.check(address).await.unwrap_or_else(|err| {
-            warn!("failed to validate '{address}' against the exit policy: {err}");
-            false
-        })
+        self.current_exit_policy_filter()
+            .check(address)
+            .await
+            .unwrap_or_else(|err| {
+                warn!("failed to validate '{address}' against the exit policy: {err}");
+                false
+            })
     }
 }
@@ -0,0 +1,121 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::request_filter::RequestFilter;
+use nym_socks5_requests::RemoteAddress;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::{lookup_host, TcpListener, TcpStream, UdpSocket};
+use tokio::time::{timeout_at, Instant};
+
+// how long we're willing to wait for the expected peer to connect to us
+const ACCEPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A listening socket opened on behalf of a SOCKS5 BIND request. It accepts a single inbound
+/// connection from the peer specified in the request (and allowed by the exit policy).
+pub(crate) struct BindListener {
+    listener: TcpListener,
+    announced_addr: SocketAddr,
+
+    // addresses the inbound connection is allowed to originate from.
+    // if empty, any peer permitted by the exit policy is accepted
+    expected_ips: Vec<IpAddr>,
+
+    // port advertised for the expected peer. the inbound connection is going to originate
+    // from some ephemeral port, so that's the one we're checking against the exit policy instead
+    expected_port: u16,
+    request_filter: RequestFilter,
+}
+
+impl BindListener {
+    pub(crate) async fn new(
+        expected_peer: &RemoteAddress,
+        announce_ip: Option<IpAddr>,
+        request_filter: RequestFilter,
+    ) -> io::Result<Self> {
+        let resolved = lookup_host(expected_peer).await?.collect::<Vec<_>>();
+        let Some(first_resolved) = resolved.first().copied() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{expected_peer} did not resolve to any address"),
+            ));
+        };
+
+        // as per the RFC, the client might not know the address of its peer
+        // in which case it sends an unspecified one
+        let expected_ips = resolved
+            .into_iter()
+            .map(|addr| addr.ip())
+            .filter(|ip| !ip.is_unspecified())
+            .collect::<Vec<_>>();
+
+        let announce_ip = match announce_ip {
+            Some(ip) => ip,
+            None => outbound_interface_ip(first_resolved).await?,
+        };
+
+        // listen on the same address family as the one we're going to announce
+        let listener = TcpListener::bind((unspecified_like(announce_ip), 0)).await?;
+        let announced_addr = SocketAddr::new(announce_ip, listener.local_addr()?.port());
+
+        Ok(BindListener {
+            listener,
+            announced_addr,
+            expected_ips,
+            expected_port: first_resolved.port(),
+            request_filter,
+        })
+    }
+
+    pub(crate) fn announced_addr(&self) -> SocketAddr {
+        self.announced_addr
+    }
+
+    async fn is_allowed(&self, peer: SocketAddr) -> bool {
+        if !self.expected_ips.is_empty() && !self.expected_ips.contains(&peer.ip()) {
+            return false;
+        }
+        let policy_addr = SocketAddr::new(peer.ip(), self.expected_port);
+        self.request_filter
+            .check_address(&policy_addr.to_string())
+            .await
+    }
+
+    /// Waits for the expected peer to connect. Any other inbound connections are dropped.
+    pub(crate) async fn accept(self) -> io::Result<(TcpStream, SocketAddr)> {
+        let deadline = Instant::now() + ACCEPT_TIMEOUT;
+        loop {
+            let (stream, peer) = timeout_at(deadline, self.listener.accept()).await??;
+            if self.is_allowed(peer).await {
+                return Ok((stream, peer));
+            }
+            log::info!(
+                "rejecting inbound connection from {peer} on {}",
+                self.announced_addr
+            );
+        }
+    }
+}
+
+// determine which of our local addresses would be used for reaching the specified peer.
+// note: connecting an udp socket does not send any packets
+async fn outbound_interface_ip(peer: SocketAddr) -> io::Result<IpAddr> {
+    if peer.ip().is_unspecified() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "could not determine the address to announce for an unspecified peer - 'bind_announce_ip' has to be set",
+        ));
+    }
+
+    let socket = UdpSocket::bind(SocketAddr::new(unspecified_like(peer.ip()), 0)).await?;
+    socket.connect(peer).await?;
+    Ok(socket.local_addr()?.ip())
+}
+
+fn unspecified_like(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+        IpAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+    }
+}
@@ -40,6 +40,21 @@ impl Connection {
         })
     }
 
+    /// Wraps an inbound connection accepted on behalf of a SOCKS5 BIND request.
+    pub(crate) fn from_accepted(
+        id: ConnectionId,
+        peer_address: RemoteAddress,
+        conn: TcpStream,
+        return_address: reply::MixnetAddress,
+    ) -> Self {
+        Connection {
+            id,
+            address: peer_address,
+            conn: Some(conn),
+            return_address,
+        }
+    }
+
     pub(crate) async fn run_proxy(
         &mut self,
         remote_version: RequestVersion<Socks5Request>,
@@ -0,0 +1,262 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::reply;
+use crate::reply::MixnetMessage;
+use crate::request_filter::RequestFilter;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use nym_service_providers_common::interface::RequestVersion;
+use nym_socks5_proxy_helpers::proxy_runner::MixProxySender;
+use nym_socks5_requests::{ConnectionId, DatagramRequest, RemoteAddress, Socks5Request};
+use nym_task::TaskClient;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::{lookup_host, UdpSocket};
+use tokio::time::{sleep_until, Instant};
+
+// the client never explicitly tells us the association is over,
+// so we have to get rid of the socket if it's not being used
+const ASSOCIATION_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+// maximum size of a UDP payload
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+pub(crate) type DatagramSender = mpsc::UnboundedSender<DatagramRequest>;
+pub(crate) type DatagramReceiver = mpsc::UnboundedReceiver<DatagramRequest>;
+
+/// An outbound UDP socket of the Socks5 service provider, which sends datagrams on behalf
+/// of a particular SOCKS5 UDP association and returns the responses through the mixnet.
+pub(crate) struct Association {
+    id: ConnectionId,
+    socket_v4: UdpSocket,
+
+    // not every host has IPv6 connectivity
+    socket_v6: Option<UdpSocket>,
+    return_address: reply::MixnetAddress,
+    remote_version: RequestVersion<Socks5Request>,
+    request_filter: RequestFilter,
+
+    // results of the exit policy checks of destinations this association has already used,
+    // so that we wouldn't have to resolve them for every single datagram
+    checked_destinations: HashMap<RemoteAddress, bool>,
+
+    // resolved addresses of the destinations, so that we wouldn't have to query the DNS
+    // for every single datagram either
+    resolved_destinations: HashMap<RemoteAddress, SocketAddr>,
+
+    // addresses this association has sent anything to. inbound datagrams from
+    // any other source are dropped
+    contacted: HashSet<SocketAddr>,
+
+    datagram_receiver: DatagramReceiver,
+    mix_sender: MixProxySender<MixnetMessage>,
+    shutdown: TaskClient,
+}
+
+impl Association {
+    pub(crate) async fn new(
+        id: ConnectionId,
+        return_address: reply::MixnetAddress,
+        remote_version: RequestVersion<Socks5Request>,
+        request_filter: RequestFilter,
+        datagram_receiver: DatagramReceiver,
+        mix_sender: MixProxySender<MixnetMessage>,
+        shutdown: TaskClient,
+    ) -> io::Result<Self> {
+        let socket_v4 = UdpSocket::bind("0.0.0.0:0").await?;
+        let socket_v6 = match UdpSocket::bind("[::]:0").await {
+            Ok(socket) => Some(socket),
+            Err(err) => {
+                log::debug!("failed to bind IPv6 socket for UDP association {id}: {err}");
+                None
+            }
+        };
+
+        Ok(Association {
+            id,
+            socket_v4,
+            socket_v6,
+            return_address,
+            remote_version,
+            request_filter,
+            checked_destinations: HashMap::new(),
+            resolved_destinations: HashMap::new(),
+            contacted: HashSet::new(),
+            datagram_receiver,
+            mix_sender,
+            shutdown,
+        })
+    }
+
+    async fn is_allowed(&mut self, remote_addr: &RemoteAddress) -> bool {
+        if let Some(allowed) = self.checked_destinations.get(remote_addr) {
+            return *allowed;
+        }
+
+        let allowed = self.request_filter.check_address(remote_addr).await;
+        self.checked_destinations
+            .insert(remote_addr.clone(), allowed);
+
+        if !allowed {
+            // only inform the client the first time the destination got rejected
+            let log_msg = format!("Datagram destination {remote_addr:?} failed filter check");
+            log::info!("{log_msg}");
+            let error_msg = MixnetMessage::new_connection_error(
+                self.return_address.clone(),
+                self.remote_version.clone(),
+                self.id,
+                log_msg,
+            );
+            self.mix_sender
+                .send(error_msg)
+                .await
+                .expect("InputMessageReceiver has stopped receiving!");
+        }
+        allowed
+    }
+
+    async fn resolve(&mut self, remote_addr: &RemoteAddress) -> Option<SocketAddr> {
+        if let Some(resolved) = self.resolved_destinations.get(remote_addr) {
+            return Some(*resolved);
+        }
+
+        let candidates = match lookup_host(remote_addr.as_str()).await {
+            Ok(candidates) => candidates.collect::<Vec<_>>(),
+            Err(err) => {
+                log::debug!(
+                    "failed to resolve datagram destination {remote_addr} (association {}): {err}",
+                    self.id
+                );
+                return None;
+            }
+        };
+
+        // prefer IPv4 destinations, but fallback to IPv6 if we can use it
+        let resolved = candidates
+            .iter()
+            .find(|addr| addr.is_ipv4())
+            .or_else(|| {
+                self.socket_v6
+                    .as_ref()
+                    .and_then(|_| candidates.iter().find(|addr| addr.is_ipv6()))
+            })
+            .copied()?;
+
+        self.resolved_destinations
+            .insert(remote_addr.clone(), resolved);
+        Some(resolved)
+    }
+
+    async fn handle_outbound(&mut self, datagram: DatagramRequest) {
+        if !self.is_allowed(&datagram.remote_addr).await {
+            return;
+        }
+
+        let Some(destination) = self.resolve(&datagram.remote_addr).await else {
+            log::debug!(
+                "no usable address for datagram destination {} (association {})",
+                datagram.remote_addr,
+                self.id
+            );
+            return;
+        };
+
+        let socket = match (destination, &self.socket_v6) {
+            (SocketAddr::V6(_), Some(socket_v6)) => socket_v6,
+            _ => &self.socket_v4,
+        };
+
+        self.contacted.insert(destination);
+        if let Err(err) = socket.send_to(&datagram.data, destination).await {
+            log::debug!(
+                "failed to send datagram to {} (association {}): {err}",
+                datagram.remote_addr,
+                self.id
+            )
+        }
+    }
+
+    async fn handle_inbound(&self, source: SocketAddr, data: Vec<u8>) {
+        // don't let arbitrary hosts push data to the client through our socket
+        if !self.contacted.contains(&source) {
+            log::trace!(
+                "dropping datagram from unexpected source {source} (association {})",
+                self.id
+            );
+            return;
+        }
+
+        let mixnet_message = MixnetMessage::new_datagram_response(
+            self.return_address.clone(),
+            self.remote_version.clone(),
+            self.id,
+            source.to_string(),
+            data,
+        );
+        self.mix_sender
+            .send(mixnet_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    pub(crate) async fn run(mut self) {
+        let mut buf_v4 = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut buf_v6 = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut idle_deadline = Instant::now() + ASSOCIATION_IDLE_TIMEOUT;
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    log::trace!("UDP association {}: received shutdown", self.id);
+                    break;
+                }
+                _ = sleep_until(idle_deadline) => {
+                    log::debug!("UDP association {} has been idle for too long", self.id);
+                    break;
+                }
+                datagram = self.datagram_receiver.next() => match datagram {
+                    Some(datagram) => {
+                        idle_deadline = Instant::now() + ASSOCIATION_IDLE_TIMEOUT;
+                        self.handle_outbound(datagram).await
+                    }
+                    None => break,
+                },
+                received = self.socket_v4.recv_from(&mut buf_v4) => match received {
+                    Ok((n, source)) => {
+                        idle_deadline = Instant::now() + ASSOCIATION_IDLE_TIMEOUT;
+                        self.handle_inbound(source, buf_v4[..n].to_vec()).await
+                    }
+                    Err(err) => {
+                        log::warn!("failed to receive datagram on UDP association {}: {err}", self.id);
+                        break;
+                    }
+                },
+                received = recv_from_optional(self.socket_v6.as_ref(), &mut buf_v6) => match received {
+                    Ok((n, source)) => {
+                        idle_deadline = Instant::now() + ASSOCIATION_IDLE_TIMEOUT;
+                        self.handle_inbound(source, buf_v6[..n].to_vec()).await
+                    }
+                    Err(err) => {
+                        log::warn!("failed to receive datagram on UDP association {}: {err}", self.id);
+                        break;
+                    }
+                },
+            }
+        }
+        self.shutdown.mark_as_success();
+    }
+}
+
+async fn recv_from_optional(
+    socket: Option<&UdpSocket>,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr)> {
+    match socket {
+        Some(socket) => socket.recv_from(buf).await,
+        None => std::future::pending().await,
+    }
+}
@@ -9,6 +9,7 @@ use nym_validator_client::nyxd::AccountId;
 
 mod coconut;
 mod completion;
+mod topology;
 mod validator;
 
 #[derive(Debug, Parser)]
@@ -74,6 +75,8 @@ pub(crate) enum Commands {
     VestingSchedule(nym_cli_commands::validator::vesting::VestingSchedule),
     /// Manage your mixnet infrastructure, delegate stake or query the directory
     Mixnet(nym_cli_commands::validator::mixnet::Mixnet),
+    /// Export signed snapshots of the network topology
+    Topology(nym_cli_commands::topology::Topology),
     /// Generates shell completion
     GenerateFig,
 }
@@ -118,6 +121,7 @@ async fn execute(cli: Cli) -> anyhow::Result<()> {
         Commands::Mixnet(mixnet) => {
             validator::mixnet::execute(args, mixnet, &network_details).await?
         }
+        Commands::Topology(topology) => topology::execute(topology, &network_details).await?,
         Commands::GenerateFig => {
             let mut cmd = Cli::command();
             completion::print_fig(&mut cmd);
@@ -0,0 +1,21 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_cli_commands::context::create_query_client_with_nym_api;
+use nym_network_defaults::NymNetworkDetails;
+
+pub(crate) async fn execute(
+    topology: nym_cli_commands::topology::Topology,
+    network_details: &NymNetworkDetails,
+) -> anyhow::Result<()> {
+    match topology.command {
+        nym_cli_commands::topology::TopologyCommands::Export(args) => {
+            nym_cli_commands::topology::export::execute(
+                args,
+                create_query_client_with_nym_api(network_details)?,
+            )
+            .await?
+        }
+    }
+    Ok(())
+}
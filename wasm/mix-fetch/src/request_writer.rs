@@ -70,6 +70,12 @@ impl RequestWriter {
                     Socks5ResponseContent::NetworkData { content } => {
                         self.requests.try_send_data_to_go(content).await;
                     }
+                    Socks5ResponseContent::Datagram(datagram) => {
+                        console_error!("received a datagram response even though we didn't send any datagrams! (association {})", datagram.association_id)
+                    }
+                    Socks5ResponseContent::Bind(bind) => {
+                        console_error!("received a bind response even though we didn't send any bind requests! (request {})", bind.connection_id)
+                    }
                 },
             },
         }